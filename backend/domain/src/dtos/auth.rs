@@ -1,7 +1,63 @@
+use serde::{ Deserialize, Serialize };
 use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::oauth::Scope;
 
 #[derive(Debug)]
 pub struct CredentialsDTO {
     pub user_id: Uuid,
     pub password_hash: String,
 }
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct LoginEmailDTO {
+    #[validate(email)]
+    pub email: String,
+    #[validate(length(min = 8, max = 256))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SessionDTO {
+    pub token: String,
+    pub user_id: Uuid,
+}
+
+/// Outcome of the first login step, accounts with two-factor authentication
+/// enabled get their session from `LoginWithTwoFactor` or `LoginWithPasskey`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LoginResultDTO {
+    Session(SessionDTO),
+    TwoFactorRequired {
+        challenge_token: String,
+    },
+    /// Only from a "sign in with" provider, see `CompleteOidcSignup`.
+    UsernameRequired {
+        signup_token: String,
+        email: String,
+    },
+}
+
+/// Identity attached to an authenticated request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthenticatedUserDTO {
+    pub user_id: Uuid,
+    /// The third-party app acting for the user, `None` for first-party sessions.
+    pub client_id: Option<Uuid>,
+    /// Set for scripts and bots, so rate limits and audit logs can tell them apart.
+    pub personal_access_token_id: Option<Uuid>,
+    /// Sessions get every scope, app tokens what the user consented to.
+    pub scopes: Vec<Scope>,
+}
+
+impl AuthenticatedUserDTO {
+    pub fn is_session(&self) -> bool {
+        self.client_id.is_none() && self.personal_access_token_id.is_none()
+    }
+
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
@@ -0,0 +1,7 @@
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct BlockUserDTO {
+    pub blocker_id: Uuid,
+    pub blocked_id: Uuid,
+}
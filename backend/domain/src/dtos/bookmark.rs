@@ -0,0 +1,70 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::{ pagination::PageRequest, post::PostDTO };
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct CreateBookmarkCollectionDTO {
+    pub collection_id: Uuid,
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct BookmarkCollectionDTO {
+    pub collection_id: Uuid,
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A saved post. Bookmarks without a collection live in the default one.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct BookmarkDTO {
+    pub post_id: Uuid,
+    pub collection_id: Option<Uuid>,
+    /// Order within the collection, smallest first.
+    #[serde(skip)]
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    /// `None` once the post is deleted, the bookmark then shows as a tombstone.
+    pub post: Option<PostDTO>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BookmarkPostDTO {
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[serde(skip)]
+    pub post_id: Uuid,
+    #[serde(default)]
+    pub collection_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoveBookmarkDTO {
+    pub owner_id: Uuid,
+    pub post_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MoveBookmarkDTO {
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[serde(skip)]
+    pub post_id: Uuid,
+    /// Zero based index within the bookmark's collection.
+    pub position: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListBookmarksDTO {
+    pub owner_id: Uuid,
+    pub collection_id: Option<Uuid>,
+    pub page: PageRequest,
+}
@@ -0,0 +1,191 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::pagination::PageRequest;
+
+/// Who may open a conversation with a user.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DmPermission {
+    #[default]
+    Everyone,
+    /// Only the people the user follows.
+    Following,
+    Nobody,
+}
+
+impl DmPermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DmPermission::Everyone => "everyone",
+            DmPermission::Following => "following",
+            DmPermission::Nobody => "nobody",
+        }
+    }
+
+    pub fn parse(permission: &str) -> Option<Self> {
+        match permission {
+            "everyone" => Some(DmPermission::Everyone),
+            "following" => Some(DmPermission::Following),
+            "nobody" => Some(DmPermission::Nobody),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct DmSettingsDTO {
+    pub dm_permission: DmPermission,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateDmSettingsDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub dm_permission: DmPermission,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StartConversationDTO {
+    pub conversation_id: Uuid,
+    #[serde(skip)]
+    pub creator_id: Uuid,
+    /// Everyone but the creator. A single participant makes a one-to-one
+    /// conversation, which is reused if it already exists.
+    pub participant_ids: Vec<Uuid>,
+}
+
+/// How far a participant has read: up to `last_read_message_id`, sent at `last_read_at`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ReadPositionDTO {
+    pub user_id: Uuid,
+    pub last_read_message_id: Uuid,
+    pub last_read_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ConversationDTO {
+    pub conversation_id: Uuid,
+    pub participant_ids: Vec<Uuid>,
+    pub read_positions: Vec<ReadPositionDTO>,
+    pub created_at: DateTime<Utc>,
+    pub last_message_at: Option<DateTime<Utc>>,
+}
+
+impl ConversationDTO {
+    pub fn is_direct(&self) -> bool {
+        self.participant_ids.len() == 2
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MessageDTO {
+    pub message_id: Uuid,
+    pub conversation_id: Uuid,
+    pub sender_id: Uuid,
+    pub content: String,
+    pub media_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct SendMessageDTO {
+    pub message_id: Uuid,
+    #[serde(skip)]
+    pub conversation_id: Uuid,
+    #[serde(skip)]
+    pub sender_id: Uuid,
+    #[serde(default)]
+    #[validate(length(max = 2000))]
+    pub content: String,
+    /// Ids of media previously uploaded by the sender.
+    #[serde(default)]
+    pub media_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListMessagesDTO {
+    pub conversation_id: Uuid,
+    pub viewer_id: Uuid,
+    pub page: PageRequest,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MarkConversationReadDTO {
+    #[serde(skip)]
+    pub conversation_id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub message_id: Uuid,
+}
+
+/// Ciphertext for one recipient device. The server never sees the plaintext.
+#[derive(Debug, Deserialize, Validate, Clone)]
+pub struct OutgoingEnvelopeDTO {
+    pub recipient_id: Uuid,
+    pub recipient_device_id: Uuid,
+    /// Client defined, e.g. to tell session-opening messages apart.
+    pub message_type: i16,
+    #[validate(length(min = 1))]
+    pub ciphertext: String,
+}
+
+#[derive(Debug, Deserialize, Validate, Clone)]
+pub struct SendEncryptedMessageDTO {
+    pub message_id: Uuid,
+    #[serde(skip)]
+    pub conversation_id: Uuid,
+    #[serde(skip)]
+    pub sender_id: Uuid,
+    pub sender_device_id: Uuid,
+    #[validate(nested)]
+    pub envelopes: Vec<OutgoingEnvelopeDTO>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct EncryptedMessageDTO {
+    pub message_id: Uuid,
+    pub conversation_id: Uuid,
+    pub sender_id: Uuid,
+    pub sender_device_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Stored until the recipient device acknowledges it.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct EnvelopeDTO {
+    pub message_id: Uuid,
+    pub conversation_id: Uuid,
+    pub sender_id: Uuid,
+    pub sender_device_id: Uuid,
+    pub recipient_id: Uuid,
+    pub recipient_device_id: Uuid,
+    pub message_type: i16,
+    pub ciphertext: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AcknowledgeEnvelopeDTO {
+    pub user_id: Uuid,
+    pub device_id: Uuid,
+    pub message_id: Uuid,
+}
+
+/// Pushed live to the participants of a conversation.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConversationEventDTO {
+    Message {
+        message: MessageDTO,
+    },
+    Read {
+        conversation_id: Uuid,
+        position: ReadPositionDTO,
+    },
+    Envelope {
+        envelope: EnvelopeDTO,
+    },
+}
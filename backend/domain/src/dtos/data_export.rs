@@ -0,0 +1,94 @@
+use chrono::{ DateTime, NaiveDate, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+
+use crate::dtos::{
+    bookmark::BookmarkDTO,
+    conversation::MessageDTO,
+    media::MediaDTO,
+    post::PostDTO,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportStatus {
+    /// Waiting for the background job to assemble the archive.
+    #[default]
+    Pending,
+    Ready,
+}
+
+impl DataExportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataExportStatus::Pending => "pending",
+            DataExportStatus::Ready => "ready",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "pending" => Some(DataExportStatus::Pending),
+            "ready" => Some(DataExportStatus::Ready),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct DataExportDTO {
+    pub export_id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub status: DataExportStatus,
+    /// Hash of the token of the emailed download link, set once ready.
+    #[serde(skip)]
+    pub token_hash: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query of the emailed link, the token stands in for a session.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DownloadDataExportDTO {
+    #[serde(skip)]
+    pub export_id: Uuid,
+    pub token: String,
+}
+
+/// The account as exported, `UserDTO` minus what only the server cares about.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ExportedProfileDTO {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub birthday: NaiveDate,
+    pub avatar_media_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ExportedFollowsDTO {
+    pub following: Vec<Uuid>,
+    pub followers: Vec<Uuid>,
+}
+
+/// Everything a user has on the server, gathered for an export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonalDataDTO {
+    pub profile: ExportedProfileDTO,
+    pub posts: Vec<PostDTO>,
+    pub follows: ExportedFollowsDTO,
+    pub bookmarks: Vec<BookmarkDTO>,
+    /// Every message of the user's conversations, theirs and the replies.
+    pub messages: Vec<MessageDTO>,
+    /// Media the user uploaded, their original files go in the archive.
+    pub media: Vec<MediaDTO>,
+}
+
+/// One file of an archive, `path` is relative to its root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveFileDTO {
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
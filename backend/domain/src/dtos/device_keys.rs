@@ -0,0 +1,92 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+/// Keys are opaque to the server: base64 encoded public keys and signatures
+/// generated and checked by the clients.
+#[derive(Debug, Serialize, Deserialize, Validate, Clone, PartialEq)]
+pub struct SignedPrekeyDTO {
+    #[validate(length(min = 1, max = 64))]
+    pub key_id: String,
+    #[validate(length(min = 1, max = 128))]
+    pub public_key: String,
+    /// Signature of `public_key` by the device's signing key.
+    #[validate(length(min = 1, max = 256))]
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone, PartialEq)]
+pub struct OneTimePrekeyDTO {
+    #[validate(length(min = 1, max = 64))]
+    pub key_id: String,
+    #[validate(length(min = 1, max = 128))]
+    pub public_key: String,
+}
+
+/// Registers a device or replaces its keys, e.g. to rotate the signed prekey.
+#[derive(Debug, Deserialize, Validate, Clone)]
+pub struct UploadDeviceKeysDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[serde(skip)]
+    pub device_id: Uuid,
+    #[validate(length(min = 1, max = 128))]
+    pub identity_key: String,
+    #[validate(length(min = 1, max = 128))]
+    pub signing_key: String,
+    #[validate(nested)]
+    pub signed_prekey: SignedPrekeyDTO,
+    #[serde(default)]
+    #[validate(nested)]
+    pub one_time_prekeys: Vec<OneTimePrekeyDTO>,
+}
+
+#[derive(Debug, Deserialize, Validate, Clone)]
+pub struct UploadPrekeysDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[serde(skip)]
+    pub device_id: Uuid,
+    #[validate(nested)]
+    pub one_time_prekeys: Vec<OneTimePrekeyDTO>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DeviceKeysDTO {
+    pub user_id: Uuid,
+    pub device_id: Uuid,
+    pub identity_key: String,
+    pub signing_key: String,
+    pub signed_prekey: SignedPrekeyDTO,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeviceRefDTO {
+    pub user_id: Uuid,
+    pub device_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct PrekeyCountDTO {
+    pub one_time_prekeys: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClaimPrekeysDTO {
+    pub claimer_id: Uuid,
+    pub user_id: Uuid,
+}
+
+/// What a sender needs to open a session with one device of the recipient.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PrekeyBundleDTO {
+    pub device_id: Uuid,
+    pub identity_key: String,
+    pub signing_key: String,
+    pub signed_prekey: SignedPrekeyDTO,
+    /// Consumed by the claim. `None` once the device ran out, the session is
+    /// then opened from the signed prekey alone.
+    pub one_time_prekey: Option<OneTimePrekeyDTO>,
+}
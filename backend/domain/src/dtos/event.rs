@@ -0,0 +1,40 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+
+/// Something that happened in the domain, written to the outbox along with
+/// the change itself and then handed to the subscribers.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+    UserRegistered {
+        user_id: Uuid,
+        username: String,
+    },
+    /// The owner proved they receive mail at the address of the account.
+    EmailVerified {
+        user_id: Uuid,
+    },
+}
+
+impl DomainEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DomainEvent::UserRegistered { .. } => "user_registered",
+            DomainEvent::EmailVerified { .. } => "email_verified",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct EventDTO {
+    pub event_id: Uuid,
+    pub event: DomainEvent,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl EventDTO {
+    pub fn new(event: DomainEvent, occurred_at: DateTime<Utc>) -> Self {
+        Self { event_id: Uuid::new_v4(), event, occurred_at }
+    }
+}
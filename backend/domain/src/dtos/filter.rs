@@ -0,0 +1,81 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+/// Where a keyword filter applies.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterContext {
+    Home,
+    Notifications,
+    Threads,
+    Search,
+}
+
+impl FilterContext {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterContext::Home => "home",
+            FilterContext::Notifications => "notifications",
+            FilterContext::Threads => "threads",
+            FilterContext::Search => "search",
+        }
+    }
+
+    pub fn parse(context: &str) -> Option<Self> {
+        match context {
+            "home" => Some(FilterContext::Home),
+            "notifications" => Some(FilterContext::Notifications),
+            "threads" => Some(FilterContext::Threads),
+            "search" => Some(FilterContext::Search),
+            _ => None,
+        }
+    }
+}
+
+fn default_whole_word() -> bool {
+    true
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct CreateKeywordFilterDTO {
+    pub filter_id: Uuid,
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[validate(length(min = 1, max = 100))]
+    pub phrase: String,
+    #[serde(default = "default_whole_word")]
+    pub whole_word: bool,
+    #[validate(length(min = 1))]
+    pub contexts: Vec<FilterContext>,
+    /// Filters without expiry apply until deleted.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Matching is always case-insensitive.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct KeywordFilterDTO {
+    pub filter_id: Uuid,
+    pub owner_id: Uuid,
+    pub phrase: String,
+    pub whole_word: bool,
+    pub contexts: Vec<FilterContext>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteKeywordFilterDTO {
+    pub owner_id: Uuid,
+    pub filter_id: Uuid,
+}
+
+/// Attached to posts and notifications matching one of the viewer's filters,
+/// so clients can collapse them behind a placeholder.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FilterMatchDTO {
+    pub filter_id: Uuid,
+    pub phrase: String,
+}
@@ -0,0 +1,147 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+
+use crate::repositories::object_store::ByteStream;
+
+/// Platforms whose archives can be imported.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    /// The ZIP downloaded from the Twitter / X settings.
+    #[default]
+    Twitter,
+    /// The ZIP of a Mastodon account export, or its bare `outbox.json`.
+    Mastodon,
+}
+
+impl ImportSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportSource::Twitter => "twitter",
+            ImportSource::Mastodon => "mastodon",
+        }
+    }
+
+    pub fn parse(source: &str) -> Option<Self> {
+        match source {
+            "twitter" => Some(ImportSource::Twitter),
+            "mastodon" => Some(ImportSource::Mastodon),
+            _ => None,
+        }
+    }
+}
+
+/// Query of the upload, the archive itself comes as the `file` field.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ImportArchiveDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub source: ImportSource,
+    /// Only reports what would be imported.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// The upload as it is received, stored before the worker imports it.
+pub struct StartImportDTO {
+    pub query: ImportArchiveDTO,
+    pub archive: Box<dyn ByteStream>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GetImportDTO {
+    pub user_id: Uuid,
+    pub import_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    /// Waiting for the background job to read the archive.
+    #[default]
+    Pending,
+    Done,
+    /// The archive can't be imported, `error` tells why.
+    Failed,
+}
+
+impl ImportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportStatus::Pending => "pending",
+            ImportStatus::Done => "done",
+            ImportStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "pending" => Some(ImportStatus::Pending),
+            "done" => Some(ImportStatus::Done),
+            "failed" => Some(ImportStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct ImportDTO {
+    pub import_id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub source: ImportSource,
+    pub dry_run: bool,
+    pub status: ImportStatus,
+    /// Set once done.
+    pub report: Option<ImportReportDTO>,
+    /// Error code of why the archive can't be imported.
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A post as read from an archive, before it becomes a Hearth post.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedPostDTO {
+    /// Id of the post on its original platform.
+    pub external_id: String,
+    /// Plain text, links expanded and markup removed.
+    pub content: String,
+    pub sensitive: bool,
+    /// Files attached to the post, in display order.
+    pub media: Vec<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Remembers which post an archived one became, so imports can be run again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedPostDTO {
+    pub user_id: Uuid,
+    pub source: ImportSource,
+    pub external_id: String,
+    pub post_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SkippedPostDTO {
+    pub external_id: String,
+    /// Error code of why the post can't be imported.
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ImportReportDTO {
+    pub dry_run: bool,
+    pub posts_found: u64,
+    /// Posts imported, or that would be on a dry run.
+    pub posts_imported: u64,
+    /// Posts a previous import already brought in.
+    pub posts_already_imported: u64,
+    pub posts_skipped: Vec<SkippedPostDTO>,
+    /// Attachments imported, or that would be on a dry run.
+    pub media_imported: u64,
+    /// Attachments of imported posts left out, over the limit or of an unsupported type.
+    pub media_skipped: u64,
+    pub oldest_post_at: Option<DateTime<Utc>>,
+    pub newest_post_at: Option<DateTime<Utc>>,
+}
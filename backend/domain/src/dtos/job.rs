@@ -0,0 +1,78 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+
+/// Work done in the background by the worker, stored as JSON so it has to
+/// stay readable by the next release.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobPayload {
+    UnfurlPostLink {
+        post_id: Uuid,
+        content: String,
+    },
+    NotifyMentions {
+        post_id: Uuid,
+    },
+    SendDataExportLink {
+        email: String,
+        link: String,
+    },
+    SendWelcomeEmail {
+        email: String,
+        username: String,
+    },
+    SendVerifyEmail {
+        email: String,
+        code: String,
+    },
+    ProcessMedia {
+        media_id: Uuid,
+    },
+    ImportArchive {
+        import_id: Uuid,
+    },
+    NotifyEndedPolls,
+    PurgeDeletedAccounts,
+    BuildDataExports,
+}
+
+impl JobPayload {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobPayload::UnfurlPostLink { .. } => "unfurl_post_link",
+            JobPayload::NotifyMentions { .. } => "notify_mentions",
+            JobPayload::SendDataExportLink { .. } => "send_data_export_link",
+            JobPayload::SendWelcomeEmail { .. } => "send_welcome_email",
+            JobPayload::SendVerifyEmail { .. } => "send_verify_email",
+            JobPayload::ProcessMedia { .. } => "process_media",
+            JobPayload::ImportArchive { .. } => "import_archive",
+            JobPayload::NotifyEndedPolls => "notify_ended_polls",
+            JobPayload::PurgeDeletedAccounts => "purge_deleted_accounts",
+            JobPayload::BuildDataExports => "build_data_exports",
+        }
+    }
+
+    /// The periodic batches keep the same id, so each of them is queued at
+    /// most once and only the worker holding its lease runs it.
+    pub fn periodic_id(&self) -> Option<Uuid> {
+        match self {
+            JobPayload::NotifyEndedPolls => Some(Uuid::from_u128(1)),
+            JobPayload::PurgeDeletedAccounts => Some(Uuid::from_u128(2)),
+            JobPayload::BuildDataExports => Some(Uuid::from_u128(3)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobDTO {
+    pub job_id: Uuid,
+    pub payload: JobPayload,
+    /// Failed runs so far.
+    pub attempts: u32,
+    /// Not claimed before then, pushed back while a worker holds the job.
+    pub run_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
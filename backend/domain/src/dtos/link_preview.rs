@@ -0,0 +1,29 @@
+use chrono::{ DateTime, Utc };
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Card built from the OpenGraph / Twitter metadata of a linked page.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct LinkPreviewDTO {
+    /// Normalized url, also the cache key.
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
+    #[serde(skip)]
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl LinkPreviewDTO {
+    /// Pages without any title are cached but never shown.
+    pub fn is_displayable(&self) -> bool {
+        self.title.is_some()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnfurlPostLinkDTO {
+    pub post_id: Uuid,
+    pub content: String,
+}
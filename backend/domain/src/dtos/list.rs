@@ -0,0 +1,84 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::pagination::PageRequest;
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct CreateListDTO {
+    pub list_id: Uuid,
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    #[serde(default)]
+    pub is_private: bool,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct UpdateListDTO {
+    #[serde(skip)]
+    pub list_id: Uuid,
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    pub is_private: bool,
+}
+
+/// Private lists, their members and their timeline are only visible to their owner.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ListDTO {
+    pub list_id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub is_private: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ListMemberDTO {
+    pub user_id: Uuid,
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AddListMemberDTO {
+    #[serde(skip)]
+    pub list_id: Uuid,
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[serde(skip)]
+    pub member_id: Uuid,
+    /// Lets the member know they were added. Ignored for private lists.
+    #[serde(default)]
+    pub notify: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoveListMemberDTO {
+    pub list_id: Uuid,
+    pub owner_id: Uuid,
+    pub member_id: Uuid,
+}
+
+/// A user acting on a list they may not own.
+#[derive(Debug, Clone)]
+pub struct ListAccessDTO {
+    pub list_id: Uuid,
+    pub viewer_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserListsDTO {
+    pub owner_id: Uuid,
+    pub viewer_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListTimelineDTO {
+    pub list_id: Uuid,
+    pub viewer_id: Uuid,
+    pub page: PageRequest,
+}
@@ -0,0 +1,121 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+
+/// Variant keeping the uploaded dimensions, present for every media.
+pub const ORIGINAL_VARIANT: &str = "original";
+
+#[derive(Debug, Clone)]
+pub struct UploadMediaDTO {
+    pub media_id: Uuid,
+    pub owner_id: Uuid,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MediaVariantDTO {
+    pub name: String,
+    pub mime_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaStatus {
+    /// Uploaded, waiting for the worker to resize it.
+    Pending,
+    Ready,
+    /// Couldn't be decoded or didn't fit in the owner's quota once processed.
+    Failed,
+}
+
+impl MediaStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaStatus::Pending => "pending",
+            MediaStatus::Ready => "ready",
+            MediaStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "pending" => Some(MediaStatus::Pending),
+            "ready" => Some(MediaStatus::Ready),
+            "failed" => Some(MediaStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MediaDTO {
+    pub media_id: Uuid,
+    pub owner_id: Uuid,
+    /// Mime type of the uploaded file, as sniffed from its content.
+    pub mime_type: String,
+    pub status: MediaStatus,
+    /// Empty until processed.
+    pub blurhash: String,
+    /// `original` first, followed by the resized variants. Empty until processed.
+    pub variants: Vec<MediaVariantDTO>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MediaDTO {
+    /// Bytes counted against the owner's quota.
+    pub fn size_bytes(&self) -> u64 {
+        self.variants
+            .iter()
+            .map(|v| v.size_bytes)
+            .sum()
+    }
+
+    pub fn storage_key(media_id: &Uuid, variant: &str) -> String {
+        format!("media/{}/{}", media_id, variant)
+    }
+
+    /// Where the uploaded file waits to be processed, outside of `media/` so
+    /// it is never served as is.
+    pub fn upload_key(media_id: &Uuid) -> String {
+        format!("uploads/{}", media_id)
+    }
+}
+
+/// One encoded file produced by a `MediaProcessor`.
+#[derive(Debug, Clone)]
+pub struct ProcessedFileDTO {
+    pub variant: String,
+    pub mime_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessedMediaDTO {
+    pub files: Vec<ProcessedFileDTO>,
+    pub blurhash: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MediaFileRequestDTO {
+    pub media_id: Uuid,
+    pub variant: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaFileDTO {
+    pub mime_type: String,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SetAvatarDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    /// `None` removes the current avatar.
+    pub media_id: Option<Uuid>,
+}
@@ -1,3 +1,27 @@
 pub mod auth;
+pub mod block;
+pub mod bookmark;
+pub mod conversation;
+pub mod data_export;
+pub mod device_keys;
+pub mod event;
+pub mod filter;
+pub mod import;
+pub mod job;
+pub mod link_preview;
+pub mod list;
+pub mod media;
+pub mod moderation;
+pub mod notification;
+pub mod oauth;
+pub mod oidc;
+pub mod pagination;
+pub mod passkey;
+pub mod personal_access_token;
+pub mod poll;
+pub mod post;
+pub mod rate_limit;
 pub mod signup;
+pub mod trend;
+pub mod two_factor;
 pub mod user;
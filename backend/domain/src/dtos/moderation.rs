@@ -0,0 +1,238 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::{ pagination::PageRequest, user::Role };
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportTargetType {
+    Post,
+    User,
+    Message,
+}
+
+impl ReportTargetType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportTargetType::Post => "post",
+            ReportTargetType::User => "user",
+            ReportTargetType::Message => "message",
+        }
+    }
+
+    pub fn parse(target_type: &str) -> Option<Self> {
+        match target_type {
+            "post" => Some(ReportTargetType::Post),
+            "user" => Some(ReportTargetType::User),
+            "message" => Some(ReportTargetType::Message),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    Violence,
+    SexualContent,
+    SelfHarm,
+    Misinformation,
+    Other,
+}
+
+impl ReportReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportReason::Spam => "spam",
+            ReportReason::Harassment => "harassment",
+            ReportReason::HateSpeech => "hate_speech",
+            ReportReason::Violence => "violence",
+            ReportReason::SexualContent => "sexual_content",
+            ReportReason::SelfHarm => "self_harm",
+            ReportReason::Misinformation => "misinformation",
+            ReportReason::Other => "other",
+        }
+    }
+
+    pub fn parse(reason: &str) -> Option<Self> {
+        match reason {
+            "spam" => Some(ReportReason::Spam),
+            "harassment" => Some(ReportReason::Harassment),
+            "hate_speech" => Some(ReportReason::HateSpeech),
+            "violence" => Some(ReportReason::Violence),
+            "sexual_content" => Some(ReportReason::SexualContent),
+            "self_harm" => Some(ReportReason::SelfHarm),
+            "misinformation" => Some(ReportReason::Misinformation),
+            "other" => Some(ReportReason::Other),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportState {
+    #[default]
+    Open,
+    Actioned,
+    Dismissed,
+}
+
+impl ReportState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportState::Open => "open",
+            ReportState::Actioned => "actioned",
+            ReportState::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn parse(state: &str) -> Option<Self> {
+        match state {
+            "open" => Some(ReportState::Open),
+            "actioned" => Some(ReportState::Actioned),
+            "dismissed" => Some(ReportState::Dismissed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct CreateReportDTO {
+    pub report_id: Uuid,
+    #[serde(skip)]
+    pub reporter_id: Uuid,
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    pub reason: ReportReason,
+    #[serde(default)]
+    #[validate(length(max = 1000))]
+    pub details: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ReportDTO {
+    pub report_id: Uuid,
+    pub reporter_id: Uuid,
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    /// Author of the reported post or message, or the reported user.
+    pub target_user_id: Uuid,
+    pub reason: ReportReason,
+    pub details: String,
+    pub state: ReportState,
+    pub created_at: DateTime<Utc>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListReportsDTO {
+    pub moderator_id: Uuid,
+    pub state: ReportState,
+    pub page: PageRequest,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    HidePost,
+    SuspendUser,
+    Warn,
+    Dismiss,
+    /// Only recorded in the log, see `SetUserRole`.
+    ChangeRole,
+}
+
+impl ModerationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationAction::HidePost => "hide_post",
+            ModerationAction::SuspendUser => "suspend_user",
+            ModerationAction::Warn => "warn",
+            ModerationAction::Dismiss => "dismiss",
+            ModerationAction::ChangeRole => "change_role",
+        }
+    }
+
+    pub fn parse(action: &str) -> Option<Self> {
+        match action {
+            "hide_post" => Some(ModerationAction::HidePost),
+            "suspend_user" => Some(ModerationAction::SuspendUser),
+            "warn" => Some(ModerationAction::Warn),
+            "dismiss" => Some(ModerationAction::Dismiss),
+            "change_role" => Some(ModerationAction::ChangeRole),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct ResolveReportDTO {
+    #[serde(skip)]
+    pub moderator_id: Uuid,
+    #[serde(skip)]
+    pub personal_access_token_id: Option<Uuid>,
+    #[serde(skip)]
+    pub report_id: Uuid,
+    pub action: ModerationAction,
+    #[serde(default)]
+    #[validate(length(max = 1000))]
+    pub note: String,
+    /// Only used by `suspend_user`, suspending indefinitely when missing.
+    #[serde(default)]
+    pub suspended_until: Option<DateTime<Utc>>,
+}
+
+/// An entry of the audit log, never updated nor deleted.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ModerationLogEntryDTO {
+    pub entry_id: Uuid,
+    pub moderator_id: Uuid,
+    /// Set when the moderator acted through a script or bot.
+    pub personal_access_token_id: Option<Uuid>,
+    pub action: ModerationAction,
+    pub report_id: Option<Uuid>,
+    pub target_type: ReportTargetType,
+    pub target_id: Uuid,
+    pub note: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListModerationLogDTO {
+    pub moderator_id: Uuid,
+    pub page: PageRequest,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SetUserRoleDTO {
+    #[serde(skip)]
+    pub admin_id: Uuid,
+    #[serde(skip)]
+    pub personal_access_token_id: Option<Uuid>,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub role: Role,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct SuspendUserDTO {
+    #[serde(skip)]
+    pub admin_id: Uuid,
+    #[serde(skip)]
+    pub personal_access_token_id: Option<Uuid>,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    /// Suspends indefinitely when missing.
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[validate(length(max = 1000))]
+    pub note: String,
+}
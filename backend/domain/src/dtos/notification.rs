@@ -0,0 +1,58 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+
+use crate::dtos::{ filter::FilterMatchDTO, pagination::PageRequest };
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    Mention,
+    /// Sent to every voter once a poll closes.
+    PollEnded,
+    /// Sent when added to a public list, if the owner asked for it.
+    ListAdded,
+    /// Sent by a moderator acting on a report. The recipient is also the
+    /// actor so moderators stay anonymous.
+    ModerationWarning,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Mention => "mention",
+            NotificationKind::PollEnded => "poll_ended",
+            NotificationKind::ListAdded => "list_added",
+            NotificationKind::ModerationWarning => "moderation_warning",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "mention" => Some(NotificationKind::Mention),
+            "poll_ended" => Some(NotificationKind::PollEnded),
+            "list_added" => Some(NotificationKind::ListAdded),
+            "moderation_warning" => Some(NotificationKind::ModerationWarning),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct NotificationDTO {
+    pub notification_id: Uuid,
+    pub recipient_id: Uuid,
+    pub actor_id: Uuid,
+    pub kind: NotificationKind,
+    pub post_id: Option<Uuid>,
+    pub list_id: Option<Uuid>,
+    /// Filters of the recipient the notified post matched.
+    pub filtered: Vec<FilterMatchDTO>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListNotificationsDTO {
+    pub user_id: Uuid,
+    pub page: PageRequest,
+}
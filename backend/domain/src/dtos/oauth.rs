@@ -0,0 +1,201 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+/// What an access token lets a third-party app do on behalf of a user.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    Read,
+    Write,
+    /// Direct messages and the device keys they are encrypted with.
+    Dm,
+    /// Moderation and administration, on top of the user's own role.
+    Admin,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 4] = [Scope::Read, Scope::Write, Scope::Dm, Scope::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Write => "write",
+            Scope::Dm => "dm",
+            Scope::Admin => "admin",
+        }
+    }
+
+    pub fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "read" => Some(Scope::Read),
+            "write" => Some(Scope::Write),
+            "dm" => Some(Scope::Dm),
+            "admin" => Some(Scope::Admin),
+            _ => None,
+        }
+    }
+
+    /// Space separated, as in OAuth2 `scope` parameters. `None` if any is unknown.
+    pub fn parse_list(scopes: &str) -> Option<Vec<Self>> {
+        let mut parsed: Vec<Self> = vec![];
+        for scope in scopes.split_whitespace() {
+            let scope = Scope::parse(scope)?;
+            if !parsed.contains(&scope) {
+                parsed.push(scope);
+            }
+        }
+        Some(parsed)
+    }
+
+    pub fn join(scopes: &[Self]) -> String {
+        scopes
+            .iter()
+            .map(|scope| scope.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A registered third-party app, its id is the OAuth2 `client_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthAppDTO {
+    pub client_id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    /// The most any of its tokens may be granted.
+    pub scopes: Vec<Scope>,
+    /// Only confidential apps have a secret, public ones (mobile, desktop) rely on PKCE.
+    pub client_secret_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct RegisterOAuthAppDTO {
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+    #[validate(length(min = 1, max = 10))]
+    pub redirect_uris: Vec<String>,
+    #[validate(length(min = 1))]
+    pub scopes: Vec<Scope>,
+    /// Apps running on a server that can keep a secret, needed for client credentials.
+    #[serde(default)]
+    pub confidential: bool,
+}
+
+/// `client_secret` is only ever shown here.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct RegisteredOAuthAppDTO {
+    pub client_id: Uuid,
+    pub client_secret: Option<String>,
+}
+
+/// Query of the authorization endpoint, PKCE with S256 is required of every app.
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct OAuthAuthorizationRequestDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub response_type: String,
+    pub client_id: Uuid,
+    #[validate(length(min = 1, max = 2048))]
+    pub redirect_uri: String,
+    #[validate(length(min = 1, max = 256))]
+    pub scope: String,
+    #[validate(length(max = 512))]
+    pub state: Option<String>,
+    #[validate(length(min = 43, max = 128))]
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+}
+
+/// What the consent screen shows before the user approves or denies.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct OAuthConsentDTO {
+    pub client_id: Uuid,
+    pub app_name: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<Scope>,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct OAuthConsentDecisionDTO {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub request: OAuthAuthorizationRequestDTO,
+    pub approved: bool,
+}
+
+/// Where the consent screen sends the user back to the app.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct OAuthRedirectDTO {
+    pub redirect_uri: String,
+}
+
+/// Kept until the app exchanges it, keyed by the hash of the code.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OAuthCodeDTO {
+    pub client_id: Uuid,
+    pub user_id: Uuid,
+    pub redirect_uri: String,
+    pub scopes: Vec<Scope>,
+    pub code_challenge: String,
+}
+
+/// Body of the token endpoint, the fields used depend on `grant_type`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct OAuthTokenRequestDTO {
+    pub grant_type: String,
+    pub client_id: Uuid,
+    pub client_secret: Option<String>,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct OAuthAccessTokenDTO {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub scope: String,
+}
+
+/// Stored access token, `token_hash` is the only trace of the token itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthTokenDTO {
+    pub token_hash: String,
+    pub client_id: Uuid,
+    /// The user who consented, or the owner of the app for client credentials.
+    pub user_id: Uuid,
+    pub scopes: Vec<Scope>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Introspection (RFC 7662) and revocation (RFC 7009) requests, both need
+/// the credentials of the app the token was issued to.
+#[derive(Debug, Deserialize, Clone)]
+pub struct OAuthTokenActionDTO {
+    pub token: String,
+    pub client_id: Uuid,
+    pub client_secret: Option<String>,
+}
+
+/// Inactive tokens only ever answer `active: false`.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct OAuthIntrospectionDTO {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+}
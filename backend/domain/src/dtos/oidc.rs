@@ -0,0 +1,104 @@
+use chrono::{ DateTime, NaiveDate, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+/// The part of the provider metadata the authorization code flow needs.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct OidcDiscoveryDTO {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+/// Public key of the provider, only RSA and P-256 keys are used.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct JwkDTO {
+    pub kty: String,
+    #[serde(default)]
+    pub kid: Option<String>,
+    #[serde(default)]
+    pub alg: Option<String>,
+    #[serde(default)]
+    pub n: Option<String>,
+    #[serde(default)]
+    pub e: Option<String>,
+    #[serde(default)]
+    pub crv: Option<String>,
+    #[serde(default)]
+    pub x: Option<String>,
+    #[serde(default)]
+    pub y: Option<String>,
+}
+
+/// Where to send the user, the provider redirects back with `code` and `state`.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct OidcAuthorizationDTO {
+    pub authorization_url: String,
+    pub state: String,
+}
+
+/// Kept until the provider redirects back, keyed by `state`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OidcStateDTO {
+    pub state: String,
+    pub provider: String,
+    pub nonce: String,
+    /// PKCE verifier, only its S256 challenge left the server.
+    pub code_verifier: String,
+    /// Account the identity gets linked to, when a logged-in user started the flow.
+    #[serde(default)]
+    pub link_user_id: Option<Uuid>,
+}
+
+/// A logged-in user connecting a provider to their account.
+#[derive(Debug, Clone)]
+pub struct OidcLinkDTO {
+    pub user_id: Uuid,
+    pub provider: String,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct OidcCallbackDTO {
+    #[serde(skip)]
+    pub provider: String,
+    #[validate(length(min = 1, max = 2048))]
+    pub code: String,
+    #[validate(length(min = 1, max = 128))]
+    pub state: String,
+}
+
+/// Claims of a validated ID token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcClaimsDTO {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+}
+
+/// Identity at a provider, `subject` is only unique within that provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedIdentityDTO {
+    pub user_id: Uuid,
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A provider vouched for someone without an account yet, waiting for a username.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PendingOidcSignupDTO {
+    pub provider: String,
+    pub subject: String,
+    pub email: String,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct CompleteOidcSignupDTO {
+    pub signup_token: String,
+    #[validate(length(min = 3, max = 42))]
+    pub username: String,
+    pub birthday: NaiveDate,
+}
@@ -0,0 +1,149 @@
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error_codes::INVALID_CURSOR_ERROR_CODE;
+
+pub const DEFAULT_PAGE_LIMIT: u64 = 20;
+
+fn default_limit() -> u64 {
+    DEFAULT_PAGE_LIMIT
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct PageRequest {
+    pub cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100))]
+    pub limit: u64,
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self { cursor: None, limit: DEFAULT_PAGE_LIMIT }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self { items: vec![], next_cursor: None }
+    }
+}
+
+impl<T> Page<T> {
+    /// Builds a page out of up to `limit + 1` fetched items, the extra item only
+    /// signalling that another page exists.
+    pub fn from_overfetched(mut items: Vec<T>, limit: u64, cursor_of: impl Fn(&T) -> String) -> Self {
+        let has_more = (items.len() as u64) > limit;
+        items.truncate(limit as usize);
+        let next_cursor = if has_more { items.last().map(cursor_of) } else { None };
+        Self { items, next_cursor }
+    }
+}
+
+impl PageRequest {
+    pub fn timeline_cursor(&self) -> Result<Option<TimelineCursor>, HearthError> {
+        match &self.cursor {
+            None => Ok(None),
+            Some(cursor) =>
+                TimelineCursor::decode(cursor)
+                    .map(Some)
+                    .ok_or_else(|| HearthError::Domain(INVALID_CURSOR_ERROR_CODE.into())),
+        }
+    }
+
+    pub fn position_cursor(&self) -> Result<Option<PositionCursor>, HearthError> {
+        match &self.cursor {
+            None => Ok(None),
+            Some(cursor) =>
+                PositionCursor::decode(cursor)
+                    .map(Some)
+                    .ok_or_else(|| HearthError::Domain(INVALID_CURSOR_ERROR_CODE.into())),
+        }
+    }
+}
+
+/// Keyset cursor for lists ordered by `(created_at DESC, id DESC)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl TimelineCursor {
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.created_at.timestamp_micros(), self.id.simple())
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (micros, id) = cursor.split_once('_')?;
+        let created_at = DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+        let id = Uuid::parse_str(id).ok()?;
+        Some(Self { created_at, id })
+    }
+
+    /// Whether an item sorted at `(created_at, id)` comes after this cursor.
+    pub fn is_before(&self, created_at: DateTime<Utc>, id: Uuid) -> bool {
+        (created_at, id) < (self.created_at, self.id)
+    }
+}
+
+/// Keyset cursor for user ordered lists sorted by `(position ASC, id ASC)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionCursor {
+    pub position: i64,
+    pub id: Uuid,
+}
+
+impl PositionCursor {
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.position, self.id.simple())
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (position, id) = cursor.split_once('_')?;
+        Some(Self { position: position.parse().ok()?, id: Uuid::parse_str(id).ok()? })
+    }
+
+    /// Whether an item sorted at `(position, id)` comes after this cursor.
+    pub fn is_after(&self, position: i64, id: Uuid) -> bool {
+        (position, id) > (self.position, self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{ PositionCursor, TimelineCursor };
+
+    #[test]
+    fn should_roundtrip_timeline_cursor() {
+        let cursor = TimelineCursor { created_at: Utc::now(), id: Uuid::new_v4() };
+        let decoded = TimelineCursor::decode(&cursor.encode()).unwrap();
+
+        assert_eq!(decoded.id, cursor.id);
+        assert_eq!(decoded.created_at.timestamp_micros(), cursor.created_at.timestamp_micros());
+    }
+
+    #[test]
+    fn should_roundtrip_position_cursor() {
+        let cursor = PositionCursor { position: -3, id: Uuid::new_v4() };
+        assert_eq!(PositionCursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn should_reject_malformed_cursor() {
+        assert!(TimelineCursor::decode("nope").is_none());
+        assert!(TimelineCursor::decode("12_notauuid").is_none());
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+/// COSE identifier of ES256, the only algorithm accepted.
+pub const ES256: i64 = -7;
+
+/// Credential registered by an authenticator, bound to `users.id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasskeyDTO {
+    pub passkey_id: Uuid,
+    pub user_id: Uuid,
+    pub credential_id: Vec<u8>,
+    /// Uncompressed P-256 point.
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebAuthnCeremony {
+    Registration,
+    Authentication,
+}
+
+/// Kept until the ceremony it was issued for finishes, keyed by `challenge`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WebAuthnChallengeDTO {
+    /// Base64url, as echoed back in the client data.
+    pub challenge: String,
+    pub ceremony: WebAuthnCeremony,
+    /// Owner of the registration, or the user a second factor is expected from.
+    pub user_id: Option<Uuid>,
+    /// Hash of the login challenge, when the passkey is used as a second factor.
+    pub login_challenge: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct RelyingPartyDTO {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyUserDTO {
+    /// Base64url of the user id bytes, returned as the user handle.
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct CredentialParameterDTO {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub alg: i64,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct CredentialDescriptorDTO {
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Base64url.
+    pub id: String,
+}
+
+/// `PublicKeyCredentialCreationOptions`, camel cased like the WebAuthn API
+/// with binary values in base64url.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyRegistrationOptionsDTO {
+    pub challenge: String,
+    pub rp: RelyingPartyDTO,
+    pub user: PasskeyUserDTO,
+    pub pub_key_cred_params: Vec<CredentialParameterDTO>,
+    /// Milliseconds.
+    pub timeout: u64,
+    pub exclude_credentials: Vec<CredentialDescriptorDTO>,
+    pub user_verification: String,
+}
+
+/// Binary fields are base64url, straight from `AuthenticatorAttestationResponse`.
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct RegisterPasskeyDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+    #[validate(length(min = 1, max = 1366))]
+    pub credential_id: String,
+    #[validate(length(min = 1, max = 4096))]
+    pub client_data_json: String,
+    #[validate(length(min = 1, max = 16384))]
+    pub attestation_object: String,
+}
+
+/// Without a challenge token the login is passwordless and any discoverable
+/// credential is accepted, with one it is the second step of a password login.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PasskeyLoginOptionsRequestDTO {
+    #[serde(default)]
+    pub challenge_token: Option<String>,
+}
+
+/// `PublicKeyCredentialRequestOptions`, see `PasskeyRegistrationOptionsDTO`.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyLoginOptionsDTO {
+    pub challenge: String,
+    pub rp_id: String,
+    pub timeout: u64,
+    pub allow_credentials: Vec<CredentialDescriptorDTO>,
+    pub user_verification: String,
+}
+
+/// Binary fields are base64url, straight from `AuthenticatorAssertionResponse`.
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct LoginPasskeyDTO {
+    #[validate(length(min = 1, max = 1366))]
+    pub credential_id: String,
+    #[validate(length(min = 1, max = 4096))]
+    pub client_data_json: String,
+    #[validate(length(min = 1, max = 4096))]
+    pub authenticator_data: String,
+    #[validate(length(min = 1, max = 512))]
+    pub signature: String,
+    /// Same token as the one the options were asked with.
+    #[serde(default)]
+    pub challenge_token: Option<String>,
+}
@@ -0,0 +1,48 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::oauth::Scope;
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct CreatePersonalAccessTokenDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub scopes: Vec<Scope>,
+    /// Defaults to `PersonalAccessTokenPolicy::default_ttl_days`, tokens never outlive a year.
+    #[serde(default)]
+    #[validate(range(min = 1, max = 365))]
+    pub expires_in_days: Option<u32>,
+}
+
+/// `token` is only ever shown here.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct CreatedPersonalAccessTokenDTO {
+    pub token_id: Uuid,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PersonalAccessTokenDTO {
+    pub token_id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub name: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RevokePersonalAccessTokenDTO {
+    pub user_id: Uuid,
+    pub token_id: Uuid,
+}
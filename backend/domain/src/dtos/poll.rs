@@ -0,0 +1,63 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CreatePollDTO {
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub multiple_choice: bool,
+    /// How long the poll stays open, counted from the post creation.
+    pub duration_seconds: i64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PollOptionDTO {
+    pub title: String,
+    /// `None` while results are hidden from the viewer.
+    pub votes_count: Option<u64>,
+}
+
+/// A poll is identified by the post carrying it.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PollDTO {
+    pub post_id: Uuid,
+    pub options: Vec<PollOptionDTO>,
+    pub multiple_choice: bool,
+    pub closes_at: DateTime<Utc>,
+    /// `None` while results are hidden from the viewer.
+    pub voters_count: Option<u64>,
+    /// Positions of the options picked by the viewer, empty if they did not vote.
+    pub own_votes: Vec<u8>,
+}
+
+impl PollDTO {
+    pub fn is_closed(&self, at: DateTime<Utc>) -> bool {
+        at >= self.closes_at
+    }
+
+    /// Strips the tallies, used until the viewer voted or the poll closed.
+    pub fn without_results(mut self) -> Self {
+        self.voters_count = None;
+        for option in &mut self.options {
+            option.votes_count = None;
+        }
+        self
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VotePollDTO {
+    #[serde(skip)]
+    pub post_id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    /// Positions of the picked options.
+    pub choices: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GetPollDTO {
+    pub post_id: Uuid,
+    pub viewer_id: Uuid,
+}
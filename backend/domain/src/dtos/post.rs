@@ -0,0 +1,67 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dtos::{
+    filter::FilterMatchDTO,
+    link_preview::LinkPreviewDTO,
+    media::MediaDTO,
+    pagination::PageRequest,
+    poll::{ CreatePollDTO, PollDTO },
+};
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct CreatePostDTO {
+    pub post_id: Uuid,
+    #[serde(skip)]
+    pub author_id: Uuid,
+    #[validate(length(min = 1, max = 500))]
+    pub content: String,
+    /// Ids of media previously uploaded by the author, in display order.
+    #[serde(default)]
+    pub media_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub poll: Option<CreatePollDTO>,
+    /// Only shown to logged in viewers old enough, see `AgePolicy`.
+    #[serde(default)]
+    pub sensitive: bool,
+}
+
+/// A resolved `@username`, with byte offsets into the post content so clients
+/// can render links without parsing the text again.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MentionDTO {
+    pub user_id: Uuid,
+    pub username: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PostDTO {
+    pub post_id: Uuid,
+    pub author_id: Uuid,
+    pub content: String,
+    pub hashtags: Vec<String>,
+    pub mentions: Vec<MentionDTO>,
+    pub media: Vec<MediaDTO>,
+    pub poll: Option<PollDTO>,
+    /// Filled asynchronously once the first link of the content is unfurled.
+    pub link_preview: Option<LinkPreviewDTO>,
+    /// Filters of the viewer the post matched, empty when it can be shown as is.
+    pub filtered: Vec<FilterMatchDTO>,
+    pub sensitive: bool,
+    /// Set when the post is sensitive and the viewer may not see it, its
+    /// content and attachments are then left out.
+    pub age_gated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HashtagTimelineDTO {
+    pub tag: String,
+    /// Anonymous viewers get the timeline unfiltered.
+    pub viewer_id: Option<Uuid>,
+    pub page: PageRequest,
+}
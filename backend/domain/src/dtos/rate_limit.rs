@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+/// Groups of routes sharing a token bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitScope {
+    Signup,
+    Login,
+    VerificationCode,
+    Posting,
+}
+
+impl RateLimitScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitScope::Signup => "signup",
+            RateLimitScope::Login => "login",
+            RateLimitScope::VerificationCode => "verification_code",
+            RateLimitScope::Posting => "posting",
+        }
+    }
+}
+
+/// Who is calling, the user is only known on authenticated routes.
+#[derive(Debug, Clone)]
+pub struct RateLimitRequestDTO {
+    pub scope: RateLimitScope,
+    pub ip: Option<String>,
+    pub user_id: Option<Uuid>,
+    /// Set when a script or bot calls with one of the user's tokens.
+    pub personal_access_token_id: Option<Uuid>,
+}
+
+/// State of the bucket after taking a token, `retry_after_seconds` is set
+/// when the bucket was empty and the request must be rejected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitDTO {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_seconds: u64,
+    pub retry_after_seconds: Option<u64>,
+}
+
+impl RateLimitDTO {
+    pub fn is_allowed(&self) -> bool {
+        self.retry_after_seconds.is_none()
+    }
+}
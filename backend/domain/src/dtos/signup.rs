@@ -1,6 +1,6 @@
-use chrono::NaiveDate;
+use chrono::{ DateTime, NaiveDate, Utc };
 use email_verification_code::EmailVerificationCode;
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use uuid::Uuid;
 use validator::Validate;
 
@@ -14,6 +14,26 @@ pub struct SignupEmailDTO {
     #[validate(length(min = 8, max = 256))]
     pub password: String,
     pub birthday: NaiveDate,
+    /// Required when the deployment enables proof-of-work challenges.
+    #[serde(default)]
+    #[validate(nested)]
+    pub proof_of_work: Option<ProofOfWorkDTO>,
+}
+
+/// Handed out by `POST /signup/challenge`, see `ProofOfWorkPolicy`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SignupChallengeDTO {
+    pub challenge_id: Uuid,
+    pub prefix: String,
+    pub difficulty: u8,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct ProofOfWorkDTO {
+    pub challenge_id: Uuid,
+    #[validate(length(min = 1, max = 64))]
+    pub solution: String,
 }
 
 #[derive(Debug, Validate, Deserialize, Clone)]
@@ -0,0 +1,7 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct TrendDTO {
+    pub tag: String,
+    pub score: f64,
+}
@@ -0,0 +1,56 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use uuid::Uuid;
+use validator::Validate;
+
+/// Second factor of an account, `enabled_at` stays empty until the first
+/// code confirmed the authenticator app was set up.
+#[derive(Debug, Clone)]
+pub struct TwoFactorDTO {
+    pub user_id: Uuid,
+    /// Encrypted with the `SecretCipher`, never stored in the clear.
+    pub encrypted_secret: Vec<u8>,
+    pub enabled_at: Option<DateTime<Utc>>,
+    /// Time step of the last accepted code, a code is only ever accepted once.
+    pub last_step: Option<i64>,
+}
+
+/// What the authenticator app needs, usually shown as a QR code of the uri.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct TwoFactorEnrollmentDTO {
+    /// Base32, for apps the uri can't be scanned into.
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct ConfirmTwoFactorDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[validate(length(equal = 6))]
+    pub code: String,
+}
+
+/// Shown once, only their hashes are stored.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct RecoveryCodesDTO {
+    pub codes: Vec<String>,
+}
+
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct DisableTwoFactorDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[validate(length(min = 8, max = 256))]
+    pub password: String,
+}
+
+/// Second login step, with either a code of the app or a recovery code.
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct LoginTwoFactorDTO {
+    pub challenge_token: String,
+    #[validate(length(equal = 6))]
+    pub code: Option<String>,
+    #[validate(length(min = 1, max = 32))]
+    pub recovery_code: Option<String>,
+}
@@ -1,14 +1,87 @@
 use chrono::{DateTime, NaiveDate, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+/// What a user may do beyond their own account.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    User,
+    /// Works the moderation queue.
+    Moderator,
+    /// Moderator who also manages roles.
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn parse(role: &str) -> Option<Self> {
+        match role {
+            "user" => Some(Role::User),
+            "moderator" => Some(Role::Moderator),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+
+    pub fn can_moderate(&self) -> bool {
+        matches!(self, Role::Moderator | Role::Admin)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    #[default]
+    Active,
+    Suspended,
+    /// Scheduled for deletion, logging back in during the grace period undoes it.
+    Deactivated,
+}
+
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserStatus::Active => "active",
+            UserStatus::Suspended => "suspended",
+            UserStatus::Deactivated => "deactivated",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "active" => Some(UserStatus::Active),
+            "suspended" => Some(UserStatus::Suspended),
+            "deactivated" => Some(UserStatus::Deactivated),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Validate, Clone)]
 pub struct UserDTO {
     pub user_id: Uuid,
     pub username: String,
     pub email: String,
+    /// Whether the owner proved they receive mail at `email`.
+    pub email_verified: bool,
     pub birthday: NaiveDate,
+    pub avatar_media_id: Option<Uuid>,
+    pub role: Role,
+    pub status: UserStatus,
+    /// End of a temporary suspension, `None` while active or suspended indefinitely.
+    pub suspended_until: Option<DateTime<Utc>>,
+    /// When the owner asked for the account to be deleted, the purge runs after the grace period.
+    pub deactivated_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -19,7 +92,13 @@ impl UserDTO {
             user_id: dto.user_id,
             username: dto.username,
             email: dto.email,
+            email_verified: false,
             birthday: dto.birthday,
+            avatar_media_id: None,
+            role: Role::default(),
+            status: UserStatus::default(),
+            suspended_until: None,
+            deactivated_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -35,3 +114,36 @@ pub struct CreateUserDTO {
     pub email: String,
     pub birthday: NaiveDate,
 }
+
+/// What anyone can see of an account.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct ProfileDTO {
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_media_id: Option<Uuid>,
+    pub suspended: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VerifyEmailDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    /// Sent to the address of the account.
+    pub code: String,
+}
+
+/// The password is asked again, a stolen session alone can't delete an account.
+#[derive(Debug, Validate, Deserialize, Clone)]
+pub struct DeleteAccountDTO {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    #[validate(length(min = 1))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct AccountDeletionDTO {
+    /// Until then, logging in with the password reactivates the account.
+    pub purge_at: DateTime<Utc>,
+}
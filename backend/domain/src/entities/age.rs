@@ -0,0 +1,127 @@
+use chrono::{ Datelike, NaiveDate };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{ post::PostDTO, user::UserDTO },
+    error_codes::{ BELOW_MINIMUM_AGE_ERROR_CODE, INVALID_BIRTHDAY_ERROR_CODE },
+    policies::age::AgePolicy,
+};
+
+pub struct Age {}
+
+impl Age {
+    /// Full years lived on `today`, `None` for a birthday in the future.
+    pub fn years(birthday: NaiveDate, today: NaiveDate) -> Option<u32> {
+        if birthday > today {
+            return None;
+        }
+
+        let had_birthday = (today.month(), today.day()) >= (birthday.month(), birthday.day());
+        let years = today.year() - birthday.year() - if had_birthday { 0 } else { 1 };
+
+        Some(years as u32)
+    }
+
+    pub fn check_signup(
+        birthday: NaiveDate,
+        today: NaiveDate,
+        policy: &AgePolicy
+    ) -> Result<(), HearthError> {
+        let years = Age::years(birthday, today)
+            .filter(|years| *years <= policy.maximum_age)
+            .ok_or_else(|| HearthError::Domain(INVALID_BIRTHDAY_ERROR_CODE.into()))?;
+
+        if years < policy.minimum_age {
+            return Err(HearthError::Domain(BELOW_MINIMUM_AGE_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Anonymous viewers are treated as underage.
+    pub fn can_view_sensitive(
+        viewer: Option<&UserDTO>,
+        today: NaiveDate,
+        policy: &AgePolicy
+    ) -> bool {
+        viewer
+            .and_then(|viewer| Age::years(viewer.birthday, today))
+            .is_some_and(|years| years >= policy.sensitive_content_age)
+    }
+
+    /// Whether `post` is hidden from `viewer_id` unless they are old enough.
+    /// Authors always see their own posts.
+    pub fn is_gated(post: &PostDTO, viewer_id: Option<&Uuid>) -> bool {
+        post.sensitive && viewer_id.is_none_or(|viewer_id| *viewer_id != post.author_id)
+    }
+
+    /// Withholds everything but the metadata of a gated post, so timelines
+    /// keep their pagination and clients can render a placeholder.
+    pub fn gate_post(
+        post: PostDTO,
+        viewer_id: Option<&Uuid>,
+        can_view_sensitive: bool
+    ) -> PostDTO {
+        if can_view_sensitive || !Age::is_gated(&post, viewer_id) {
+            return post;
+        }
+
+        PostDTO {
+            content: String::new(),
+            hashtags: vec![],
+            mentions: vec![],
+            media: vec![],
+            poll: None,
+            link_preview: None,
+            filtered: vec![],
+            age_gated: true,
+            ..post
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use errors::HearthError;
+
+    use crate::{
+        entities::age::Age,
+        error_codes::{ BELOW_MINIMUM_AGE_ERROR_CODE, INVALID_BIRTHDAY_ERROR_CODE },
+        policies::age::AgePolicy,
+    };
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn should_count_full_years_only() {
+        let today = date(2026, 10, 19);
+
+        assert_eq!(Age::years(date(2013, 10, 19), today), Some(13));
+        assert_eq!(Age::years(date(2013, 10, 20), today), Some(12));
+        assert_eq!(Age::years(date(2026, 10, 20), today), None);
+    }
+
+    #[test]
+    fn should_reject_underage_future_and_implausible_birthdays() {
+        let today = date(2026, 10, 19);
+        let policy = AgePolicy::default();
+
+        assert!(Age::check_signup(date(1991, 12, 29), today, &policy).is_ok());
+        assert_eq!(
+            Age::check_signup(date(2015, 9, 5), today, &policy).unwrap_err(),
+            HearthError::Domain(BELOW_MINIMUM_AGE_ERROR_CODE.into())
+        );
+        assert_eq!(
+            Age::check_signup(date(2030, 1, 1), today, &policy).unwrap_err(),
+            HearthError::Domain(INVALID_BIRTHDAY_ERROR_CODE.into())
+        );
+        assert_eq!(
+            Age::check_signup(date(1850, 1, 1), today, &policy).unwrap_err(),
+            HearthError::Domain(INVALID_BIRTHDAY_ERROR_CODE.into())
+        );
+    }
+}
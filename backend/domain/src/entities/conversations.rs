@@ -0,0 +1,107 @@
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{
+    dtos::conversation::{ ConversationDTO, DmPermission, MessageDTO },
+    error_codes::CONVERSATION_NOT_FOUND_ERROR_CODE,
+};
+
+pub struct Conversations {}
+
+impl Conversations {
+    /// A conversation doesn't exist for anyone outside of it.
+    pub fn check_participant(conversation: &ConversationDTO, user_id: &Uuid) -> Result<(), HearthError> {
+        if !conversation.participant_ids.contains(user_id) {
+            return Err(HearthError::not_found(CONVERSATION_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Whether a recipient with `permission` accepts a new conversation from
+    /// someone they (don't) follow.
+    pub fn accepts(permission: DmPermission, follows_sender: bool) -> bool {
+        match permission {
+            DmPermission::Everyone => true,
+            DmPermission::Following => follows_sender,
+            DmPermission::Nobody => false,
+        }
+    }
+
+    /// Read positions only move forward, acknowledging an older message is a no-op.
+    pub fn advances_read_position(
+        conversation: &ConversationDTO,
+        user_id: &Uuid,
+        message: &MessageDTO
+    ) -> bool {
+        conversation.read_positions
+            .iter()
+            .find(|position| position.user_id == *user_id)
+            .is_none_or(|position| {
+                (position.last_read_at, position.last_read_message_id) <
+                    (message.created_at, message.message_id)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::conversation::{ ConversationDTO, DmPermission, MessageDTO, ReadPositionDTO },
+        entities::conversations::Conversations,
+    };
+
+    #[test]
+    fn should_apply_dm_permissions() {
+        assert!(Conversations::accepts(DmPermission::Everyone, false));
+        assert!(Conversations::accepts(DmPermission::Following, true));
+        assert!(!Conversations::accepts(DmPermission::Following, false));
+        assert!(!Conversations::accepts(DmPermission::Nobody, true));
+    }
+
+    #[test]
+    fn should_only_move_read_positions_forward() {
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        let message = |created_at| MessageDTO {
+            message_id: Uuid::new_v4(),
+            conversation_id: Uuid::new_v4(),
+            sender_id: Uuid::new_v4(),
+            content: "hi".into(),
+            media_ids: vec![],
+            created_at,
+        };
+        let mut conversation = ConversationDTO {
+            conversation_id: Uuid::new_v4(),
+            participant_ids: vec![user_id, Uuid::new_v4()],
+            read_positions: vec![],
+            created_at: now,
+            last_message_at: None,
+        };
+
+        assert!(Conversations::advances_read_position(&conversation, &user_id, &message(now)));
+
+        conversation.read_positions.push(ReadPositionDTO {
+            user_id,
+            last_read_message_id: Uuid::new_v4(),
+            last_read_at: now,
+        });
+        assert!(
+            !Conversations::advances_read_position(
+                &conversation,
+                &user_id,
+                &message(now - Duration::seconds(1))
+            )
+        );
+        assert!(
+            Conversations::advances_read_position(
+                &conversation,
+                &user_id,
+                &message(now + Duration::seconds(1))
+            )
+        );
+    }
+}
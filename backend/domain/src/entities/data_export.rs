@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+pub struct DataExport {}
+
+impl DataExport {
+    /// Key of the archive in the export store.
+    pub fn archive_key(export_id: &Uuid) -> String {
+        format!("exports/{}.zip", export_id)
+    }
+
+    pub fn download_url(base_url: &str, export_id: &Uuid, token: &str) -> String {
+        format!("{}/exports/{}?token={}", base_url.trim_end_matches('/'), export_id, token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::entities::data_export::DataExport;
+
+    #[test]
+    fn should_build_download_urls() {
+        let export_id = Uuid::new_v4();
+
+        assert_eq!(
+            DataExport::download_url("https://hearth.example/", &export_id, "abc"),
+            format!("https://hearth.example/exports/{}?token=abc", export_id)
+        );
+    }
+}
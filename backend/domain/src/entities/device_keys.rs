@@ -0,0 +1,28 @@
+use errors::HearthError;
+
+use crate::{ dtos::device_keys::OneTimePrekeyDTO, error_codes::TOO_MANY_PREKEYS_ERROR_CODE };
+
+pub struct DeviceKeys {}
+
+impl DeviceKeys {
+    /// Drops prekeys repeating an id of the batch and refuses batches that
+    /// would leave the device with more than `max` unclaimed prekeys.
+    pub fn check_prekeys(
+        prekeys: Vec<OneTimePrekeyDTO>,
+        unclaimed: u64,
+        max: u64
+    ) -> Result<Vec<OneTimePrekeyDTO>, HearthError> {
+        let mut unique: Vec<OneTimePrekeyDTO> = vec![];
+        for prekey in prekeys {
+            if !unique.iter().any(|p| p.key_id == prekey.key_id) {
+                unique.push(prekey);
+            }
+        }
+
+        if unclaimed + (unique.len() as u64) > max {
+            return Err(HearthError::Domain(TOO_MANY_PREKEYS_ERROR_CODE.into()));
+        }
+
+        Ok(unique)
+    }
+}
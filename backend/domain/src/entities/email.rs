@@ -0,0 +1,78 @@
+use errors::HearthError;
+
+use crate::{
+    error_codes::EMAIL_DOMAIN_NOT_ALLOWED_ERROR_CODE,
+    policies::signup::{ EmailPolicy, PlusAddressing },
+};
+
+pub struct Email {}
+
+impl Email {
+    /// The form an address is stored and looked up in, so differently typed
+    /// variants of an address map to a single account.
+    pub fn normalize(email: &str, plus_addressing: PlusAddressing) -> String {
+        let email = email.trim().to_lowercase();
+        let Some((local, domain)) = email.rsplit_once('@') else {
+            return email;
+        };
+
+        let local = match plus_addressing {
+            PlusAddressing::Keep => local,
+            PlusAddressing::Strip => local.split('+').next().unwrap_or(local),
+        };
+
+        format!("{}@{}", local, domain)
+    }
+
+    pub fn domain(email: &str) -> &str {
+        email.rsplit_once('@').map_or("", |(_, domain)| domain)
+    }
+
+    /// Expects a normalized address.
+    pub fn check_domain(email: &str, policy: &EmailPolicy) -> Result<(), HearthError> {
+        let domain = Email::domain(email);
+        let covers = |listed: &String| {
+            domain == listed || domain.ends_with(&format!(".{}", listed))
+        };
+
+        let allowed = policy.allowed_domains.is_empty() || policy.allowed_domains.iter().any(covers);
+        if !allowed || policy.blocked_domains.iter().any(covers) {
+            return Err(HearthError::Domain(EMAIL_DOMAIN_NOT_ALLOWED_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::email::Email,
+        policies::signup::{ EmailPolicy, PlusAddressing },
+    };
+
+    #[test]
+    fn should_normalize_case_and_plus_addressing() {
+        assert_eq!(
+            Email::normalize(" John.Smith+News@GMail.com ", PlusAddressing::Strip),
+            "john.smith@gmail.com"
+        );
+        assert_eq!(
+            Email::normalize("John.Smith+News@GMail.com", PlusAddressing::Keep),
+            "john.smith+news@gmail.com"
+        );
+    }
+
+    #[test]
+    fn should_check_blocked_and_allowed_domains() {
+        let policy = EmailPolicy::default();
+        assert!(Email::check_domain("john@gmail.com", &policy).is_ok());
+        assert!(Email::check_domain("john@mailinator.com", &policy).is_err());
+        assert!(Email::check_domain("john@eu.mailinator.com", &policy).is_err());
+        assert!(Email::check_domain("john@notmailinator.com", &policy).is_ok());
+
+        let policy = EmailPolicy { allowed_domains: vec!["hearth.dev".into()], ..policy };
+        assert!(Email::check_domain("john@hearth.dev", &policy).is_ok());
+        assert!(Email::check_domain("john@gmail.com", &policy).is_err());
+    }
+}
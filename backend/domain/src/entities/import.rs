@@ -0,0 +1,10 @@
+use uuid::Uuid;
+
+pub struct Import {}
+
+impl Import {
+    /// Key of the uploaded archive in the object store, until the worker imported it.
+    pub fn archive_key(import_id: &Uuid) -> String {
+        format!("imports/{}", import_id)
+    }
+}
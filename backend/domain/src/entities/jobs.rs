@@ -0,0 +1,47 @@
+use chrono::{ DateTime, Duration, Utc };
+use uuid::Uuid;
+
+use crate::{ dtos::job::{ JobDTO, JobPayload }, policies::job::JobPolicy };
+
+pub struct Jobs {}
+
+impl Jobs {
+    /// A job first run at `run_at`, now or later.
+    pub fn schedule(payload: JobPayload, run_at: DateTime<Utc>) -> JobDTO {
+        JobDTO {
+            job_id: payload.periodic_id().unwrap_or_else(Uuid::new_v4),
+            payload,
+            attempts: 0,
+            run_at,
+            last_error: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Delay before the next run of a job that failed `attempts` times.
+    pub fn backoff(attempts: u32, policy: &JobPolicy) -> Duration {
+        let factor = 2_i32.saturating_pow(attempts.saturating_sub(1));
+
+        policy.base_backoff
+            .checked_mul(factor)
+            .map_or(policy.max_backoff, |backoff| backoff.min(policy.max_backoff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::{ entities::jobs::Jobs, policies::job::JobPolicy };
+
+    #[test]
+    fn should_double_the_backoff_up_to_the_cap() {
+        let policy = JobPolicy::default();
+
+        assert_eq!(Jobs::backoff(1, &policy), Duration::seconds(30));
+        assert_eq!(Jobs::backoff(2, &policy), Duration::seconds(60));
+        assert_eq!(Jobs::backoff(4, &policy), Duration::seconds(240));
+        assert_eq!(Jobs::backoff(10, &policy), Duration::hours(1));
+        assert_eq!(Jobs::backoff(40, &policy), Duration::hours(1));
+    }
+}
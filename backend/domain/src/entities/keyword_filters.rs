@@ -0,0 +1,111 @@
+use chrono::{ DateTime, Utc };
+use uuid::Uuid;
+
+use crate::dtos::{ filter::{ FilterContext, FilterMatchDTO, KeywordFilterDTO }, post::PostDTO };
+
+pub struct KeywordFilters {}
+
+impl KeywordFilters {
+    /// Filters of `context` that have not expired yet.
+    pub fn active(
+        filters: Vec<KeywordFilterDTO>,
+        context: FilterContext,
+        now: DateTime<Utc>
+    ) -> Vec<KeywordFilterDTO> {
+        filters
+            .into_iter()
+            .filter(|filter| filter.contexts.contains(&context))
+            .filter(|filter| filter.expires_at.is_none_or(|expires_at| expires_at > now))
+            .collect()
+    }
+
+    /// Case-insensitive, whole words only unless the filter says otherwise.
+    pub fn matches(filters: &[KeywordFilterDTO], content: &str) -> Vec<FilterMatchDTO> {
+        if filters.is_empty() {
+            return vec![];
+        }
+
+        let content = content.to_lowercase();
+        filters
+            .iter()
+            .filter(|filter| contains_phrase(&content, &filter.phrase.to_lowercase(), filter.whole_word))
+            .map(|filter| FilterMatchDTO { filter_id: filter.filter_id, phrase: filter.phrase.clone() })
+            .collect()
+    }
+
+    /// Flags the post with the filters it matched. The viewer's own posts are never filtered.
+    pub fn mark_post(post: PostDTO, filters: &[KeywordFilterDTO], viewer_id: &Uuid) -> PostDTO {
+        if post.author_id == *viewer_id {
+            return post;
+        }
+
+        PostDTO { filtered: KeywordFilters::matches(filters, &post.content), ..post }
+    }
+}
+
+fn contains_phrase(content: &str, phrase: &str, whole_word: bool) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    content.match_indices(phrase).any(|(start, _)| {
+        if !whole_word {
+            return true;
+        }
+
+        let before = content[..start].chars().next_back();
+        let after = content[start + phrase.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::filter::{ FilterContext, KeywordFilterDTO },
+        entities::keyword_filters::KeywordFilters,
+    };
+
+    fn filter(phrase: &str, whole_word: bool) -> KeywordFilterDTO {
+        KeywordFilterDTO {
+            filter_id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            phrase: phrase.into(),
+            whole_word,
+            contexts: vec![FilterContext::Home],
+            expires_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn should_match_whole_words_case_insensitively() {
+        let filters = vec![filter("Spoiler", true), filter("season finale", true), filter("crypt", false)];
+
+        let matched = |content: &str| -> Vec<String> {
+            KeywordFilters::matches(&filters, content)
+                .into_iter()
+                .map(|m| m.phrase)
+                .collect()
+        };
+
+        assert_eq!(matched("SPOILER: he dies"), vec!["Spoiler"]);
+        assert!(matched("no spoilers here").is_empty());
+        assert_eq!(matched("that Season  finale... no, season finale!"), vec!["season finale"]);
+        assert_eq!(matched("Cryptography is fun"), vec!["crypt"]);
+    }
+
+    #[test]
+    fn should_skip_expired_and_out_of_context_filters() {
+        let now = Utc::now();
+        let mut expired = filter("a", true);
+        expired.expires_at = Some(now - Duration::minutes(1));
+        let mut search_only = filter("b", true);
+        search_only.contexts = vec![FilterContext::Search];
+        let active = filter("c", true);
+
+        let filters = KeywordFilters::active(vec![expired, search_only, active.clone()], FilterContext::Home, now);
+        assert_eq!(filters, vec![active]);
+    }
+}
@@ -0,0 +1,69 @@
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{
+    dtos::list::ListDTO,
+    error_codes::{ LIST_NOT_FOUND_ERROR_CODE, NOT_LIST_OWNER_ERROR_CODE },
+};
+
+pub struct Lists {}
+
+impl Lists {
+    /// A private list doesn't exist for anyone but its owner.
+    pub fn check_visible(list: &ListDTO, viewer_id: &Uuid) -> Result<(), HearthError> {
+        if list.is_private && list.owner_id != *viewer_id {
+            return Err(HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Only the owner edits a list. Private lists stay hidden to the others.
+    pub fn check_owner(list: &ListDTO, viewer_id: &Uuid) -> Result<(), HearthError> {
+        Self::check_visible(list, viewer_id)?;
+
+        if list.owner_id != *viewer_id {
+            return Err(HearthError::Forbidden(NOT_LIST_OWNER_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::ListDTO,
+        entities::lists::Lists,
+        error_codes::{ LIST_NOT_FOUND_ERROR_CODE, NOT_LIST_OWNER_ERROR_CODE },
+    };
+
+    #[test]
+    fn should_hide_private_lists_from_everyone_but_their_owner() {
+        let owner_id = Uuid::new_v4();
+        let stranger = Uuid::new_v4();
+        let mut list = ListDTO {
+            list_id: Uuid::new_v4(),
+            owner_id,
+            name: "Friends".into(),
+            is_private: false,
+            created_at: Utc::now(),
+        };
+
+        assert!(Lists::check_visible(&list, &stranger).is_ok());
+        assert_eq!(
+            Lists::check_owner(&list, &stranger).unwrap_err(),
+            HearthError::Forbidden(NOT_LIST_OWNER_ERROR_CODE.into())
+        );
+
+        list.is_private = true;
+        let not_found = HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into());
+        assert_eq!(Lists::check_visible(&list, &stranger).unwrap_err(), not_found);
+        assert_eq!(Lists::check_owner(&list, &stranger).unwrap_err(), not_found);
+        assert!(Lists::check_owner(&list, &owner_id).is_ok());
+    }
+}
@@ -1,2 +1,18 @@
+pub mod age;
+pub mod conversations;
+pub mod data_export;
+pub mod device_keys;
+pub mod email;
+pub mod import;
+pub mod jobs;
+pub mod keyword_filters;
+pub mod lists;
+pub mod moderation;
+pub mod oauth;
+pub mod oidc;
+pub mod personal_access_token;
 pub mod posts;
+pub mod proof_of_work;
+pub mod totp;
 pub mod user;
+pub mod webauthn;
@@ -0,0 +1,55 @@
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+
+use crate::{
+    dtos::{ moderation::{ ModerationAction, ReportState }, user::{ Role, UserDTO } },
+    error_codes::{
+        INVALID_MODERATION_ACTION_ERROR_CODE,
+        INVALID_SUSPENSION_EXPIRY_ERROR_CODE,
+        NOT_ADMIN_ERROR_CODE,
+        NOT_MODERATOR_ERROR_CODE,
+    },
+};
+
+pub struct Moderation {}
+
+impl Moderation {
+    pub fn check_moderator(user: &UserDTO) -> Result<(), HearthError> {
+        if !user.role.can_moderate() {
+            return Err(HearthError::Forbidden(NOT_MODERATOR_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_admin(user: &UserDTO) -> Result<(), HearthError> {
+        if user.role != Role::Admin {
+            return Err(HearthError::Forbidden(NOT_ADMIN_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    /// State a report ends up in once `action` is taken on it.
+    pub fn resolved_state(action: ModerationAction) -> Result<ReportState, HearthError> {
+        match action {
+            ModerationAction::Dismiss => Ok(ReportState::Dismissed),
+            ModerationAction::HidePost | ModerationAction::SuspendUser | ModerationAction::Warn =>
+                Ok(ReportState::Actioned),
+            ModerationAction::ChangeRole =>
+                Err(HearthError::Domain(INVALID_MODERATION_ACTION_ERROR_CODE.into())),
+        }
+    }
+
+    /// A temporary suspension has to end in the future.
+    pub fn check_suspension_expiry(
+        until: Option<DateTime<Utc>>,
+        now: DateTime<Utc>
+    ) -> Result<(), HearthError> {
+        if until.is_some_and(|until| until <= now) {
+            return Err(HearthError::Domain(INVALID_SUSPENSION_EXPIRY_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use url::{ Host, Url };
+
+use crate::{ dtos::oauth::{ OAuthAppDTO, Scope }, entities::oidc::Oidc };
+
+const FORBIDDEN_REDIRECT_SCHEMES: [&str; 4] = ["javascript", "data", "file", "vbscript"];
+
+/// Authorization server side of OAuth2 (RFC 6749), with PKCE (RFC 7636).
+pub struct OAuth {}
+
+impl OAuth {
+    /// Secrets, codes and access tokens, 256 bits each.
+    pub fn generate_token() -> String {
+        Oidc::generate_token()
+    }
+
+    /// Only S256 challenges are ever accepted.
+    pub fn verify_pkce(code_verifier: &str, code_challenge: &str) -> bool {
+        (43..=128).contains(&code_verifier.len()) &&
+            Oidc::code_challenge(code_verifier) == code_challenge
+    }
+
+    /// HTTPS, plain HTTP on the loopback interface or a private scheme for native
+    /// apps (RFC 8252), never with a fragment.
+    pub fn is_valid_redirect_uri(redirect_uri: &str) -> bool {
+        let Ok(url) = Url::parse(redirect_uri) else {
+            return false;
+        };
+        if url.fragment().is_some() || FORBIDDEN_REDIRECT_SCHEMES.contains(&url.scheme()) {
+            return false;
+        }
+
+        match url.scheme() {
+            "https" => url.host().is_some(),
+            "http" =>
+                match url.host() {
+                    Some(Host::Domain(domain)) => domain == "localhost",
+                    Some(Host::Ipv4(ip)) => ip.is_loopback(),
+                    Some(Host::Ipv6(ip)) => ip.is_loopback(),
+                    None => false,
+                }
+            _ => true,
+        }
+    }
+
+    /// `redirect_uri` with `params` appended to its query.
+    pub fn redirect(redirect_uri: &str, params: &[(&str, &str)]) -> Option<String> {
+        let mut url = Url::parse(redirect_uri).ok()?;
+        url.query_pairs_mut().extend_pairs(params);
+        Some(url.into())
+    }
+
+    /// Scopes of a request, every one of them must be allowed for the app.
+    pub fn grantable_scopes(app: &OAuthAppDTO, scope: &str) -> Option<Vec<Scope>> {
+        let scopes = Scope::parse_list(scope)?;
+        let allowed = !scopes.is_empty() && scopes.iter().all(|scope| app.scopes.contains(scope));
+        allowed.then_some(scopes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use crate::dtos::oauth::{ OAuthAppDTO, Scope };
+
+    use super::OAuth;
+
+    #[test]
+    fn should_only_accept_safe_redirect_uris() {
+        for uri in [
+            "https://bot.example/callback",
+            "http://localhost:8080/callback",
+            "http://127.0.0.1/callback",
+            "com.example.app:/oauth",
+        ] {
+            assert!(OAuth::is_valid_redirect_uri(uri), "{}", uri);
+        }
+
+        for uri in [
+            "http://bot.example/callback",
+            "https://bot.example/callback#token",
+            "javascript:alert(1)",
+            "/relative",
+        ] {
+            assert!(!OAuth::is_valid_redirect_uri(uri), "{}", uri);
+        }
+    }
+
+    #[test]
+    fn should_only_grant_scopes_allowed_for_the_app() {
+        let app = OAuthAppDTO {
+            client_id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Bot".into(),
+            redirect_uris: vec![],
+            scopes: vec![Scope::Read, Scope::Write],
+            client_secret_hash: None,
+            created_at: Utc::now(),
+        };
+
+        assert_eq!(OAuth::grantable_scopes(&app, "read read"), Some(vec![Scope::Read]));
+        assert_eq!(OAuth::grantable_scopes(&app, "read dm"), None);
+        assert_eq!(OAuth::grantable_scopes(&app, "read profile"), None);
+        assert_eq!(OAuth::grantable_scopes(&app, ""), None);
+    }
+
+    #[test]
+    fn should_verify_s256_challenges() {
+        // RFC 7636 appendix B.
+        let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+        let challenge = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+        assert!(OAuth::verify_pkce(verifier, challenge));
+        assert!(!OAuth::verify_pkce(challenge, challenge));
+        assert!(!OAuth::verify_pkce("short", challenge));
+    }
+}
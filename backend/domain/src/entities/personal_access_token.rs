@@ -0,0 +1,51 @@
+use chrono::{ DateTime, Duration, Utc };
+
+use crate::entities::oauth::OAuth;
+
+/// Tells personal access tokens apart from sessions and OAuth tokens, and
+/// makes leaked ones easy to spot by secret scanners.
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "hpat_";
+
+/// `last_used_at` is only written once a minute per token, bots tend to be chatty.
+const LAST_USED_RESOLUTION_SECONDS: i64 = 60;
+
+pub struct PersonalAccessToken {}
+
+impl PersonalAccessToken {
+    pub fn generate() -> String {
+        format!("{}{}", PERSONAL_ACCESS_TOKEN_PREFIX, OAuth::generate_token())
+    }
+
+    pub fn is_personal_access_token(token: &str) -> bool {
+        token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX)
+    }
+
+    pub fn should_touch(last_used_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+        last_used_at.is_none_or(|at| now - at >= Duration::seconds(LAST_USED_RESOLUTION_SECONDS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+
+    use crate::entities::personal_access_token::PersonalAccessToken;
+
+    #[test]
+    fn should_generate_prefixed_tokens() {
+        let token = PersonalAccessToken::generate();
+
+        assert!(PersonalAccessToken::is_personal_access_token(&token));
+        assert_ne!(token, PersonalAccessToken::generate());
+        assert!(!PersonalAccessToken::is_personal_access_token("session-token"));
+    }
+
+    #[test]
+    fn should_only_touch_once_a_minute() {
+        let now = Utc::now();
+
+        assert!(PersonalAccessToken::should_touch(None, now));
+        assert!(!PersonalAccessToken::should_touch(Some(now - Duration::seconds(30)), now));
+        assert!(PersonalAccessToken::should_touch(Some(now - Duration::seconds(90)), now));
+    }
+}
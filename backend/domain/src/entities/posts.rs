@@ -1 +1,167 @@
+use chrono::{ DateTime, Duration, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{ poll::{ CreatePollDTO, PollDTO, PollOptionDTO }, post::CreatePostDTO },
+    error_codes::{
+        INVALID_POLL_CHOICES_ERROR_CODE,
+        INVALID_POLL_DURATION_ERROR_CODE,
+        INVALID_POLL_OPTIONS_ERROR_CODE,
+        POLL_WITH_MEDIA_ERROR_CODE,
+    },
+    policies::poll::PollPolicy,
+};
+
 pub struct Posts {}
+
+impl Posts {
+    /// A post carries a single kind of attachment: media or a poll.
+    pub fn check_attachments(dto: &CreatePostDTO) -> Result<(), HearthError> {
+        if dto.poll.is_some() && !dto.media_ids.is_empty() {
+            return Err(HearthError::Domain(POLL_WITH_MEDIA_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Builds the empty poll of a post created at `created_at`.
+    pub fn new_poll(
+        post_id: Uuid,
+        created_at: DateTime<Utc>,
+        dto: &CreatePollDTO,
+        policy: &PollPolicy
+    ) -> Result<PollDTO, HearthError> {
+        let options: Vec<String> = dto.options
+            .iter()
+            .map(|option| option.trim().to_string())
+            .collect();
+
+        let valid_options =
+            (policy.min_options..=policy.max_options).contains(&options.len()) &&
+            options
+                .iter()
+                .all(|o| !o.is_empty() && o.chars().count() <= policy.max_option_length) &&
+            options
+                .iter()
+                .enumerate()
+                .all(|(i, o)| !options[..i].iter().any(|other| other.eq_ignore_ascii_case(o)));
+
+        if !valid_options {
+            return Err(HearthError::Domain(INVALID_POLL_OPTIONS_ERROR_CODE.into()));
+        }
+
+        if !(policy.min_duration_seconds..=policy.max_duration_seconds).contains(&dto.duration_seconds) {
+            return Err(HearthError::Domain(INVALID_POLL_DURATION_ERROR_CODE.into()));
+        }
+
+        Ok(PollDTO {
+            post_id,
+            options: options
+                .into_iter()
+                .map(|title| PollOptionDTO { title, votes_count: Some(0) })
+                .collect(),
+            multiple_choice: dto.multiple_choice,
+            closes_at: created_at + Duration::seconds(dto.duration_seconds),
+            voters_count: Some(0),
+            own_votes: vec![],
+        })
+    }
+
+    /// What `viewer` may see of a poll: tallies stay hidden until they voted,
+    /// unless they wrote the post or the poll is closed.
+    pub fn poll_for_viewer(
+        poll: PollDTO,
+        own_votes: Vec<u8>,
+        is_author: bool,
+        at: DateTime<Utc>
+    ) -> PollDTO {
+        let visible = is_author || !own_votes.is_empty() || poll.is_closed(at);
+        let poll = PollDTO { own_votes, ..poll };
+
+        if visible { poll } else { poll.without_results() }
+    }
+
+    /// Validates the picked options and returns them sorted and deduplicated.
+    pub fn check_choices(poll: &PollDTO, choices: &[u8]) -> Result<Vec<u8>, HearthError> {
+        let mut choices = choices.to_vec();
+        choices.sort();
+        choices.dedup();
+
+        let valid =
+            !choices.is_empty() &&
+            (poll.multiple_choice || choices.len() == 1) &&
+            choices.iter().all(|c| (*c as usize) < poll.options.len());
+
+        if !valid {
+            return Err(HearthError::Domain(INVALID_POLL_CHOICES_ERROR_CODE.into()));
+        }
+
+        Ok(choices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::poll::CreatePollDTO,
+        entities::posts::Posts,
+        error_codes::{
+            INVALID_POLL_CHOICES_ERROR_CODE,
+            INVALID_POLL_DURATION_ERROR_CODE,
+            INVALID_POLL_OPTIONS_ERROR_CODE,
+        },
+        policies::poll::PollPolicy,
+    };
+
+    fn poll(options: &[&str], duration_seconds: i64) -> CreatePollDTO {
+        CreatePollDTO {
+            options: options
+                .iter()
+                .map(|o| o.to_string())
+                .collect(),
+            multiple_choice: false,
+            duration_seconds,
+        }
+    }
+
+    #[test]
+    fn should_validate_poll_shape() {
+        let policy = PollPolicy::default();
+        let new_poll = |dto: CreatePollDTO| Posts::new_poll(Uuid::new_v4(), Utc::now(), &dto, &policy);
+        let invalid_options = HearthError::Domain(INVALID_POLL_OPTIONS_ERROR_CODE.into());
+
+        assert!(new_poll(poll(&["yes", "no"], 3600)).is_ok());
+        assert_eq!(new_poll(poll(&["yes"], 3600)).unwrap_err(), invalid_options);
+        assert_eq!(new_poll(poll(&["a", "b", "c", "d", "e"], 3600)).unwrap_err(), invalid_options);
+        assert_eq!(new_poll(poll(&["yes", "  "], 3600)).unwrap_err(), invalid_options);
+        assert_eq!(new_poll(poll(&["Yes", "yes"], 3600)).unwrap_err(), invalid_options);
+        assert_eq!(
+            new_poll(poll(&["yes", "no"], 60)).unwrap_err(),
+            HearthError::Domain(INVALID_POLL_DURATION_ERROR_CODE.into())
+        );
+    }
+
+    #[test]
+    fn should_check_choices_against_the_poll_kind() {
+        let policy = PollPolicy::default();
+        let single = Posts::new_poll(Uuid::new_v4(), Utc::now(), &poll(&["a", "b", "c"], 3600), &policy).unwrap();
+        let multiple = Posts::new_poll(
+            Uuid::new_v4(),
+            Utc::now(),
+            &(CreatePollDTO { multiple_choice: true, ..poll(&["a", "b", "c"], 3600) }),
+            &policy
+        ).unwrap();
+        let invalid = HearthError::Domain(INVALID_POLL_CHOICES_ERROR_CODE.into());
+
+        assert_eq!(Posts::check_choices(&single, &[1]).unwrap(), vec![1]);
+        assert_eq!(Posts::check_choices(&single, &[0, 1]).unwrap_err(), invalid);
+        assert_eq!(Posts::check_choices(&single, &[3]).unwrap_err(), invalid);
+        assert_eq!(Posts::check_choices(&single, &[]).unwrap_err(), invalid);
+        assert_eq!(Posts::check_choices(&multiple, &[2, 0, 2]).unwrap(), vec![0, 2]);
+    }
+}
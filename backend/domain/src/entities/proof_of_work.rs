@@ -0,0 +1,40 @@
+use sha2::{ Digest, Sha256 };
+
+pub struct ProofOfWork {}
+
+impl ProofOfWork {
+    /// Number of leading zero bits of `SHA-256(prefix || solution)`.
+    pub fn leading_zero_bits(prefix: &str, solution: &str) -> u32 {
+        let hash = Sha256::new().chain_update(prefix).chain_update(solution).finalize();
+
+        let mut bits = 0;
+        for byte in hash {
+            bits += byte.leading_zeros();
+            if byte != 0 {
+                break;
+            }
+        }
+        bits
+    }
+
+    pub fn verify(prefix: &str, solution: &str, difficulty: u8) -> bool {
+        ProofOfWork::leading_zero_bits(prefix, solution) >= (difficulty as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::proof_of_work::ProofOfWork;
+
+    #[test]
+    fn should_verify_a_solved_challenge() {
+        let prefix = "4f1c2b";
+        let solution = (0u64..)
+            .map(|n| n.to_string())
+            .find(|s| ProofOfWork::verify(prefix, s, 8))
+            .unwrap();
+
+        assert!(ProofOfWork::leading_zero_bits(prefix, &solution) >= 8);
+        assert!(!ProofOfWork::verify(prefix, &solution, 255));
+    }
+}
@@ -1,4 +1,10 @@
-use crate::dtos::user::CreateUserDTO;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+
+use crate::{
+    dtos::user::{ CreateUserDTO, UserDTO, UserStatus },
+    error_codes::{ ACCOUNT_DEACTIVATED_ERROR_CODE, ACCOUNT_SUSPENDED_ERROR_CODE },
+};
 
 pub struct User {}
 
@@ -6,4 +12,67 @@ impl User {
     pub fn new(create_user_dto: CreateUserDTO) -> Self {
         Self {}
     }
+
+    /// Temporary suspensions lift themselves once `suspended_until` has passed.
+    pub fn is_suspended(user: &UserDTO, now: DateTime<Utc>) -> bool {
+        user.status == UserStatus::Suspended && user.suspended_until.is_none_or(|until| until > now)
+    }
+
+    /// The check every entry point runs before letting a user act.
+    pub fn check_active(user: &UserDTO, now: DateTime<Utc>) -> Result<(), HearthError> {
+        if User::is_suspended(user, now) {
+            return Err(HearthError::Forbidden(ACCOUNT_SUSPENDED_ERROR_CODE.into()));
+        }
+        if user.status == UserStatus::Deactivated {
+            return Err(HearthError::Forbidden(ACCOUNT_DEACTIVATED_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::user::{ CreateUserDTO, UserDTO, UserStatus },
+        entities::user::User,
+    };
+
+    #[test]
+    fn should_lift_temporary_suspensions_once_expired() {
+        let now = Utc::now();
+        let mut user = UserDTO::new(CreateUserDTO {
+            user_id: Uuid::new_v4(),
+            username: "john.smith".into(),
+            email: "john.smith@gmail.com".into(),
+            birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+        });
+        assert!(User::check_active(&user, now).is_ok());
+
+        user.status = UserStatus::Suspended;
+        assert!(User::check_active(&user, now).is_err());
+
+        user.suspended_until = Some(now + Duration::days(1));
+        assert!(User::is_suspended(&user, now));
+        assert!(!User::is_suspended(&user, now + Duration::days(2)));
+    }
+
+    #[test]
+    fn should_reject_deactivated_accounts() {
+        let now = Utc::now();
+        let mut user = UserDTO::new(CreateUserDTO {
+            user_id: Uuid::new_v4(),
+            username: "john.smith".into(),
+            email: "john.smith@gmail.com".into(),
+            birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+        });
+        user.status = UserStatus::Deactivated;
+        user.deactivated_at = Some(now);
+
+        assert!(!User::is_suspended(&user, now));
+        assert!(User::check_active(&user, now).is_err());
+    }
 }
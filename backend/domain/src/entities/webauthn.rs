@@ -0,0 +1,238 @@
+use base64::{ Engine, engine::general_purpose::URL_SAFE_NO_PAD };
+use ciborium::Value;
+use rand::RngCore;
+use ring::signature::{ ECDSA_P256_SHA256_ASN1, UnparsedPublicKey };
+use serde::Deserialize;
+use sha2::{ Digest, Sha256 };
+
+use crate::{ dtos::passkey::ES256, policies::passkey::PasskeyPolicy };
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+pub const CREATE_CEREMONY: &str = "webauthn.create";
+pub const GET_CEREMONY: &str = "webauthn.get";
+
+/// `CollectedClientData`, signed over by the authenticator.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ClientData {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub challenge: String,
+    pub origin: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestedCredential {
+    pub credential_id: Vec<u8>,
+    /// Uncompressed P-256 point, only ES256 keys are parsed.
+    pub public_key: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthenticatorData {
+    pub rp_id_hash: Vec<u8>,
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+/// The parts of WebAuthn Level 2 needed for `none` attestation and ES256
+/// assertions. Parsers return `None` on anything malformed.
+pub struct WebAuthn {}
+
+impl WebAuthn {
+    pub fn generate_challenge() -> String {
+        let mut bytes = [0u8; 32];
+        rand::rng().fill_bytes(&mut bytes);
+        WebAuthn::encode(&bytes)
+    }
+
+    pub fn encode(bytes: &[u8]) -> String {
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    pub fn decode(value: &str) -> Option<Vec<u8>> {
+        URL_SAFE_NO_PAD.decode(value.trim_end_matches('=')).ok()
+    }
+
+    /// Client data of the expected ceremony, coming from an allowed origin.
+    pub fn parse_client_data(
+        client_data_json: &[u8],
+        ceremony: &str,
+        policy: &PasskeyPolicy
+    ) -> Option<ClientData> {
+        let client_data: ClientData = serde_json::from_slice(client_data_json).ok()?;
+
+        (client_data.kind == ceremony && policy.origins.contains(&client_data.origin)).then_some(
+            client_data
+        )
+    }
+
+    /// `authData` out of a CBOR attestation object, the statement itself is
+    /// ignored as attestation is never requested.
+    pub fn parse_attestation_object(attestation_object: &[u8]) -> Option<Vec<u8>> {
+        let value: Value = ciborium::de::from_reader(attestation_object).ok()?;
+
+        value
+            .into_map()
+            .ok()?
+            .into_iter()
+            .find(|(key, _)| key.as_text() == Some("authData"))
+            .and_then(|(_, value)| value.into_bytes().ok())
+    }
+
+    pub fn parse_authenticator_data(bytes: &[u8]) -> Option<AuthenticatorData> {
+        if bytes.len() < 37 {
+            return None;
+        }
+
+        let flags = bytes[32];
+        let sign_count = u32::from_be_bytes(bytes[33..37].try_into().ok()?);
+
+        let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+            // 16 bytes of AAGUID, then the credential id and its COSE key.
+            let rest = bytes.get(37 + 16..)?;
+            let length = u16::from_be_bytes(rest.get(..2)?.try_into().ok()?) as usize;
+            let credential_id = rest.get(2..2 + length)?.to_vec();
+            let mut cose_key = rest.get(2 + length..)?;
+            let value: Value = ciborium::de::from_reader(&mut cose_key).ok()?;
+
+            Some(AttestedCredential { credential_id, public_key: WebAuthn::es256_public_key(value)? })
+        } else {
+            None
+        };
+
+        Some(AuthenticatorData {
+            rp_id_hash: bytes[..32].to_vec(),
+            flags,
+            sign_count,
+            attested_credential,
+        })
+    }
+
+    /// Bound to our relying party, with the user present and verified when required.
+    pub fn check_authenticator_data(data: &AuthenticatorData, policy: &PasskeyPolicy) -> bool {
+        let rp_id_hash = Sha256::digest(policy.rp_id.as_bytes());
+
+        data.rp_id_hash == rp_id_hash.as_slice() &&
+            data.flags & FLAG_USER_PRESENT != 0 &&
+            (!policy.require_user_verification || data.flags & FLAG_USER_VERIFIED != 0)
+    }
+
+    /// ES256 signature over `authenticatorData || SHA-256(clientDataJSON)`.
+    pub fn verify_signature(
+        public_key: &[u8],
+        authenticator_data: &[u8],
+        client_data_json: &[u8],
+        signature: &[u8]
+    ) -> bool {
+        let mut message = authenticator_data.to_vec();
+        message.extend_from_slice(&Sha256::digest(client_data_json));
+
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, public_key)
+            .verify(&message, signature)
+            .is_ok()
+    }
+
+    /// Counters only ever grow, a lower one means the key was cloned.
+    /// Authenticators that don't count always report zero.
+    pub fn check_sign_count(stored: u32, received: u32) -> bool {
+        (stored == 0 && received == 0) || received > stored
+    }
+
+    /// EC2 key on P-256 (COSE kty 2, crv 1) announced for ES256.
+    fn es256_public_key(cose_key: Value) -> Option<Vec<u8>> {
+        let entries = cose_key.into_map().ok()?;
+        let field = |label: i64| {
+            entries
+                .iter()
+                .find(|(key, _)| key.as_integer().is_some_and(|key| i128::from(key) == label.into()))
+                .map(|(_, value)| value)
+        };
+        let integer = |label: i64| field(label)?.as_integer().map(i128::from);
+
+        if integer(1)? != 2 || integer(3)? != ES256.into() || integer(-1)? != 1 {
+            return None;
+        }
+
+        let x = field(-2)?.as_bytes()?;
+        let y = field(-3)?.as_bytes()?;
+        if x.len() != 32 || y.len() != 32 {
+            return None;
+        }
+
+        Some([&[0x04], x.as_slice(), y.as_slice()].concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::webauthn::{ CREATE_CEREMONY, GET_CEREMONY, WebAuthn },
+        policies::passkey::PasskeyPolicy,
+        test_utils::test_utils::SoftwareAuthenticator,
+    };
+
+    #[test]
+    fn should_parse_a_registration_and_verify_assertions() {
+        let policy = PasskeyPolicy::default();
+        let mut authenticator = SoftwareAuthenticator::new(&policy);
+        let challenge = WebAuthn::generate_challenge();
+
+        let (client_data_json, attestation_object) = authenticator.attestation(&challenge);
+        let client_data = WebAuthn::parse_client_data(&client_data_json, CREATE_CEREMONY, &policy);
+        assert_eq!(client_data.unwrap().challenge, challenge);
+        assert!(WebAuthn::parse_client_data(&client_data_json, GET_CEREMONY, &policy).is_none());
+
+        let auth_data = WebAuthn::parse_attestation_object(&attestation_object).unwrap();
+        let data = WebAuthn::parse_authenticator_data(&auth_data).unwrap();
+        assert!(WebAuthn::check_authenticator_data(&data, &policy));
+        let credential = data.attested_credential.unwrap();
+        assert_eq!(credential.credential_id, authenticator.credential_id);
+        assert_eq!(credential.public_key, authenticator.public_key());
+
+        let (client_data_json, authenticator_data, signature) = authenticator.assertion(&challenge);
+        assert!(
+            WebAuthn::verify_signature(
+                &credential.public_key,
+                &authenticator_data,
+                &client_data_json,
+                &signature
+            )
+        );
+        assert!(
+            !WebAuthn::verify_signature(
+                &credential.public_key,
+                &authenticator_data,
+                b"{\"type\":\"webauthn.get\"}",
+                &signature
+            )
+        );
+    }
+
+    #[test]
+    fn should_refuse_other_relying_parties_and_origins() {
+        let policy = PasskeyPolicy::default();
+        let mut authenticator = SoftwareAuthenticator::new(&PasskeyPolicy {
+            rp_id: "evil.example".into(),
+            origins: vec!["https://evil.example".into()],
+            ..PasskeyPolicy::default()
+        });
+
+        let (client_data_json, authenticator_data, _) = authenticator.assertion("challenge");
+        assert!(WebAuthn::parse_client_data(&client_data_json, GET_CEREMONY, &policy).is_none());
+
+        let data = WebAuthn::parse_authenticator_data(&authenticator_data).unwrap();
+        assert!(!WebAuthn::check_authenticator_data(&data, &policy));
+    }
+
+    #[test]
+    fn should_only_accept_growing_sign_counts() {
+        assert!(WebAuthn::check_sign_count(0, 0));
+        assert!(WebAuthn::check_sign_count(4, 5));
+        assert!(!WebAuthn::check_sign_count(5, 5));
+        assert!(!WebAuthn::check_sign_count(5, 0));
+    }
+}
@@ -1 +1,98 @@
 pub const USER_NOT_FOUND_ERROR_CODE: &str = "USER_NOT_FOUND";
+pub const POST_NOT_FOUND_ERROR_CODE: &str = "POST_NOT_FOUND";
+pub const INVALID_CREDENTIALS_ERROR_CODE: &str = "INVALID_CREDENTIALS";
+pub const INVALID_SESSION_ERROR_CODE: &str = "INVALID_SESSION";
+pub const INVALID_CURSOR_ERROR_CODE: &str = "INVALID_CURSOR";
+pub const INVALID_EMAIL_VERIFICATION_CODE_ERROR_CODE: &str = "INVALID_EMAIL_VERIFICATION_CODE";
+pub const CANNOT_BLOCK_SELF_ERROR_CODE: &str = "CANNOT_BLOCK_SELF";
+pub const MEDIA_NOT_FOUND_ERROR_CODE: &str = "MEDIA_NOT_FOUND";
+pub const MEDIA_TYPE_NOT_ALLOWED_ERROR_CODE: &str = "MEDIA_TYPE_NOT_ALLOWED";
+pub const MEDIA_UNREADABLE_ERROR_CODE: &str = "MEDIA_UNREADABLE";
+pub const MEDIA_TOO_LARGE_ERROR_CODE: &str = "MEDIA_TOO_LARGE";
+pub const MEDIA_QUOTA_EXCEEDED_ERROR_CODE: &str = "MEDIA_QUOTA_EXCEEDED";
+pub const MEDIA_NOT_OWNED_ERROR_CODE: &str = "MEDIA_NOT_OWNED";
+pub const MEDIA_NOT_READY_ERROR_CODE: &str = "MEDIA_NOT_READY";
+pub const TOO_MANY_ATTACHMENTS_ERROR_CODE: &str = "TOO_MANY_ATTACHMENTS";
+pub const POLL_NOT_FOUND_ERROR_CODE: &str = "POLL_NOT_FOUND";
+pub const POLL_CLOSED_ERROR_CODE: &str = "POLL_CLOSED";
+pub const POLL_ALREADY_VOTED_ERROR_CODE: &str = "POLL_ALREADY_VOTED";
+pub const INVALID_POLL_OPTIONS_ERROR_CODE: &str = "INVALID_POLL_OPTIONS";
+pub const INVALID_POLL_DURATION_ERROR_CODE: &str = "INVALID_POLL_DURATION";
+pub const INVALID_POLL_CHOICES_ERROR_CODE: &str = "INVALID_POLL_CHOICES";
+pub const POLL_WITH_MEDIA_ERROR_CODE: &str = "POLL_WITH_MEDIA";
+pub const LINK_PREVIEW_BLOCKED_ERROR_CODE: &str = "LINK_PREVIEW_BLOCKED";
+pub const LINK_PREVIEW_UNAVAILABLE_ERROR_CODE: &str = "LINK_PREVIEW_UNAVAILABLE";
+pub const BOOKMARK_NOT_FOUND_ERROR_CODE: &str = "BOOKMARK_NOT_FOUND";
+pub const BOOKMARK_COLLECTION_NOT_FOUND_ERROR_CODE: &str = "BOOKMARK_COLLECTION_NOT_FOUND";
+pub const BOOKMARK_COLLECTION_NAME_TAKEN_ERROR_CODE: &str = "BOOKMARK_COLLECTION_NAME_TAKEN";
+pub const LIST_NOT_FOUND_ERROR_CODE: &str = "LIST_NOT_FOUND";
+pub const NOT_LIST_OWNER_ERROR_CODE: &str = "NOT_LIST_OWNER";
+pub const LIST_FULL_ERROR_CODE: &str = "LIST_FULL";
+pub const TOO_MANY_LISTS_ERROR_CODE: &str = "TOO_MANY_LISTS";
+pub const CONVERSATION_NOT_FOUND_ERROR_CODE: &str = "CONVERSATION_NOT_FOUND";
+pub const MESSAGE_NOT_FOUND_ERROR_CODE: &str = "MESSAGE_NOT_FOUND";
+pub const DM_NOT_ALLOWED_ERROR_CODE: &str = "DM_NOT_ALLOWED";
+pub const INVALID_PARTICIPANTS_ERROR_CODE: &str = "INVALID_PARTICIPANTS";
+pub const EMPTY_MESSAGE_ERROR_CODE: &str = "EMPTY_MESSAGE";
+pub const DEVICE_NOT_FOUND_ERROR_CODE: &str = "DEVICE_NOT_FOUND";
+pub const TOO_MANY_DEVICES_ERROR_CODE: &str = "TOO_MANY_DEVICES";
+pub const TOO_MANY_PREKEYS_ERROR_CODE: &str = "TOO_MANY_PREKEYS";
+pub const INVALID_ENVELOPES_ERROR_CODE: &str = "INVALID_ENVELOPES";
+pub const REPORT_NOT_FOUND_ERROR_CODE: &str = "REPORT_NOT_FOUND";
+pub const REPORT_ALREADY_RESOLVED_ERROR_CODE: &str = "REPORT_ALREADY_RESOLVED";
+pub const CANNOT_REPORT_SELF_ERROR_CODE: &str = "CANNOT_REPORT_SELF";
+pub const INVALID_MODERATION_ACTION_ERROR_CODE: &str = "INVALID_MODERATION_ACTION";
+pub const NOT_MODERATOR_ERROR_CODE: &str = "NOT_MODERATOR";
+pub const NOT_ADMIN_ERROR_CODE: &str = "NOT_ADMIN";
+pub const ACCOUNT_SUSPENDED_ERROR_CODE: &str = "ACCOUNT_SUSPENDED";
+pub const CANNOT_SUSPEND_SELF_ERROR_CODE: &str = "CANNOT_SUSPEND_SELF";
+pub const INVALID_SUSPENSION_EXPIRY_ERROR_CODE: &str = "INVALID_SUSPENSION_EXPIRY";
+pub const FILTER_NOT_FOUND_ERROR_CODE: &str = "FILTER_NOT_FOUND";
+pub const TOO_MANY_FILTERS_ERROR_CODE: &str = "TOO_MANY_FILTERS";
+pub const INVALID_FILTER_EXPIRY_ERROR_CODE: &str = "INVALID_FILTER_EXPIRY";
+pub const RATE_LIMITED_ERROR_CODE: &str = "RATE_LIMITED";
+pub const EMAIL_DOMAIN_NOT_ALLOWED_ERROR_CODE: &str = "EMAIL_DOMAIN_NOT_ALLOWED";
+pub const PROOF_OF_WORK_REQUIRED_ERROR_CODE: &str = "PROOF_OF_WORK_REQUIRED";
+pub const INVALID_PROOF_OF_WORK_ERROR_CODE: &str = "INVALID_PROOF_OF_WORK";
+pub const PROOF_OF_WORK_DISABLED_ERROR_CODE: &str = "PROOF_OF_WORK_DISABLED";
+pub const INVALID_BIRTHDAY_ERROR_CODE: &str = "INVALID_BIRTHDAY";
+pub const BELOW_MINIMUM_AGE_ERROR_CODE: &str = "BELOW_MINIMUM_AGE";
+pub const TWO_FACTOR_ALREADY_ENABLED_ERROR_CODE: &str = "TWO_FACTOR_ALREADY_ENABLED";
+pub const TWO_FACTOR_NOT_PENDING_ERROR_CODE: &str = "TWO_FACTOR_NOT_PENDING";
+pub const TWO_FACTOR_NOT_ENABLED_ERROR_CODE: &str = "TWO_FACTOR_NOT_ENABLED";
+pub const INVALID_TWO_FACTOR_CODE_ERROR_CODE: &str = "INVALID_TWO_FACTOR_CODE";
+pub const INVALID_LOGIN_CHALLENGE_ERROR_CODE: &str = "INVALID_LOGIN_CHALLENGE";
+pub const INVALID_WEBAUTHN_CHALLENGE_ERROR_CODE: &str = "INVALID_WEBAUTHN_CHALLENGE";
+pub const INVALID_WEBAUTHN_RESPONSE_ERROR_CODE: &str = "INVALID_WEBAUTHN_RESPONSE";
+pub const UNSUPPORTED_PASSKEY_ALGORITHM_ERROR_CODE: &str = "UNSUPPORTED_PASSKEY_ALGORITHM";
+pub const PASSKEY_ALREADY_REGISTERED_ERROR_CODE: &str = "PASSKEY_ALREADY_REGISTERED";
+pub const TOO_MANY_PASSKEYS_ERROR_CODE: &str = "TOO_MANY_PASSKEYS";
+pub const INVALID_PASSKEY_ERROR_CODE: &str = "INVALID_PASSKEY";
+pub const OIDC_PROVIDER_NOT_FOUND_ERROR_CODE: &str = "OIDC_PROVIDER_NOT_FOUND";
+pub const INVALID_OIDC_STATE_ERROR_CODE: &str = "INVALID_OIDC_STATE";
+pub const OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE: &str = "OIDC_CODE_EXCHANGE_FAILED";
+pub const INVALID_ID_TOKEN_ERROR_CODE: &str = "INVALID_ID_TOKEN";
+pub const OIDC_EMAIL_NOT_VERIFIED_ERROR_CODE: &str = "OIDC_EMAIL_NOT_VERIFIED";
+pub const INVALID_OIDC_SIGNUP_ERROR_CODE: &str = "INVALID_OIDC_SIGNUP";
+pub const OIDC_LINK_REQUIRED_ERROR_CODE: &str = "OIDC_LINK_REQUIRED";
+pub const OIDC_IDENTITY_ALREADY_LINKED_ERROR_CODE: &str = "OIDC_IDENTITY_ALREADY_LINKED";
+pub const INVALID_REDIRECT_URI_ERROR_CODE: &str = "INVALID_REDIRECT_URI";
+pub const TOO_MANY_OAUTH_APPS_ERROR_CODE: &str = "TOO_MANY_OAUTH_APPS";
+pub const INVALID_CLIENT_ERROR_CODE: &str = "INVALID_CLIENT";
+pub const INVALID_SCOPE_ERROR_CODE: &str = "INVALID_SCOPE";
+pub const INVALID_GRANT_ERROR_CODE: &str = "INVALID_GRANT";
+pub const UNSUPPORTED_GRANT_TYPE_ERROR_CODE: &str = "UNSUPPORTED_GRANT_TYPE";
+pub const UNSUPPORTED_RESPONSE_TYPE_ERROR_CODE: &str = "UNSUPPORTED_RESPONSE_TYPE";
+pub const PKCE_REQUIRED_ERROR_CODE: &str = "PKCE_REQUIRED";
+pub const INSUFFICIENT_SCOPE_ERROR_CODE: &str = "INSUFFICIENT_SCOPE";
+pub const SESSION_REQUIRED_ERROR_CODE: &str = "SESSION_REQUIRED";
+pub const TOO_MANY_PERSONAL_ACCESS_TOKENS_ERROR_CODE: &str = "TOO_MANY_PERSONAL_ACCESS_TOKENS";
+pub const PERSONAL_ACCESS_TOKEN_NOT_FOUND_ERROR_CODE: &str = "PERSONAL_ACCESS_TOKEN_NOT_FOUND";
+pub const ACCOUNT_DEACTIVATED_ERROR_CODE: &str = "ACCOUNT_DEACTIVATED";
+pub const INVALID_PASSWORD_ERROR_CODE: &str = "INVALID_PASSWORD";
+pub const DATA_EXPORT_NOT_FOUND_ERROR_CODE: &str = "DATA_EXPORT_NOT_FOUND";
+pub const IMPORT_ARCHIVE_TOO_LARGE_ERROR_CODE: &str = "IMPORT_ARCHIVE_TOO_LARGE";
+pub const IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE: &str = "IMPORT_ARCHIVE_UNREADABLE";
+pub const IMPORT_NOT_FOUND_ERROR_CODE: &str = "IMPORT_NOT_FOUND";
+pub const IMPORTED_POST_EMPTY_ERROR_CODE: &str = "IMPORTED_POST_EMPTY";
+pub const IMPORTED_POST_TOO_LONG_ERROR_CODE: &str = "IMPORTED_POST_TOO_LONG";
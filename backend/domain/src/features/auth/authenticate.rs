@@ -0,0 +1,265 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::{ auth::AuthenticatedUserDTO, oauth::Scope },
+    entities::{ personal_access_token::PersonalAccessToken, user::User },
+    error_codes::INVALID_SESSION_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{
+        oauth_tokens_repository::OAuthTokensRepository,
+        personal_access_tokens_repository::PersonalAccessTokensRepository,
+        sessions_repository::SessionsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+/// Resolves a bearer token into the user it was issued to. Every authenticated
+/// route goes through here, so this is where suspensions are enforced.
+/// Sessions carry every scope, OAuth and personal access tokens only what they
+/// were granted.
+pub type AuthenticateFeature = dyn Feature<String, AuthenticatedUserDTO>;
+
+pub struct Authenticate {
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub oauth_tokens_repository: BArc<dyn OAuthTokensRepository>,
+    pub personal_access_tokens_repository: BArc<dyn PersonalAccessTokensRepository>,
+}
+
+impl Authenticate {
+    async fn personal_access_token(
+        &self,
+        token_hash: &str,
+        now: DateTime<Utc>
+    ) -> Result<AuthenticatedUserDTO, HearthError> {
+        let token = self.personal_access_tokens_repository
+            .get_by_hash(token_hash).await?
+            .filter(|token| token.expires_at > now)
+            .ok_or_else(|| HearthError::Unauthorized(INVALID_SESSION_ERROR_CODE.into()))?;
+
+        if PersonalAccessToken::should_touch(token.last_used_at, now) {
+            self.personal_access_tokens_repository.touch(&token.token_id, now).await?;
+        }
+
+        Ok(AuthenticatedUserDTO {
+            user_id: token.user_id,
+            client_id: None,
+            personal_access_token_id: Some(token.token_id),
+            scopes: token.scopes,
+        })
+    }
+
+    async fn oauth_token(
+        &self,
+        token_hash: &str,
+        now: DateTime<Utc>
+    ) -> Result<AuthenticatedUserDTO, HearthError> {
+        let token = self.oauth_tokens_repository
+            .get(token_hash).await?
+            .filter(|token| token.revoked_at.is_none() && token.expires_at > now)
+            .ok_or_else(|| HearthError::Unauthorized(INVALID_SESSION_ERROR_CODE.into()))?;
+
+        Ok(AuthenticatedUserDTO {
+            user_id: token.user_id,
+            client_id: Some(token.client_id),
+            personal_access_token_id: None,
+            scopes: token.scopes,
+        })
+    }
+}
+
+#[async_trait]
+impl Feature<String, AuthenticatedUserDTO> for Authenticate {
+    async fn execute(&self, token: String) -> Result<AuthenticatedUserDTO, HearthError> {
+        let token_hash = hasher::hash!(token);
+        let now = Utc::now();
+
+        let authenticated_user = if PersonalAccessToken::is_personal_access_token(&token) {
+            self.personal_access_token(&token_hash, now).await?
+        } else if let Some(user_id) = self.sessions_repository.get_user_id(&token_hash).await? {
+            AuthenticatedUserDTO {
+                user_id,
+                client_id: None,
+                personal_access_token_id: None,
+                scopes: Scope::ALL.to_vec(),
+            }
+        } else {
+            self.oauth_token(&token_hash, now).await?
+        };
+
+        let user = self.users_repository.get(authenticated_user.user_id.to_string()).await?;
+        User::check_active(&user, now)?;
+
+        Ok(authenticated_user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            oauth::{ OAuthTokenDTO, Scope },
+            personal_access_token::PersonalAccessTokenDTO,
+            user::{ CreateUserDTO, UserStatus },
+        },
+        error_codes::{ ACCOUNT_SUSPENDED_ERROR_CODE, INVALID_SESSION_ERROR_CODE },
+        features::{ auth::authenticate::Authenticate, feature::Feature },
+        repositories::{
+            oauth_tokens_repository::OAuthTokensRepository,
+            personal_access_tokens_repository::PersonalAccessTokensRepository,
+            sessions_repository::SessionsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryOAuthTokensRepository,
+            InMemoryPersonalAccessTokensRepository,
+            InMemorySessionsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    fn authenticate(user_id: Uuid) -> Authenticate {
+        let oauth_tokens_repository: BArc<dyn OAuthTokensRepository> = barc!(
+            InMemoryOAuthTokensRepository::default()
+        );
+        let personal_access_tokens_repository: BArc<dyn PersonalAccessTokensRepository> = barc!(
+            InMemoryPersonalAccessTokensRepository::default()
+        );
+        let sessions_repository: BArc<dyn SessionsRepository> = barc!(
+            InMemorySessionsRepository::default()
+        );
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: "john.smith@gmail.com".into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            )
+        );
+
+        Authenticate {
+            sessions_repository,
+            users_repository,
+            oauth_tokens_repository,
+            personal_access_tokens_repository,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_resolve_token_to_user() {
+        let user_id = Uuid::new_v4();
+        let authenticate = authenticate(user_id);
+        authenticate.sessions_repository.create(&hasher::hash!("token"), &user_id).await.unwrap();
+
+        let authenticated_user = authenticate.execute("token".into()).await.unwrap();
+        assert_eq!(authenticated_user.user_id, user_id);
+        assert!(authenticated_user.is_session());
+        assert!(Scope::ALL.iter().all(|scope| authenticated_user.has_scope(*scope)));
+        assert_eq!(
+            authenticate.execute("other".into()).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_SESSION_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_suspended_users() {
+        let user_id = Uuid::new_v4();
+        let authenticate = authenticate(user_id);
+        authenticate.sessions_repository.create(&hasher::hash!("token"), &user_id).await.unwrap();
+        authenticate.users_repository.set_status(&user_id, UserStatus::Suspended, None).await.unwrap();
+
+        assert_eq!(
+            authenticate.execute("token".into()).await.unwrap_err(),
+            HearthError::Forbidden(ACCOUNT_SUSPENDED_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_resolve_oauth_tokens_with_their_scopes() {
+        let user_id = Uuid::new_v4();
+        let client_id = Uuid::new_v4();
+        let authenticate = authenticate(user_id);
+        let oauth_tokens_repository = authenticate.oauth_tokens_repository.clone();
+        let token = |token_hash: &str, expires_at| OAuthTokenDTO {
+            token_hash: token_hash.into(),
+            client_id,
+            user_id,
+            scopes: vec![Scope::Read],
+            expires_at,
+            revoked_at: None,
+        };
+        oauth_tokens_repository
+            .create(token(&hasher::hash!("oauth"), Utc::now() + Duration::hours(1))).await
+            .unwrap();
+        oauth_tokens_repository
+            .create(token(&hasher::hash!("expired"), Utc::now() - Duration::hours(1))).await
+            .unwrap();
+
+        let authenticated_user = authenticate.execute("oauth".into()).await.unwrap();
+        assert_eq!(authenticated_user.client_id, Some(client_id));
+        assert!(authenticated_user.has_scope(Scope::Read));
+        assert!(!authenticated_user.has_scope(Scope::Write));
+        assert_eq!(
+            authenticate.execute("expired".into()).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_SESSION_ERROR_CODE.into())
+        );
+
+        oauth_tokens_repository.revoke(&hasher::hash!("oauth"), Utc::now()).await.unwrap();
+        assert_eq!(
+            authenticate.execute("oauth".into()).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_SESSION_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_tag_personal_access_tokens() {
+        let user_id = Uuid::new_v4();
+        let authenticate = authenticate(user_id);
+        let token = |name: &str, expires_at| {
+            let token = format!("hpat_{}", name);
+            PersonalAccessTokenDTO {
+                token_id: Uuid::new_v4(),
+                user_id,
+                name: name.into(),
+                token_hash: hasher::hash!(token),
+                scopes: vec![Scope::Read],
+                expires_at,
+                last_used_at: None,
+                created_at: Utc::now(),
+            }
+        };
+        let bot = token("bot", Utc::now() + Duration::days(1));
+        authenticate.personal_access_tokens_repository.create(bot.clone()).await.unwrap();
+        authenticate.personal_access_tokens_repository
+            .create(token("expired", Utc::now() - Duration::days(1))).await
+            .unwrap();
+
+        let authenticated_user = authenticate.execute("hpat_bot".into()).await.unwrap();
+        assert_eq!(authenticated_user.personal_access_token_id, Some(bot.token_id));
+        assert!(!authenticated_user.is_session());
+        assert!(!authenticated_user.has_scope(Scope::Write));
+
+        let bot = authenticate.personal_access_tokens_repository
+            .get_by_hash(&bot.token_hash).await
+            .unwrap()
+            .unwrap();
+        assert!(bot.last_used_at.is_some());
+
+        assert_eq!(
+            authenticate.execute("hpat_expired".into()).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_SESSION_ERROR_CODE.into())
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use macros::BArc;
+use rand::RngCore;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{ auth::{ LoginEmailDTO, LoginResultDTO, SessionDTO }, user::{ UserDTO, UserStatus } },
+    entities::{ email::Email, user::User },
+    error_codes::INVALID_CREDENTIALS_ERROR_CODE,
+    features::feature::Feature,
+    policies::{
+        account_deletion::AccountDeletionPolicy,
+        signup::EmailPolicy,
+        two_factor::TwoFactorPolicy,
+    },
+    repositories::{
+        credentials_repository::CredentialsRepository,
+        login_challenges_repository::LoginChallengesRepository,
+        sessions_repository::SessionsRepository,
+        two_factor_repository::TwoFactorRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type LoginWithEmailFeature = dyn Feature<LoginEmailDTO, LoginResultDTO>;
+
+pub struct LoginWithEmail {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub credentials_repository: BArc<dyn CredentialsRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub two_factor_repository: BArc<dyn TwoFactorRepository>,
+    pub login_challenges_repository: BArc<dyn LoginChallengesRepository>,
+    /// Must match the signup one, addresses are looked up the way they were stored.
+    pub email_policy: EmailPolicy,
+    pub two_factor_policy: TwoFactorPolicy,
+    pub account_deletion_policy: AccountDeletionPolicy,
+}
+
+/// 256 bits of randomness, hex encoded. Only its hash is ever stored.
+pub fn generate_session_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[async_trait]
+impl Feature<LoginEmailDTO, LoginResultDTO> for LoginWithEmail {
+    async fn execute(&self, input: LoginEmailDTO) -> Result<LoginResultDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("LOGIN_EMAIL".into(), e));
+        }
+
+        let invalid_credentials = || HearthError::Unauthorized(INVALID_CREDENTIALS_ERROR_CODE.into());
+
+        let email = Email::normalize(&input.email, self.email_policy.plus_addressing);
+        let user = self.users_repository
+            .get_by_email(&email).await?
+            .ok_or_else(invalid_credentials)?;
+
+        let credentials = self.credentials_repository
+            .get(&user.user_id).await?
+            .ok_or_else(invalid_credentials)?;
+
+        if credentials.password_hash != hasher::hash!(input.password) {
+            return Err(invalid_credentials());
+        }
+
+        // Only checked once the password matched, so suspensions aren't disclosed to anyone else.
+        let now = Utc::now();
+        check_can_log_in(&user, &self.account_deletion_policy, now)?;
+
+        let result = open_session(
+            &user.user_id,
+            &self.two_factor_repository,
+            &self.login_challenges_repository,
+            &self.sessions_repository,
+            &self.two_factor_policy
+        ).await?;
+
+        if let LoginResultDTO::Session(_) = result {
+            cancel_pending_deletion(
+                &user,
+                &self.users_repository,
+                &self.account_deletion_policy,
+                now
+            ).await?;
+        }
+
+        Ok(result)
+    }
+}
+
+/// `User::check_active` for a login, a deletion within its grace period doesn't
+/// stand in the way, the session cancels it.
+pub(crate) fn check_can_log_in(
+    user: &UserDTO,
+    account_deletion_policy: &AccountDeletionPolicy,
+    now: DateTime<Utc>
+) -> Result<(), HearthError> {
+    let deactivated = user.status == UserStatus::Deactivated;
+    if deactivated && account_deletion_policy.within_grace_period(user.deactivated_at, now) {
+        return User::check_active(&(UserDTO { status: UserStatus::Active, ..user.clone() }), now);
+    }
+
+    User::check_active(user, now)
+}
+
+/// Logging back in during the grace period cancels the deletion. Only called
+/// once a session is issued, the password alone must not cancel it when the
+/// second factor is still to come.
+pub(crate) async fn cancel_pending_deletion(
+    user: &UserDTO,
+    users_repository: &BArc<dyn UsersRepository>,
+    account_deletion_policy: &AccountDeletionPolicy,
+    now: DateTime<Utc>
+) -> Result<(), HearthError> {
+    if account_deletion_policy.within_grace_period(user.deactivated_at, now) {
+        users_repository.set_deactivated(&user.user_id, None).await?;
+    }
+
+    Ok(())
+}
+
+/// Session of a user whose first factor checked out, or the challenge of the
+/// second step when two-factor authentication is enabled.
+pub(crate) async fn open_session(
+    user_id: &Uuid,
+    two_factor_repository: &BArc<dyn TwoFactorRepository>,
+    login_challenges_repository: &BArc<dyn LoginChallengesRepository>,
+    sessions_repository: &BArc<dyn SessionsRepository>,
+    two_factor_policy: &TwoFactorPolicy
+) -> Result<LoginResultDTO, HearthError> {
+    let two_factor = two_factor_repository.get(user_id).await?;
+    if two_factor.is_some_and(|two_factor| two_factor.enabled_at.is_some()) {
+        let challenge_token = generate_session_token();
+        login_challenges_repository.create(
+            &hasher::hash!(challenge_token),
+            user_id,
+            two_factor_policy.login_challenge_ttl_seconds
+        ).await?;
+
+        return Ok(LoginResultDTO::TwoFactorRequired { challenge_token });
+    }
+
+    let token = generate_session_token();
+    sessions_repository.create(&hasher::hash!(token), user_id).await?;
+
+    Ok(LoginResultDTO::Session(SessionDTO { token, user_id: *user_id }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::{ CredentialsDTO, LoginEmailDTO, LoginResultDTO, SessionDTO },
+            user::CreateUserDTO,
+        },
+        error_codes::INVALID_CREDENTIALS_ERROR_CODE,
+        features::{ auth::login_with_email::LoginWithEmail, feature::Feature },
+        policies::{
+            account_deletion::AccountDeletionPolicy,
+            signup::EmailPolicy,
+            two_factor::TwoFactorPolicy,
+        },
+        repositories::{
+            credentials_repository::CredentialsRepository,
+            sessions_repository::SessionsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryLoginChallengesRepository,
+            InMemorySessionsRepository,
+            InMemoryTwoFactorRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+    const PASSWORD: &str = "qwerty123";
+
+    fn login_with_email() -> (LoginWithEmail, BArc<dyn SessionsRepository>, Uuid) {
+        let user_id = Uuid::new_v4();
+        let users = InMemoryUserRepository::from_existing_user(
+            CreateUserDTO {
+                user_id,
+                username: "john.smith".into(),
+                email: EMAIL.into(),
+                birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+            },
+            CredentialsDTO { user_id, password_hash: hasher::hash!(PASSWORD) }
+        );
+        let users_repository: BArc<dyn UsersRepository> = barc!(users.clone());
+        let credentials_repository: BArc<dyn CredentialsRepository> = barc!(users);
+        let sessions_repository: BArc<dyn SessionsRepository> = barc!(
+            InMemorySessionsRepository::default()
+        );
+
+        let feature = LoginWithEmail {
+            users_repository,
+            credentials_repository,
+            sessions_repository: sessions_repository.clone(),
+            two_factor_repository: barc!(InMemoryTwoFactorRepository::default()),
+            login_challenges_repository: barc!(InMemoryLoginChallengesRepository::default()),
+            email_policy: EmailPolicy::default(),
+            two_factor_policy: TwoFactorPolicy::default(),
+            account_deletion_policy: AccountDeletionPolicy::default(),
+        };
+
+        (feature, sessions_repository, user_id)
+    }
+
+    fn session(result: LoginResultDTO) -> SessionDTO {
+        match result {
+            LoginResultDTO::Session(session) => session,
+            other => panic!("expected a session, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_open_a_session_with_valid_credentials() {
+        let (login, sessions_repository, user_id) = login_with_email();
+
+        let session = session(
+            login
+                .execute(LoginEmailDTO { email: EMAIL.into(), password: PASSWORD.into() }).await
+                .unwrap()
+        );
+
+        assert_eq!(session.user_id, user_id);
+        let other_spelling = LoginEmailDTO {
+            email: "John.Smith+alt@GMAIL.com".into(),
+            password: PASSWORD.into(),
+        };
+        assert!(matches!(login.execute(other_spelling).await, Ok(LoginResultDTO::Session(_))));
+        assert_eq!(session.token.len(), 64);
+        assert_eq!(
+            sessions_repository.get_user_id(&hasher::hash!(session.token)).await.unwrap(),
+            Some(user_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_wrong_password_or_unknown_email() {
+        let (login, _, _) = login_with_email();
+        let expected = HearthError::Unauthorized(INVALID_CREDENTIALS_ERROR_CODE.into());
+
+        let result = login.execute(LoginEmailDTO {
+            email: EMAIL.into(),
+            password: "wrong-password".into(),
+        }).await;
+        assert_eq!(result.unwrap_err(), expected);
+
+        let result = login.execute(LoginEmailDTO {
+            email: "jane@gmail.com".into(),
+            password: PASSWORD.into(),
+        }).await;
+        assert_eq!(result.unwrap_err(), expected);
+    }
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::block::BlockUserDTO,
+    error_codes::CANNOT_BLOCK_SELF_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{ blocks_repository::BlocksRepository, users_repository::UsersRepository },
+};
+
+pub type BlockUserFeature = dyn Feature<BlockUserDTO, ()>;
+
+pub struct BlockUser {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+}
+
+#[async_trait]
+impl Feature<BlockUserDTO, ()> for BlockUser {
+    async fn execute(&self, input: BlockUserDTO) -> Result<(), HearthError> {
+        if input.blocker_id == input.blocked_id {
+            return Err(HearthError::Domain(CANNOT_BLOCK_SELF_ERROR_CODE.into()));
+        }
+
+        // Fails with USER_NOT_FOUND when blocking an unknown user.
+        self.users_repository.get(input.blocked_id.to_string()).await?;
+
+        self.blocks_repository.block(&input.blocker_id, &input.blocked_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{ auth::CredentialsDTO, block::BlockUserDTO, user::CreateUserDTO },
+        error_codes::{ CANNOT_BLOCK_SELF_ERROR_CODE, USER_NOT_FOUND_ERROR_CODE },
+        features::{ blocks::block_user::BlockUser, feature::Feature },
+        repositories::blocks_repository::BlocksRepository,
+        test_utils::test_utils::{ InMemoryBlocksRepository, InMemoryUserRepository },
+    };
+
+    fn block_user(blocked_id: Uuid) -> (BlockUser, BArc<dyn BlocksRepository>) {
+        let blocks_repository: BArc<dyn BlocksRepository> = barc!(
+            InMemoryBlocksRepository::default()
+        );
+        let users_repository = InMemoryUserRepository::from_existing_user(
+            CreateUserDTO {
+                user_id: blocked_id,
+                username: "jane".into(),
+                email: "jane@gmail.com".into(),
+                birthday: chrono::NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
+            },
+            CredentialsDTO { user_id: blocked_id, password_hash: hasher::hash!("qwerty123") }
+        );
+
+        let feature = BlockUser {
+            users_repository: barc!(users_repository),
+            blocks_repository: blocks_repository.clone(),
+        };
+
+        (feature, blocks_repository)
+    }
+
+    #[tokio::test]
+    async fn should_block_in_both_directions() {
+        let blocker_id = Uuid::new_v4();
+        let blocked_id = Uuid::new_v4();
+        let (block_user, blocks_repository) = block_user(blocked_id);
+
+        block_user.execute(BlockUserDTO { blocker_id, blocked_id }).await.unwrap();
+
+        assert!(blocks_repository.is_blocked_between(&blocker_id, &blocked_id).await.unwrap());
+        assert!(blocks_repository.is_blocked_between(&blocked_id, &blocker_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_not_block_self_or_unknown_users() {
+        let user_id = Uuid::new_v4();
+        let (block_user, _) = block_user(user_id);
+
+        assert_eq!(
+            block_user
+                .execute(BlockUserDTO { blocker_id: user_id, blocked_id: user_id }).await
+                .unwrap_err(),
+            HearthError::Domain(CANNOT_BLOCK_SELF_ERROR_CODE.into())
+        );
+        assert_eq!(
+            block_user
+                .execute(BlockUserDTO { blocker_id: user_id, blocked_id: Uuid::new_v4() }).await
+                .unwrap_err(),
+            HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+}
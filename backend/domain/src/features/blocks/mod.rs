@@ -0,0 +1,2 @@
+pub mod block_user;
+pub mod unblock_user;
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::block::BlockUserDTO,
+    features::feature::Feature,
+    repositories::blocks_repository::BlocksRepository,
+};
+
+pub type UnblockUserFeature = dyn Feature<BlockUserDTO, ()>;
+
+pub struct UnblockUser {
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+}
+
+#[async_trait]
+impl Feature<BlockUserDTO, ()> for UnblockUser {
+    async fn execute(&self, input: BlockUserDTO) -> Result<(), HearthError> {
+        self.blocks_repository.unblock(&input.blocker_id, &input.blocked_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::block::BlockUserDTO,
+        features::{ blocks::unblock_user::UnblockUser, feature::Feature },
+        repositories::blocks_repository::BlocksRepository,
+        test_utils::test_utils::InMemoryBlocksRepository,
+    };
+
+    #[tokio::test]
+    async fn should_unblock_only_own_block() {
+        let blocks_repository: BArc<dyn BlocksRepository> = barc!(
+            InMemoryBlocksRepository::default()
+        );
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        blocks_repository.block(&a, &b).await.unwrap();
+        blocks_repository.block(&b, &a).await.unwrap();
+
+        let unblock_user = UnblockUser { blocks_repository: blocks_repository.clone() };
+        unblock_user.execute(BlockUserDTO { blocker_id: a, blocked_id: b }).await.unwrap();
+
+        // b still blocks a.
+        assert!(blocks_repository.is_blocked_between(&a, &b).await.unwrap());
+
+        unblock_user.execute(BlockUserDTO { blocker_id: b, blocked_id: a }).await.unwrap();
+        assert!(!blocks_repository.is_blocked_between(&a, &b).await.unwrap());
+    }
+}
@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::bookmark::{ BookmarkDTO, BookmarkPostDTO },
+    features::feature::Feature,
+    repositories::{
+        bookmarks_repository::BookmarksRepository,
+        posts_repository::PostsRepository,
+    },
+};
+
+pub type BookmarkPostFeature = dyn Feature<BookmarkPostDTO, BookmarkDTO>;
+
+/// Saves a post for later. Bookmarks are private, the author is never told.
+pub struct BookmarkPost {
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub bookmarks_repository: BArc<dyn BookmarksRepository>,
+}
+
+#[async_trait]
+impl Feature<BookmarkPostDTO, BookmarkDTO> for BookmarkPost {
+    async fn execute(&self, input: BookmarkPostDTO) -> Result<BookmarkDTO, HearthError> {
+        let post = self.posts_repository.get(&input.post_id).await?;
+
+        // Someone else's collection is reported as missing.
+        if let Some(collection_id) = &input.collection_id {
+            self.bookmarks_repository.get_collection(&input.owner_id, collection_id).await?;
+        }
+
+        let bookmark = self.bookmarks_repository.save(
+            &input.owner_id,
+            &input.post_id,
+            input.collection_id,
+            Utc::now()
+        ).await?;
+
+        Ok(BookmarkDTO { post: Some(post), ..bookmark })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            bookmark::{ BookmarkCollectionDTO, BookmarkPostDTO },
+            pagination::PageRequest,
+            post::CreatePostDTO,
+        },
+        error_codes::{ BOOKMARK_COLLECTION_NOT_FOUND_ERROR_CODE, POST_NOT_FOUND_ERROR_CODE },
+        features::{
+            bookmarks::bookmark_post::BookmarkPost,
+            feature::Feature,
+            posts::create_post::CreatePost,
+        },
+        repositories::{
+            bookmarks_repository::BookmarksRepository,
+            notifications_repository::NotificationsRepository,
+            posts_repository::PostsRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryBookmarksRepository,
+            InMemoryNotificationsRepository,
+            InMemoryPostsRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_bookmark_posts_privately() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(InMemoryPostsRepository::default());
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let notifications_repository: BArc<dyn NotificationsRepository> = barc!(
+            InMemoryNotificationsRepository::default()
+        );
+        let post = (CreatePost {
+            posts_repository: posts_repository.clone(),
+            ..Default::default()
+        })
+            .execute(CreatePostDTO::default()).await
+            .unwrap();
+        let bookmark_post = BookmarkPost {
+            posts_repository: posts_repository.clone(),
+            bookmarks_repository: bookmarks_repository.clone(),
+        };
+
+        let owner_id = Uuid::new_v4();
+        let bookmark = bookmark_post
+            .execute(BookmarkPostDTO { owner_id, post_id: post.post_id, collection_id: None }).await
+            .unwrap();
+
+        assert_eq!(bookmark.post, Some(post.clone()));
+        assert!(
+            notifications_repository
+                .list(&post.author_id, None, PageRequest::default().limit).await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            bookmarks_repository
+                .list(&post.author_id, None, None, 10).await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_only_bookmark_into_own_collections() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(InMemoryPostsRepository::default());
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let post = (CreatePost { posts_repository: posts_repository.clone(), ..Default::default() })
+            .execute(CreatePostDTO::default()).await
+            .unwrap();
+        let bookmark_post = BookmarkPost {
+            posts_repository: posts_repository.clone(),
+            bookmarks_repository: bookmarks_repository.clone(),
+        };
+
+        let foreign = BookmarkCollectionDTO {
+            collection_id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Theirs".into(),
+            created_at: chrono::Utc::now(),
+        };
+        bookmarks_repository.create_collection(foreign.clone()).await.unwrap();
+
+        let result = bookmark_post.execute(BookmarkPostDTO {
+            owner_id: Uuid::new_v4(),
+            post_id: post.post_id,
+            collection_id: Some(foreign.collection_id),
+        }).await;
+        assert_eq!(
+            result.unwrap_err(),
+            HearthError::not_found(BOOKMARK_COLLECTION_NOT_FOUND_ERROR_CODE.into())
+        );
+
+        let result = bookmark_post.execute(BookmarkPostDTO {
+            owner_id: Uuid::new_v4(),
+            post_id: Uuid::new_v4(),
+            collection_id: None,
+        }).await;
+        assert_eq!(result.unwrap_err(), HearthError::not_found(POST_NOT_FOUND_ERROR_CODE.into()));
+    }
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::bookmark::{ BookmarkCollectionDTO, CreateBookmarkCollectionDTO },
+    features::feature::Feature,
+    repositories::bookmarks_repository::BookmarksRepository,
+};
+
+pub type CreateBookmarkCollectionFeature = dyn Feature<
+    CreateBookmarkCollectionDTO,
+    BookmarkCollectionDTO
+>;
+
+pub struct CreateBookmarkCollection {
+    pub bookmarks_repository: BArc<dyn BookmarksRepository>,
+}
+
+#[async_trait]
+impl Feature<CreateBookmarkCollectionDTO, BookmarkCollectionDTO> for CreateBookmarkCollection {
+    async fn execute(
+        &self,
+        input: CreateBookmarkCollectionDTO
+    ) -> Result<BookmarkCollectionDTO, HearthError> {
+        let input = CreateBookmarkCollectionDTO { name: input.name.trim().into(), ..input };
+
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("CREATE_BOOKMARK_COLLECTION".into(), e));
+        }
+
+        let collection = BookmarkCollectionDTO {
+            collection_id: input.collection_id,
+            owner_id: input.owner_id,
+            name: input.name,
+            created_at: Utc::now(),
+        };
+
+        self.bookmarks_repository.create_collection(collection.clone()).await?;
+
+        Ok(collection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::bookmark::CreateBookmarkCollectionDTO,
+        error_codes::BOOKMARK_COLLECTION_NAME_TAKEN_ERROR_CODE,
+        features::{
+            bookmarks::create_bookmark_collection::CreateBookmarkCollection,
+            feature::Feature,
+        },
+        test_utils::test_utils::InMemoryBookmarksRepository,
+    };
+
+    #[tokio::test]
+    async fn should_create_collections_with_unique_names_per_owner() {
+        let create = CreateBookmarkCollection {
+            bookmarks_repository: barc!(InMemoryBookmarksRepository::default()),
+        };
+        let owner_id = Uuid::new_v4();
+        let dto = |owner_id: Uuid, name: &str| CreateBookmarkCollectionDTO {
+            collection_id: Uuid::new_v4(),
+            owner_id,
+            name: name.into(),
+        };
+
+        let collection = create.execute(dto(owner_id, "  Recipes ")).await.unwrap();
+        assert_eq!(collection.name, "Recipes");
+
+        assert_eq!(
+            create.execute(dto(owner_id, "Recipes")).await.unwrap_err(),
+            HearthError::Domain(BOOKMARK_COLLECTION_NAME_TAKEN_ERROR_CODE.into())
+        );
+        assert!(create.execute(dto(Uuid::new_v4(), "Recipes")).await.is_ok());
+        assert!(matches!(create.execute(dto(owner_id, "   ")).await, Err(HearthError::Validation(..))));
+    }
+}
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::bookmark::BookmarkCollectionDTO,
+    features::feature::Feature,
+    repositories::bookmarks_repository::BookmarksRepository,
+};
+
+pub type ListBookmarkCollectionsFeature = dyn Feature<Uuid, Vec<BookmarkCollectionDTO>>;
+
+pub struct ListBookmarkCollections {
+    pub bookmarks_repository: BArc<dyn BookmarksRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, Vec<BookmarkCollectionDTO>> for ListBookmarkCollections {
+    async fn execute(&self, owner_id: Uuid) -> Result<Vec<BookmarkCollectionDTO>, HearthError> {
+        self.bookmarks_repository.list_collections(&owner_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::bookmark::BookmarkCollectionDTO,
+        features::{
+            bookmarks::list_bookmark_collections::ListBookmarkCollections,
+            feature::Feature,
+        },
+        repositories::bookmarks_repository::BookmarksRepository,
+        test_utils::test_utils::InMemoryBookmarksRepository,
+    };
+
+    #[tokio::test]
+    async fn should_list_only_the_owner_collections() {
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let list_collections = ListBookmarkCollections {
+            bookmarks_repository: bookmarks_repository.clone(),
+        };
+        let (owner_id, other_owner_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let collection = |owner_id: Uuid| BookmarkCollectionDTO {
+            collection_id: Uuid::new_v4(),
+            owner_id,
+            name: "Recipes".into(),
+            created_at: Utc::now(),
+        };
+        let own = collection(owner_id);
+        bookmarks_repository.create_collection(own.clone()).await.unwrap();
+        bookmarks_repository.create_collection(collection(other_owner_id)).await.unwrap();
+
+        assert_eq!(list_collections.execute(owner_id).await.unwrap(), vec![own]);
+        assert!(list_collections.execute(Uuid::new_v4()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        bookmark::{ BookmarkDTO, ListBookmarksDTO },
+        pagination::{ Page, PositionCursor },
+        post::PostDTO,
+    },
+    entities::{ age::Age, posts::Posts },
+    features::feature::Feature,
+    policies::age::AgePolicy,
+    repositories::{
+        bookmarks_repository::BookmarksRepository,
+        polls_repository::PollsRepository,
+        posts_repository::PostsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type ListBookmarksFeature = dyn Feature<ListBookmarksDTO, Page<BookmarkDTO>>;
+
+pub struct ListBookmarks {
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub bookmarks_repository: BArc<dyn BookmarksRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub polls_repository: BArc<dyn PollsRepository>,
+    pub age_policy: AgePolicy,
+}
+
+#[async_trait]
+impl Feature<ListBookmarksDTO, Page<BookmarkDTO>> for ListBookmarks {
+    async fn execute(&self, input: ListBookmarksDTO) -> Result<Page<BookmarkDTO>, HearthError> {
+        if let Err(e) = input.page.validate() {
+            return Err(HearthError::Validation("LIST_BOOKMARKS".into(), e));
+        }
+
+        if let Some(collection_id) = &input.collection_id {
+            self.bookmarks_repository.get_collection(&input.owner_id, collection_id).await?;
+        }
+
+        let cursor = input.page.position_cursor()?;
+        let bookmarks = self.bookmarks_repository.list(
+            &input.owner_id,
+            input.collection_id,
+            cursor,
+            input.page.limit + 1
+        ).await?;
+
+        let post_ids: Vec<Uuid> = bookmarks
+            .iter()
+            .map(|b| b.post_id)
+            .collect();
+        let now = Utc::now();
+        let posts = self.posts_repository.get_many(&post_ids).await?;
+
+        // Bookmarking a post doesn't get around the age gate.
+        let viewer_id = Some(&input.owner_id);
+        let can_view_sensitive =
+            posts.iter().any(|post| Age::is_gated(post, viewer_id)) &&
+            Age::can_view_sensitive(
+                Some(&self.users_repository.get(input.owner_id.to_string()).await?),
+                now.date_naive(),
+                &self.age_policy
+            );
+
+        let mut own_votes: HashMap<Uuid, Vec<u8>> = HashMap::new();
+        for post in posts.iter().filter(|post| post.poll.is_some()) {
+            let votes = self.polls_repository.own_votes(&post.post_id, &input.owner_id).await?;
+            own_votes.insert(post.post_id, votes);
+        }
+
+        let mut posts: HashMap<Uuid, PostDTO> = posts
+            .into_iter()
+            .map(|post| {
+                let is_author = post.author_id == input.owner_id;
+                let votes = own_votes.remove(&post.post_id).unwrap_or_default();
+                let post = PostDTO {
+                    poll: post.poll.map(|poll| Posts::poll_for_viewer(poll, votes, is_author, now)),
+                    ..post
+                };
+                let post = Age::gate_post(post, viewer_id, can_view_sensitive);
+                (post.post_id, post)
+            })
+            .collect();
+
+        // Deleted posts keep their bookmark, shown as a tombstone.
+        let bookmarks = bookmarks
+            .into_iter()
+            .map(|bookmark| BookmarkDTO { post: posts.remove(&bookmark.post_id), ..bookmark })
+            .collect();
+
+        Ok(
+            Page::from_overfetched(bookmarks, input.page.limit, |b| {
+                (PositionCursor { position: b.position, id: b.post_id }).encode()
+            })
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            bookmark::{ ListBookmarksDTO, MoveBookmarkDTO },
+            pagination::PageRequest,
+            poll::CreatePollDTO,
+            post::CreatePostDTO,
+        },
+        features::{
+            bookmarks::{ list_bookmarks::ListBookmarks, move_bookmark::MoveBookmark },
+            feature::Feature,
+            posts::create_post::CreatePost,
+        },
+        policies::age::AgePolicy,
+        repositories::{
+            bookmarks_repository::BookmarksRepository,
+            polls_repository::PollsRepository,
+            posts_repository::PostsRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryBookmarksRepository,
+            InMemoryPollsRepository,
+            InMemoryPostsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    fn list(owner_id: Uuid, cursor: Option<String>, limit: u64) -> ListBookmarksDTO {
+        ListBookmarksDTO { owner_id, collection_id: None, page: PageRequest { cursor, limit } }
+    }
+
+    #[tokio::test]
+    async fn should_list_reordered_bookmarks_with_tombstones() {
+        let posts = InMemoryPostsRepository::default();
+        let posts_repository: BArc<dyn PostsRepository> = barc!(posts.clone());
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let create_post = CreatePost {
+            posts_repository: posts_repository.clone(),
+            ..Default::default()
+        };
+        let list_bookmarks = ListBookmarks {
+            posts_repository: posts_repository.clone(),
+            bookmarks_repository: bookmarks_repository.clone(),
+            users_repository: barc!(InMemoryUserRepository::default()),
+            polls_repository: barc!(InMemoryPollsRepository::default()),
+            age_policy: AgePolicy::default(),
+        };
+
+        let owner_id = Uuid::new_v4();
+        let mut post_ids = vec![];
+        for _ in 0..3 {
+            let post = create_post.execute(CreatePostDTO::default()).await.unwrap();
+            bookmarks_repository.save(&owner_id, &post.post_id, None, Utc::now()).await.unwrap();
+            post_ids.push(post.post_id);
+        }
+
+        // Newest bookmark first, then move the oldest one to the top.
+        (MoveBookmark { bookmarks_repository: bookmarks_repository.clone() })
+            .execute(MoveBookmarkDTO { owner_id, post_id: post_ids[0], position: 0 }).await
+            .unwrap();
+        posts.remove(&post_ids[1]);
+
+        let first = list_bookmarks.execute(list(owner_id, None, 2)).await.unwrap();
+        let second = list_bookmarks.execute(list(owner_id, first.next_cursor.clone(), 2)).await.unwrap();
+
+        let listed: Vec<(Uuid, bool)> = first.items
+            .iter()
+            .chain(second.items.iter())
+            .map(|b| (b.post_id, b.post.is_some()))
+            .collect();
+        assert_eq!(listed, vec![(post_ids[0], true), (post_ids[2], true), (post_ids[1], false)]);
+        assert_eq!(second.next_cursor, None);
+
+        let others = list_bookmarks.execute(list(Uuid::new_v4(), None, 10)).await.unwrap();
+        assert!(others.items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_show_the_owner_their_poll_votes() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(InMemoryPostsRepository::default());
+        let polls_repository: BArc<dyn PollsRepository> = barc!(InMemoryPollsRepository::default());
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let create_post = CreatePost {
+            posts_repository: posts_repository.clone(),
+            polls_repository: polls_repository.clone(),
+            ..Default::default()
+        };
+        let list_bookmarks = ListBookmarks {
+            posts_repository,
+            bookmarks_repository: bookmarks_repository.clone(),
+            users_repository: barc!(InMemoryUserRepository::default()),
+            polls_repository: polls_repository.clone(),
+            age_policy: AgePolicy::default(),
+        };
+
+        let owner_id = Uuid::new_v4();
+        let mut post_ids = vec![];
+        for _ in 0..2 {
+            let post = create_post
+                .execute(CreatePostDTO {
+                    poll: Some(CreatePollDTO {
+                        options: vec!["Yes".into(), "No".into()],
+                        multiple_choice: false,
+                        duration_seconds: 3600,
+                    }),
+                    ..CreatePostDTO::default()
+                }).await
+                .unwrap();
+            bookmarks_repository.save(&owner_id, &post.post_id, None, Utc::now()).await.unwrap();
+            post_ids.push(post.post_id);
+        }
+        polls_repository.vote(&post_ids[0], &owner_id, &[1]).await.unwrap();
+
+        let polls: Vec<(Uuid, Vec<u8>, bool)> = list_bookmarks
+            .execute(list(owner_id, None, 10)).await
+            .unwrap()
+            .items.into_iter()
+            .map(|bookmark| {
+                let poll = bookmark.post.unwrap().poll.unwrap();
+                (bookmark.post_id, poll.own_votes, poll.voters_count.is_some())
+            })
+            .collect();
+        // Tallies stay hidden on the poll they didn't vote in.
+        assert_eq!(polls, vec![(post_ids[1], vec![], false), (post_ids[0], vec![1], true)]);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod bookmark_post;
+pub mod create_bookmark_collection;
+pub mod list_bookmark_collections;
+pub mod list_bookmarks;
+pub mod move_bookmark;
+pub mod remove_bookmark;
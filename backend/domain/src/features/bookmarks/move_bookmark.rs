@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::bookmark::MoveBookmarkDTO,
+    features::feature::Feature,
+    repositories::bookmarks_repository::BookmarksRepository,
+};
+
+pub type MoveBookmarkFeature = dyn Feature<MoveBookmarkDTO, ()>;
+
+pub struct MoveBookmark {
+    pub bookmarks_repository: BArc<dyn BookmarksRepository>,
+}
+
+#[async_trait]
+impl Feature<MoveBookmarkDTO, ()> for MoveBookmark {
+    async fn execute(&self, input: MoveBookmarkDTO) -> Result<(), HearthError> {
+        self.bookmarks_repository.move_to(&input.owner_id, &input.post_id, input.position).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::bookmark::MoveBookmarkDTO,
+        error_codes::BOOKMARK_NOT_FOUND_ERROR_CODE,
+        features::{ bookmarks::move_bookmark::MoveBookmark, feature::Feature },
+        repositories::bookmarks_repository::BookmarksRepository,
+        test_utils::test_utils::InMemoryBookmarksRepository,
+    };
+
+    async fn listed(repository: &BArc<dyn BookmarksRepository>, owner_id: &Uuid) -> Vec<Uuid> {
+        repository
+            .list(owner_id, None, None, 10).await
+            .unwrap()
+            .into_iter()
+            .map(|bookmark| bookmark.post_id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_move_bookmarks_within_their_collection() {
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let move_bookmark = MoveBookmark { bookmarks_repository: bookmarks_repository.clone() };
+        let owner_id = Uuid::new_v4();
+        let [a, b, c] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        for post_id in [a, b, c] {
+            bookmarks_repository.save(&owner_id, &post_id, None, Utc::now()).await.unwrap();
+        }
+        // Newest first.
+        assert_eq!(listed(&bookmarks_repository, &owner_id).await, vec![c, b, a]);
+
+        move_bookmark
+            .execute(MoveBookmarkDTO { owner_id, post_id: c, position: 1 }).await
+            .unwrap();
+        assert_eq!(listed(&bookmarks_repository, &owner_id).await, vec![b, c, a]);
+
+        // Past the end moves it last.
+        move_bookmark
+            .execute(MoveBookmarkDTO { owner_id, post_id: b, position: 10 }).await
+            .unwrap();
+        assert_eq!(listed(&bookmarks_repository, &owner_id).await, vec![c, a, b]);
+    }
+
+    #[tokio::test]
+    async fn should_not_move_bookmarks_of_others() {
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let move_bookmark = MoveBookmark { bookmarks_repository: bookmarks_repository.clone() };
+        let post_id = Uuid::new_v4();
+        bookmarks_repository.save(&Uuid::new_v4(), &post_id, None, Utc::now()).await.unwrap();
+
+        assert_eq!(
+            move_bookmark
+                .execute(MoveBookmarkDTO { owner_id: Uuid::new_v4(), post_id, position: 0 }).await
+                .unwrap_err(),
+            HearthError::not_found(BOOKMARK_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+}
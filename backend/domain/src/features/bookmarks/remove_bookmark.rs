@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::bookmark::RemoveBookmarkDTO,
+    features::feature::Feature,
+    repositories::bookmarks_repository::BookmarksRepository,
+};
+
+pub type RemoveBookmarkFeature = dyn Feature<RemoveBookmarkDTO, ()>;
+
+pub struct RemoveBookmark {
+    pub bookmarks_repository: BArc<dyn BookmarksRepository>,
+}
+
+#[async_trait]
+impl Feature<RemoveBookmarkDTO, ()> for RemoveBookmark {
+    async fn execute(&self, input: RemoveBookmarkDTO) -> Result<(), HearthError> {
+        self.bookmarks_repository.remove(&input.owner_id, &input.post_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::bookmark::RemoveBookmarkDTO,
+        features::{ bookmarks::remove_bookmark::RemoveBookmark, feature::Feature },
+        repositories::bookmarks_repository::BookmarksRepository,
+        test_utils::test_utils::InMemoryBookmarksRepository,
+    };
+
+    #[tokio::test]
+    async fn should_only_remove_the_owner_bookmark() {
+        let bookmarks_repository: BArc<dyn BookmarksRepository> = barc!(
+            InMemoryBookmarksRepository::default()
+        );
+        let remove_bookmark = RemoveBookmark { bookmarks_repository: bookmarks_repository.clone() };
+        let (owner_id, other_owner_id, post_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        bookmarks_repository.save(&owner_id, &post_id, None, Utc::now()).await.unwrap();
+        bookmarks_repository.save(&other_owner_id, &post_id, None, Utc::now()).await.unwrap();
+
+        remove_bookmark.execute(RemoveBookmarkDTO { owner_id, post_id }).await.unwrap();
+        // Removing twice is a no-op.
+        remove_bookmark.execute(RemoveBookmarkDTO { owner_id, post_id }).await.unwrap();
+
+        assert!(bookmarks_repository.list(&owner_id, None, None, 10).await.unwrap().is_empty());
+        assert_eq!(bookmarks_repository.list(&other_owner_id, None, None, 10).await.unwrap().len(), 1);
+    }
+}
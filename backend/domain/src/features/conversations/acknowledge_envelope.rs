@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::conversation::AcknowledgeEnvelopeDTO,
+    error_codes::MESSAGE_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::envelopes_repository::EnvelopesRepository,
+};
+
+pub type AcknowledgeEnvelopeFeature = dyn Feature<AcknowledgeEnvelopeDTO, ()>;
+
+/// Deletes the envelope once the device decrypted it, the server keeps no copy.
+pub struct AcknowledgeEnvelope {
+    pub envelopes_repository: BArc<dyn EnvelopesRepository>,
+}
+
+#[async_trait]
+impl Feature<AcknowledgeEnvelopeDTO, ()> for AcknowledgeEnvelope {
+    async fn execute(&self, input: AcknowledgeEnvelopeDTO) -> Result<(), HearthError> {
+        let deleted = self.envelopes_repository
+            .delete(&input.user_id, &input.device_id, &input.message_id).await?;
+
+        if !deleted {
+            return Err(HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
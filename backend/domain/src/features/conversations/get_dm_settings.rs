@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::conversation::DmSettingsDTO,
+    features::feature::Feature,
+    repositories::dm_settings_repository::DmSettingsRepository,
+};
+
+pub type GetDmSettingsFeature = dyn Feature<Uuid, DmSettingsDTO>;
+
+pub struct GetDmSettings {
+    pub dm_settings_repository: BArc<dyn DmSettingsRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, DmSettingsDTO> for GetDmSettings {
+    async fn execute(&self, user_id: Uuid) -> Result<DmSettingsDTO, HearthError> {
+        Ok(DmSettingsDTO { dm_permission: self.dm_settings_repository.get(&user_id).await? })
+    }
+}
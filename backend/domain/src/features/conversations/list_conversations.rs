@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::conversation::ConversationDTO,
+    features::feature::Feature,
+    repositories::conversations_repository::ConversationsRepository,
+};
+
+pub type ListConversationsFeature = dyn Feature<Uuid, Vec<ConversationDTO>>;
+
+pub struct ListConversations {
+    pub conversations_repository: BArc<dyn ConversationsRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, Vec<ConversationDTO>> for ListConversations {
+    async fn execute(&self, user_id: Uuid) -> Result<Vec<ConversationDTO>, HearthError> {
+        self.conversations_repository.list_for_user(&user_id).await
+    }
+}
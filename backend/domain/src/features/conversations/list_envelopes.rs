@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::{ conversation::EnvelopeDTO, device_keys::DeviceRefDTO },
+    features::feature::Feature,
+    repositories::envelopes_repository::EnvelopesRepository,
+};
+
+/// Devices acknowledge what they've read before fetching the next batch.
+const PENDING_ENVELOPES_LIMIT: u64 = 100;
+
+pub type ListEnvelopesFeature = dyn Feature<DeviceRefDTO, Vec<EnvelopeDTO>>;
+
+pub struct ListEnvelopes {
+    pub envelopes_repository: BArc<dyn EnvelopesRepository>,
+}
+
+#[async_trait]
+impl Feature<DeviceRefDTO, Vec<EnvelopeDTO>> for ListEnvelopes {
+    async fn execute(&self, input: DeviceRefDTO) -> Result<Vec<EnvelopeDTO>, HearthError> {
+        self.envelopes_repository.pending(&input.user_id, &input.device_id, PENDING_ENVELOPES_LIMIT).await
+    }
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        conversation::{ ListMessagesDTO, MessageDTO },
+        pagination::{ Page, TimelineCursor },
+    },
+    entities::conversations::Conversations,
+    features::feature::Feature,
+    repositories::conversations_repository::ConversationsRepository,
+};
+
+pub type ListMessagesFeature = dyn Feature<ListMessagesDTO, Page<MessageDTO>>;
+
+pub struct ListMessages {
+    pub conversations_repository: BArc<dyn ConversationsRepository>,
+}
+
+#[async_trait]
+impl Feature<ListMessagesDTO, Page<MessageDTO>> for ListMessages {
+    async fn execute(&self, input: ListMessagesDTO) -> Result<Page<MessageDTO>, HearthError> {
+        if let Err(e) = input.page.validate() {
+            return Err(HearthError::Validation("LIST_MESSAGES".into(), e));
+        }
+
+        let conversation = self.conversations_repository.get(&input.conversation_id).await?;
+        Conversations::check_participant(&conversation, &input.viewer_id)?;
+
+        let cursor = input.page.timeline_cursor()?;
+        let messages = self.conversations_repository.messages(
+            &input.conversation_id,
+            cursor,
+            input.page.limit + 1
+        ).await?;
+
+        Ok(
+            Page::from_overfetched(messages, input.page.limit, |message| {
+                (TimelineCursor { created_at: message.created_at, id: message.message_id }).encode()
+            })
+        )
+    }
+}
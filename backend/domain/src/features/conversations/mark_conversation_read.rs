@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::conversation::{ ConversationEventDTO, MarkConversationReadDTO, ReadPositionDTO },
+    entities::conversations::Conversations,
+    features::feature::Feature,
+    repositories::{
+        conversations_repository::ConversationsRepository,
+        message_broadcaster::MessageBroadcaster,
+    },
+};
+
+pub type MarkConversationReadFeature = dyn Feature<MarkConversationReadDTO, ()>;
+
+/// Moves the reader's position up to a message and lets the other
+/// participants know, which is what clients render as read receipts.
+pub struct MarkConversationRead {
+    pub conversations_repository: BArc<dyn ConversationsRepository>,
+    pub message_broadcaster: BArc<dyn MessageBroadcaster>,
+}
+
+#[async_trait]
+impl Feature<MarkConversationReadDTO, ()> for MarkConversationRead {
+    async fn execute(&self, input: MarkConversationReadDTO) -> Result<(), HearthError> {
+        let conversation = self.conversations_repository.get(&input.conversation_id).await?;
+        Conversations::check_participant(&conversation, &input.user_id)?;
+
+        let message = self.conversations_repository.get_message(
+            &input.conversation_id,
+            &input.message_id
+        ).await?;
+
+        if !Conversations::advances_read_position(&conversation, &input.user_id, &message) {
+            return Ok(());
+        }
+
+        let position = ReadPositionDTO {
+            user_id: input.user_id,
+            last_read_message_id: message.message_id,
+            last_read_at: message.created_at,
+        };
+        self.conversations_repository.set_read_position(
+            &conversation.conversation_id,
+            position.clone()
+        ).await?;
+
+        self.message_broadcaster.publish(&conversation.participant_ids, ConversationEventDTO::Read {
+            conversation_id: conversation.conversation_id,
+            position,
+        }).await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::conversation::{ ConversationDTO, MarkConversationReadDTO, MessageDTO },
+        error_codes::MESSAGE_NOT_FOUND_ERROR_CODE,
+        features::{ conversations::mark_conversation_read::MarkConversationRead, feature::Feature },
+        repositories::conversations_repository::ConversationsRepository,
+        test_utils::test_utils::{ FakeMessageBroadcaster, InMemoryConversationsRepository },
+    };
+
+    #[tokio::test]
+    async fn should_only_publish_read_receipts_moving_forward() {
+        let conversations_repository: BArc<dyn ConversationsRepository> = barc!(
+            InMemoryConversationsRepository::default()
+        );
+        let message_broadcaster = FakeMessageBroadcaster::default();
+        let mark_read = MarkConversationRead {
+            conversations_repository: conversations_repository.clone(),
+            message_broadcaster: barc!(message_broadcaster.clone()),
+        };
+
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        conversations_repository
+            .create(ConversationDTO {
+                conversation_id,
+                participant_ids: vec![alice, bob],
+                read_positions: vec![],
+                created_at: Utc::now(),
+                last_message_at: None,
+            }).await
+            .unwrap();
+
+        let mut message_ids = vec![];
+        for minutes in [2, 1] {
+            let message = MessageDTO {
+                message_id: Uuid::new_v4(),
+                conversation_id,
+                sender_id: alice,
+                content: "hello".into(),
+                media_ids: vec![],
+                created_at: Utc::now() - Duration::minutes(minutes),
+            };
+            conversations_repository.add_message(message.clone()).await.unwrap();
+            message_ids.push(message.message_id);
+        }
+
+        let dto = |message_id| MarkConversationReadDTO { conversation_id, user_id: bob, message_id };
+
+        mark_read.execute(dto(message_ids[1])).await.unwrap();
+        mark_read.execute(dto(message_ids[0])).await.unwrap();
+
+        let conversation = conversations_repository.get(&conversation_id).await.unwrap();
+        assert_eq!(conversation.read_positions.len(), 1);
+        assert_eq!(conversation.read_positions[0].last_read_message_id, message_ids[1]);
+        assert_eq!(message_broadcaster.events().len(), 1);
+
+        assert_eq!(
+            mark_read.execute(dto(Uuid::new_v4())).await.unwrap_err(),
+            HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+}
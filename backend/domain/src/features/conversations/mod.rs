@@ -0,0 +1,10 @@
+pub mod acknowledge_envelope;
+pub mod get_dm_settings;
+pub mod list_conversations;
+pub mod list_envelopes;
+pub mod list_messages;
+pub mod mark_conversation_read;
+pub mod send_encrypted_message;
+pub mod send_message;
+pub mod start_conversation;
+pub mod update_dm_settings;
@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::conversation::{
+        ConversationEventDTO,
+        EncryptedMessageDTO,
+        EnvelopeDTO,
+        SendEncryptedMessageDTO,
+    },
+    entities::conversations::Conversations,
+    error_codes::{
+        DEVICE_NOT_FOUND_ERROR_CODE,
+        DM_NOT_ALLOWED_ERROR_CODE,
+        EMPTY_MESSAGE_ERROR_CODE,
+        INVALID_ENVELOPES_ERROR_CODE,
+    },
+    features::feature::Feature,
+    policies::conversation::ConversationPolicy,
+    repositories::{
+        blocks_repository::BlocksRepository,
+        conversations_repository::ConversationsRepository,
+        device_keys_repository::DeviceKeysRepository,
+        envelopes_repository::EnvelopesRepository,
+        message_broadcaster::MessageBroadcaster,
+    },
+};
+
+pub type SendEncryptedMessageFeature = dyn Feature<SendEncryptedMessageDTO, EncryptedMessageDTO>;
+
+/// Stores and relays a message encrypted by the sender for every recipient
+/// device. The server only checks who the envelopes are addressed to.
+pub struct SendEncryptedMessage {
+    pub conversations_repository: BArc<dyn ConversationsRepository>,
+    pub envelopes_repository: BArc<dyn EnvelopesRepository>,
+    pub device_keys_repository: BArc<dyn DeviceKeysRepository>,
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+    pub message_broadcaster: BArc<dyn MessageBroadcaster>,
+    pub policy: ConversationPolicy,
+}
+
+#[async_trait]
+impl Feature<SendEncryptedMessageDTO, EncryptedMessageDTO> for SendEncryptedMessage {
+    async fn execute(&self, input: SendEncryptedMessageDTO) -> Result<EncryptedMessageDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("SEND_ENCRYPTED_MESSAGE".into(), e));
+        }
+
+        if input.envelopes.is_empty() {
+            return Err(HearthError::Domain(EMPTY_MESSAGE_ERROR_CODE.into()));
+        }
+
+        if
+            input.envelopes.len() > self.policy.max_envelopes_per_message ||
+            input.envelopes.iter().any(|e| e.ciphertext.len() > self.policy.max_ciphertext_length)
+        {
+            return Err(HearthError::Domain(INVALID_ENVELOPES_ERROR_CODE.into()));
+        }
+
+        let conversation = self.conversations_repository.get(&input.conversation_id).await?;
+        Conversations::check_participant(&conversation, &input.sender_id)?;
+
+        for participant_id in &conversation.participant_ids {
+            if
+                *participant_id != input.sender_id &&
+                self.blocks_repository.is_blocked_between(&input.sender_id, participant_id).await?
+            {
+                return Err(HearthError::Forbidden(DM_NOT_ALLOWED_ERROR_CODE.into()));
+            }
+        }
+
+        if
+            self.device_keys_repository
+                .get(&input.sender_id, &input.sender_device_id).await?
+                .is_none()
+        {
+            return Err(HearthError::not_found(DEVICE_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        // One envelope per device of the participants, never for the sending device itself.
+        for (index, envelope) in input.envelopes.iter().enumerate() {
+            let duplicated = input.envelopes[..index]
+                .iter()
+                .any(|e| e.recipient_device_id == envelope.recipient_device_id);
+
+            if
+                duplicated ||
+                envelope.recipient_device_id == input.sender_device_id ||
+                !conversation.participant_ids.contains(&envelope.recipient_id)
+            {
+                return Err(HearthError::Domain(INVALID_ENVELOPES_ERROR_CODE.into()));
+            }
+        }
+
+        let message = EncryptedMessageDTO {
+            message_id: input.message_id,
+            conversation_id: conversation.conversation_id,
+            sender_id: input.sender_id,
+            sender_device_id: input.sender_device_id,
+            created_at: Utc::now(),
+        };
+
+        let envelopes: Vec<EnvelopeDTO> = input.envelopes
+            .into_iter()
+            .map(|envelope| EnvelopeDTO {
+                message_id: message.message_id,
+                conversation_id: message.conversation_id,
+                sender_id: message.sender_id,
+                sender_device_id: message.sender_device_id,
+                recipient_id: envelope.recipient_id,
+                recipient_device_id: envelope.recipient_device_id,
+                message_type: envelope.message_type,
+                ciphertext: envelope.ciphertext,
+                created_at: message.created_at,
+            })
+            .collect();
+
+        self.envelopes_repository.add(envelopes.clone()).await?;
+
+        for envelope in envelopes {
+            let recipient_id = envelope.recipient_id;
+            self.message_broadcaster.publish(&[recipient_id], ConversationEventDTO::Envelope {
+                envelope,
+            }).await;
+        }
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            conversation::{
+                ConversationDTO,
+                ConversationEventDTO,
+                OutgoingEnvelopeDTO,
+                SendEncryptedMessageDTO,
+            },
+            device_keys::{ DeviceKeysDTO, SignedPrekeyDTO },
+        },
+        error_codes::INVALID_ENVELOPES_ERROR_CODE,
+        features::{ conversations::send_encrypted_message::SendEncryptedMessage, feature::Feature },
+        policies::conversation::ConversationPolicy,
+        repositories::{
+            conversations_repository::ConversationsRepository,
+            device_keys_repository::DeviceKeysRepository,
+            envelopes_repository::EnvelopesRepository,
+        },
+        test_utils::test_utils::{
+            FakeMessageBroadcaster,
+            InMemoryBlocksRepository,
+            InMemoryConversationsRepository,
+            InMemoryDeviceKeysRepository,
+            InMemoryEnvelopesRepository,
+        },
+    };
+
+    fn envelope(recipient_id: Uuid, recipient_device_id: Uuid) -> OutgoingEnvelopeDTO {
+        OutgoingEnvelopeDTO {
+            recipient_id,
+            recipient_device_id,
+            message_type: 0,
+            ciphertext: "b3BhcXVl".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_relay_envelopes_to_participant_devices() {
+        let conversations_repository: BArc<dyn ConversationsRepository> = barc!(
+            InMemoryConversationsRepository::default()
+        );
+        let envelopes_repository: BArc<dyn EnvelopesRepository> = barc!(
+            InMemoryEnvelopesRepository::default()
+        );
+        let device_keys_repository: BArc<dyn DeviceKeysRepository> = barc!(
+            InMemoryDeviceKeysRepository::default()
+        );
+        let message_broadcaster = FakeMessageBroadcaster::default();
+        let send_encrypted_message = SendEncryptedMessage {
+            conversations_repository: conversations_repository.clone(),
+            envelopes_repository: envelopes_repository.clone(),
+            device_keys_repository: device_keys_repository.clone(),
+            blocks_repository: barc!(InMemoryBlocksRepository::default()),
+            message_broadcaster: barc!(message_broadcaster.clone()),
+            policy: ConversationPolicy::default(),
+        };
+
+        let (alice, alice_device) = (Uuid::new_v4(), Uuid::new_v4());
+        let (bob, bob_device) = (Uuid::new_v4(), Uuid::new_v4());
+        let conversation_id = Uuid::new_v4();
+        conversations_repository
+            .create(ConversationDTO {
+                conversation_id,
+                participant_ids: vec![alice, bob],
+                read_positions: vec![],
+                created_at: Utc::now(),
+                last_message_at: None,
+            }).await
+            .unwrap();
+        device_keys_repository
+            .upsert(DeviceKeysDTO {
+                user_id: alice,
+                device_id: alice_device,
+                identity_key: "identity".into(),
+                signing_key: "signing".into(),
+                signed_prekey: SignedPrekeyDTO {
+                    key_id: "spk".into(),
+                    public_key: "signed-prekey".into(),
+                    signature: "signature".into(),
+                },
+                updated_at: Utc::now(),
+            }).await
+            .unwrap();
+
+        let dto = |envelopes| SendEncryptedMessageDTO {
+            message_id: Uuid::new_v4(),
+            conversation_id,
+            sender_id: alice,
+            sender_device_id: alice_device,
+            envelopes,
+        };
+
+        let result = send_encrypted_message.execute(dto(vec![envelope(Uuid::new_v4(), Uuid::new_v4())])).await;
+        assert!(matches!(result, Err(HearthError::Domain(code)) if code == INVALID_ENVELOPES_ERROR_CODE));
+
+        let result = send_encrypted_message.execute(dto(vec![envelope(alice, alice_device)])).await;
+        assert!(matches!(result, Err(HearthError::Domain(code)) if code == INVALID_ENVELOPES_ERROR_CODE));
+
+        let message = send_encrypted_message.execute(dto(vec![envelope(bob, bob_device)])).await.unwrap();
+
+        let pending = envelopes_repository.pending(&bob, &bob_device, 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].message_id, message.message_id);
+        assert_eq!(pending[0].ciphertext, "b3BhcXVl");
+
+        let events = message_broadcaster.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, vec![bob]);
+        assert!(matches!(&events[0].1, ConversationEventDTO::Envelope { envelope } if envelope.recipient_device_id == bob_device));
+    }
+}
@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        conversation::{ ConversationEventDTO, MessageDTO, ReadPositionDTO, SendMessageDTO },
+        media::MediaStatus,
+    },
+    entities::conversations::Conversations,
+    error_codes::{
+        DM_NOT_ALLOWED_ERROR_CODE,
+        EMPTY_MESSAGE_ERROR_CODE,
+        MEDIA_NOT_FOUND_ERROR_CODE,
+        MEDIA_NOT_OWNED_ERROR_CODE,
+        MEDIA_NOT_READY_ERROR_CODE,
+        TOO_MANY_ATTACHMENTS_ERROR_CODE,
+    },
+    features::feature::Feature,
+    policies::conversation::ConversationPolicy,
+    repositories::{
+        blocks_repository::BlocksRepository,
+        conversations_repository::ConversationsRepository,
+        media_repository::MediaRepository,
+        message_broadcaster::MessageBroadcaster,
+    },
+};
+
+pub type SendMessageFeature = dyn Feature<SendMessageDTO, MessageDTO>;
+
+pub struct SendMessage {
+    pub conversations_repository: BArc<dyn ConversationsRepository>,
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub message_broadcaster: BArc<dyn MessageBroadcaster>,
+    pub policy: ConversationPolicy,
+}
+
+impl SendMessage {
+    /// Deduplicates the attachments, all of them must belong to the sender.
+    async fn check_media(&self, sender_id: &Uuid, media_ids: &[Uuid]) -> Result<Vec<Uuid>, HearthError> {
+        let mut unique: Vec<Uuid> = vec![];
+        for media_id in media_ids {
+            if !unique.contains(media_id) {
+                unique.push(*media_id);
+            }
+        }
+
+        if unique.len() > self.policy.max_attachments_per_message {
+            return Err(HearthError::Domain(TOO_MANY_ATTACHMENTS_ERROR_CODE.into()));
+        }
+
+        if unique.is_empty() {
+            return Ok(unique);
+        }
+
+        let found = self.media_repository.get_many(&unique).await?;
+        for media_id in &unique {
+            let media = found
+                .iter()
+                .find(|m| m.media_id == *media_id)
+                .ok_or_else(|| HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()))?;
+
+            if media.owner_id != *sender_id {
+                return Err(HearthError::Forbidden(MEDIA_NOT_OWNED_ERROR_CODE.into()));
+            }
+
+            if media.status != MediaStatus::Ready {
+                return Err(HearthError::Domain(MEDIA_NOT_READY_ERROR_CODE.into()));
+            }
+        }
+
+        Ok(unique)
+    }
+}
+
+#[async_trait]
+impl Feature<SendMessageDTO, MessageDTO> for SendMessage {
+    async fn execute(&self, input: SendMessageDTO) -> Result<MessageDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("SEND_MESSAGE".into(), e));
+        }
+
+        if input.content.trim().is_empty() && input.media_ids.is_empty() {
+            return Err(HearthError::Domain(EMPTY_MESSAGE_ERROR_CODE.into()));
+        }
+
+        let conversation = self.conversations_repository.get(&input.conversation_id).await?;
+        Conversations::check_participant(&conversation, &input.sender_id)?;
+
+        // A block between the sender and anyone in the conversation ends it for the sender.
+        for participant_id in &conversation.participant_ids {
+            if
+                *participant_id != input.sender_id &&
+                self.blocks_repository.is_blocked_between(&input.sender_id, participant_id).await?
+            {
+                return Err(HearthError::Forbidden(DM_NOT_ALLOWED_ERROR_CODE.into()));
+            }
+        }
+
+        let message = MessageDTO {
+            message_id: input.message_id,
+            conversation_id: conversation.conversation_id,
+            sender_id: input.sender_id,
+            media_ids: self.check_media(&input.sender_id, &input.media_ids).await?,
+            content: input.content,
+            created_at: Utc::now(),
+        };
+
+        self.conversations_repository.add_message(message.clone()).await?;
+        self.conversations_repository.set_read_position(&conversation.conversation_id, ReadPositionDTO {
+            user_id: message.sender_id,
+            last_read_message_id: message.message_id,
+            last_read_at: message.created_at,
+        }).await?;
+
+        self.message_broadcaster.publish(&conversation.participant_ids, ConversationEventDTO::Message {
+            message: message.clone(),
+        }).await;
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::conversation::{ ConversationDTO, ConversationEventDTO, SendMessageDTO },
+        error_codes::{
+            CONVERSATION_NOT_FOUND_ERROR_CODE,
+            DM_NOT_ALLOWED_ERROR_CODE,
+            EMPTY_MESSAGE_ERROR_CODE,
+        },
+        features::{ conversations::send_message::SendMessage, feature::Feature },
+        policies::conversation::ConversationPolicy,
+        repositories::{
+            blocks_repository::BlocksRepository,
+            conversations_repository::ConversationsRepository,
+        },
+        test_utils::test_utils::{
+            FakeMessageBroadcaster,
+            InMemoryBlocksRepository,
+            InMemoryConversationsRepository,
+            InMemoryMediaRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_deliver_messages_until_a_block() {
+        let conversations_repository: BArc<dyn ConversationsRepository> = barc!(
+            InMemoryConversationsRepository::default()
+        );
+        let blocks_repository: BArc<dyn BlocksRepository> = barc!(InMemoryBlocksRepository::default());
+        let message_broadcaster = FakeMessageBroadcaster::default();
+        let send_message = SendMessage {
+            conversations_repository: conversations_repository.clone(),
+            blocks_repository: blocks_repository.clone(),
+            media_repository: barc!(InMemoryMediaRepository::default()),
+            message_broadcaster: barc!(message_broadcaster.clone()),
+            policy: ConversationPolicy::default(),
+        };
+
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let conversation = ConversationDTO {
+            conversation_id: Uuid::new_v4(),
+            participant_ids: vec![alice, bob],
+            read_positions: vec![],
+            created_at: Utc::now(),
+            last_message_at: None,
+        };
+        conversations_repository.create(conversation.clone()).await.unwrap();
+
+        let dto = |sender_id: Uuid, content: &str| SendMessageDTO {
+            message_id: Uuid::new_v4(),
+            conversation_id: conversation.conversation_id,
+            sender_id,
+            content: content.into(),
+            media_ids: vec![],
+        };
+
+        let message = send_message.execute(dto(alice, "hey bob")).await.unwrap();
+
+        let events = message_broadcaster.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, vec![alice, bob]);
+        assert_eq!(events[0].1, ConversationEventDTO::Message { message: message.clone() });
+
+        let stored = conversations_repository.get(&conversation.conversation_id).await.unwrap();
+        assert_eq!(stored.last_message_at, Some(message.created_at));
+        assert_eq!(stored.read_positions[0].last_read_message_id, message.message_id);
+
+        assert_eq!(
+            send_message.execute(dto(bob, "  ")).await.unwrap_err(),
+            HearthError::Domain(EMPTY_MESSAGE_ERROR_CODE.into())
+        );
+        assert_eq!(
+            send_message.execute(dto(Uuid::new_v4(), "let me in")).await.unwrap_err(),
+            HearthError::not_found(CONVERSATION_NOT_FOUND_ERROR_CODE.into())
+        );
+
+        blocks_repository.block(&bob, &alice).await.unwrap();
+        for sender_id in [alice, bob] {
+            assert_eq!(
+                send_message.execute(dto(sender_id, "still there?")).await.unwrap_err(),
+                HearthError::Forbidden(DM_NOT_ALLOWED_ERROR_CODE.into())
+            );
+        }
+        assert_eq!(message_broadcaster.events().len(), 1);
+    }
+}
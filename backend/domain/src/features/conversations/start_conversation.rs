@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::conversation::{ ConversationDTO, DmPermission, StartConversationDTO },
+    entities::conversations::Conversations,
+    error_codes::{ DM_NOT_ALLOWED_ERROR_CODE, INVALID_PARTICIPANTS_ERROR_CODE },
+    features::feature::Feature,
+    policies::conversation::ConversationPolicy,
+    repositories::{
+        blocks_repository::BlocksRepository,
+        conversations_repository::ConversationsRepository,
+        dm_settings_repository::DmSettingsRepository,
+        follows_repository::FollowsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type StartConversationFeature = dyn Feature<StartConversationDTO, ConversationDTO>;
+
+pub struct StartConversation {
+    pub conversations_repository: BArc<dyn ConversationsRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+    pub dm_settings_repository: BArc<dyn DmSettingsRepository>,
+    pub follows_repository: BArc<dyn FollowsRepository>,
+    pub policy: ConversationPolicy,
+}
+
+impl StartConversation {
+    /// Blocks and DM settings answer the same way, so the creator can't tell them apart.
+    async fn check_accepts(&self, creator_id: &Uuid, recipient_id: &Uuid) -> Result<(), HearthError> {
+        if self.blocks_repository.is_blocked_between(creator_id, recipient_id).await? {
+            return Err(HearthError::Forbidden(DM_NOT_ALLOWED_ERROR_CODE.into()));
+        }
+
+        let permission = self.dm_settings_repository.get(recipient_id).await?;
+        let follows_creator =
+            permission == DmPermission::Following &&
+            self.follows_repository.is_following(recipient_id, creator_id).await?;
+
+        if !Conversations::accepts(permission, follows_creator) {
+            return Err(HearthError::Forbidden(DM_NOT_ALLOWED_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Feature<StartConversationDTO, ConversationDTO> for StartConversation {
+    async fn execute(&self, input: StartConversationDTO) -> Result<ConversationDTO, HearthError> {
+        let mut participant_ids = vec![input.creator_id];
+        for participant_id in input.participant_ids {
+            if !participant_ids.contains(&participant_id) {
+                participant_ids.push(participant_id);
+            }
+        }
+
+        if participant_ids.len() < 2 || participant_ids.len() > self.policy.max_participants {
+            return Err(HearthError::Domain(INVALID_PARTICIPANTS_ERROR_CODE.into()));
+        }
+
+        for recipient_id in &participant_ids[1..] {
+            self.users_repository.get(recipient_id.to_string()).await?;
+            self.check_accepts(&input.creator_id, recipient_id).await?;
+        }
+
+        if let [creator_id, recipient_id] = participant_ids[..] {
+            let existing = self.conversations_repository.find_direct(&creator_id, &recipient_id).await?;
+            if let Some(existing) = existing {
+                return Ok(existing);
+            }
+        }
+
+        let conversation = ConversationDTO {
+            conversation_id: input.conversation_id,
+            participant_ids,
+            read_positions: vec![],
+            created_at: Utc::now(),
+            last_message_at: None,
+        };
+
+        self.conversations_repository.create(conversation.clone()).await?;
+
+        Ok(conversation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            conversation::{ DmPermission, StartConversationDTO },
+            user::CreateUserDTO,
+        },
+        error_codes::{ DM_NOT_ALLOWED_ERROR_CODE, INVALID_PARTICIPANTS_ERROR_CODE },
+        features::{ conversations::start_conversation::StartConversation, feature::Feature },
+        policies::conversation::ConversationPolicy,
+        repositories::{
+            blocks_repository::BlocksRepository,
+            dm_settings_repository::DmSettingsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryBlocksRepository,
+            InMemoryConversationsRepository,
+            InMemoryDmSettingsRepository,
+            InMemoryFollowsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    struct Fixture {
+        start_conversation: StartConversation,
+        users_repository: BArc<dyn UsersRepository>,
+        blocks_repository: BArc<dyn BlocksRepository>,
+        dm_settings_repository: BArc<dyn DmSettingsRepository>,
+        follows_repository: InMemoryFollowsRepository,
+    }
+
+    fn fixture() -> Fixture {
+        let users_repository: BArc<dyn UsersRepository> = barc!(InMemoryUserRepository::default());
+        let blocks_repository: BArc<dyn BlocksRepository> = barc!(InMemoryBlocksRepository::default());
+        let dm_settings_repository: BArc<dyn DmSettingsRepository> = barc!(
+            InMemoryDmSettingsRepository::default()
+        );
+        let follows_repository = InMemoryFollowsRepository::default();
+
+        Fixture {
+            start_conversation: StartConversation {
+                conversations_repository: barc!(InMemoryConversationsRepository::default()),
+                users_repository: users_repository.clone(),
+                blocks_repository: blocks_repository.clone(),
+                dm_settings_repository: dm_settings_repository.clone(),
+                follows_repository: barc!(follows_repository.clone()),
+                policy: ConversationPolicy { max_participants: 3, ..Default::default() },
+            },
+            users_repository,
+            blocks_repository,
+            dm_settings_repository,
+            follows_repository,
+        }
+    }
+
+    async fn create_user(fixture: &Fixture, username: &str) -> Uuid {
+        let user_id = Uuid::new_v4();
+        fixture.users_repository
+            .create(
+                CreateUserDTO {
+                    user_id,
+                    username: username.into(),
+                    email: format!("{}@gmail.com", username),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: "hash".into() }
+            ).await
+            .unwrap();
+        user_id
+    }
+
+    fn dto(creator_id: Uuid, participant_ids: Vec<Uuid>) -> StartConversationDTO {
+        StartConversationDTO { conversation_id: Uuid::new_v4(), creator_id, participant_ids }
+    }
+
+    #[tokio::test]
+    async fn should_reuse_one_to_one_conversations() {
+        let fixture = fixture();
+        let alice = create_user(&fixture, "alice").await;
+        let bob = create_user(&fixture, "bob").await;
+
+        let first = fixture.start_conversation.execute(dto(alice, vec![bob])).await.unwrap();
+        let second = fixture.start_conversation.execute(dto(bob, vec![alice, bob])).await.unwrap();
+
+        assert_eq!(first.participant_ids, vec![alice, bob]);
+        assert_eq!(first.conversation_id, second.conversation_id);
+
+        let carol = create_user(&fixture, "carol").await;
+        let group = fixture.start_conversation.execute(dto(alice, vec![bob, carol])).await.unwrap();
+        assert_ne!(group.conversation_id, first.conversation_id);
+
+        let dave = create_user(&fixture, "dave").await;
+        assert_eq!(
+            fixture.start_conversation.execute(dto(alice, vec![bob, carol, dave])).await.unwrap_err(),
+            HearthError::Domain(INVALID_PARTICIPANTS_ERROR_CODE.into())
+        );
+        assert_eq!(
+            fixture.start_conversation.execute(dto(alice, vec![alice])).await.unwrap_err(),
+            HearthError::Domain(INVALID_PARTICIPANTS_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_enforce_dm_permissions_and_blocks() {
+        let fixture = fixture();
+        let alice = create_user(&fixture, "alice").await;
+        let bob = create_user(&fixture, "bob").await;
+        let not_allowed = HearthError::Forbidden(DM_NOT_ALLOWED_ERROR_CODE.into());
+
+        fixture.dm_settings_repository.set(&bob, DmPermission::Following).await.unwrap();
+        assert_eq!(
+            fixture.start_conversation.execute(dto(alice, vec![bob])).await.unwrap_err(),
+            not_allowed
+        );
+
+        fixture.follows_repository.follow(&bob, &alice);
+        fixture.start_conversation.execute(dto(alice, vec![bob])).await.unwrap();
+
+        fixture.dm_settings_repository.set(&bob, DmPermission::Nobody).await.unwrap();
+        assert_eq!(
+            fixture.start_conversation.execute(dto(alice, vec![bob])).await.unwrap_err(),
+            not_allowed
+        );
+
+        fixture.dm_settings_repository.set(&bob, DmPermission::Everyone).await.unwrap();
+        fixture.blocks_repository.block(&bob, &alice).await.unwrap();
+        assert_eq!(
+            fixture.start_conversation.execute(dto(alice, vec![bob])).await.unwrap_err(),
+            not_allowed
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::conversation::{ DmSettingsDTO, UpdateDmSettingsDTO },
+    features::feature::Feature,
+    repositories::dm_settings_repository::DmSettingsRepository,
+};
+
+pub type UpdateDmSettingsFeature = dyn Feature<UpdateDmSettingsDTO, DmSettingsDTO>;
+
+/// Only applies to new conversations, existing ones carry on.
+pub struct UpdateDmSettings {
+    pub dm_settings_repository: BArc<dyn DmSettingsRepository>,
+}
+
+#[async_trait]
+impl Feature<UpdateDmSettingsDTO, DmSettingsDTO> for UpdateDmSettings {
+    async fn execute(&self, input: UpdateDmSettingsDTO) -> Result<DmSettingsDTO, HearthError> {
+        self.dm_settings_repository.set(&input.user_id, input.dm_permission).await?;
+
+        Ok(DmSettingsDTO { dm_permission: input.dm_permission })
+    }
+}
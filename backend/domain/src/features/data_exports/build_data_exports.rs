@@ -0,0 +1,246 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Duration, Utc };
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::{
+        data_export::{ ArchiveFileDTO, DataExportDTO },
+        job::JobPayload,
+        media::{ MediaDTO, MediaStatus, ORIGINAL_VARIANT },
+    },
+    entities::{ data_export::DataExport, jobs::Jobs },
+    features::{ auth::login_with_email::generate_session_token, feature::Feature },
+    policies::data_export::DataExportPolicy,
+    repositories::{
+        archive_writer::ArchiveWriter,
+        data_exports_repository::DataExportsRepository,
+        job_queue::JobQueue,
+        object_store::ObjectStore,
+        personal_data_repository::PersonalDataRepository,
+    },
+};
+
+const BATCH_SIZE: u64 = 10;
+
+/// Assembles the pending exports and deletes the expired ones as of the given
+/// instant, returns the number of exports handled. Meant to be run periodically.
+pub type BuildDataExportsFeature = dyn Feature<DateTime<Utc>, u64>;
+
+pub struct BuildDataExports {
+    pub data_exports_repository: BArc<dyn DataExportsRepository>,
+    pub personal_data_repository: BArc<dyn PersonalDataRepository>,
+    /// Where the uploaded media are read from.
+    pub object_store: BArc<dyn ObjectStore>,
+    /// Where the archives are written to.
+    pub export_store: BArc<dyn ObjectStore>,
+    pub archive_writer: BArc<dyn ArchiveWriter>,
+    /// The download link is emailed by the worker.
+    pub job_queue: BArc<dyn JobQueue>,
+    pub policy: DataExportPolicy,
+}
+
+impl BuildDataExports {
+    async fn build(&self, export: &DataExportDTO, at: DateTime<Utc>) -> Result<(), HearthError> {
+        let data = self.personal_data_repository.collect(&export.user_id).await?;
+
+        let json = |path: &str, value: serde_json::Result<Vec<u8>>| {
+            value
+                .map(|bytes| ArchiveFileDTO { path: path.into(), bytes })
+                .map_err(|e| {
+                    HearthError::unexpected("DATA_EXPORT_JSON_ERROR".into(), Some(e.to_string()))
+                })
+        };
+        let mut files = vec![
+            json("profile.json", serde_json::to_vec_pretty(&data.profile))?,
+            json("posts.json", serde_json::to_vec_pretty(&data.posts))?,
+            json("follows.json", serde_json::to_vec_pretty(&data.follows))?,
+            json("bookmarks.json", serde_json::to_vec_pretty(&data.bookmarks))?,
+            json("messages.json", serde_json::to_vec_pretty(&data.messages))?,
+            json("media.json", serde_json::to_vec_pretty(&data.media))?
+        ];
+
+        // Only the uploaded files, the resized variants can be made again from them.
+        for media in data.media.iter().filter(|media| media.status == MediaStatus::Ready) {
+            let key = MediaDTO::storage_key(&media.media_id, ORIGINAL_VARIANT);
+            let bytes = self.object_store.get(&key).await?;
+            files.push(ArchiveFileDTO { path: key, bytes });
+        }
+
+        let archive = self.archive_writer.write(files).await?;
+        self.export_store.put(
+            &DataExport::archive_key(&export.export_id),
+            archive,
+            "application/zip"
+        ).await?;
+
+        let token = generate_session_token();
+        self.data_exports_repository.mark_ready(
+            &export.export_id,
+            &hasher::hash!(token),
+            at + Duration::days(self.policy.ttl_days as i64)
+        ).await?;
+
+        let link = DataExport::download_url(&self.policy.download_base_url, &export.export_id, &token);
+        self.job_queue.enqueue(
+            Jobs::schedule(JobPayload::SendDataExportLink { email: data.profile.email, link }, at)
+        ).await
+    }
+}
+
+#[async_trait]
+impl Feature<DateTime<Utc>, u64> for BuildDataExports {
+    async fn execute(&self, at: DateTime<Utc>) -> Result<u64, HearthError> {
+        let expired = self.data_exports_repository.list_expired(at, BATCH_SIZE).await?;
+        for export in &expired {
+            self.export_store.delete(&DataExport::archive_key(&export.export_id)).await?;
+            self.data_exports_repository.delete(&export.export_id).await?;
+        }
+
+        let pending = self.data_exports_repository.list_pending(BATCH_SIZE).await?;
+        for export in &pending {
+            self.build(export, at).await?;
+        }
+
+        Ok((expired.len() + pending.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{ Duration, NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            data_export::{
+                DataExportStatus,
+                DownloadDataExportDTO,
+                ExportedFollowsDTO,
+                ExportedProfileDTO,
+                PersonalDataDTO,
+            },
+            job::JobPayload,
+            media::{ MediaDTO, MediaStatus, MediaVariantDTO, ORIGINAL_VARIANT },
+        },
+        error_codes::DATA_EXPORT_NOT_FOUND_ERROR_CODE,
+        features::{
+            data_exports::{
+                build_data_exports::BuildDataExports,
+                download_data_export::DownloadDataExport,
+                request_data_export::RequestDataExport,
+            },
+            feature::Feature,
+        },
+        policies::data_export::DataExportPolicy,
+        repositories::object_store::ObjectStore,
+        test_utils::test_utils::{
+            FakeArchiveWriter,
+            InMemoryDataExportsRepository,
+            InMemoryJobQueue,
+            InMemoryObjectStore,
+            InMemoryPersonalDataRepository,
+        },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+
+    #[tokio::test]
+    async fn should_build_email_and_expire_exports() {
+        let user_id = Uuid::new_v4();
+        let media_id = Uuid::new_v4();
+        let personal_data_repository = InMemoryPersonalDataRepository::default();
+        personal_data_repository.data.lock().unwrap().insert(user_id, PersonalDataDTO {
+            profile: ExportedProfileDTO {
+                user_id,
+                username: "john.smith".into(),
+                email: EMAIL.into(),
+                birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                avatar_media_id: None,
+                created_at: Utc::now(),
+            },
+            posts: vec![],
+            follows: ExportedFollowsDTO { following: vec![Uuid::new_v4()], followers: vec![] },
+            bookmarks: vec![],
+            messages: vec![],
+            media: vec![MediaDTO {
+                media_id,
+                owner_id: user_id,
+                mime_type: "image/jpeg".into(),
+                status: MediaStatus::Ready,
+                blurhash: "".into(),
+                variants: vec![MediaVariantDTO {
+                    name: ORIGINAL_VARIANT.into(),
+                    mime_type: "image/jpeg".into(),
+                    width: 1,
+                    height: 1,
+                    size_bytes: 3,
+                }],
+                created_at: Utc::now(),
+            }],
+        });
+        let data_exports_repository = InMemoryDataExportsRepository::default();
+        let job_queue = InMemoryJobQueue::default();
+        let object_store = InMemoryObjectStore::default();
+        let export_store = InMemoryObjectStore::default();
+        let media_key = MediaDTO::storage_key(&media_id, ORIGINAL_VARIANT);
+        object_store.put(&media_key, vec![1, 2, 3], "image/jpeg").await.unwrap();
+
+        let request = RequestDataExport {
+            data_exports_repository: barc!(data_exports_repository.clone()),
+        };
+        let build = BuildDataExports {
+            data_exports_repository: barc!(data_exports_repository.clone()),
+            personal_data_repository: barc!(personal_data_repository),
+            object_store: barc!(object_store),
+            export_store: barc!(export_store.clone()),
+            archive_writer: barc!(FakeArchiveWriter),
+            job_queue: barc!(job_queue.clone()),
+            policy: DataExportPolicy::default(),
+        };
+        let download = DownloadDataExport {
+            data_exports_repository: barc!(data_exports_repository),
+            export_store: barc!(export_store),
+        };
+
+        let export = request.execute(user_id).await.unwrap();
+        assert_eq!(export.status, DataExportStatus::Pending);
+        assert_eq!(request.execute(user_id).await.unwrap().export_id, export.export_id);
+
+        let now = Utc::now();
+        assert_eq!(build.execute(now).await.unwrap(), 1);
+        assert_eq!(build.execute(now).await.unwrap(), 0);
+
+        let payload = job_queue.jobs.lock().unwrap()[0].payload.clone();
+        let JobPayload::SendDataExportLink { email, link } = payload else {
+            panic!("expected the download link to be emailed");
+        };
+        assert_eq!(email, EMAIL);
+        let token = link.split("token=").nth(1).unwrap().to_string();
+
+        let archive = download
+            .execute(DownloadDataExportDTO { export_id: export.export_id, token }).await
+            .unwrap();
+        let files: HashMap<String, Vec<u8>> = serde_json::from_slice(&archive).unwrap();
+        assert_eq!(files[&media_key], vec![1, 2, 3]);
+        for path in ["profile.json", "posts.json", "follows.json", "bookmarks.json", "messages.json"] {
+            assert!(files.contains_key(path), "{} is missing", path);
+        }
+
+        let wrong_token = download.execute(DownloadDataExportDTO {
+            export_id: export.export_id,
+            token: "wrong".into(),
+        }).await;
+        assert_eq!(
+            wrong_token.unwrap_err(),
+            HearthError::not_found(DATA_EXPORT_NOT_FOUND_ERROR_CODE.into())
+        );
+
+        assert_eq!(build.execute(now + Duration::days(4)).await.unwrap(), 1);
+        assert!(build.data_exports_repository.get(&export.export_id).await.is_err());
+    }
+}
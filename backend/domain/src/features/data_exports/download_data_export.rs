@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::data_export::{ DataExportStatus, DownloadDataExportDTO },
+    entities::data_export::DataExport,
+    error_codes::DATA_EXPORT_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{
+        data_exports_repository::DataExportsRepository,
+        object_store::ObjectStore,
+    },
+};
+
+/// Bytes of the ZIP archive.
+pub type DownloadDataExportFeature = dyn Feature<DownloadDataExportDTO, Vec<u8>>;
+
+pub struct DownloadDataExport {
+    pub data_exports_repository: BArc<dyn DataExportsRepository>,
+    pub export_store: BArc<dyn ObjectStore>,
+}
+
+#[async_trait]
+impl Feature<DownloadDataExportDTO, Vec<u8>> for DownloadDataExport {
+    async fn execute(&self, input: DownloadDataExportDTO) -> Result<Vec<u8>, HearthError> {
+        let not_found = || HearthError::not_found(DATA_EXPORT_NOT_FOUND_ERROR_CODE.into());
+
+        let export = self.data_exports_repository.get(&input.export_id).await?;
+        let downloadable =
+            export.status == DataExportStatus::Ready &&
+            export.token_hash.is_some_and(|hash| hash == hasher::hash!(input.token)) &&
+            export.expires_at.is_some_and(|at| at > Utc::now());
+        if !downloadable {
+            return Err(not_found());
+        }
+
+        self.export_store.get(&DataExport::archive_key(&export.export_id)).await
+    }
+}
@@ -0,0 +1,3 @@
+pub mod build_data_exports;
+pub mod download_data_export;
+pub mod request_data_export;
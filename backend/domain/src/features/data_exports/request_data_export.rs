@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::data_export::{ DataExportDTO, DataExportStatus },
+    features::feature::Feature,
+    repositories::data_exports_repository::DataExportsRepository,
+};
+
+/// Takes the user id, `BuildDataExports` assembles the archive in the
+/// background and emails the download link once done.
+pub type RequestDataExportFeature = dyn Feature<Uuid, DataExportDTO>;
+
+pub struct RequestDataExport {
+    pub data_exports_repository: BArc<dyn DataExportsRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, DataExportDTO> for RequestDataExport {
+    async fn execute(&self, user_id: Uuid) -> Result<DataExportDTO, HearthError> {
+        let now = Utc::now();
+
+        // Asking again before the previous one expired gives it back instead of piling up jobs.
+        if let Some(export) = self.data_exports_repository.find_active(&user_id, now).await? {
+            return Ok(export);
+        }
+
+        let export = DataExportDTO {
+            export_id: Uuid::new_v4(),
+            user_id,
+            status: DataExportStatus::Pending,
+            token_hash: None,
+            expires_at: None,
+            created_at: now,
+        };
+        self.data_exports_repository.create(export.clone()).await?;
+
+        Ok(export)
+    }
+}
@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::device_keys::{ ClaimPrekeysDTO, PrekeyBundleDTO },
+    error_codes::DM_NOT_ALLOWED_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{
+        blocks_repository::BlocksRepository,
+        device_keys_repository::DeviceKeysRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type ClaimPrekeysFeature = dyn Feature<ClaimPrekeysDTO, Vec<PrekeyBundleDTO>>;
+
+/// Hands out one prekey bundle per device of the user, each claim consumes
+/// a one-time prekey of every device.
+pub struct ClaimPrekeys {
+    pub device_keys_repository: BArc<dyn DeviceKeysRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+}
+
+#[async_trait]
+impl Feature<ClaimPrekeysDTO, Vec<PrekeyBundleDTO>> for ClaimPrekeys {
+    async fn execute(&self, input: ClaimPrekeysDTO) -> Result<Vec<PrekeyBundleDTO>, HearthError> {
+        self.users_repository.get(input.user_id.to_string()).await?;
+
+        if
+            input.claimer_id != input.user_id &&
+            self.blocks_repository.is_blocked_between(&input.claimer_id, &input.user_id).await?
+        {
+            return Err(HearthError::Forbidden(DM_NOT_ALLOWED_ERROR_CODE.into()));
+        }
+
+        let mut bundles = vec![];
+        for device in self.device_keys_repository.list_for_user(&input.user_id).await? {
+            let one_time_prekey = self.device_keys_repository
+                .claim_one_time_prekey(&input.user_id, &device.device_id).await?;
+
+            bundles.push(PrekeyBundleDTO {
+                device_id: device.device_id,
+                identity_key: device.identity_key,
+                signing_key: device.signing_key,
+                signed_prekey: device.signed_prekey,
+                one_time_prekey,
+            });
+        }
+
+        Ok(bundles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            device_keys::{ ClaimPrekeysDTO, DeviceKeysDTO, OneTimePrekeyDTO, SignedPrekeyDTO },
+            user::CreateUserDTO,
+        },
+        error_codes::DM_NOT_ALLOWED_ERROR_CODE,
+        features::{ device_keys::claim_prekeys::ClaimPrekeys, feature::Feature },
+        repositories::{
+            blocks_repository::BlocksRepository,
+            device_keys_repository::DeviceKeysRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryBlocksRepository,
+            InMemoryDeviceKeysRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_hand_out_each_one_time_prekey_once() {
+        let device_keys_repository: BArc<dyn DeviceKeysRepository> = barc!(
+            InMemoryDeviceKeysRepository::default()
+        );
+        let users_repository: BArc<dyn UsersRepository> = barc!(InMemoryUserRepository::default());
+        let blocks_repository: BArc<dyn BlocksRepository> = barc!(InMemoryBlocksRepository::default());
+        let claim_prekeys = ClaimPrekeys {
+            device_keys_repository: device_keys_repository.clone(),
+            users_repository: users_repository.clone(),
+            blocks_repository: blocks_repository.clone(),
+        };
+
+        let bob = Uuid::new_v4();
+        users_repository
+            .create(
+                CreateUserDTO {
+                    user_id: bob,
+                    username: "bob".into(),
+                    email: "bob@gmail.com".into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id: bob, password_hash: "hash".into() }
+            ).await
+            .unwrap();
+
+        let device_id = Uuid::new_v4();
+        device_keys_repository
+            .upsert(DeviceKeysDTO {
+                user_id: bob,
+                device_id,
+                identity_key: "identity".into(),
+                signing_key: "signing".into(),
+                signed_prekey: SignedPrekeyDTO {
+                    key_id: "spk".into(),
+                    public_key: "signed-prekey".into(),
+                    signature: "signature".into(),
+                },
+                updated_at: Utc::now(),
+            }).await
+            .unwrap();
+        device_keys_repository
+            .add_one_time_prekeys(&bob, &device_id, vec![OneTimePrekeyDTO {
+                key_id: "otk".into(),
+                public_key: "one-time-prekey".into(),
+            }]).await
+            .unwrap();
+
+        let alice = Uuid::new_v4();
+        let claim = ClaimPrekeysDTO { claimer_id: alice, user_id: bob };
+
+        let bundles = claim_prekeys.execute(claim.clone()).await.unwrap();
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].device_id, device_id);
+        assert_eq!(bundles[0].one_time_prekey.as_ref().unwrap().key_id, "otk");
+
+        // Exhausted devices fall back to the signed prekey.
+        let bundles = claim_prekeys.execute(claim.clone()).await.unwrap();
+        assert_eq!(bundles[0].signed_prekey.key_id, "spk");
+        assert!(bundles[0].one_time_prekey.is_none());
+
+        blocks_repository.block(&bob, &alice).await.unwrap();
+        let result = claim_prekeys.execute(claim).await;
+        assert!(matches!(result, Err(HearthError::Forbidden(code)) if code == DM_NOT_ALLOWED_ERROR_CODE));
+    }
+}
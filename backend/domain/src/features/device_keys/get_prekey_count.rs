@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::device_keys::{ DeviceRefDTO, PrekeyCountDTO },
+    error_codes::DEVICE_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::device_keys_repository::DeviceKeysRepository,
+};
+
+pub type GetPrekeyCountFeature = dyn Feature<DeviceRefDTO, PrekeyCountDTO>;
+
+/// Lets a device know when to upload more one-time prekeys.
+pub struct GetPrekeyCount {
+    pub device_keys_repository: BArc<dyn DeviceKeysRepository>,
+}
+
+#[async_trait]
+impl Feature<DeviceRefDTO, PrekeyCountDTO> for GetPrekeyCount {
+    async fn execute(&self, input: DeviceRefDTO) -> Result<PrekeyCountDTO, HearthError> {
+        if self.device_keys_repository.get(&input.user_id, &input.device_id).await?.is_none() {
+            return Err(HearthError::not_found(DEVICE_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(PrekeyCountDTO {
+            one_time_prekeys: self.device_keys_repository
+                .count_one_time_prekeys(&input.user_id, &input.device_id).await?,
+        })
+    }
+}
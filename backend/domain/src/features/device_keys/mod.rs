@@ -0,0 +1,4 @@
+pub mod claim_prekeys;
+pub mod get_prekey_count;
+pub mod upload_device_keys;
+pub mod upload_prekeys;
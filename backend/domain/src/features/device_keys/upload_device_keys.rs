@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::device_keys::{ DeviceKeysDTO, UploadDeviceKeysDTO },
+    entities::device_keys::DeviceKeys,
+    error_codes::TOO_MANY_DEVICES_ERROR_CODE,
+    features::feature::Feature,
+    policies::device_keys::DeviceKeysPolicy,
+    repositories::device_keys_repository::DeviceKeysRepository,
+};
+
+pub type UploadDeviceKeysFeature = dyn Feature<UploadDeviceKeysDTO, ()>;
+
+pub struct UploadDeviceKeys {
+    pub device_keys_repository: BArc<dyn DeviceKeysRepository>,
+    pub policy: DeviceKeysPolicy,
+}
+
+#[async_trait]
+impl Feature<UploadDeviceKeysDTO, ()> for UploadDeviceKeys {
+    async fn execute(&self, input: UploadDeviceKeysDTO) -> Result<(), HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("UPLOAD_DEVICE_KEYS".into(), e));
+        }
+
+        let existing = self.device_keys_repository.get(&input.user_id, &input.device_id).await?;
+        let unclaimed = match existing {
+            Some(_) => {
+                self.device_keys_repository.count_one_time_prekeys(&input.user_id, &input.device_id).await?
+            }
+            None => {
+                let devices = self.device_keys_repository.list_for_user(&input.user_id).await?;
+                if devices.len() >= self.policy.max_devices_per_user {
+                    return Err(HearthError::Domain(TOO_MANY_DEVICES_ERROR_CODE.into()));
+                }
+                0
+            }
+        };
+
+        let prekeys = DeviceKeys::check_prekeys(
+            input.one_time_prekeys,
+            unclaimed,
+            self.policy.max_one_time_prekeys
+        )?;
+
+        self.device_keys_repository.upsert(DeviceKeysDTO {
+            user_id: input.user_id,
+            device_id: input.device_id,
+            identity_key: input.identity_key,
+            signing_key: input.signing_key,
+            signed_prekey: input.signed_prekey,
+            updated_at: Utc::now(),
+        }).await?;
+
+        if !prekeys.is_empty() {
+            self.device_keys_repository.add_one_time_prekeys(&input.user_id, &input.device_id, prekeys).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::device_keys::{ OneTimePrekeyDTO, SignedPrekeyDTO, UploadDeviceKeysDTO },
+        error_codes::{ TOO_MANY_DEVICES_ERROR_CODE, TOO_MANY_PREKEYS_ERROR_CODE },
+        features::{ device_keys::upload_device_keys::UploadDeviceKeys, feature::Feature },
+        policies::device_keys::DeviceKeysPolicy,
+        repositories::device_keys_repository::DeviceKeysRepository,
+        test_utils::test_utils::InMemoryDeviceKeysRepository,
+    };
+
+    fn prekeys(ids: &[&str]) -> Vec<OneTimePrekeyDTO> {
+        ids.iter()
+            .map(|id| OneTimePrekeyDTO { key_id: (*id).into(), public_key: format!("otk-{}", id) })
+            .collect()
+    }
+
+    fn dto(user_id: Uuid, device_id: Uuid, one_time_prekeys: Vec<OneTimePrekeyDTO>) -> UploadDeviceKeysDTO {
+        UploadDeviceKeysDTO {
+            user_id,
+            device_id,
+            identity_key: "identity".into(),
+            signing_key: "signing".into(),
+            signed_prekey: SignedPrekeyDTO {
+                key_id: "spk".into(),
+                public_key: "signed-prekey".into(),
+                signature: "signature".into(),
+            },
+            one_time_prekeys,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_limit_devices_and_unclaimed_prekeys() {
+        let repository = InMemoryDeviceKeysRepository::default();
+        let upload_device_keys = UploadDeviceKeys {
+            device_keys_repository: barc!(repository.clone()),
+            policy: DeviceKeysPolicy { max_devices_per_user: 1, max_one_time_prekeys: 3 },
+        };
+        let user_id = Uuid::new_v4();
+        let device_id = Uuid::new_v4();
+
+        upload_device_keys.execute(dto(user_id, device_id, prekeys(&["a", "b", "a"]))).await.unwrap();
+        assert_eq!(repository.count_one_time_prekeys(&user_id, &device_id).await.unwrap(), 2);
+
+        // Re-uploading rotates the keys of the same device.
+        upload_device_keys.execute(dto(user_id, device_id, prekeys(&["c"]))).await.unwrap();
+        assert_eq!(repository.count_one_time_prekeys(&user_id, &device_id).await.unwrap(), 3);
+
+        let result = upload_device_keys.execute(dto(user_id, device_id, prekeys(&["d"]))).await;
+        assert!(matches!(result, Err(HearthError::Domain(code)) if code == TOO_MANY_PREKEYS_ERROR_CODE));
+
+        let result = upload_device_keys.execute(dto(user_id, Uuid::new_v4(), vec![])).await;
+        assert!(matches!(result, Err(HearthError::Domain(code)) if code == TOO_MANY_DEVICES_ERROR_CODE));
+    }
+}
@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::device_keys::{ PrekeyCountDTO, UploadPrekeysDTO },
+    entities::device_keys::DeviceKeys,
+    error_codes::DEVICE_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    policies::device_keys::DeviceKeysPolicy,
+    repositories::device_keys_repository::DeviceKeysRepository,
+};
+
+pub type UploadPrekeysFeature = dyn Feature<UploadPrekeysDTO, PrekeyCountDTO>;
+
+/// Tops up the one-time prekeys of a registered device.
+pub struct UploadPrekeys {
+    pub device_keys_repository: BArc<dyn DeviceKeysRepository>,
+    pub policy: DeviceKeysPolicy,
+}
+
+#[async_trait]
+impl Feature<UploadPrekeysDTO, PrekeyCountDTO> for UploadPrekeys {
+    async fn execute(&self, input: UploadPrekeysDTO) -> Result<PrekeyCountDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("UPLOAD_PREKEYS".into(), e));
+        }
+
+        if self.device_keys_repository.get(&input.user_id, &input.device_id).await?.is_none() {
+            return Err(HearthError::not_found(DEVICE_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        let unclaimed = self.device_keys_repository
+            .count_one_time_prekeys(&input.user_id, &input.device_id).await?;
+        let prekeys = DeviceKeys::check_prekeys(
+            input.one_time_prekeys,
+            unclaimed,
+            self.policy.max_one_time_prekeys
+        )?;
+
+        if !prekeys.is_empty() {
+            self.device_keys_repository.add_one_time_prekeys(&input.user_id, &input.device_id, prekeys).await?;
+        }
+
+        Ok(PrekeyCountDTO {
+            one_time_prekeys: self.device_keys_repository
+                .count_one_time_prekeys(&input.user_id, &input.device_id).await?,
+        })
+    }
+}
@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Duration, Utc };
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::event::EventDTO,
+    features::feature::Feature,
+    repositories::{ event_publisher::EventPublisher, outbox_repository::OutboxRepository },
+};
+
+const BATCH_SIZE: u64 = 50;
+const LEASE_MINUTES: i64 = 1;
+
+/// In-process reaction to an event. Events are delivered at least once, so
+/// subscribers have to tolerate seeing one again.
+pub type EventSubscriberFeature = dyn Feature<EventDTO, ()>;
+
+/// Hands the outbox events to the subscribers then publishes them, returns
+/// the number of events claimed. An event stays in the outbox until all of
+/// that succeeded. Meant to be run by the worker.
+pub type DispatchEventsFeature = dyn Feature<DateTime<Utc>, u64>;
+
+pub struct DispatchEvents {
+    pub outbox_repository: BArc<dyn OutboxRepository>,
+    pub event_publisher: BArc<dyn EventPublisher>,
+    pub subscribers: Vec<Box<EventSubscriberFeature>>,
+}
+
+impl DispatchEvents {
+    async fn dispatch(&self, event: &EventDTO, at: DateTime<Utc>) -> Result<(), HearthError> {
+        for subscriber in &self.subscribers {
+            subscriber.execute(event.clone()).await?;
+        }
+
+        self.event_publisher.publish(event).await?;
+        self.outbox_repository.mark_dispatched(&event.event_id, at).await
+    }
+}
+
+#[async_trait]
+impl Feature<DateTime<Utc>, u64> for DispatchEvents {
+    async fn execute(&self, at: DateTime<Utc>) -> Result<u64, HearthError> {
+        let events = self.outbox_repository.claim(
+            at,
+            at + Duration::minutes(LEASE_MINUTES),
+            BATCH_SIZE
+        ).await?;
+
+        // One failing event must not hold back the others, it is retried
+        // once its lease is over.
+        let mut failure = None;
+        for event in &events {
+            if let Err(e) = self.dispatch(event, at).await {
+                failure.get_or_insert(e);
+            }
+        }
+
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(events.len() as u64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            event::{ DomainEvent, EventDTO },
+            job::JobPayload,
+            user::CreateUserDTO,
+        },
+        features::{
+            events::dispatch_events::DispatchEvents,
+            feature::Feature,
+            users::welcome_registered_user::WelcomeRegisteredUser,
+        },
+        repositories::users_repository::UsersRepository,
+        test_utils::test_utils::{
+            InMemoryEventPublisher,
+            InMemoryJobQueue,
+            InMemoryOutboxRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+    const USERNAME: &str = "john.smith";
+
+    async fn register(users_repository: &BArc<dyn UsersRepository>) -> EventDTO {
+        let user_id = Uuid::new_v4();
+        let registered = EventDTO::new(
+            DomainEvent::UserRegistered { user_id, username: USERNAME.into() },
+            Utc::now()
+        );
+        users_repository
+            .create_with_events(
+                CreateUserDTO {
+                    user_id,
+                    username: USERNAME.into(),
+                    email: EMAIL.into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") },
+                vec![registered.clone()]
+            ).await
+            .unwrap();
+        registered
+    }
+
+    #[tokio::test]
+    async fn should_welcome_and_publish_registered_users_once() {
+        let outbox = InMemoryOutboxRepository::default();
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::with_outbox(outbox.clone())
+        );
+        let job_queue = InMemoryJobQueue::default();
+        let event_publisher = InMemoryEventPublisher::default();
+        let dispatch_events = DispatchEvents {
+            outbox_repository: barc!(outbox.clone()),
+            event_publisher: barc!(event_publisher.clone()),
+            subscribers: vec![
+                Box::new(WelcomeRegisteredUser {
+                    users_repository: users_repository.clone(),
+                    job_queue: barc!(job_queue.clone()),
+                })
+            ],
+        };
+
+        let registered = register(&users_repository).await;
+        let now = Utc::now();
+
+        assert_eq!(dispatch_events.execute(now).await.unwrap(), 1);
+        assert_eq!(dispatch_events.execute(now + Duration::hours(1)).await.unwrap(), 0);
+
+        assert!(outbox.is_dispatched(&registered.event_id));
+        assert_eq!(event_publisher.published.lock().unwrap().clone(), vec![registered]);
+        let jobs = job_queue.jobs.lock().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].payload, JobPayload::SendWelcomeEmail {
+            email: EMAIL.into(),
+            username: USERNAME.into(),
+        });
+    }
+
+    #[tokio::test]
+    async fn should_redeliver_events_whose_dispatch_failed() {
+        let outbox = InMemoryOutboxRepository::default();
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::with_outbox(outbox.clone())
+        );
+        let job_queue = InMemoryJobQueue::default();
+        let event_publisher = InMemoryEventPublisher::default();
+        let dispatch_events = DispatchEvents {
+            outbox_repository: barc!(outbox.clone()),
+            event_publisher: barc!(event_publisher.clone()),
+            subscribers: vec![
+                Box::new(WelcomeRegisteredUser {
+                    users_repository: users_repository.clone(),
+                    job_queue: barc!(job_queue.clone()),
+                })
+            ],
+        };
+
+        let registered = register(&users_repository).await;
+        let now = Utc::now();
+
+        *event_publisher.down.lock().unwrap() = true;
+        assert!(dispatch_events.execute(now).await.is_err());
+        assert!(!outbox.is_dispatched(&registered.event_id));
+        assert_eq!(dispatch_events.execute(now).await.unwrap(), 0);
+
+        *event_publisher.down.lock().unwrap() = false;
+        assert_eq!(dispatch_events.execute(now + Duration::minutes(1)).await.unwrap(), 1);
+        assert!(outbox.is_dispatched(&registered.event_id));
+
+        // The welcome subscriber ran twice, the email is still sent once.
+        assert_eq!(job_queue.jobs.lock().unwrap().len(), 1);
+    }
+}
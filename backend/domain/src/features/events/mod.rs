@@ -0,0 +1 @@
+pub mod dispatch_events;
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::filter::{ CreateKeywordFilterDTO, KeywordFilterDTO },
+    error_codes::{ INVALID_FILTER_EXPIRY_ERROR_CODE, TOO_MANY_FILTERS_ERROR_CODE },
+    features::feature::Feature,
+    policies::filter::FilterPolicy,
+    repositories::keyword_filters_repository::KeywordFiltersRepository,
+};
+
+pub type CreateKeywordFilterFeature = dyn Feature<CreateKeywordFilterDTO, KeywordFilterDTO>;
+
+pub struct CreateKeywordFilter {
+    pub keyword_filters_repository: BArc<dyn KeywordFiltersRepository>,
+    pub policy: FilterPolicy,
+}
+
+#[async_trait]
+impl Feature<CreateKeywordFilterDTO, KeywordFilterDTO> for CreateKeywordFilter {
+    async fn execute(&self, input: CreateKeywordFilterDTO) -> Result<KeywordFilterDTO, HearthError> {
+        let input = CreateKeywordFilterDTO { phrase: input.phrase.trim().into(), ..input };
+
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("CREATE_KEYWORD_FILTER".into(), e));
+        }
+
+        let now = Utc::now();
+        if input.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(HearthError::Domain(INVALID_FILTER_EXPIRY_ERROR_CODE.into()));
+        }
+
+        let owned = self.keyword_filters_repository.list(&input.owner_id).await?;
+        if owned.len() >= self.policy.max_filters_per_user {
+            return Err(HearthError::Domain(TOO_MANY_FILTERS_ERROR_CODE.into()));
+        }
+
+        let mut contexts = input.contexts;
+        contexts.sort_by_key(|context| context.as_str());
+        contexts.dedup();
+
+        let filter = KeywordFilterDTO {
+            filter_id: input.filter_id,
+            owner_id: input.owner_id,
+            phrase: input.phrase,
+            whole_word: input.whole_word,
+            contexts,
+            expires_at: input.expires_at,
+            created_at: now,
+        };
+
+        self.keyword_filters_repository.create(filter.clone()).await?;
+
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::filter::{ CreateKeywordFilterDTO, FilterContext },
+        error_codes::{ INVALID_FILTER_EXPIRY_ERROR_CODE, TOO_MANY_FILTERS_ERROR_CODE },
+        features::{ feature::Feature, filters::create_keyword_filter::CreateKeywordFilter },
+        policies::filter::FilterPolicy,
+        repositories::keyword_filters_repository::KeywordFiltersRepository,
+        test_utils::test_utils::InMemoryKeywordFiltersRepository,
+    };
+
+    #[tokio::test]
+    async fn should_create_filters_up_to_the_limit() {
+        let keyword_filters_repository: BArc<dyn KeywordFiltersRepository> = barc!(
+            InMemoryKeywordFiltersRepository::default()
+        );
+        let create_keyword_filter = CreateKeywordFilter {
+            keyword_filters_repository: keyword_filters_repository.clone(),
+            policy: FilterPolicy { max_filters_per_user: 1 },
+        };
+        let owner_id = Uuid::new_v4();
+
+        let dto = |expires_at| CreateKeywordFilterDTO {
+            filter_id: Uuid::new_v4(),
+            owner_id,
+            phrase: "  spoiler ".into(),
+            whole_word: true,
+            contexts: vec![FilterContext::Search, FilterContext::Home, FilterContext::Home],
+            expires_at,
+        };
+
+        assert_eq!(
+            create_keyword_filter.execute(dto(Some(Utc::now() - Duration::hours(1)))).await.unwrap_err(),
+            HearthError::Domain(INVALID_FILTER_EXPIRY_ERROR_CODE.into())
+        );
+
+        let filter = create_keyword_filter.execute(dto(None)).await.unwrap();
+        assert_eq!(filter.phrase, "spoiler");
+        assert_eq!(filter.contexts, vec![FilterContext::Home, FilterContext::Search]);
+        assert_eq!(keyword_filters_repository.list(&owner_id).await.unwrap(), vec![filter]);
+
+        assert_eq!(
+            create_keyword_filter.execute(dto(None)).await.unwrap_err(),
+            HearthError::Domain(TOO_MANY_FILTERS_ERROR_CODE.into())
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::filter::DeleteKeywordFilterDTO,
+    features::feature::Feature,
+    repositories::keyword_filters_repository::KeywordFiltersRepository,
+};
+
+pub type DeleteKeywordFilterFeature = dyn Feature<DeleteKeywordFilterDTO, ()>;
+
+pub struct DeleteKeywordFilter {
+    pub keyword_filters_repository: BArc<dyn KeywordFiltersRepository>,
+}
+
+#[async_trait]
+impl Feature<DeleteKeywordFilterDTO, ()> for DeleteKeywordFilter {
+    async fn execute(&self, input: DeleteKeywordFilterDTO) -> Result<(), HearthError> {
+        self.keyword_filters_repository.delete(&input.owner_id, &input.filter_id).await
+    }
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::filter::KeywordFilterDTO,
+    features::feature::Feature,
+    repositories::keyword_filters_repository::KeywordFiltersRepository,
+};
+
+pub type ListKeywordFiltersFeature = dyn Feature<Uuid, Vec<KeywordFilterDTO>>;
+
+/// Expired filters are listed too, so they can be renewed or deleted.
+pub struct ListKeywordFilters {
+    pub keyword_filters_repository: BArc<dyn KeywordFiltersRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, Vec<KeywordFilterDTO>> for ListKeywordFilters {
+    async fn execute(&self, owner_id: Uuid) -> Result<Vec<KeywordFilterDTO>, HearthError> {
+        self.keyword_filters_repository.list(&owner_id).await
+    }
+}
@@ -0,0 +1,3 @@
+pub mod create_keyword_filter;
+pub mod delete_keyword_filter;
+pub mod list_keyword_filters;
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::import::{ GetImportDTO, ImportDTO },
+    error_codes::IMPORT_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::imports_repository::ImportsRepository,
+};
+
+/// Status of one of the user's imports, with its report once done.
+pub type GetImportFeature = dyn Feature<GetImportDTO, ImportDTO>;
+
+pub struct GetImport {
+    pub imports_repository: BArc<dyn ImportsRepository>,
+}
+
+#[async_trait]
+impl Feature<GetImportDTO, ImportDTO> for GetImport {
+    async fn execute(&self, input: GetImportDTO) -> Result<ImportDTO, HearthError> {
+        let import = self.imports_repository.get(&input.import_id).await?;
+
+        // Someone else's import doesn't exist as far as the caller knows.
+        if import.user_id != input.user_id {
+            return Err(HearthError::not_found(IMPORT_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(import)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::import::{ GetImportDTO, ImportDTO },
+        error_codes::IMPORT_NOT_FOUND_ERROR_CODE,
+        features::{ feature::Feature, imports::get_import::GetImport },
+        repositories::imports_repository::ImportsRepository,
+        test_utils::test_utils::InMemoryImportsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_only_show_imports_to_their_owner() {
+        let imports_repository = InMemoryImportsRepository::default();
+        let get_import = GetImport { imports_repository: barc!(imports_repository.clone()) };
+        let import = ImportDTO {
+            import_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            ..Default::default()
+        };
+        imports_repository.create(import.clone()).await.unwrap();
+
+        let owned = GetImportDTO { user_id: import.user_id, import_id: import.import_id };
+        assert_eq!(get_import.execute(owned).await.unwrap(), import);
+
+        let other = GetImportDTO { user_id: Uuid::new_v4(), import_id: import.import_id };
+        assert_eq!(
+            get_import.execute(other).await.unwrap_err(),
+            HearthError::not_found(IMPORT_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+}
@@ -0,0 +1,433 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        import::{ ImportDTO, ImportReportDTO, ImportStatus, ImportedPostDTO, SkippedPostDTO },
+        media::{ MediaDTO, UploadMediaDTO },
+        post::PostDTO,
+    },
+    entities::import::Import,
+    error_codes::{ IMPORTED_POST_EMPTY_ERROR_CODE, IMPORTED_POST_TOO_LONG_ERROR_CODE },
+    features::{ feature::Feature, media::upload_media::UploadMedia },
+    parsers::hashtags::unique_hashtags,
+    policies::{ import::ImportPolicy, media::MediaPolicy },
+    repositories::{
+        archive_reader::ArchiveReader,
+        imported_posts_repository::ImportedPostsRepository,
+        imports_repository::ImportsRepository,
+        job_queue::JobQueue,
+        media_processor::MediaProcessor,
+        media_repository::MediaRepository,
+        object_store::ObjectStore,
+        posts_repository::PostsRepository,
+    },
+};
+
+/// Takes the id of a pending import and records its report, run by the worker
+/// once `StartImport` stored the archive. An archive that can't be read fails
+/// the import rather than the job.
+pub type ImportArchiveFeature = dyn Feature<Uuid, ()>;
+
+/// Recreates the posts of an archive as backdated posts of the importing user.
+/// Mentions point to accounts of the other platform so they stay plain text,
+/// and nobody gets notified nor do hashtags trend for posts of the past.
+pub struct ImportArchive {
+    pub archive_reader: BArc<dyn ArchiveReader>,
+    pub imports_repository: BArc<dyn ImportsRepository>,
+    pub imported_posts_repository: BArc<dyn ImportedPostsRepository>,
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub object_store: BArc<dyn ObjectStore>,
+    pub media_processor: BArc<dyn MediaProcessor>,
+    pub job_queue: BArc<dyn JobQueue>,
+    pub media_policy: MediaPolicy,
+    pub policy: ImportPolicy,
+}
+
+impl ImportArchive {
+    /// Mirrors the checks of `UploadMedia` that don't need to process the file.
+    fn is_importable_media(&self, bytes: &[u8]) -> bool {
+        (bytes.len() as u64) <= self.media_policy.max_upload_bytes &&
+            self.media_processor
+                .sniff_mime_type(bytes)
+                .is_some_and(|mime_type| self.media_policy.is_allowed(&mime_type))
+    }
+
+    /// Attachments that can't be stored are left out, the post is imported anyway.
+    /// Stored ones are processed by the worker like any other upload.
+    async fn import_media(
+        &self,
+        owner_id: &Uuid,
+        files: Vec<Vec<u8>>,
+        dry_run: bool,
+        report: &mut ImportReportDTO
+    ) -> Result<Vec<MediaDTO>, HearthError> {
+        let upload_media = UploadMedia {
+            media_repository: self.media_repository.clone(),
+            object_store: self.object_store.clone(),
+            media_processor: self.media_processor.clone(),
+            job_queue: self.job_queue.clone(),
+            policy: self.media_policy.clone(),
+        };
+        let mut media = vec![];
+        let mut kept = 0;
+
+        for bytes in files {
+            if kept == self.media_policy.max_attachments_per_post {
+                report.media_skipped += 1;
+                continue;
+            }
+
+            if dry_run {
+                if self.is_importable_media(&bytes) {
+                    kept += 1;
+                    report.media_imported += 1;
+                } else {
+                    report.media_skipped += 1;
+                }
+                continue;
+            }
+
+            let upload = UploadMediaDTO { media_id: Uuid::new_v4(), owner_id: *owner_id, bytes };
+            match upload_media.execute(upload).await {
+                Ok(item) => {
+                    kept += 1;
+                    report.media_imported += 1;
+                    media.push(item);
+                }
+                Err(HearthError::Domain(_)) => {
+                    report.media_skipped += 1;
+                }
+                Err(e) => {
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(media)
+    }
+
+    async fn import(&self, input: &ImportDTO, bytes: Vec<u8>) -> Result<ImportReportDTO, HearthError> {
+        let mut archived = self.archive_reader.read(input.source, bytes).await?;
+        archived.sort_by_key(|post| post.created_at);
+
+        let external_ids: Vec<String> = archived
+            .iter()
+            .map(|post| post.external_id.clone())
+            .collect();
+        let mut seen: HashSet<String> = self.imported_posts_repository
+            .find_imported(&input.user_id, input.source, &external_ids).await?
+            .into_iter()
+            .collect();
+
+        let mut report = ImportReportDTO {
+            dry_run: input.dry_run,
+            posts_found: archived.len() as u64,
+            ..Default::default()
+        };
+
+        for post in archived {
+            if !seen.insert(post.external_id.clone()) {
+                report.posts_already_imported += 1;
+                continue;
+            }
+
+            let content = post.content.trim().to_string();
+            let skipped = if content.is_empty() {
+                Some(IMPORTED_POST_EMPTY_ERROR_CODE)
+            } else if content.chars().count() > self.policy.max_content_chars {
+                Some(IMPORTED_POST_TOO_LONG_ERROR_CODE)
+            } else {
+                None
+            };
+            if let Some(reason) = skipped {
+                report.posts_skipped.push(SkippedPostDTO {
+                    external_id: post.external_id,
+                    reason: reason.into(),
+                });
+                continue;
+            }
+
+            let media = self.import_media(
+                &input.user_id,
+                post.media,
+                input.dry_run,
+                &mut report
+            ).await?;
+
+            report.posts_imported += 1;
+            report.oldest_post_at = report.oldest_post_at.or(Some(post.created_at));
+            report.newest_post_at = Some(post.created_at);
+
+            if input.dry_run {
+                continue;
+            }
+
+            let post_id = Uuid::new_v4();
+            self.posts_repository.create(PostDTO {
+                post_id,
+                author_id: input.user_id,
+                hashtags: unique_hashtags(&content),
+                mentions: vec![],
+                media,
+                poll: None,
+                link_preview: None,
+                filtered: vec![],
+                sensitive: post.sensitive,
+                age_gated: false,
+                content,
+                created_at: post.created_at,
+            }).await?;
+
+            self.imported_posts_repository.create(ImportedPostDTO {
+                user_id: input.user_id,
+                source: input.source,
+                external_id: post.external_id,
+                post_id,
+            }).await?;
+        }
+
+        Ok(report)
+    }
+}
+
+#[async_trait]
+impl Feature<Uuid, ()> for ImportArchive {
+    async fn execute(&self, import_id: Uuid) -> Result<(), HearthError> {
+        let import = self.imports_repository.get(&import_id).await?;
+        // Only a job retried after the import was recorded gets there.
+        if import.status != ImportStatus::Pending {
+            return Ok(());
+        }
+
+        let key = Import::archive_key(&import_id);
+        let bytes = self.object_store.get(&key).await?;
+
+        match self.import(&import, bytes).await {
+            Ok(report) => self.imports_repository.mark_done(&import_id, &report).await?,
+            Err(HearthError::Domain(code)) => {
+                self.imports_repository.mark_failed(&import_id, &code).await?
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        }
+
+        self.object_store.delete(&key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ TimeZone, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::import::{ ArchivedPostDTO, ImportDTO, ImportReportDTO, ImportSource, ImportStatus },
+        entities::import::Import,
+        error_codes::{
+            IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE,
+            IMPORTED_POST_EMPTY_ERROR_CODE,
+            IMPORTED_POST_TOO_LONG_ERROR_CODE,
+            MEDIA_NOT_FOUND_ERROR_CODE,
+        },
+        features::{ feature::Feature, imports::import_archive::ImportArchive },
+        policies::{ import::ImportPolicy, media::MediaPolicy },
+        repositories::{ media_repository::MediaRepository, posts_repository::PostsRepository },
+        test_utils::test_utils::{
+            FAKE_JPEG,
+            FakeArchiveReader,
+            FakeMediaProcessor,
+            InMemoryImportedPostsRepository,
+            InMemoryImportsRepository,
+            InMemoryJobQueue,
+            InMemoryMediaRepository,
+            InMemoryObjectStore,
+            InMemoryPostsRepository,
+        },
+    };
+
+    fn archived(external_id: &str, content: &str, media: Vec<Vec<u8>>) -> ArchivedPostDTO {
+        ArchivedPostDTO {
+            external_id: external_id.into(),
+            content: content.into(),
+            sensitive: false,
+            media,
+            created_at: Utc.with_ymd_and_hms(2019, 3, 14, 9, 26, 53).unwrap(),
+        }
+    }
+
+    fn import_archive(posts: Vec<ArchivedPostDTO>) -> ImportArchive {
+        ImportArchive {
+            archive_reader: barc!(FakeArchiveReader { posts }),
+            imports_repository: barc!(InMemoryImportsRepository::default()),
+            imported_posts_repository: barc!(InMemoryImportedPostsRepository::default()),
+            posts_repository: barc!(InMemoryPostsRepository::default()),
+            media_repository: barc!(InMemoryMediaRepository::default()),
+            object_store: barc!(InMemoryObjectStore::default()),
+            media_processor: barc!(FakeMediaProcessor),
+            job_queue: barc!(InMemoryJobQueue::default()),
+            media_policy: MediaPolicy::default(),
+            policy: ImportPolicy::default(),
+        }
+    }
+
+    /// Stores the archive the way `StartImport` does, returns the import id.
+    async fn upload(
+        import_archive: &ImportArchive,
+        user_id: Uuid,
+        dry_run: bool,
+        bytes: &[u8]
+    ) -> Uuid {
+        let import_id = Uuid::new_v4();
+        import_archive.object_store
+            .put(&Import::archive_key(&import_id), bytes.to_vec(), "application/zip").await
+            .unwrap();
+        import_archive.imports_repository
+            .create(ImportDTO {
+                import_id,
+                user_id,
+                source: ImportSource::Twitter,
+                dry_run,
+                created_at: Utc::now(),
+                ..Default::default()
+            }).await
+            .unwrap();
+        import_id
+    }
+
+    async fn run(import_archive: &ImportArchive, user_id: Uuid, dry_run: bool) -> ImportReportDTO {
+        let import_id = upload(import_archive, user_id, dry_run, b"PK").await;
+        import_archive.execute(import_id).await.unwrap();
+
+        let import = import_archive.imports_repository.get(&import_id).await.unwrap();
+        assert_eq!(import.status, ImportStatus::Done);
+        import.report.unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_recreate_posts_backdated_with_their_media() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(
+            InMemoryPostsRepository::default()
+        );
+        let import_archive = ImportArchive {
+            posts_repository: posts_repository.clone(),
+            ..import_archive(
+                vec![archived("1", "Moving to #Hearth", vec![FAKE_JPEG.to_vec(), b"mp4".to_vec()])]
+            )
+        };
+        let user_id = Uuid::new_v4();
+
+        let report = run(&import_archive, user_id, false).await;
+
+        assert_eq!(report.posts_imported, 1);
+        assert_eq!(report.media_imported, 1);
+        assert_eq!(report.media_skipped, 1);
+
+        let posts = posts_repository.list_by_authors(&[user_id], None, 10).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].created_at, Utc.with_ymd_and_hms(2019, 3, 14, 9, 26, 53).unwrap());
+        assert_eq!(posts[0].hashtags, vec!["hearth"]);
+        assert_eq!(posts[0].media.len(), 1);
+        assert_eq!(posts[0].media[0].owner_id, user_id);
+    }
+
+    #[tokio::test]
+    async fn should_not_duplicate_posts_when_run_again() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(
+            InMemoryPostsRepository::default()
+        );
+        let import_archive = ImportArchive {
+            posts_repository: posts_repository.clone(),
+            ..import_archive(vec![archived("1", "First", vec![]), archived("2", "Second", vec![])])
+        };
+        let user_id = Uuid::new_v4();
+
+        run(&import_archive, user_id, false).await;
+        let report = run(&import_archive, user_id, false).await;
+
+        assert_eq!(report.posts_found, 2);
+        assert_eq!(report.posts_imported, 0);
+        assert_eq!(report.posts_already_imported, 2);
+        let posts = posts_repository.list_by_authors(&[user_id], None, 10).await.unwrap();
+        assert_eq!(posts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_only_report_on_a_dry_run() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(
+            InMemoryPostsRepository::default()
+        );
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let import_archive = ImportArchive {
+            posts_repository: posts_repository.clone(),
+            media_repository: media_repository.clone(),
+            ..import_archive(
+                vec![
+                    archived("1", "With a picture", vec![FAKE_JPEG.to_vec()]),
+                    archived("2", "   ", vec![]),
+                    archived("3", &"a".repeat(501), vec![])
+                ]
+            )
+        };
+        let user_id = Uuid::new_v4();
+
+        let report = run(&import_archive, user_id, true).await;
+
+        assert!(report.dry_run);
+        assert_eq!(report.posts_imported, 1);
+        assert_eq!(report.media_imported, 1);
+        assert_eq!(
+            report.posts_skipped
+                .iter()
+                .map(|skipped| skipped.reason.as_str())
+                .collect::<Vec<_>>(),
+            vec![IMPORTED_POST_EMPTY_ERROR_CODE, IMPORTED_POST_TOO_LONG_ERROR_CODE]
+        );
+        assert!(posts_repository.list_by_authors(&[user_id], None, 10).await.unwrap().is_empty());
+        assert!(media_repository.list_by_owner(&user_id).await.unwrap().is_empty());
+
+        // Nothing was recorded, the real run imports everything.
+        let report = run(&import_archive, user_id, false).await;
+        assert_eq!(report.posts_imported, 1);
+    }
+
+    #[tokio::test]
+    async fn should_delete_the_archive_once_imported() {
+        let import_archive = import_archive(vec![archived("1", "First", vec![])]);
+        let import_id = upload(&import_archive, Uuid::new_v4(), false, b"PK").await;
+
+        import_archive.execute(import_id).await.unwrap();
+        // A retried job leaves the recorded import alone.
+        import_archive.execute(import_id).await.unwrap();
+
+        assert_eq!(
+            import_archive.object_store.get(&Import::archive_key(&import_id)).await.unwrap_err(),
+            HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into())
+        );
+        let import = import_archive.imports_repository.get(&import_id).await.unwrap();
+        assert_eq!(import.report.unwrap().posts_imported, 1);
+    }
+
+    #[tokio::test]
+    async fn should_fail_the_import_of_an_unreadable_archive() {
+        let import_archive = import_archive(vec![archived("1", "First", vec![])]);
+        let import_id = upload(&import_archive, Uuid::new_v4(), false, b"").await;
+
+        import_archive.execute(import_id).await.unwrap();
+
+        let import = import_archive.imports_repository.get(&import_id).await.unwrap();
+        assert_eq!(import.status, ImportStatus::Failed);
+        assert_eq!(import.error.as_deref(), Some(IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE));
+        assert!(import.report.is_none());
+    }
+}
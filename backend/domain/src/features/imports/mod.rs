@@ -0,0 +1,3 @@
+pub mod get_import;
+pub mod import_archive;
+pub mod start_import;
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        import::{ ImportDTO, ImportStatus, StartImportDTO },
+        job::JobPayload,
+    },
+    entities::{ import::Import, jobs::Jobs },
+    error_codes::IMPORT_ARCHIVE_TOO_LARGE_ERROR_CODE,
+    features::feature::Feature,
+    policies::import::ImportPolicy,
+    repositories::{
+        imports_repository::ImportsRepository,
+        job_queue::JobQueue,
+        object_store::{ ByteStream, ObjectStore },
+    },
+};
+
+/// Stores the uploaded archive and queues its import, the report is read
+/// back with `GetImport` once the worker is done.
+pub type StartImportFeature = dyn Feature<StartImportDTO, ImportDTO>;
+
+pub struct StartImport {
+    pub imports_repository: BArc<dyn ImportsRepository>,
+    pub object_store: BArc<dyn ObjectStore>,
+    pub job_queue: BArc<dyn JobQueue>,
+    pub policy: ImportPolicy,
+}
+
+/// Fails the upload as soon as it grows past the limit.
+struct LimitedStream {
+    inner: Box<dyn ByteStream>,
+    remaining: u64,
+}
+
+#[async_trait]
+impl ByteStream for LimitedStream {
+    async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, HearthError>> {
+        let chunk = self.inner.next_chunk().await?;
+
+        Some(
+            chunk.and_then(|chunk| {
+                self.remaining = self.remaining
+                    .checked_sub(chunk.len() as u64)
+                    .ok_or_else(|| HearthError::Domain(IMPORT_ARCHIVE_TOO_LARGE_ERROR_CODE.into()))?;
+                Ok(chunk)
+            })
+        )
+    }
+}
+
+#[async_trait]
+impl Feature<StartImportDTO, ImportDTO> for StartImport {
+    async fn execute(&self, input: StartImportDTO) -> Result<ImportDTO, HearthError> {
+        let now = Utc::now();
+        let import = ImportDTO {
+            import_id: Uuid::new_v4(),
+            user_id: input.query.user_id,
+            source: input.query.source,
+            dry_run: input.query.dry_run,
+            status: ImportStatus::Pending,
+            report: None,
+            error: None,
+            created_at: now,
+        };
+
+        let archive = LimitedStream { inner: input.archive, remaining: self.policy.max_archive_bytes };
+        self.object_store.put_stream(
+            &Import::archive_key(&import.import_id),
+            Box::new(archive),
+            "application/octet-stream"
+        ).await?;
+
+        self.imports_repository.create(import.clone()).await?;
+        self.job_queue.enqueue(
+            Jobs::schedule(JobPayload::ImportArchive { import_id: import.import_id }, now)
+        ).await?;
+
+        Ok(import)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            import::{ ImportArchiveDTO, ImportSource, ImportStatus, StartImportDTO },
+            job::JobPayload,
+        },
+        entities::import::Import,
+        error_codes::IMPORT_ARCHIVE_TOO_LARGE_ERROR_CODE,
+        features::{ feature::Feature, imports::start_import::StartImport },
+        policies::import::ImportPolicy,
+        repositories::{ imports_repository::ImportsRepository, object_store::ObjectStore },
+        test_utils::test_utils::{
+            FakeByteStream,
+            InMemoryImportsRepository,
+            InMemoryJobQueue,
+            InMemoryObjectStore,
+        },
+    };
+
+    struct Fixture {
+        imports_repository: InMemoryImportsRepository,
+        object_store: InMemoryObjectStore,
+        job_queue: InMemoryJobQueue,
+        start_import: StartImport,
+    }
+
+    fn fixture(max_archive_bytes: u64) -> Fixture {
+        let imports_repository = InMemoryImportsRepository::default();
+        let object_store = InMemoryObjectStore::default();
+        let job_queue = InMemoryJobQueue::default();
+        let start_import = StartImport {
+            imports_repository: barc!(imports_repository.clone()),
+            object_store: barc!(object_store.clone()),
+            job_queue: barc!(job_queue.clone()),
+            policy: ImportPolicy { max_archive_bytes, ..ImportPolicy::default() },
+        };
+
+        Fixture { imports_repository, object_store, job_queue, start_import }
+    }
+
+    fn upload(bytes: &[u8]) -> StartImportDTO {
+        StartImportDTO {
+            query: ImportArchiveDTO {
+                user_id: Uuid::new_v4(),
+                source: ImportSource::Mastodon,
+                dry_run: true,
+            },
+            archive: FakeByteStream::of(bytes, 4),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_store_the_archive_and_queue_its_import() {
+        let fixture = fixture(1024);
+
+        let import = fixture.start_import.execute(upload(b"PK archive")).await.unwrap();
+
+        assert_eq!(import.status, ImportStatus::Pending);
+        assert_eq!(import.source, ImportSource::Mastodon);
+        assert!(import.dry_run);
+        assert_eq!(fixture.imports_repository.get(&import.import_id).await.unwrap(), import);
+        assert_eq!(
+            fixture.object_store.get(&Import::archive_key(&import.import_id)).await.unwrap(),
+            b"PK archive"
+        );
+        let jobs = fixture.job_queue.jobs.lock().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].payload, JobPayload::ImportArchive { import_id: import.import_id });
+    }
+
+    #[tokio::test]
+    async fn should_refuse_archives_over_the_limit_while_receiving_them() {
+        let fixture = fixture(8);
+
+        let result = fixture.start_import.execute(upload(b"PK archive")).await;
+
+        assert!(
+            matches!(result, Err(HearthError::Domain(code)) if code == IMPORT_ARCHIVE_TOO_LARGE_ERROR_CODE)
+        );
+        assert!(fixture.job_queue.jobs.lock().unwrap().is_empty());
+    }
+}
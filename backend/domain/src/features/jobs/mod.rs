@@ -0,0 +1,2 @@
+pub mod run_jobs;
+pub mod schedule_periodic_job;
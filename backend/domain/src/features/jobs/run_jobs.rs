@@ -0,0 +1,371 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use email_verification_code::EmailVerificationCode;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::{ job::{ JobDTO, JobPayload }, link_preview::UnfurlPostLinkDTO },
+    entities::jobs::Jobs,
+    features::{
+        data_exports::build_data_exports::BuildDataExportsFeature,
+        feature::Feature,
+        imports::import_archive::ImportArchiveFeature,
+        media::process_media::ProcessMediaFeature,
+        polls::notify_ended_polls::NotifyEndedPollsFeature,
+        posts::{ notify_mentions::NotifyMentionsFeature, unfurl_post_link::UnfurlPostLinkFeature },
+        users::purge_deleted_accounts::PurgeDeletedAccountsFeature,
+    },
+    policies::job::JobPolicy,
+    repositories::{ email_sender_repository::EmailSenderRepository, job_queue::JobQueue },
+};
+
+/// Runs one batch of the jobs due at the given instant, returns the number of
+/// jobs claimed. A failed job is retried with an exponential backoff, then
+/// dead-lettered once out of attempts. Meant to be run by the worker.
+///
+/// A periodic batch that handled anything is queued again right away, so a
+/// backlog drains without waiting for the next tick.
+pub type RunJobsFeature = dyn Feature<DateTime<Utc>, u64>;
+
+pub struct RunJobs {
+    pub job_queue: BArc<dyn JobQueue>,
+    pub unfurl_post_link: Box<UnfurlPostLinkFeature>,
+    pub notify_mentions: Box<NotifyMentionsFeature>,
+    pub process_media: Box<ProcessMediaFeature>,
+    pub import_archive: Box<ImportArchiveFeature>,
+    pub notify_ended_polls: Box<NotifyEndedPollsFeature>,
+    pub purge_deleted_accounts: Box<PurgeDeletedAccountsFeature>,
+    pub build_data_exports: Box<BuildDataExportsFeature>,
+    pub email_sender_repository: BArc<dyn EmailSenderRepository>,
+    pub policy: JobPolicy,
+}
+
+impl RunJobs {
+    /// Returns the number of items a periodic batch handled, none for the
+    /// other jobs.
+    async fn run(&self, payload: JobPayload, at: DateTime<Utc>) -> Result<u64, HearthError> {
+        match payload {
+            JobPayload::UnfurlPostLink { post_id, content } => {
+                self.unfurl_post_link.execute(UnfurlPostLinkDTO { post_id, content }).await?;
+            }
+            JobPayload::NotifyMentions { post_id } => {
+                self.notify_mentions.execute(post_id).await?;
+            }
+            JobPayload::SendDataExportLink { email, link } => {
+                self.email_sender_repository.send_data_export_link(&email, &link).await?;
+            }
+            JobPayload::SendWelcomeEmail { email, username } => {
+                self.email_sender_repository.send_welcome_email(&email, &username).await?;
+            }
+            JobPayload::SendVerifyEmail { email, code } => {
+                self.email_sender_repository
+                    .send_verify_email(&email, &(EmailVerificationCode { code })).await?;
+            }
+            JobPayload::ProcessMedia { media_id } => {
+                self.process_media.execute(media_id).await?;
+            }
+            JobPayload::ImportArchive { import_id } => {
+                self.import_archive.execute(import_id).await?;
+            }
+            JobPayload::NotifyEndedPolls => {
+                return self.notify_ended_polls.execute(at).await;
+            }
+            JobPayload::PurgeDeletedAccounts => {
+                return self.purge_deleted_accounts.execute(at).await;
+            }
+            JobPayload::BuildDataExports => {
+                return self.build_data_exports.execute(at).await;
+            }
+        }
+
+        Ok(0)
+    }
+
+    async fn fail(&self, job: &JobDTO, error: HearthError, at: DateTime<Utc>) -> Result<(), HearthError> {
+        let attempts = job.attempts + 1;
+        let error = format!("{:?}", error);
+
+        // A dead periodic job would keep its id taken, and the batch from ever
+        // being scheduled again.
+        if attempts >= self.policy.max_attempts && job.payload.periodic_id().is_none() {
+            return self.job_queue.dead_letter(&job.job_id, attempts, &error, at).await;
+        }
+
+        self.job_queue.retry(
+            &job.job_id,
+            attempts,
+            at + Jobs::backoff(attempts, &self.policy),
+            &error
+        ).await
+    }
+}
+
+#[async_trait]
+impl Feature<DateTime<Utc>, u64> for RunJobs {
+    async fn execute(&self, at: DateTime<Utc>) -> Result<u64, HearthError> {
+        let jobs = self.job_queue.claim(
+            at,
+            at + self.policy.lease,
+            self.policy.batch_size
+        ).await?;
+
+        for job in &jobs {
+            match self.run(job.payload.clone(), at).await {
+                Ok(handled) => {
+                    self.job_queue.complete(&job.job_id).await?;
+                    if handled > 0 {
+                        self.job_queue.enqueue(Jobs::schedule(job.payload.clone(), at)).await?;
+                    }
+                }
+                Err(e) => self.fail(job, e, at).await?,
+            }
+        }
+
+        Ok(jobs.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{ Arc, Mutex };
+
+    use async_trait::async_trait;
+    use chrono::{ DateTime, Duration, Utc };
+    use email_verification_code::EmailVerificationCode;
+    use errors::HearthError;
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::job::JobPayload,
+        entities::jobs::Jobs,
+        features::{
+            feature::Feature,
+            imports::import_archive::ImportArchive,
+            jobs::run_jobs::RunJobs,
+            media::process_media::ProcessMedia,
+            posts::{ notify_mentions::NotifyMentions, unfurl_post_link::UnfurlPostLink },
+        },
+        policies::{
+            import::ImportPolicy,
+            job::JobPolicy,
+            link_preview::LinkPreviewPolicy,
+            media::MediaPolicy,
+        },
+        repositories::job_queue::JobQueue,
+        test_utils::test_utils::{
+            FakeArchiveReader,
+            FakeLinkUnfurler,
+            FakeMediaProcessor,
+            InMemoryBlocksRepository,
+            InMemoryEmailSenderRepository,
+            InMemoryImportedPostsRepository,
+            InMemoryImportsRepository,
+            InMemoryJobQueue,
+            InMemoryLinkPreviewsRepository,
+            InMemoryMediaRepository,
+            InMemoryNotificationsRepository,
+            InMemoryObjectStore,
+            InMemoryPostsRepository,
+        },
+    };
+
+    fn run_jobs(job_queue: &InMemoryJobQueue, email_sender: &InMemoryEmailSenderRepository) -> RunJobs {
+        RunJobs {
+            job_queue: barc!(job_queue.clone()),
+            unfurl_post_link: Box::new(UnfurlPostLink {
+                link_previews_repository: barc!(InMemoryLinkPreviewsRepository::default()),
+                link_unfurler: barc!(FakeLinkUnfurler::default()),
+                policy: LinkPreviewPolicy::default(),
+            }),
+            notify_mentions: Box::new(NotifyMentions {
+                posts_repository: barc!(InMemoryPostsRepository::default()),
+                blocks_repository: barc!(InMemoryBlocksRepository::default()),
+                notifications_repository: barc!(InMemoryNotificationsRepository::default()),
+            }),
+            process_media: Box::new(ProcessMedia::default()),
+            import_archive: Box::new(ImportArchive {
+                archive_reader: barc!(FakeArchiveReader { posts: vec![] }),
+                imports_repository: barc!(InMemoryImportsRepository::default()),
+                imported_posts_repository: barc!(InMemoryImportedPostsRepository::default()),
+                posts_repository: barc!(InMemoryPostsRepository::default()),
+                media_repository: barc!(InMemoryMediaRepository::default()),
+                object_store: barc!(InMemoryObjectStore::default()),
+                media_processor: barc!(FakeMediaProcessor),
+                job_queue: barc!(job_queue.clone()),
+                media_policy: MediaPolicy::default(),
+                policy: ImportPolicy::default(),
+            }),
+            notify_ended_polls: Box::new(FakeBatch::default()),
+            purge_deleted_accounts: Box::new(FakeBatch::default()),
+            build_data_exports: Box::new(FakeBatch::default()),
+            email_sender_repository: barc!(email_sender.clone()),
+            policy: JobPolicy::default(),
+        }
+    }
+
+    /// Handles what is left two items at a time.
+    #[derive(Clone, Default)]
+    struct FakeBatch {
+        remaining: Arc<Mutex<u64>>,
+        runs: Arc<Mutex<u32>>,
+        failing: bool,
+    }
+
+    #[async_trait]
+    impl Feature<DateTime<Utc>, u64> for FakeBatch {
+        async fn execute(&self, _at: DateTime<Utc>) -> Result<u64, HearthError> {
+            *self.runs.lock().unwrap() += 1;
+            if self.failing {
+                return Err(HearthError::unexpected("BATCH_ERROR".into(), None));
+            }
+
+            let mut remaining = self.remaining.lock().unwrap();
+            let handled = (*remaining).min(2);
+            *remaining -= handled;
+            Ok(handled)
+        }
+    }
+
+    #[tokio::test]
+    async fn should_run_due_jobs_once() {
+        let job_queue = InMemoryJobQueue::default();
+        let email_sender = InMemoryEmailSenderRepository::default();
+        let run_jobs = run_jobs(&job_queue, &email_sender);
+        let now = Utc::now();
+
+        let send_link = JobPayload::SendDataExportLink {
+            email: "john.smith@gmail.com".into(),
+            link: "http://localhost:1337/exports/1?token=abc".into(),
+        };
+        job_queue.enqueue(Jobs::schedule(send_link.clone(), now)).await.unwrap();
+        job_queue.enqueue(Jobs::schedule(send_link, now + Duration::hours(1))).await.unwrap();
+
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 1);
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 0);
+        assert_eq!(email_sender.data_export_links.lock().unwrap().len(), 1);
+
+        assert_eq!(run_jobs.execute(now + Duration::hours(1)).await.unwrap(), 1);
+        assert_eq!(email_sender.data_export_links.lock().unwrap().len(), 2);
+        assert!(job_queue.jobs.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_send_queued_emails() {
+        let job_queue = InMemoryJobQueue::default();
+        let email_sender = InMemoryEmailSenderRepository::default();
+        let run_jobs = run_jobs(&job_queue, &email_sender);
+        let now = Utc::now();
+
+        let welcome = JobPayload::SendWelcomeEmail {
+            email: "john.smith@gmail.com".into(),
+            username: "john.smith".into(),
+        };
+        let verify = JobPayload::SendVerifyEmail {
+            email: "john.smith@gmail.com".into(),
+            code: "A1B2C3".into(),
+        };
+        job_queue.enqueue(Jobs::schedule(welcome, now)).await.unwrap();
+        job_queue.enqueue(Jobs::schedule(verify, now)).await.unwrap();
+
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 2);
+        assert_eq!(email_sender.welcome_emails.lock().unwrap().clone(), vec![
+            ("john.smith@gmail.com".to_string(), "john.smith".to_string()),
+        ]);
+        assert_eq!(email_sender.verify_emails.lock().unwrap().clone(), vec![
+            ("john.smith@gmail.com".to_string(), EmailVerificationCode { code: "A1B2C3".into() }),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn should_back_off_then_dead_letter_failing_jobs() {
+        let job_queue = InMemoryJobQueue::default();
+        let run_jobs = run_jobs(&job_queue, &InMemoryEmailSenderRepository::default());
+        let mut at = Utc::now();
+
+        let unfurl = JobPayload::UnfurlPostLink {
+            post_id: Uuid::new_v4(),
+            content: "https://example.com/down".into(),
+        };
+        job_queue.enqueue(Jobs::schedule(unfurl, at)).await.unwrap();
+
+        for attempt in 1..run_jobs.policy.max_attempts {
+            assert_eq!(run_jobs.execute(at).await.unwrap(), 1);
+
+            let job = job_queue.jobs.lock().unwrap()[0].clone();
+            assert_eq!(job.attempts, attempt);
+            assert!(job.last_error.unwrap().contains("LINK_UNREACHABLE"));
+            assert_eq!(job.run_at, at + Jobs::backoff(attempt, &run_jobs.policy));
+            assert_eq!(run_jobs.execute(job.run_at - Duration::seconds(1)).await.unwrap(), 0);
+
+            at = job.run_at;
+        }
+
+        assert_eq!(run_jobs.execute(at).await.unwrap(), 1);
+        assert!(job_queue.jobs.lock().unwrap().is_empty());
+        let dead = job_queue.dead.lock().unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, run_jobs.policy.max_attempts);
+    }
+
+    #[tokio::test]
+    async fn should_hide_claimed_jobs_until_the_lease_is_over() {
+        let job_queue = InMemoryJobQueue::default();
+        let run_jobs = run_jobs(&job_queue, &InMemoryEmailSenderRepository::default());
+        let now = Utc::now();
+
+        job_queue
+            .enqueue(Jobs::schedule(JobPayload::NotifyMentions { post_id: Uuid::new_v4() }, now)).await
+            .unwrap();
+
+        let lease_until = now + run_jobs.policy.lease;
+        assert_eq!(job_queue.claim(now, lease_until, 10).await.unwrap().len(), 1);
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 0);
+        assert_eq!(run_jobs.execute(lease_until).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_run_a_periodic_batch_once_until_it_is_drained() {
+        let job_queue = InMemoryJobQueue::default();
+        let batch = FakeBatch { remaining: Arc::new(Mutex::new(3)), ..FakeBatch::default() };
+        let run_jobs = RunJobs {
+            notify_ended_polls: Box::new(batch.clone()),
+            ..run_jobs(&job_queue, &InMemoryEmailSenderRepository::default())
+        };
+        let now = Utc::now();
+
+        // Every worker schedules it, a single one is queued.
+        job_queue.enqueue(Jobs::schedule(JobPayload::NotifyEndedPolls, now)).await.unwrap();
+        job_queue.enqueue(Jobs::schedule(JobPayload::NotifyEndedPolls, now)).await.unwrap();
+        assert_eq!(job_queue.jobs.lock().unwrap().len(), 1);
+
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 1);
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 1);
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 1);
+        assert_eq!(run_jobs.execute(now).await.unwrap(), 0);
+        assert_eq!(*batch.runs.lock().unwrap(), 3);
+        assert!(job_queue.jobs.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_keep_retrying_a_failing_periodic_batch() {
+        let job_queue = InMemoryJobQueue::default();
+        let batch = FakeBatch { failing: true, ..FakeBatch::default() };
+        let run_jobs = RunJobs {
+            purge_deleted_accounts: Box::new(batch.clone()),
+            ..run_jobs(&job_queue, &InMemoryEmailSenderRepository::default())
+        };
+        let mut at = Utc::now();
+
+        job_queue.enqueue(Jobs::schedule(JobPayload::PurgeDeletedAccounts, at)).await.unwrap();
+
+        for _ in 0..=run_jobs.policy.max_attempts {
+            assert_eq!(run_jobs.execute(at).await.unwrap(), 1);
+            at = job_queue.jobs.lock().unwrap()[0].run_at;
+        }
+
+        assert_eq!(*batch.runs.lock().unwrap(), run_jobs.policy.max_attempts + 1);
+        assert!(job_queue.dead.lock().unwrap().is_empty());
+    }
+}
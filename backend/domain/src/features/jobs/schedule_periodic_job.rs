@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::job::JobPayload,
+    entities::jobs::Jobs,
+    features::feature::Feature,
+    repositories::job_queue::JobQueue,
+};
+
+/// Queues a periodic batch unless it already is, every worker schedules it and
+/// whichever claims it runs it.
+pub type SchedulePeriodicJobFeature = dyn Feature<JobPayload, ()>;
+
+pub struct SchedulePeriodicJob {
+    pub job_queue: BArc<dyn JobQueue>,
+}
+
+#[async_trait]
+impl Feature<JobPayload, ()> for SchedulePeriodicJob {
+    async fn execute(&self, payload: JobPayload) -> Result<(), HearthError> {
+        self.job_queue.enqueue(Jobs::schedule(payload, Utc::now())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::barc;
+
+    use crate::{
+        dtos::job::JobPayload,
+        features::{ feature::Feature, jobs::schedule_periodic_job::SchedulePeriodicJob },
+        test_utils::test_utils::InMemoryJobQueue,
+    };
+
+    #[tokio::test]
+    async fn should_queue_each_batch_once() {
+        let job_queue = InMemoryJobQueue::default();
+        let schedule_periodic_job = SchedulePeriodicJob { job_queue: barc!(job_queue.clone()) };
+
+        for _ in 0..2 {
+            schedule_periodic_job.execute(JobPayload::NotifyEndedPolls).await.unwrap();
+            schedule_periodic_job.execute(JobPayload::BuildDataExports).await.unwrap();
+        }
+
+        let kinds = job_queue.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|job| job.payload.kind())
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["notify_ended_polls", "build_data_exports"]);
+    }
+}
@@ -0,0 +1,214 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::{
+        list::{ AddListMemberDTO, ListMemberDTO },
+        notification::{ NotificationDTO, NotificationKind },
+    },
+    entities::lists::Lists,
+    error_codes::{ LIST_FULL_ERROR_CODE, USER_NOT_FOUND_ERROR_CODE },
+    features::feature::Feature,
+    policies::list::ListPolicy,
+    repositories::{
+        blocks_repository::BlocksRepository,
+        lists_repository::ListsRepository,
+        notifications_repository::NotificationsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type AddListMemberFeature = dyn Feature<AddListMemberDTO, ()>;
+
+pub struct AddListMember {
+    pub lists_repository: BArc<dyn ListsRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+    pub notifications_repository: BArc<dyn NotificationsRepository>,
+    pub policy: ListPolicy,
+}
+
+#[async_trait]
+impl Feature<AddListMemberDTO, ()> for AddListMember {
+    async fn execute(&self, input: AddListMemberDTO) -> Result<(), HearthError> {
+        let list = self.lists_repository.get(&input.list_id).await?;
+        Lists::check_owner(&list, &input.owner_id)?;
+
+        let user = self.users_repository.get(input.member_id.to_string()).await?;
+
+        // Someone blocking the owner can't be listed, and is reported as unknown.
+        if self.blocks_repository.is_blocked_between(&input.owner_id, &user.user_id).await? {
+            return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        let members = self.lists_repository.members(&list.list_id).await?;
+        if members.len() >= self.policy.max_members_per_list {
+            return Err(HearthError::Domain(LIST_FULL_ERROR_CODE.into()));
+        }
+
+        let now = Utc::now();
+        let added = self.lists_repository.add_member(
+            &list.list_id,
+            ListMemberDTO { user_id: user.user_id, username: user.username },
+            now
+        ).await?;
+
+        // Private lists never tell anyone they exist.
+        if added && input.notify && !list.is_private && input.member_id != input.owner_id {
+            self.notifications_repository.create(NotificationDTO {
+                notification_id: Uuid::new_v4(),
+                recipient_id: input.member_id,
+                actor_id: input.owner_id,
+                kind: NotificationKind::ListAdded,
+                post_id: None,
+                list_id: Some(list.list_id),
+                filtered: vec![],
+                created_at: now,
+            }).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            list::{ AddListMemberDTO, ListDTO },
+            notification::NotificationKind,
+            user::CreateUserDTO,
+        },
+        error_codes::USER_NOT_FOUND_ERROR_CODE,
+        features::{ feature::Feature, lists::add_list_member::AddListMember },
+        policies::list::ListPolicy,
+        repositories::{
+            blocks_repository::BlocksRepository,
+            lists_repository::ListsRepository,
+            notifications_repository::NotificationsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryBlocksRepository,
+            InMemoryListsRepository,
+            InMemoryNotificationsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    struct Fixture {
+        add_list_member: AddListMember,
+        lists_repository: BArc<dyn ListsRepository>,
+        blocks_repository: BArc<dyn BlocksRepository>,
+        notifications_repository: BArc<dyn NotificationsRepository>,
+        owner_id: Uuid,
+        member_id: Uuid,
+    }
+
+    async fn fixture() -> Fixture {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let users_repository: BArc<dyn UsersRepository> = barc!(InMemoryUserRepository::default());
+        let blocks_repository: BArc<dyn BlocksRepository> = barc!(InMemoryBlocksRepository::default());
+        let notifications_repository: BArc<dyn NotificationsRepository> = barc!(
+            InMemoryNotificationsRepository::default()
+        );
+
+        let member_id = Uuid::new_v4();
+        users_repository
+            .create(
+                CreateUserDTO {
+                    user_id: member_id,
+                    username: "ferris".into(),
+                    email: "ferris@example.com".into(),
+                    birthday: NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
+                },
+                CredentialsDTO { user_id: member_id, password_hash: "hash".into() }
+            ).await
+            .unwrap();
+
+        Fixture {
+            add_list_member: AddListMember {
+                lists_repository: lists_repository.clone(),
+                users_repository,
+                blocks_repository: blocks_repository.clone(),
+                notifications_repository: notifications_repository.clone(),
+                policy: ListPolicy::default(),
+            },
+            lists_repository,
+            blocks_repository,
+            notifications_repository,
+            owner_id: Uuid::new_v4(),
+            member_id,
+        }
+    }
+
+    async fn create_list(fixture: &Fixture, is_private: bool) -> ListDTO {
+        let list = ListDTO {
+            list_id: Uuid::new_v4(),
+            owner_id: fixture.owner_id,
+            name: "Crabs".into(),
+            is_private,
+            created_at: chrono::Utc::now(),
+        };
+        fixture.lists_repository.create(list.clone()).await.unwrap();
+        list
+    }
+
+    fn dto(fixture: &Fixture, list: &ListDTO, notify: bool) -> AddListMemberDTO {
+        AddListMemberDTO {
+            list_id: list.list_id,
+            owner_id: fixture.owner_id,
+            member_id: fixture.member_id,
+            notify,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_notify_only_when_asked_and_for_public_lists() {
+        let fixture = fixture().await;
+        let public = create_list(&fixture, false).await;
+        let private = create_list(&fixture, true).await;
+
+        fixture.add_list_member.execute(dto(&fixture, &private, true)).await.unwrap();
+        fixture.add_list_member.execute(dto(&fixture, &public, false)).await.unwrap();
+        assert!(
+            fixture.notifications_repository.list(&fixture.member_id, None, 10).await.unwrap().is_empty()
+        );
+
+        let other_public = create_list(&fixture, false).await;
+        fixture.add_list_member.execute(dto(&fixture, &other_public, true)).await.unwrap();
+        // Adding twice doesn't notify twice.
+        fixture.add_list_member.execute(dto(&fixture, &other_public, true)).await.unwrap();
+
+        let notifications = fixture.notifications_repository
+            .list(&fixture.member_id, None, 10).await
+            .unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::ListAdded);
+        assert_eq!(notifications[0].list_id, Some(other_public.list_id));
+
+        let members = fixture.lists_repository.members(&private.list_id).await.unwrap();
+        assert_eq!(members[0].username, "ferris");
+    }
+
+    #[tokio::test]
+    async fn should_not_add_users_blocking_the_owner() {
+        let fixture = fixture().await;
+        let list = create_list(&fixture, false).await;
+        fixture.blocks_repository.block(&fixture.member_id, &fixture.owner_id).await.unwrap();
+
+        assert_eq!(
+            fixture.add_list_member.execute(dto(&fixture, &list, true)).await.unwrap_err(),
+            HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+}
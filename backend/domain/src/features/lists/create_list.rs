@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::list::{ CreateListDTO, ListDTO },
+    error_codes::TOO_MANY_LISTS_ERROR_CODE,
+    features::feature::Feature,
+    policies::list::ListPolicy,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type CreateListFeature = dyn Feature<CreateListDTO, ListDTO>;
+
+pub struct CreateList {
+    pub lists_repository: BArc<dyn ListsRepository>,
+    pub policy: ListPolicy,
+}
+
+#[async_trait]
+impl Feature<CreateListDTO, ListDTO> for CreateList {
+    async fn execute(&self, input: CreateListDTO) -> Result<ListDTO, HearthError> {
+        let input = CreateListDTO { name: input.name.trim().into(), ..input };
+
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("CREATE_LIST".into(), e));
+        }
+
+        let owned = self.lists_repository.list_by_owner(&input.owner_id).await?;
+        if owned.len() >= self.policy.max_lists_per_owner {
+            return Err(HearthError::Domain(TOO_MANY_LISTS_ERROR_CODE.into()));
+        }
+
+        let list = ListDTO {
+            list_id: input.list_id,
+            owner_id: input.owner_id,
+            name: input.name,
+            is_private: input.is_private,
+            created_at: Utc::now(),
+        };
+
+        self.lists_repository.create(list.clone()).await?;
+
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::CreateListDTO,
+        error_codes::TOO_MANY_LISTS_ERROR_CODE,
+        features::{ feature::Feature, lists::create_list::CreateList },
+        policies::list::ListPolicy,
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    fn dto(owner_id: Uuid, name: &str) -> CreateListDTO {
+        CreateListDTO { list_id: Uuid::new_v4(), owner_id, name: name.into(), is_private: false }
+    }
+
+    #[tokio::test]
+    async fn should_create_lists_with_a_trimmed_name() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let create_list = CreateList {
+            lists_repository: lists_repository.clone(),
+            policy: ListPolicy::default(),
+        };
+
+        let list = create_list.execute(dto(Uuid::new_v4(), "  Rustaceans ")).await.unwrap();
+
+        assert_eq!(list.name, "Rustaceans");
+        assert_eq!(lists_repository.get(&list.list_id).await.unwrap(), list);
+        assert!(matches!(
+            create_list.execute(dto(Uuid::new_v4(), "   ")).await.unwrap_err(),
+            HearthError::Validation(..)
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_limit_the_lists_of_each_owner() {
+        let create_list = CreateList {
+            lists_repository: barc!(InMemoryListsRepository::default()),
+            policy: ListPolicy { max_lists_per_owner: 1, ..ListPolicy::default() },
+        };
+        let owner_id = Uuid::new_v4();
+
+        create_list.execute(dto(owner_id, "Crabs")).await.unwrap();
+
+        assert_eq!(
+            create_list.execute(dto(owner_id, "More crabs")).await.unwrap_err(),
+            HearthError::Domain(TOO_MANY_LISTS_ERROR_CODE.into())
+        );
+        create_list.execute(dto(Uuid::new_v4(), "Crabs")).await.unwrap();
+    }
+}
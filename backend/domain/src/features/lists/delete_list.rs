@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::list::ListAccessDTO,
+    entities::lists::Lists,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type DeleteListFeature = dyn Feature<ListAccessDTO, ()>;
+
+pub struct DeleteList {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<ListAccessDTO, ()> for DeleteList {
+    async fn execute(&self, input: ListAccessDTO) -> Result<(), HearthError> {
+        let list = self.lists_repository.get(&input.list_id).await?;
+        Lists::check_owner(&list, &input.viewer_id)?;
+
+        self.lists_repository.delete(&input.list_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::{ ListAccessDTO, ListDTO },
+        error_codes::{ LIST_NOT_FOUND_ERROR_CODE, NOT_LIST_OWNER_ERROR_CODE },
+        features::{ feature::Feature, lists::delete_list::DeleteList },
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    async fn create_list(lists_repository: &BArc<dyn ListsRepository>, is_private: bool) -> ListDTO {
+        let list = ListDTO {
+            list_id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Crabs".into(),
+            is_private,
+            created_at: Utc::now(),
+        };
+        lists_repository.create(list.clone()).await.unwrap();
+        list
+    }
+
+    #[tokio::test]
+    async fn should_only_let_the_owner_delete_a_list() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let delete_list = DeleteList { lists_repository: lists_repository.clone() };
+        let public = create_list(&lists_repository, false).await;
+        let private = create_list(&lists_repository, true).await;
+        let stranger = Uuid::new_v4();
+
+        assert_eq!(
+            delete_list
+                .execute(ListAccessDTO { list_id: public.list_id, viewer_id: stranger }).await
+                .unwrap_err(),
+            HearthError::Forbidden(NOT_LIST_OWNER_ERROR_CODE.into())
+        );
+        // Private lists don't exist for the others.
+        assert_eq!(
+            delete_list
+                .execute(ListAccessDTO { list_id: private.list_id, viewer_id: stranger }).await
+                .unwrap_err(),
+            HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into())
+        );
+        assert!(lists_repository.get(&public.list_id).await.is_ok());
+
+        delete_list
+            .execute(ListAccessDTO { list_id: public.list_id, viewer_id: public.owner_id }).await
+            .unwrap();
+
+        assert_eq!(
+            lists_repository.get(&public.list_id).await.unwrap_err(),
+            HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+}
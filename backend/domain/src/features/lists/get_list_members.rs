@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::list::{ ListAccessDTO, ListMemberDTO },
+    entities::lists::Lists,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type GetListMembersFeature = dyn Feature<ListAccessDTO, Vec<ListMemberDTO>>;
+
+pub struct GetListMembers {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<ListAccessDTO, Vec<ListMemberDTO>> for GetListMembers {
+    async fn execute(&self, input: ListAccessDTO) -> Result<Vec<ListMemberDTO>, HearthError> {
+        let list = self.lists_repository.get(&input.list_id).await?;
+        Lists::check_visible(&list, &input.viewer_id)?;
+
+        self.lists_repository.members(&input.list_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::{ ListAccessDTO, ListDTO, ListMemberDTO },
+        error_codes::LIST_NOT_FOUND_ERROR_CODE,
+        features::{ feature::Feature, lists::get_list_members::GetListMembers },
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_hide_members_of_private_lists_from_everyone_but_the_owner() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let get_list_members = GetListMembers { lists_repository: lists_repository.clone() };
+        let member = ListMemberDTO { user_id: Uuid::new_v4(), username: "ferris".into() };
+        let mut lists = vec![];
+        for is_private in [false, true] {
+            let list = ListDTO {
+                list_id: Uuid::new_v4(),
+                owner_id: Uuid::new_v4(),
+                name: "Crabs".into(),
+                is_private,
+                created_at: Utc::now(),
+            };
+            lists_repository.create(list.clone()).await.unwrap();
+            lists_repository.add_member(&list.list_id, member.clone(), Utc::now()).await.unwrap();
+            lists.push(list);
+        }
+        let (public, private) = (&lists[0], &lists[1]);
+        let access = |list: &ListDTO, viewer_id| ListAccessDTO { list_id: list.list_id, viewer_id };
+
+        assert_eq!(
+            get_list_members.execute(access(public, Uuid::new_v4())).await.unwrap(),
+            vec![member.clone()]
+        );
+        assert_eq!(
+            get_list_members.execute(access(private, private.owner_id)).await.unwrap(),
+            vec![member]
+        );
+        assert_eq!(
+            get_list_members.execute(access(private, Uuid::new_v4())).await.unwrap_err(),
+            HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::list::ListDTO,
+    entities::lists::Lists,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type GetListSubscriptionsFeature = dyn Feature<Uuid, Vec<ListDTO>>;
+
+pub struct GetListSubscriptions {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, Vec<ListDTO>> for GetListSubscriptions {
+    async fn execute(&self, user_id: Uuid) -> Result<Vec<ListDTO>, HearthError> {
+        Ok(
+            self.lists_repository
+                .subscriptions(&user_id).await?
+                .into_iter()
+                .filter(|list| Lists::check_visible(list, &user_id).is_ok())
+                .collect()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::ListDTO,
+        features::{ feature::Feature, lists::get_list_subscriptions::GetListSubscriptions },
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_skip_subscriptions_to_lists_that_became_private() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let get_list_subscriptions = GetListSubscriptions {
+            lists_repository: lists_repository.clone(),
+        };
+        let subscriber_id = Uuid::new_v4();
+        let mut lists = vec![];
+        for is_private in [false, true] {
+            let list = ListDTO {
+                list_id: Uuid::new_v4(),
+                owner_id: Uuid::new_v4(),
+                name: "Crabs".into(),
+                is_private,
+                created_at: Utc::now(),
+            };
+            lists_repository.create(list.clone()).await.unwrap();
+            lists_repository.subscribe(&list.list_id, &subscriber_id).await.unwrap();
+            lists.push(list);
+        }
+
+        assert_eq!(
+            get_list_subscriptions.execute(subscriber_id).await.unwrap(),
+            vec![lists[0].clone()]
+        );
+        assert!(get_list_subscriptions.execute(Uuid::new_v4()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        filter::FilterContext,
+        list::ListTimelineDTO,
+        pagination::{ Page, TimelineCursor },
+        post::PostDTO,
+    },
+    entities::{ age::Age, keyword_filters::KeywordFilters, lists::Lists, posts::Posts },
+    features::feature::Feature,
+    policies::age::AgePolicy,
+    repositories::{
+        keyword_filters_repository::KeywordFiltersRepository,
+        lists_repository::ListsRepository,
+        posts_repository::PostsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type GetListTimelineFeature = dyn Feature<ListTimelineDTO, Page<PostDTO>>;
+
+/// Lists are the home timeline of the viewer as far as keyword filters go.
+pub struct GetListTimeline {
+    pub lists_repository: BArc<dyn ListsRepository>,
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub keyword_filters_repository: BArc<dyn KeywordFiltersRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub age_policy: AgePolicy,
+}
+
+#[async_trait]
+impl Feature<ListTimelineDTO, Page<PostDTO>> for GetListTimeline {
+    async fn execute(&self, input: ListTimelineDTO) -> Result<Page<PostDTO>, HearthError> {
+        if let Err(e) = input.page.validate() {
+            return Err(HearthError::Validation("LIST_TIMELINE".into(), e));
+        }
+
+        let list = self.lists_repository.get(&input.list_id).await?;
+        Lists::check_visible(&list, &input.viewer_id)?;
+
+        let cursor = input.page.timeline_cursor()?;
+        let author_ids: Vec<Uuid> = self.lists_repository
+            .members(&list.list_id).await?
+            .into_iter()
+            .map(|member| member.user_id)
+            .collect();
+
+        if author_ids.is_empty() {
+            return Ok(Page::default());
+        }
+
+        let now = Utc::now();
+        let filters = KeywordFilters::active(
+            self.keyword_filters_repository.list(&input.viewer_id).await?,
+            FilterContext::Home,
+            now
+        );
+
+        let posts = self.posts_repository.list_by_authors(
+            &author_ids,
+            cursor,
+            input.page.limit + 1
+        ).await?;
+
+        let viewer_id = Some(&input.viewer_id);
+        let can_view_sensitive =
+            posts.iter().any(|post| Age::is_gated(post, viewer_id)) &&
+            Age::can_view_sensitive(
+                Some(&self.users_repository.get(input.viewer_id.to_string()).await?),
+                now.date_naive(),
+                &self.age_policy
+            );
+
+        let posts = posts
+            .into_iter()
+            .map(|post| {
+                let is_author = post.author_id == input.viewer_id;
+                let post = PostDTO {
+                    poll: post.poll.map(|poll| Posts::poll_for_viewer(poll, vec![], is_author, now)),
+                    ..post
+                };
+                let post = KeywordFilters::mark_post(post, &filters, &input.viewer_id);
+                Age::gate_post(post, viewer_id, can_view_sensitive)
+            })
+            .collect();
+
+        Ok(
+            Page::from_overfetched(posts, input.page.limit, |post| {
+                (TimelineCursor { created_at: post.created_at, id: post.post_id }).encode()
+            })
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            filter::{ FilterContext, FilterMatchDTO, KeywordFilterDTO },
+            list::{ ListDTO, ListMemberDTO, ListTimelineDTO },
+            pagination::PageRequest,
+            post::CreatePostDTO,
+        },
+        error_codes::LIST_NOT_FOUND_ERROR_CODE,
+        features::{
+            feature::Feature,
+            lists::get_list_timeline::GetListTimeline,
+            posts::create_post::CreatePost,
+        },
+        policies::age::AgePolicy,
+        repositories::{
+            keyword_filters_repository::KeywordFiltersRepository,
+            lists_repository::ListsRepository,
+            posts_repository::PostsRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryKeywordFiltersRepository,
+            InMemoryListsRepository,
+            InMemoryPostsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    struct Fixture {
+        get_timeline: GetListTimeline,
+        create_post: CreatePost,
+        lists_repository: BArc<dyn ListsRepository>,
+        keyword_filters_repository: BArc<dyn KeywordFiltersRepository>,
+    }
+
+    fn fixture() -> Fixture {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let posts_repository: BArc<dyn PostsRepository> = barc!(InMemoryPostsRepository::default());
+        let keyword_filters_repository: BArc<dyn KeywordFiltersRepository> = barc!(
+            InMemoryKeywordFiltersRepository::default()
+        );
+
+        Fixture {
+            get_timeline: GetListTimeline {
+                lists_repository: lists_repository.clone(),
+                posts_repository: posts_repository.clone(),
+                keyword_filters_repository: keyword_filters_repository.clone(),
+                users_repository: barc!(InMemoryUserRepository::default()),
+                age_policy: AgePolicy::default(),
+            },
+            create_post: CreatePost { posts_repository, ..Default::default() },
+            lists_repository,
+            keyword_filters_repository,
+        }
+    }
+
+    async fn create_list(fixture: &Fixture, owner_id: Uuid, member_id: Uuid) -> ListDTO {
+        let list = ListDTO {
+            list_id: Uuid::new_v4(),
+            owner_id,
+            name: "Close friends".into(),
+            is_private: true,
+            created_at: Utc::now(),
+        };
+        fixture.lists_repository.create(list.clone()).await.unwrap();
+        fixture.lists_repository
+            .add_member(&list.list_id, ListMemberDTO { user_id: member_id, username: "jane".into() }, Utc::now()).await
+            .unwrap();
+        list
+    }
+
+    #[tokio::test]
+    async fn should_build_the_timeline_from_members_posts() {
+        let fixture = fixture();
+        let Fixture { get_timeline, create_post, .. } = &fixture;
+
+        let owner_id = Uuid::new_v4();
+        let member_id = Uuid::new_v4();
+        let list = create_list(&fixture, owner_id, member_id).await;
+
+        let mut posts = vec![];
+        for _ in 0..3 {
+            posts.push(
+                create_post.execute(CreatePostDTO { author_id: member_id, ..Default::default() }).await.unwrap()
+            );
+        }
+        posts.sort_by_key(|post| Reverse((post.created_at, post.post_id)));
+        let expected: Vec<Uuid> = posts.iter().map(|post| post.post_id).collect();
+        create_post.execute(CreatePostDTO::default()).await.unwrap();
+
+        let dto = |viewer_id: Uuid, cursor: Option<String>| ListTimelineDTO {
+            list_id: list.list_id,
+            viewer_id,
+            page: PageRequest { cursor, limit: 2 },
+        };
+
+        let first = get_timeline.execute(dto(owner_id, None)).await.unwrap();
+        let second = get_timeline.execute(dto(owner_id, first.next_cursor.clone())).await.unwrap();
+        let ids: Vec<Uuid> = first.items
+            .iter()
+            .chain(second.items.iter())
+            .map(|p| p.post_id)
+            .collect();
+        assert_eq!(ids, expected);
+
+        assert_eq!(
+            get_timeline.execute(dto(member_id, None)).await.unwrap_err(),
+            HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_mark_posts_matching_home_filters() {
+        let fixture = fixture();
+        let owner_id = Uuid::new_v4();
+        let member_id = Uuid::new_v4();
+        let list = create_list(&fixture, owner_id, member_id).await;
+
+        let filter_id = Uuid::new_v4();
+        fixture.keyword_filters_repository
+            .create(KeywordFilterDTO {
+                filter_id,
+                owner_id,
+                phrase: "goodbye".into(),
+                whole_word: true,
+                contexts: vec![FilterContext::Home],
+                expires_at: None,
+                created_at: Utc::now(),
+            }).await
+            .unwrap();
+        fixture.create_post
+            .execute(CreatePostDTO { author_id: member_id, ..Default::default() }).await
+            .unwrap();
+
+        let page = fixture.get_timeline
+            .execute(ListTimelineDTO {
+                list_id: list.list_id,
+                viewer_id: owner_id,
+                page: PageRequest { cursor: None, limit: 10 },
+            }).await
+            .unwrap();
+
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].filtered, vec![FilterMatchDTO { filter_id, phrase: "goodbye".into() }]);
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::list::{ ListDTO, UserListsDTO },
+    entities::lists::Lists,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type GetUserListsFeature = dyn Feature<UserListsDTO, Vec<ListDTO>>;
+
+/// Lists created by a user: all of them for the owner, the public ones otherwise.
+pub struct GetUserLists {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<UserListsDTO, Vec<ListDTO>> for GetUserLists {
+    async fn execute(&self, input: UserListsDTO) -> Result<Vec<ListDTO>, HearthError> {
+        Ok(
+            self.lists_repository
+                .list_by_owner(&input.owner_id).await?
+                .into_iter()
+                .filter(|list| Lists::check_visible(list, &input.viewer_id).is_ok())
+                .collect()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::{ ListDTO, UserListsDTO },
+        features::{ feature::Feature, lists::get_user_lists::GetUserLists },
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_only_show_private_lists_to_their_owner() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let get_user_lists = GetUserLists { lists_repository: lists_repository.clone() };
+        let owner_id = Uuid::new_v4();
+        let mut lists = vec![];
+        for is_private in [false, true] {
+            let list = ListDTO {
+                list_id: Uuid::new_v4(),
+                owner_id,
+                name: "Crabs".into(),
+                is_private,
+                created_at: Utc::now(),
+            };
+            lists_repository.create(list.clone()).await.unwrap();
+            lists.push(list);
+        }
+
+        assert_eq!(
+            get_user_lists.execute(UserListsDTO { owner_id, viewer_id: owner_id }).await.unwrap(),
+            lists
+        );
+        assert_eq!(
+            get_user_lists.execute(UserListsDTO { owner_id, viewer_id: Uuid::new_v4() }).await.unwrap(),
+            vec![lists[0].clone()]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+pub mod add_list_member;
+pub mod create_list;
+pub mod delete_list;
+pub mod get_list_members;
+pub mod get_list_subscriptions;
+pub mod get_list_timeline;
+pub mod get_user_lists;
+pub mod remove_list_member;
+pub mod subscribe_list;
+pub mod unsubscribe_list;
+pub mod update_list;
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::list::RemoveListMemberDTO,
+    entities::lists::Lists,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type RemoveListMemberFeature = dyn Feature<RemoveListMemberDTO, ()>;
+
+pub struct RemoveListMember {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<RemoveListMemberDTO, ()> for RemoveListMember {
+    async fn execute(&self, input: RemoveListMemberDTO) -> Result<(), HearthError> {
+        let list = self.lists_repository.get(&input.list_id).await?;
+        Lists::check_owner(&list, &input.owner_id)?;
+
+        self.lists_repository.remove_member(&input.list_id, &input.member_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::{ ListDTO, ListMemberDTO, RemoveListMemberDTO },
+        error_codes::NOT_LIST_OWNER_ERROR_CODE,
+        features::{ feature::Feature, lists::remove_list_member::RemoveListMember },
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_only_let_the_owner_remove_members() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let remove_list_member = RemoveListMember { lists_repository: lists_repository.clone() };
+        let list = ListDTO {
+            list_id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Crabs".into(),
+            is_private: false,
+            created_at: Utc::now(),
+        };
+        let member = ListMemberDTO { user_id: Uuid::new_v4(), username: "ferris".into() };
+        lists_repository.create(list.clone()).await.unwrap();
+        lists_repository.add_member(&list.list_id, member.clone(), Utc::now()).await.unwrap();
+
+        // Not even the member themselves.
+        assert_eq!(
+            remove_list_member
+                .execute(RemoveListMemberDTO {
+                    list_id: list.list_id,
+                    owner_id: member.user_id,
+                    member_id: member.user_id,
+                }).await
+                .unwrap_err(),
+            HearthError::Forbidden(NOT_LIST_OWNER_ERROR_CODE.into())
+        );
+        assert_eq!(lists_repository.members(&list.list_id).await.unwrap(), vec![member.clone()]);
+
+        remove_list_member
+            .execute(RemoveListMemberDTO {
+                list_id: list.list_id,
+                owner_id: list.owner_id,
+                member_id: member.user_id,
+            }).await
+            .unwrap();
+
+        assert!(lists_repository.members(&list.list_id).await.unwrap().is_empty());
+    }
+}
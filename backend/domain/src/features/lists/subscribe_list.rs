@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::list::ListAccessDTO,
+    entities::lists::Lists,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type SubscribeListFeature = dyn Feature<ListAccessDTO, ()>;
+
+pub struct SubscribeList {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<ListAccessDTO, ()> for SubscribeList {
+    async fn execute(&self, input: ListAccessDTO) -> Result<(), HearthError> {
+        let list = self.lists_repository.get(&input.list_id).await?;
+        Lists::check_visible(&list, &input.viewer_id)?;
+
+        self.lists_repository.subscribe(&input.list_id, &input.viewer_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::{ ListAccessDTO, ListDTO },
+        error_codes::LIST_NOT_FOUND_ERROR_CODE,
+        features::{
+            feature::Feature,
+            lists::{ get_list_subscriptions::GetListSubscriptions, subscribe_list::SubscribeList },
+        },
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_subscribe_to_public_lists_only() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let subscribe = SubscribeList { lists_repository: lists_repository.clone() };
+        let subscriber_id = Uuid::new_v4();
+
+        let mut lists = vec![];
+        for is_private in [false, true] {
+            let list = ListDTO {
+                list_id: Uuid::new_v4(),
+                owner_id: Uuid::new_v4(),
+                name: "News".into(),
+                is_private,
+                created_at: Utc::now(),
+            };
+            lists_repository.create(list.clone()).await.unwrap();
+            lists.push(list);
+        }
+
+        subscribe.execute(ListAccessDTO { list_id: lists[0].list_id, viewer_id: subscriber_id }).await.unwrap();
+        assert_eq!(
+            subscribe
+                .execute(ListAccessDTO { list_id: lists[1].list_id, viewer_id: subscriber_id }).await
+                .unwrap_err(),
+            HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into())
+        );
+
+        let subscriptions = (GetListSubscriptions { lists_repository: lists_repository.clone() })
+            .execute(subscriber_id).await
+            .unwrap();
+        assert_eq!(subscriptions, vec![lists[0].clone()]);
+    }
+}
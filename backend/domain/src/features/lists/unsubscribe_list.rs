@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::list::ListAccessDTO,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type UnsubscribeListFeature = dyn Feature<ListAccessDTO, ()>;
+
+pub struct UnsubscribeList {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<ListAccessDTO, ()> for UnsubscribeList {
+    async fn execute(&self, input: ListAccessDTO) -> Result<(), HearthError> {
+        self.lists_repository.unsubscribe(&input.list_id, &input.viewer_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::{ ListAccessDTO, ListDTO },
+        features::{ feature::Feature, lists::unsubscribe_list::UnsubscribeList },
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_only_unsubscribe_the_viewer() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let unsubscribe_list = UnsubscribeList { lists_repository: lists_repository.clone() };
+        let list = ListDTO {
+            list_id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Crabs".into(),
+            is_private: false,
+            created_at: Utc::now(),
+        };
+        let (subscriber_id, other_subscriber_id) = (Uuid::new_v4(), Uuid::new_v4());
+        lists_repository.create(list.clone()).await.unwrap();
+        lists_repository.subscribe(&list.list_id, &subscriber_id).await.unwrap();
+        lists_repository.subscribe(&list.list_id, &other_subscriber_id).await.unwrap();
+
+        unsubscribe_list
+            .execute(ListAccessDTO { list_id: list.list_id, viewer_id: subscriber_id }).await
+            .unwrap();
+        // Unsubscribing twice is a no-op.
+        unsubscribe_list
+            .execute(ListAccessDTO { list_id: list.list_id, viewer_id: subscriber_id }).await
+            .unwrap();
+
+        assert!(lists_repository.subscriptions(&subscriber_id).await.unwrap().is_empty());
+        assert_eq!(lists_repository.subscriptions(&other_subscriber_id).await.unwrap(), vec![list]);
+    }
+}
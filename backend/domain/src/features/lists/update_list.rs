@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::list::{ ListDTO, UpdateListDTO },
+    entities::lists::Lists,
+    features::feature::Feature,
+    repositories::lists_repository::ListsRepository,
+};
+
+pub type UpdateListFeature = dyn Feature<UpdateListDTO, ListDTO>;
+
+pub struct UpdateList {
+    pub lists_repository: BArc<dyn ListsRepository>,
+}
+
+#[async_trait]
+impl Feature<UpdateListDTO, ListDTO> for UpdateList {
+    async fn execute(&self, input: UpdateListDTO) -> Result<ListDTO, HearthError> {
+        let input = UpdateListDTO { name: input.name.trim().into(), ..input };
+
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("UPDATE_LIST".into(), e));
+        }
+
+        let list = self.lists_repository.get(&input.list_id).await?;
+        Lists::check_owner(&list, &input.owner_id)?;
+
+        let updated = ListDTO { name: input.name, is_private: input.is_private, ..list.clone() };
+        self.lists_repository.update(updated.clone()).await?;
+
+        // Subscribers would otherwise keep reading a list that is now private.
+        if updated.is_private && !list.is_private {
+            self.lists_repository.remove_subscribers(&updated.list_id).await?;
+        }
+
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::list::{ CreateListDTO, ListAccessDTO, UpdateListDTO },
+        error_codes::NOT_LIST_OWNER_ERROR_CODE,
+        features::{
+            feature::Feature,
+            lists::{ create_list::CreateList, subscribe_list::SubscribeList, update_list::UpdateList },
+        },
+        policies::list::ListPolicy,
+        repositories::lists_repository::ListsRepository,
+        test_utils::test_utils::InMemoryListsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_drop_subscribers_when_a_list_becomes_private() {
+        let lists_repository: BArc<dyn ListsRepository> = barc!(InMemoryListsRepository::default());
+        let owner_id = Uuid::new_v4();
+        let subscriber_id = Uuid::new_v4();
+
+        let list = (CreateList { lists_repository: lists_repository.clone(), policy: ListPolicy::default() })
+            .execute(CreateListDTO {
+                list_id: Uuid::new_v4(),
+                owner_id,
+                name: "Rustaceans".into(),
+                is_private: false,
+            }).await
+            .unwrap();
+        (SubscribeList { lists_repository: lists_repository.clone() })
+            .execute(ListAccessDTO { list_id: list.list_id, viewer_id: subscriber_id }).await
+            .unwrap();
+
+        let update_list = UpdateList { lists_repository: lists_repository.clone() };
+        let update = |owner_id: Uuid| UpdateListDTO {
+            list_id: list.list_id,
+            owner_id,
+            name: "Secret crabs".into(),
+            is_private: true,
+        };
+
+        assert_eq!(
+            update_list.execute(update(subscriber_id)).await.unwrap_err(),
+            HearthError::Forbidden(NOT_LIST_OWNER_ERROR_CODE.into())
+        );
+
+        let updated = update_list.execute(update(owner_id)).await.unwrap();
+        assert!(updated.is_private);
+        assert!(lists_repository.subscriptions(&subscriber_id).await.unwrap().is_empty());
+    }
+}
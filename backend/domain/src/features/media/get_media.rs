@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::media::MediaDTO,
+    features::feature::Feature,
+    repositories::media_repository::MediaRepository,
+};
+
+/// Lets uploaders poll a media until it is processed.
+pub type GetMediaFeature = dyn Feature<Uuid, MediaDTO>;
+
+pub struct GetMedia {
+    pub media_repository: BArc<dyn MediaRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, MediaDTO> for GetMedia {
+    async fn execute(&self, media_id: Uuid) -> Result<MediaDTO, HearthError> {
+        self.media_repository.get(&media_id).await
+    }
+}
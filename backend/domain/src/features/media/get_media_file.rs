@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::media::{ MediaDTO, MediaFileDTO, MediaFileRequestDTO },
+    error_codes::MEDIA_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{ media_repository::MediaRepository, object_store::ObjectStore },
+};
+
+pub type GetMediaFileFeature = dyn Feature<MediaFileRequestDTO, MediaFileDTO>;
+
+pub struct GetMediaFile {
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub object_store: BArc<dyn ObjectStore>,
+}
+
+#[async_trait]
+impl Feature<MediaFileRequestDTO, MediaFileDTO> for GetMediaFile {
+    async fn execute(&self, input: MediaFileRequestDTO) -> Result<MediaFileDTO, HearthError> {
+        let media = self.media_repository.get(&input.media_id).await?;
+
+        // Only known variants may be read, the key is never built from raw input.
+        let variant = media.variants
+            .iter()
+            .find(|v| v.name == input.variant)
+            .ok_or_else(|| HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()))?;
+
+        let bytes = self.object_store.get(
+            &MediaDTO::storage_key(&media.media_id, &variant.name)
+        ).await?;
+
+        Ok(MediaFileDTO { mime_type: variant.mime_type.clone(), bytes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::media::{ MediaFileRequestDTO, ORIGINAL_VARIANT, UploadMediaDTO },
+        error_codes::MEDIA_NOT_FOUND_ERROR_CODE,
+        features::{
+            feature::Feature,
+            media::{
+                get_media_file::GetMediaFile,
+                process_media::ProcessMedia,
+                upload_media::UploadMedia,
+            },
+        },
+        repositories::{ media_repository::MediaRepository, object_store::ObjectStore },
+        test_utils::test_utils::{ FAKE_JPEG, InMemoryMediaRepository, InMemoryObjectStore },
+    };
+
+    #[tokio::test]
+    async fn should_read_existing_variants_only() {
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let object_store: BArc<dyn ObjectStore> = barc!(InMemoryObjectStore::default());
+        let upload_media = UploadMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            ..Default::default()
+        };
+        let process_media = ProcessMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            ..Default::default()
+        };
+        let media = upload_media
+            .execute(UploadMediaDTO {
+                media_id: Uuid::new_v4(),
+                owner_id: Uuid::new_v4(),
+                bytes: FAKE_JPEG.to_vec(),
+            }).await
+            .unwrap();
+        process_media.execute(media.media_id).await.unwrap();
+
+        let get_media_file = GetMediaFile { media_repository, object_store };
+
+        let file = get_media_file
+            .execute(MediaFileRequestDTO { media_id: media.media_id, variant: ORIGINAL_VARIANT.into() }).await
+            .unwrap();
+        assert_eq!(file.bytes, FAKE_JPEG);
+        assert_eq!(file.mime_type, "image/jpeg");
+
+        let result = get_media_file.execute(MediaFileRequestDTO {
+            media_id: media.media_id,
+            variant: "../../etc/passwd".into(),
+        }).await;
+        assert_eq!(result.unwrap_err(), HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod get_media;
+pub mod get_media_file;
+pub mod process_media;
+pub mod set_avatar;
+pub mod upload_media;
@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::media::{ MediaDTO, MediaStatus, MediaVariantDTO },
+    error_codes::MEDIA_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    policies::media::MediaPolicy,
+    repositories::{
+        media_processor::MediaProcessor,
+        media_repository::MediaRepository,
+        object_store::ObjectStore,
+    },
+};
+
+/// Resizes a pending upload into its variants. Files that can't be decoded
+/// or don't fit in the owner's quota once processed are marked as failed,
+/// retrying wouldn't change the outcome. `None` when the media was deleted
+/// in the meantime. Meant to be run by the worker.
+pub type ProcessMediaFeature = dyn Feature<Uuid, Option<MediaDTO>>;
+
+pub struct ProcessMedia {
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub object_store: BArc<dyn ObjectStore>,
+    pub media_processor: BArc<dyn MediaProcessor>,
+    pub policy: MediaPolicy,
+}
+
+impl ProcessMedia {
+    async fn finish(&self, media: MediaDTO) -> Result<Option<MediaDTO>, HearthError> {
+        self.media_repository.update(media.clone()).await?;
+        self.object_store.delete(&MediaDTO::upload_key(&media.media_id)).await?;
+
+        Ok(Some(media))
+    }
+}
+
+#[async_trait]
+impl Feature<Uuid, Option<MediaDTO>> for ProcessMedia {
+    async fn execute(&self, media_id: Uuid) -> Result<Option<MediaDTO>, HearthError> {
+        let media = match self.media_repository.get(&media_id).await {
+            Ok(media) => media,
+            Err(e) if e == HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()) => {
+                return Ok(None);
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        // Already done by a previous run of the job.
+        if media.status != MediaStatus::Pending {
+            return Ok(Some(media));
+        }
+
+        let bytes = self.object_store.get(&MediaDTO::upload_key(&media.media_id)).await?;
+
+        let processed = match self.media_processor.process(bytes).await {
+            Ok(processed) => processed,
+            Err(HearthError::Domain(_)) => {
+                return self.finish(MediaDTO { status: MediaStatus::Failed, ..media }).await;
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let ready = MediaDTO {
+            status: MediaStatus::Ready,
+            blurhash: processed.blurhash,
+            variants: processed.files
+                .iter()
+                .map(|file| MediaVariantDTO {
+                    name: file.variant.clone(),
+                    mime_type: file.mime_type.clone(),
+                    width: file.width,
+                    height: file.height,
+                    size_bytes: file.bytes.len() as u64,
+                })
+                .collect(),
+            ..media.clone()
+        };
+
+        // Quota is enforced on what actually gets stored, variants included.
+        let used = self.media_repository.total_bytes(&ready.owner_id).await?;
+        if used + ready.size_bytes() > self.policy.quota_bytes {
+            return self.finish(MediaDTO { status: MediaStatus::Failed, ..media }).await;
+        }
+
+        for file in processed.files {
+            self.object_store.put(
+                &MediaDTO::storage_key(&ready.media_id, &file.variant),
+                file.bytes,
+                &file.mime_type
+            ).await?;
+        }
+
+        self.finish(ready).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::media::{ MediaDTO, MediaStatus, UploadMediaDTO },
+        features::{
+            feature::Feature,
+            media::{ process_media::ProcessMedia, upload_media::UploadMedia },
+        },
+        policies::media::MediaPolicy,
+        repositories::{ media_repository::MediaRepository, object_store::ObjectStore },
+        test_utils::test_utils::{
+            FAKE_JPEG,
+            FakeMediaProcessor,
+            InMemoryMediaRepository,
+            InMemoryObjectStore,
+        },
+    };
+
+    impl Default for ProcessMedia {
+        fn default() -> Self {
+            Self {
+                media_repository: barc!(InMemoryMediaRepository::default()),
+                object_store: barc!(InMemoryObjectStore::default()),
+                media_processor: barc!(FakeMediaProcessor),
+                policy: MediaPolicy::default(),
+            }
+        }
+    }
+
+    fn upload(owner_id: Uuid) -> UploadMediaDTO {
+        UploadMediaDTO { media_id: Uuid::new_v4(), owner_id, bytes: FAKE_JPEG.to_vec() }
+    }
+
+    #[tokio::test]
+    async fn should_store_every_variant_and_drop_the_upload() {
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let object_store: BArc<dyn ObjectStore> = barc!(InMemoryObjectStore::default());
+        let upload_media = UploadMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            ..Default::default()
+        };
+        let process_media = ProcessMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            ..Default::default()
+        };
+        let pending = upload_media.execute(upload(Uuid::new_v4())).await.unwrap();
+
+        let media = process_media.execute(pending.media_id).await.unwrap().unwrap();
+
+        assert_eq!(media.status, MediaStatus::Ready);
+        assert!(!media.blurhash.is_empty());
+        assert_eq!(media_repository.get(&media.media_id).await.unwrap(), media);
+        for variant in &media.variants {
+            let key = MediaDTO::storage_key(&media.media_id, &variant.name);
+            assert!(object_store.get(&key).await.is_ok());
+        }
+        assert!(object_store.get(&MediaDTO::upload_key(&media.media_id)).await.is_err());
+
+        // A second run of the job leaves it as is.
+        assert_eq!(process_media.execute(media.media_id).await.unwrap(), Some(media));
+    }
+
+    #[tokio::test]
+    async fn should_fail_media_over_the_quota_once_processed() {
+        let owner_id = Uuid::new_v4();
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let object_store: BArc<dyn ObjectStore> = barc!(InMemoryObjectStore::default());
+        let policy = MediaPolicy { quota_bytes: (FAKE_JPEG.len() as u64) * 3, ..Default::default() };
+        let upload_media = UploadMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            policy: policy.clone(),
+            ..Default::default()
+        };
+        let process_media = ProcessMedia { media_repository, object_store, policy, ..Default::default() };
+        let first = upload_media.execute(upload(owner_id)).await.unwrap();
+        let second = upload_media.execute(upload(owner_id)).await.unwrap();
+
+        // The fake processor stores the original plus one thumbnail.
+        let first = process_media.execute(first.media_id).await.unwrap().unwrap();
+        let second = process_media.execute(second.media_id).await.unwrap().unwrap();
+
+        assert_eq!(first.status, MediaStatus::Ready);
+        assert_eq!(second.status, MediaStatus::Failed);
+        assert!(second.variants.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_skip_media_deleted_before_processing() {
+        assert_eq!(ProcessMedia::default().execute(Uuid::new_v4()).await.unwrap(), None);
+    }
+}
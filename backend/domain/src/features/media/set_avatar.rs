@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::media::{ MediaStatus, SetAvatarDTO },
+    error_codes::{ MEDIA_NOT_OWNED_ERROR_CODE, MEDIA_NOT_READY_ERROR_CODE },
+    features::feature::Feature,
+    repositories::{ media_repository::MediaRepository, users_repository::UsersRepository },
+};
+
+pub type SetAvatarFeature = dyn Feature<SetAvatarDTO, ()>;
+
+pub struct SetAvatar {
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+}
+
+#[async_trait]
+impl Feature<SetAvatarDTO, ()> for SetAvatar {
+    async fn execute(&self, input: SetAvatarDTO) -> Result<(), HearthError> {
+        if let Some(media_id) = input.media_id {
+            let media = self.media_repository.get(&media_id).await?;
+
+            if media.owner_id != input.user_id {
+                return Err(HearthError::Forbidden(MEDIA_NOT_OWNED_ERROR_CODE.into()));
+            }
+
+            if media.status != MediaStatus::Ready {
+                return Err(HearthError::Domain(MEDIA_NOT_READY_ERROR_CODE.into()));
+            }
+        }
+
+        self.users_repository.set_avatar(&input.user_id, input.media_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{ auth::CredentialsDTO, media::{ SetAvatarDTO, UploadMediaDTO }, user::CreateUserDTO },
+        error_codes::{ MEDIA_NOT_OWNED_ERROR_CODE, MEDIA_NOT_READY_ERROR_CODE },
+        features::{
+            feature::Feature,
+            media::{ process_media::ProcessMedia, set_avatar::SetAvatar, upload_media::UploadMedia },
+        },
+        repositories::{
+            media_repository::MediaRepository,
+            object_store::ObjectStore,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            FAKE_JPEG,
+            InMemoryMediaRepository,
+            InMemoryObjectStore,
+            InMemoryUserRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_only_accept_own_processed_media_as_avatar() {
+        let user_id = Uuid::new_v4();
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: "john.smith@gmail.com".into(),
+                    birthday: chrono::NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            )
+        );
+        let object_store: BArc<dyn ObjectStore> = barc!(InMemoryObjectStore::default());
+        let upload_media = UploadMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            ..Default::default()
+        };
+        let process_media = ProcessMedia {
+            media_repository: media_repository.clone(),
+            object_store,
+            ..Default::default()
+        };
+        let mine = upload_media
+            .execute(UploadMediaDTO { media_id: Uuid::new_v4(), owner_id: user_id, bytes: FAKE_JPEG.to_vec() }).await
+            .unwrap();
+        let theirs = upload_media
+            .execute(UploadMediaDTO {
+                media_id: Uuid::new_v4(),
+                owner_id: Uuid::new_v4(),
+                bytes: FAKE_JPEG.to_vec(),
+            }).await
+            .unwrap();
+
+        let set_avatar = SetAvatar { media_repository, users_repository: users_repository.clone() };
+
+        let result = set_avatar.execute(SetAvatarDTO { user_id, media_id: Some(theirs.media_id) }).await;
+        assert_eq!(result.unwrap_err(), HearthError::Forbidden(MEDIA_NOT_OWNED_ERROR_CODE.into()));
+
+        let result = set_avatar.execute(SetAvatarDTO { user_id, media_id: Some(mine.media_id) }).await;
+        assert_eq!(result.unwrap_err(), HearthError::Domain(MEDIA_NOT_READY_ERROR_CODE.into()));
+
+        process_media.execute(mine.media_id).await.unwrap();
+        set_avatar.execute(SetAvatarDTO { user_id, media_id: Some(mine.media_id) }).await.unwrap();
+        let user = users_repository.get(user_id.to_string()).await.unwrap();
+        assert_eq!(user.avatar_media_id, Some(mine.media_id));
+    }
+}
@@ -0,0 +1,197 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::{ job::JobPayload, media::{ MediaDTO, MediaStatus, UploadMediaDTO } },
+    entities::jobs::Jobs,
+    error_codes::{
+        MEDIA_QUOTA_EXCEEDED_ERROR_CODE,
+        MEDIA_TOO_LARGE_ERROR_CODE,
+        MEDIA_TYPE_NOT_ALLOWED_ERROR_CODE,
+    },
+    features::feature::Feature,
+    policies::media::MediaPolicy,
+    repositories::{
+        job_queue::JobQueue,
+        media_processor::MediaProcessor,
+        media_repository::MediaRepository,
+        object_store::ObjectStore,
+    },
+};
+
+/// Stores the upload and hands it to the worker, the returned media stays
+/// pending until `ProcessMedia` ran.
+pub type UploadMediaFeature = dyn Feature<UploadMediaDTO, MediaDTO>;
+
+pub struct UploadMedia {
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub object_store: BArc<dyn ObjectStore>,
+    pub media_processor: BArc<dyn MediaProcessor>,
+    pub job_queue: BArc<dyn JobQueue>,
+    pub policy: MediaPolicy,
+}
+
+#[async_trait]
+impl Feature<UploadMediaDTO, MediaDTO> for UploadMedia {
+    async fn execute(&self, input: UploadMediaDTO) -> Result<MediaDTO, HearthError> {
+        if (input.bytes.len() as u64) > self.policy.max_upload_bytes {
+            return Err(HearthError::Domain(MEDIA_TOO_LARGE_ERROR_CODE.into()));
+        }
+
+        let mime_type = self.media_processor
+            .sniff_mime_type(&input.bytes)
+            .filter(|mime_type| self.policy.is_allowed(mime_type))
+            .ok_or_else(|| HearthError::Domain(MEDIA_TYPE_NOT_ALLOWED_ERROR_CODE.into()))?;
+
+        // Checked again on the variants once processed.
+        let used = self.media_repository.total_bytes(&input.owner_id).await?;
+        if used + (input.bytes.len() as u64) > self.policy.quota_bytes {
+            return Err(HearthError::Domain(MEDIA_QUOTA_EXCEEDED_ERROR_CODE.into()));
+        }
+
+        let media = MediaDTO {
+            media_id: input.media_id,
+            owner_id: input.owner_id,
+            mime_type,
+            status: MediaStatus::Pending,
+            blurhash: String::new(),
+            variants: vec![],
+            created_at: Utc::now(),
+        };
+
+        self.object_store.put(
+            &MediaDTO::upload_key(&media.media_id),
+            input.bytes,
+            &media.mime_type
+        ).await?;
+
+        self.media_repository.create(media.clone()).await?;
+
+        self.job_queue.enqueue(
+            Jobs::schedule(JobPayload::ProcessMedia { media_id: media.media_id }, media.created_at)
+        ).await?;
+
+        Ok(media)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{ job::JobPayload, media::{ MediaDTO, MediaStatus, UploadMediaDTO } },
+        error_codes::{
+            MEDIA_QUOTA_EXCEEDED_ERROR_CODE,
+            MEDIA_TOO_LARGE_ERROR_CODE,
+            MEDIA_TYPE_NOT_ALLOWED_ERROR_CODE,
+        },
+        features::{
+            feature::Feature,
+            media::{ process_media::ProcessMedia, upload_media::UploadMedia },
+        },
+        policies::media::MediaPolicy,
+        repositories::{ media_repository::MediaRepository, object_store::ObjectStore },
+        test_utils::test_utils::{
+            FAKE_JPEG,
+            FakeMediaProcessor,
+            InMemoryJobQueue,
+            InMemoryMediaRepository,
+            InMemoryObjectStore,
+        },
+    };
+
+    impl Default for UploadMedia {
+        fn default() -> Self {
+            Self {
+                media_repository: barc!(InMemoryMediaRepository::default()),
+                object_store: barc!(InMemoryObjectStore::default()),
+                media_processor: barc!(FakeMediaProcessor),
+                job_queue: barc!(InMemoryJobQueue::default()),
+                policy: MediaPolicy::default(),
+            }
+        }
+    }
+
+    fn upload(owner_id: Uuid, bytes: &[u8]) -> UploadMediaDTO {
+        UploadMediaDTO { media_id: Uuid::new_v4(), owner_id, bytes: bytes.to_vec() }
+    }
+
+    #[tokio::test]
+    async fn should_store_the_upload_and_enqueue_its_processing() {
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let object_store: BArc<dyn ObjectStore> = barc!(InMemoryObjectStore::default());
+        let job_queue = InMemoryJobQueue::default();
+        let upload_media = UploadMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            job_queue: barc!(job_queue.clone()),
+            ..Default::default()
+        };
+
+        let media = upload_media.execute(upload(Uuid::new_v4(), FAKE_JPEG)).await.unwrap();
+
+        assert_eq!(media.mime_type, "image/jpeg");
+        assert_eq!(media.status, MediaStatus::Pending);
+        assert!(media.variants.is_empty());
+        assert_eq!(media_repository.get(&media.media_id).await.unwrap(), media);
+        assert_eq!(object_store.get(&MediaDTO::upload_key(&media.media_id)).await.unwrap(), FAKE_JPEG);
+
+        let jobs = job_queue.jobs.lock().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].payload, JobPayload::ProcessMedia { media_id: media.media_id });
+    }
+
+    #[tokio::test]
+    async fn should_reject_types_outside_the_allowlist() {
+        let result = UploadMedia::default().execute(
+            upload(Uuid::new_v4(), b"<svg onload=alert(1)>")
+        ).await;
+
+        assert_eq!(result.unwrap_err(), HearthError::Domain(MEDIA_TYPE_NOT_ALLOWED_ERROR_CODE.into()));
+    }
+
+    #[tokio::test]
+    async fn should_reject_uploads_over_the_size_limit() {
+        let upload_media = UploadMedia {
+            policy: MediaPolicy { max_upload_bytes: 2, ..Default::default() },
+            ..Default::default()
+        };
+
+        let result = upload_media.execute(upload(Uuid::new_v4(), FAKE_JPEG)).await;
+
+        assert_eq!(result.unwrap_err(), HearthError::Domain(MEDIA_TOO_LARGE_ERROR_CODE.into()));
+    }
+
+    #[tokio::test]
+    async fn should_enforce_per_user_quota() {
+        let owner_id = Uuid::new_v4();
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let object_store: BArc<dyn ObjectStore> = barc!(InMemoryObjectStore::default());
+        let policy = MediaPolicy { quota_bytes: (FAKE_JPEG.len() as u64) * 2, ..Default::default() };
+        let upload_media = UploadMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            policy: policy.clone(),
+            ..Default::default()
+        };
+        let process_media = ProcessMedia { media_repository, object_store, policy, ..Default::default() };
+
+        // The fake processor stores the original plus one thumbnail.
+        let media = upload_media.execute(upload(owner_id, FAKE_JPEG)).await.unwrap();
+        process_media.execute(media.media_id).await.unwrap();
+        let result = upload_media.execute(upload(owner_id, FAKE_JPEG)).await;
+        assert_eq!(result.unwrap_err(), HearthError::Domain(MEDIA_QUOTA_EXCEEDED_ERROR_CODE.into()));
+
+        // Someone else still has room.
+        assert!(upload_media.execute(upload(Uuid::new_v4(), FAKE_JPEG)).await.is_ok());
+    }
+}
@@ -1,2 +1,26 @@
+pub mod auth;
+pub mod blocks;
+pub mod bookmarks;
+pub mod conversations;
+pub mod data_exports;
+pub mod device_keys;
+pub mod events;
 pub mod feature;
+pub mod filters;
+pub mod imports;
+pub mod jobs;
+pub mod lists;
+pub mod media;
+pub mod moderation;
+pub mod notifications;
+pub mod oauth;
+pub mod oidc;
+pub mod passkeys;
+pub mod personal_access_tokens;
+pub mod polls;
+pub mod posts;
+pub mod rate_limit;
 pub mod signup;
+pub mod trends;
+pub mod two_factor;
+pub mod users;
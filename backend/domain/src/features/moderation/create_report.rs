@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::moderation::{ CreateReportDTO, ReportDTO, ReportState, ReportTargetType },
+    entities::conversations::Conversations,
+    error_codes::{ CANNOT_REPORT_SELF_ERROR_CODE, MESSAGE_NOT_FOUND_ERROR_CODE },
+    features::feature::Feature,
+    repositories::{
+        conversations_repository::ConversationsRepository,
+        posts_repository::PostsRepository,
+        reports_repository::ReportsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type CreateReportFeature = dyn Feature<CreateReportDTO, ReportDTO>;
+
+/// Files a report for the moderators. Reporting the same target again while
+/// the first report is open returns that report.
+pub struct CreateReport {
+    pub reports_repository: BArc<dyn ReportsRepository>,
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub conversations_repository: BArc<dyn ConversationsRepository>,
+}
+
+impl CreateReport {
+    /// The user accountable for the target. Messages can only be reported
+    /// by the participants of their conversation.
+    async fn target_user_id(&self, input: &CreateReportDTO) -> Result<Uuid, HearthError> {
+        match input.target_type {
+            ReportTargetType::Post => {
+                Ok(self.posts_repository.get(&input.target_id).await?.author_id)
+            }
+            ReportTargetType::User => {
+                Ok(self.users_repository.get(input.target_id.to_string()).await?.user_id)
+            }
+            ReportTargetType::Message => {
+                let message = self.conversations_repository
+                    .find_message(&input.target_id).await?
+                    .ok_or_else(|| HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into()))?;
+
+                let conversation = self.conversations_repository.get(&message.conversation_id).await?;
+                Conversations::check_participant(&conversation, &input.reporter_id).map_err(|_| {
+                    HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into())
+                })?;
+
+                Ok(message.sender_id)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Feature<CreateReportDTO, ReportDTO> for CreateReport {
+    async fn execute(&self, input: CreateReportDTO) -> Result<ReportDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("CREATE_REPORT".into(), e));
+        }
+
+        let target_user_id = self.target_user_id(&input).await?;
+        if target_user_id == input.reporter_id {
+            return Err(HearthError::Domain(CANNOT_REPORT_SELF_ERROR_CODE.into()));
+        }
+
+        let existing = self.reports_repository
+            .find_open(&input.reporter_id, input.target_type, &input.target_id).await?;
+        if let Some(report) = existing {
+            return Ok(report);
+        }
+
+        let report = ReportDTO {
+            report_id: input.report_id,
+            reporter_id: input.reporter_id,
+            target_type: input.target_type,
+            target_id: input.target_id,
+            target_user_id,
+            reason: input.reason,
+            details: input.details.trim().to_string(),
+            state: ReportState::Open,
+            created_at: Utc::now(),
+            resolved_by: None,
+            resolved_at: None,
+        };
+
+        self.reports_repository.create(report.clone()).await?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            conversation::{ ConversationDTO, MessageDTO },
+            moderation::{ CreateReportDTO, ReportReason, ReportTargetType },
+        },
+        error_codes::{ CANNOT_REPORT_SELF_ERROR_CODE, MESSAGE_NOT_FOUND_ERROR_CODE },
+        features::{ feature::Feature, moderation::create_report::CreateReport },
+        repositories::conversations_repository::ConversationsRepository,
+        test_utils::test_utils::{
+            InMemoryConversationsRepository,
+            InMemoryPostsRepository,
+            InMemoryReportsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_only_let_participants_report_a_message() {
+        let conversations_repository: BArc<dyn ConversationsRepository> = barc!(
+            InMemoryConversationsRepository::default()
+        );
+        let create_report = CreateReport {
+            reports_repository: barc!(InMemoryReportsRepository::default()),
+            posts_repository: barc!(InMemoryPostsRepository::default()),
+            users_repository: barc!(InMemoryUserRepository::default()),
+            conversations_repository: conversations_repository.clone(),
+        };
+
+        let (alice, bob, eve) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let conversation_id = Uuid::new_v4();
+        conversations_repository
+            .create(ConversationDTO {
+                conversation_id,
+                participant_ids: vec![alice, bob],
+                read_positions: vec![],
+                created_at: Utc::now(),
+                last_message_at: None,
+            }).await
+            .unwrap();
+
+        let message_id = Uuid::new_v4();
+        conversations_repository
+            .add_message(MessageDTO {
+                message_id,
+                conversation_id,
+                sender_id: bob,
+                content: "Buy my course".into(),
+                media_ids: vec![],
+                created_at: Utc::now(),
+            }).await
+            .unwrap();
+
+        let dto = |reporter_id| CreateReportDTO {
+            report_id: Uuid::new_v4(),
+            reporter_id,
+            target_type: ReportTargetType::Message,
+            target_id: message_id,
+            reason: ReportReason::Spam,
+            details: " unsolicited ".into(),
+        };
+
+        assert_eq!(
+            create_report.execute(dto(eve)).await.unwrap_err(),
+            HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into())
+        );
+        assert_eq!(
+            create_report.execute(dto(bob)).await.unwrap_err(),
+            HearthError::Domain(CANNOT_REPORT_SELF_ERROR_CODE.into())
+        );
+
+        let report = create_report.execute(dto(alice)).await.unwrap();
+        assert_eq!(report.target_user_id, bob);
+        assert_eq!(report.details, "unsolicited");
+
+        let again = create_report.execute(dto(alice)).await.unwrap();
+        assert_eq!(again.report_id, report.report_id);
+    }
+}
@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        moderation::{ ListModerationLogDTO, ModerationLogEntryDTO },
+        pagination::{ Page, TimelineCursor },
+    },
+    entities::moderation::Moderation,
+    features::feature::Feature,
+    repositories::{
+        moderation_log_repository::ModerationLogRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type ListModerationLogFeature = dyn Feature<ListModerationLogDTO, Page<ModerationLogEntryDTO>>;
+
+pub struct ListModerationLog {
+    pub moderation_log_repository: BArc<dyn ModerationLogRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+}
+
+#[async_trait]
+impl Feature<ListModerationLogDTO, Page<ModerationLogEntryDTO>> for ListModerationLog {
+    async fn execute(
+        &self,
+        input: ListModerationLogDTO
+    ) -> Result<Page<ModerationLogEntryDTO>, HearthError> {
+        if let Err(e) = input.page.validate() {
+            return Err(HearthError::Validation("LIST_MODERATION_LOG".into(), e));
+        }
+
+        let moderator = self.users_repository.get(input.moderator_id.to_string()).await?;
+        Moderation::check_moderator(&moderator)?;
+
+        let cursor = input.page.timeline_cursor()?;
+        let entries = self.moderation_log_repository.list(cursor, input.page.limit + 1).await?;
+
+        Ok(
+            Page::from_overfetched(entries, input.page.limit, |e| {
+                (TimelineCursor { created_at: e.created_at, id: e.entry_id }).encode()
+            })
+        )
+    }
+}
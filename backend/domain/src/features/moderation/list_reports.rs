@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        moderation::{ ListReportsDTO, ReportDTO },
+        pagination::{ Page, TimelineCursor },
+    },
+    entities::moderation::Moderation,
+    features::feature::Feature,
+    repositories::{ reports_repository::ReportsRepository, users_repository::UsersRepository },
+};
+
+pub type ListReportsFeature = dyn Feature<ListReportsDTO, Page<ReportDTO>>;
+
+/// The moderation queue.
+pub struct ListReports {
+    pub reports_repository: BArc<dyn ReportsRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+}
+
+#[async_trait]
+impl Feature<ListReportsDTO, Page<ReportDTO>> for ListReports {
+    async fn execute(&self, input: ListReportsDTO) -> Result<Page<ReportDTO>, HearthError> {
+        if let Err(e) = input.page.validate() {
+            return Err(HearthError::Validation("LIST_REPORTS".into(), e));
+        }
+
+        let moderator = self.users_repository.get(input.moderator_id.to_string()).await?;
+        Moderation::check_moderator(&moderator)?;
+
+        let cursor = input.page.timeline_cursor()?;
+        let reports = self.reports_repository.list(input.state, cursor, input.page.limit + 1).await?;
+
+        Ok(
+            Page::from_overfetched(reports, input.page.limit, |r| {
+                (TimelineCursor { created_at: r.created_at, id: r.report_id }).encode()
+            })
+        )
+    }
+}
@@ -0,0 +1,6 @@
+pub mod create_report;
+pub mod list_moderation_log;
+pub mod list_reports;
+pub mod resolve_report;
+pub mod set_user_role;
+pub mod suspend_user;
@@ -0,0 +1,314 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        moderation::{
+            ModerationAction,
+            ModerationLogEntryDTO,
+            ReportDTO,
+            ReportState,
+            ReportTargetType,
+            ResolveReportDTO,
+        },
+        notification::{ NotificationDTO, NotificationKind },
+        user::{ Role, UserDTO, UserStatus },
+    },
+    entities::moderation::Moderation,
+    error_codes::{
+        INVALID_MODERATION_ACTION_ERROR_CODE,
+        NOT_ADMIN_ERROR_CODE,
+        REPORT_ALREADY_RESOLVED_ERROR_CODE,
+    },
+    features::feature::Feature,
+    repositories::{
+        moderation_log_repository::ModerationLogRepository,
+        notifications_repository::NotificationsRepository,
+        posts_repository::PostsRepository,
+        reports_repository::ReportsRepository,
+        sessions_repository::SessionsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type ResolveReportFeature = dyn Feature<ResolveReportDTO, ReportDTO>;
+
+/// Takes a moderator action on an open report and records it in the audit log.
+pub struct ResolveReport {
+    pub reports_repository: BArc<dyn ReportsRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub notifications_repository: BArc<dyn NotificationsRepository>,
+    pub moderation_log_repository: BArc<dyn ModerationLogRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+}
+
+impl ResolveReport {
+    /// Applies the action, returning what it was applied to.
+    async fn apply(
+        &self,
+        moderator: &UserDTO,
+        report: &ReportDTO,
+        input: &ResolveReportDTO
+    ) -> Result<(ReportTargetType, Uuid), HearthError> {
+        match input.action {
+            ModerationAction::HidePost => {
+                if report.target_type != ReportTargetType::Post {
+                    return Err(HearthError::Domain(INVALID_MODERATION_ACTION_ERROR_CODE.into()));
+                }
+
+                self.posts_repository.hide(&report.target_id).await?;
+                Ok((ReportTargetType::Post, report.target_id))
+            }
+            ModerationAction::SuspendUser => {
+                // Staff can only be suspended by an admin.
+                let target = self.users_repository.get(report.target_user_id.to_string()).await?;
+                if target.role.can_moderate() && moderator.role != Role::Admin {
+                    return Err(HearthError::Forbidden(NOT_ADMIN_ERROR_CODE.into()));
+                }
+
+                Moderation::check_suspension_expiry(input.suspended_until, Utc::now())?;
+                self.users_repository.set_status(
+                    &target.user_id,
+                    UserStatus::Suspended,
+                    input.suspended_until
+                ).await?;
+                self.sessions_repository.revoke_all(&target.user_id).await?;
+                Ok((ReportTargetType::User, target.user_id))
+            }
+            ModerationAction::Warn => {
+                self.notifications_repository.create(NotificationDTO {
+                    notification_id: Uuid::new_v4(),
+                    recipient_id: report.target_user_id,
+                    actor_id: report.target_user_id,
+                    kind: NotificationKind::ModerationWarning,
+                    post_id: (report.target_type == ReportTargetType::Post).then_some(report.target_id),
+                    list_id: None,
+                    filtered: vec![],
+                    created_at: Utc::now(),
+                }).await?;
+                Ok((ReportTargetType::User, report.target_user_id))
+            }
+            ModerationAction::Dismiss => Ok((report.target_type, report.target_id)),
+            ModerationAction::ChangeRole => {
+                Err(HearthError::Domain(INVALID_MODERATION_ACTION_ERROR_CODE.into()))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Feature<ResolveReportDTO, ReportDTO> for ResolveReport {
+    async fn execute(&self, input: ResolveReportDTO) -> Result<ReportDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("RESOLVE_REPORT".into(), e));
+        }
+
+        let moderator = self.users_repository.get(input.moderator_id.to_string()).await?;
+        Moderation::check_moderator(&moderator)?;
+
+        let state = Moderation::resolved_state(input.action)?;
+        let report = self.reports_repository.get(&input.report_id).await?;
+        if report.state != ReportState::Open {
+            return Err(HearthError::Domain(REPORT_ALREADY_RESOLVED_ERROR_CODE.into()));
+        }
+
+        let (target_type, target_id) = self.apply(&moderator, &report, &input).await?;
+
+        let now = Utc::now();
+        self.reports_repository.resolve(&report.report_id, state, &moderator.user_id, now).await?;
+        self.moderation_log_repository.append(ModerationLogEntryDTO {
+            entry_id: Uuid::new_v4(),
+            moderator_id: moderator.user_id,
+            personal_access_token_id: input.personal_access_token_id,
+            action: input.action,
+            report_id: Some(report.report_id),
+            target_type,
+            target_id,
+            note: input.note.trim().to_string(),
+            created_at: now,
+        }).await?;
+
+        Ok(ReportDTO {
+            state,
+            resolved_by: Some(moderator.user_id),
+            resolved_at: Some(now),
+            ..report
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            moderation::{
+                ModerationAction,
+                ReportDTO,
+                ReportReason,
+                ReportState,
+                ReportTargetType,
+                ResolveReportDTO,
+            },
+            post::PostDTO,
+            user::{ CreateUserDTO, Role, UserStatus },
+        },
+        error_codes::{
+            NOT_MODERATOR_ERROR_CODE,
+            POST_NOT_FOUND_ERROR_CODE,
+            REPORT_ALREADY_RESOLVED_ERROR_CODE,
+        },
+        features::{ feature::Feature, moderation::resolve_report::ResolveReport },
+        repositories::{
+            moderation_log_repository::ModerationLogRepository,
+            posts_repository::PostsRepository,
+            reports_repository::ReportsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryModerationLogRepository,
+            InMemoryNotificationsRepository,
+            InMemoryPostsRepository,
+            InMemoryReportsRepository,
+            InMemorySessionsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    struct Fixture {
+        resolve_report: ResolveReport,
+        reports_repository: BArc<dyn ReportsRepository>,
+        users_repository: BArc<dyn UsersRepository>,
+        posts_repository: BArc<dyn PostsRepository>,
+        moderation_log_repository: BArc<dyn ModerationLogRepository>,
+    }
+
+    fn fixture() -> Fixture {
+        let reports_repository: BArc<dyn ReportsRepository> = barc!(InMemoryReportsRepository::default());
+        let users_repository: BArc<dyn UsersRepository> = barc!(InMemoryUserRepository::default());
+        let posts_repository: BArc<dyn PostsRepository> = barc!(InMemoryPostsRepository::default());
+        let moderation_log_repository: BArc<dyn ModerationLogRepository> = barc!(
+            InMemoryModerationLogRepository::default()
+        );
+
+        Fixture {
+            resolve_report: ResolveReport {
+                reports_repository: reports_repository.clone(),
+                users_repository: users_repository.clone(),
+                posts_repository: posts_repository.clone(),
+                notifications_repository: barc!(InMemoryNotificationsRepository::default()),
+                moderation_log_repository: moderation_log_repository.clone(),
+                sessions_repository: barc!(InMemorySessionsRepository::default()),
+            },
+            reports_repository,
+            users_repository,
+            posts_repository,
+            moderation_log_repository,
+        }
+    }
+
+    async fn create_user(fixture: &Fixture, username: &str, role: Role) -> Uuid {
+        let user_id = Uuid::new_v4();
+        fixture.users_repository
+            .create(
+                CreateUserDTO {
+                    user_id,
+                    username: username.into(),
+                    email: format!("{}@gmail.com", username),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: "hash".into() }
+            ).await
+            .unwrap();
+        fixture.users_repository.set_role(&user_id, role).await.unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    async fn should_hide_a_reported_post_and_log_it() {
+        let fixture = fixture();
+        let moderator = create_user(&fixture, "moderator", Role::Moderator).await;
+        let author = create_user(&fixture, "author", Role::User).await;
+        let reporter = create_user(&fixture, "reporter", Role::User).await;
+
+        let post_id = Uuid::new_v4();
+        fixture.posts_repository
+            .create(PostDTO {
+                post_id,
+                author_id: author,
+                content: "spam spam spam".into(),
+                hashtags: vec![],
+                mentions: vec![],
+                media: vec![],
+                poll: None,
+                link_preview: None,
+                filtered: vec![],
+                sensitive: false,
+                age_gated: false,
+                created_at: Utc::now(),
+            }).await
+            .unwrap();
+
+        let report_id = Uuid::new_v4();
+        fixture.reports_repository
+            .create(ReportDTO {
+                report_id,
+                reporter_id: reporter,
+                target_type: ReportTargetType::Post,
+                target_id: post_id,
+                target_user_id: author,
+                reason: ReportReason::Spam,
+                details: String::new(),
+                state: ReportState::Open,
+                created_at: Utc::now(),
+                resolved_by: None,
+                resolved_at: None,
+            }).await
+            .unwrap();
+
+        let dto = |moderator_id, action| ResolveReportDTO {
+            moderator_id,
+            personal_access_token_id: None,
+            report_id,
+            action,
+            note: "obvious spam".into(),
+            suspended_until: None,
+        };
+
+        assert_eq!(
+            fixture.resolve_report.execute(dto(reporter, ModerationAction::Dismiss)).await.unwrap_err(),
+            HearthError::Forbidden(NOT_MODERATOR_ERROR_CODE.into())
+        );
+
+        let report = fixture.resolve_report.execute(dto(moderator, ModerationAction::HidePost)).await.unwrap();
+        assert_eq!(report.state, ReportState::Actioned);
+        assert_eq!(report.resolved_by, Some(moderator));
+        assert_eq!(
+            fixture.posts_repository.get(&post_id).await.unwrap_err(),
+            HearthError::not_found(POST_NOT_FOUND_ERROR_CODE.into())
+        );
+
+        let log = fixture.moderation_log_repository.list(None, 10).await.unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].action, ModerationAction::HidePost);
+        assert_eq!(log[0].report_id, Some(report_id));
+        assert_eq!(log[0].note, "obvious spam");
+
+        assert_eq!(
+            fixture.resolve_report.execute(dto(moderator, ModerationAction::SuspendUser)).await.unwrap_err(),
+            HearthError::Domain(REPORT_ALREADY_RESOLVED_ERROR_CODE.into())
+        );
+        let author = fixture.users_repository.get(author.to_string()).await.unwrap();
+        assert_eq!(author.status, UserStatus::Active);
+    }
+}
@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::moderation::{ ModerationAction, ModerationLogEntryDTO, ReportTargetType, SetUserRoleDTO },
+    entities::moderation::Moderation,
+    error_codes::INVALID_MODERATION_ACTION_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{
+        moderation_log_repository::ModerationLogRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type SetUserRoleFeature = dyn Feature<SetUserRoleDTO, ()>;
+
+/// Admin only. Admins can't change their own role so there is always one left.
+pub struct SetUserRole {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub moderation_log_repository: BArc<dyn ModerationLogRepository>,
+}
+
+#[async_trait]
+impl Feature<SetUserRoleDTO, ()> for SetUserRole {
+    async fn execute(&self, input: SetUserRoleDTO) -> Result<(), HearthError> {
+        let admin = self.users_repository.get(input.admin_id.to_string()).await?;
+        Moderation::check_admin(&admin)?;
+
+        if input.user_id == admin.user_id {
+            return Err(HearthError::Domain(INVALID_MODERATION_ACTION_ERROR_CODE.into()));
+        }
+
+        let user = self.users_repository.get(input.user_id.to_string()).await?;
+        if user.role == input.role {
+            return Ok(());
+        }
+
+        self.users_repository.set_role(&user.user_id, input.role).await?;
+        self.moderation_log_repository.append(ModerationLogEntryDTO {
+            entry_id: Uuid::new_v4(),
+            moderator_id: admin.user_id,
+            personal_access_token_id: input.personal_access_token_id,
+            action: ModerationAction::ChangeRole,
+            report_id: None,
+            target_type: ReportTargetType::User,
+            target_id: user.user_id,
+            note: format!("{} -> {}", user.role.as_str(), input.role.as_str()),
+            created_at: Utc::now(),
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            moderation::SetUserRoleDTO,
+            user::{ CreateUserDTO, Role },
+        },
+        error_codes::NOT_ADMIN_ERROR_CODE,
+        features::{ feature::Feature, moderation::set_user_role::SetUserRole },
+        repositories::{
+            moderation_log_repository::ModerationLogRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{ InMemoryModerationLogRepository, InMemoryUserRepository },
+    };
+
+    async fn create_user(users_repository: &BArc<dyn UsersRepository>, username: &str) -> Uuid {
+        let user_id = Uuid::new_v4();
+        users_repository
+            .create(
+                CreateUserDTO {
+                    user_id,
+                    username: username.into(),
+                    email: format!("{}@gmail.com", username),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: "hash".into() }
+            ).await
+            .unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    async fn should_only_let_admins_change_roles() {
+        let users_repository: BArc<dyn UsersRepository> = barc!(InMemoryUserRepository::default());
+        let moderation_log_repository: BArc<dyn ModerationLogRepository> = barc!(
+            InMemoryModerationLogRepository::default()
+        );
+        let set_user_role = SetUserRole {
+            users_repository: users_repository.clone(),
+            moderation_log_repository: moderation_log_repository.clone(),
+        };
+
+        let admin = create_user(&users_repository, "admin").await;
+        let user = create_user(&users_repository, "user").await;
+        users_repository.set_role(&admin, Role::Admin).await.unwrap();
+
+        let demote = SetUserRoleDTO {
+            admin_id: user,
+            personal_access_token_id: None,
+            user_id: admin,
+            role: Role::User,
+        };
+        assert_eq!(
+            set_user_role.execute(demote).await.unwrap_err(),
+            HearthError::Forbidden(NOT_ADMIN_ERROR_CODE.into())
+        );
+
+        // Through a bot, which the log keeps track of.
+        let token_id = Uuid::new_v4();
+        set_user_role
+            .execute(SetUserRoleDTO {
+                admin_id: admin,
+                personal_access_token_id: Some(token_id),
+                user_id: user,
+                role: Role::Moderator,
+            }).await
+            .unwrap();
+
+        assert_eq!(users_repository.get(user.to_string()).await.unwrap().role, Role::Moderator);
+        let log = moderation_log_repository.list(None, 10).await.unwrap();
+        assert_eq!(log[0].note, "user -> moderator");
+        assert_eq!(log[0].personal_access_token_id, Some(token_id));
+    }
+}
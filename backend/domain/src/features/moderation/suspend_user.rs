@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        moderation::{ ModerationAction, ModerationLogEntryDTO, ReportTargetType, SuspendUserDTO },
+        user::UserStatus,
+    },
+    entities::moderation::Moderation,
+    error_codes::CANNOT_SUSPEND_SELF_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{
+        moderation_log_repository::ModerationLogRepository,
+        sessions_repository::SessionsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type SuspendUserFeature = dyn Feature<SuspendUserDTO, ()>;
+
+/// Admin only. Suspends an account outside of any report and logs it out everywhere.
+pub struct SuspendUser {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub moderation_log_repository: BArc<dyn ModerationLogRepository>,
+}
+
+#[async_trait]
+impl Feature<SuspendUserDTO, ()> for SuspendUser {
+    async fn execute(&self, input: SuspendUserDTO) -> Result<(), HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("SUSPEND_USER".into(), e));
+        }
+
+        let admin = self.users_repository.get(input.admin_id.to_string()).await?;
+        Moderation::check_admin(&admin)?;
+
+        if input.user_id == admin.user_id {
+            return Err(HearthError::Domain(CANNOT_SUSPEND_SELF_ERROR_CODE.into()));
+        }
+
+        let now = Utc::now();
+        Moderation::check_suspension_expiry(input.until, now)?;
+
+        let user = self.users_repository.get(input.user_id.to_string()).await?;
+        self.users_repository.set_status(&user.user_id, UserStatus::Suspended, input.until).await?;
+        self.sessions_repository.revoke_all(&user.user_id).await?;
+
+        let note = match input.until {
+            Some(until) => format!("until {} {}", until.to_rfc3339(), input.note.trim()),
+            None => input.note.clone(),
+        };
+
+        self.moderation_log_repository.append(ModerationLogEntryDTO {
+            entry_id: Uuid::new_v4(),
+            moderator_id: admin.user_id,
+            personal_access_token_id: input.personal_access_token_id,
+            action: ModerationAction::SuspendUser,
+            report_id: None,
+            target_type: ReportTargetType::User,
+            target_id: user.user_id,
+            note: note.trim().to_string(),
+            created_at: now,
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            moderation::SuspendUserDTO,
+            user::{ CreateUserDTO, Role, UserStatus },
+        },
+        error_codes::{ INVALID_SUSPENSION_EXPIRY_ERROR_CODE, NOT_ADMIN_ERROR_CODE },
+        features::{ feature::Feature, moderation::suspend_user::SuspendUser },
+        repositories::{
+            moderation_log_repository::ModerationLogRepository,
+            sessions_repository::SessionsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryModerationLogRepository,
+            InMemorySessionsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    async fn create_user(users_repository: &BArc<dyn UsersRepository>, username: &str) -> Uuid {
+        let user_id = Uuid::new_v4();
+        users_repository
+            .create(
+                CreateUserDTO {
+                    user_id,
+                    username: username.into(),
+                    email: format!("{}@gmail.com", username),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: "hash".into() }
+            ).await
+            .unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    async fn should_suspend_temporarily_and_revoke_sessions() {
+        let users_repository: BArc<dyn UsersRepository> = barc!(InMemoryUserRepository::default());
+        let sessions_repository: BArc<dyn SessionsRepository> = barc!(
+            InMemorySessionsRepository::default()
+        );
+        let moderation_log_repository: BArc<dyn ModerationLogRepository> = barc!(
+            InMemoryModerationLogRepository::default()
+        );
+        let suspend_user = SuspendUser {
+            users_repository: users_repository.clone(),
+            sessions_repository: sessions_repository.clone(),
+            moderation_log_repository: moderation_log_repository.clone(),
+        };
+
+        let admin = create_user(&users_repository, "admin").await;
+        let user = create_user(&users_repository, "user").await;
+        users_repository.set_role(&admin, Role::Admin).await.unwrap();
+        sessions_repository.create("token", &user).await.unwrap();
+
+        let dto = |admin_id, until| SuspendUserDTO {
+            admin_id,
+            personal_access_token_id: None,
+            user_id: user,
+            until,
+            note: "spam wave".into(),
+        };
+
+        assert_eq!(
+            suspend_user.execute(dto(user, None)).await.unwrap_err(),
+            HearthError::Forbidden(NOT_ADMIN_ERROR_CODE.into())
+        );
+        assert_eq!(
+            suspend_user.execute(dto(admin, Some(Utc::now() - Duration::hours(1)))).await.unwrap_err(),
+            HearthError::Domain(INVALID_SUSPENSION_EXPIRY_ERROR_CODE.into())
+        );
+
+        let until = Utc::now() + Duration::days(7);
+        suspend_user.execute(dto(admin, Some(until))).await.unwrap();
+
+        let suspended = users_repository.get(user.to_string()).await.unwrap();
+        assert_eq!(suspended.status, UserStatus::Suspended);
+        assert_eq!(suspended.suspended_until, Some(until));
+        assert_eq!(sessions_repository.get_user_id("token").await.unwrap(), None);
+        assert_eq!(moderation_log_repository.list(None, 10).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        filter::FilterContext,
+        notification::{ ListNotificationsDTO, NotificationDTO },
+        pagination::{ Page, TimelineCursor },
+    },
+    entities::keyword_filters::KeywordFilters,
+    features::feature::Feature,
+    repositories::{
+        keyword_filters_repository::KeywordFiltersRepository,
+        notifications_repository::NotificationsRepository,
+        posts_repository::PostsRepository,
+    },
+};
+
+pub type ListNotificationsFeature = dyn Feature<ListNotificationsDTO, Page<NotificationDTO>>;
+
+/// Notifications about a post matching one of the recipient's keyword
+/// filters are marked, not dropped.
+pub struct ListNotifications {
+    pub notifications_repository: BArc<dyn NotificationsRepository>,
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub keyword_filters_repository: BArc<dyn KeywordFiltersRepository>,
+}
+
+#[async_trait]
+impl Feature<ListNotificationsDTO, Page<NotificationDTO>> for ListNotifications {
+    async fn execute(
+        &self,
+        input: ListNotificationsDTO
+    ) -> Result<Page<NotificationDTO>, HearthError> {
+        if let Err(e) = input.page.validate() {
+            return Err(HearthError::Validation("LIST_NOTIFICATIONS".into(), e));
+        }
+
+        let cursor = input.page.timeline_cursor()?;
+        let mut notifications = self.notifications_repository.list(
+            &input.user_id,
+            cursor,
+            input.page.limit + 1
+        ).await?;
+
+        let filters = KeywordFilters::active(
+            self.keyword_filters_repository.list(&input.user_id).await?,
+            FilterContext::Notifications,
+            Utc::now()
+        );
+
+        if !filters.is_empty() {
+            let post_ids: Vec<Uuid> = notifications.iter().filter_map(|n| n.post_id).collect();
+            let posts: HashMap<Uuid, String> = self.posts_repository
+                .get_many(&post_ids).await?
+                .into_iter()
+                .filter(|post| post.author_id != input.user_id)
+                .map(|post| (post.post_id, post.content))
+                .collect();
+
+            for notification in notifications.iter_mut() {
+                if let Some(content) = notification.post_id.and_then(|id| posts.get(&id)) {
+                    notification.filtered = KeywordFilters::matches(&filters, content);
+                }
+            }
+        }
+
+        Ok(
+            Page::from_overfetched(notifications, input.page.limit, |n| {
+                (TimelineCursor { created_at: n.created_at, id: n.notification_id }).encode()
+            })
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            notification::{ ListNotificationsDTO, NotificationDTO, NotificationKind },
+            pagination::PageRequest,
+        },
+        features::{ feature::Feature, notifications::list_notifications::ListNotifications },
+        repositories::notifications_repository::NotificationsRepository,
+        test_utils::test_utils::{
+            InMemoryKeywordFiltersRepository,
+            InMemoryNotificationsRepository,
+            InMemoryPostsRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_list_only_own_notifications_newest_first() {
+        let notifications_repository: BArc<dyn NotificationsRepository> = barc!(
+            InMemoryNotificationsRepository::default()
+        );
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        for (recipient_id, minutes) in [(user_id, 2), (Uuid::new_v4(), 1), (user_id, 0)] {
+            notifications_repository
+                .create(NotificationDTO {
+                    notification_id: Uuid::new_v4(),
+                    recipient_id,
+                    actor_id: Uuid::new_v4(),
+                    kind: NotificationKind::Mention,
+                    post_id: None,
+                    list_id: None,
+                    filtered: vec![],
+                    created_at: now - Duration::minutes(minutes),
+                }).await
+                .unwrap();
+        }
+
+        let list_notifications = ListNotifications {
+            notifications_repository,
+            posts_repository: barc!(InMemoryPostsRepository::default()),
+            keyword_filters_repository: barc!(InMemoryKeywordFiltersRepository::default()),
+        };
+        let page = list_notifications
+            .execute(ListNotificationsDTO {
+                user_id,
+                page: PageRequest { cursor: None, limit: 1 },
+            }).await
+            .unwrap();
+
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].created_at, now);
+
+        let page = list_notifications
+            .execute(ListNotificationsDTO {
+                user_id,
+                page: PageRequest { cursor: page.next_cursor, limit: 1 },
+            }).await
+            .unwrap();
+
+        assert_eq!(page.items.len(), 1);
+        assert!(page.next_cursor.is_none());
+    }
+}
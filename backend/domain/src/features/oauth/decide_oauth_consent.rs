@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::oauth::{ OAuthCodeDTO, OAuthConsentDecisionDTO, OAuthRedirectDTO },
+    entities::oauth::OAuth,
+    error_codes::INVALID_REDIRECT_URI_ERROR_CODE,
+    features::{ feature::Feature, oauth::get_oauth_consent::check_authorization_request },
+    policies::oauth::OAuthPolicy,
+    repositories::{
+        oauth_apps_repository::OAuthAppsRepository,
+        oauth_codes_repository::OAuthCodesRepository,
+    },
+};
+
+pub type DecideOAuthConsentFeature = dyn Feature<OAuthConsentDecisionDTO, OAuthRedirectDTO>;
+
+/// The user approved or denied the app, either way they are sent back to it.
+pub struct DecideOAuthConsent {
+    pub oauth_apps_repository: BArc<dyn OAuthAppsRepository>,
+    pub oauth_codes_repository: BArc<dyn OAuthCodesRepository>,
+    pub policy: OAuthPolicy,
+}
+
+#[async_trait]
+impl Feature<OAuthConsentDecisionDTO, OAuthRedirectDTO> for DecideOAuthConsent {
+    async fn execute(&self, input: OAuthConsentDecisionDTO) -> Result<OAuthRedirectDTO, HearthError> {
+        let request = input.request;
+        let (app, scopes) = check_authorization_request(&self.oauth_apps_repository, &request).await?;
+        let state = request.state.as_deref().unwrap_or_default();
+
+        let params = if input.approved {
+            let code = OAuth::generate_token();
+            self.oauth_codes_repository.create(
+                &hasher::hash!(code),
+                &(OAuthCodeDTO {
+                    client_id: app.client_id,
+                    user_id: request.user_id,
+                    redirect_uri: request.redirect_uri.clone(),
+                    scopes,
+                    code_challenge: request.code_challenge.clone(),
+                }),
+                self.policy.code_ttl_seconds
+            ).await?;
+
+            vec![("code", code), ("state", state.to_string())]
+        } else {
+            vec![("error", "access_denied".to_string()), ("state", state.to_string())]
+        };
+
+        let params: Vec<(&str, &str)> = params
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
+
+        let redirect_uri = OAuth::redirect(&request.redirect_uri, &params).ok_or_else(||
+            HearthError::Domain(INVALID_REDIRECT_URI_ERROR_CODE.into())
+        )?;
+
+        Ok(OAuthRedirectDTO { redirect_uri })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::oauth::{
+            OAuthAppDTO,
+            OAuthAuthorizationRequestDTO,
+            OAuthCodeDTO,
+            OAuthConsentDecisionDTO,
+            Scope,
+        },
+        features::{ feature::Feature, oauth::decide_oauth_consent::DecideOAuthConsent },
+        policies::oauth::OAuthPolicy,
+        repositories::{
+            oauth_apps_repository::OAuthAppsRepository,
+            oauth_codes_repository::OAuthCodesRepository,
+        },
+        test_utils::test_utils::{ InMemoryOAuthAppsRepository, InMemoryOAuthCodesRepository },
+    };
+
+    const REDIRECT_URI: &str = "https://app.example.com/callback";
+    const CODE_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    struct Setup {
+        feature: DecideOAuthConsent,
+        oauth_codes_repository: BArc<dyn OAuthCodesRepository>,
+        client_id: Uuid,
+    }
+
+    async fn setup() -> Setup {
+        let oauth_apps_repository: BArc<dyn OAuthAppsRepository> = barc!(
+            InMemoryOAuthAppsRepository::default()
+        );
+        let oauth_codes_repository: BArc<dyn OAuthCodesRepository> = barc!(
+            InMemoryOAuthCodesRepository::default()
+        );
+        let client_id = Uuid::new_v4();
+        oauth_apps_repository
+            .create(OAuthAppDTO {
+                client_id,
+                owner_id: Uuid::new_v4(),
+                name: "Client".into(),
+                redirect_uris: vec![REDIRECT_URI.into()],
+                scopes: vec![Scope::Read],
+                client_secret_hash: None,
+                created_at: Utc::now(),
+            }).await
+            .unwrap();
+
+        Setup {
+            feature: DecideOAuthConsent {
+                oauth_apps_repository,
+                oauth_codes_repository: oauth_codes_repository.clone(),
+                policy: OAuthPolicy::default(),
+            },
+            oauth_codes_repository,
+            client_id,
+        }
+    }
+
+    fn decision(client_id: Uuid, user_id: Uuid, approved: bool) -> OAuthConsentDecisionDTO {
+        OAuthConsentDecisionDTO {
+            request: OAuthAuthorizationRequestDTO {
+                user_id,
+                response_type: "code".into(),
+                client_id,
+                redirect_uri: REDIRECT_URI.into(),
+                scope: "read".into(),
+                state: Some("xyz".into()),
+                code_challenge: CODE_CHALLENGE.into(),
+                code_challenge_method: "S256".into(),
+            },
+            approved,
+        }
+    }
+
+    fn query_of(redirect_uri: &str) -> Vec<(String, String)> {
+        url::Url::parse(redirect_uri).unwrap().query_pairs().into_owned().collect()
+    }
+
+    #[tokio::test]
+    async fn should_redirect_with_a_code_for_the_approved_request() {
+        let setup = setup().await;
+        let user_id = Uuid::new_v4();
+
+        let redirect = setup.feature
+            .execute(decision(setup.client_id, user_id, true)).await
+            .unwrap();
+        let params = query_of(&redirect.redirect_uri);
+        let code = params
+            .iter()
+            .find(|(name, _)| name == "code")
+            .map(|(_, code)| code.clone())
+            .unwrap();
+
+        assert!(redirect.redirect_uri.starts_with(REDIRECT_URI));
+        assert!(params.contains(&("state".into(), "xyz".into())));
+        assert_eq!(
+            setup.oauth_codes_repository.take(&hasher::hash!(code)).await.unwrap(),
+            Some(OAuthCodeDTO {
+                client_id: setup.client_id,
+                user_id,
+                redirect_uri: REDIRECT_URI.into(),
+                scopes: vec![Scope::Read],
+                code_challenge: CODE_CHALLENGE.into(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_redirect_with_access_denied_when_refused() {
+        let setup = setup().await;
+
+        let redirect = setup.feature
+            .execute(decision(setup.client_id, Uuid::new_v4(), false)).await
+            .unwrap();
+
+        assert_eq!(
+            query_of(&redirect.redirect_uri),
+            vec![("error".into(), "access_denied".into()), ("state".into(), "xyz".into())]
+        );
+    }
+}
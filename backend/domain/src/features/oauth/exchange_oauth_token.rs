@@ -0,0 +1,365 @@
+use async_trait::async_trait;
+use chrono::{ Duration, Utc };
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::oauth::{ OAuthAccessTokenDTO, OAuthAppDTO, OAuthTokenDTO, OAuthTokenRequestDTO, Scope },
+    entities::oauth::OAuth,
+    error_codes::{
+        INVALID_CLIENT_ERROR_CODE,
+        INVALID_GRANT_ERROR_CODE,
+        INVALID_SCOPE_ERROR_CODE,
+        UNSUPPORTED_GRANT_TYPE_ERROR_CODE,
+    },
+    features::feature::Feature,
+    policies::oauth::OAuthPolicy,
+    repositories::{
+        oauth_apps_repository::OAuthAppsRepository,
+        oauth_codes_repository::OAuthCodesRepository,
+        oauth_tokens_repository::OAuthTokensRepository,
+    },
+};
+
+pub type ExchangeOAuthTokenFeature = dyn Feature<OAuthTokenRequestDTO, OAuthAccessTokenDTO>;
+
+/// Token endpoint, for the `authorization_code` and `client_credentials` grants.
+pub struct ExchangeOAuthToken {
+    pub oauth_apps_repository: BArc<dyn OAuthAppsRepository>,
+    pub oauth_codes_repository: BArc<dyn OAuthCodesRepository>,
+    pub oauth_tokens_repository: BArc<dyn OAuthTokensRepository>,
+    pub policy: OAuthPolicy,
+}
+
+/// The app behind `client_id`, confidential apps must also present their secret.
+pub(crate) async fn authenticate_client(
+    oauth_apps_repository: &BArc<dyn OAuthAppsRepository>,
+    client_id: &Uuid,
+    client_secret: Option<&str>
+) -> Result<OAuthAppDTO, HearthError> {
+    let invalid_client = || HearthError::Unauthorized(INVALID_CLIENT_ERROR_CODE.into());
+
+    let app = oauth_apps_repository.get(client_id).await?.ok_or_else(invalid_client)?;
+
+    match (&app.client_secret_hash, client_secret) {
+        (None, _) => Ok(app),
+        (Some(hash), Some(secret)) if *hash == hasher::hash!(secret) => Ok(app),
+        _ => Err(invalid_client()),
+    }
+}
+
+impl ExchangeOAuthToken {
+    async fn issue(
+        &self,
+        client_id: Uuid,
+        user_id: Uuid,
+        scopes: Vec<Scope>
+    ) -> Result<OAuthAccessTokenDTO, HearthError> {
+        let access_token = OAuth::generate_token();
+        let scope = Scope::join(&scopes);
+
+        self.oauth_tokens_repository.create(OAuthTokenDTO {
+            token_hash: hasher::hash!(access_token),
+            client_id,
+            user_id,
+            scopes,
+            expires_at: Utc::now() + Duration::seconds(self.policy.access_token_ttl_seconds as i64),
+            revoked_at: None,
+        }).await?;
+
+        Ok(OAuthAccessTokenDTO {
+            access_token,
+            token_type: "Bearer".into(),
+            expires_in: self.policy.access_token_ttl_seconds,
+            scope,
+        })
+    }
+}
+
+#[async_trait]
+impl Feature<OAuthTokenRequestDTO, OAuthAccessTokenDTO> for ExchangeOAuthToken {
+    async fn execute(&self, input: OAuthTokenRequestDTO) -> Result<OAuthAccessTokenDTO, HearthError> {
+        let app = authenticate_client(
+            &self.oauth_apps_repository,
+            &input.client_id,
+            input.client_secret.as_deref()
+        ).await?;
+        let invalid_grant = || HearthError::Domain(INVALID_GRANT_ERROR_CODE.into());
+
+        match input.grant_type.as_str() {
+            "authorization_code" => {
+                let (Some(code), Some(redirect_uri), Some(code_verifier)) = (
+                    input.code,
+                    input.redirect_uri,
+                    input.code_verifier,
+                ) else {
+                    return Err(invalid_grant());
+                };
+
+                let code = self.oauth_codes_repository
+                    .take(&hasher::hash!(code)).await?
+                    .ok_or_else(invalid_grant)?;
+
+                if
+                    code.client_id != app.client_id ||
+                    code.redirect_uri != redirect_uri ||
+                    !OAuth::verify_pkce(&code_verifier, &code.code_challenge)
+                {
+                    return Err(invalid_grant());
+                }
+
+                self.issue(app.client_id, code.user_id, code.scopes).await
+            }
+            // Bots acting as the account that registered them, only for apps that can keep a secret.
+            "client_credentials" => {
+                if app.client_secret_hash.is_none() {
+                    return Err(HearthError::Unauthorized(INVALID_CLIENT_ERROR_CODE.into()));
+                }
+
+                let scopes = match &input.scope {
+                    Some(scope) =>
+                        OAuth::grantable_scopes(&app, scope).ok_or_else(||
+                            HearthError::Domain(INVALID_SCOPE_ERROR_CODE.into())
+                        )?,
+                    None => app.scopes.clone(),
+                };
+
+                self.issue(app.client_id, app.owner_id, scopes).await
+            }
+            _ => Err(HearthError::Domain(UNSUPPORTED_GRANT_TYPE_ERROR_CODE.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::oauth::{
+            OAuthAuthorizationRequestDTO,
+            OAuthConsentDecisionDTO,
+            OAuthTokenActionDTO,
+            OAuthTokenRequestDTO,
+            RegisterOAuthAppDTO,
+            RegisteredOAuthAppDTO,
+            Scope,
+        },
+        error_codes::{ INVALID_CLIENT_ERROR_CODE, INVALID_GRANT_ERROR_CODE },
+        features::{
+            feature::Feature,
+            oauth::{
+                decide_oauth_consent::DecideOAuthConsent,
+                exchange_oauth_token::ExchangeOAuthToken,
+                introspect_oauth_token::IntrospectOAuthToken,
+                register_oauth_app::RegisterOAuthApp,
+                revoke_oauth_token::RevokeOAuthToken,
+            },
+        },
+        policies::oauth::OAuthPolicy,
+        repositories::{
+            oauth_apps_repository::OAuthAppsRepository,
+            oauth_codes_repository::OAuthCodesRepository,
+            oauth_tokens_repository::OAuthTokensRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryOAuthAppsRepository,
+            InMemoryOAuthCodesRepository,
+            InMemoryOAuthTokensRepository,
+        },
+    };
+
+    const REDIRECT_URI: &str = "https://app.example.com/callback";
+    // RFC 7636 appendix B.
+    const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    const CODE_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    struct OAuthFeatures {
+        register: RegisterOAuthApp,
+        decide: DecideOAuthConsent,
+        exchange: ExchangeOAuthToken,
+        introspect: IntrospectOAuthToken,
+        revoke: RevokeOAuthToken,
+    }
+
+    fn features() -> OAuthFeatures {
+        let oauth_apps_repository: BArc<dyn OAuthAppsRepository> = barc!(
+            InMemoryOAuthAppsRepository::default()
+        );
+        let oauth_codes_repository: BArc<dyn OAuthCodesRepository> = barc!(
+            InMemoryOAuthCodesRepository::default()
+        );
+        let oauth_tokens_repository: BArc<dyn OAuthTokensRepository> = barc!(
+            InMemoryOAuthTokensRepository::default()
+        );
+
+        OAuthFeatures {
+            register: RegisterOAuthApp {
+                oauth_apps_repository: oauth_apps_repository.clone(),
+                policy: OAuthPolicy::default(),
+            },
+            decide: DecideOAuthConsent {
+                oauth_apps_repository: oauth_apps_repository.clone(),
+                oauth_codes_repository: oauth_codes_repository.clone(),
+                policy: OAuthPolicy::default(),
+            },
+            exchange: ExchangeOAuthToken {
+                oauth_apps_repository: oauth_apps_repository.clone(),
+                oauth_codes_repository,
+                oauth_tokens_repository: oauth_tokens_repository.clone(),
+                policy: OAuthPolicy::default(),
+            },
+            introspect: IntrospectOAuthToken {
+                oauth_apps_repository: oauth_apps_repository.clone(),
+                oauth_tokens_repository: oauth_tokens_repository.clone(),
+            },
+            revoke: RevokeOAuthToken { oauth_apps_repository, oauth_tokens_repository },
+        }
+    }
+
+    async fn register(features: &OAuthFeatures, confidential: bool) -> RegisteredOAuthAppDTO {
+        features.register
+            .execute(RegisterOAuthAppDTO {
+                owner_id: Uuid::new_v4(),
+                name: "Client".into(),
+                redirect_uris: vec![REDIRECT_URI.into()],
+                scopes: vec![Scope::Read, Scope::Write],
+                confidential,
+            }).await
+            .unwrap()
+    }
+
+    /// Approves a `read` request and returns the code the app is redirected with.
+    async fn authorize(features: &OAuthFeatures, client_id: Uuid, user_id: Uuid) -> String {
+        let redirect = features.decide
+            .execute(OAuthConsentDecisionDTO {
+                request: OAuthAuthorizationRequestDTO {
+                    user_id,
+                    response_type: "code".into(),
+                    client_id,
+                    redirect_uri: REDIRECT_URI.into(),
+                    scope: "read".into(),
+                    state: Some("xyz".into()),
+                    code_challenge: CODE_CHALLENGE.into(),
+                    code_challenge_method: "S256".into(),
+                },
+                approved: true,
+            }).await
+            .unwrap();
+
+        let url = url::Url::parse(&redirect.redirect_uri).unwrap();
+        let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert!(params.contains(&("state".into(), "xyz".into())));
+
+        params
+            .into_iter()
+            .find(|(name, _)| name == "code")
+            .map(|(_, code)| code)
+            .unwrap()
+    }
+
+    fn code_request(client_id: Uuid, code: &str, code_verifier: &str) -> OAuthTokenRequestDTO {
+        OAuthTokenRequestDTO {
+            grant_type: "authorization_code".into(),
+            client_id,
+            code: Some(code.into()),
+            redirect_uri: Some(REDIRECT_URI.into()),
+            code_verifier: Some(code_verifier.into()),
+            ..Default::default()
+        }
+    }
+
+    fn token_action(client_id: Uuid, token: &str) -> OAuthTokenActionDTO {
+        OAuthTokenActionDTO { token: token.into(), client_id, client_secret: None }
+    }
+
+    #[tokio::test]
+    async fn should_exchange_authorization_code_with_pkce() {
+        let features = features();
+        let app = register(&features, false).await;
+        let user_id = Uuid::new_v4();
+        let code = authorize(&features, app.client_id, user_id).await;
+
+        let token = features.exchange
+            .execute(code_request(app.client_id, &code, CODE_VERIFIER)).await
+            .unwrap();
+        assert_eq!(token.token_type, "Bearer");
+        assert_eq!(token.scope, "read");
+
+        // Codes are single use.
+        assert_eq!(
+            features.exchange
+                .execute(code_request(app.client_id, &code, CODE_VERIFIER)).await
+                .unwrap_err(),
+            HearthError::Domain(INVALID_GRANT_ERROR_CODE.into())
+        );
+
+        let introspection = features.introspect
+            .execute(token_action(app.client_id, &token.access_token)).await
+            .unwrap();
+        assert!(introspection.active);
+        assert_eq!(introspection.sub, Some(user_id));
+        assert_eq!(introspection.scope.as_deref(), Some("read"));
+
+        features.revoke.execute(token_action(app.client_id, &token.access_token)).await.unwrap();
+        assert!(
+            !features.introspect
+                .execute(token_action(app.client_id, &token.access_token)).await
+                .unwrap().active
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_wrong_code_verifier() {
+        let features = features();
+        let app = register(&features, false).await;
+        let code = authorize(&features, app.client_id, Uuid::new_v4()).await;
+
+        assert_eq!(
+            features.exchange
+                .execute(code_request(app.client_id, &code, &"a".repeat(43))).await
+                .unwrap_err(),
+            HearthError::Domain(INVALID_GRANT_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_issue_client_credentials_to_confidential_apps_only() {
+        let features = features();
+        let public_app = register(&features, false).await;
+        let confidential_app = register(&features, true).await;
+        let request = |client_id, client_secret| OAuthTokenRequestDTO {
+            grant_type: "client_credentials".into(),
+            client_id,
+            client_secret,
+            scope: Some("write".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            features.exchange.execute(request(public_app.client_id, None)).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_CLIENT_ERROR_CODE.into())
+        );
+        assert_eq!(
+            features.exchange
+                .execute(request(confidential_app.client_id, Some("wrong".into()))).await
+                .unwrap_err(),
+            HearthError::Unauthorized(INVALID_CLIENT_ERROR_CODE.into())
+        );
+
+        let token = features.exchange
+            .execute(request(confidential_app.client_id, confidential_app.client_secret)).await
+            .unwrap();
+        assert_eq!(token.scope, "write");
+
+        // Other apps cannot look into the token.
+        assert!(
+            !features.introspect
+                .execute(token_action(public_app.client_id, &token.access_token)).await
+                .unwrap().active
+        );
+    }
+}
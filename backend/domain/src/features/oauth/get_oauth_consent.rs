@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::oauth::{ OAuthAppDTO, OAuthAuthorizationRequestDTO, OAuthConsentDTO, Scope },
+    entities::oauth::OAuth,
+    error_codes::{
+        INVALID_CLIENT_ERROR_CODE,
+        INVALID_REDIRECT_URI_ERROR_CODE,
+        INVALID_SCOPE_ERROR_CODE,
+        PKCE_REQUIRED_ERROR_CODE,
+        UNSUPPORTED_RESPONSE_TYPE_ERROR_CODE,
+    },
+    features::feature::Feature,
+    repositories::oauth_apps_repository::OAuthAppsRepository,
+};
+
+pub type GetOAuthConsentFeature = dyn Feature<OAuthAuthorizationRequestDTO, OAuthConsentDTO>;
+
+pub struct GetOAuthConsent {
+    pub oauth_apps_repository: BArc<dyn OAuthAppsRepository>,
+}
+
+/// App and scopes of a well-formed authorization request. Nothing here is
+/// redirected back, an unknown app or redirect uri must not be trusted with errors.
+pub(crate) async fn check_authorization_request(
+    oauth_apps_repository: &BArc<dyn OAuthAppsRepository>,
+    request: &OAuthAuthorizationRequestDTO
+) -> Result<(OAuthAppDTO, Vec<Scope>), HearthError> {
+    if let Err(e) = request.validate() {
+        return Err(HearthError::Validation("OAUTH_AUTHORIZATION_REQUEST".into(), e));
+    }
+
+    let app = oauth_apps_repository
+        .get(&request.client_id).await?
+        .ok_or_else(|| HearthError::Unauthorized(INVALID_CLIENT_ERROR_CODE.into()))?;
+
+    if !app.redirect_uris.contains(&request.redirect_uri) {
+        return Err(HearthError::Domain(INVALID_REDIRECT_URI_ERROR_CODE.into()));
+    }
+    if request.response_type != "code" {
+        return Err(HearthError::Domain(UNSUPPORTED_RESPONSE_TYPE_ERROR_CODE.into()));
+    }
+    if request.code_challenge_method != "S256" {
+        return Err(HearthError::Domain(PKCE_REQUIRED_ERROR_CODE.into()));
+    }
+
+    let scopes = OAuth::grantable_scopes(&app, &request.scope).ok_or_else(||
+        HearthError::Domain(INVALID_SCOPE_ERROR_CODE.into())
+    )?;
+
+    Ok((app, scopes))
+}
+
+#[async_trait]
+impl Feature<OAuthAuthorizationRequestDTO, OAuthConsentDTO> for GetOAuthConsent {
+    async fn execute(&self, input: OAuthAuthorizationRequestDTO) -> Result<OAuthConsentDTO, HearthError> {
+        let (app, scopes) = check_authorization_request(&self.oauth_apps_repository, &input).await?;
+
+        Ok(OAuthConsentDTO {
+            client_id: app.client_id,
+            app_name: app.name,
+            redirect_uri: input.redirect_uri,
+            scopes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::oauth::{ OAuthAppDTO, OAuthAuthorizationRequestDTO, OAuthConsentDTO, Scope },
+        error_codes::{
+            INVALID_CLIENT_ERROR_CODE,
+            INVALID_REDIRECT_URI_ERROR_CODE,
+            INVALID_SCOPE_ERROR_CODE,
+            PKCE_REQUIRED_ERROR_CODE,
+        },
+        features::{ feature::Feature, oauth::get_oauth_consent::GetOAuthConsent },
+        repositories::oauth_apps_repository::OAuthAppsRepository,
+        test_utils::test_utils::InMemoryOAuthAppsRepository,
+    };
+
+    const REDIRECT_URI: &str = "https://app.example.com/callback";
+
+    async fn feature() -> (GetOAuthConsent, Uuid) {
+        let oauth_apps_repository: BArc<dyn OAuthAppsRepository> = barc!(
+            InMemoryOAuthAppsRepository::default()
+        );
+        let client_id = Uuid::new_v4();
+        oauth_apps_repository
+            .create(OAuthAppDTO {
+                client_id,
+                owner_id: Uuid::new_v4(),
+                name: "Client".into(),
+                redirect_uris: vec![REDIRECT_URI.into()],
+                scopes: vec![Scope::Read, Scope::Write],
+                client_secret_hash: None,
+                created_at: Utc::now(),
+            }).await
+            .unwrap();
+
+        (GetOAuthConsent { oauth_apps_repository }, client_id)
+    }
+
+    fn request(client_id: Uuid) -> OAuthAuthorizationRequestDTO {
+        OAuthAuthorizationRequestDTO {
+            user_id: Uuid::new_v4(),
+            response_type: "code".into(),
+            client_id,
+            redirect_uri: REDIRECT_URI.into(),
+            scope: "read write".into(),
+            state: None,
+            code_challenge: "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".into(),
+            code_challenge_method: "S256".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_describe_the_app_and_requested_scopes() {
+        let (feature, client_id) = feature().await;
+
+        assert_eq!(
+            feature.execute(request(client_id)).await.unwrap(),
+            OAuthConsentDTO {
+                client_id,
+                app_name: "Client".into(),
+                redirect_uri: REDIRECT_URI.into(),
+                scopes: vec![Scope::Read, Scope::Write],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_unknown_apps_and_redirect_uris() {
+        let (feature, client_id) = feature().await;
+
+        assert_eq!(
+            feature.execute(request(Uuid::new_v4())).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_CLIENT_ERROR_CODE.into())
+        );
+        assert_eq!(
+            feature
+                .execute(OAuthAuthorizationRequestDTO {
+                    redirect_uri: "https://evil.example.com/callback".into(),
+                    ..request(client_id)
+                }).await
+                .unwrap_err(),
+            HearthError::Domain(INVALID_REDIRECT_URI_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_require_pkce_and_scopes_granted_to_the_app() {
+        let (feature, client_id) = feature().await;
+
+        assert_eq!(
+            feature
+                .execute(OAuthAuthorizationRequestDTO {
+                    code_challenge_method: "plain".into(),
+                    ..request(client_id)
+                }).await
+                .unwrap_err(),
+            HearthError::Domain(PKCE_REQUIRED_ERROR_CODE.into())
+        );
+        assert_eq!(
+            feature
+                .execute(OAuthAuthorizationRequestDTO {
+                    scope: "read admin".into(),
+                    ..request(client_id)
+                }).await
+                .unwrap_err(),
+            HearthError::Domain(INVALID_SCOPE_ERROR_CODE.into())
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::oauth::{ OAuthIntrospectionDTO, OAuthTokenActionDTO, Scope },
+    features::{ feature::Feature, oauth::exchange_oauth_token::authenticate_client },
+    repositories::{
+        oauth_apps_repository::OAuthAppsRepository,
+        oauth_tokens_repository::OAuthTokensRepository,
+    },
+};
+
+pub type IntrospectOAuthTokenFeature = dyn Feature<OAuthTokenActionDTO, OAuthIntrospectionDTO>;
+
+/// RFC 7662. Apps only learn about their own tokens, any other one is inactive.
+pub struct IntrospectOAuthToken {
+    pub oauth_apps_repository: BArc<dyn OAuthAppsRepository>,
+    pub oauth_tokens_repository: BArc<dyn OAuthTokensRepository>,
+}
+
+#[async_trait]
+impl Feature<OAuthTokenActionDTO, OAuthIntrospectionDTO> for IntrospectOAuthToken {
+    async fn execute(&self, input: OAuthTokenActionDTO) -> Result<OAuthIntrospectionDTO, HearthError> {
+        let app = authenticate_client(
+            &self.oauth_apps_repository,
+            &input.client_id,
+            input.client_secret.as_deref()
+        ).await?;
+
+        let token = self.oauth_tokens_repository
+            .get(&hasher::hash!(input.token)).await?
+            .filter(|token| token.client_id == app.client_id)
+            .filter(|token| token.revoked_at.is_none() && token.expires_at > Utc::now());
+
+        Ok(match token {
+            Some(token) =>
+                OAuthIntrospectionDTO {
+                    active: true,
+                    scope: Some(Scope::join(&token.scopes)),
+                    client_id: Some(token.client_id),
+                    sub: Some(token.user_id),
+                    exp: Some(token.expires_at.timestamp()),
+                },
+            None => OAuthIntrospectionDTO::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ DateTime, Duration, Utc };
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::oauth::{
+            OAuthAppDTO,
+            OAuthIntrospectionDTO,
+            OAuthTokenActionDTO,
+            OAuthTokenDTO,
+            Scope,
+        },
+        features::{ feature::Feature, oauth::introspect_oauth_token::IntrospectOAuthToken },
+        repositories::{
+            oauth_apps_repository::OAuthAppsRepository,
+            oauth_tokens_repository::OAuthTokensRepository,
+        },
+        test_utils::test_utils::{ InMemoryOAuthAppsRepository, InMemoryOAuthTokensRepository },
+    };
+
+    struct Setup {
+        feature: IntrospectOAuthToken,
+        oauth_tokens_repository: BArc<dyn OAuthTokensRepository>,
+        client_id: Uuid,
+    }
+
+    async fn setup() -> Setup {
+        let oauth_apps_repository: BArc<dyn OAuthAppsRepository> = barc!(
+            InMemoryOAuthAppsRepository::default()
+        );
+        let oauth_tokens_repository: BArc<dyn OAuthTokensRepository> = barc!(
+            InMemoryOAuthTokensRepository::default()
+        );
+        let client_id = Uuid::new_v4();
+        oauth_apps_repository
+            .create(OAuthAppDTO {
+                client_id,
+                owner_id: Uuid::new_v4(),
+                name: "Client".into(),
+                redirect_uris: vec!["https://app.example.com/callback".into()],
+                scopes: vec![Scope::Read, Scope::Write],
+                client_secret_hash: None,
+                created_at: Utc::now(),
+            }).await
+            .unwrap();
+
+        Setup {
+            feature: IntrospectOAuthToken {
+                oauth_apps_repository,
+                oauth_tokens_repository: oauth_tokens_repository.clone(),
+            },
+            oauth_tokens_repository,
+            client_id,
+        }
+    }
+
+    fn token(token: &str, client_id: Uuid, user_id: Uuid, expires_at: DateTime<Utc>) -> OAuthTokenDTO {
+        OAuthTokenDTO {
+            token_hash: hasher::hash!(token),
+            client_id,
+            user_id,
+            scopes: vec![Scope::Read, Scope::Write],
+            expires_at,
+            revoked_at: None,
+        }
+    }
+
+    fn token_action(client_id: Uuid, token: &str) -> OAuthTokenActionDTO {
+        OAuthTokenActionDTO { token: token.into(), client_id, client_secret: None }
+    }
+
+    #[tokio::test]
+    async fn should_describe_active_tokens() {
+        let setup = setup().await;
+        let user_id = Uuid::new_v4();
+        let expires_at = Utc::now() + Duration::hours(1);
+        setup.oauth_tokens_repository
+            .create(token("active", setup.client_id, user_id, expires_at)).await
+            .unwrap();
+
+        assert_eq!(
+            setup.feature.execute(token_action(setup.client_id, "active")).await.unwrap(),
+            OAuthIntrospectionDTO {
+                active: true,
+                scope: Some("read write".into()),
+                client_id: Some(setup.client_id),
+                sub: Some(user_id),
+                exp: Some(expires_at.timestamp()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_report_expired_and_revoked_tokens_as_inactive() {
+        let setup = setup().await;
+        let user_id = Uuid::new_v4();
+        setup.oauth_tokens_repository
+            .create(token("expired", setup.client_id, user_id, Utc::now() - Duration::seconds(1))).await
+            .unwrap();
+        setup.oauth_tokens_repository
+            .create(OAuthTokenDTO {
+                revoked_at: Some(Utc::now()),
+                ..token("revoked", setup.client_id, user_id, Utc::now() + Duration::hours(1))
+            }).await
+            .unwrap();
+
+        for revoked_or_expired in ["expired", "revoked", "unknown"] {
+            assert_eq!(
+                setup.feature.execute(token_action(setup.client_id, revoked_or_expired)).await.unwrap(),
+                OAuthIntrospectionDTO::default()
+            );
+        }
+    }
+}
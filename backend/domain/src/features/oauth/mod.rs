@@ -0,0 +1,6 @@
+pub mod decide_oauth_consent;
+pub mod exchange_oauth_token;
+pub mod get_oauth_consent;
+pub mod introspect_oauth_token;
+pub mod register_oauth_app;
+pub mod revoke_oauth_token;
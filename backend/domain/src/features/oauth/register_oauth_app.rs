@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::oauth::{ OAuthAppDTO, RegisterOAuthAppDTO, RegisteredOAuthAppDTO },
+    entities::oauth::OAuth,
+    error_codes::{ INVALID_REDIRECT_URI_ERROR_CODE, TOO_MANY_OAUTH_APPS_ERROR_CODE },
+    features::feature::Feature,
+    policies::oauth::OAuthPolicy,
+    repositories::oauth_apps_repository::OAuthAppsRepository,
+};
+
+pub type RegisterOAuthAppFeature = dyn Feature<RegisterOAuthAppDTO, RegisteredOAuthAppDTO>;
+
+pub struct RegisterOAuthApp {
+    pub oauth_apps_repository: BArc<dyn OAuthAppsRepository>,
+    pub policy: OAuthPolicy,
+}
+
+#[async_trait]
+impl Feature<RegisterOAuthAppDTO, RegisteredOAuthAppDTO> for RegisterOAuthApp {
+    async fn execute(&self, input: RegisterOAuthAppDTO) -> Result<RegisteredOAuthAppDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("REGISTER_OAUTH_APP".into(), e));
+        }
+
+        if !input.redirect_uris.iter().all(|uri| OAuth::is_valid_redirect_uri(uri)) {
+            return Err(HearthError::Domain(INVALID_REDIRECT_URI_ERROR_CODE.into()));
+        }
+
+        let count = self.oauth_apps_repository.count_by_owner(&input.owner_id).await?;
+        if count >= (self.policy.max_apps_per_user as u64) {
+            return Err(HearthError::Domain(TOO_MANY_OAUTH_APPS_ERROR_CODE.into()));
+        }
+
+        let client_secret = input.confidential.then(OAuth::generate_token);
+        let mut scopes = vec![];
+        for scope in input.scopes {
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+
+        let app = OAuthAppDTO {
+            client_id: Uuid::new_v4(),
+            owner_id: input.owner_id,
+            name: input.name,
+            redirect_uris: input.redirect_uris,
+            scopes,
+            client_secret_hash: client_secret.as_ref().map(|secret| hasher::hash!(secret)),
+            created_at: Utc::now(),
+        };
+        let client_id = app.client_id;
+        self.oauth_apps_repository.create(app).await?;
+
+        Ok(RegisteredOAuthAppDTO { client_id, client_secret })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::oauth::{ RegisterOAuthAppDTO, Scope },
+        error_codes::{ INVALID_REDIRECT_URI_ERROR_CODE, TOO_MANY_OAUTH_APPS_ERROR_CODE },
+        features::{ feature::Feature, oauth::register_oauth_app::RegisterOAuthApp },
+        policies::oauth::OAuthPolicy,
+        repositories::oauth_apps_repository::OAuthAppsRepository,
+        test_utils::test_utils::InMemoryOAuthAppsRepository,
+    };
+
+    fn feature(oauth_apps_repository: BArc<dyn OAuthAppsRepository>) -> RegisterOAuthApp {
+        RegisterOAuthApp {
+            oauth_apps_repository,
+            policy: OAuthPolicy { max_apps_per_user: 1, ..OAuthPolicy::default() },
+        }
+    }
+
+    fn app(owner_id: Uuid, confidential: bool) -> RegisterOAuthAppDTO {
+        RegisterOAuthAppDTO {
+            owner_id,
+            name: "Client".into(),
+            redirect_uris: vec!["https://app.example.com/callback".into()],
+            scopes: vec![Scope::Read, Scope::Write, Scope::Read],
+            confidential,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_register_confidential_apps_with_a_hashed_secret() {
+        let oauth_apps_repository: BArc<dyn OAuthAppsRepository> = barc!(
+            InMemoryOAuthAppsRepository::default()
+        );
+        let owner_id = Uuid::new_v4();
+
+        let registered = feature(oauth_apps_repository.clone())
+            .execute(app(owner_id, true)).await
+            .unwrap();
+        let secret = registered.client_secret.unwrap();
+        let stored = oauth_apps_repository.get(&registered.client_id).await.unwrap().unwrap();
+
+        assert_eq!(stored.owner_id, owner_id);
+        assert_eq!(stored.scopes, vec![Scope::Read, Scope::Write]);
+        assert_eq!(stored.client_secret_hash, Some(hasher::hash!(secret)));
+    }
+
+    #[tokio::test]
+    async fn should_register_public_apps_without_a_secret() {
+        let oauth_apps_repository: BArc<dyn OAuthAppsRepository> = barc!(
+            InMemoryOAuthAppsRepository::default()
+        );
+
+        let registered = feature(oauth_apps_repository.clone())
+            .execute(app(Uuid::new_v4(), false)).await
+            .unwrap();
+
+        assert_eq!(registered.client_secret, None);
+        assert_eq!(
+            oauth_apps_repository
+                .get(&registered.client_id).await
+                .unwrap()
+                .unwrap().client_secret_hash,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_unsafe_redirect_uris() {
+        let feature = feature(barc!(InMemoryOAuthAppsRepository::default()));
+
+        for redirect_uri in ["http://app.example.com/callback", "https://app.example.com/#callback"] {
+            assert_eq!(
+                feature
+                    .execute(RegisterOAuthAppDTO {
+                        redirect_uris: vec![redirect_uri.into()],
+                        ..app(Uuid::new_v4(), false)
+                    }).await
+                    .unwrap_err(),
+                HearthError::Domain(INVALID_REDIRECT_URI_ERROR_CODE.into())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_limit_the_apps_of_each_user() {
+        let feature = feature(barc!(InMemoryOAuthAppsRepository::default()));
+        let owner_id = Uuid::new_v4();
+
+        feature.execute(app(owner_id, false)).await.unwrap();
+
+        assert_eq!(
+            feature.execute(app(owner_id, false)).await.unwrap_err(),
+            HearthError::Domain(TOO_MANY_OAUTH_APPS_ERROR_CODE.into())
+        );
+        feature.execute(app(Uuid::new_v4(), false)).await.unwrap();
+    }
+}
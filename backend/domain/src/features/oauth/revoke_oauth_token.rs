@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::oauth::OAuthTokenActionDTO,
+    features::{ feature::Feature, oauth::exchange_oauth_token::authenticate_client },
+    repositories::{
+        oauth_apps_repository::OAuthAppsRepository,
+        oauth_tokens_repository::OAuthTokensRepository,
+    },
+};
+
+pub type RevokeOAuthTokenFeature = dyn Feature<OAuthTokenActionDTO, ()>;
+
+/// RFC 7009, unknown tokens and tokens of other apps are silently ignored.
+pub struct RevokeOAuthToken {
+    pub oauth_apps_repository: BArc<dyn OAuthAppsRepository>,
+    pub oauth_tokens_repository: BArc<dyn OAuthTokensRepository>,
+}
+
+#[async_trait]
+impl Feature<OAuthTokenActionDTO, ()> for RevokeOAuthToken {
+    async fn execute(&self, input: OAuthTokenActionDTO) -> Result<(), HearthError> {
+        let app = authenticate_client(
+            &self.oauth_apps_repository,
+            &input.client_id,
+            input.client_secret.as_deref()
+        ).await?;
+
+        let token_hash = hasher::hash!(input.token);
+        let token = self.oauth_tokens_repository.get(&token_hash).await?;
+
+        if token.is_some_and(|token| token.client_id == app.client_id) {
+            self.oauth_tokens_repository.revoke(&token_hash, Utc::now()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::oauth::{ OAuthAppDTO, OAuthTokenActionDTO, OAuthTokenDTO, Scope },
+        error_codes::INVALID_CLIENT_ERROR_CODE,
+        features::{ feature::Feature, oauth::revoke_oauth_token::RevokeOAuthToken },
+        repositories::{
+            oauth_apps_repository::OAuthAppsRepository,
+            oauth_tokens_repository::OAuthTokensRepository,
+        },
+        test_utils::test_utils::{ InMemoryOAuthAppsRepository, InMemoryOAuthTokensRepository },
+    };
+
+    struct Setup {
+        feature: RevokeOAuthToken,
+        oauth_tokens_repository: BArc<dyn OAuthTokensRepository>,
+        client_id: Uuid,
+        other_client_id: Uuid,
+    }
+
+    /// Two public apps, the token belongs to the first one.
+    async fn setup(token: &str) -> Setup {
+        let oauth_apps_repository: BArc<dyn OAuthAppsRepository> = barc!(
+            InMemoryOAuthAppsRepository::default()
+        );
+        let oauth_tokens_repository: BArc<dyn OAuthTokensRepository> = barc!(
+            InMemoryOAuthTokensRepository::default()
+        );
+        let (client_id, other_client_id) = (Uuid::new_v4(), Uuid::new_v4());
+        for client_id in [client_id, other_client_id] {
+            oauth_apps_repository
+                .create(OAuthAppDTO {
+                    client_id,
+                    owner_id: Uuid::new_v4(),
+                    name: "Client".into(),
+                    redirect_uris: vec!["https://app.example.com/callback".into()],
+                    scopes: vec![Scope::Read],
+                    client_secret_hash: None,
+                    created_at: Utc::now(),
+                }).await
+                .unwrap();
+        }
+        oauth_tokens_repository
+            .create(OAuthTokenDTO {
+                token_hash: hasher::hash!(token),
+                client_id,
+                user_id: Uuid::new_v4(),
+                scopes: vec![Scope::Read],
+                expires_at: Utc::now() + Duration::hours(1),
+                revoked_at: None,
+            }).await
+            .unwrap();
+
+        Setup {
+            feature: RevokeOAuthToken {
+                oauth_apps_repository,
+                oauth_tokens_repository: oauth_tokens_repository.clone(),
+            },
+            oauth_tokens_repository,
+            client_id,
+            other_client_id,
+        }
+    }
+
+    fn token_action(client_id: Uuid, token: &str) -> OAuthTokenActionDTO {
+        OAuthTokenActionDTO { token: token.into(), client_id, client_secret: None }
+    }
+
+    async fn is_revoked(setup: &Setup, token: &str) -> bool {
+        setup.oauth_tokens_repository
+            .get(&hasher::hash!(token)).await
+            .unwrap()
+            .unwrap()
+            .revoked_at.is_some()
+    }
+
+    #[tokio::test]
+    async fn should_revoke_tokens_of_the_app() {
+        let setup = setup("token").await;
+
+        setup.feature.execute(token_action(setup.client_id, "token")).await.unwrap();
+
+        assert!(is_revoked(&setup, "token").await);
+    }
+
+    #[tokio::test]
+    async fn should_ignore_tokens_of_other_apps() {
+        let setup = setup("token").await;
+
+        setup.feature.execute(token_action(setup.other_client_id, "token")).await.unwrap();
+        setup.feature.execute(token_action(setup.client_id, "unknown")).await.unwrap();
+
+        assert!(!is_revoked(&setup, "token").await);
+    }
+
+    #[tokio::test]
+    async fn should_reject_unknown_apps() {
+        let setup = setup("token").await;
+
+        assert_eq!(
+            setup.feature.execute(token_action(Uuid::new_v4(), "token")).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_CLIENT_ERROR_CODE.into())
+        );
+        assert!(!is_revoked(&setup, "token").await);
+    }
+}
@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        auth::{ CredentialsDTO, SessionDTO },
+        event::{ DomainEvent, EventDTO },
+        oidc::{ CompleteOidcSignupDTO, LinkedIdentityDTO },
+        user::CreateUserDTO,
+    },
+    entities::{ age::Age, email::Email },
+    error_codes::INVALID_OIDC_SIGNUP_ERROR_CODE,
+    features::{ auth::login_with_email::generate_session_token, feature::Feature },
+    policies::{ age::AgePolicy, signup::EmailPolicy },
+    repositories::{
+        linked_identities_repository::LinkedIdentitiesRepository,
+        oidc_states_repository::OidcStatesRepository,
+        sessions_repository::SessionsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type CompleteOidcSignupFeature = dyn Feature<CompleteOidcSignupDTO, SessionDTO>;
+
+/// Second step of a "sign in with" signup, the user picks a username.
+pub struct CompleteOidcSignup {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub linked_identities_repository: BArc<dyn LinkedIdentitiesRepository>,
+    pub oidc_states_repository: BArc<dyn OidcStatesRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub email_policy: EmailPolicy,
+    pub age_policy: AgePolicy,
+}
+
+#[async_trait]
+impl Feature<CompleteOidcSignupDTO, SessionDTO> for CompleteOidcSignup {
+    async fn execute(&self, input: CompleteOidcSignupDTO) -> Result<SessionDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("COMPLETE_OIDC_SIGNUP".into(), e));
+        }
+
+        let signup = self.oidc_states_repository
+            .take_pending_signup(&hasher::hash!(input.signup_token)).await?
+            .ok_or_else(|| HearthError::Unauthorized(INVALID_OIDC_SIGNUP_ERROR_CODE.into()))?;
+
+        Age::check_signup(input.birthday, Utc::now().date_naive(), &self.age_policy)?;
+        Email::check_domain(&signup.email, &self.email_policy)?;
+
+        if self.users_repository.email_exists(&signup.email).await? {
+            return Err(HearthError::Domain("EMAIL_ALREADY_TAKEN".into()));
+        }
+
+        if self.users_repository.username_exists(&input.username).await? {
+            return Err(HearthError::Domain("USERNAME_ALREADY_TAKEN".into()));
+        }
+
+        let user_id = Uuid::new_v4();
+        // Nobody knows the password, the account is only reachable through its provider.
+        let unusable_password = generate_session_token();
+
+        let registered = EventDTO::new(
+            DomainEvent::UserRegistered { user_id, username: input.username.clone() },
+            Utc::now()
+        );
+
+        self.users_repository.create_with_events(
+            CreateUserDTO {
+                user_id,
+                username: input.username,
+                email: signup.email.clone(),
+                birthday: input.birthday,
+            },
+            CredentialsDTO { user_id, password_hash: hasher::hash!(unusable_password) },
+            vec![registered]
+        ).await?;
+
+        self.linked_identities_repository.create(LinkedIdentityDTO {
+            user_id,
+            provider: signup.provider,
+            subject: signup.subject,
+            email: Some(signup.email),
+            created_at: Utc::now(),
+        }).await?;
+
+        let token = generate_session_token();
+        self.sessions_repository.create(&hasher::hash!(token), &user_id).await?;
+
+        Ok(SessionDTO { token, user_id })
+    }
+}
@@ -0,0 +1,501 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        auth::LoginResultDTO,
+        oidc::{ LinkedIdentityDTO, OidcCallbackDTO, PendingOidcSignupDTO },
+        user::UserDTO,
+    },
+    entities::{ email::Email, oidc::Oidc },
+    error_codes::{
+        INVALID_ID_TOKEN_ERROR_CODE,
+        INVALID_OIDC_STATE_ERROR_CODE,
+        OIDC_EMAIL_NOT_VERIFIED_ERROR_CODE,
+        OIDC_IDENTITY_ALREADY_LINKED_ERROR_CODE,
+        OIDC_LINK_REQUIRED_ERROR_CODE,
+        OIDC_PROVIDER_NOT_FOUND_ERROR_CODE,
+    },
+    features::{
+        auth::login_with_email::{
+            cancel_pending_deletion,
+            check_can_log_in,
+            generate_session_token,
+            open_session,
+        },
+        feature::Feature,
+    },
+    policies::{
+        account_deletion::AccountDeletionPolicy,
+        oidc::OidcPolicy,
+        signup::EmailPolicy,
+        two_factor::TwoFactorPolicy,
+    },
+    repositories::{
+        linked_identities_repository::LinkedIdentitiesRepository,
+        login_challenges_repository::LoginChallengesRepository,
+        oidc_client::OidcClient,
+        oidc_states_repository::OidcStatesRepository,
+        sessions_repository::SessionsRepository,
+        two_factor_repository::TwoFactorRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type FinishOidcLoginFeature = dyn Feature<OidcCallbackDTO, LoginResultDTO>;
+
+pub struct FinishOidcLogin {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub linked_identities_repository: BArc<dyn LinkedIdentitiesRepository>,
+    pub oidc_client: BArc<dyn OidcClient>,
+    pub oidc_states_repository: BArc<dyn OidcStatesRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub two_factor_repository: BArc<dyn TwoFactorRepository>,
+    pub login_challenges_repository: BArc<dyn LoginChallengesRepository>,
+    pub policy: OidcPolicy,
+    pub email_policy: EmailPolicy,
+    pub two_factor_policy: TwoFactorPolicy,
+    pub account_deletion_policy: AccountDeletionPolicy,
+}
+
+#[async_trait]
+impl Feature<OidcCallbackDTO, LoginResultDTO> for FinishOidcLogin {
+    async fn execute(&self, input: OidcCallbackDTO) -> Result<LoginResultDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("OIDC_CALLBACK".into(), e));
+        }
+
+        let provider = self.policy
+            .provider(&input.provider)
+            .ok_or_else(|| HearthError::not_found(OIDC_PROVIDER_NOT_FOUND_ERROR_CODE.into()))?;
+
+        // Taken before anything else so a state can't be replayed, even after a failure.
+        let state = self.oidc_states_repository
+            .take_state(&input.state).await?
+            .filter(|state| state.provider == provider.name)
+            .ok_or_else(|| HearthError::Unauthorized(INVALID_OIDC_STATE_ERROR_CODE.into()))?;
+
+        let discovery = self.oidc_client.discover(&provider.issuer).await?;
+        let id_token = self.oidc_client.exchange_code(
+            &discovery,
+            provider,
+            &input.code,
+            &state.code_verifier
+        ).await?;
+        let jwks = self.oidc_client.jwks(&discovery.jwks_uri).await?;
+
+        let claims = Oidc::verify_id_token(
+            &id_token,
+            &jwks,
+            &discovery.issuer,
+            &provider.client_id,
+            &state.nonce,
+            Utc::now(),
+            self.policy.clock_skew_seconds
+        ).ok_or_else(|| HearthError::Unauthorized(INVALID_ID_TOKEN_ERROR_CODE.into()))?;
+
+        let identity = self.linked_identities_repository.get(
+            &provider.name,
+            &claims.subject
+        ).await?;
+
+        let user = match (identity, state.link_user_id) {
+            (Some(identity), Some(user_id)) if identity.user_id != user_id => {
+                return Err(HearthError::Domain(OIDC_IDENTITY_ALREADY_LINKED_ERROR_CODE.into()));
+            }
+            (Some(identity), _) => self.users_repository.get(identity.user_id.to_string()).await?,
+            // The user logged in to the account before starting the flow.
+            (None, Some(user_id)) => {
+                let user = self.users_repository.get(user_id.to_string()).await?;
+                let email = claims.email
+                    .as_deref()
+                    .map(|email| Email::normalize(email, self.email_policy.plus_addressing));
+                self.link(&user, &provider.name, &claims.subject, email).await?;
+                user
+            }
+            (None, None) => {
+                // Unlinked identities are only matched on an address the provider vouches for,
+                // anyone could otherwise take over an account by claiming its email.
+                let email = match (&claims.email, claims.email_verified) {
+                    (Some(email), true) =>
+                        Email::normalize(email, self.email_policy.plus_addressing),
+                    _ => {
+                        return Err(HearthError::Domain(OIDC_EMAIL_NOT_VERIFIED_ERROR_CODE.into()));
+                    }
+                };
+
+                match self.users_repository.get_by_email(&email).await? {
+                    Some(user) if user.email_verified => {
+                        self.link(&user, &provider.name, &claims.subject, Some(email)).await?;
+                        user
+                    }
+                    // Anyone can register an address they don't own and keep its password,
+                    // the owner has to log in to that account to link it.
+                    Some(_) => {
+                        return Err(HearthError::Domain(OIDC_LINK_REQUIRED_ERROR_CODE.into()));
+                    }
+                    None => {
+                        let signup_token = generate_session_token();
+                        self.oidc_states_repository.create_pending_signup(
+                            &hasher::hash!(signup_token),
+                            &(PendingOidcSignupDTO {
+                                provider: provider.name.clone(),
+                                subject: claims.subject,
+                                email: email.clone(),
+                            }),
+                            self.policy.signup_ttl_seconds
+                        ).await?;
+
+                        return Ok(LoginResultDTO::UsernameRequired { signup_token, email });
+                    }
+                }
+            }
+        };
+
+        let now = Utc::now();
+        check_can_log_in(&user, &self.account_deletion_policy, now)?;
+
+        let result = open_session(
+            &user.user_id,
+            &self.two_factor_repository,
+            &self.login_challenges_repository,
+            &self.sessions_repository,
+            &self.two_factor_policy
+        ).await?;
+
+        if let LoginResultDTO::Session(_) = result {
+            cancel_pending_deletion(
+                &user,
+                &self.users_repository,
+                &self.account_deletion_policy,
+                now
+            ).await?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl FinishOidcLogin {
+    async fn link(
+        &self,
+        user: &UserDTO,
+        provider: &str,
+        subject: &str,
+        email: Option<String>
+    ) -> Result<(), HearthError> {
+        self.linked_identities_repository.create(LinkedIdentityDTO {
+            user_id: user.user_id,
+            provider: provider.into(),
+            subject: subject.into(),
+            email,
+            created_at: Utc::now(),
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::{ CredentialsDTO, LoginResultDTO },
+            oidc::{
+                CompleteOidcSignupDTO,
+                OidcAuthorizationDTO,
+                OidcCallbackDTO,
+                OidcClaimsDTO,
+                OidcLinkDTO,
+            },
+            user::{ CreateUserDTO, UserStatus },
+        },
+        error_codes::{
+            ACCOUNT_DEACTIVATED_ERROR_CODE,
+            INVALID_OIDC_SIGNUP_ERROR_CODE,
+            INVALID_OIDC_STATE_ERROR_CODE,
+            OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE,
+            OIDC_EMAIL_NOT_VERIFIED_ERROR_CODE,
+            OIDC_IDENTITY_ALREADY_LINKED_ERROR_CODE,
+            OIDC_LINK_REQUIRED_ERROR_CODE,
+        },
+        features::{
+            feature::Feature,
+            oidc::{ complete_oidc_signup::CompleteOidcSignup, start_oidc_login::StartOidcLogin },
+        },
+        policies::{
+            account_deletion::AccountDeletionPolicy,
+            age::AgePolicy,
+            oidc::{ OidcPolicy, OidcProvider },
+            signup::EmailPolicy,
+            two_factor::TwoFactorPolicy,
+        },
+        repositories::{
+            linked_identities_repository::LinkedIdentitiesRepository,
+            oidc_states_repository::OidcStatesRepository,
+            sessions_repository::SessionsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryLinkedIdentitiesRepository,
+            InMemoryLoginChallengesRepository,
+            InMemoryOidcStatesRepository,
+            InMemorySessionsRepository,
+            InMemoryTwoFactorRepository,
+            InMemoryUserRepository,
+            MockOidcProvider,
+        },
+    };
+
+    use super::FinishOidcLogin;
+
+    const EMAIL: &str = "john.smith@gmail.com";
+
+    struct Fixture {
+        user_id: Uuid,
+        policy: OidcPolicy,
+        provider: MockOidcProvider,
+        users: InMemoryUserRepository,
+        users_repository: BArc<dyn UsersRepository>,
+        linked_identities_repository: BArc<dyn LinkedIdentitiesRepository>,
+        oidc_states_repository: BArc<dyn OidcStatesRepository>,
+        sessions_repository: BArc<dyn SessionsRepository>,
+    }
+
+    impl Fixture {
+        fn new(claims: OidcClaimsDTO) -> Self {
+            let provider = OidcProvider {
+                name: "example".into(),
+                issuer: "https://id.example".into(),
+                client_id: "hearth".into(),
+                client_secret: "secret".into(),
+                redirect_uri: "tauri://localhost/oidc/example".into(),
+                scopes: vec!["openid".into(), "email".into()],
+            };
+            let user_id = Uuid::new_v4();
+            let users = InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: EMAIL.into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            );
+
+            Fixture {
+                user_id,
+                provider: MockOidcProvider::new(&provider, claims),
+                policy: OidcPolicy { providers: vec![provider], ..Default::default() },
+                users_repository: barc!(users.clone()),
+                users,
+                linked_identities_repository: barc!(InMemoryLinkedIdentitiesRepository::default()),
+                oidc_states_repository: barc!(InMemoryOidcStatesRepository::default()),
+                sessions_repository: barc!(InMemorySessionsRepository::default()),
+            }
+        }
+
+        fn start(&self) -> StartOidcLogin {
+            StartOidcLogin {
+                oidc_client: barc!(self.provider.clone()),
+                oidc_states_repository: self.oidc_states_repository.clone(),
+                policy: self.policy.clone(),
+            }
+        }
+
+        /// Authorization request, consent at the provider and the redirect back.
+        async fn callback(&self) -> OidcCallbackDTO {
+            let authorization = self.start().execute("example".to_string()).await.unwrap();
+            self.redirect(authorization)
+        }
+
+        /// Same as `callback`, started by `user_id` while logged in.
+        async fn link_callback(&self, user_id: Uuid) -> OidcCallbackDTO {
+            let authorization = self
+                .start()
+                .execute(OidcLinkDTO { user_id, provider: "example".into() }).await
+                .unwrap();
+            self.redirect(authorization)
+        }
+
+        fn redirect(&self, authorization: OidcAuthorizationDTO) -> OidcCallbackDTO {
+            let (code, state) = self.provider.authorize(&authorization.authorization_url);
+            assert_eq!(state, authorization.state);
+
+            OidcCallbackDTO { provider: "example".into(), code, state }
+        }
+
+        fn finish(&self) -> FinishOidcLogin {
+            FinishOidcLogin {
+                users_repository: self.users_repository.clone(),
+                linked_identities_repository: self.linked_identities_repository.clone(),
+                oidc_client: barc!(self.provider.clone()),
+                oidc_states_repository: self.oidc_states_repository.clone(),
+                sessions_repository: self.sessions_repository.clone(),
+                two_factor_repository: barc!(InMemoryTwoFactorRepository::default()),
+                login_challenges_repository: barc!(InMemoryLoginChallengesRepository::default()),
+                policy: self.policy.clone(),
+                email_policy: EmailPolicy::default(),
+                two_factor_policy: TwoFactorPolicy::default(),
+                account_deletion_policy: AccountDeletionPolicy::default(),
+            }
+        }
+
+        fn complete(&self) -> CompleteOidcSignup {
+            CompleteOidcSignup {
+                users_repository: self.users_repository.clone(),
+                linked_identities_repository: self.linked_identities_repository.clone(),
+                oidc_states_repository: self.oidc_states_repository.clone(),
+                sessions_repository: self.sessions_repository.clone(),
+                email_policy: EmailPolicy::default(),
+                age_policy: AgePolicy::default(),
+            }
+        }
+    }
+
+    fn claims(email: &str, email_verified: bool) -> OidcClaimsDTO {
+        OidcClaimsDTO { subject: "248289761001".into(), email: Some(email.into()), email_verified }
+    }
+
+    #[tokio::test]
+    async fn should_link_an_existing_account_by_verified_email() {
+        let fixture = Fixture::new(claims("John.Smith@gmail.com", true));
+        fixture.users.verify_email(&fixture.user_id);
+
+        let result = fixture.finish().execute(fixture.callback().await).await.unwrap();
+
+        let LoginResultDTO::Session(session) = result else {
+            panic!("expected a session, got {:?}", result);
+        };
+        assert_eq!(session.user_id, fixture.user_id);
+        let identity = fixture.linked_identities_repository
+            .get("example", "248289761001").await
+            .unwrap()
+            .unwrap();
+        assert_eq!(identity.user_id, fixture.user_id);
+
+        // Once linked, the provider's email no longer matters.
+        *fixture.provider.claims.lock().unwrap() = claims("new@example.com", false);
+        let result = fixture.finish().execute(fixture.callback().await).await.unwrap();
+        assert!(matches!(result, LoginResultDTO::Session(s) if s.user_id == fixture.user_id));
+    }
+
+    #[tokio::test]
+    async fn should_not_link_an_unverified_email() {
+        let fixture = Fixture::new(claims(EMAIL, false));
+
+        let result = fixture.finish().execute(fixture.callback().await).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HearthError::Domain(OIDC_EMAIL_NOT_VERIFIED_ERROR_CODE.into())
+        );
+        let identity = fixture.linked_identities_repository.get("example", "248289761001").await;
+        assert_eq!(identity.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn should_require_a_login_to_link_an_account_whose_email_is_unverified() {
+        let fixture = Fixture::new(claims(EMAIL, true));
+
+        let result = fixture.finish().execute(fixture.callback().await).await;
+
+        assert_eq!(result.unwrap_err(), HearthError::Domain(OIDC_LINK_REQUIRED_ERROR_CODE.into()));
+        let identity = fixture.linked_identities_repository.get("example", "248289761001").await;
+        assert_eq!(identity.unwrap(), None);
+
+        let callback = fixture.link_callback(fixture.user_id).await;
+        let result = fixture.finish().execute(callback).await.unwrap();
+        assert!(matches!(result, LoginResultDTO::Session(s) if s.user_id == fixture.user_id));
+        let result = fixture.finish().execute(fixture.callback().await).await.unwrap();
+        assert!(matches!(result, LoginResultDTO::Session(s) if s.user_id == fixture.user_id));
+
+        // An identity stays with the account it was linked to.
+        let callback = fixture.link_callback(Uuid::new_v4()).await;
+        assert_eq!(
+            fixture.finish().execute(callback).await.unwrap_err(),
+            HearthError::Domain(OIDC_IDENTITY_ALREADY_LINKED_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_ask_new_users_for_a_username() {
+        let fixture = Fixture::new(claims("jane@example.com", true));
+
+        let result = fixture.finish().execute(fixture.callback().await).await.unwrap();
+        let LoginResultDTO::UsernameRequired { signup_token, email } = result else {
+            panic!("expected a username step, got {:?}", result);
+        };
+        assert_eq!(email, "jane@example.com");
+
+        let signup = |signup_token: String| CompleteOidcSignupDTO {
+            signup_token,
+            username: "jane".into(),
+            birthday: NaiveDate::from_ymd_opt(1994, 3, 8).unwrap(),
+        };
+        let session = fixture.complete().execute(signup(signup_token.clone())).await.unwrap();
+
+        let user = fixture.users_repository.get(session.user_id.to_string()).await.unwrap();
+        assert_eq!((user.username.as_str(), user.email.as_str()), ("jane", "jane@example.com"));
+        assert_eq!(
+            fixture.sessions_repository.get_user_id(&hasher::hash!(session.token)).await.unwrap(),
+            Some(session.user_id)
+        );
+        assert_eq!(
+            fixture.complete().execute(signup(signup_token)).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_OIDC_SIGNUP_ERROR_CODE.into())
+        );
+
+        let result = fixture.finish().execute(fixture.callback().await).await.unwrap();
+        assert!(matches!(result, LoginResultDTO::Session(s) if s.user_id == session.user_id));
+    }
+
+    #[tokio::test]
+    async fn should_reject_replayed_states_and_wrong_verifiers() {
+        let fixture = Fixture::new(claims(EMAIL, true));
+        fixture.users.verify_email(&fixture.user_id);
+        let callback = fixture.callback().await;
+
+        fixture.finish().execute(callback.clone()).await.unwrap();
+        assert_eq!(
+            fixture.finish().execute(callback).await.unwrap_err(),
+            HearthError::Unauthorized(INVALID_OIDC_STATE_ERROR_CODE.into())
+        );
+
+        // A code intercepted on its way back is useless without the verifier of its state.
+        let intercepted = fixture.callback().await;
+        let own = fixture.callback().await;
+        let stolen = OidcCallbackDTO { code: intercepted.code, ..own };
+        let result = fixture.finish().execute(stolen).await;
+        assert_eq!(
+            result.unwrap_err(),
+            HearthError::Unauthorized(OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_cancel_a_pending_deletion_during_the_grace_period_only() {
+        let fixture = Fixture::new(claims(EMAIL, true));
+        fixture.users.verify_email(&fixture.user_id);
+        fixture.users_repository.set_deactivated(&fixture.user_id, Some(Utc::now())).await.unwrap();
+
+        let result = fixture.finish().execute(fixture.callback().await).await.unwrap();
+        assert!(matches!(result, LoginResultDTO::Session(s) if s.user_id == fixture.user_id));
+        let user = fixture.users_repository.get(fixture.user_id.to_string()).await.unwrap();
+        assert_eq!((user.status, user.deactivated_at), (UserStatus::Active, None));
+
+        fixture.users_repository
+            .set_deactivated(&fixture.user_id, Some(Utc::now() - Duration::days(31))).await
+            .unwrap();
+        assert_eq!(
+            fixture.finish().execute(fixture.callback().await).await.unwrap_err(),
+            HearthError::Forbidden(ACCOUNT_DEACTIVATED_ERROR_CODE.into())
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod complete_oidc_signup;
+pub mod finish_oidc_login;
+pub mod start_oidc_login;
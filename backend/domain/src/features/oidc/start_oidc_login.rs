@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::oidc::{ OidcAuthorizationDTO, OidcLinkDTO, OidcStateDTO },
+    entities::oidc::Oidc,
+    error_codes::OIDC_PROVIDER_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    policies::oidc::OidcPolicy,
+    repositories::{ oidc_client::OidcClient, oidc_states_repository::OidcStatesRepository },
+};
+
+/// Input is the provider name.
+pub type StartOidcLoginFeature = dyn Feature<String, OidcAuthorizationDTO>;
+/// Same flow, the callback links the identity to the user instead of matching an account.
+pub type StartOidcLinkFeature = dyn Feature<OidcLinkDTO, OidcAuthorizationDTO>;
+
+pub struct StartOidcLogin {
+    pub oidc_client: BArc<dyn OidcClient>,
+    pub oidc_states_repository: BArc<dyn OidcStatesRepository>,
+    pub policy: OidcPolicy,
+}
+
+impl StartOidcLogin {
+    async fn authorize(
+        &self,
+        provider: &str,
+        link_user_id: Option<Uuid>
+    ) -> Result<OidcAuthorizationDTO, HearthError> {
+        let provider = self.policy
+            .provider(provider)
+            .ok_or_else(|| HearthError::not_found(OIDC_PROVIDER_NOT_FOUND_ERROR_CODE.into()))?;
+
+        let discovery = self.oidc_client.discover(&provider.issuer).await?;
+
+        let state = OidcStateDTO {
+            state: Oidc::generate_token(),
+            provider: provider.name.clone(),
+            nonce: Oidc::generate_token(),
+            code_verifier: Oidc::generate_token(),
+            link_user_id,
+        };
+
+        let authorization_url = Oidc::authorization_url(
+            &discovery,
+            provider,
+            &state.state,
+            &state.nonce,
+            &state.code_verifier
+        ).ok_or_else(||
+            HearthError::unexpected(
+                "INVALID_OIDC_DISCOVERY".into(),
+                Some(discovery.authorization_endpoint.clone())
+            )
+        )?;
+
+        self.oidc_states_repository.create_state(&state, self.policy.state_ttl_seconds).await?;
+
+        Ok(OidcAuthorizationDTO { authorization_url, state: state.state })
+    }
+}
+
+#[async_trait]
+impl Feature<String, OidcAuthorizationDTO> for StartOidcLogin {
+    async fn execute(&self, provider: String) -> Result<OidcAuthorizationDTO, HearthError> {
+        self.authorize(&provider, None).await
+    }
+}
+
+#[async_trait]
+impl Feature<OidcLinkDTO, OidcAuthorizationDTO> for StartOidcLogin {
+    async fn execute(&self, input: OidcLinkDTO) -> Result<OidcAuthorizationDTO, HearthError> {
+        self.authorize(&input.provider, Some(input.user_id)).await
+    }
+}
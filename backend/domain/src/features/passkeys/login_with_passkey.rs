@@ -0,0 +1,435 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::{ auth::SessionDTO, passkey::{ LoginPasskeyDTO, WebAuthnCeremony } },
+    entities::webauthn::{ GET_CEREMONY, WebAuthn },
+    error_codes::{ INVALID_LOGIN_CHALLENGE_ERROR_CODE, INVALID_PASSKEY_ERROR_CODE },
+    features::{
+        auth::login_with_email::{
+            cancel_pending_deletion,
+            check_can_log_in,
+            generate_session_token,
+        },
+        feature::Feature,
+    },
+    policies::{ account_deletion::AccountDeletionPolicy, passkey::PasskeyPolicy },
+    repositories::{
+        login_challenges_repository::LoginChallengesRepository,
+        passkeys_repository::PasskeysRepository,
+        sessions_repository::SessionsRepository,
+        users_repository::UsersRepository,
+        webauthn_challenges_repository::WebAuthnChallengesRepository,
+    },
+};
+
+pub type LoginWithPasskeyFeature = dyn Feature<LoginPasskeyDTO, SessionDTO>;
+
+/// Finishes the authentication ceremony started by `StartPasskeyLogin`, either
+/// as a passwordless login or as the second step of a password login.
+pub struct LoginWithPasskey {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub passkeys_repository: BArc<dyn PasskeysRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub login_challenges_repository: BArc<dyn LoginChallengesRepository>,
+    pub webauthn_challenges_repository: BArc<dyn WebAuthnChallengesRepository>,
+    pub policy: PasskeyPolicy,
+    pub account_deletion_policy: AccountDeletionPolicy,
+}
+
+#[async_trait]
+impl Feature<LoginPasskeyDTO, SessionDTO> for LoginWithPasskey {
+    async fn execute(&self, input: LoginPasskeyDTO) -> Result<SessionDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("LOGIN_PASSKEY".into(), e));
+        }
+
+        let invalid_passkey = || HearthError::Unauthorized(INVALID_PASSKEY_ERROR_CODE.into());
+
+        let client_data_json = WebAuthn::decode(&input.client_data_json).ok_or_else(invalid_passkey)?;
+        let authenticator_data = WebAuthn::decode(&input.authenticator_data).ok_or_else(
+            invalid_passkey
+        )?;
+        let signature = WebAuthn::decode(&input.signature).ok_or_else(invalid_passkey)?;
+        let credential_id = WebAuthn::decode(&input.credential_id).ok_or_else(invalid_passkey)?;
+
+        let client_data = WebAuthn::parse_client_data(
+            &client_data_json,
+            GET_CEREMONY,
+            &self.policy
+        ).ok_or_else(invalid_passkey)?;
+
+        let challenge = self.webauthn_challenges_repository
+            .take(&client_data.challenge).await?
+            .filter(|challenge| challenge.ceremony == WebAuthnCeremony::Authentication)
+            .ok_or_else(invalid_passkey)?;
+
+        let passkey = self.passkeys_repository
+            .get_by_credential_id(&credential_id).await?
+            .ok_or_else(invalid_passkey)?;
+        if challenge.user_id.is_some_and(|user_id| user_id != passkey.user_id) {
+            return Err(invalid_passkey());
+        }
+
+        let data = WebAuthn::parse_authenticator_data(&authenticator_data).ok_or_else(
+            invalid_passkey
+        )?;
+        if
+            !WebAuthn::check_authenticator_data(&data, &self.policy) ||
+            !WebAuthn::verify_signature(
+                &passkey.public_key,
+                &authenticator_data,
+                &client_data_json,
+                &signature
+            )
+        {
+            return Err(invalid_passkey());
+        }
+
+        let now = Utc::now();
+        if
+            !WebAuthn::check_sign_count(passkey.sign_count, data.sign_count) ||
+            !self.passkeys_repository.update_sign_count(
+                &passkey.passkey_id,
+                data.sign_count,
+                now
+            ).await?
+        {
+            return Err(invalid_passkey());
+        }
+
+        match (challenge.login_challenge, input.challenge_token) {
+            // Second factor, the password step already checked the account can log in.
+            (Some(login_challenge), Some(challenge_token)) if
+                login_challenge == hasher::hash!(challenge_token)
+            => {
+                let user_id = self.login_challenges_repository.get_user_id(&login_challenge).await?;
+                if user_id != Some(passkey.user_id) {
+                    return Err(
+                        HearthError::Unauthorized(INVALID_LOGIN_CHALLENGE_ERROR_CODE.into())
+                    );
+                }
+                self.login_challenges_repository.delete(&login_challenge).await?;
+            }
+            (None, None) => {
+                let user = self.users_repository.get(passkey.user_id.to_string()).await?;
+                check_can_log_in(&user, &self.account_deletion_policy, now)?;
+            }
+            _ => {
+                return Err(HearthError::Unauthorized(INVALID_LOGIN_CHALLENGE_ERROR_CODE.into()));
+            }
+        }
+
+        let token = generate_session_token();
+        self.sessions_repository.create(&hasher::hash!(token), &passkey.user_id).await?;
+
+        let user = self.users_repository.get(passkey.user_id.to_string()).await?;
+        cancel_pending_deletion(&user, &self.users_repository, &self.account_deletion_policy, now).await?;
+
+        Ok(SessionDTO { token, user_id: passkey.user_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            passkey::{ LoginPasskeyDTO, PasskeyLoginOptionsRequestDTO, RegisterPasskeyDTO },
+            user::{ CreateUserDTO, UserStatus },
+        },
+        entities::webauthn::WebAuthn,
+        error_codes::{
+            ACCOUNT_DEACTIVATED_ERROR_CODE,
+            INVALID_PASSKEY_ERROR_CODE,
+            INVALID_WEBAUTHN_CHALLENGE_ERROR_CODE,
+            PASSKEY_ALREADY_REGISTERED_ERROR_CODE,
+        },
+        features::{
+            feature::Feature,
+            passkeys::{
+                register_passkey::RegisterPasskey,
+                start_passkey_login::StartPasskeyLogin,
+                start_passkey_registration::StartPasskeyRegistration,
+            },
+        },
+        policies::{ account_deletion::AccountDeletionPolicy, passkey::PasskeyPolicy },
+        repositories::{
+            login_challenges_repository::LoginChallengesRepository,
+            passkeys_repository::PasskeysRepository,
+            sessions_repository::SessionsRepository,
+            users_repository::UsersRepository,
+            webauthn_challenges_repository::WebAuthnChallengesRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryLoginChallengesRepository,
+            InMemoryPasskeysRepository,
+            InMemorySessionsRepository,
+            InMemoryUserRepository,
+            InMemoryWebAuthnChallengesRepository,
+            SoftwareAuthenticator,
+        },
+    };
+
+    use super::LoginWithPasskey;
+
+    struct Fixture {
+        user_id: Uuid,
+        users_repository: BArc<dyn UsersRepository>,
+        passkeys_repository: BArc<dyn PasskeysRepository>,
+        sessions_repository: BArc<dyn SessionsRepository>,
+        login_challenges_repository: BArc<dyn LoginChallengesRepository>,
+        webauthn_challenges_repository: BArc<dyn WebAuthnChallengesRepository>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let user_id = Uuid::new_v4();
+            let users = InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: "john.smith@gmail.com".into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            );
+
+            Fixture {
+                user_id,
+                users_repository: barc!(users),
+                passkeys_repository: barc!(InMemoryPasskeysRepository::default()),
+                sessions_repository: barc!(InMemorySessionsRepository::default()),
+                login_challenges_repository: barc!(InMemoryLoginChallengesRepository::default()),
+                webauthn_challenges_repository: barc!(
+                    InMemoryWebAuthnChallengesRepository::default()
+                ),
+            }
+        }
+
+        async fn register(&self, authenticator: &mut SoftwareAuthenticator) -> Result<(), HearthError> {
+            let options = (StartPasskeyRegistration {
+                users_repository: self.users_repository.clone(),
+                passkeys_repository: self.passkeys_repository.clone(),
+                webauthn_challenges_repository: self.webauthn_challenges_repository.clone(),
+                policy: PasskeyPolicy::default(),
+            }).execute(self.user_id).await?;
+
+            let (client_data_json, attestation_object) = authenticator.attestation(&options.challenge);
+
+            (RegisterPasskey {
+                passkeys_repository: self.passkeys_repository.clone(),
+                webauthn_challenges_repository: self.webauthn_challenges_repository.clone(),
+                policy: PasskeyPolicy::default(),
+            }).execute(RegisterPasskeyDTO {
+                user_id: self.user_id,
+                name: "Laptop".into(),
+                credential_id: authenticator.encoded_credential_id(),
+                client_data_json: WebAuthn::encode(&client_data_json),
+                attestation_object: WebAuthn::encode(&attestation_object),
+            }).await
+        }
+
+        async fn challenge(&self, challenge_token: Option<String>) -> String {
+            (StartPasskeyLogin {
+                passkeys_repository: self.passkeys_repository.clone(),
+                login_challenges_repository: self.login_challenges_repository.clone(),
+                webauthn_challenges_repository: self.webauthn_challenges_repository.clone(),
+                policy: PasskeyPolicy::default(),
+            })
+                .execute(PasskeyLoginOptionsRequestDTO { challenge_token }).await
+                .unwrap().challenge
+        }
+
+        fn login(&self) -> LoginWithPasskey {
+            LoginWithPasskey {
+                users_repository: self.users_repository.clone(),
+                passkeys_repository: self.passkeys_repository.clone(),
+                sessions_repository: self.sessions_repository.clone(),
+                login_challenges_repository: self.login_challenges_repository.clone(),
+                webauthn_challenges_repository: self.webauthn_challenges_repository.clone(),
+                policy: PasskeyPolicy::default(),
+                account_deletion_policy: AccountDeletionPolicy::default(),
+            }
+        }
+
+        async fn status(&self) -> (UserStatus, bool) {
+            let user = self.users_repository.get(self.user_id.to_string()).await.unwrap();
+            (user.status, user.deactivated_at.is_some())
+        }
+    }
+
+    fn assertion(
+        authenticator: &mut SoftwareAuthenticator,
+        challenge: &str,
+        challenge_token: Option<String>
+    ) -> LoginPasskeyDTO {
+        let (client_data_json, authenticator_data, signature) = authenticator.assertion(challenge);
+
+        LoginPasskeyDTO {
+            credential_id: authenticator.encoded_credential_id(),
+            client_data_json: WebAuthn::encode(&client_data_json),
+            authenticator_data: WebAuthn::encode(&authenticator_data),
+            signature: WebAuthn::encode(&signature),
+            challenge_token,
+        }
+    }
+
+    fn is_invalid_passkey(result: &Result<impl std::fmt::Debug, HearthError>) -> bool {
+        matches!(result, Err(HearthError::Unauthorized(code)) if code == INVALID_PASSKEY_ERROR_CODE)
+    }
+
+    #[tokio::test]
+    async fn should_register_and_login_without_a_password() {
+        let fixture = Fixture::new();
+        let mut authenticator = SoftwareAuthenticator::new(&PasskeyPolicy::default());
+        fixture.register(&mut authenticator).await.unwrap();
+
+        let result = fixture.register(&mut authenticator).await;
+        assert!(
+            matches!(result, Err(HearthError::Domain(code)) if code == PASSKEY_ALREADY_REGISTERED_ERROR_CODE)
+        );
+
+        let challenge = fixture.challenge(None).await;
+        let session = fixture
+            .login()
+            .execute(assertion(&mut authenticator, &challenge, None)).await
+            .unwrap();
+        assert_eq!(session.user_id, fixture.user_id);
+        assert_eq!(
+            fixture.sessions_repository.get_user_id(&hasher::hash!(session.token)).await.unwrap(),
+            Some(fixture.user_id)
+        );
+
+        // The challenge was spent by the first answer.
+        let result = fixture.login().execute(assertion(&mut authenticator, &challenge, None)).await;
+        assert!(is_invalid_passkey(&result));
+    }
+
+    #[tokio::test]
+    async fn should_refuse_a_registration_for_another_challenge_owner() {
+        let fixture = Fixture::new();
+        let mut authenticator = SoftwareAuthenticator::new(&PasskeyPolicy::default());
+        let challenge = fixture.challenge(None).await;
+        let (client_data_json, attestation_object) = authenticator.attestation(&challenge);
+
+        let result = (RegisterPasskey {
+            passkeys_repository: fixture.passkeys_repository.clone(),
+            webauthn_challenges_repository: fixture.webauthn_challenges_repository.clone(),
+            policy: PasskeyPolicy::default(),
+        }).execute(RegisterPasskeyDTO {
+            user_id: fixture.user_id,
+            name: "Laptop".into(),
+            credential_id: authenticator.encoded_credential_id(),
+            client_data_json: WebAuthn::encode(&client_data_json),
+            attestation_object: WebAuthn::encode(&attestation_object),
+        }).await;
+
+        assert!(
+            matches!(result, Err(HearthError::Domain(code)) if code == INVALID_WEBAUTHN_CHALLENGE_ERROR_CODE)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_refuse_a_cloned_authenticator() {
+        let fixture = Fixture::new();
+        let mut authenticator = SoftwareAuthenticator::new(&PasskeyPolicy::default());
+        fixture.register(&mut authenticator).await.unwrap();
+
+        let challenge = fixture.challenge(None).await;
+        let dto = assertion(&mut authenticator, &challenge, None);
+        fixture.login().execute(dto).await.unwrap();
+
+        authenticator.sign_count -= 1;
+        let challenge = fixture.challenge(None).await;
+        let result = fixture.login().execute(assertion(&mut authenticator, &challenge, None)).await;
+        assert!(is_invalid_passkey(&result));
+    }
+
+    #[tokio::test]
+    async fn should_be_usable_as_a_second_factor() {
+        let fixture = Fixture::new();
+        let mut authenticator = SoftwareAuthenticator::new(&PasskeyPolicy::default());
+        fixture.register(&mut authenticator).await.unwrap();
+
+        let challenge_token = "password-step-token".to_string();
+        fixture.login_challenges_repository
+            .create(&hasher::hash!(challenge_token), &fixture.user_id, 300).await
+            .unwrap();
+
+        let challenge = fixture.challenge(Some(challenge_token.clone())).await;
+        let session = fixture
+            .login()
+            .execute(assertion(&mut authenticator, &challenge, Some(challenge_token.clone()))).await
+            .unwrap();
+        assert_eq!(session.user_id, fixture.user_id);
+        assert_eq!(
+            fixture.login_challenges_repository
+                .get_user_id(&hasher::hash!(challenge_token)).await
+                .unwrap(),
+            None
+        );
+
+        // Another user's passkey can't answer someone else's login.
+        let challenge_token = "other-token".to_string();
+        fixture.login_challenges_repository
+            .create(&hasher::hash!(challenge_token), &Uuid::new_v4(), 300).await
+            .unwrap();
+        let challenge = fixture.challenge(Some(challenge_token.clone())).await;
+        let result = fixture
+            .login()
+            .execute(assertion(&mut authenticator, &challenge, Some(challenge_token))).await;
+        assert!(is_invalid_passkey(&result));
+    }
+
+    #[tokio::test]
+    async fn should_cancel_a_pending_deletion_during_the_grace_period_only() {
+        let fixture = Fixture::new();
+        let mut authenticator = SoftwareAuthenticator::new(&PasskeyPolicy::default());
+        fixture.register(&mut authenticator).await.unwrap();
+        fixture.users_repository.set_deactivated(&fixture.user_id, Some(Utc::now())).await.unwrap();
+
+        let challenge = fixture.challenge(None).await;
+        fixture.login().execute(assertion(&mut authenticator, &challenge, None)).await.unwrap();
+        assert_eq!(fixture.status().await, (UserStatus::Active, false));
+
+        fixture.users_repository
+            .set_deactivated(&fixture.user_id, Some(Utc::now() - Duration::days(31))).await
+            .unwrap();
+        let challenge = fixture.challenge(None).await;
+        let result = fixture.login().execute(assertion(&mut authenticator, &challenge, None)).await;
+        assert_eq!(
+            result.unwrap_err(),
+            HearthError::Forbidden(ACCOUNT_DEACTIVATED_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_cancel_a_pending_deletion_once_the_second_factor_passed() {
+        let fixture = Fixture::new();
+        let mut authenticator = SoftwareAuthenticator::new(&PasskeyPolicy::default());
+        fixture.register(&mut authenticator).await.unwrap();
+        fixture.users_repository.set_deactivated(&fixture.user_id, Some(Utc::now())).await.unwrap();
+
+        let challenge_token = "password-step-token".to_string();
+        fixture.login_challenges_repository
+            .create(&hasher::hash!(challenge_token), &fixture.user_id, 300).await
+            .unwrap();
+        let challenge = fixture.challenge(Some(challenge_token.clone())).await;
+        assert_eq!(fixture.status().await, (UserStatus::Deactivated, true));
+
+        fixture
+            .login()
+            .execute(assertion(&mut authenticator, &challenge, Some(challenge_token))).await
+            .unwrap();
+        assert_eq!(fixture.status().await, (UserStatus::Active, false));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod login_with_passkey;
+pub mod register_passkey;
+pub mod start_passkey_login;
+pub mod start_passkey_registration;
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::passkey::{ PasskeyDTO, RegisterPasskeyDTO, WebAuthnCeremony },
+    entities::webauthn::{ CREATE_CEREMONY, WebAuthn },
+    error_codes::{
+        INVALID_WEBAUTHN_CHALLENGE_ERROR_CODE,
+        INVALID_WEBAUTHN_RESPONSE_ERROR_CODE,
+        PASSKEY_ALREADY_REGISTERED_ERROR_CODE,
+        UNSUPPORTED_PASSKEY_ALGORITHM_ERROR_CODE,
+    },
+    features::feature::Feature,
+    policies::passkey::PasskeyPolicy,
+    repositories::{
+        passkeys_repository::PasskeysRepository,
+        webauthn_challenges_repository::WebAuthnChallengesRepository,
+    },
+};
+
+pub type RegisterPasskeyFeature = dyn Feature<RegisterPasskeyDTO, ()>;
+
+/// Finishes the registration ceremony started by `StartPasskeyRegistration`.
+pub struct RegisterPasskey {
+    pub passkeys_repository: BArc<dyn PasskeysRepository>,
+    pub webauthn_challenges_repository: BArc<dyn WebAuthnChallengesRepository>,
+    pub policy: PasskeyPolicy,
+}
+
+#[async_trait]
+impl Feature<RegisterPasskeyDTO, ()> for RegisterPasskey {
+    async fn execute(&self, input: RegisterPasskeyDTO) -> Result<(), HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("REGISTER_PASSKEY".into(), e));
+        }
+
+        let invalid_response = || HearthError::Domain(INVALID_WEBAUTHN_RESPONSE_ERROR_CODE.into());
+
+        let client_data_json = WebAuthn::decode(&input.client_data_json).ok_or_else(invalid_response)?;
+        let client_data = WebAuthn::parse_client_data(
+            &client_data_json,
+            CREATE_CEREMONY,
+            &self.policy
+        ).ok_or_else(invalid_response)?;
+
+        let challenge = self.webauthn_challenges_repository.take(&client_data.challenge).await?;
+        if
+            challenge.is_none_or(
+                |challenge|
+                    challenge.ceremony != WebAuthnCeremony::Registration ||
+                    challenge.user_id != Some(input.user_id)
+            )
+        {
+            return Err(HearthError::Domain(INVALID_WEBAUTHN_CHALLENGE_ERROR_CODE.into()));
+        }
+
+        let attestation_object = WebAuthn::decode(&input.attestation_object).ok_or_else(
+            invalid_response
+        )?;
+        let auth_data = WebAuthn::parse_attestation_object(&attestation_object).ok_or_else(
+            invalid_response
+        )?;
+        let data = WebAuthn::parse_authenticator_data(&auth_data).ok_or_else(|| {
+            HearthError::Domain(UNSUPPORTED_PASSKEY_ALGORITHM_ERROR_CODE.into())
+        })?;
+        if !WebAuthn::check_authenticator_data(&data, &self.policy) {
+            return Err(invalid_response());
+        }
+
+        let credential = data.attested_credential.ok_or_else(invalid_response)?;
+        if Some(&credential.credential_id) != WebAuthn::decode(&input.credential_id).as_ref() {
+            return Err(invalid_response());
+        }
+
+        if
+            self.passkeys_repository
+                .get_by_credential_id(&credential.credential_id).await?
+                .is_some()
+        {
+            return Err(HearthError::Domain(PASSKEY_ALREADY_REGISTERED_ERROR_CODE.into()));
+        }
+
+        self.passkeys_repository.create(PasskeyDTO {
+            passkey_id: Uuid::new_v4(),
+            user_id: input.user_id,
+            credential_id: credential.credential_id,
+            public_key: credential.public_key,
+            sign_count: data.sign_count,
+            name: input.name,
+            created_at: Utc::now(),
+            last_used_at: None,
+        }).await
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::passkey::{
+        CredentialDescriptorDTO,
+        PasskeyLoginOptionsDTO,
+        PasskeyLoginOptionsRequestDTO,
+        WebAuthnCeremony,
+        WebAuthnChallengeDTO,
+    },
+    entities::webauthn::WebAuthn,
+    error_codes::INVALID_LOGIN_CHALLENGE_ERROR_CODE,
+    features::{
+        feature::Feature,
+        passkeys::start_passkey_registration::user_verification,
+    },
+    policies::passkey::PasskeyPolicy,
+    repositories::{
+        login_challenges_repository::LoginChallengesRepository,
+        passkeys_repository::PasskeysRepository,
+        webauthn_challenges_repository::WebAuthnChallengesRepository,
+    },
+};
+
+pub type StartPasskeyLoginFeature = dyn Feature<PasskeyLoginOptionsRequestDTO, PasskeyLoginOptionsDTO>;
+
+pub struct StartPasskeyLogin {
+    pub passkeys_repository: BArc<dyn PasskeysRepository>,
+    pub login_challenges_repository: BArc<dyn LoginChallengesRepository>,
+    pub webauthn_challenges_repository: BArc<dyn WebAuthnChallengesRepository>,
+    pub policy: PasskeyPolicy,
+}
+
+#[async_trait]
+impl Feature<PasskeyLoginOptionsRequestDTO, PasskeyLoginOptionsDTO> for StartPasskeyLogin {
+    async fn execute(
+        &self,
+        input: PasskeyLoginOptionsRequestDTO
+    ) -> Result<PasskeyLoginOptionsDTO, HearthError> {
+        // As a second factor only the passkeys of the user who typed the password are allowed.
+        let (user_id, login_challenge) = match input.challenge_token {
+            Some(challenge_token) => {
+                let login_challenge = hasher::hash!(challenge_token);
+                let user_id = self.login_challenges_repository
+                    .get_user_id(&login_challenge).await?
+                    .ok_or_else(|| {
+                        HearthError::Unauthorized(INVALID_LOGIN_CHALLENGE_ERROR_CODE.into())
+                    })?;
+                (Some(user_id), Some(login_challenge))
+            }
+            None => (None, None),
+        };
+
+        let allow_credentials = match &user_id {
+            Some(user_id) =>
+                self.passkeys_repository
+                    .list(user_id).await?
+                    .iter()
+                    .map(|passkey| CredentialDescriptorDTO {
+                        kind: "public-key".into(),
+                        id: WebAuthn::encode(&passkey.credential_id),
+                    })
+                    .collect(),
+            None => vec![],
+        };
+
+        let challenge = WebAuthnChallengeDTO {
+            challenge: WebAuthn::generate_challenge(),
+            ceremony: WebAuthnCeremony::Authentication,
+            user_id,
+            login_challenge,
+        };
+        self.webauthn_challenges_repository.create(
+            &challenge,
+            self.policy.challenge_ttl_seconds
+        ).await?;
+
+        Ok(PasskeyLoginOptionsDTO {
+            challenge: challenge.challenge,
+            rp_id: self.policy.rp_id.clone(),
+            timeout: self.policy.challenge_ttl_seconds * 1000,
+            allow_credentials,
+            user_verification: user_verification(&self.policy),
+        })
+    }
+}
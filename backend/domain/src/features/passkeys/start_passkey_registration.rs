@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::passkey::{
+        CredentialDescriptorDTO,
+        CredentialParameterDTO,
+        ES256,
+        PasskeyRegistrationOptionsDTO,
+        PasskeyUserDTO,
+        RelyingPartyDTO,
+        WebAuthnCeremony,
+        WebAuthnChallengeDTO,
+    },
+    entities::webauthn::WebAuthn,
+    error_codes::TOO_MANY_PASSKEYS_ERROR_CODE,
+    features::feature::Feature,
+    policies::passkey::PasskeyPolicy,
+    repositories::{
+        passkeys_repository::PasskeysRepository,
+        users_repository::UsersRepository,
+        webauthn_challenges_repository::WebAuthnChallengesRepository,
+    },
+};
+
+pub type StartPasskeyRegistrationFeature = dyn Feature<Uuid, PasskeyRegistrationOptionsDTO>;
+
+pub struct StartPasskeyRegistration {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub passkeys_repository: BArc<dyn PasskeysRepository>,
+    pub webauthn_challenges_repository: BArc<dyn WebAuthnChallengesRepository>,
+    pub policy: PasskeyPolicy,
+}
+
+#[async_trait]
+impl Feature<Uuid, PasskeyRegistrationOptionsDTO> for StartPasskeyRegistration {
+    async fn execute(&self, user_id: Uuid) -> Result<PasskeyRegistrationOptionsDTO, HearthError> {
+        let passkeys = self.passkeys_repository.list(&user_id).await?;
+        if passkeys.len() >= self.policy.max_passkeys_per_user {
+            return Err(HearthError::Domain(TOO_MANY_PASSKEYS_ERROR_CODE.into()));
+        }
+
+        let user = self.users_repository.get(user_id.to_string()).await?;
+
+        let challenge = WebAuthnChallengeDTO {
+            challenge: WebAuthn::generate_challenge(),
+            ceremony: WebAuthnCeremony::Registration,
+            user_id: Some(user_id),
+            login_challenge: None,
+        };
+        self.webauthn_challenges_repository.create(
+            &challenge,
+            self.policy.challenge_ttl_seconds
+        ).await?;
+
+        Ok(PasskeyRegistrationOptionsDTO {
+            challenge: challenge.challenge,
+            rp: RelyingPartyDTO { id: self.policy.rp_id.clone(), name: self.policy.rp_name.clone() },
+            user: PasskeyUserDTO {
+                id: WebAuthn::encode(user_id.as_bytes()),
+                name: user.username.clone(),
+                display_name: user.username,
+            },
+            pub_key_cred_params: vec![CredentialParameterDTO { kind: "public-key".into(), alg: ES256 }],
+            timeout: self.policy.challenge_ttl_seconds * 1000,
+            exclude_credentials: passkeys
+                .iter()
+                .map(|passkey| CredentialDescriptorDTO {
+                    kind: "public-key".into(),
+                    id: WebAuthn::encode(&passkey.credential_id),
+                })
+                .collect(),
+            user_verification: user_verification(&self.policy),
+        })
+    }
+}
+
+pub(crate) fn user_verification(policy: &PasskeyPolicy) -> String {
+    (if policy.require_user_verification { "required" } else { "preferred" }).into()
+}
@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use chrono::{ Duration, Utc };
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        oauth::Scope,
+        personal_access_token::{
+            CreatePersonalAccessTokenDTO,
+            CreatedPersonalAccessTokenDTO,
+            PersonalAccessTokenDTO,
+        },
+    },
+    entities::personal_access_token::PersonalAccessToken,
+    error_codes::TOO_MANY_PERSONAL_ACCESS_TOKENS_ERROR_CODE,
+    features::feature::Feature,
+    policies::personal_access_token::PersonalAccessTokenPolicy,
+    repositories::personal_access_tokens_repository::PersonalAccessTokensRepository,
+};
+
+pub type CreatePersonalAccessTokenFeature = dyn Feature<
+    CreatePersonalAccessTokenDTO,
+    CreatedPersonalAccessTokenDTO
+>;
+
+/// Tokens for scripts and bots, scoped like OAuth tokens but minted by the user.
+pub struct CreatePersonalAccessToken {
+    pub personal_access_tokens_repository: BArc<dyn PersonalAccessTokensRepository>,
+    pub policy: PersonalAccessTokenPolicy,
+}
+
+#[async_trait]
+impl Feature<CreatePersonalAccessTokenDTO, CreatedPersonalAccessTokenDTO>
+for CreatePersonalAccessToken {
+    async fn execute(
+        &self,
+        input: CreatePersonalAccessTokenDTO
+    ) -> Result<CreatedPersonalAccessTokenDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("CREATE_PERSONAL_ACCESS_TOKEN".into(), e));
+        }
+
+        let count = self.personal_access_tokens_repository.count_by_user(&input.user_id).await?;
+        if count >= (self.policy.max_tokens_per_user as u64) {
+            return Err(HearthError::Domain(TOO_MANY_PERSONAL_ACCESS_TOKENS_ERROR_CODE.into()));
+        }
+
+        let token = PersonalAccessToken::generate();
+        let now = Utc::now();
+        let ttl_days = input.expires_in_days.unwrap_or(self.policy.default_ttl_days);
+
+        let personal_access_token = PersonalAccessTokenDTO {
+            token_id: Uuid::new_v4(),
+            user_id: input.user_id,
+            name: input.name,
+            token_hash: hasher::hash!(token),
+            scopes: Scope::ALL.into_iter()
+                .filter(|scope| input.scopes.contains(scope))
+                .collect(),
+            expires_at: now + Duration::days(ttl_days as i64),
+            last_used_at: None,
+            created_at: now,
+        };
+        let token_id = personal_access_token.token_id;
+        let expires_at = personal_access_token.expires_at;
+        self.personal_access_tokens_repository.create(personal_access_token).await?;
+
+        Ok(CreatedPersonalAccessTokenDTO { token_id, token, expires_at })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            oauth::Scope,
+            personal_access_token::{ CreatePersonalAccessTokenDTO, RevokePersonalAccessTokenDTO },
+        },
+        entities::personal_access_token::PersonalAccessToken,
+        error_codes::{
+            PERSONAL_ACCESS_TOKEN_NOT_FOUND_ERROR_CODE,
+            TOO_MANY_PERSONAL_ACCESS_TOKENS_ERROR_CODE,
+        },
+        features::{
+            feature::Feature,
+            personal_access_tokens::{
+                create_personal_access_token::CreatePersonalAccessToken,
+                list_personal_access_tokens::ListPersonalAccessTokens,
+                revoke_personal_access_token::RevokePersonalAccessToken,
+            },
+        },
+        policies::personal_access_token::PersonalAccessTokenPolicy,
+        repositories::personal_access_tokens_repository::PersonalAccessTokensRepository,
+        test_utils::test_utils::InMemoryPersonalAccessTokensRepository,
+    };
+
+    fn dto(user_id: Uuid, expires_in_days: Option<u32>) -> CreatePersonalAccessTokenDTO {
+        CreatePersonalAccessTokenDTO {
+            user_id,
+            name: "Backup script".into(),
+            scopes: vec![Scope::Write, Scope::Read, Scope::Read],
+            expires_in_days,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_create_list_and_revoke_tokens() {
+        let repository: BArc<dyn PersonalAccessTokensRepository> = barc!(
+            InMemoryPersonalAccessTokensRepository::default()
+        );
+        let create = CreatePersonalAccessToken {
+            personal_access_tokens_repository: repository.clone(),
+            policy: PersonalAccessTokenPolicy { max_tokens_per_user: 1, ..Default::default() },
+        };
+        let list = ListPersonalAccessTokens { personal_access_tokens_repository: repository.clone() };
+        let revoke = RevokePersonalAccessToken { personal_access_tokens_repository: repository };
+        let user_id = Uuid::new_v4();
+
+        let created = create.execute(dto(user_id, Some(7))).await.unwrap();
+        assert!(PersonalAccessToken::is_personal_access_token(&created.token));
+
+        let tokens = list.execute(user_id).await.unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].token_id, created.token_id);
+        assert_eq!(tokens[0].token_hash, hasher::hash!(created.token));
+        assert_eq!(tokens[0].scopes, vec![Scope::Read, Scope::Write]);
+        assert_eq!((tokens[0].expires_at - tokens[0].created_at).num_days(), 7);
+
+        assert_eq!(
+            create.execute(dto(user_id, None)).await.unwrap_err(),
+            HearthError::Domain(TOO_MANY_PERSONAL_ACCESS_TOKENS_ERROR_CODE.into())
+        );
+
+        let revocation = |user_id| RevokePersonalAccessTokenDTO {
+            user_id,
+            token_id: created.token_id,
+        };
+        assert_eq!(
+            revoke.execute(revocation(Uuid::new_v4())).await.unwrap_err(),
+            HearthError::not_found(PERSONAL_ACCESS_TOKEN_NOT_FOUND_ERROR_CODE.into())
+        );
+        revoke.execute(revocation(user_id)).await.unwrap();
+        assert!(list.execute(user_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_reject_tokens_living_over_a_year() {
+        let create = CreatePersonalAccessToken {
+            personal_access_tokens_repository: barc!(
+                InMemoryPersonalAccessTokensRepository::default()
+            ),
+            policy: PersonalAccessTokenPolicy::default(),
+        };
+
+        let Err(HearthError::Validation(code, _)) = create.execute(
+            dto(Uuid::new_v4(), Some(366))
+        ).await else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(code, "CREATE_PERSONAL_ACCESS_TOKEN");
+    }
+}
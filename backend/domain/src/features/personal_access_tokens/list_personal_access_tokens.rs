@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::personal_access_token::PersonalAccessTokenDTO,
+    features::feature::Feature,
+    repositories::personal_access_tokens_repository::PersonalAccessTokensRepository,
+};
+
+pub type ListPersonalAccessTokensFeature = dyn Feature<Uuid, Vec<PersonalAccessTokenDTO>>;
+
+/// Expired tokens are listed too, until they are revoked.
+pub struct ListPersonalAccessTokens {
+    pub personal_access_tokens_repository: BArc<dyn PersonalAccessTokensRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, Vec<PersonalAccessTokenDTO>> for ListPersonalAccessTokens {
+    async fn execute(&self, user_id: Uuid) -> Result<Vec<PersonalAccessTokenDTO>, HearthError> {
+        self.personal_access_tokens_repository.list_by_user(&user_id).await
+    }
+}
@@ -0,0 +1,3 @@
+pub mod create_personal_access_token;
+pub mod list_personal_access_tokens;
+pub mod revoke_personal_access_token;
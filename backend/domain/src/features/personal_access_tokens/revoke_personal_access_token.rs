@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::personal_access_token::RevokePersonalAccessTokenDTO,
+    features::feature::Feature,
+    repositories::personal_access_tokens_repository::PersonalAccessTokensRepository,
+};
+
+pub type RevokePersonalAccessTokenFeature = dyn Feature<RevokePersonalAccessTokenDTO, ()>;
+
+pub struct RevokePersonalAccessToken {
+    pub personal_access_tokens_repository: BArc<dyn PersonalAccessTokensRepository>,
+}
+
+#[async_trait]
+impl Feature<RevokePersonalAccessTokenDTO, ()> for RevokePersonalAccessToken {
+    async fn execute(&self, input: RevokePersonalAccessTokenDTO) -> Result<(), HearthError> {
+        self.personal_access_tokens_repository.delete(&input.user_id, &input.token_id).await
+    }
+}
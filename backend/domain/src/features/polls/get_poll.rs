@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::poll::{ GetPollDTO, PollDTO },
+    entities::posts::Posts,
+    features::feature::Feature,
+    repositories::{ polls_repository::PollsRepository, posts_repository::PostsRepository },
+};
+
+pub type GetPollFeature = dyn Feature<GetPollDTO, PollDTO>;
+
+pub struct GetPoll {
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub polls_repository: BArc<dyn PollsRepository>,
+}
+
+#[async_trait]
+impl Feature<GetPollDTO, PollDTO> for GetPoll {
+    async fn execute(&self, input: GetPollDTO) -> Result<PollDTO, HearthError> {
+        let post = self.posts_repository.get(&input.post_id).await?;
+        let poll = self.polls_repository.get(&input.post_id).await?;
+        let own_votes = self.polls_repository.own_votes(&input.post_id, &input.viewer_id).await?;
+
+        Ok(Posts::poll_for_viewer(poll, own_votes, post.author_id == input.viewer_id, Utc::now()))
+    }
+}
@@ -0,0 +1,3 @@
+pub mod get_poll;
+pub mod notify_ended_polls;
+pub mod vote_poll;
@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::notification::{ NotificationDTO, NotificationKind },
+    features::feature::Feature,
+    repositories::{
+        notifications_repository::NotificationsRepository,
+        polls_repository::PollsRepository,
+        posts_repository::PostsRepository,
+    },
+};
+
+const BATCH_SIZE: u64 = 100;
+
+/// Notifies the voters of every poll closed at the given instant, returns the
+/// number of polls handled. Meant to be run periodically.
+pub type NotifyEndedPollsFeature = dyn Feature<DateTime<Utc>, u64>;
+
+pub struct NotifyEndedPolls {
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub polls_repository: BArc<dyn PollsRepository>,
+    pub notifications_repository: BArc<dyn NotificationsRepository>,
+}
+
+#[async_trait]
+impl Feature<DateTime<Utc>, u64> for NotifyEndedPolls {
+    async fn execute(&self, at: DateTime<Utc>) -> Result<u64, HearthError> {
+        let ended = self.polls_repository.list_ended_unnotified(at, BATCH_SIZE).await?;
+
+        for post_id in &ended {
+            let post = self.posts_repository.get(post_id).await?;
+
+            for voter_id in self.polls_repository.voters(post_id).await? {
+                if voter_id == post.author_id {
+                    continue;
+                }
+
+                self.notifications_repository.create(NotificationDTO {
+                    notification_id: Uuid::new_v4(),
+                    recipient_id: voter_id,
+                    actor_id: post.author_id,
+                    kind: NotificationKind::PollEnded,
+                    post_id: Some(*post_id),
+                    list_id: None,
+                    filtered: vec![],
+                    created_at: at,
+                }).await?;
+            }
+
+            self.polls_repository.mark_ended_notified(post_id).await?;
+        }
+
+        Ok(ended.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{ notification::NotificationKind, poll::{ CreatePollDTO, VotePollDTO }, post::CreatePostDTO },
+        features::{
+            feature::Feature,
+            polls::{ notify_ended_polls::NotifyEndedPolls, vote_poll::VotePoll },
+            posts::create_post::CreatePost,
+        },
+        repositories::{
+            notifications_repository::NotificationsRepository,
+            polls_repository::PollsRepository,
+            posts_repository::PostsRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryNotificationsRepository,
+            InMemoryPollsRepository,
+            InMemoryPostsRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_notify_voters_once_when_the_poll_ends() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(InMemoryPostsRepository::default());
+        let polls_repository: BArc<dyn PollsRepository> = barc!(InMemoryPollsRepository::default());
+        let notifications_repository: BArc<dyn NotificationsRepository> = barc!(
+            InMemoryNotificationsRepository::default()
+        );
+        let author_id = Uuid::new_v4();
+        let voter_id = Uuid::new_v4();
+
+        let post = CreatePost {
+            posts_repository: posts_repository.clone(),
+            polls_repository: polls_repository.clone(),
+            ..Default::default()
+        }
+            .execute(CreatePostDTO {
+                author_id,
+                poll: Some(CreatePollDTO {
+                    options: vec!["yes".into(), "no".into()],
+                    multiple_choice: false,
+                    duration_seconds: 3600,
+                }),
+                ..Default::default()
+            }).await
+            .unwrap();
+
+        let vote_poll = VotePoll { polls_repository: polls_repository.clone() };
+        for user_id in [voter_id, author_id] {
+            vote_poll
+                .execute(VotePollDTO { post_id: post.post_id, user_id, choices: vec![0] }).await
+                .unwrap();
+        }
+
+        let notify_ended_polls = NotifyEndedPolls {
+            posts_repository,
+            polls_repository,
+            notifications_repository: notifications_repository.clone(),
+        };
+
+        assert_eq!(notify_ended_polls.execute(Utc::now()).await.unwrap(), 0);
+
+        let after_close = Utc::now() + Duration::hours(2);
+        assert_eq!(notify_ended_polls.execute(after_close).await.unwrap(), 1);
+        assert_eq!(notify_ended_polls.execute(after_close).await.unwrap(), 0);
+
+        let notifications = notifications_repository.list(&voter_id, None, 10).await.unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::PollEnded);
+        assert_eq!(notifications[0].post_id, Some(post.post_id));
+        assert!(notifications_repository.list(&author_id, None, 10).await.unwrap().is_empty());
+    }
+}
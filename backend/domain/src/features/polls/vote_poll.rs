@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::poll::{ PollDTO, VotePollDTO },
+    entities::posts::Posts,
+    error_codes::POLL_CLOSED_ERROR_CODE,
+    features::feature::Feature,
+    repositories::polls_repository::PollsRepository,
+};
+
+pub type VotePollFeature = dyn Feature<VotePollDTO, PollDTO>;
+
+pub struct VotePoll {
+    pub polls_repository: BArc<dyn PollsRepository>,
+}
+
+#[async_trait]
+impl Feature<VotePollDTO, PollDTO> for VotePoll {
+    async fn execute(&self, input: VotePollDTO) -> Result<PollDTO, HearthError> {
+        let poll = self.polls_repository.get(&input.post_id).await?;
+
+        if poll.is_closed(Utc::now()) {
+            return Err(HearthError::Domain(POLL_CLOSED_ERROR_CODE.into()));
+        }
+
+        let choices = Posts::check_choices(&poll, &input.choices)?;
+        self.polls_repository.vote(&input.post_id, &input.user_id, &choices).await?;
+
+        let poll = self.polls_repository.get(&input.post_id).await?;
+        Ok(Posts::poll_for_viewer(poll, choices, false, Utc::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{ poll::{ CreatePollDTO, GetPollDTO, PollDTO, VotePollDTO }, post::CreatePostDTO },
+        entities::posts::Posts,
+        error_codes::{ POLL_ALREADY_VOTED_ERROR_CODE, POLL_CLOSED_ERROR_CODE },
+        features::{
+            feature::Feature,
+            polls::{ get_poll::GetPoll, vote_poll::VotePoll },
+            posts::create_post::CreatePost,
+        },
+        policies::poll::PollPolicy,
+        repositories::{ polls_repository::PollsRepository, posts_repository::PostsRepository },
+        test_utils::test_utils::{ InMemoryPollsRepository, InMemoryPostsRepository },
+    };
+
+    async fn create_poll(polls_repository: &BArc<dyn PollsRepository>, multiple_choice: bool) -> PollDTO {
+        let poll = Posts::new_poll(
+            Uuid::new_v4(),
+            Utc::now(),
+            &(CreatePollDTO {
+                options: vec!["tea".into(), "coffee".into(), "water".into()],
+                multiple_choice,
+                duration_seconds: 3600,
+            }),
+            &PollPolicy::default()
+        ).unwrap();
+        polls_repository.create(poll.clone()).await.unwrap();
+        poll
+    }
+
+    fn vote(poll: &PollDTO, user_id: Uuid, choices: Vec<u8>) -> VotePollDTO {
+        VotePollDTO { post_id: poll.post_id, user_id, choices }
+    }
+
+    #[tokio::test]
+    async fn should_count_votes_and_reveal_results_to_voters() {
+        let polls_repository: BArc<dyn PollsRepository> = barc!(InMemoryPollsRepository::default());
+        let poll = create_poll(&polls_repository, true).await;
+        let vote_poll = VotePoll { polls_repository: polls_repository.clone() };
+
+        vote_poll.execute(vote(&poll, Uuid::new_v4(), vec![0])).await.unwrap();
+        let result = vote_poll.execute(vote(&poll, Uuid::new_v4(), vec![0, 2])).await.unwrap();
+
+        assert_eq!(result.voters_count, Some(2));
+        assert_eq!(
+            result.options
+                .iter()
+                .map(|o| o.votes_count)
+                .collect::<Vec<_>>(),
+            vec![Some(2), Some(0), Some(1)]
+        );
+        assert_eq!(result.own_votes, vec![0, 2]);
+    }
+
+    #[tokio::test]
+    async fn should_allow_a_single_vote_per_user() {
+        let polls_repository: BArc<dyn PollsRepository> = barc!(InMemoryPollsRepository::default());
+        let poll = create_poll(&polls_repository, false).await;
+        let vote_poll = VotePoll { polls_repository: polls_repository.clone() };
+        let user_id = Uuid::new_v4();
+
+        vote_poll.execute(vote(&poll, user_id, vec![1])).await.unwrap();
+        let result = vote_poll.execute(vote(&poll, user_id, vec![2])).await;
+
+        assert_eq!(result.unwrap_err(), HearthError::Domain(POLL_ALREADY_VOTED_ERROR_CODE.into()));
+        assert_eq!(polls_repository.get(&poll.post_id).await.unwrap().voters_count, Some(1));
+    }
+
+    #[tokio::test]
+    async fn should_reject_votes_on_closed_polls() {
+        let polls_repository: BArc<dyn PollsRepository> = barc!(InMemoryPollsRepository::default());
+        let poll = PollDTO {
+            closes_at: Utc::now() - Duration::seconds(1),
+            ..create_poll(&polls_repository, false).await
+        };
+        polls_repository.create(poll.clone()).await.unwrap();
+        let vote_poll = VotePoll { polls_repository };
+
+        let result = vote_poll.execute(vote(&poll, Uuid::new_v4(), vec![0])).await;
+
+        assert_eq!(result.unwrap_err(), HearthError::Domain(POLL_CLOSED_ERROR_CODE.into()));
+    }
+
+    #[tokio::test]
+    async fn should_hide_results_until_the_viewer_voted() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(InMemoryPostsRepository::default());
+        let polls_repository: BArc<dyn PollsRepository> = barc!(InMemoryPollsRepository::default());
+        let author_id = Uuid::new_v4();
+        let post = CreatePost {
+            posts_repository: posts_repository.clone(),
+            polls_repository: polls_repository.clone(),
+            ..Default::default()
+        }
+            .execute(CreatePostDTO {
+                author_id,
+                poll: Some(CreatePollDTO {
+                    options: vec!["yes".into(), "no".into()],
+                    multiple_choice: false,
+                    duration_seconds: 3600,
+                }),
+                ..Default::default()
+            }).await
+            .unwrap();
+        let get_poll = GetPoll { posts_repository, polls_repository: polls_repository.clone() };
+        let viewer_id = Uuid::new_v4();
+
+        let hidden = get_poll.execute(GetPollDTO { post_id: post.post_id, viewer_id }).await.unwrap();
+        assert_eq!(hidden.voters_count, None);
+        assert!(hidden.options.iter().all(|o| o.votes_count.is_none()));
+
+        let for_author = get_poll
+            .execute(GetPollDTO { post_id: post.post_id, viewer_id: author_id }).await
+            .unwrap();
+        assert_eq!(for_author.voters_count, Some(0));
+
+        VotePoll { polls_repository }
+            .execute(VotePollDTO { post_id: post.post_id, user_id: viewer_id, choices: vec![1] }).await
+            .unwrap();
+        let revealed = get_poll.execute(GetPollDTO { post_id: post.post_id, viewer_id }).await.unwrap();
+        assert_eq!(revealed.voters_count, Some(1));
+        assert_eq!(revealed.own_votes, vec![1]);
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        job::JobPayload,
+        media::{ MediaDTO, MediaStatus },
+        post::{ CreatePostDTO, MentionDTO, PostDTO },
+    },
+    entities::{ jobs::Jobs, posts::Posts },
+    error_codes::{
+        MEDIA_NOT_FOUND_ERROR_CODE,
+        MEDIA_NOT_OWNED_ERROR_CODE,
+        MEDIA_NOT_READY_ERROR_CODE,
+        TOO_MANY_ATTACHMENTS_ERROR_CODE,
+    },
+    features::feature::Feature,
+    parsers::{ hashtags::unique_hashtags, links::extract_links, mentions::extract_mentions },
+    policies::{ media::MediaPolicy, poll::PollPolicy },
+    repositories::{
+        job_queue::JobQueue,
+        media_repository::MediaRepository,
+        polls_repository::PollsRepository,
+        posts_repository::PostsRepository,
+        trends_repository::TrendsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type CreatePostFeature = dyn Feature<CreatePostDTO, PostDTO>;
+
+pub struct CreatePost {
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub trends_repository: BArc<dyn TrendsRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub media_policy: MediaPolicy,
+    pub polls_repository: BArc<dyn PollsRepository>,
+    pub poll_policy: PollPolicy,
+    /// Mentions are notified and links unfurled in the background.
+    pub job_queue: BArc<dyn JobQueue>,
+}
+
+impl CreatePost {
+    /// Loads the attachments in the requested order, all of them must belong to the author.
+    async fn resolve_media(
+        &self,
+        author_id: &Uuid,
+        media_ids: &[Uuid]
+    ) -> Result<Vec<MediaDTO>, HearthError> {
+        if media_ids.len() > self.media_policy.max_attachments_per_post {
+            return Err(HearthError::Domain(TOO_MANY_ATTACHMENTS_ERROR_CODE.into()));
+        }
+
+        if media_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let found = self.media_repository.get_many(media_ids).await?;
+        let mut media = vec![];
+
+        for media_id in media_ids {
+            let item = found
+                .iter()
+                .find(|m| m.media_id == *media_id)
+                .ok_or_else(|| HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()))?;
+
+            if item.owner_id != *author_id {
+                return Err(HearthError::Forbidden(MEDIA_NOT_OWNED_ERROR_CODE.into()));
+            }
+
+            if item.status != MediaStatus::Ready {
+                return Err(HearthError::Domain(MEDIA_NOT_READY_ERROR_CODE.into()));
+            }
+
+            if !media.contains(item) {
+                media.push(item.clone());
+            }
+        }
+
+        Ok(media)
+    }
+
+    /// Keeps only the mentions matching an existing user, the others stay plain text.
+    async fn resolve_mentions(&self, content: &str) -> Result<Vec<MentionDTO>, HearthError> {
+        let mentions = extract_mentions(content);
+
+        if mentions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut usernames: Vec<String> = mentions
+            .iter()
+            .map(|m| m.username.to_lowercase())
+            .collect();
+        usernames.sort();
+        usernames.dedup();
+
+        let users: HashMap<String, (Uuid, String)> = self.users_repository
+            .get_by_usernames(&usernames).await?
+            .into_iter()
+            .map(|user| (user.username.to_lowercase(), (user.user_id, user.username)))
+            .collect();
+
+        Ok(
+            mentions
+                .into_iter()
+                .filter_map(|mention| {
+                    users.get(&mention.username.to_lowercase()).map(|(user_id, username)| MentionDTO {
+                        user_id: *user_id,
+                        username: username.clone(),
+                        start: mention.start,
+                        end: mention.end,
+                    })
+                })
+                .collect()
+        )
+    }
+}
+
+#[async_trait]
+impl Feature<CreatePostDTO, PostDTO> for CreatePost {
+    async fn execute(&self, input: CreatePostDTO) -> Result<PostDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("CREATE_POST".into(), e));
+        }
+
+        Posts::check_attachments(&input)?;
+
+        let created_at = Utc::now();
+        let poll = match &input.poll {
+            Some(poll) => Some(Posts::new_poll(input.post_id, created_at, poll, &self.poll_policy)?),
+            None => None,
+        };
+
+        let post = PostDTO {
+            post_id: input.post_id,
+            author_id: input.author_id,
+            hashtags: unique_hashtags(&input.content),
+            mentions: self.resolve_mentions(&input.content).await?,
+            media: self.resolve_media(&input.author_id, &input.media_ids).await?,
+            poll,
+            link_preview: None,
+            filtered: vec![],
+            sensitive: input.sensitive,
+            age_gated: false,
+            content: input.content,
+            created_at,
+        };
+
+        self.posts_repository.create(post.clone()).await?;
+
+        if let Some(poll) = &post.poll {
+            self.polls_repository.create(poll.clone()).await?;
+        }
+
+        if !post.hashtags.is_empty() {
+            self.trends_repository.record(&post.hashtags, post.created_at).await?;
+        }
+
+        if !post.mentions.is_empty() {
+            self.job_queue.enqueue(
+                Jobs::schedule(JobPayload::NotifyMentions { post_id: post.post_id }, created_at)
+            ).await?;
+        }
+
+        if !extract_links(&post.content).is_empty() {
+            self.job_queue.enqueue(
+                Jobs::schedule(
+                    JobPayload::UnfurlPostLink {
+                        post_id: post.post_id,
+                        content: post.content.clone(),
+                    },
+                    created_at
+                )
+            ).await?;
+        }
+
+        Ok(post)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            job::JobPayload,
+            media::UploadMediaDTO,
+            poll::CreatePollDTO,
+            post::CreatePostDTO,
+            user::CreateUserDTO,
+        },
+        error_codes::{
+            MEDIA_NOT_OWNED_ERROR_CODE,
+            MEDIA_NOT_READY_ERROR_CODE,
+            POLL_WITH_MEDIA_ERROR_CODE,
+            TOO_MANY_ATTACHMENTS_ERROR_CODE,
+        },
+        features::{
+            feature::Feature,
+            media::{ process_media::ProcessMedia, upload_media::UploadMedia },
+            posts::create_post::CreatePost,
+        },
+        policies::{ media::MediaPolicy, poll::PollPolicy },
+        repositories::{
+            media_repository::MediaRepository,
+            object_store::ObjectStore,
+            posts_repository::PostsRepository,
+            trends_repository::TrendsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            FAKE_JPEG,
+            InMemoryJobQueue,
+            InMemoryMediaRepository,
+            InMemoryObjectStore,
+            InMemoryPollsRepository,
+            InMemoryPostsRepository,
+            InMemoryTrendsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    impl Default for CreatePostDTO {
+        fn default() -> Self {
+            Self {
+                post_id: Uuid::new_v4(),
+                author_id: Uuid::new_v4(),
+                content: "Hello #Hearth, goodbye #birdsite #hearth".into(),
+                media_ids: vec![],
+                poll: None,
+                sensitive: false,
+            }
+        }
+    }
+
+    impl Default for CreatePost {
+        fn default() -> Self {
+            Self {
+                posts_repository: barc!(InMemoryPostsRepository::default()),
+                trends_repository: barc!(InMemoryTrendsRepository::default()),
+                users_repository: barc!(InMemoryUserRepository::default()),
+                media_repository: barc!(InMemoryMediaRepository::default()),
+                media_policy: MediaPolicy::default(),
+                polls_repository: barc!(InMemoryPollsRepository::default()),
+                poll_policy: PollPolicy::default(),
+                job_queue: barc!(InMemoryJobQueue::default()),
+            }
+        }
+    }
+
+    async fn create_user(users_repository: &BArc<dyn UsersRepository>, username: &str) -> Uuid {
+        let user_id = Uuid::new_v4();
+        users_repository
+            .create(
+                CreateUserDTO {
+                    user_id,
+                    username: username.into(),
+                    email: format!("{}@gmail.com", username),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            ).await
+            .unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    async fn should_create_post_with_normalized_hashtags() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(
+            InMemoryPostsRepository::default()
+        );
+        let create_post = CreatePost {
+            posts_repository: posts_repository.clone(),
+            ..Default::default()
+        };
+        let input = CreatePostDTO::default();
+
+        let post = create_post.execute(input.clone()).await.unwrap();
+
+        assert_eq!(post.hashtags, vec!["hearth", "birdsite"]);
+        assert_eq!(posts_repository.get(&input.post_id).await.unwrap(), post);
+    }
+
+    #[tokio::test]
+    async fn should_record_hashtags_as_trending() {
+        let trends_repository: BArc<dyn TrendsRepository> = barc!(
+            InMemoryTrendsRepository::default()
+        );
+        let create_post = CreatePost {
+            trends_repository: trends_repository.clone(),
+            ..Default::default()
+        };
+
+        create_post.execute(CreatePostDTO::default()).await.unwrap();
+        create_post
+            .execute(CreatePostDTO {
+                post_id: Uuid::new_v4(),
+                content: "#hearth again".into(),
+                ..CreatePostDTO::default()
+            }).await
+            .unwrap();
+
+        let trends = trends_repository.top(10, Utc::now()).await.unwrap();
+
+        assert_eq!(trends[0].tag, "hearth");
+        assert_eq!(trends[0].score, 2.0);
+        assert_eq!(trends[1].tag, "birdsite");
+    }
+
+    #[tokio::test]
+    async fn should_fail_on_empty_content() {
+        let create_post = CreatePost::default();
+
+        let result = create_post.execute(CreatePostDTO {
+            content: "".into(),
+            ..CreatePostDTO::default()
+        }).await;
+
+        assert!(matches!(result, Err(HearthError::Validation(_, _))));
+    }
+
+    #[tokio::test]
+    async fn should_resolve_mentions_case_insensitively_and_keep_unknown_as_text() {
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::default()
+        );
+        let jane_id = create_user(&users_repository, "Jane.Doe").await;
+        let create_post = CreatePost {
+            users_repository,
+            ..Default::default()
+        };
+
+        let content = "hi @jane.doe and @nobody_here";
+        let post = create_post
+            .execute(CreatePostDTO { content: content.into(), ..Default::default() }).await
+            .unwrap();
+
+        assert_eq!(post.mentions.len(), 1);
+        assert_eq!(post.mentions[0].user_id, jane_id);
+        assert_eq!(post.mentions[0].username, "Jane.Doe");
+        assert_eq!(&content[post.mentions[0].start..post.mentions[0].end], "@jane.doe");
+    }
+
+    #[tokio::test]
+    async fn should_enqueue_mention_notifications_and_link_unfurling() {
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::default()
+        );
+        create_user(&users_repository, "jane").await;
+        let job_queue = InMemoryJobQueue::default();
+        let create_post = CreatePost {
+            users_repository,
+            job_queue: barc!(job_queue.clone()),
+            ..Default::default()
+        };
+
+        create_post.execute(CreatePostDTO::default()).await.unwrap();
+        assert!(job_queue.jobs.lock().unwrap().is_empty());
+
+        let content = "@jane look https://example.com";
+        let post = create_post
+            .execute(CreatePostDTO { content: content.into(), ..Default::default() }).await
+            .unwrap();
+
+        let payloads: Vec<JobPayload> = job_queue.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|job| job.payload.clone())
+            .collect();
+        assert_eq!(payloads, vec![
+            JobPayload::NotifyMentions { post_id: post.post_id },
+            JobPayload::UnfurlPostLink { post_id: post.post_id, content: content.into() }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn should_attach_own_media_in_order() {
+        let media_repository: BArc<dyn MediaRepository> = barc!(
+            InMemoryMediaRepository::default()
+        );
+        let object_store: BArc<dyn ObjectStore> = barc!(InMemoryObjectStore::default());
+        let author_id = Uuid::new_v4();
+        let upload_media = UploadMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            ..Default::default()
+        };
+        let process_media = ProcessMedia {
+            media_repository: media_repository.clone(),
+            object_store,
+            ..Default::default()
+        };
+        let mut media_ids = vec![];
+        for owner_id in [author_id, author_id, Uuid::new_v4()] {
+            let media = upload_media
+                .execute(UploadMediaDTO {
+                    media_id: Uuid::new_v4(),
+                    owner_id,
+                    bytes: FAKE_JPEG.to_vec(),
+                }).await
+                .unwrap();
+            media_ids.push(media.media_id);
+        }
+        let create_post = CreatePost {
+            media_repository,
+            media_policy: MediaPolicy { max_attachments_per_post: 2, ..Default::default() },
+            ..Default::default()
+        };
+
+        let result = create_post.execute(CreatePostDTO {
+            author_id,
+            media_ids: vec![media_ids[0]],
+            ..Default::default()
+        }).await;
+        assert_eq!(result.unwrap_err(), HearthError::Domain(MEDIA_NOT_READY_ERROR_CODE.into()));
+
+        for media_id in &media_ids {
+            process_media.execute(*media_id).await.unwrap();
+        }
+
+        let post = create_post
+            .execute(CreatePostDTO {
+                author_id,
+                media_ids: vec![media_ids[1], media_ids[0]],
+                ..Default::default()
+            }).await
+            .unwrap();
+        assert_eq!(
+            post.media
+                .iter()
+                .map(|m| m.media_id)
+                .collect::<Vec<_>>(),
+            vec![media_ids[1], media_ids[0]]
+        );
+
+        let result = create_post.execute(CreatePostDTO {
+            author_id,
+            media_ids: vec![media_ids[2]],
+            ..Default::default()
+        }).await;
+        assert_eq!(result.unwrap_err(), HearthError::Forbidden(MEDIA_NOT_OWNED_ERROR_CODE.into()));
+
+        let result = create_post.execute(CreatePostDTO {
+            author_id,
+            media_ids: media_ids.clone(),
+            ..Default::default()
+        }).await;
+        assert_eq!(result.unwrap_err(), HearthError::Domain(TOO_MANY_ATTACHMENTS_ERROR_CODE.into()));
+    }
+
+    #[tokio::test]
+    async fn should_attach_a_poll_but_not_alongside_media() {
+        let poll = CreatePollDTO {
+            options: vec!["yes".into(), "no".into()],
+            multiple_choice: false,
+            duration_seconds: 24 * 60 * 60,
+        };
+        let create_post = CreatePost::default();
+
+        let post = create_post
+            .execute(CreatePostDTO { poll: Some(poll.clone()), ..Default::default() }).await
+            .unwrap();
+        let created = post.poll.unwrap();
+        assert_eq!(created.post_id, post.post_id);
+        assert_eq!(created.closes_at, post.created_at + chrono::Duration::days(1));
+
+        let result = create_post.execute(CreatePostDTO {
+            poll: Some(poll),
+            media_ids: vec![Uuid::new_v4()],
+            ..Default::default()
+        }).await;
+        assert_eq!(result.unwrap_err(), HearthError::Domain(POLL_WITH_MEDIA_ERROR_CODE.into()));
+    }
+}
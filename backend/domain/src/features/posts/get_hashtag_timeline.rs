@@ -0,0 +1,240 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::{
+        filter::FilterContext,
+        pagination::{ Page, TimelineCursor },
+        post::{ HashtagTimelineDTO, PostDTO },
+    },
+    entities::{ age::Age, keyword_filters::KeywordFilters, posts::Posts },
+    features::feature::Feature,
+    parsers::hashtags::normalize_hashtag,
+    policies::age::AgePolicy,
+    repositories::{
+        keyword_filters_repository::KeywordFiltersRepository,
+        posts_repository::PostsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type GetHashtagTimelineFeature = dyn Feature<HashtagTimelineDTO, Page<PostDTO>>;
+
+/// Hashtag timelines are the search context of keyword filters.
+pub struct GetHashtagTimeline {
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub keyword_filters_repository: BArc<dyn KeywordFiltersRepository>,
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub age_policy: AgePolicy,
+}
+
+#[async_trait]
+impl Feature<HashtagTimelineDTO, Page<PostDTO>> for GetHashtagTimeline {
+    async fn execute(&self, input: HashtagTimelineDTO) -> Result<Page<PostDTO>, HearthError> {
+        if let Err(e) = input.page.validate() {
+            return Err(HearthError::Validation("HASHTAG_TIMELINE".into(), e));
+        }
+
+        let cursor = input.page.timeline_cursor()?;
+        let tag = normalize_hashtag(&input.tag);
+
+        let now = Utc::now();
+        let filters = match input.viewer_id {
+            Some(viewer_id) =>
+                KeywordFilters::active(
+                    self.keyword_filters_repository.list(&viewer_id).await?,
+                    FilterContext::Search,
+                    now
+                ),
+            None => vec![],
+        };
+
+        let posts = self.posts_repository.list_by_hashtag(&tag, cursor, input.page.limit + 1).await?;
+
+        // The viewer is only looked up when the page has something to withhold.
+        let viewer_id = input.viewer_id.as_ref();
+        let can_view_sensitive = match viewer_id {
+            Some(viewer_id) if posts.iter().any(|post| Age::is_gated(post, Some(viewer_id))) => {
+                let viewer = self.users_repository.get(viewer_id.to_string()).await?;
+                Age::can_view_sensitive(Some(&viewer), now.date_naive(), &self.age_policy)
+            }
+            _ => false,
+        };
+
+        let posts = posts
+            .into_iter()
+            .map(|post| {
+                let post = PostDTO {
+                    // Public timelines are not personalized, tallies show once closed.
+                    poll: post.poll.map(|poll| Posts::poll_for_viewer(poll, vec![], false, now)),
+                    ..post
+                };
+                let post = match viewer_id {
+                    Some(viewer_id) => KeywordFilters::mark_post(post, &filters, viewer_id),
+                    None => post,
+                };
+                Age::gate_post(post, viewer_id, can_view_sensitive)
+            })
+            .collect();
+
+        Ok(
+            Page::from_overfetched(posts, input.page.limit, |post| {
+                (TimelineCursor { created_at: post.created_at, id: post.post_id }).encode()
+            })
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            pagination::PageRequest,
+            post::{ CreatePostDTO, HashtagTimelineDTO },
+            user::CreateUserDTO,
+        },
+        features::{
+            feature::Feature,
+            posts::{ create_post::CreatePost, get_hashtag_timeline::GetHashtagTimeline },
+        },
+        policies::age::AgePolicy,
+        repositories::{ posts_repository::PostsRepository, users_repository::UsersRepository },
+        test_utils::test_utils::{
+            InMemoryKeywordFiltersRepository,
+            InMemoryPostsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_paginate_hashtag_timeline_newest_first() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(
+            InMemoryPostsRepository::default()
+        );
+        let create_post = CreatePost {
+            posts_repository: posts_repository.clone(),
+            ..Default::default()
+        };
+
+        let mut ids = vec![];
+        for content in ["#Rust one", "two #rust", "#other", "#RUST three"] {
+            let post_id = Uuid::new_v4();
+            create_post
+                .execute(CreatePostDTO { post_id, content: content.into(), ..Default::default() }).await
+                .unwrap();
+            ids.push(post_id);
+        }
+
+        let timeline = GetHashtagTimeline {
+            posts_repository,
+            keyword_filters_repository: barc!(InMemoryKeywordFiltersRepository::default()),
+            users_repository: barc!(InMemoryUserRepository::default()),
+            age_policy: AgePolicy::default(),
+        };
+
+        let first = timeline
+            .execute(HashtagTimelineDTO {
+                tag: "Rust".into(),
+                viewer_id: None,
+                page: PageRequest { cursor: None, limit: 2 },
+            }).await
+            .unwrap();
+
+        assert_eq!(
+            first.items
+                .iter()
+                .map(|p| p.post_id)
+                .collect::<Vec<_>>(),
+            vec![ids[3], ids[1]]
+        );
+        assert!(first.next_cursor.is_some());
+
+        let second = timeline
+            .execute(HashtagTimelineDTO {
+                tag: "#rust".into(),
+                viewer_id: None,
+                page: PageRequest { cursor: first.next_cursor, limit: 2 },
+            }).await
+            .unwrap();
+
+        assert_eq!(
+            second.items
+                .iter()
+                .map(|p| p.post_id)
+                .collect::<Vec<_>>(),
+            vec![ids[0]]
+        );
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_withhold_sensitive_posts_from_anonymous_and_underage_viewers() {
+        let posts_repository: BArc<dyn PostsRepository> = barc!(
+            InMemoryPostsRepository::default()
+        );
+        let users_repository: BArc<dyn UsersRepository> = barc!(InMemoryUserRepository::default());
+
+        let fifteen_years_ago = Utc::now().date_naive() - Duration::days(15 * 365);
+        let mut viewers = vec![];
+        for (username, birthday) in [
+            ("adult", NaiveDate::from_ymd_opt(1991, 12, 29).unwrap()),
+            ("teen", fifteen_years_ago),
+        ] {
+            let user_id = Uuid::new_v4();
+            users_repository
+                .create(
+                    CreateUserDTO {
+                        user_id,
+                        username: username.into(),
+                        email: format!("{}@gmail.com", username),
+                        birthday,
+                    },
+                    CredentialsDTO { user_id, password_hash: "hash".into() }
+                ).await
+                .unwrap();
+            viewers.push(Some(user_id));
+        }
+        let [adult, teen] = [viewers[0], viewers[1]];
+
+        let post = (CreatePost { posts_repository: posts_repository.clone(), ..Default::default() })
+            .execute(CreatePostDTO {
+                content: "#rust unsafe code ahead".into(),
+                sensitive: true,
+                ..Default::default()
+            }).await
+            .unwrap();
+
+        let timeline = GetHashtagTimeline {
+            posts_repository,
+            keyword_filters_repository: barc!(InMemoryKeywordFiltersRepository::default()),
+            users_repository,
+            age_policy: AgePolicy::default(),
+        };
+        let view = |viewer_id: Option<Uuid>| {
+            timeline.execute(HashtagTimelineDTO {
+                tag: "rust".into(),
+                viewer_id,
+                page: PageRequest { cursor: None, limit: 10 },
+            })
+        };
+
+        for viewer_id in [None, teen] {
+            let gated = view(viewer_id).await.unwrap().items.remove(0);
+            assert!(gated.age_gated);
+            assert!(gated.content.is_empty());
+        }
+        for viewer_id in [adult, Some(post.author_id)] {
+            let shown = view(viewer_id).await.unwrap().items.remove(0);
+            assert!(!shown.age_gated);
+            assert_eq!(shown.content, post.content);
+        }
+    }
+}
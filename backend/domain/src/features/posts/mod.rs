@@ -0,0 +1,4 @@
+pub mod create_post;
+pub mod get_hashtag_timeline;
+pub mod notify_mentions;
+pub mod unfurl_post_link;
@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::notification::{ NotificationDTO, NotificationKind },
+    error_codes::POST_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{
+        blocks_repository::BlocksRepository,
+        notifications_repository::NotificationsRepository,
+        posts_repository::PostsRepository,
+    },
+};
+
+/// Notifies the users mentioned in a post, once each, unless either side
+/// blocked the other. Nothing to do if the post is gone by the time it runs.
+pub type NotifyMentionsFeature = dyn Feature<Uuid, ()>;
+
+pub struct NotifyMentions {
+    pub posts_repository: BArc<dyn PostsRepository>,
+    pub blocks_repository: BArc<dyn BlocksRepository>,
+    pub notifications_repository: BArc<dyn NotificationsRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, ()> for NotifyMentions {
+    async fn execute(&self, post_id: Uuid) -> Result<(), HearthError> {
+        let post = match self.posts_repository.get(&post_id).await {
+            Ok(post) => post,
+            Err(e) if e == HearthError::not_found(POST_NOT_FOUND_ERROR_CODE.into()) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let mut notified: Vec<Uuid> = vec![];
+
+        for mention in &post.mentions {
+            if mention.user_id == post.author_id || notified.contains(&mention.user_id) {
+                continue;
+            }
+            notified.push(mention.user_id);
+
+            if
+                self.blocks_repository.is_blocked_between(&post.author_id, &mention.user_id).await?
+            {
+                continue;
+            }
+
+            self.notifications_repository.create(NotificationDTO {
+                notification_id: Uuid::new_v4(),
+                recipient_id: mention.user_id,
+                actor_id: post.author_id,
+                kind: NotificationKind::Mention,
+                post_id: Some(post.post_id),
+                list_id: None,
+                filtered: vec![],
+                created_at: post.created_at,
+            }).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            notification::NotificationKind,
+            pagination::PageRequest,
+            post::CreatePostDTO,
+            user::CreateUserDTO,
+        },
+        features::{
+            feature::Feature,
+            posts::{ create_post::CreatePost, notify_mentions::NotifyMentions },
+        },
+        repositories::{
+            blocks_repository::BlocksRepository,
+            notifications_repository::NotificationsRepository,
+            posts_repository::PostsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryBlocksRepository,
+            InMemoryNotificationsRepository,
+            InMemoryPostsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    async fn create_user(users_repository: &BArc<dyn UsersRepository>, username: &str) -> Uuid {
+        let user_id = Uuid::new_v4();
+        users_repository
+            .create(
+                CreateUserDTO {
+                    user_id,
+                    username: username.into(),
+                    email: format!("{}@gmail.com", username),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            ).await
+            .unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    async fn should_notify_mentioned_users_unless_blocked() {
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::default()
+        );
+        let posts_repository: BArc<dyn PostsRepository> = barc!(
+            InMemoryPostsRepository::default()
+        );
+        let blocks_repository: BArc<dyn BlocksRepository> = barc!(
+            InMemoryBlocksRepository::default()
+        );
+        let notifications_repository: BArc<dyn NotificationsRepository> = barc!(
+            InMemoryNotificationsRepository::default()
+        );
+
+        let author_id = create_user(&users_repository, "author").await;
+        let jane_id = create_user(&users_repository, "jane").await;
+        let blocker_id = create_user(&users_repository, "blocker").await;
+        blocks_repository.block(&blocker_id, &author_id).await.unwrap();
+
+        let create_post = CreatePost {
+            users_repository,
+            posts_repository: posts_repository.clone(),
+            ..Default::default()
+        };
+        let notify_mentions = NotifyMentions {
+            posts_repository,
+            blocks_repository,
+            notifications_repository: notifications_repository.clone(),
+        };
+
+        let post = create_post
+            .execute(CreatePostDTO {
+                author_id,
+                content: "@jane @JANE @blocker @author".into(),
+                ..Default::default()
+            }).await
+            .unwrap();
+        notify_mentions.execute(post.post_id).await.unwrap();
+
+        let page = PageRequest::default();
+        let jane = notifications_repository.list(&jane_id, None, page.limit).await.unwrap();
+        assert_eq!(jane.len(), 1);
+        assert_eq!(jane[0].kind, NotificationKind::Mention);
+        assert_eq!(jane[0].actor_id, author_id);
+
+        assert!(notifications_repository.list(&blocker_id, None, page.limit).await.unwrap().is_empty());
+        assert!(notifications_repository.list(&author_id, None, page.limit).await.unwrap().is_empty());
+
+        assert!(notify_mentions.execute(Uuid::new_v4()).await.is_ok());
+    }
+}
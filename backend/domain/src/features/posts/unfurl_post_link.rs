@@ -0,0 +1,151 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::link_preview::{ LinkPreviewDTO, UnfurlPostLinkDTO },
+    features::feature::Feature,
+    parsers::links::{ extract_links, normalize_url },
+    policies::link_preview::LinkPreviewPolicy,
+    repositories::{
+        link_previews_repository::LinkPreviewsRepository,
+        link_unfurler::LinkUnfurler,
+    },
+};
+
+pub type UnfurlPostLinkFeature = dyn Feature<UnfurlPostLinkDTO, Option<LinkPreviewDTO>>;
+
+/// Attaches a preview of the first link of a post. Runs after the post is
+/// created so that slow or unreachable pages never delay publishing.
+pub struct UnfurlPostLink {
+    pub link_previews_repository: BArc<dyn LinkPreviewsRepository>,
+    pub link_unfurler: BArc<dyn LinkUnfurler>,
+    pub policy: LinkPreviewPolicy,
+}
+
+#[async_trait]
+impl Feature<UnfurlPostLinkDTO, Option<LinkPreviewDTO>> for UnfurlPostLink {
+    async fn execute(&self, input: UnfurlPostLinkDTO) -> Result<Option<LinkPreviewDTO>, HearthError> {
+        let Some(url) = extract_links(&input.content)
+            .iter()
+            .find_map(|link| normalize_url(link)) else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        let cached = self.link_previews_repository
+            .get(&url).await?
+            .filter(|preview| preview.fetched_at + self.policy.cache_ttl > now);
+
+        let preview = match cached {
+            Some(preview) => preview,
+            None => {
+                let preview = LinkPreviewDTO {
+                    url: url.clone(),
+                    fetched_at: now,
+                    ..self.link_unfurler.unfurl(&url).await?
+                };
+                self.link_previews_repository.save(preview.clone()).await?;
+                preview
+            }
+        };
+
+        if !preview.is_displayable() {
+            return Ok(None);
+        }
+
+        self.link_previews_repository.attach(&input.post_id, &preview.url).await?;
+
+        Ok(Some(preview))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::link_preview::{ LinkPreviewDTO, UnfurlPostLinkDTO },
+        features::{ feature::Feature, posts::unfurl_post_link::UnfurlPostLink },
+        policies::link_preview::LinkPreviewPolicy,
+        repositories::link_previews_repository::LinkPreviewsRepository,
+        test_utils::test_utils::{ FakeLinkUnfurler, InMemoryLinkPreviewsRepository },
+    };
+
+    fn dto(content: &str) -> UnfurlPostLinkDTO {
+        UnfurlPostLinkDTO { post_id: Uuid::new_v4(), content: content.into() }
+    }
+
+    #[tokio::test]
+    async fn should_unfurl_the_first_link_and_cache_it() {
+        let repository = InMemoryLinkPreviewsRepository::default();
+        let unfurler = FakeLinkUnfurler::default();
+        let unfurl = UnfurlPostLink {
+            link_previews_repository: barc!(repository.clone()),
+            link_unfurler: barc!(unfurler.clone()),
+            policy: LinkPreviewPolicy::default(),
+        };
+
+        let first = dto("look https://Example.com/page?utm_source=x and https://other.org");
+        let preview = unfurl.execute(first.clone()).await.unwrap().unwrap();
+
+        assert_eq!(preview.url, "https://example.com/page");
+        assert_eq!(preview.title.as_deref(), Some("Title of https://example.com/page"));
+        assert_eq!(repository.attached(&first.post_id).as_deref(), Some("https://example.com/page"));
+
+        let second = dto("again https://example.com/page#comments");
+        unfurl.execute(second.clone()).await.unwrap().unwrap();
+
+        assert_eq!(unfurler.calls(), 1);
+        assert_eq!(repository.attached(&second.post_id).as_deref(), Some("https://example.com/page"));
+    }
+
+    #[tokio::test]
+    async fn should_refetch_stale_previews() {
+        let repository = InMemoryLinkPreviewsRepository::default();
+        let unfurler = FakeLinkUnfurler::default();
+        let link_previews_repository: BArc<dyn LinkPreviewsRepository> = barc!(repository.clone());
+        let unfurl = UnfurlPostLink {
+            link_previews_repository: link_previews_repository.clone(),
+            link_unfurler: barc!(unfurler.clone()),
+            policy: LinkPreviewPolicy::default(),
+        };
+
+        link_previews_repository.save(LinkPreviewDTO {
+            url: "https://example.com/".into(),
+            title: Some("Old".into()),
+            description: None,
+            image_url: None,
+            site_name: None,
+            fetched_at: Utc::now() - Duration::days(2),
+        }).await.unwrap();
+
+        let preview = unfurl.execute(dto("https://example.com")).await.unwrap().unwrap();
+
+        assert_eq!(unfurler.calls(), 1);
+        assert_eq!(preview.title.as_deref(), Some("Title of https://example.com/"));
+    }
+
+    #[tokio::test]
+    async fn should_not_attach_posts_without_links_or_title() {
+        let repository = InMemoryLinkPreviewsRepository::default();
+        let unfurler = FakeLinkUnfurler::default();
+        let unfurl = UnfurlPostLink {
+            link_previews_repository: barc!(repository.clone()),
+            link_unfurler: barc!(unfurler.clone()),
+            policy: LinkPreviewPolicy::default(),
+        };
+
+        let plain = dto("no link here, `https://in.code`");
+        assert_eq!(unfurl.execute(plain.clone()).await.unwrap(), None);
+        assert_eq!(unfurler.calls(), 0);
+
+        let untitled = dto("https://example.com/untitled");
+        assert_eq!(unfurl.execute(untitled.clone()).await.unwrap(), None);
+        assert_eq!(unfurler.calls(), 1);
+        assert_eq!(repository.attached(&untitled.post_id), None);
+    }
+}
@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::rate_limit::{ RateLimitDTO, RateLimitRequestDTO },
+    features::feature::Feature,
+    policies::rate_limit::{ RateLimitKey, RateLimitPolicy },
+    repositories::rate_limiter::RateLimiter,
+};
+
+pub type CheckRateLimitFeature = dyn Feature<RateLimitRequestDTO, RateLimitDTO>;
+
+/// Takes a token from the caller's bucket. A rejected request still answers
+/// with the bucket state so the caller can tell when to retry.
+pub struct CheckRateLimit {
+    pub rate_limiter: BArc<dyn RateLimiter>,
+    pub policy: RateLimitPolicy,
+}
+
+impl CheckRateLimit {
+    fn key(&self, input: &RateLimitRequestDTO) -> String {
+        let caller = match (self.policy.key(input.scope), input.user_id) {
+            // Personal access tokens draw from their owner's bucket, a user
+            // can't multiply their limit by minting tokens.
+            (RateLimitKey::User, Some(user_id)) => format!("user:{}", user_id),
+            _ => format!("ip:{}", input.ip.as_deref().unwrap_or("unknown")),
+        };
+
+        format!("rate_limit:{}:{}", input.scope.as_str(), caller)
+    }
+
+    /// Each token also counts its requests in a bucket of its own, so bot
+    /// traffic can be told apart from its owner's. It only ever sees part of
+    /// what the owner's bucket sees, the latter is the one that throttles.
+    fn token_key(&self, input: &RateLimitRequestDTO) -> Option<String> {
+        input.personal_access_token_id.map(|token_id| {
+            format!("rate_limit:{}:token:{}", input.scope.as_str(), token_id)
+        })
+    }
+}
+
+#[async_trait]
+impl Feature<RateLimitRequestDTO, RateLimitDTO> for CheckRateLimit {
+    async fn execute(&self, input: RateLimitRequestDTO) -> Result<RateLimitDTO, HearthError> {
+        let bucket = self.policy.bucket(input.scope);
+        let limit = self.rate_limiter.take(&self.key(&input), bucket).await?;
+
+        if let Some(token_key) = self.token_key(&input) && limit.is_allowed() {
+            self.rate_limiter.take(&token_key, bucket).await?;
+        }
+
+        Ok(limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::rate_limit::{ RateLimitRequestDTO, RateLimitScope },
+        features::{ feature::Feature, rate_limit::check_rate_limit::CheckRateLimit },
+        policies::rate_limit::{ RateLimitPolicy, TokenBucket },
+        repositories::rate_limiter::RateLimiter,
+        test_utils::test_utils::InMemoryRateLimiter,
+    };
+
+    fn check_rate_limit() -> CheckRateLimit {
+        let bucket = TokenBucket { capacity: 2, refill_seconds: 60 };
+
+        CheckRateLimit {
+            rate_limiter: barc!(InMemoryRateLimiter::default()),
+            policy: RateLimitPolicy {
+                signup: bucket,
+                login: bucket,
+                verification_code: bucket,
+                posting: bucket,
+            },
+        }
+    }
+
+    fn request(scope: RateLimitScope, ip: &str, user_id: Option<Uuid>) -> RateLimitRequestDTO {
+        RateLimitRequestDTO { scope, ip: Some(ip.into()), user_id, personal_access_token_id: None }
+    }
+
+    #[tokio::test]
+    async fn should_reject_once_the_bucket_of_an_ip_is_empty() {
+        let feature = check_rate_limit();
+        let signup = || request(RateLimitScope::Signup, "203.0.113.9", None);
+
+        assert_eq!(feature.execute(signup()).await.unwrap().remaining, 1);
+        assert_eq!(feature.execute(signup()).await.unwrap().remaining, 0);
+
+        let rejected = feature.execute(signup()).await.unwrap();
+        assert!(!rejected.is_allowed());
+        assert_eq!(rejected.retry_after_seconds, Some(60));
+
+        // Other callers and other scopes have their own buckets.
+        let other_ip = request(RateLimitScope::Signup, "198.51.100.4", None);
+        assert!(feature.execute(other_ip).await.unwrap().is_allowed());
+        let login = request(RateLimitScope::Login, "203.0.113.9", None);
+        assert!(feature.execute(login).await.unwrap().is_allowed());
+    }
+
+    #[tokio::test]
+    async fn should_key_posting_by_user_across_ips() {
+        let feature = check_rate_limit();
+        let user_id = Some(Uuid::new_v4());
+
+        for ip in ["203.0.113.9", "198.51.100.4"] {
+            let post = request(RateLimitScope::Posting, ip, user_id);
+            assert!(feature.execute(post).await.unwrap().is_allowed());
+        }
+
+        let post = request(RateLimitScope::Posting, "192.0.2.1", user_id);
+        assert!(!feature.execute(post).await.unwrap().is_allowed());
+    }
+
+    #[tokio::test]
+    async fn should_draw_personal_access_tokens_from_their_owner_bucket() {
+        let feature = check_rate_limit();
+        let user_id = Some(Uuid::new_v4());
+
+        for _ in 0..2 {
+            let post = request(RateLimitScope::Posting, "203.0.113.9", user_id);
+            assert!(feature.execute(post).await.unwrap().is_allowed());
+        }
+
+        // A script authenticated with one of the owner's tokens, from another host.
+        let bot = RateLimitRequestDTO {
+            personal_access_token_id: Some(Uuid::new_v4()),
+            ..request(RateLimitScope::Posting, "198.51.100.4", user_id)
+        };
+        assert!(!feature.execute(bot).await.unwrap().is_allowed());
+    }
+
+    #[tokio::test]
+    async fn should_also_count_personal_access_tokens_in_their_own_bucket() {
+        let rate_limiter = InMemoryRateLimiter::default();
+        let feature = CheckRateLimit {
+            rate_limiter: barc!(rate_limiter.clone()),
+            ..check_rate_limit()
+        };
+        let user_id = Some(Uuid::new_v4());
+        let bot = RateLimitRequestDTO {
+            personal_access_token_id: Some(Uuid::new_v4()),
+            ..request(RateLimitScope::Posting, "198.51.100.4", user_id)
+        };
+
+        assert!(feature.execute(bot.clone()).await.unwrap().is_allowed());
+        let owner = request(RateLimitScope::Posting, "203.0.113.9", user_id);
+        assert!(feature.execute(owner).await.unwrap().is_allowed());
+
+        // The token's bucket only saw its own request.
+        let token_key = feature.token_key(&bot).unwrap();
+        let bucket = feature.policy.bucket(RateLimitScope::Posting);
+        assert_eq!(rate_limiter.take(&token_key, bucket).await.unwrap().remaining, 0);
+        assert!(!feature.execute(bot).await.unwrap().is_allowed());
+    }
+}
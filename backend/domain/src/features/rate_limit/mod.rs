@@ -0,0 +1 @@
+pub mod check_rate_limit;
@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use chrono::{ Duration, Utc };
+use errors::HearthError;
+use macros::BArc;
+use rand::RngCore;
+use uuid::Uuid;
+
+use crate::{
+    dtos::signup::SignupChallengeDTO,
+    error_codes::PROOF_OF_WORK_DISABLED_ERROR_CODE,
+    features::feature::Feature,
+    policies::signup::ProofOfWorkPolicy,
+    repositories::signup_challenges_repository::SignupChallengesRepository,
+};
+
+pub type CreateSignupChallengeFeature = dyn Feature<(), SignupChallengeDTO>;
+
+pub struct CreateSignupChallenge {
+    pub signup_challenges_repository: BArc<dyn SignupChallengesRepository>,
+    /// `None` when the deployment doesn't ask for a proof of work.
+    pub policy: Option<ProofOfWorkPolicy>,
+}
+
+#[async_trait]
+impl Feature<(), SignupChallengeDTO> for CreateSignupChallenge {
+    async fn execute(&self, _input: ()) -> Result<SignupChallengeDTO, HearthError> {
+        let policy = self.policy
+            .as_ref()
+            .ok_or_else(|| HearthError::not_found(PROOF_OF_WORK_DISABLED_ERROR_CODE.into()))?;
+
+        let mut bytes = [0u8; 16];
+        rand::rng().fill_bytes(&mut bytes);
+
+        let challenge = SignupChallengeDTO {
+            challenge_id: Uuid::new_v4(),
+            prefix: bytes
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+            difficulty: policy.difficulty,
+            expires_at: Utc::now() + Duration::seconds(policy.ttl_seconds),
+        };
+
+        self.signup_challenges_repository.create(&challenge).await?;
+
+        Ok(challenge)
+    }
+}
@@ -1 +1,2 @@
+pub mod create_signup_challenge;
 pub mod signup_with_email;
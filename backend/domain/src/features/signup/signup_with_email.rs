@@ -1,27 +1,80 @@
 use async_trait::async_trait;
+use chrono::Utc;
 use errors::HearthError;
 use macros::BArc;
 use validator::Validate;
 
 use crate::{
-    dtos::{ auth::CredentialsDTO, signup::SignupEmailDTO, user::CreateUserDTO },
+    dtos::{
+        auth::CredentialsDTO,
+        event::{ DomainEvent, EventDTO },
+        signup::{ ProofOfWorkDTO, SignupEmailDTO },
+        user::CreateUserDTO,
+    },
+    entities::{ age::Age, email::Email, proof_of_work::ProofOfWork },
+    error_codes::{ INVALID_PROOF_OF_WORK_ERROR_CODE, PROOF_OF_WORK_REQUIRED_ERROR_CODE },
     features::feature::Feature,
-    repositories::users_repository::UsersRepository,
+    policies::{ age::AgePolicy, signup::{ EmailPolicy, ProofOfWorkPolicy } },
+    repositories::{
+        signup_challenges_repository::SignupChallengesRepository,
+        users_repository::UsersRepository,
+    },
 };
 
 pub type SignupWithEmailFeature = dyn Feature<SignupEmailDTO, ()>;
 
 pub struct SignupWithEmail {
     pub users_repository: BArc<dyn UsersRepository>,
+    pub signup_challenges_repository: BArc<dyn SignupChallengesRepository>,
+    pub email_policy: EmailPolicy,
+    pub age_policy: AgePolicy,
+    /// `None` when the deployment doesn't ask for a proof of work.
+    pub proof_of_work_policy: Option<ProofOfWorkPolicy>,
+}
+
+impl SignupWithEmail {
+    async fn check_proof_of_work(&self, proof: Option<&ProofOfWorkDTO>) -> Result<(), HearthError> {
+        if self.proof_of_work_policy.is_none() {
+            return Ok(());
+        }
+
+        let proof = proof.ok_or_else(||
+            HearthError::Domain(PROOF_OF_WORK_REQUIRED_ERROR_CODE.into())
+        )?;
+        let invalid = || HearthError::Domain(INVALID_PROOF_OF_WORK_ERROR_CODE.into());
+
+        let challenge = self.signup_challenges_repository
+            .take(&proof.challenge_id).await?
+            .ok_or_else(invalid)?;
+
+        if
+            challenge.expires_at <= Utc::now() ||
+            !ProofOfWork::verify(&challenge.prefix, &proof.solution, challenge.difficulty)
+        {
+            return Err(invalid());
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl Feature<SignupEmailDTO, ()> for SignupWithEmail {
     async fn execute(&self, input: SignupEmailDTO) -> Result<(), errors::HearthError> {
+        let input = SignupEmailDTO {
+            email: Email::normalize(&input.email, self.email_policy.plus_addressing),
+            ..input
+        };
+
         if let Err(e) = input.validate() {
             return Err(HearthError::Validation("SIGNUP_EMAIL".into(), e));
         }
 
+        Age::check_signup(input.birthday, Utc::now().date_naive(), &self.age_policy)?;
+        Email::check_domain(&input.email, &self.email_policy)?;
+
+        self.check_proof_of_work(input.proof_of_work.as_ref()).await?;
+
         {
             let exists = self.users_repository.email_exists(&input.email).await?;
 
@@ -50,7 +103,16 @@ impl Feature<SignupEmailDTO, ()> for SignupWithEmail {
             password_hash: hasher::hash!(input.password),
         };
 
-        self.users_repository.create(create_user_dto, credentials_dto).await?;
+        let registered = EventDTO::new(
+            DomainEvent::UserRegistered { user_id: input.user_id, username: input.username },
+            Utc::now()
+        );
+
+        self.users_repository.create_with_events(
+            create_user_dto,
+            credentials_dto,
+            vec![registered]
+        ).await?;
         Ok(())
     }
 }
@@ -67,9 +129,29 @@ mod tests {
     use uuid::Uuid;
 
     use crate::{
-        dtos::{ signup::SignupEmailDTO, user::CreateUserDTO },
-        repositories::users_repository::UsersRepository,
-        test_utils::test_utils::InMemoryUserRepository,
+        dtos::{
+            event::DomainEvent,
+            signup::{ ProofOfWorkDTO, SignupEmailDTO },
+            user::CreateUserDTO,
+        },
+        entities::proof_of_work::ProofOfWork,
+        error_codes::{
+            BELOW_MINIMUM_AGE_ERROR_CODE,
+            EMAIL_DOMAIN_NOT_ALLOWED_ERROR_CODE,
+            INVALID_PROOF_OF_WORK_ERROR_CODE,
+            PROOF_OF_WORK_REQUIRED_ERROR_CODE,
+        },
+        features::signup::create_signup_challenge::CreateSignupChallenge,
+        policies::{ age::AgePolicy, signup::{ EmailPolicy, ProofOfWorkPolicy } },
+        repositories::{
+            signup_challenges_repository::SignupChallengesRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryOutboxRepository,
+            InMemorySignupChallengesRepository,
+            InMemoryUserRepository,
+        },
     };
 
     const EMAIL: &str = "john.smith@gmail.com";
@@ -83,7 +165,8 @@ mod tests {
                 email: EMAIL.into(),
                 username: USERNAME.into(),
                 password: "qwerty123".into(),
-                birthday: NaiveDate::parse_from_str("2015-09-05", "%Y-%m-%d").unwrap(),
+                birthday: NaiveDate::parse_from_str("1991-12-29", "%Y-%m-%d").unwrap(),
+                proof_of_work: None,
             }
         }
     }
@@ -93,17 +176,29 @@ mod tests {
             let users_repository: BArc<dyn UsersRepository> = barc!(
                 InMemoryUserRepository::default()
             );
-            Self { users_repository }
+            SignupWithEmail::with_users_repository(users_repository)
         }
     }
 
     impl SignupWithEmail {
+        fn with_users_repository(users_repository: BArc<dyn UsersRepository>) -> Self {
+            Self {
+                users_repository,
+                signup_challenges_repository: barc!(
+                    InMemorySignupChallengesRepository::default()
+                ),
+                email_policy: EmailPolicy::default(),
+                age_policy: AgePolicy::default(),
+                proof_of_work_policy: None,
+            }
+        }
+
         fn from_existing_user(dto: CreateUserDTO, credentials_dto: CredentialsDTO) -> Self {
             let users_repository: BArc<dyn UsersRepository> = barc!(
                 InMemoryUserRepository::from_existing_user(dto, credentials_dto)
             );
 
-            Self { users_repository }
+            SignupWithEmail::with_users_repository(users_repository)
         }
     }
 
@@ -118,6 +213,24 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn should_record_the_registration_in_the_outbox() {
+        let outbox = InMemoryOutboxRepository::default();
+        let signup_with_email = SignupWithEmail::with_users_repository(
+            barc!(InMemoryUserRepository::with_outbox(outbox.clone()))
+        );
+        let input = SignupEmailDTO::default();
+
+        signup_with_email.execute(input.clone()).await.unwrap();
+
+        let events = outbox.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, DomainEvent::UserRegistered {
+            user_id: input.user_id,
+            username: USERNAME.into(),
+        });
+    }
+
     #[tokio::test]
     async fn should_fail_if_a_user_with_given_email_already_exists() {
         let input = SignupEmailDTO::default();
@@ -171,4 +284,95 @@ mod tests {
         let err = result.unwrap_err();
         assert_eq!(err, HearthError::Domain("USERNAME_ALREADY_TAKEN".into()));
     }
+
+    #[tokio::test]
+    async fn should_normalize_the_email_before_checking_uniqueness() {
+        let input = SignupEmailDTO::default();
+        let user_id = Uuid::new_v4();
+        let dto = CreateUserDTO {
+            user_id,
+            username: "someone.else".into(),
+            birthday: input.birthday,
+            email: input.email.clone(),
+        };
+        let credentials_dto = CredentialsDTO {
+            user_id,
+            password_hash: hasher::hash!(PASSWORD),
+        };
+        let signup_with_email = SignupWithEmail::from_existing_user(dto, credentials_dto);
+
+        let input = SignupEmailDTO { email: " John.Smith+alt@GMAIL.com".into(), ..input };
+
+        assert_eq!(
+            signup_with_email.execute(input).await.unwrap_err(),
+            HearthError::Domain("EMAIL_ALREADY_TAKEN".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_below_the_minimum_age() {
+        let input = SignupEmailDTO {
+            birthday: NaiveDate::parse_from_str("2015-09-05", "%Y-%m-%d").unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            SignupWithEmail::default().execute(input).await.unwrap_err(),
+            HearthError::Domain(BELOW_MINIMUM_AGE_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_for_a_blocked_email_domain() {
+        let input = SignupEmailDTO { email: "bot@mailinator.com".into(), ..Default::default() };
+
+        assert_eq!(
+            SignupWithEmail::default().execute(input).await.unwrap_err(),
+            HearthError::Domain(EMAIL_DOMAIN_NOT_ALLOWED_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_require_a_solved_challenge_when_enabled() {
+        let signup_challenges_repository: BArc<dyn SignupChallengesRepository> = barc!(
+            InMemorySignupChallengesRepository::default()
+        );
+        let policy = ProofOfWorkPolicy { difficulty: 8, ttl_seconds: 60 };
+        let signup_with_email = SignupWithEmail {
+            signup_challenges_repository: signup_challenges_repository.clone(),
+            proof_of_work_policy: Some(policy.clone()),
+            ..SignupWithEmail::default()
+        };
+        let create_challenge = CreateSignupChallenge {
+            signup_challenges_repository,
+            policy: Some(policy),
+        };
+
+        assert_eq!(
+            signup_with_email.execute(SignupEmailDTO::default()).await.unwrap_err(),
+            HearthError::Domain(PROOF_OF_WORK_REQUIRED_ERROR_CODE.into())
+        );
+
+        let challenge = create_challenge.execute(()).await.unwrap();
+        let solution = (0u64..)
+            .map(|n| n.to_string())
+            .find(|s| ProofOfWork::verify(&challenge.prefix, s, challenge.difficulty))
+            .unwrap();
+        let proof = ProofOfWorkDTO { challenge_id: challenge.challenge_id, solution };
+        let input = SignupEmailDTO { proof_of_work: Some(proof.clone()), ..Default::default() };
+
+        assert!(signup_with_email.execute(input).await.is_ok());
+
+        // A challenge only buys one signup.
+        let input = SignupEmailDTO {
+            username: "jane.smith".into(),
+            email: "jane.smith@gmail.com".into(),
+            proof_of_work: Some(proof),
+            ..Default::default()
+        };
+        assert_eq!(
+            signup_with_email.execute(input).await.unwrap_err(),
+            HearthError::Domain(INVALID_PROOF_OF_WORK_ERROR_CODE.into())
+        );
+    }
 }
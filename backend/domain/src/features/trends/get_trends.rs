@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::trend::TrendDTO,
+    features::feature::Feature,
+    repositories::trends_repository::TrendsRepository,
+};
+
+pub const MAX_TRENDS: u64 = 50;
+
+pub type GetTrendsFeature = dyn Feature<u64, Vec<TrendDTO>>;
+
+pub struct GetTrends {
+    pub trends_repository: BArc<dyn TrendsRepository>,
+}
+
+#[async_trait]
+impl Feature<u64, Vec<TrendDTO>> for GetTrends {
+    async fn execute(&self, limit: u64) -> Result<Vec<TrendDTO>, HearthError> {
+        self.trends_repository.top(limit.clamp(1, MAX_TRENDS), Utc::now()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, Utc };
+    use macros::{ BArc, barc };
+
+    use crate::{
+        features::{ feature::Feature, trends::get_trends::GetTrends },
+        repositories::trends_repository::TrendsRepository,
+        test_utils::test_utils::InMemoryTrendsRepository,
+    };
+
+    #[tokio::test]
+    async fn should_rank_recent_bursts_above_older_chatter() {
+        let trends_repository: BArc<dyn TrendsRepository> = barc!(
+            InMemoryTrendsRepository::default()
+        );
+        let now = Utc::now();
+
+        for _ in 0..3 {
+            trends_repository.record(&["old".into()], now - Duration::hours(12)).await.unwrap();
+        }
+        for _ in 0..2 {
+            trends_repository.record(&["new".into()], now).await.unwrap();
+        }
+        trends_repository.record(&["expired".into()], now - Duration::days(2)).await.unwrap();
+
+        let trends = (GetTrends { trends_repository }).execute(10).await.unwrap();
+
+        assert_eq!(
+            trends
+                .iter()
+                .map(|t| t.tag.as_str())
+                .collect::<Vec<_>>(),
+            vec!["new", "old"]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::two_factor::{ ConfirmTwoFactorDTO, RecoveryCodesDTO },
+    entities::totp::Totp,
+    error_codes::{
+        INVALID_TWO_FACTOR_CODE_ERROR_CODE,
+        TWO_FACTOR_ALREADY_ENABLED_ERROR_CODE,
+        TWO_FACTOR_NOT_PENDING_ERROR_CODE,
+    },
+    features::feature::Feature,
+    policies::two_factor::TwoFactorPolicy,
+    repositories::{ secret_cipher::SecretCipher, two_factor_repository::TwoFactorRepository },
+};
+
+pub type ConfirmTwoFactorFeature = dyn Feature<ConfirmTwoFactorDTO, RecoveryCodesDTO>;
+
+/// Turns two-factor authentication on once a first code proves the app holds
+/// the secret, and hands out the recovery codes.
+pub struct ConfirmTwoFactor {
+    pub two_factor_repository: BArc<dyn TwoFactorRepository>,
+    pub secret_cipher: BArc<dyn SecretCipher>,
+    pub policy: TwoFactorPolicy,
+}
+
+#[async_trait]
+impl Feature<ConfirmTwoFactorDTO, RecoveryCodesDTO> for ConfirmTwoFactor {
+    async fn execute(&self, input: ConfirmTwoFactorDTO) -> Result<RecoveryCodesDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("CONFIRM_TWO_FACTOR".into(), e));
+        }
+
+        let two_factor = self.two_factor_repository
+            .get(&input.user_id).await?
+            .ok_or_else(|| HearthError::Domain(TWO_FACTOR_NOT_PENDING_ERROR_CODE.into()))?;
+
+        if two_factor.enabled_at.is_some() {
+            return Err(HearthError::Domain(TWO_FACTOR_ALREADY_ENABLED_ERROR_CODE.into()));
+        }
+
+        let now = Utc::now();
+        let secret = self.secret_cipher.decrypt(&two_factor.encrypted_secret)?;
+        let step = Totp::verify(&secret, &input.code, now, None, &self.policy).ok_or_else(||
+            HearthError::Domain(INVALID_TWO_FACTOR_CODE_ERROR_CODE.into())
+        )?;
+
+        let codes: Vec<String> = (0..self.policy.recovery_codes)
+            .map(|_| Totp::generate_recovery_code())
+            .collect();
+        let hashes = codes
+            .iter()
+            .map(|code| {
+                let normalized = Totp::normalize_recovery_code(code);
+                hasher::hash!(normalized)
+            })
+            .collect();
+
+        self.two_factor_repository.enable(&input.user_id, step, hashes, now).await?;
+
+        Ok(RecoveryCodesDTO { codes })
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::two_factor::DisableTwoFactorDTO,
+    error_codes::{ INVALID_CREDENTIALS_ERROR_CODE, TWO_FACTOR_NOT_ENABLED_ERROR_CODE },
+    features::feature::Feature,
+    repositories::{
+        credentials_repository::CredentialsRepository,
+        two_factor_repository::TwoFactorRepository,
+    },
+};
+
+pub type DisableTwoFactorFeature = dyn Feature<DisableTwoFactorDTO, ()>;
+
+/// Needs the password again, a stolen session alone can't remove the second factor.
+pub struct DisableTwoFactor {
+    pub credentials_repository: BArc<dyn CredentialsRepository>,
+    pub two_factor_repository: BArc<dyn TwoFactorRepository>,
+}
+
+#[async_trait]
+impl Feature<DisableTwoFactorDTO, ()> for DisableTwoFactor {
+    async fn execute(&self, input: DisableTwoFactorDTO) -> Result<(), HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("DISABLE_TWO_FACTOR".into(), e));
+        }
+
+        let credentials = self.credentials_repository.get(&input.user_id).await?;
+        if credentials.is_none_or(|credentials| credentials.password_hash != hasher::hash!(input.password)) {
+            return Err(HearthError::Unauthorized(INVALID_CREDENTIALS_ERROR_CODE.into()));
+        }
+
+        let two_factor = self.two_factor_repository.get(&input.user_id).await?;
+        if two_factor.is_none() {
+            return Err(HearthError::Domain(TWO_FACTOR_NOT_ENABLED_ERROR_CODE.into()));
+        }
+
+        self.two_factor_repository.disable(&input.user_id).await
+    }
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::two_factor::TwoFactorEnrollmentDTO,
+    entities::totp::Totp,
+    error_codes::TWO_FACTOR_ALREADY_ENABLED_ERROR_CODE,
+    features::feature::Feature,
+    policies::two_factor::TwoFactorPolicy,
+    repositories::{
+        secret_cipher::SecretCipher,
+        two_factor_repository::TwoFactorRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type EnrollTwoFactorFeature = dyn Feature<Uuid, TwoFactorEnrollmentDTO>;
+
+/// Starts the setup with a fresh secret, nothing changes at login until
+/// `ConfirmTwoFactor` saw a first code.
+pub struct EnrollTwoFactor {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub two_factor_repository: BArc<dyn TwoFactorRepository>,
+    pub secret_cipher: BArc<dyn SecretCipher>,
+    pub policy: TwoFactorPolicy,
+}
+
+#[async_trait]
+impl Feature<Uuid, TwoFactorEnrollmentDTO> for EnrollTwoFactor {
+    async fn execute(&self, user_id: Uuid) -> Result<TwoFactorEnrollmentDTO, HearthError> {
+        let existing = self.two_factor_repository.get(&user_id).await?;
+        if existing.is_some_and(|two_factor| two_factor.enabled_at.is_some()) {
+            return Err(HearthError::Domain(TWO_FACTOR_ALREADY_ENABLED_ERROR_CODE.into()));
+        }
+
+        let user = self.users_repository.get(user_id.to_string()).await?;
+
+        let secret = Totp::generate_secret();
+        let encrypted_secret = self.secret_cipher.encrypt(&secret)?;
+        self.two_factor_repository.save_pending(&user_id, encrypted_secret).await?;
+
+        Ok(TwoFactorEnrollmentDTO {
+            secret: Totp::base32(&secret),
+            otpauth_uri: Totp::otpauth_uri(&secret, &user.username, &self.policy),
+        })
+    }
+}
@@ -0,0 +1,409 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::{ auth::SessionDTO, two_factor::LoginTwoFactorDTO },
+    entities::totp::Totp,
+    error_codes::{ INVALID_LOGIN_CHALLENGE_ERROR_CODE, INVALID_TWO_FACTOR_CODE_ERROR_CODE },
+    features::{
+        auth::login_with_email::{ cancel_pending_deletion, generate_session_token },
+        feature::Feature,
+    },
+    policies::{ account_deletion::AccountDeletionPolicy, two_factor::TwoFactorPolicy },
+    repositories::{
+        login_challenges_repository::LoginChallengesRepository,
+        secret_cipher::SecretCipher,
+        sessions_repository::SessionsRepository,
+        two_factor_repository::TwoFactorRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type LoginWithTwoFactorFeature = dyn Feature<LoginTwoFactorDTO, SessionDTO>;
+
+/// Second login step, the challenge stays valid after a wrong code so a typo
+/// doesn't require the password again, up to the policy's failure limit.
+pub struct LoginWithTwoFactor {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub login_challenges_repository: BArc<dyn LoginChallengesRepository>,
+    pub two_factor_repository: BArc<dyn TwoFactorRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub secret_cipher: BArc<dyn SecretCipher>,
+    pub policy: TwoFactorPolicy,
+    pub account_deletion_policy: AccountDeletionPolicy,
+}
+
+#[async_trait]
+impl Feature<LoginTwoFactorDTO, SessionDTO> for LoginWithTwoFactor {
+    async fn execute(&self, input: LoginTwoFactorDTO) -> Result<SessionDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("LOGIN_TWO_FACTOR".into(), e));
+        }
+
+        let invalid_code = || HearthError::Unauthorized(INVALID_TWO_FACTOR_CODE_ERROR_CODE.into());
+
+        let challenge = hasher::hash!(input.challenge_token);
+        let user_id = self.login_challenges_repository
+            .get_user_id(&challenge).await?
+            .ok_or_else(|| HearthError::Unauthorized(INVALID_LOGIN_CHALLENGE_ERROR_CODE.into()))?;
+
+        let two_factor = self.two_factor_repository
+            .get(&user_id).await?
+            .filter(|two_factor| two_factor.enabled_at.is_some())
+            .ok_or_else(|| HearthError::Unauthorized(INVALID_LOGIN_CHALLENGE_ERROR_CODE.into()))?;
+
+        let now = Utc::now();
+        let accepted = match (input.code, input.recovery_code) {
+            (Some(code), None) => {
+                let secret = self.secret_cipher.decrypt(&two_factor.encrypted_secret)?;
+                match Totp::verify(&secret, &code, now, two_factor.last_step, &self.policy) {
+                    Some(step) => self.two_factor_repository.record_step(&user_id, step).await?,
+                    None => false,
+                }
+            }
+            (None, Some(recovery_code)) => {
+                let normalized = Totp::normalize_recovery_code(&recovery_code);
+                let code_hash = hasher::hash!(normalized);
+                self.two_factor_repository.use_recovery_code(&user_id, &code_hash, now).await?
+            }
+            _ => false,
+        };
+
+        if !accepted {
+            let failures = self.login_challenges_repository
+                .record_failure(&challenge, self.policy.login_challenge_ttl_seconds).await?;
+            if failures >= self.policy.login_challenge_max_failures {
+                self.login_challenges_repository.delete(&challenge).await?;
+            }
+            return Err(invalid_code());
+        }
+
+        self.login_challenges_repository.delete(&challenge).await?;
+
+        let token = generate_session_token();
+        self.sessions_repository.create(&hasher::hash!(token), &user_id).await?;
+
+        let user = self.users_repository.get(user_id.to_string()).await?;
+        cancel_pending_deletion(&user, &self.users_repository, &self.account_deletion_policy, now).await?;
+
+        Ok(SessionDTO { token, user_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::{ CredentialsDTO, LoginEmailDTO, LoginResultDTO },
+            two_factor::{ ConfirmTwoFactorDTO, DisableTwoFactorDTO, LoginTwoFactorDTO },
+            user::{ CreateUserDTO, UserStatus },
+        },
+        entities::totp::Totp,
+        error_codes::{
+            INVALID_CREDENTIALS_ERROR_CODE,
+            INVALID_LOGIN_CHALLENGE_ERROR_CODE,
+            INVALID_TWO_FACTOR_CODE_ERROR_CODE,
+        },
+        features::{
+            auth::login_with_email::LoginWithEmail,
+            feature::Feature,
+            two_factor::{
+                confirm_two_factor::ConfirmTwoFactor,
+                disable_two_factor::DisableTwoFactor,
+                enroll_two_factor::EnrollTwoFactor,
+            },
+        },
+        policies::{
+            account_deletion::AccountDeletionPolicy,
+            signup::EmailPolicy,
+            two_factor::TwoFactorPolicy,
+        },
+        repositories::{
+            credentials_repository::CredentialsRepository,
+            login_challenges_repository::LoginChallengesRepository,
+            secret_cipher::SecretCipher,
+            sessions_repository::SessionsRepository,
+            two_factor_repository::TwoFactorRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryLoginChallengesRepository,
+            InMemorySessionsRepository,
+            InMemoryTwoFactorRepository,
+            InMemoryUserRepository,
+            XorSecretCipher,
+        },
+    };
+
+    use super::LoginWithTwoFactor;
+
+    const EMAIL: &str = "john.smith@gmail.com";
+    const PASSWORD: &str = "qwerty123";
+
+    struct Fixture {
+        user_id: Uuid,
+        users_repository: InMemoryUserRepository,
+        two_factor_repository: BArc<dyn TwoFactorRepository>,
+        login_challenges_repository: BArc<dyn LoginChallengesRepository>,
+        sessions_repository: BArc<dyn SessionsRepository>,
+        secret_cipher: BArc<dyn SecretCipher>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let user_id = Uuid::new_v4();
+            let users_repository = InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: EMAIL.into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!(PASSWORD) }
+            );
+
+            Fixture {
+                user_id,
+                users_repository,
+                two_factor_repository: barc!(InMemoryTwoFactorRepository::default()),
+                login_challenges_repository: barc!(InMemoryLoginChallengesRepository::default()),
+                sessions_repository: barc!(InMemorySessionsRepository::default()),
+                secret_cipher: barc!(XorSecretCipher::default()),
+            }
+        }
+
+        /// Enrolls and confirms, returns the raw secret and the recovery codes.
+        async fn enable(&self) -> (Vec<u8>, Vec<String>) {
+            let users_repository: BArc<dyn UsersRepository> = barc!(self.users_repository.clone());
+            let enrollment = (EnrollTwoFactor {
+                users_repository,
+                two_factor_repository: self.two_factor_repository.clone(),
+                secret_cipher: self.secret_cipher.clone(),
+                policy: TwoFactorPolicy::default(),
+            })
+                .execute(self.user_id).await
+                .unwrap();
+            assert!(enrollment.otpauth_uri.contains("john.smith"));
+
+            let stored = self.two_factor_repository.get(&self.user_id).await.unwrap().unwrap();
+            let secret = self.secret_cipher.decrypt(&stored.encrypted_secret).unwrap();
+            assert_eq!(Totp::base32(&secret), enrollment.secret);
+
+            let policy = TwoFactorPolicy::default();
+            let code = Totp::code(&secret, Totp::step(Utc::now(), &policy) - 1, &policy);
+            let recovery_codes = (ConfirmTwoFactor {
+                two_factor_repository: self.two_factor_repository.clone(),
+                secret_cipher: self.secret_cipher.clone(),
+                policy,
+            })
+                .execute(ConfirmTwoFactorDTO { user_id: self.user_id, code }).await
+                .unwrap();
+
+            (secret, recovery_codes.codes)
+        }
+
+        async fn password_step(&self) -> String {
+            let users_repository: BArc<dyn UsersRepository> = barc!(self.users_repository.clone());
+            let credentials_repository: BArc<dyn CredentialsRepository> = barc!(
+                self.users_repository.clone()
+            );
+            let result = (LoginWithEmail {
+                users_repository,
+                credentials_repository,
+                sessions_repository: self.sessions_repository.clone(),
+                two_factor_repository: self.two_factor_repository.clone(),
+                login_challenges_repository: self.login_challenges_repository.clone(),
+                email_policy: EmailPolicy::default(),
+                two_factor_policy: TwoFactorPolicy::default(),
+                account_deletion_policy: AccountDeletionPolicy::default(),
+            })
+                .execute(LoginEmailDTO { email: EMAIL.into(), password: PASSWORD.into() }).await
+                .unwrap();
+
+            match result {
+                LoginResultDTO::TwoFactorRequired { challenge_token } => challenge_token,
+                other => panic!("expected a two-factor challenge, got {:?}", other),
+            }
+        }
+
+        fn login(&self) -> LoginWithTwoFactor {
+            LoginWithTwoFactor {
+                users_repository: barc!(self.users_repository.clone()),
+                login_challenges_repository: self.login_challenges_repository.clone(),
+                two_factor_repository: self.two_factor_repository.clone(),
+                sessions_repository: self.sessions_repository.clone(),
+                secret_cipher: self.secret_cipher.clone(),
+                policy: TwoFactorPolicy::default(),
+                account_deletion_policy: AccountDeletionPolicy::default(),
+            }
+        }
+    }
+
+    fn with_code(challenge_token: &str, code: String) -> LoginTwoFactorDTO {
+        LoginTwoFactorDTO {
+            challenge_token: challenge_token.into(),
+            code: Some(code),
+            recovery_code: None,
+        }
+    }
+
+    fn with_recovery_code(challenge_token: &str, recovery_code: &str) -> LoginTwoFactorDTO {
+        LoginTwoFactorDTO {
+            challenge_token: challenge_token.into(),
+            code: None,
+            recovery_code: Some(recovery_code.into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_require_a_current_code_once_enabled_and_refuse_replays() {
+        let fixture = Fixture::new();
+        let (secret, _) = fixture.enable().await;
+        let policy = TwoFactorPolicy::default();
+        let login = fixture.login();
+
+        // The step used to confirm is spent already.
+        let challenge_token = fixture.password_step().await;
+        let spent = Totp::code(&secret, Totp::step(Utc::now(), &policy) - 1, &policy);
+        assert!(
+            matches!(
+                login.execute(with_code(&challenge_token, spent)).await,
+                Err(HearthError::Unauthorized(code)) if code == INVALID_TWO_FACTOR_CODE_ERROR_CODE
+            )
+        );
+
+        let code = Totp::code(&secret, Totp::step(Utc::now(), &policy), &policy);
+        let session = login.execute(with_code(&challenge_token, code.clone())).await.unwrap();
+        assert_eq!(session.user_id, fixture.user_id);
+        assert_eq!(
+            fixture.sessions_repository.get_user_id(&hasher::hash!(session.token)).await.unwrap(),
+            Some(fixture.user_id)
+        );
+
+        // The challenge is single use, and so is the code on a new one.
+        assert!(
+            matches!(
+                login.execute(with_code(&challenge_token, code.clone())).await,
+                Err(HearthError::Unauthorized(code)) if code == INVALID_LOGIN_CHALLENGE_ERROR_CODE
+            )
+        );
+        let challenge_token = fixture.password_step().await;
+        assert!(
+            matches!(
+                login.execute(with_code(&challenge_token, code)).await,
+                Err(HearthError::Unauthorized(code)) if code == INVALID_TWO_FACTOR_CODE_ERROR_CODE
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn should_invalidate_the_challenge_after_too_many_wrong_codes() {
+        let fixture = Fixture::new();
+        let (secret, _) = fixture.enable().await;
+        let policy = TwoFactorPolicy::default();
+        let login = fixture.login();
+
+        let challenge_token = fixture.password_step().await;
+        let wrong = Totp::code(&secret, Totp::step(Utc::now(), &policy) + 100, &policy);
+        for _ in 0..policy.login_challenge_max_failures {
+            assert!(
+                matches!(
+                    login.execute(with_code(&challenge_token, wrong.clone())).await,
+                    Err(HearthError::Unauthorized(code)) if code == INVALID_TWO_FACTOR_CODE_ERROR_CODE
+                )
+            );
+        }
+
+        let code = Totp::code(&secret, Totp::step(Utc::now(), &policy), &policy);
+        assert!(
+            matches!(
+                login.execute(with_code(&challenge_token, code.clone())).await,
+                Err(HearthError::Unauthorized(code)) if code == INVALID_LOGIN_CHALLENGE_ERROR_CODE
+            )
+        );
+        let challenge_token = fixture.password_step().await;
+        assert!(login.execute(with_code(&challenge_token, code)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_accept_each_recovery_code_once() {
+        let fixture = Fixture::new();
+        let (_, recovery_codes) = fixture.enable().await;
+        assert_eq!(recovery_codes.len(), TwoFactorPolicy::default().recovery_codes);
+        let login = fixture.login();
+
+        let challenge_token = fixture.password_step().await;
+        let typed = recovery_codes[0].to_uppercase().replace('-', "");
+        assert!(login.execute(with_recovery_code(&challenge_token, &typed)).await.is_ok());
+
+        let challenge_token = fixture.password_step().await;
+        assert!(
+            matches!(
+                login.execute(with_recovery_code(&challenge_token, &recovery_codes[0])).await,
+                Err(HearthError::Unauthorized(code)) if code == INVALID_TWO_FACTOR_CODE_ERROR_CODE
+            )
+        );
+        assert!(
+            login.execute(with_recovery_code(&challenge_token, &recovery_codes[1])).await.is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_cancel_a_pending_deletion_only_once_the_second_factor_passed() {
+        let fixture = Fixture::new();
+        let (secret, _) = fixture.enable().await;
+        let policy = TwoFactorPolicy::default();
+        let login = fixture.login();
+        let users_repository = login.users_repository.clone();
+        let status = || async {
+            users_repository.get(fixture.user_id.to_string()).await.unwrap().status
+        };
+        users_repository.set_deactivated(&fixture.user_id, Some(Utc::now())).await.unwrap();
+
+        // The password alone doesn't undo the deletion, nor does a wrong code.
+        let challenge_token = fixture.password_step().await;
+        assert_eq!(status().await, UserStatus::Deactivated);
+        let wrong = Totp::code(&secret, Totp::step(Utc::now(), &policy) + 100, &policy);
+        assert!(login.execute(with_code(&challenge_token, wrong)).await.is_err());
+        assert_eq!(status().await, UserStatus::Deactivated);
+
+        let code = Totp::code(&secret, Totp::step(Utc::now(), &policy), &policy);
+        login.execute(with_code(&challenge_token, code)).await.unwrap();
+        let user = users_repository.get(fixture.user_id.to_string()).await.unwrap();
+        assert_eq!((user.status, user.deactivated_at), (UserStatus::Active, None));
+    }
+
+    #[tokio::test]
+    async fn should_disable_only_with_the_password() {
+        let fixture = Fixture::new();
+        fixture.enable().await;
+        let credentials_repository: BArc<dyn CredentialsRepository> = barc!(
+            fixture.users_repository.clone()
+        );
+        let disable = DisableTwoFactor {
+            credentials_repository,
+            two_factor_repository: fixture.two_factor_repository.clone(),
+        };
+
+        let result = disable.execute(DisableTwoFactorDTO {
+            user_id: fixture.user_id,
+            password: "wrong password".into(),
+        }).await;
+        assert!(
+            matches!(result, Err(HearthError::Unauthorized(code)) if code == INVALID_CREDENTIALS_ERROR_CODE)
+        );
+
+        disable
+            .execute(DisableTwoFactorDTO { user_id: fixture.user_id, password: PASSWORD.into() }).await
+            .unwrap();
+        assert!(fixture.two_factor_repository.get(&fixture.user_id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod confirm_two_factor;
+pub mod disable_two_factor;
+pub mod enroll_two_factor;
+pub mod login_with_two_factor;
@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use validator::Validate;
+
+use crate::{
+    dtos::user::{ AccountDeletionDTO, DeleteAccountDTO },
+    error_codes::INVALID_PASSWORD_ERROR_CODE,
+    features::feature::Feature,
+    policies::account_deletion::AccountDeletionPolicy,
+    repositories::{
+        credentials_repository::CredentialsRepository,
+        sessions_repository::SessionsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type DeleteAccountFeature = dyn Feature<DeleteAccountDTO, AccountDeletionDTO>;
+
+/// Only deactivates the account, `PurgeDeletedAccounts` deletes it once the
+/// grace period is over unless its owner logged back in by then.
+pub struct DeleteAccount {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub credentials_repository: BArc<dyn CredentialsRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub policy: AccountDeletionPolicy,
+}
+
+#[async_trait]
+impl Feature<DeleteAccountDTO, AccountDeletionDTO> for DeleteAccount {
+    async fn execute(&self, input: DeleteAccountDTO) -> Result<AccountDeletionDTO, HearthError> {
+        if let Err(e) = input.validate() {
+            return Err(HearthError::Validation("DELETE_ACCOUNT".into(), e));
+        }
+
+        let credentials = self.credentials_repository.get(&input.user_id).await?;
+        if credentials.is_none_or(|c| c.password_hash != hasher::hash!(input.password)) {
+            return Err(HearthError::Forbidden(INVALID_PASSWORD_ERROR_CODE.into()));
+        }
+
+        let now = Utc::now();
+        self.users_repository.set_deactivated(&input.user_id, Some(now)).await?;
+        self.sessions_repository.revoke_all(&input.user_id).await?;
+
+        Ok(AccountDeletionDTO { purge_at: self.policy.purge_at(now) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::{ CredentialsDTO, LoginEmailDTO, LoginResultDTO },
+            user::{ CreateUserDTO, DeleteAccountDTO, UserStatus },
+        },
+        error_codes::{
+            ACCOUNT_DEACTIVATED_ERROR_CODE,
+            ACCOUNT_SUSPENDED_ERROR_CODE,
+            INVALID_PASSWORD_ERROR_CODE,
+        },
+        features::{
+            auth::login_with_email::LoginWithEmail,
+            feature::Feature,
+            users::delete_account::DeleteAccount,
+        },
+        policies::{
+            account_deletion::AccountDeletionPolicy,
+            signup::EmailPolicy,
+            two_factor::TwoFactorPolicy,
+        },
+        repositories::{
+            sessions_repository::SessionsRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryLoginChallengesRepository,
+            InMemorySessionsRepository,
+            InMemoryTwoFactorRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+    const PASSWORD: &str = "qwerty123";
+
+    fn delete_account() -> (DeleteAccount, LoginWithEmail, Uuid) {
+        let user_id = Uuid::new_v4();
+        let users = InMemoryUserRepository::from_existing_user(
+            CreateUserDTO {
+                user_id,
+                username: "john.smith".into(),
+                email: EMAIL.into(),
+                birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+            },
+            CredentialsDTO { user_id, password_hash: hasher::hash!(PASSWORD) }
+        );
+        let sessions_repository: BArc<dyn SessionsRepository> = barc!(
+            InMemorySessionsRepository::default()
+        );
+
+        let delete_account = DeleteAccount {
+            users_repository: barc!(users.clone()),
+            credentials_repository: barc!(users.clone()),
+            sessions_repository: sessions_repository.clone(),
+            policy: AccountDeletionPolicy::default(),
+        };
+        let login = LoginWithEmail {
+            users_repository: barc!(users.clone()),
+            credentials_repository: barc!(users),
+            sessions_repository,
+            two_factor_repository: barc!(InMemoryTwoFactorRepository::default()),
+            login_challenges_repository: barc!(InMemoryLoginChallengesRepository::default()),
+            email_policy: EmailPolicy::default(),
+            two_factor_policy: TwoFactorPolicy::default(),
+            account_deletion_policy: AccountDeletionPolicy::default(),
+        };
+
+        (delete_account, login, user_id)
+    }
+
+    #[tokio::test]
+    async fn should_deactivate_and_log_out_everywhere() {
+        let (delete_account, login, user_id) = delete_account();
+        let session = match
+            login.execute(LoginEmailDTO { email: EMAIL.into(), password: PASSWORD.into() }).await
+        {
+            Ok(LoginResultDTO::Session(session)) => session,
+            other => panic!("expected a session, got {:?}", other),
+        };
+
+        let deletion = delete_account
+            .execute(DeleteAccountDTO { user_id, password: PASSWORD.into() }).await
+            .unwrap();
+
+        assert!(deletion.purge_at > Utc::now() + Duration::days(29));
+        let user = delete_account.users_repository.get(user_id.to_string()).await.unwrap();
+        assert_eq!(user.status, UserStatus::Deactivated);
+        assert_eq!(
+            delete_account.sessions_repository
+                .get_user_id(&hasher::hash!(session.token)).await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn should_require_the_password() {
+        let (delete_account, _, user_id) = delete_account();
+
+        let result = delete_account.execute(DeleteAccountDTO {
+            user_id,
+            password: "wrong-password".into(),
+        }).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HearthError::Forbidden(INVALID_PASSWORD_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reactivate_on_login_during_the_grace_period_only() {
+        let (delete_account, login, user_id) = delete_account();
+        let users_repository: &BArc<dyn UsersRepository> = &delete_account.users_repository;
+        delete_account
+            .execute(DeleteAccountDTO { user_id, password: PASSWORD.into() }).await
+            .unwrap();
+
+        let credentials = || LoginEmailDTO { email: EMAIL.into(), password: PASSWORD.into() };
+        assert!(matches!(login.execute(credentials()).await, Ok(LoginResultDTO::Session(_))));
+        let user = users_repository.get(user_id.to_string()).await.unwrap();
+        assert_eq!(user.status, UserStatus::Active);
+        assert_eq!(user.deactivated_at, None);
+
+        users_repository
+            .set_deactivated(&user_id, Some(Utc::now() - Duration::days(31))).await
+            .unwrap();
+        assert_eq!(
+            login.execute(credentials()).await.unwrap_err(),
+            HearthError::Forbidden(ACCOUNT_DEACTIVATED_ERROR_CODE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_keep_a_suspension_made_during_the_grace_period() {
+        let (delete_account, login, user_id) = delete_account();
+        let users_repository: &BArc<dyn UsersRepository> = &delete_account.users_repository;
+        delete_account
+            .execute(DeleteAccountDTO { user_id, password: PASSWORD.into() }).await
+            .unwrap();
+        users_repository.set_status(&user_id, UserStatus::Suspended, None).await.unwrap();
+
+        let credentials = LoginEmailDTO { email: EMAIL.into(), password: PASSWORD.into() };
+        assert_eq!(
+            login.execute(credentials).await.unwrap_err(),
+            HearthError::Forbidden(ACCOUNT_SUSPENDED_ERROR_CODE.into())
+        );
+        let user = users_repository.get(user_id.to_string()).await.unwrap();
+        assert_eq!(user.status, UserStatus::Suspended);
+
+        // Cancelling the deletion some other way doesn't lift the suspension either.
+        users_repository.set_deactivated(&user_id, None).await.unwrap();
+        let user = users_repository.get(user_id.to_string()).await.unwrap();
+        assert_eq!((user.status, user.deactivated_at), (UserStatus::Suspended, None));
+    }
+}
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::user::{ ProfileDTO, UserStatus },
+    entities::user::User,
+    error_codes::USER_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::users_repository::UsersRepository,
+};
+
+pub type GetProfileFeature = dyn Feature<Uuid, ProfileDTO>;
+
+/// Suspended accounts are still shown, flagged and without their avatar.
+/// Accounts pending deletion are hidden as if already gone.
+pub struct GetProfile {
+    pub users_repository: BArc<dyn UsersRepository>,
+}
+
+#[async_trait]
+impl Feature<Uuid, ProfileDTO> for GetProfile {
+    async fn execute(&self, user_id: Uuid) -> Result<ProfileDTO, HearthError> {
+        let user = self.users_repository.get(user_id.to_string()).await?;
+        if user.status == UserStatus::Deactivated {
+            return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+        }
+        let suspended = User::is_suspended(&user, Utc::now());
+
+        Ok(ProfileDTO {
+            user_id: user.user_id,
+            username: user.username,
+            avatar_media_id: if suspended { None } else { user.avatar_media_id },
+            suspended,
+            created_at: user.created_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{ auth::CredentialsDTO, user::{ CreateUserDTO, UserStatus } },
+        features::{ feature::Feature, users::get_profile::GetProfile },
+        repositories::users_repository::UsersRepository,
+        test_utils::test_utils::InMemoryUserRepository,
+    };
+
+    #[tokio::test]
+    async fn should_show_suspended_accounts_as_suspended() {
+        let user_id = Uuid::new_v4();
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: "john.smith@gmail.com".into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: "hash".into() }
+            )
+        );
+        users_repository.set_avatar(&user_id, Some(Uuid::new_v4())).await.unwrap();
+        let get_profile = GetProfile { users_repository: users_repository.clone() };
+
+        let profile = get_profile.execute(user_id).await.unwrap();
+        assert!(!profile.suspended);
+        assert!(profile.avatar_media_id.is_some());
+
+        users_repository.set_status(&user_id, UserStatus::Suspended, None).await.unwrap();
+        let profile = get_profile.execute(user_id).await.unwrap();
+        assert_eq!(profile.username, "john.smith");
+        assert!(profile.suspended);
+        assert_eq!(profile.avatar_media_id, None);
+    }
+}
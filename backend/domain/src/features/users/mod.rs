@@ -0,0 +1,6 @@
+pub mod delete_account;
+pub mod get_profile;
+pub mod purge_deleted_accounts;
+pub mod send_email_verification;
+pub mod verify_email;
+pub mod welcome_registered_user;
@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Duration, Utc };
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::media::MediaDTO,
+    features::feature::Feature,
+    policies::account_deletion::AccountDeletionPolicy,
+    repositories::{
+        email_verifications_repository::EmailVerificationRepository,
+        media_repository::MediaRepository,
+        object_store::ObjectStore,
+        sessions_repository::SessionsRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+const BATCH_SIZE: u64 = 50;
+
+/// Purges the accounts whose grace period ended by the given instant, returns
+/// the number of accounts handled. Meant to be run periodically.
+pub type PurgeDeletedAccountsFeature = dyn Feature<DateTime<Utc>, u64>;
+
+pub struct PurgeDeletedAccounts {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub media_repository: BArc<dyn MediaRepository>,
+    pub object_store: BArc<dyn ObjectStore>,
+    pub email_verification_repository: BArc<dyn EmailVerificationRepository>,
+    pub sessions_repository: BArc<dyn SessionsRepository>,
+    pub policy: AccountDeletionPolicy,
+}
+
+#[async_trait]
+impl Feature<DateTime<Utc>, u64> for PurgeDeletedAccounts {
+    async fn execute(&self, at: DateTime<Utc>) -> Result<u64, HearthError> {
+        let deactivated_before = at - Duration::days(self.policy.grace_period_days as i64);
+        let users = self.users_repository
+            .list_deactivated_before(deactivated_before, BATCH_SIZE).await?;
+
+        for user in &users {
+            // Files first, their rows go away with the account.
+            for media in self.media_repository.list_by_owner(&user.user_id).await? {
+                for variant in &media.variants {
+                    self.object_store.delete(
+                        &MediaDTO::storage_key(&media.media_id, &variant.name)
+                    ).await?;
+                }
+                // Left behind when the account goes before the upload got processed.
+                self.object_store.delete(&MediaDTO::upload_key(&media.media_id)).await?;
+            }
+
+            self.email_verification_repository.delete(&user.email).await?;
+            self.sessions_repository.revoke_all(&user.user_id).await?;
+            self.users_repository.purge(
+                &user.user_id,
+                at + Duration::days(self.policy.username_hold_days as i64)
+            ).await?;
+        }
+
+        Ok(users.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, NaiveDate, Utc };
+    use email_verification_code::EmailVerificationCode;
+    use macros::barc;
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            media::{ MediaDTO, MediaStatus, MediaVariantDTO, ORIGINAL_VARIANT },
+            user::CreateUserDTO,
+        },
+        features::{ feature::Feature, users::purge_deleted_accounts::PurgeDeletedAccounts },
+        policies::account_deletion::AccountDeletionPolicy,
+        repositories::credentials_repository::CredentialsRepository,
+        test_utils::test_utils::{
+            InMemoryEmailVerificationRepository,
+            InMemoryMediaRepository,
+            InMemoryObjectStore,
+            InMemorySessionsRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+
+    #[tokio::test]
+    async fn should_purge_accounts_once_the_grace_period_is_over() {
+        let user_id = Uuid::new_v4();
+        let users = InMemoryUserRepository::from_existing_user(
+            CreateUserDTO {
+                user_id,
+                username: "john.smith".into(),
+                email: EMAIL.into(),
+                birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+            },
+            CredentialsDTO { user_id, password_hash: "hash".into() }
+        );
+        let code = EmailVerificationCode::default();
+        let purge = PurgeDeletedAccounts {
+            users_repository: barc!(users.clone()),
+            media_repository: barc!(InMemoryMediaRepository::default()),
+            object_store: barc!(InMemoryObjectStore::default()),
+            email_verification_repository: barc!(
+                InMemoryEmailVerificationRepository::from_email_and_code(EMAIL.into(), code.clone())
+            ),
+            sessions_repository: barc!(InMemorySessionsRepository::default()),
+            policy: AccountDeletionPolicy::default(),
+        };
+
+        let media_id = Uuid::new_v4();
+        let key = MediaDTO::storage_key(&media_id, ORIGINAL_VARIANT);
+        purge.object_store.put(&key, vec![1, 2, 3], "image/jpeg").await.unwrap();
+        purge.media_repository.create(MediaDTO {
+            media_id,
+            owner_id: user_id,
+            mime_type: "image/jpeg".into(),
+            status: MediaStatus::Ready,
+            blurhash: "".into(),
+            variants: vec![MediaVariantDTO {
+                name: ORIGINAL_VARIANT.into(),
+                mime_type: "image/jpeg".into(),
+                width: 1,
+                height: 1,
+                size_bytes: 3,
+            }],
+            created_at: Utc::now(),
+        }).await.unwrap();
+
+        let now = Utc::now();
+        purge.users_repository.set_deactivated(&user_id, Some(now)).await.unwrap();
+        assert_eq!(purge.execute(now + Duration::days(29)).await.unwrap(), 0);
+
+        assert_eq!(purge.execute(now + Duration::days(31)).await.unwrap(), 1);
+        assert!(purge.users_repository.get(user_id.to_string()).await.is_err());
+        assert!(CredentialsRepository::get(&users, &user_id).await.unwrap().is_none());
+        assert!(purge.object_store.get(&key).await.is_err());
+        assert!(
+            !purge.email_verification_repository.code_matches(&EMAIL.into(), &code).await.unwrap()
+        );
+        // Held from reuse for a while.
+        assert!(purge.users_repository.username_exists(&"john.smith".into()).await.unwrap());
+    }
+}
@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use email_verification_code::EmailVerificationCode;
+use errors::HearthError;
+use macros::BArc;
+use uuid::Uuid;
+
+use crate::{
+    dtos::job::JobPayload,
+    entities::jobs::Jobs,
+    features::feature::Feature,
+    repositories::{
+        email_verifications_repository::EmailVerificationRepository,
+        job_queue::JobQueue,
+        users_repository::UsersRepository,
+    },
+};
+
+/// Emails a code to the address of the account, `VerifyEmail` checks it.
+/// Asking again replaces the previous code. The email itself is sent by the
+/// worker, a relay outage doesn't fail the request.
+pub type SendEmailVerificationFeature = dyn Feature<Uuid, ()>;
+
+pub struct SendEmailVerification {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub email_verification_repository: BArc<dyn EmailVerificationRepository>,
+    pub job_queue: BArc<dyn JobQueue>,
+}
+
+#[async_trait]
+impl Feature<Uuid, ()> for SendEmailVerification {
+    async fn execute(&self, user_id: Uuid) -> Result<(), HearthError> {
+        let user = self.users_repository.get(user_id.to_string()).await?;
+
+        if user.email_verified {
+            return Ok(());
+        }
+
+        let code = EmailVerificationCode::default();
+        self.email_verification_repository.store(&user.email, &code).await?;
+        let payload = JobPayload::SendVerifyEmail { email: user.email, code: code.code };
+        self.job_queue.enqueue(Jobs::schedule(payload, Utc::now())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use email_verification_code::EmailVerificationCode;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{ auth::CredentialsDTO, job::JobPayload, user::CreateUserDTO },
+        features::{ feature::Feature, users::send_email_verification::SendEmailVerification },
+        repositories::email_verifications_repository::EmailVerificationRepository,
+        test_utils::test_utils::{
+            InMemoryEmailVerificationRepository,
+            InMemoryJobQueue,
+            InMemoryUserRepository,
+        },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+
+    struct Fixture {
+        user_id: Uuid,
+        users: InMemoryUserRepository,
+        email_verification_repository: BArc<dyn EmailVerificationRepository>,
+        job_queue: InMemoryJobQueue,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let user_id = Uuid::new_v4();
+            let users = InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: EMAIL.into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            );
+
+            Fixture {
+                user_id,
+                users,
+                email_verification_repository: barc!(
+                    InMemoryEmailVerificationRepository::default()
+                ),
+                job_queue: InMemoryJobQueue::default(),
+            }
+        }
+
+        fn send(&self) -> SendEmailVerification {
+            SendEmailVerification {
+                users_repository: barc!(self.users.clone()),
+                email_verification_repository: self.email_verification_repository.clone(),
+                job_queue: barc!(self.job_queue.clone()),
+            }
+        }
+
+        fn queued_codes(&self) -> Vec<EmailVerificationCode> {
+            self.job_queue.jobs
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|job| {
+                    let JobPayload::SendVerifyEmail { email, code } = job.payload.clone() else {
+                        panic!("expected a verification email, got {:?}", job.payload);
+                    };
+                    assert_eq!(email, EMAIL);
+                    EmailVerificationCode { code }
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn should_store_the_code_and_queue_its_email() {
+        let fixture = Fixture::new();
+
+        fixture.send().execute(fixture.user_id).await.unwrap();
+
+        let codes = fixture.queued_codes();
+        assert_eq!(codes.len(), 1);
+        let repository = &fixture.email_verification_repository;
+        assert!(repository.code_matches(&EMAIL.into(), &codes[0]).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_replace_the_previous_code() {
+        let fixture = Fixture::new();
+
+        fixture.send().execute(fixture.user_id).await.unwrap();
+        fixture.send().execute(fixture.user_id).await.unwrap();
+
+        let codes = fixture.queued_codes();
+        assert_eq!(codes.len(), 2);
+        let repository = &fixture.email_verification_repository;
+        assert!(repository.code_matches(&EMAIL.into(), &codes[1]).await.unwrap());
+        // Two random codes can come out the same.
+        if codes[0] != codes[1] {
+            assert!(!repository.code_matches(&EMAIL.into(), &codes[0]).await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_not_send_anything_to_a_verified_address() {
+        let fixture = Fixture::new();
+        fixture.users.verify_email(&fixture.user_id);
+
+        fixture.send().execute(fixture.user_id).await.unwrap();
+
+        assert!(fixture.queued_codes().is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use email_verification_code::EmailVerificationCode;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::{ event::{ DomainEvent, EventDTO }, user::VerifyEmailDTO },
+    error_codes::INVALID_EMAIL_VERIFICATION_CODE_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{
+        email_verifications_repository::EmailVerificationRepository,
+        users_repository::UsersRepository,
+    },
+};
+
+pub type VerifyEmailFeature = dyn Feature<VerifyEmailDTO, ()>;
+
+pub struct VerifyEmail {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub email_verification_repository: BArc<dyn EmailVerificationRepository>,
+}
+
+#[async_trait]
+impl Feature<VerifyEmailDTO, ()> for VerifyEmail {
+    async fn execute(&self, input: VerifyEmailDTO) -> Result<(), HearthError> {
+        let invalid = || HearthError::Domain(INVALID_EMAIL_VERIFICATION_CODE_ERROR_CODE.into());
+        let code = EmailVerificationCode::from_str(input.code).map_err(|_| invalid())?;
+
+        let user = self.users_repository.get(input.user_id.to_string()).await?;
+
+        if user.email_verified {
+            return Ok(());
+        }
+
+        if !self.email_verification_repository.code_matches(&user.email, &code).await? {
+            return Err(invalid());
+        }
+
+        let verified = EventDTO::new(DomainEvent::EmailVerified { user_id: user.user_id }, Utc::now());
+
+        self.users_repository.set_email_verified_with_events(&user.user_id, vec![verified]).await?;
+        self.email_verification_repository.delete(&user.email).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use email_verification_code::EmailVerificationCode;
+    use errors::HearthError;
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            event::DomainEvent,
+            job::JobPayload,
+            user::{ CreateUserDTO, VerifyEmailDTO },
+        },
+        error_codes::INVALID_EMAIL_VERIFICATION_CODE_ERROR_CODE,
+        features::{
+            feature::Feature,
+            users::{ send_email_verification::SendEmailVerification, verify_email::VerifyEmail },
+        },
+        repositories::{
+            email_verifications_repository::EmailVerificationRepository,
+            users_repository::UsersRepository,
+        },
+        test_utils::test_utils::{
+            InMemoryEmailVerificationRepository,
+            InMemoryJobQueue,
+            InMemoryOutboxRepository,
+            InMemoryUserRepository,
+        },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+
+    async fn user(users_repository: &BArc<dyn UsersRepository>) -> Uuid {
+        let user_id = Uuid::new_v4();
+        users_repository
+            .create(
+                CreateUserDTO {
+                    user_id,
+                    username: "john.smith".into(),
+                    email: EMAIL.into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            ).await
+            .unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    async fn should_verify_the_emailed_code_and_record_it_in_the_outbox() {
+        let outbox = InMemoryOutboxRepository::default();
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::with_outbox(outbox.clone())
+        );
+        let email_verification_repository: BArc<dyn EmailVerificationRepository> = barc!(
+            InMemoryEmailVerificationRepository::default()
+        );
+        let job_queue = InMemoryJobQueue::default();
+        let user_id = user(&users_repository).await;
+
+        SendEmailVerification {
+            users_repository: users_repository.clone(),
+            email_verification_repository: email_verification_repository.clone(),
+            job_queue: barc!(job_queue.clone()),
+        }
+            .execute(user_id).await
+            .unwrap();
+        let payload = job_queue.jobs.lock().unwrap()[0].payload.clone();
+        let JobPayload::SendVerifyEmail { email, code } = payload else {
+            panic!("expected a verification email, got {:?}", payload);
+        };
+        assert_eq!(email, EMAIL);
+        let code = EmailVerificationCode { code };
+
+        let verify_email = VerifyEmail {
+            users_repository: users_repository.clone(),
+            email_verification_repository: email_verification_repository.clone(),
+        };
+        verify_email.execute(VerifyEmailDTO { user_id, code: code.code.clone() }).await.unwrap();
+
+        assert!(users_repository.get(user_id.to_string()).await.unwrap().email_verified);
+        assert!(!email_verification_repository.code_matches(&EMAIL.into(), &code).await.unwrap());
+        let events = outbox.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, DomainEvent::EmailVerified { user_id });
+
+        // Verifying again is a no-op, no second event.
+        verify_email.execute(VerifyEmailDTO { user_id, code: code.code }).await.unwrap();
+        assert_eq!(outbox.events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_reject_a_wrong_code() {
+        let outbox = InMemoryOutboxRepository::default();
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::with_outbox(outbox.clone())
+        );
+        let user_id = user(&users_repository).await;
+        let verify_email = VerifyEmail {
+            users_repository: users_repository.clone(),
+            email_verification_repository: barc!(
+                InMemoryEmailVerificationRepository::from_email_and_code(
+                    EMAIL.into(),
+                    EmailVerificationCode::from_str("ABC123".into()).unwrap()
+                )
+            ),
+        };
+
+        let result = verify_email.execute(VerifyEmailDTO { user_id, code: "XYZ789".into() }).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HearthError::Domain(INVALID_EMAIL_VERIFICATION_CODE_ERROR_CODE.into())
+        );
+        assert!(!users_repository.get(user_id.to_string()).await.unwrap().email_verified);
+        assert!(outbox.events().is_empty());
+    }
+}
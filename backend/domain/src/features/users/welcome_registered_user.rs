@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use macros::BArc;
+
+use crate::{
+    dtos::{ event::{ DomainEvent, EventDTO }, job::{ JobDTO, JobPayload } },
+    entities::jobs::Jobs,
+    error_codes::USER_NOT_FOUND_ERROR_CODE,
+    features::feature::Feature,
+    repositories::{ job_queue::JobQueue, users_repository::UsersRepository },
+};
+
+/// Queues the welcome email of every new account. The job is keyed on the
+/// event, so an event dispatched twice still sends a single email.
+pub struct WelcomeRegisteredUser {
+    pub users_repository: BArc<dyn UsersRepository>,
+    pub job_queue: BArc<dyn JobQueue>,
+}
+
+#[async_trait]
+impl Feature<EventDTO, ()> for WelcomeRegisteredUser {
+    async fn execute(&self, input: EventDTO) -> Result<(), HearthError> {
+        let DomainEvent::UserRegistered { user_id, username } = input.event else {
+            return Ok(());
+        };
+
+        // Nobody to welcome if the account is already gone.
+        let user = match self.users_repository.get(user_id.to_string()).await {
+            Ok(user) => user,
+            Err(e) if e == HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        self.job_queue.enqueue(JobDTO {
+            job_id: input.event_id,
+            ..Jobs::schedule(
+                JobPayload::SendWelcomeEmail { email: user.email, username },
+                input.occurred_at
+            )
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ NaiveDate, Utc };
+    use macros::{ BArc, barc };
+    use uuid::Uuid;
+
+    use crate::{
+        dtos::{
+            auth::CredentialsDTO,
+            event::{ DomainEvent, EventDTO },
+            job::JobPayload,
+            user::CreateUserDTO,
+        },
+        features::{ feature::Feature, users::welcome_registered_user::WelcomeRegisteredUser },
+        repositories::users_repository::UsersRepository,
+        test_utils::test_utils::{ InMemoryJobQueue, InMemoryUserRepository },
+    };
+
+    const EMAIL: &str = "john.smith@gmail.com";
+    const USERNAME: &str = "john.smith";
+
+    fn welcome(
+        users_repository: BArc<dyn UsersRepository>,
+        job_queue: &InMemoryJobQueue
+    ) -> WelcomeRegisteredUser {
+        WelcomeRegisteredUser { users_repository, job_queue: barc!(job_queue.clone()) }
+    }
+
+    fn registered(user_id: Uuid) -> EventDTO {
+        EventDTO::new(DomainEvent::UserRegistered { user_id, username: USERNAME.into() }, Utc::now())
+    }
+
+    #[tokio::test]
+    async fn should_queue_a_single_welcome_email_per_event() {
+        let user_id = Uuid::new_v4();
+        let users_repository: BArc<dyn UsersRepository> = barc!(
+            InMemoryUserRepository::from_existing_user(
+                CreateUserDTO {
+                    user_id,
+                    username: USERNAME.into(),
+                    email: EMAIL.into(),
+                    birthday: NaiveDate::from_ymd_opt(1991, 12, 29).unwrap(),
+                },
+                CredentialsDTO { user_id, password_hash: hasher::hash!("qwerty123") }
+            )
+        );
+        let job_queue = InMemoryJobQueue::default();
+        let welcome = welcome(users_repository, &job_queue);
+        let event = registered(user_id);
+
+        welcome.execute(event.clone()).await.unwrap();
+        welcome.execute(event.clone()).await.unwrap();
+
+        let jobs = job_queue.jobs.lock().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].job_id, event.event_id);
+        assert_eq!(jobs[0].run_at, event.occurred_at);
+        assert_eq!(jobs[0].payload, JobPayload::SendWelcomeEmail {
+            email: EMAIL.into(),
+            username: USERNAME.into(),
+        });
+    }
+
+    #[tokio::test]
+    async fn should_skip_other_events_and_accounts_already_gone() {
+        let job_queue = InMemoryJobQueue::default();
+        let welcome = welcome(barc!(InMemoryUserRepository::default()), &job_queue);
+
+        let verified = DomainEvent::EmailVerified { user_id: Uuid::new_v4() };
+        welcome.execute(EventDTO::new(verified, Utc::now())).await.unwrap();
+        welcome.execute(registered(Uuid::new_v4())).await.unwrap();
+
+        assert!(job_queue.jobs.lock().unwrap().is_empty());
+    }
+}
@@ -2,6 +2,8 @@ pub mod dtos;
 pub mod entities;
 pub mod error_codes;
 pub mod features;
+pub mod parsers;
+pub mod policies;
 pub mod repositories;
 
 #[cfg(test)]
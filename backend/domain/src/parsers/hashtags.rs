@@ -0,0 +1,150 @@
+use unicode_normalization::{ UnicodeNormalization, char::is_combining_mark };
+
+use crate::parsers::spans::{ excluded_ranges, is_excluded };
+
+pub const HASHTAG_MAX_LENGTH: usize = 100;
+
+const ZERO_WIDTH_NON_JOINER: char = '\u{200C}';
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hashtag {
+    /// Normalized form, used for storage and lookups.
+    pub tag: String,
+    /// Byte offset of the leading `#`.
+    pub start: usize,
+    /// Byte offset right after the last character of the tag.
+    pub end: usize,
+}
+
+/// NFKC + lowercase so that `#Café`, `#CAFÉ` and `#cafe\u{301}` share a timeline.
+pub fn normalize_hashtag(tag: &str) -> String {
+    tag.trim_start_matches('#').nfkc().collect::<String>().to_lowercase()
+}
+
+/// Extracts every hashtag of `text` in order of appearance, skipping URLs and
+/// code spans.
+pub fn extract_hashtags(text: &str) -> Vec<Hashtag> {
+    let excluded = excluded_ranges(text);
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut hashtags = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+
+        if c != '#' || is_excluded(&excluded, start) {
+            i += 1;
+            continue;
+        }
+
+        if i > 0 && !is_boundary(chars[i - 1].1) {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        while j < chars.len() && is_tag_char(chars[j].1) {
+            j += 1;
+        }
+
+        let end = chars.get(j).map(|(index, _)| *index).unwrap_or(text.len());
+        let body = &text[start + 1..end];
+        let body = body.trim_end_matches([ZERO_WIDTH_JOINER, ZERO_WIDTH_NON_JOINER]);
+
+        if is_valid_body(body) {
+            hashtags.push(Hashtag {
+                tag: normalize_hashtag(body),
+                start,
+                end: start + 1 + body.len(),
+            });
+        }
+
+        i = j.max(i + 1);
+    }
+
+    hashtags
+}
+
+/// Distinct normalized tags of `text`, in order of first appearance.
+pub fn unique_hashtags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = vec![];
+
+    for hashtag in extract_hashtags(text) {
+        if !tags.contains(&hashtag.tag) {
+            tags.push(hashtag.tag);
+        }
+    }
+
+    tags
+}
+
+fn is_boundary(previous: char) -> bool {
+    !(previous.is_alphanumeric() || previous == '_' || previous == '#' || previous == '&')
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() ||
+        c == '_' ||
+        is_combining_mark(c) ||
+        c == ZERO_WIDTH_JOINER ||
+        c == ZERO_WIDTH_NON_JOINER
+}
+
+fn is_valid_body(body: &str) -> bool {
+    !body.is_empty() &&
+        body.chars().count() <= HASHTAG_MAX_LENGTH &&
+        body.chars().any(|c| c.is_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ extract_hashtags, normalize_hashtag, unique_hashtags };
+
+    fn tags(text: &str) -> Vec<String> {
+        extract_hashtags(text)
+            .into_iter()
+            .map(|h| h.tag)
+            .collect()
+    }
+
+    #[test]
+    fn should_extract_hashtags_with_offsets() {
+        let text = "hello #Rust and #rust_lang!";
+        let hashtags = extract_hashtags(text);
+
+        assert_eq!(hashtags.len(), 2);
+        assert_eq!(hashtags[0].tag, "rust");
+        assert_eq!(&text[hashtags[0].start..hashtags[0].end], "#Rust");
+        assert_eq!(&text[hashtags[1].start..hashtags[1].end], "#rust_lang");
+    }
+
+    #[test]
+    fn should_support_unicode_hashtags() {
+        assert_eq!(tags("#日本語 #Café #हिन्दी"), vec!["日本語", "café", "हिन्दी"]);
+    }
+
+    #[test]
+    fn should_normalize_equivalent_forms() {
+        assert_eq!(normalize_hashtag("#Cafe\u{301}"), normalize_hashtag("CAFÉ"));
+        assert_eq!(normalize_hashtag("Ｒｕｓｔ"), "rust");
+    }
+
+    #[test]
+    fn should_ignore_hashtags_inside_words_urls_and_code() {
+        let text =
+            "foo#bar https://example.com/#anchor `#code` ```\n#fenced\n``` &#123; #real";
+        assert_eq!(tags(text), vec!["real"]);
+    }
+
+    #[test]
+    fn should_ignore_numeric_only_and_empty_hashtags() {
+        assert!(tags("#1 #2024 # #").is_empty());
+        assert_eq!(tags("#2024goals"), vec!["2024goals"]);
+    }
+
+    #[test]
+    fn should_deduplicate_unique_hashtags() {
+        assert_eq!(unique_hashtags("#a #A #b #a"), vec!["a", "b"]);
+    }
+}
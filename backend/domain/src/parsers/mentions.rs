@@ -0,0 +1,102 @@
+use crate::parsers::spans::{ excluded_ranges, is_excluded };
+
+pub const USERNAME_MIN_LENGTH: usize = 3;
+pub const USERNAME_MAX_LENGTH: usize = 42;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    /// Username as written, without the leading `@`.
+    pub username: String,
+    /// Byte offset of the leading `@`.
+    pub start: usize,
+    /// Byte offset right after the last character of the username.
+    pub end: usize,
+}
+
+/// Extracts every `@username` of `text` in order of appearance, skipping URLs,
+/// code spans and email addresses.
+pub fn extract_mentions(text: &str) -> Vec<Mention> {
+    let excluded = excluded_ranges(text);
+    let bytes = text.as_bytes();
+    let mut mentions = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'@' || is_excluded(&excluded, i) {
+            i += 1;
+            continue;
+        }
+
+        let previous = text[..i].chars().next_back();
+        if previous.is_some_and(|c| !is_boundary(c)) {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        while j < bytes.len() && is_username_byte(bytes[j]) {
+            j += 1;
+        }
+
+        // A sentence may end right after a mention: "thanks @john.smith."
+        let username = text[i + 1..j].trim_end_matches('.');
+        let end = i + 1 + username.len();
+
+        // "@john@example.com" is neither a mention nor an email we should touch.
+        let followed_by_at = bytes.get(end) == Some(&b'@');
+
+        if
+            !followed_by_at &&
+            (USERNAME_MIN_LENGTH..=USERNAME_MAX_LENGTH).contains(&username.len())
+        {
+            mentions.push(Mention { username: username.to_string(), start: i, end });
+        }
+
+        i = j.max(i + 1);
+    }
+
+    mentions
+}
+
+fn is_boundary(previous: char) -> bool {
+    !(previous.is_alphanumeric() || matches!(previous, '_' | '.' | '@' | '/'))
+}
+
+fn is_username_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'.'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_mentions;
+
+    fn usernames(text: &str) -> Vec<String> {
+        extract_mentions(text)
+            .into_iter()
+            .map(|m| m.username)
+            .collect()
+    }
+
+    #[test]
+    fn should_extract_mentions_with_offsets() {
+        let text = "héllo @John.Smith, meet @jane_doe.";
+        let mentions = extract_mentions(text);
+
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(&text[mentions[0].start..mentions[0].end], "@John.Smith");
+        assert_eq!(&text[mentions[1].start..mentions[1].end], "@jane_doe");
+    }
+
+    #[test]
+    fn should_ignore_emails_urls_and_code() {
+        let text =
+            "mail john@smith.com or @bob@example.com, see https://x.com/@alice `@carol` @dave";
+        assert_eq!(usernames(text), vec!["dave"]);
+    }
+
+    #[test]
+    fn should_ignore_too_short_or_too_long_usernames() {
+        let long = format!("@{}", "a".repeat(43));
+        assert!(usernames(&format!("@ab @ {}", long)).is_empty());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod hashtags;
+pub mod links;
+pub mod mentions;
+pub(crate) mod spans;
@@ -0,0 +1,117 @@
+use std::ops::Range;
+
+/// Byte ranges of `text` that must never be parsed for entities: inline code
+/// spans, fenced code blocks and URLs.
+pub(crate) fn excluded_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = code_ranges(text);
+    ranges.extend(url_ranges(text));
+    ranges.sort_by_key(|range| range.start);
+    ranges
+}
+
+pub(crate) fn is_excluded(ranges: &[Range<usize>], index: usize) -> bool {
+    ranges.iter().any(|range| range.contains(&index))
+}
+
+/// A run of N backticks opens a code span which is closed by the next run of
+/// exactly N backticks. Unclosed runs are treated as plain text.
+pub(crate) fn code_ranges(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut ranges = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'`' {
+            i += 1;
+            continue;
+        }
+
+        let open_start = i;
+        while i < bytes.len() && bytes[i] == b'`' {
+            i += 1;
+        }
+        let run = i - open_start;
+
+        let mut j = i;
+        let mut closed_at = None;
+        while j < bytes.len() {
+            if bytes[j] != b'`' {
+                j += 1;
+                continue;
+            }
+            let close_start = j;
+            while j < bytes.len() && bytes[j] == b'`' {
+                j += 1;
+            }
+            if j - close_start == run {
+                closed_at = Some(j);
+                break;
+            }
+        }
+
+        if let Some(end) = closed_at {
+            ranges.push(open_start..end);
+            i = end;
+        }
+    }
+
+    ranges
+}
+
+fn url_ranges(text: &str) -> Vec<Range<usize>> {
+    tokens(text)
+        .into_iter()
+        .filter(|range| looks_like_url(&text[range.clone()]))
+        .collect()
+}
+
+/// Byte ranges of the whitespace separated tokens of `text`.
+pub(crate) fn tokens(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = None;
+
+    for (index, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(index),
+            (true, Some(token_start)) => {
+                ranges.push(token_start..index);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    ranges
+}
+
+fn looks_like_url(token: &str) -> bool {
+    let lower = token.to_lowercase();
+    lower.contains("://") || lower.starts_with("www.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::excluded_ranges;
+
+    #[test]
+    fn should_exclude_inline_code_and_fences() {
+        let text = "a `#b` c ```\n#d\n``` e";
+        let ranges = excluded_ranges(text);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(&text[ranges[0].clone()], "`#b`");
+        assert_eq!(&text[ranges[1].clone()], "```\n#d\n```");
+    }
+
+    #[test]
+    fn should_not_exclude_unclosed_backticks() {
+        assert!(excluded_ranges("a `#b c").is_empty());
+    }
+
+    #[test]
+    fn should_exclude_urls() {
+        let text = "see https://example.com/#top and www.example.com/#x";
+        let ranges = excluded_ranges(text);
+        assert_eq!(&text[ranges[0].clone()], "https://example.com/#top");
+        assert_eq!(&text[ranges[1].clone()], "www.example.com/#x");
+    }
+}
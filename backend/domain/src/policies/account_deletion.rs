@@ -0,0 +1,33 @@
+use chrono::{ DateTime, Duration, Utc };
+
+#[derive(Debug, Clone)]
+pub struct AccountDeletionPolicy {
+    /// Days a deactivated account can still be reactivated before being purged.
+    pub grace_period_days: u32,
+    /// Days a purged account's username stays unavailable, so it can't be impersonated.
+    pub username_hold_days: u32,
+}
+
+impl AccountDeletionPolicy {
+    pub fn purge_at(&self, deactivated_at: DateTime<Utc>) -> DateTime<Utc> {
+        deactivated_at + Duration::days(self.grace_period_days as i64)
+    }
+
+    /// Whether a deletion requested at `deactivated_at` can still be cancelled.
+    pub fn within_grace_period(
+        &self,
+        deactivated_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>
+    ) -> bool {
+        deactivated_at.is_some_and(|deactivated_at| self.purge_at(deactivated_at) > now)
+    }
+}
+
+impl Default for AccountDeletionPolicy {
+    fn default() -> Self {
+        Self {
+            grace_period_days: 30,
+            username_hold_days: 90,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+/// Age rules derived from the birthday given at signup.
+#[derive(Debug, Clone)]
+pub struct AgePolicy {
+    pub minimum_age: u32,
+    /// Older birthdays are considered typos or made up.
+    pub maximum_age: u32,
+    /// Below this age, or when not logged in, sensitive posts are withheld.
+    pub sensitive_content_age: u32,
+}
+
+impl Default for AgePolicy {
+    fn default() -> Self {
+        Self {
+            minimum_age: 13,
+            maximum_age: 120,
+            sensitive_content_age: 18,
+        }
+    }
+}
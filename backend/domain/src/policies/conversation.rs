@@ -0,0 +1,22 @@
+/// Limits applied to direct messages.
+#[derive(Debug, Clone)]
+pub struct ConversationPolicy {
+    /// Creator included.
+    pub max_participants: usize,
+    pub max_attachments_per_message: usize,
+    /// One envelope per recipient device, the sender's other devices included.
+    pub max_envelopes_per_message: usize,
+    /// Base64 encoded ciphertext of a single envelope.
+    pub max_ciphertext_length: usize,
+}
+
+impl Default for ConversationPolicy {
+    fn default() -> Self {
+        Self {
+            max_participants: 8,
+            max_attachments_per_message: 4,
+            max_envelopes_per_message: 64,
+            max_ciphertext_length: 65_536,
+        }
+    }
+}
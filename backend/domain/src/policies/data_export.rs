@@ -0,0 +1,16 @@
+#[derive(Debug, Clone)]
+pub struct DataExportPolicy {
+    /// Days an archive can be downloaded before it is deleted.
+    pub ttl_days: u32,
+    /// Public url of the server, emailed download links point below it.
+    pub download_base_url: String,
+}
+
+impl Default for DataExportPolicy {
+    fn default() -> Self {
+        Self {
+            ttl_days: 3,
+            download_base_url: "http://localhost:1337".into(),
+        }
+    }
+}
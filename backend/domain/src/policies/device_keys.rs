@@ -0,0 +1,16 @@
+/// Limits applied to the end-to-end encryption key directory.
+#[derive(Debug, Clone)]
+pub struct DeviceKeysPolicy {
+    pub max_devices_per_user: usize,
+    /// Unclaimed one-time prekeys kept per device.
+    pub max_one_time_prekeys: u64,
+}
+
+impl Default for DeviceKeysPolicy {
+    fn default() -> Self {
+        Self {
+            max_devices_per_user: 10,
+            max_one_time_prekeys: 100,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/// Limits applied to keyword filters.
+#[derive(Debug, Clone)]
+pub struct FilterPolicy {
+    pub max_filters_per_user: usize,
+}
+
+impl Default for FilterPolicy {
+    fn default() -> Self {
+        Self {
+            max_filters_per_user: 100,
+        }
+    }
+}
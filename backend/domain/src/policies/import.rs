@@ -0,0 +1,17 @@
+#[derive(Debug, Clone)]
+pub struct ImportPolicy {
+    /// Uploads are refused once past it. The worker reads archives in memory,
+    /// media included.
+    pub max_archive_bytes: u64,
+    /// Same limit as posts written on Hearth.
+    pub max_content_chars: usize,
+}
+
+impl Default for ImportPolicy {
+    fn default() -> Self {
+        Self {
+            max_archive_bytes: 512 * 1024 * 1024,
+            max_content_chars: 500,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::Duration;
+
+#[derive(Debug, Clone)]
+pub struct JobPolicy {
+    /// Runs before a failing job is dead-lettered.
+    pub max_attempts: u32,
+    pub batch_size: u64,
+    /// How long a claimed job is hidden from the other workers.
+    pub lease: Duration,
+    /// Delay before the first retry, doubled after every failure.
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for JobPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            batch_size: 20,
+            lease: Duration::minutes(5),
+            base_backoff: Duration::seconds(30),
+            max_backoff: Duration::hours(1),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::Duration;
+
+/// How long an unfurled page is reused before being fetched again.
+#[derive(Debug, Clone)]
+pub struct LinkPreviewPolicy {
+    pub cache_ttl: Duration,
+}
+
+impl Default for LinkPreviewPolicy {
+    fn default() -> Self {
+        Self {
+            cache_ttl: Duration::hours(24),
+        }
+    }
+}
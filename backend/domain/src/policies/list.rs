@@ -0,0 +1,15 @@
+/// Limits applied to user curated lists.
+#[derive(Debug, Clone)]
+pub struct ListPolicy {
+    pub max_lists_per_owner: usize,
+    pub max_members_per_list: usize,
+}
+
+impl Default for ListPolicy {
+    fn default() -> Self {
+        Self {
+            max_lists_per_owner: 100,
+            max_members_per_list: 500,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+/// Limits applied to uploads.
+#[derive(Debug, Clone)]
+pub struct MediaPolicy {
+    pub allowed_mime_types: Vec<String>,
+    pub max_upload_bytes: u64,
+    pub quota_bytes: u64,
+    pub max_attachments_per_post: usize,
+}
+
+impl Default for MediaPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_mime_types: ["image/jpeg", "image/png", "image/gif", "image/webp"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            max_upload_bytes: 10 * 1024 * 1024,
+            quota_bytes: 1024 * 1024 * 1024,
+            max_attachments_per_post: 4,
+        }
+    }
+}
+
+impl MediaPolicy {
+    pub fn is_allowed(&self, mime_type: &str) -> bool {
+        self.allowed_mime_types.iter().any(|allowed| allowed == mime_type)
+    }
+}
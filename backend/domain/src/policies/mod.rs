@@ -0,0 +1,20 @@
+pub mod account_deletion;
+pub mod age;
+pub mod conversation;
+pub mod data_export;
+pub mod device_keys;
+pub mod filter;
+pub mod import;
+pub mod job;
+pub mod link_preview;
+pub mod list;
+pub mod media;
+pub mod oauth;
+pub mod oidc;
+pub mod passkey;
+pub mod personal_access_token;
+pub mod poll;
+pub mod rate_limit;
+pub mod signup;
+pub mod trending;
+pub mod two_factor;
@@ -0,0 +1,18 @@
+/// Lifetimes and limits of the OAuth2 authorization server.
+#[derive(Debug, Clone)]
+pub struct OAuthPolicy {
+    /// Time to exchange an authorization code, RFC 6749 recommends at most ten minutes.
+    pub code_ttl_seconds: u64,
+    pub access_token_ttl_seconds: u64,
+    pub max_apps_per_user: usize,
+}
+
+impl Default for OAuthPolicy {
+    fn default() -> Self {
+        Self {
+            code_ttl_seconds: 300,
+            access_token_ttl_seconds: 3600 * 24,
+            max_apps_per_user: 25,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/// A "sign in with" provider, `name` is the slug used in the routes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcProvider {
+    pub name: String,
+    /// Discovery is fetched from `{issuer}/.well-known/openid-configuration`.
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OidcPolicy {
+    pub providers: Vec<OidcProvider>,
+    /// Time to come back from the provider.
+    pub state_ttl_seconds: u64,
+    /// Time to pick a username once the provider vouched for a new user.
+    pub signup_ttl_seconds: u64,
+    pub clock_skew_seconds: i64,
+}
+
+impl OidcPolicy {
+    pub fn provider(&self, name: &str) -> Option<&OidcProvider> {
+        self.providers.iter().find(|provider| provider.name == name)
+    }
+}
+
+impl Default for OidcPolicy {
+    fn default() -> Self {
+        Self {
+            providers: vec![],
+            state_ttl_seconds: 600,
+            signup_ttl_seconds: 1800,
+            clock_skew_seconds: 60,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+/// WebAuthn relying party settings, `rp_id` is the domain passkeys are bound to.
+#[derive(Debug, Clone)]
+pub struct PasskeyPolicy {
+    pub rp_id: String,
+    pub rp_name: String,
+    /// Origins the ceremonies may come from, the Tauri client included.
+    pub origins: Vec<String>,
+    pub challenge_ttl_seconds: u64,
+    /// Refuses authenticators that only tested user presence.
+    pub require_user_verification: bool,
+    pub max_passkeys_per_user: usize,
+}
+
+impl Default for PasskeyPolicy {
+    fn default() -> Self {
+        Self {
+            rp_id: "localhost".into(),
+            rp_name: "Hearth".into(),
+            origins: vec!["http://localhost:1420".into(), "tauri://localhost".into()],
+            challenge_ttl_seconds: 300,
+            require_user_verification: false,
+            max_passkeys_per_user: 10,
+        }
+    }
+}
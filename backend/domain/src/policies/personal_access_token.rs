@@ -0,0 +1,15 @@
+#[derive(Debug, Clone)]
+pub struct PersonalAccessTokenPolicy {
+    /// Lifetime of tokens created without `expires_in_days`.
+    pub default_ttl_days: u32,
+    pub max_tokens_per_user: usize,
+}
+
+impl Default for PersonalAccessTokenPolicy {
+    fn default() -> Self {
+        Self {
+            default_ttl_days: 30,
+            max_tokens_per_user: 50,
+        }
+    }
+}
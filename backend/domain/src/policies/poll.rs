@@ -0,0 +1,21 @@
+/// Shape and lifetime limits of polls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollPolicy {
+    pub min_options: usize,
+    pub max_options: usize,
+    pub max_option_length: usize,
+    pub min_duration_seconds: i64,
+    pub max_duration_seconds: i64,
+}
+
+impl Default for PollPolicy {
+    fn default() -> Self {
+        Self {
+            min_options: 2,
+            max_options: 4,
+            max_option_length: 50,
+            min_duration_seconds: 5 * 60,
+            max_duration_seconds: 7 * 24 * 60 * 60,
+        }
+    }
+}
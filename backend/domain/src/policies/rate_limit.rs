@@ -0,0 +1,56 @@
+use crate::dtos::rate_limit::RateLimitScope;
+
+/// A bucket holding up to `capacity` tokens, refilled with one token every
+/// `refill_seconds`. Each request takes a token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucket {
+    pub capacity: u32,
+    pub refill_seconds: u32,
+}
+
+/// What identifies the caller of a scope.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitKey {
+    Ip,
+    /// Falls back to the ip when the request is anonymous.
+    User,
+}
+
+/// Buckets applied per scope. Anonymous routes are keyed by ip, posting is
+/// keyed by user so a user cannot get around it by switching networks.
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    pub signup: TokenBucket,
+    pub login: TokenBucket,
+    pub verification_code: TokenBucket,
+    pub posting: TokenBucket,
+}
+
+impl RateLimitPolicy {
+    pub fn bucket(&self, scope: RateLimitScope) -> TokenBucket {
+        match scope {
+            RateLimitScope::Signup => self.signup,
+            RateLimitScope::Login => self.login,
+            RateLimitScope::VerificationCode => self.verification_code,
+            RateLimitScope::Posting => self.posting,
+        }
+    }
+
+    pub fn key(&self, scope: RateLimitScope) -> RateLimitKey {
+        match scope {
+            RateLimitScope::Posting => RateLimitKey::User,
+            _ => RateLimitKey::Ip,
+        }
+    }
+}
+
+impl Default for RateLimitPolicy {
+    fn default() -> Self {
+        Self {
+            signup: TokenBucket { capacity: 5, refill_seconds: 720 },
+            login: TokenBucket { capacity: 10, refill_seconds: 60 },
+            verification_code: TokenBucket { capacity: 3, refill_seconds: 300 },
+            posting: TokenBucket { capacity: 30, refill_seconds: 10 },
+        }
+    }
+}
@@ -0,0 +1,63 @@
+/// Throwaway inbox providers refused at signup unless the deployment
+/// configures its own list.
+pub const DISPOSABLE_EMAIL_DOMAINS: &[&str] = &[
+    "10minutemail.com",
+    "dispostable.com",
+    "getnada.com",
+    "guerrillamail.com",
+    "mailinator.com",
+    "maildrop.cc",
+    "sharklasers.com",
+    "temp-mail.org",
+    "tempmail.com",
+    "throwawaymail.com",
+    "trashmail.com",
+    "yopmail.com",
+];
+
+/// What to do with the `+tag` part of `john+tag@example.com`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlusAddressing {
+    Keep,
+    /// `john+a@example.com` and `john+b@example.com` are the same account.
+    Strip,
+}
+
+/// Which addresses may register, domains also cover their subdomains.
+#[derive(Debug, Clone)]
+pub struct EmailPolicy {
+    pub blocked_domains: Vec<String>,
+    /// When not empty, only these domains may register.
+    pub allowed_domains: Vec<String>,
+    pub plus_addressing: PlusAddressing,
+}
+
+impl Default for EmailPolicy {
+    fn default() -> Self {
+        Self {
+            blocked_domains: DISPOSABLE_EMAIL_DOMAINS.iter()
+                .map(|domain| domain.to_string())
+                .collect(),
+            allowed_domains: vec![],
+            plus_addressing: PlusAddressing::Strip,
+        }
+    }
+}
+
+/// Hashcash style challenge, solved by finding a suffix whose SHA-256 hash
+/// with the challenge prefix starts with `difficulty` zero bits. Each extra
+/// bit doubles the average work of the client.
+#[derive(Debug, Clone)]
+pub struct ProofOfWorkPolicy {
+    pub difficulty: u8,
+    pub ttl_seconds: i64,
+}
+
+impl Default for ProofOfWorkPolicy {
+    fn default() -> Self {
+        Self {
+            difficulty: 20,
+            ttl_seconds: 300,
+        }
+    }
+}
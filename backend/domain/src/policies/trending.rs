@@ -0,0 +1,72 @@
+use chrono::{ DateTime, Utc };
+
+/// Hashtag uses are counted in fixed time buckets. A tag's score is the sum of
+/// its bucket counts over the rolling window, each weighted by an exponential
+/// decay on the bucket's age, so recent bursts outrank steady chatter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrendingPolicy {
+    pub bucket_seconds: i64,
+    pub window_buckets: i64,
+    pub half_life_seconds: i64,
+}
+
+impl Default for TrendingPolicy {
+    fn default() -> Self {
+        Self {
+            bucket_seconds: 60 * 60,
+            window_buckets: 24,
+            half_life_seconds: 6 * 60 * 60,
+        }
+    }
+}
+
+impl TrendingPolicy {
+    pub fn bucket_of(&self, at: DateTime<Utc>) -> i64 {
+        at.timestamp().div_euclid(self.bucket_seconds)
+    }
+
+    /// How long a bucket must be kept around to remain part of the window.
+    pub fn retention_seconds(&self) -> i64 {
+        self.bucket_seconds * (self.window_buckets + 1)
+    }
+
+    /// Buckets of the window ending at `at` with their decay weight, newest first.
+    pub fn weighted_buckets(&self, at: DateTime<Utc>) -> Vec<(i64, f64)> {
+        let current = self.bucket_of(at);
+
+        (0..self.window_buckets)
+            .map(|age| {
+                let elapsed = (age * self.bucket_seconds) as f64;
+                let weight = (0.5f64).powf(elapsed / (self.half_life_seconds as f64));
+                (current - age, weight)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ Duration, TimeZone, Utc };
+
+    use super::TrendingPolicy;
+
+    #[test]
+    fn should_halve_weight_every_half_life() {
+        let policy = TrendingPolicy::default();
+        let weights = policy.weighted_buckets(Utc::now());
+
+        assert_eq!(weights.len(), 24);
+        assert_eq!(weights[0].1, 1.0);
+        assert!((weights[6].1 - 0.5).abs() < f64::EPSILON);
+        assert!((weights[12].1 - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn should_place_times_in_consecutive_buckets() {
+        let policy = TrendingPolicy::default();
+        let at = Utc.with_ymd_and_hms(2025, 1, 1, 10, 30, 0).unwrap();
+
+        assert_eq!(policy.bucket_of(at) + 1, policy.bucket_of(at + Duration::hours(1)));
+        assert_eq!(policy.weighted_buckets(at)[1].0, policy.bucket_of(at) - 1);
+    }
+}
@@ -0,0 +1,29 @@
+/// TOTP parameters (RFC 6238), the defaults are the ones every
+/// authenticator app supports.
+#[derive(Debug, Clone)]
+pub struct TwoFactorPolicy {
+    pub issuer: String,
+    pub period_seconds: i64,
+    pub digits: u32,
+    /// Steps accepted on each side of the current one, for clock drift.
+    pub drift_steps: i64,
+    pub recovery_codes: usize,
+    /// Time to enter the code once the password matched.
+    pub login_challenge_ttl_seconds: u64,
+    /// Wrong codes after which the password is required again.
+    pub login_challenge_max_failures: u32,
+}
+
+impl Default for TwoFactorPolicy {
+    fn default() -> Self {
+        Self {
+            issuer: "Hearth".into(),
+            period_seconds: 30,
+            digits: 6,
+            drift_steps: 1,
+            recovery_codes: 10,
+            login_challenge_ttl_seconds: 300,
+            login_challenge_max_failures: 5,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::import::{ ArchivedPostDTO, ImportSource };
+
+/// Reads the posts out of another platform's archive. Reposts and posts that
+/// weren't public are left out.
+#[async_trait]
+pub trait ArchiveReader: Send + Sync {
+    async fn read(
+        &self,
+        source: ImportSource,
+        bytes: Vec<u8>
+    ) -> Result<Vec<ArchivedPostDTO>, HearthError>;
+}
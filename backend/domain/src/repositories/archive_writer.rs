@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::data_export::ArchiveFileDTO;
+
+/// Packs files into a single ZIP archive.
+#[async_trait]
+pub trait ArchiveWriter: Send + Sync {
+    async fn write(&self, files: Vec<ArchiveFileDTO>) -> Result<Vec<u8>, HearthError>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait BlocksRepository: Send + Sync {
+    async fn block(&self, blocker_id: &Uuid, blocked_id: &Uuid) -> Result<(), HearthError>;
+    async fn unblock(&self, blocker_id: &Uuid, blocked_id: &Uuid) -> Result<(), HearthError>;
+    /// Whether either user blocks the other.
+    async fn is_blocked_between(&self, user_a: &Uuid, user_b: &Uuid) -> Result<bool, HearthError>;
+}
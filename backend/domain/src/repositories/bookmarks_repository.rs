@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::{
+    bookmark::{ BookmarkCollectionDTO, BookmarkDTO },
+    pagination::PositionCursor,
+};
+
+/// Bookmarks are private: every call is scoped to their owner.
+#[async_trait]
+pub trait BookmarksRepository: Send + Sync {
+    /// Fails with BOOKMARK_COLLECTION_NAME_TAKEN when the owner already has a
+    /// collection with that name.
+    async fn create_collection(&self, collection: BookmarkCollectionDTO) -> Result<(), HearthError>;
+    async fn get_collection(
+        &self,
+        owner_id: &Uuid,
+        collection_id: &Uuid
+    ) -> Result<BookmarkCollectionDTO, HearthError>;
+    async fn list_collections(&self, owner_id: &Uuid) -> Result<Vec<BookmarkCollectionDTO>, HearthError>;
+
+    /// Saves the post at the top of `collection_id`, moving it there if it was
+    /// already bookmarked elsewhere.
+    async fn save(
+        &self,
+        owner_id: &Uuid,
+        post_id: &Uuid,
+        collection_id: Option<Uuid>,
+        at: DateTime<Utc>
+    ) -> Result<BookmarkDTO, HearthError>;
+    async fn remove(&self, owner_id: &Uuid, post_id: &Uuid) -> Result<(), HearthError>;
+    /// Moves the bookmark to `index` within its collection, clamped to its size.
+    async fn move_to(&self, owner_id: &Uuid, post_id: &Uuid, index: usize) -> Result<(), HearthError>;
+    /// Bookmarks of a collection ordered by position, strictly after `cursor`.
+    /// Posts are not loaded.
+    async fn list(
+        &self,
+        owner_id: &Uuid,
+        collection_id: Option<Uuid>,
+        cursor: Option<PositionCursor>,
+        limit: u64
+    ) -> Result<Vec<BookmarkDTO>, HearthError>;
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::{
+    conversation::{ ConversationDTO, MessageDTO, ReadPositionDTO },
+    pagination::TimelineCursor,
+};
+
+#[async_trait]
+pub trait ConversationsRepository: Send + Sync {
+    async fn create(&self, conversation: ConversationDTO) -> Result<(), HearthError>;
+    async fn get(&self, conversation_id: &Uuid) -> Result<ConversationDTO, HearthError>;
+    /// The one-to-one conversation between both users, if any.
+    async fn find_direct(
+        &self,
+        user_a: &Uuid,
+        user_b: &Uuid
+    ) -> Result<Option<ConversationDTO>, HearthError>;
+    /// Conversations of `user_id`, most recently active first.
+    async fn list_for_user(&self, user_id: &Uuid) -> Result<Vec<ConversationDTO>, HearthError>;
+
+    /// Stores the message and bumps the conversation's `last_message_at`.
+    async fn add_message(&self, message: MessageDTO) -> Result<(), HearthError>;
+    async fn get_message(
+        &self,
+        conversation_id: &Uuid,
+        message_id: &Uuid
+    ) -> Result<MessageDTO, HearthError>;
+    /// Looks a message up by id alone, e.g. when it is reported.
+    async fn find_message(&self, message_id: &Uuid) -> Result<Option<MessageDTO>, HearthError>;
+    /// Messages of a conversation, newest first, strictly after `cursor`.
+    async fn messages(
+        &self,
+        conversation_id: &Uuid,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<MessageDTO>, HearthError>;
+
+    async fn set_read_position(
+        &self,
+        conversation_id: &Uuid,
+        position: ReadPositionDTO
+    ) -> Result<(), HearthError>;
+}
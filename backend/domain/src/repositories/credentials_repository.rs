@@ -1,4 +1,10 @@
 use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::auth::CredentialsDTO;
 
 #[async_trait]
-pub trait CredentialsRepository: Send + Sync {}
+pub trait CredentialsRepository: Send + Sync {
+    async fn get(&self, user_id: &Uuid) -> Result<Option<CredentialsDTO>, HearthError>;
+}
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::data_export::DataExportDTO;
+
+#[async_trait]
+pub trait DataExportsRepository: Send + Sync {
+    async fn create(&self, export: DataExportDTO) -> Result<(), HearthError>;
+    async fn get(&self, export_id: &Uuid) -> Result<DataExportDTO, HearthError>;
+    /// The user's export still pending, or ready and not yet expired at `at`.
+    async fn find_active(
+        &self,
+        user_id: &Uuid,
+        at: DateTime<Utc>
+    ) -> Result<Option<DataExportDTO>, HearthError>;
+    /// Oldest first.
+    async fn list_pending(&self, limit: u64) -> Result<Vec<DataExportDTO>, HearthError>;
+    async fn mark_ready(
+        &self,
+        export_id: &Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>
+    ) -> Result<(), HearthError>;
+    async fn list_expired(
+        &self,
+        at: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<DataExportDTO>, HearthError>;
+    async fn delete(&self, export_id: &Uuid) -> Result<(), HearthError>;
+}
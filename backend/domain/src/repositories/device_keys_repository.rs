@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::device_keys::{ DeviceKeysDTO, OneTimePrekeyDTO };
+
+#[async_trait]
+pub trait DeviceKeysRepository: Send + Sync {
+    /// Inserts or replaces the keys of the device, its one-time prekeys are kept.
+    async fn upsert(&self, keys: DeviceKeysDTO) -> Result<(), HearthError>;
+    async fn get(&self, user_id: &Uuid, device_id: &Uuid) -> Result<Option<DeviceKeysDTO>, HearthError>;
+    async fn list_for_user(&self, user_id: &Uuid) -> Result<Vec<DeviceKeysDTO>, HearthError>;
+
+    /// Prekeys whose id is already known for the device are ignored.
+    async fn add_one_time_prekeys(
+        &self,
+        user_id: &Uuid,
+        device_id: &Uuid,
+        prekeys: Vec<OneTimePrekeyDTO>
+    ) -> Result<(), HearthError>;
+    async fn count_one_time_prekeys(&self, user_id: &Uuid, device_id: &Uuid) -> Result<u64, HearthError>;
+    /// Removes and returns one prekey of the device, a prekey is never handed out twice.
+    async fn claim_one_time_prekey(
+        &self,
+        user_id: &Uuid,
+        device_id: &Uuid
+    ) -> Result<Option<OneTimePrekeyDTO>, HearthError>;
+}
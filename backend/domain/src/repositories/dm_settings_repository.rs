@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::conversation::DmPermission;
+
+#[async_trait]
+pub trait DmSettingsRepository: Send + Sync {
+    /// Falls back to `DmPermission::default()` for users who never changed it.
+    async fn get(&self, user_id: &Uuid) -> Result<DmPermission, HearthError>;
+    async fn set(&self, user_id: &Uuid, permission: DmPermission) -> Result<(), HearthError>;
+}
@@ -6,7 +6,11 @@ use errors::HearthError;
 pub trait EmailSenderRepository: Send + Sync {
     async fn send_verify_email(
         &self,
-        email: &String,
+        email: &str,
         code: &EmailVerificationCode,
     ) -> Result<(), HearthError>;
+
+    async fn send_data_export_link(&self, email: &str, link: &str) -> Result<(), HearthError>;
+
+    async fn send_welcome_email(&self, email: &str, username: &str) -> Result<(), HearthError>;
 }
@@ -11,4 +11,6 @@ pub trait EmailVerificationRepository: Send + Sync {
         email: &String,
         code: &EmailVerificationCode,
     ) -> Result<bool, HearthError>;
+
+    async fn delete(&self, email: &String) -> Result<(), HearthError>;
 }
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::conversation::EnvelopeDTO;
+
+#[async_trait]
+pub trait EnvelopesRepository: Send + Sync {
+    /// Stores the envelopes of one message and bumps the conversation's `last_message_at`.
+    async fn add(&self, envelopes: Vec<EnvelopeDTO>) -> Result<(), HearthError>;
+    /// Envelopes waiting for the device, oldest first.
+    async fn pending(
+        &self,
+        user_id: &Uuid,
+        device_id: &Uuid,
+        limit: u64
+    ) -> Result<Vec<EnvelopeDTO>, HearthError>;
+    /// Returns whether an envelope was removed.
+    async fn delete(&self, user_id: &Uuid, device_id: &Uuid, message_id: &Uuid) -> Result<bool, HearthError>;
+}
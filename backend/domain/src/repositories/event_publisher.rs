@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::event::EventDTO;
+
+/// Announces events to the other processes, delivery is at least once.
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    async fn publish(&self, event: &EventDTO) -> Result<(), HearthError>;
+}
@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait FollowsRepository: Send + Sync {
+    async fn is_following(&self, follower_id: &Uuid, followee_id: &Uuid) -> Result<bool, HearthError>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::import::{ ImportSource, ImportedPostDTO };
+
+#[async_trait]
+pub trait ImportedPostsRepository: Send + Sync {
+    async fn create(&self, imported: ImportedPostDTO) -> Result<(), HearthError>;
+    /// The ones among `external_ids` the user already imported from `source`.
+    async fn find_imported(
+        &self,
+        user_id: &Uuid,
+        source: ImportSource,
+        external_ids: &[String]
+    ) -> Result<Vec<String>, HearthError>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::import::{ ImportDTO, ImportReportDTO };
+
+#[async_trait]
+pub trait ImportsRepository: Send + Sync {
+    async fn create(&self, import: ImportDTO) -> Result<(), HearthError>;
+    async fn get(&self, import_id: &Uuid) -> Result<ImportDTO, HearthError>;
+    async fn mark_done(&self, import_id: &Uuid, report: &ImportReportDTO) -> Result<(), HearthError>;
+    async fn mark_failed(&self, import_id: &Uuid, error: &str) -> Result<(), HearthError>;
+}
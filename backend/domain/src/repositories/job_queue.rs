@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::job::JobDTO;
+
+#[async_trait]
+pub trait JobQueue: Send + Sync {
+    /// Enqueuing a job id that is already queued does nothing.
+    async fn enqueue(&self, job: JobDTO) -> Result<(), HearthError>;
+    /// Leases up to `limit` jobs due at `at` until `lease_until`, skipping the
+    /// ones another worker holds. A job whose worker died is due again once
+    /// the lease is over.
+    async fn claim(
+        &self,
+        at: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<JobDTO>, HearthError>;
+    async fn complete(&self, job_id: &Uuid) -> Result<(), HearthError>;
+    async fn retry(
+        &self,
+        job_id: &Uuid,
+        attempts: u32,
+        run_at: DateTime<Utc>,
+        error: &str
+    ) -> Result<(), HearthError>;
+    /// Keeps the job for inspection, it is never claimed again.
+    async fn dead_letter(
+        &self,
+        job_id: &Uuid,
+        attempts: u32,
+        error: &str,
+        at: DateTime<Utc>
+    ) -> Result<(), HearthError>;
+}
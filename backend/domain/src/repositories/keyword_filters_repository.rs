@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::filter::KeywordFilterDTO;
+
+/// Filters are private: every call is scoped to their owner.
+#[async_trait]
+pub trait KeywordFiltersRepository: Send + Sync {
+    async fn create(&self, filter: KeywordFilterDTO) -> Result<(), HearthError>;
+    /// Every filter of the owner, expired ones included, oldest first.
+    async fn list(&self, owner_id: &Uuid) -> Result<Vec<KeywordFilterDTO>, HearthError>;
+    async fn delete(&self, owner_id: &Uuid, filter_id: &Uuid) -> Result<(), HearthError>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::link_preview::LinkPreviewDTO;
+
+#[async_trait]
+pub trait LinkPreviewsRepository: Send + Sync {
+    async fn get(&self, url: &str) -> Result<Option<LinkPreviewDTO>, HearthError>;
+
+    /// Inserts or refreshes the cached preview of `preview.url`.
+    async fn save(&self, preview: LinkPreviewDTO) -> Result<(), HearthError>;
+
+    async fn attach(&self, post_id: &Uuid, url: &str) -> Result<(), HearthError>;
+}
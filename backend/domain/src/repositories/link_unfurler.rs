@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::link_preview::LinkPreviewDTO;
+
+#[async_trait]
+pub trait LinkUnfurler: Send + Sync {
+    /// Fetches the page behind `url` and reads its metadata. Implementations
+    /// must refuse to reach private networks.
+    async fn unfurl(&self, url: &str) -> Result<LinkPreviewDTO, HearthError>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::oidc::LinkedIdentityDTO;
+
+#[async_trait]
+pub trait LinkedIdentitiesRepository: Send + Sync {
+    async fn get(&self, provider: &str, subject: &str) -> Result<Option<LinkedIdentityDTO>, HearthError>;
+    async fn create(&self, identity: LinkedIdentityDTO) -> Result<(), HearthError>;
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::list::{ ListDTO, ListMemberDTO };
+
+#[async_trait]
+pub trait ListsRepository: Send + Sync {
+    async fn create(&self, list: ListDTO) -> Result<(), HearthError>;
+    async fn get(&self, list_id: &Uuid) -> Result<ListDTO, HearthError>;
+    async fn update(&self, list: ListDTO) -> Result<(), HearthError>;
+    async fn delete(&self, list_id: &Uuid) -> Result<(), HearthError>;
+    async fn list_by_owner(&self, owner_id: &Uuid) -> Result<Vec<ListDTO>, HearthError>;
+
+    /// Returns false when the user already was a member.
+    async fn add_member(
+        &self,
+        list_id: &Uuid,
+        member: ListMemberDTO,
+        at: DateTime<Utc>
+    ) -> Result<bool, HearthError>;
+    async fn remove_member(&self, list_id: &Uuid, member_id: &Uuid) -> Result<(), HearthError>;
+    async fn members(&self, list_id: &Uuid) -> Result<Vec<ListMemberDTO>, HearthError>;
+
+    async fn subscribe(&self, list_id: &Uuid, user_id: &Uuid) -> Result<(), HearthError>;
+    async fn unsubscribe(&self, list_id: &Uuid, user_id: &Uuid) -> Result<(), HearthError>;
+    /// Drops every subscription, used when a list becomes private.
+    async fn remove_subscribers(&self, list_id: &Uuid) -> Result<(), HearthError>;
+    async fn subscriptions(&self, user_id: &Uuid) -> Result<Vec<ListDTO>, HearthError>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+/// Logins waiting for their second factor, keyed by the hash of the token
+/// handed to the client once the password matched.
+#[async_trait]
+pub trait LoginChallengesRepository: Send + Sync {
+    async fn create(&self, token: &str, user_id: &Uuid, ttl_seconds: u64) -> Result<(), HearthError>;
+    async fn get_user_id(&self, token: &str) -> Result<Option<Uuid>, HearthError>;
+    /// Counts a wrong code against the challenge, returns the count so far.
+    async fn record_failure(&self, token: &str, ttl_seconds: u64) -> Result<u32, HearthError>;
+    async fn delete(&self, token: &str) -> Result<(), HearthError>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::media::ProcessedMediaDTO;
+
+#[async_trait]
+pub trait MediaProcessor: Send + Sync {
+    /// Mime type detected from the content itself, if recognized.
+    fn sniff_mime_type(&self, bytes: &[u8]) -> Option<String>;
+
+    /// Strips metadata and produces the stored variants and placeholder.
+    async fn process(&self, bytes: Vec<u8>) -> Result<ProcessedMediaDTO, HearthError>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::media::MediaDTO;
+
+#[async_trait]
+pub trait MediaRepository: Send + Sync {
+    async fn create(&self, media: MediaDTO) -> Result<(), HearthError>;
+    /// Stores the outcome of processing: status, placeholder and variants.
+    async fn update(&self, media: MediaDTO) -> Result<(), HearthError>;
+    async fn get(&self, media_id: &Uuid) -> Result<MediaDTO, HearthError>;
+    async fn get_many(&self, media_ids: &[Uuid]) -> Result<Vec<MediaDTO>, HearthError>;
+    /// Total bytes stored by `owner_id`, every variant included.
+    async fn total_bytes(&self, owner_id: &Uuid) -> Result<u64, HearthError>;
+    async fn list_by_owner(&self, owner_id: &Uuid) -> Result<Vec<MediaDTO>, HearthError>;
+}
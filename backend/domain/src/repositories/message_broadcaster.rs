@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::dtos::conversation::ConversationEventDTO;
+
+/// Live delivery to the connected clients of `recipient_ids`. Best effort:
+/// offline users catch up by listing messages, so this never fails.
+#[async_trait]
+pub trait MessageBroadcaster: Send + Sync {
+    async fn publish(&self, recipient_ids: &[Uuid], event: ConversationEventDTO);
+}
@@ -1,4 +1,49 @@
+pub mod archive_reader;
+pub mod archive_writer;
+pub mod blocks_repository;
+pub mod bookmarks_repository;
+pub mod conversations_repository;
 pub mod credentials_repository;
+pub mod data_exports_repository;
+pub mod device_keys_repository;
+pub mod dm_settings_repository;
 pub mod email_sender_repository;
 pub mod email_verifications_repository;
+pub mod envelopes_repository;
+pub mod event_publisher;
+pub mod follows_repository;
+pub mod imported_posts_repository;
+pub mod imports_repository;
+pub mod job_queue;
+pub mod keyword_filters_repository;
+pub mod link_previews_repository;
+pub mod link_unfurler;
+pub mod linked_identities_repository;
+pub mod lists_repository;
+pub mod login_challenges_repository;
+pub mod media_processor;
+pub mod media_repository;
+pub mod message_broadcaster;
+pub mod moderation_log_repository;
+pub mod notifications_repository;
+pub mod oauth_apps_repository;
+pub mod oauth_codes_repository;
+pub mod oauth_tokens_repository;
+pub mod object_store;
+pub mod oidc_client;
+pub mod oidc_states_repository;
+pub mod outbox_repository;
+pub mod passkeys_repository;
+pub mod personal_data_repository;
+pub mod personal_access_tokens_repository;
+pub mod polls_repository;
+pub mod posts_repository;
+pub mod rate_limiter;
+pub mod reports_repository;
+pub mod secret_cipher;
+pub mod sessions_repository;
+pub mod signup_challenges_repository;
+pub mod trends_repository;
+pub mod two_factor_repository;
 pub mod users_repository;
+pub mod webauthn_challenges_repository;
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::{ moderation::ModerationLogEntryDTO, pagination::TimelineCursor };
+
+/// Append only: entries can't be changed once written.
+#[async_trait]
+pub trait ModerationLogRepository: Send + Sync {
+    async fn append(&self, entry: ModerationLogEntryDTO) -> Result<(), HearthError>;
+    /// Entries newest first, strictly after `cursor`.
+    async fn list(
+        &self,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<ModerationLogEntryDTO>, HearthError>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::{ notification::NotificationDTO, pagination::TimelineCursor };
+
+#[async_trait]
+pub trait NotificationsRepository: Send + Sync {
+    async fn create(&self, notification: NotificationDTO) -> Result<(), HearthError>;
+    /// Notifications of `recipient_id`, newest first, strictly after `cursor`.
+    async fn list(
+        &self,
+        recipient_id: &Uuid,
+        cursor: Option<TimelineCursor>,
+        limit: u64,
+    ) -> Result<Vec<NotificationDTO>, HearthError>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::oauth::OAuthAppDTO;
+
+#[async_trait]
+pub trait OAuthAppsRepository: Send + Sync {
+    async fn create(&self, app: OAuthAppDTO) -> Result<(), HearthError>;
+    async fn get(&self, client_id: &Uuid) -> Result<Option<OAuthAppDTO>, HearthError>;
+    async fn count_by_owner(&self, owner_id: &Uuid) -> Result<u64, HearthError>;
+}
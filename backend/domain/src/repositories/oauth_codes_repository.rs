@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::oauth::OAuthCodeDTO;
+
+/// Authorization codes, keyed by their hash and readable only once.
+#[async_trait]
+pub trait OAuthCodesRepository: Send + Sync {
+    async fn create(
+        &self,
+        code_hash: &str,
+        code: &OAuthCodeDTO,
+        ttl_seconds: u64
+    ) -> Result<(), HearthError>;
+    async fn take(&self, code_hash: &str) -> Result<Option<OAuthCodeDTO>, HearthError>;
+}
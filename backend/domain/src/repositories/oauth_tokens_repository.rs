@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+
+use crate::dtos::oauth::OAuthTokenDTO;
+
+#[async_trait]
+pub trait OAuthTokensRepository: Send + Sync {
+    async fn create(&self, token: OAuthTokenDTO) -> Result<(), HearthError>;
+    async fn get(&self, token_hash: &str) -> Result<Option<OAuthTokenDTO>, HearthError>;
+    async fn revoke(&self, token_hash: &str, at: DateTime<Utc>) -> Result<(), HearthError>;
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+/// A file received chunk by chunk, so it never has to fit in memory whole.
+#[async_trait]
+pub trait ByteStream: Send {
+    /// `None` once the whole file was read.
+    async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, HearthError>>;
+}
+
+/// Blob storage for user uploaded files, keyed by slash separated paths.
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), HearthError>;
+    /// Stores the stream as it is read, returns its size. Nothing is stored
+    /// if the stream fails.
+    async fn put_stream(
+        &self,
+        key: &str,
+        stream: Box<dyn ByteStream>,
+        content_type: &str
+    ) -> Result<u64, HearthError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, HearthError>;
+    async fn delete(&self, key: &str) -> Result<(), HearthError>;
+}
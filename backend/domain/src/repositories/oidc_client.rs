@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::{ dtos::oidc::{ JwkDTO, OidcDiscoveryDTO }, policies::oidc::OidcProvider };
+
+/// HTTP side of the OpenID Connect flow, tokens are validated by `Oidc`.
+#[async_trait]
+pub trait OidcClient: Send + Sync {
+    async fn discover(&self, issuer: &str) -> Result<OidcDiscoveryDTO, HearthError>;
+    /// Raw ID token of the authorization code exchange.
+    async fn exchange_code(
+        &self,
+        discovery: &OidcDiscoveryDTO,
+        provider: &OidcProvider,
+        code: &str,
+        code_verifier: &str
+    ) -> Result<String, HearthError>;
+    async fn jwks(&self, jwks_uri: &str) -> Result<Vec<JwkDTO>, HearthError>;
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::oidc::{ OidcStateDTO, PendingOidcSignupDTO };
+
+/// Short lived state of "sign in with" logins, between the redirect to the
+/// provider and the username selection of new users.
+#[async_trait]
+pub trait OidcStatesRepository: Send + Sync {
+    async fn create_state(&self, state: &OidcStateDTO, ttl_seconds: u64) -> Result<(), HearthError>;
+    async fn take_state(&self, state: &str) -> Result<Option<OidcStateDTO>, HearthError>;
+    /// `token` is the hash of the signup token handed to the client.
+    async fn create_pending_signup(
+        &self,
+        token: &str,
+        signup: &PendingOidcSignupDTO,
+        ttl_seconds: u64
+    ) -> Result<(), HearthError>;
+    async fn take_pending_signup(&self, token: &str) -> Result<Option<PendingOidcSignupDTO>, HearthError>;
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::event::EventDTO;
+
+/// Events are written by the repositories, in the transaction of the change
+/// they describe, this only hands them out for dispatch.
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    /// Leases up to `limit` undispatched events until `lease_until`, oldest
+    /// first. An event whose dispatch failed is handed out again once the
+    /// lease is over.
+    async fn claim(
+        &self,
+        at: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<EventDTO>, HearthError>;
+    async fn mark_dispatched(&self, event_id: &Uuid, at: DateTime<Utc>) -> Result<(), HearthError>;
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::passkey::PasskeyDTO;
+
+#[async_trait]
+pub trait PasskeysRepository: Send + Sync {
+    async fn create(&self, passkey: PasskeyDTO) -> Result<(), HearthError>;
+    async fn list(&self, user_id: &Uuid) -> Result<Vec<PasskeyDTO>, HearthError>;
+    async fn get_by_credential_id(&self, credential_id: &[u8]) -> Result<Option<PasskeyDTO>, HearthError>;
+    /// `false` when another login moved the counter past `sign_count` first.
+    async fn update_sign_count(
+        &self,
+        passkey_id: &Uuid,
+        sign_count: u32,
+        at: DateTime<Utc>
+    ) -> Result<bool, HearthError>;
+}
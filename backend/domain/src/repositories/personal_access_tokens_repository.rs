@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::personal_access_token::PersonalAccessTokenDTO;
+
+#[async_trait]
+pub trait PersonalAccessTokensRepository: Send + Sync {
+    async fn create(&self, token: PersonalAccessTokenDTO) -> Result<(), HearthError>;
+    async fn get_by_hash(
+        &self,
+        token_hash: &str
+    ) -> Result<Option<PersonalAccessTokenDTO>, HearthError>;
+    /// Newest first.
+    async fn list_by_user(&self, user_id: &Uuid) -> Result<Vec<PersonalAccessTokenDTO>, HearthError>;
+    async fn count_by_user(&self, user_id: &Uuid) -> Result<u64, HearthError>;
+    /// Not found unless the token belongs to `user_id`.
+    async fn delete(&self, user_id: &Uuid, token_id: &Uuid) -> Result<(), HearthError>;
+    async fn touch(&self, token_id: &Uuid, at: DateTime<Utc>) -> Result<(), HearthError>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::data_export::PersonalDataDTO;
+
+#[async_trait]
+pub trait PersonalDataRepository: Send + Sync {
+    async fn collect(&self, user_id: &Uuid) -> Result<PersonalDataDTO, HearthError>;
+}
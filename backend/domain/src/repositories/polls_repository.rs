@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::poll::PollDTO;
+
+#[async_trait]
+pub trait PollsRepository: Send + Sync {
+    async fn create(&self, poll: PollDTO) -> Result<(), HearthError>;
+    /// Poll with its tallies, `own_votes` left empty.
+    async fn get(&self, post_id: &Uuid) -> Result<PollDTO, HearthError>;
+    async fn own_votes(&self, post_id: &Uuid, user_id: &Uuid) -> Result<Vec<u8>, HearthError>;
+    /// Records the vote and bumps the tallies. A second vote of the same user
+    /// fails with `POLL_ALREADY_VOTED`.
+    async fn vote(&self, post_id: &Uuid, user_id: &Uuid, choices: &[u8]) -> Result<(), HearthError>;
+    /// Polls closed at `at` whose voters were not notified yet, oldest first.
+    async fn list_ended_unnotified(
+        &self,
+        at: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<Uuid>, HearthError>;
+    async fn voters(&self, post_id: &Uuid) -> Result<Vec<Uuid>, HearthError>;
+    async fn mark_ended_notified(&self, post_id: &Uuid) -> Result<(), HearthError>;
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::{ pagination::TimelineCursor, post::PostDTO };
+
+#[async_trait]
+pub trait PostsRepository: Send + Sync {
+    /// Persists the post along with its normalized hashtags.
+    async fn create(&self, post: PostDTO) -> Result<(), HearthError>;
+    /// Hidden posts are not found.
+    async fn get(&self, post_id: &Uuid) -> Result<PostDTO, HearthError>;
+    /// Existing posts among `post_ids`, in no particular order.
+    async fn get_many(&self, post_ids: &[Uuid]) -> Result<Vec<PostDTO>, HearthError>;
+    /// Posts tagged with `tag`, newest first, strictly after `cursor`.
+    async fn list_by_hashtag(
+        &self,
+        tag: &str,
+        cursor: Option<TimelineCursor>,
+        limit: u64,
+    ) -> Result<Vec<PostDTO>, HearthError>;
+    /// Posts written by any of `author_ids`, newest first, strictly after `cursor`.
+    async fn list_by_authors(
+        &self,
+        author_ids: &[Uuid],
+        cursor: Option<TimelineCursor>,
+        limit: u64,
+    ) -> Result<Vec<PostDTO>, HearthError>;
+    /// Takes the post down for moderation, it no longer shows up anywhere.
+    async fn hide(&self, post_id: &Uuid) -> Result<(), HearthError>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::{ dtos::rate_limit::RateLimitDTO, policies::rate_limit::TokenBucket };
+
+#[async_trait]
+pub trait RateLimiter: Send + Sync {
+    /// Refills the bucket stored under `key` and takes one token from it if
+    /// there is one left. Buckets must be shared by every server instance.
+    async fn take(&self, key: &str, bucket: TokenBucket) -> Result<RateLimitDTO, HearthError>;
+}
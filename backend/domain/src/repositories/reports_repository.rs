@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::{
+    moderation::{ ReportDTO, ReportState, ReportTargetType },
+    pagination::TimelineCursor,
+};
+
+#[async_trait]
+pub trait ReportsRepository: Send + Sync {
+    async fn create(&self, report: ReportDTO) -> Result<(), HearthError>;
+    async fn get(&self, report_id: &Uuid) -> Result<ReportDTO, HearthError>;
+    /// The report `reporter_id` filed on the target that is still waiting for a moderator.
+    async fn find_open(
+        &self,
+        reporter_id: &Uuid,
+        target_type: ReportTargetType,
+        target_id: &Uuid
+    ) -> Result<Option<ReportDTO>, HearthError>;
+    /// Reports in `state`, newest first, strictly after `cursor`.
+    async fn list(
+        &self,
+        state: ReportState,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<ReportDTO>, HearthError>;
+    async fn resolve(
+        &self,
+        report_id: &Uuid,
+        state: ReportState,
+        moderator_id: &Uuid,
+        at: DateTime<Utc>
+    ) -> Result<(), HearthError>;
+}
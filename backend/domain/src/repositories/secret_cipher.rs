@@ -0,0 +1,7 @@
+use errors::HearthError;
+
+/// Symmetric encryption of secrets stored at rest, such as TOTP keys.
+pub trait SecretCipher: Send + Sync {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, HearthError>;
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, HearthError>;
+}
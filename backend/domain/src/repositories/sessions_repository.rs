@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait SessionsRepository: Send + Sync {
+    async fn create(&self, token: &str, user_id: &Uuid) -> Result<(), HearthError>;
+    async fn get_user_id(&self, token: &str) -> Result<Option<Uuid>, HearthError>;
+    /// Logs the user out everywhere.
+    async fn revoke_all(&self, user_id: &Uuid) -> Result<(), HearthError>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::signup::SignupChallengeDTO;
+
+#[async_trait]
+pub trait SignupChallengesRepository: Send + Sync {
+    /// Kept until its `expires_at`.
+    async fn create(&self, challenge: &SignupChallengeDTO) -> Result<(), HearthError>;
+    /// Removes the challenge while reading it, a challenge only buys one signup.
+    async fn take(&self, challenge_id: &Uuid) -> Result<Option<SignupChallengeDTO>, HearthError>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use errors::HearthError;
+
+use crate::dtos::trend::TrendDTO;
+
+#[async_trait]
+pub trait TrendsRepository: Send + Sync {
+    /// Counts one use of every tag in the bucket containing `at`.
+    async fn record(&self, tags: &[String], at: DateTime<Utc>) -> Result<(), HearthError>;
+    /// Highest scoring tags over the rolling window ending at `at`.
+    async fn top(&self, limit: u64, at: DateTime<Utc>) -> Result<Vec<TrendDTO>, HearthError>;
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::dtos::two_factor::TwoFactorDTO;
+
+#[async_trait]
+pub trait TwoFactorRepository: Send + Sync {
+    async fn get(&self, user_id: &Uuid) -> Result<Option<TwoFactorDTO>, HearthError>;
+    /// Replaces any previous, not yet confirmed, secret.
+    async fn save_pending(&self, user_id: &Uuid, encrypted_secret: Vec<u8>) -> Result<(), HearthError>;
+    /// Replaces the recovery codes as well.
+    async fn enable(
+        &self,
+        user_id: &Uuid,
+        step: i64,
+        recovery_code_hashes: Vec<String>,
+        at: DateTime<Utc>
+    ) -> Result<(), HearthError>;
+    /// Moves `last_step` forward, `false` when `step` was already used so two
+    /// concurrent logins can't both spend the same code.
+    async fn record_step(&self, user_id: &Uuid, step: i64) -> Result<bool, HearthError>;
+    /// `false` when the code doesn't exist or was used already.
+    async fn use_recovery_code(
+        &self,
+        user_id: &Uuid,
+        code_hash: &str,
+        at: DateTime<Utc>
+    ) -> Result<bool, HearthError>;
+    /// Forgets the secret and the recovery codes.
+    async fn disable(&self, user_id: &Uuid) -> Result<(), HearthError>;
+}
@@ -1,9 +1,12 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use errors::HearthError;
+use uuid::Uuid;
 
 use crate::dtos::{
     auth::CredentialsDTO,
-    user::{CreateUserDTO, UserDTO},
+    event::EventDTO,
+    user::{CreateUserDTO, Role, UserDTO, UserStatus},
 };
 
 #[async_trait]
@@ -13,8 +16,59 @@ pub trait UsersRepository: Send + Sync {
         &self,
         create_user_dto: CreateUserDTO,
         credentials_dto: CredentialsDTO,
+    ) -> Result<(), HearthError> {
+        self.create_with_events(create_user_dto, credentials_dto, vec![])
+            .await
+    }
+    /// Same as `create`, the events are written to the outbox in the same transaction.
+    async fn create_with_events(
+        &self,
+        create_user_dto: CreateUserDTO,
+        credentials_dto: CredentialsDTO,
+        events: Vec<EventDTO>,
     ) -> Result<(), HearthError>;
     async fn get(&self, user_id: String) -> Result<UserDTO, HearthError>;
+    async fn get_by_email(&self, email: &str) -> Result<Option<UserDTO>, HearthError>;
+    /// Case-insensitive lookup of several usernames at once.
+    async fn get_by_usernames(&self, usernames: &[String]) -> Result<Vec<UserDTO>, HearthError>;
     async fn email_exists(&self, email: &String) -> Result<bool, HearthError>;
+    /// Also true of usernames still held after their account was purged.
     async fn username_exists(&self, username: &String) -> Result<bool, HearthError>;
+    async fn set_avatar(&self, user_id: &Uuid, media_id: Option<Uuid>) -> Result<(), HearthError>;
+    /// Marks the email as verified, the events are written to the outbox in
+    /// the same transaction.
+    async fn set_email_verified_with_events(
+        &self,
+        user_id: &Uuid,
+        events: Vec<EventDTO>,
+    ) -> Result<(), HearthError>;
+    async fn set_role(&self, user_id: &Uuid, role: Role) -> Result<(), HearthError>;
+    /// `suspended_until` is only kept for suspensions, `None` meaning indefinitely.
+    async fn set_status(
+        &self,
+        user_id: &Uuid,
+        status: UserStatus,
+        suspended_until: Option<DateTime<Utc>>,
+    ) -> Result<(), HearthError>;
+    /// Deactivates the account at the given instant, `None` cancels the deletion
+    /// and reactivates it, unless it was suspended in the meantime.
+    async fn set_deactivated(
+        &self,
+        user_id: &Uuid,
+        deactivated_at: Option<DateTime<Utc>>,
+    ) -> Result<(), HearthError>;
+    /// Accounts deactivated before `before`, at most `limit` of them.
+    async fn list_deactivated_before(
+        &self,
+        before: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<UserDTO>, HearthError>;
+    /// Deletes the account and its credentials. Posts and messages are kept as
+    /// empty tombstones so threads and conversations stay whole, and the
+    /// username can't be taken again before `username_held_until`.
+    async fn purge(
+        &self,
+        user_id: &Uuid,
+        username_held_until: DateTime<Utc>,
+    ) -> Result<(), HearthError>;
 }
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use errors::HearthError;
+
+use crate::dtos::passkey::WebAuthnChallengeDTO;
+
+#[async_trait]
+pub trait WebAuthnChallengesRepository: Send + Sync {
+    async fn create(&self, challenge: &WebAuthnChallengeDTO, ttl_seconds: u64) -> Result<(), HearthError>;
+    /// Removes the challenge, each one can only be answered once.
+    async fn take(&self, challenge: &str) -> Result<Option<WebAuthnChallengeDTO>, HearthError>;
+}
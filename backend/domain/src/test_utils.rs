@@ -1,27 +1,155 @@
 #[cfg(test)]
 pub mod test_utils {
-    use std::{ collections::HashMap, sync::{ Arc, Mutex } };
+    use std::{ cmp::Reverse, collections::HashMap, sync::{ Arc, Mutex } };
 
     use crate::{
-        dtos::{ auth::CredentialsDTO, user::{ CreateUserDTO, UserDTO } },
-        error_codes::USER_NOT_FOUND_ERROR_CODE,
+        dtos::{
+            auth::CredentialsDTO,
+            bookmark::{ BookmarkCollectionDTO, BookmarkDTO },
+            data_export::{ ArchiveFileDTO, DataExportDTO, DataExportStatus, PersonalDataDTO },
+            conversation::{
+                ConversationDTO,
+                ConversationEventDTO,
+                DmPermission,
+                EnvelopeDTO,
+                MessageDTO,
+                ReadPositionDTO,
+            },
+            device_keys::{ DeviceKeysDTO, OneTimePrekeyDTO },
+            event::EventDTO,
+            filter::KeywordFilterDTO,
+            import::{
+                ArchivedPostDTO,
+                ImportDTO,
+                ImportReportDTO,
+                ImportSource,
+                ImportStatus,
+                ImportedPostDTO,
+            },
+            job::JobDTO,
+            link_preview::LinkPreviewDTO,
+            list::{ ListDTO, ListMemberDTO },
+            media::{ MediaDTO, ORIGINAL_VARIANT, ProcessedFileDTO, ProcessedMediaDTO },
+            moderation::{ ModerationLogEntryDTO, ReportDTO, ReportState, ReportTargetType },
+            notification::NotificationDTO,
+            oauth::{ OAuthAppDTO, OAuthCodeDTO, OAuthTokenDTO },
+            personal_access_token::PersonalAccessTokenDTO,
+            oidc::{
+                JwkDTO,
+                LinkedIdentityDTO,
+                OidcClaimsDTO,
+                OidcDiscoveryDTO,
+                OidcStateDTO,
+                PendingOidcSignupDTO,
+            },
+            pagination::{ PositionCursor, TimelineCursor },
+            passkey::{ PasskeyDTO, WebAuthnChallengeDTO },
+            poll::PollDTO,
+            post::PostDTO,
+            rate_limit::RateLimitDTO,
+            signup::SignupChallengeDTO,
+            trend::TrendDTO,
+            two_factor::TwoFactorDTO,
+            user::{ CreateUserDTO, Role, UserDTO, UserStatus },
+        },
+        error_codes::{
+            BOOKMARK_COLLECTION_NAME_TAKEN_ERROR_CODE,
+            BOOKMARK_COLLECTION_NOT_FOUND_ERROR_CODE,
+            BOOKMARK_NOT_FOUND_ERROR_CODE,
+            CONVERSATION_NOT_FOUND_ERROR_CODE,
+            FILTER_NOT_FOUND_ERROR_CODE,
+            IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE,
+            IMPORT_NOT_FOUND_ERROR_CODE,
+            LIST_NOT_FOUND_ERROR_CODE,
+            DATA_EXPORT_NOT_FOUND_ERROR_CODE,
+            MEDIA_NOT_FOUND_ERROR_CODE,
+            MESSAGE_NOT_FOUND_ERROR_CODE,
+            OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE,
+            PERSONAL_ACCESS_TOKEN_NOT_FOUND_ERROR_CODE,
+            POLL_ALREADY_VOTED_ERROR_CODE,
+            POLL_NOT_FOUND_ERROR_CODE,
+            POST_NOT_FOUND_ERROR_CODE,
+            REPORT_NOT_FOUND_ERROR_CODE,
+            USER_NOT_FOUND_ERROR_CODE,
+        },
+        entities::{ oidc::Oidc, webauthn::WebAuthn },
+        policies::{ oidc::OidcProvider, passkey::PasskeyPolicy, rate_limit::TokenBucket, trending::TrendingPolicy },
         repositories::{
+            archive_reader::ArchiveReader,
+            archive_writer::ArchiveWriter,
+            blocks_repository::BlocksRepository,
+            bookmarks_repository::BookmarksRepository,
+            conversations_repository::ConversationsRepository,
             credentials_repository::CredentialsRepository,
+            data_exports_repository::DataExportsRepository,
+            device_keys_repository::DeviceKeysRepository,
+            dm_settings_repository::DmSettingsRepository,
             email_sender_repository::EmailSenderRepository,
             email_verifications_repository::EmailVerificationRepository,
+            envelopes_repository::EnvelopesRepository,
+            event_publisher::EventPublisher,
+            follows_repository::FollowsRepository,
+            imported_posts_repository::ImportedPostsRepository,
+            imports_repository::ImportsRepository,
+            job_queue::JobQueue,
+            keyword_filters_repository::KeywordFiltersRepository,
+            link_previews_repository::LinkPreviewsRepository,
+            link_unfurler::LinkUnfurler,
+            linked_identities_repository::LinkedIdentitiesRepository,
+            login_challenges_repository::LoginChallengesRepository,
+            lists_repository::ListsRepository,
+            media_processor::MediaProcessor,
+            media_repository::MediaRepository,
+            message_broadcaster::MessageBroadcaster,
+            moderation_log_repository::ModerationLogRepository,
+            notifications_repository::NotificationsRepository,
+            oauth_apps_repository::OAuthAppsRepository,
+            oauth_codes_repository::OAuthCodesRepository,
+            oauth_tokens_repository::OAuthTokensRepository,
+            object_store::{ ByteStream, ObjectStore },
+            oidc_client::OidcClient,
+            oidc_states_repository::OidcStatesRepository,
+            outbox_repository::OutboxRepository,
+            passkeys_repository::PasskeysRepository,
+            personal_access_tokens_repository::PersonalAccessTokensRepository,
+            personal_data_repository::PersonalDataRepository,
+            polls_repository::PollsRepository,
+            posts_repository::PostsRepository,
+            rate_limiter::RateLimiter,
+            reports_repository::ReportsRepository,
+            secret_cipher::SecretCipher,
+            sessions_repository::SessionsRepository,
+            signup_challenges_repository::SignupChallengesRepository,
+            trends_repository::TrendsRepository,
+            two_factor_repository::TwoFactorRepository,
             users_repository::UsersRepository,
+            webauthn_challenges_repository::WebAuthnChallengesRepository,
         },
     };
     use async_trait::async_trait;
+    use chrono::{ DateTime, Utc };
     use email_verification_code::EmailVerificationCode;
     use errors::HearthError;
+    use sha2::Digest;
     use uuid::Uuid;
 
-    pub struct InMemoryEmailSenderRepository {}
+    #[derive(Clone)]
+    pub struct InMemoryEmailSenderRepository {
+        /// `(email, link)` of every data export link sent.
+        pub data_export_links: Arc<Mutex<Vec<(String, String)>>>,
+        /// `(email, username)` of every welcome email sent.
+        pub welcome_emails: Arc<Mutex<Vec<(String, String)>>>,
+        /// `(email, code)` of every verification email sent.
+        pub verify_emails: Arc<Mutex<Vec<(String, EmailVerificationCode)>>>,
+    }
 
     impl Default for InMemoryEmailSenderRepository {
         fn default() -> Self {
-            Self {}
+            Self {
+                data_export_links: Arc::new(Mutex::new(vec![])),
+                welcome_emails: Arc::new(Mutex::new(vec![])),
+                verify_emails: Arc::new(Mutex::new(vec![])),
+            }
         }
     }
 
@@ -29,9 +157,20 @@ pub mod test_utils {
     impl EmailSenderRepository for InMemoryEmailSenderRepository {
         async fn send_verify_email(
             &self,
-            _email: &String,
-            _code: &EmailVerificationCode
+            email: &str,
+            code: &EmailVerificationCode
         ) -> Result<(), HearthError> {
+            self.verify_emails.lock().unwrap().push((email.into(), code.clone()));
+            Ok(())
+        }
+
+        async fn send_data_export_link(&self, email: &str, link: &str) -> Result<(), HearthError> {
+            self.data_export_links.lock().unwrap().push((email.into(), link.into()));
+            Ok(())
+        }
+
+        async fn send_welcome_email(&self, email: &str, username: &str) -> Result<(), HearthError> {
+            self.welcome_emails.lock().unwrap().push((email.into(), username.into()));
             Ok(())
         }
     }
@@ -76,12 +215,19 @@ pub mod test_utils {
             let stored_code = map.get(email);
             Ok(stored_code.is_some_and(|v| v == code))
         }
+
+        async fn delete(&self, email: &String) -> Result<(), HearthError> {
+            self.map.lock().unwrap().remove(email);
+            Ok(())
+        }
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
     pub struct InMemoryUserRepository {
         users: Arc<Mutex<HashMap<String, UserDTO>>>,
         credentials: Arc<Mutex<HashMap<String, String>>>,
+        held_usernames: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
+        outbox: InMemoryOutboxRepository,
     }
 
     impl Default for InMemoryUserRepository {
@@ -89,6 +235,8 @@ pub mod test_utils {
             Self {
                 users: Arc::new(Mutex::new(HashMap::default())),
                 credentials: Arc::new(Mutex::new(HashMap::new())),
+                held_usernames: Arc::new(Mutex::new(HashMap::new())),
+                outbox: InMemoryOutboxRepository::default(),
             }
         }
     }
@@ -105,22 +253,37 @@ pub mod test_utils {
             Self {
                 users: Arc::new(Mutex::new(users)),
                 credentials: Arc::new(Mutex::new(credentials)),
+                held_usernames: Arc::new(Mutex::new(HashMap::new())),
+                outbox: InMemoryOutboxRepository::default(),
+            }
+        }
+
+        pub fn verify_email(&self, user_id: &Uuid) {
+            if let Some(user) = self.users.lock().unwrap().get_mut(&user_id.to_string()) {
+                user.email_verified = true;
             }
         }
+
+        /// Writes the events of the created users to `outbox`.
+        pub fn with_outbox(outbox: InMemoryOutboxRepository) -> Self {
+            Self { outbox, ..Default::default() }
+        }
     }
 
     #[async_trait]
     impl UsersRepository for InMemoryUserRepository {
-        async fn create(
+        async fn create_with_events(
             &self,
             dto: CreateUserDTO,
-            credentials_dto: CredentialsDTO
+            credentials_dto: CredentialsDTO,
+            events: Vec<EventDTO>
         ) -> Result<(), HearthError> {
             self.users.lock().unwrap().insert(dto.user_id.to_string(), UserDTO::new(dto));
             self.credentials
                 .lock()
                 .unwrap()
                 .insert(credentials_dto.user_id.to_string(), credentials_dto.password_hash.clone());
+            self.outbox.append(events);
             Ok(())
         }
 
@@ -135,8 +298,13 @@ pub mod test_utils {
             let users = self.users.lock().unwrap();
 
             let opt = users.iter().find(|user| user.1.username == *username);
+            let held = self.held_usernames
+                .lock()
+                .unwrap()
+                .get(&username.to_lowercase())
+                .is_some_and(|until| *until > Utc::now());
 
-            Ok(opt.is_some())
+            Ok(opt.is_some() || held)
         }
 
         async fn email_exists(&self, email: &String) -> Result<bool, HearthError> {
@@ -146,5 +314,2513 @@ pub mod test_utils {
 
             Ok(opt.is_some())
         }
+
+        async fn get_by_email(&self, email: &str) -> Result<Option<UserDTO>, HearthError> {
+            let users = self.users.lock().unwrap();
+
+            Ok(users.values().find(|user| user.email == email).cloned())
+        }
+
+        async fn set_avatar(&self, user_id: &Uuid, media_id: Option<Uuid>) -> Result<(), HearthError> {
+            match self.users.lock().unwrap().get_mut(&user_id.to_string()) {
+                Some(user) => {
+                    user.avatar_media_id = media_id;
+                    Ok(())
+                }
+                None => Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into())),
+            }
+        }
+
+        async fn set_email_verified_with_events(
+            &self,
+            user_id: &Uuid,
+            events: Vec<EventDTO>
+        ) -> Result<(), HearthError> {
+            match self.users.lock().unwrap().get_mut(&user_id.to_string()) {
+                Some(user) => {
+                    user.email_verified = true;
+                }
+                None => {
+                    return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+                }
+            }
+            self.outbox.append(events);
+            Ok(())
+        }
+
+        async fn set_role(&self, user_id: &Uuid, role: Role) -> Result<(), HearthError> {
+            match self.users.lock().unwrap().get_mut(&user_id.to_string()) {
+                Some(user) => {
+                    user.role = role;
+                    Ok(())
+                }
+                None => Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into())),
+            }
+        }
+
+        async fn set_status(
+            &self,
+            user_id: &Uuid,
+            status: UserStatus,
+            suspended_until: Option<DateTime<Utc>>
+        ) -> Result<(), HearthError> {
+            match self.users.lock().unwrap().get_mut(&user_id.to_string()) {
+                Some(user) => {
+                    user.status = status;
+                    user.suspended_until = suspended_until;
+                    Ok(())
+                }
+                None => Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into())),
+            }
+        }
+
+        async fn set_deactivated(
+            &self,
+            user_id: &Uuid,
+            deactivated_at: Option<DateTime<Utc>>
+        ) -> Result<(), HearthError> {
+            match self.users.lock().unwrap().get_mut(&user_id.to_string()) {
+                Some(user) => {
+                    user.status = match (deactivated_at, user.status) {
+                        (Some(_), _) => UserStatus::Deactivated,
+                        (None, UserStatus::Deactivated) => UserStatus::Active,
+                        (None, status) => status,
+                    };
+                    user.deactivated_at = deactivated_at;
+                    Ok(())
+                }
+                None => Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into())),
+            }
+        }
+
+        async fn list_deactivated_before(
+            &self,
+            before: DateTime<Utc>,
+            limit: u64
+        ) -> Result<Vec<UserDTO>, HearthError> {
+            Ok(
+                self.users
+                    .lock()
+                    .unwrap()
+                    .values()
+                    .filter(|user| user.deactivated_at.is_some_and(|at| at < before))
+                    .take(limit as usize)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn purge(
+            &self,
+            user_id: &Uuid,
+            username_held_until: DateTime<Utc>
+        ) -> Result<(), HearthError> {
+            let user = self.users
+                .lock()
+                .unwrap()
+                .remove(&user_id.to_string())
+                .ok_or_else(|| HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()))?;
+            self.credentials.lock().unwrap().remove(&user_id.to_string());
+            self.held_usernames
+                .lock()
+                .unwrap()
+                .insert(user.username.to_lowercase(), username_held_until);
+            Ok(())
+        }
+
+        async fn get_by_usernames(&self, usernames: &[String]) -> Result<Vec<UserDTO>, HearthError> {
+            let users = self.users.lock().unwrap();
+
+            Ok(
+                users
+                    .values()
+                    .filter(|user| usernames.iter().any(|u| u.eq_ignore_ascii_case(&user.username)))
+                    .cloned()
+                    .collect()
+            )
+        }
+    }
+
+    #[async_trait]
+    impl CredentialsRepository for InMemoryUserRepository {
+        async fn get(&self, user_id: &Uuid) -> Result<Option<CredentialsDTO>, HearthError> {
+            let credentials = self.credentials.lock().unwrap();
+
+            Ok(
+                credentials.get(&user_id.to_string()).map(|password_hash| CredentialsDTO {
+                    user_id: *user_id,
+                    password_hash: password_hash.clone(),
+                })
+            )
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemorySessionsRepository {
+        sessions: Arc<Mutex<HashMap<String, Uuid>>>,
+    }
+
+    #[async_trait]
+    impl SessionsRepository for InMemorySessionsRepository {
+        async fn create(&self, token: &str, user_id: &Uuid) -> Result<(), HearthError> {
+            self.sessions.lock().unwrap().insert(token.to_string(), *user_id);
+            Ok(())
+        }
+
+        async fn get_user_id(&self, token: &str) -> Result<Option<Uuid>, HearthError> {
+            Ok(self.sessions.lock().unwrap().get(token).copied())
+        }
+
+        async fn revoke_all(&self, user_id: &Uuid) -> Result<(), HearthError> {
+            self.sessions.lock().unwrap().retain(|_, id| id != user_id);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryPostsRepository {
+        posts: Arc<Mutex<Vec<PostDTO>>>,
+    }
+
+    impl InMemoryPostsRepository {
+        pub fn remove(&self, post_id: &Uuid) {
+            self.posts.lock().unwrap().retain(|post| post.post_id != *post_id);
+        }
+    }
+
+    #[async_trait]
+    impl PostsRepository for InMemoryPostsRepository {
+        async fn create(&self, post: PostDTO) -> Result<(), HearthError> {
+            self.posts.lock().unwrap().push(post);
+            Ok(())
+        }
+
+        async fn get(&self, post_id: &Uuid) -> Result<PostDTO, HearthError> {
+            self.posts
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|post| post.post_id == *post_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(POST_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn hide(&self, post_id: &Uuid) -> Result<(), HearthError> {
+            self.remove(post_id);
+            Ok(())
+        }
+
+        async fn get_many(&self, post_ids: &[Uuid]) -> Result<Vec<PostDTO>, HearthError> {
+            Ok(
+                self.posts
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|post| post_ids.contains(&post.post_id))
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn list_by_hashtag(
+            &self,
+            tag: &str,
+            cursor: Option<TimelineCursor>,
+            limit: u64
+        ) -> Result<Vec<PostDTO>, HearthError> {
+            let mut posts: Vec<PostDTO> = self.posts
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|post| post.hashtags.iter().any(|hashtag| hashtag == tag))
+                .filter(|post| cursor.is_none_or(|c| c.is_before(post.created_at, post.post_id)))
+                .cloned()
+                .collect();
+
+            posts.sort_by_key(|post| Reverse((post.created_at, post.post_id)));
+            posts.truncate(limit as usize);
+            Ok(posts)
+        }
+
+        async fn list_by_authors(
+            &self,
+            author_ids: &[Uuid],
+            cursor: Option<TimelineCursor>,
+            limit: u64
+        ) -> Result<Vec<PostDTO>, HearthError> {
+            let mut posts: Vec<PostDTO> = self.posts
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|post| author_ids.contains(&post.author_id))
+                .filter(|post| cursor.is_none_or(|c| c.is_before(post.created_at, post.post_id)))
+                .cloned()
+                .collect();
+
+            posts.sort_by_key(|post| Reverse((post.created_at, post.post_id)));
+            posts.truncate(limit as usize);
+            Ok(posts)
+        }
+    }
+
+    /// Mirrors the Redis implementation: one counter map per time bucket.
+    #[derive(Default, Clone)]
+    pub struct InMemoryTrendsRepository {
+        policy: TrendingPolicy,
+        buckets: Arc<Mutex<HashMap<i64, HashMap<String, f64>>>>,
+    }
+
+    #[async_trait]
+    impl TrendsRepository for InMemoryTrendsRepository {
+        async fn record(&self, tags: &[String], at: DateTime<Utc>) -> Result<(), HearthError> {
+            let mut buckets = self.buckets.lock().unwrap();
+            let bucket = buckets.entry(self.policy.bucket_of(at)).or_default();
+
+            for tag in tags {
+                *bucket.entry(tag.clone()).or_default() += 1.0;
+            }
+
+            Ok(())
+        }
+
+        async fn top(&self, limit: u64, at: DateTime<Utc>) -> Result<Vec<TrendDTO>, HearthError> {
+            let buckets = self.buckets.lock().unwrap();
+            let mut scores: HashMap<String, f64> = HashMap::new();
+
+            for (bucket, weight) in self.policy.weighted_buckets(at) {
+                for (tag, count) in buckets.get(&bucket).into_iter().flatten() {
+                    *scores.entry(tag.clone()).or_default() += count * weight;
+                }
+            }
+
+            let mut trends: Vec<TrendDTO> = scores
+                .into_iter()
+                .map(|(tag, score)| TrendDTO { tag, score })
+                .collect();
+
+            trends.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.tag.cmp(&b.tag)));
+            trends.truncate(limit as usize);
+            Ok(trends)
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryBlocksRepository {
+        blocks: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+    }
+
+    #[async_trait]
+    impl BlocksRepository for InMemoryBlocksRepository {
+        async fn block(&self, blocker_id: &Uuid, blocked_id: &Uuid) -> Result<(), HearthError> {
+            let mut blocks = self.blocks.lock().unwrap();
+            if !blocks.contains(&(*blocker_id, *blocked_id)) {
+                blocks.push((*blocker_id, *blocked_id));
+            }
+            Ok(())
+        }
+
+        async fn unblock(&self, blocker_id: &Uuid, blocked_id: &Uuid) -> Result<(), HearthError> {
+            self.blocks
+                .lock()
+                .unwrap()
+                .retain(|block| *block != (*blocker_id, *blocked_id));
+            Ok(())
+        }
+
+        async fn is_blocked_between(
+            &self,
+            user_a: &Uuid,
+            user_b: &Uuid
+        ) -> Result<bool, HearthError> {
+            let blocks = self.blocks.lock().unwrap();
+            Ok(blocks.contains(&(*user_a, *user_b)) || blocks.contains(&(*user_b, *user_a)))
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryNotificationsRepository {
+        notifications: Arc<Mutex<Vec<NotificationDTO>>>,
+    }
+
+    #[async_trait]
+    impl NotificationsRepository for InMemoryNotificationsRepository {
+        async fn create(&self, notification: NotificationDTO) -> Result<(), HearthError> {
+            self.notifications.lock().unwrap().push(notification);
+            Ok(())
+        }
+
+        async fn list(
+            &self,
+            recipient_id: &Uuid,
+            cursor: Option<TimelineCursor>,
+            limit: u64
+        ) -> Result<Vec<NotificationDTO>, HearthError> {
+            let mut notifications: Vec<NotificationDTO> = self.notifications
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|n| n.recipient_id == *recipient_id)
+                .filter(|n| cursor.is_none_or(|c| c.is_before(n.created_at, n.notification_id)))
+                .cloned()
+                .collect();
+
+            notifications.sort_by_key(|n| Reverse((n.created_at, n.notification_id)));
+            notifications.truncate(limit as usize);
+            Ok(notifications)
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryMediaRepository {
+        media: Arc<Mutex<Vec<MediaDTO>>>,
+    }
+
+    #[async_trait]
+    impl MediaRepository for InMemoryMediaRepository {
+        async fn create(&self, media: MediaDTO) -> Result<(), HearthError> {
+            self.media.lock().unwrap().push(media);
+            Ok(())
+        }
+
+        async fn update(&self, media: MediaDTO) -> Result<(), HearthError> {
+            if let Some(stored) = self.media.lock().unwrap().iter_mut().find(|m| m.media_id == media.media_id) {
+                *stored = media;
+            }
+            Ok(())
+        }
+
+        async fn get(&self, media_id: &Uuid) -> Result<MediaDTO, HearthError> {
+            self.media
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|m| m.media_id == *media_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn get_many(&self, media_ids: &[Uuid]) -> Result<Vec<MediaDTO>, HearthError> {
+            Ok(
+                self.media
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|m| media_ids.contains(&m.media_id))
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn total_bytes(&self, owner_id: &Uuid) -> Result<u64, HearthError> {
+            Ok(
+                self.media
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|m| m.owner_id == *owner_id)
+                    .map(|m| m.size_bytes())
+                    .sum()
+            )
+        }
+
+        async fn list_by_owner(&self, owner_id: &Uuid) -> Result<Vec<MediaDTO>, HearthError> {
+            Ok(
+                self.media
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|m| m.owner_id == *owner_id)
+                    .cloned()
+                    .collect()
+            )
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryObjectStore {
+        objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    }
+
+    #[async_trait]
+    impl ObjectStore for InMemoryObjectStore {
+        async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), HearthError> {
+            self.objects.lock().unwrap().insert(key.into(), bytes);
+            Ok(())
+        }
+
+        async fn put_stream(
+            &self,
+            key: &str,
+            mut stream: Box<dyn ByteStream>,
+            _content_type: &str
+        ) -> Result<u64, HearthError> {
+            let mut bytes = vec![];
+            while let Some(chunk) = stream.next_chunk().await {
+                bytes.extend(chunk?);
+            }
+
+            let size = bytes.len() as u64;
+            self.objects.lock().unwrap().insert(key.into(), bytes);
+            Ok(size)
+        }
+
+        async fn get(&self, key: &str) -> Result<Vec<u8>, HearthError> {
+            self.objects
+                .lock()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), HearthError> {
+            self.objects.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
+    /// Hands out the chunks it was given, like an upload being received.
+    pub struct FakeByteStream {
+        pub chunks: Vec<Result<Vec<u8>, HearthError>>,
+    }
+
+    impl FakeByteStream {
+        pub fn of(bytes: &[u8], chunk_size: usize) -> Box<Self> {
+            Box::new(Self { chunks: bytes.chunks(chunk_size).map(|chunk| Ok(chunk.to_vec())).collect() })
+        }
+    }
+
+    #[async_trait]
+    impl ByteStream for FakeByteStream {
+        async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, HearthError>> {
+            (!self.chunks.is_empty()).then(|| self.chunks.remove(0))
+        }
+    }
+
+    /// JPEG magic bytes followed by filler, enough for `FakeMediaProcessor`.
+    pub const FAKE_JPEG: &[u8] = b"\xFF\xD8\xFF\xE0fake-jpeg";
+
+    /// Recognizes JPEG magic bytes and stores the upload as-is plus a thumbnail.
+    pub struct FakeMediaProcessor;
+
+    #[async_trait]
+    impl MediaProcessor for FakeMediaProcessor {
+        fn sniff_mime_type(&self, bytes: &[u8]) -> Option<String> {
+            bytes.starts_with(&[0xff, 0xd8, 0xff]).then(|| "image/jpeg".into())
+        }
+
+        async fn process(&self, bytes: Vec<u8>) -> Result<ProcessedMediaDTO, HearthError> {
+            let file = |variant: &str| ProcessedFileDTO {
+                variant: variant.into(),
+                mime_type: "image/jpeg".into(),
+                width: 1,
+                height: 1,
+                bytes: bytes.clone(),
+            };
+
+            Ok(ProcessedMediaDTO {
+                files: vec![file(ORIGINAL_VARIANT), file("thumbnail")],
+                blurhash: "LEHV6nWB2yk8pyo0adR*.7kCMdnj".into(),
+            })
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryLinkPreviewsRepository {
+        previews: Arc<Mutex<HashMap<String, LinkPreviewDTO>>>,
+        attached: Arc<Mutex<HashMap<Uuid, String>>>,
+    }
+
+    impl InMemoryLinkPreviewsRepository {
+        pub fn attached(&self, post_id: &Uuid) -> Option<String> {
+            self.attached.lock().unwrap().get(post_id).cloned()
+        }
+    }
+
+    #[async_trait]
+    impl LinkPreviewsRepository for InMemoryLinkPreviewsRepository {
+        async fn get(&self, url: &str) -> Result<Option<LinkPreviewDTO>, HearthError> {
+            Ok(self.previews.lock().unwrap().get(url).cloned())
+        }
+
+        async fn save(&self, preview: LinkPreviewDTO) -> Result<(), HearthError> {
+            self.previews.lock().unwrap().insert(preview.url.clone(), preview);
+            Ok(())
+        }
+
+        async fn attach(&self, post_id: &Uuid, url: &str) -> Result<(), HearthError> {
+            self.attached.lock().unwrap().insert(*post_id, url.into());
+            Ok(())
+        }
+    }
+
+    /// Titles every page after its url, except the ones ending with `/untitled`.
+    #[derive(Default, Clone)]
+    pub struct FakeLinkUnfurler {
+        calls: Arc<Mutex<usize>>,
+    }
+
+    impl FakeLinkUnfurler {
+        pub fn calls(&self) -> usize {
+            *self.calls.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl LinkUnfurler for FakeLinkUnfurler {
+        async fn unfurl(&self, url: &str) -> Result<LinkPreviewDTO, HearthError> {
+            *self.calls.lock().unwrap() += 1;
+
+            if url.ends_with("/down") {
+                return Err(HearthError::unexpected("LINK_UNREACHABLE".into(), None));
+            }
+
+            Ok(LinkPreviewDTO {
+                url: url.into(),
+                title: (!url.ends_with("/untitled")).then(|| format!("Title of {}", url)),
+                description: None,
+                image_url: None,
+                site_name: None,
+                fetched_at: Utc::now(),
+            })
+        }
+    }
+
+    /// Bookmarks keyed by `(owner_id, post_id)`.
+    type Bookmarks = HashMap<(Uuid, Uuid), BookmarkDTO>;
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryBookmarksRepository {
+        collections: Arc<Mutex<Vec<BookmarkCollectionDTO>>>,
+        bookmarks: Arc<Mutex<Bookmarks>>,
+    }
+
+    #[async_trait]
+    impl BookmarksRepository for InMemoryBookmarksRepository {
+        async fn create_collection(&self, collection: BookmarkCollectionDTO) -> Result<(), HearthError> {
+            let mut collections = self.collections.lock().unwrap();
+
+            if
+                collections
+                    .iter()
+                    .any(|c| c.owner_id == collection.owner_id && c.name == collection.name)
+            {
+                return Err(HearthError::Domain(BOOKMARK_COLLECTION_NAME_TAKEN_ERROR_CODE.into()));
+            }
+
+            collections.push(collection);
+            Ok(())
+        }
+
+        async fn get_collection(
+            &self,
+            owner_id: &Uuid,
+            collection_id: &Uuid
+        ) -> Result<BookmarkCollectionDTO, HearthError> {
+            self.collections
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|c| c.owner_id == *owner_id && c.collection_id == *collection_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(BOOKMARK_COLLECTION_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn list_collections(
+            &self,
+            owner_id: &Uuid
+        ) -> Result<Vec<BookmarkCollectionDTO>, HearthError> {
+            Ok(
+                self.collections
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|c| c.owner_id == *owner_id)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn save(
+            &self,
+            owner_id: &Uuid,
+            post_id: &Uuid,
+            collection_id: Option<Uuid>,
+            at: DateTime<Utc>
+        ) -> Result<BookmarkDTO, HearthError> {
+            let mut bookmarks = self.bookmarks.lock().unwrap();
+            let top = bookmarks
+                .iter()
+                .filter(|((owner, _), b)| owner == owner_id && b.collection_id == collection_id)
+                .map(|(_, b)| b.position)
+                .min()
+                .unwrap_or(0);
+
+            let bookmark = BookmarkDTO {
+                post_id: *post_id,
+                collection_id,
+                position: top - 1,
+                created_at: at,
+                post: None,
+            };
+            bookmarks.insert((*owner_id, *post_id), bookmark.clone());
+            Ok(bookmark)
+        }
+
+        async fn remove(&self, owner_id: &Uuid, post_id: &Uuid) -> Result<(), HearthError> {
+            self.bookmarks.lock().unwrap().remove(&(*owner_id, *post_id));
+            Ok(())
+        }
+
+        async fn move_to(&self, owner_id: &Uuid, post_id: &Uuid, index: usize) -> Result<(), HearthError> {
+            let mut bookmarks = self.bookmarks.lock().unwrap();
+            let collection_id = bookmarks
+                .get(&(*owner_id, *post_id))
+                .ok_or_else(|| HearthError::not_found(BOOKMARK_NOT_FOUND_ERROR_CODE.into()))?
+                .collection_id;
+
+            let mut ordered: Vec<(i64, Uuid)> = bookmarks
+                .iter()
+                .filter(|((owner, id), b)| {
+                    owner == owner_id && b.collection_id == collection_id && id != post_id
+                })
+                .map(|((_, id), b)| (b.position, *id))
+                .collect();
+            ordered.sort();
+            ordered.insert(index.min(ordered.len()), (0, *post_id));
+
+            for (position, (_, id)) in ordered.into_iter().enumerate() {
+                if let Some(bookmark) = bookmarks.get_mut(&(*owner_id, id)) {
+                    bookmark.position = position as i64;
+                }
+            }
+
+            Ok(())
+        }
+
+        async fn list(
+            &self,
+            owner_id: &Uuid,
+            collection_id: Option<Uuid>,
+            cursor: Option<PositionCursor>,
+            limit: u64
+        ) -> Result<Vec<BookmarkDTO>, HearthError> {
+            let mut bookmarks: Vec<BookmarkDTO> = self.bookmarks
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|((owner, _), b)| owner == owner_id && b.collection_id == collection_id)
+                .filter(|(_, b)| cursor.is_none_or(|c| c.is_after(b.position, b.post_id)))
+                .map(|(_, b)| b.clone())
+                .collect();
+
+            bookmarks.sort_by_key(|b| (b.position, b.post_id));
+            bookmarks.truncate(limit as usize);
+            Ok(bookmarks)
+        }
+    }
+
+    /// Choices keyed by `(post_id, user_id)`.
+    type PollVotes = HashMap<(Uuid, Uuid), Vec<u8>>;
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryPollsRepository {
+        polls: Arc<Mutex<HashMap<Uuid, PollDTO>>>,
+        votes: Arc<Mutex<PollVotes>>,
+        notified: Arc<Mutex<Vec<Uuid>>>,
+    }
+
+    #[async_trait]
+    impl PollsRepository for InMemoryPollsRepository {
+        async fn create(&self, poll: PollDTO) -> Result<(), HearthError> {
+            self.polls.lock().unwrap().insert(poll.post_id, poll);
+            Ok(())
+        }
+
+        async fn get(&self, post_id: &Uuid) -> Result<PollDTO, HearthError> {
+            self.polls
+                .lock()
+                .unwrap()
+                .get(post_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(POLL_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn own_votes(&self, post_id: &Uuid, user_id: &Uuid) -> Result<Vec<u8>, HearthError> {
+            Ok(self.votes.lock().unwrap().get(&(*post_id, *user_id)).cloned().unwrap_or_default())
+        }
+
+        async fn vote(&self, post_id: &Uuid, user_id: &Uuid, choices: &[u8]) -> Result<(), HearthError> {
+            let mut votes = self.votes.lock().unwrap();
+            if votes.contains_key(&(*post_id, *user_id)) {
+                return Err(HearthError::Domain(POLL_ALREADY_VOTED_ERROR_CODE.into()));
+            }
+            votes.insert((*post_id, *user_id), choices.to_vec());
+
+            let mut polls = self.polls.lock().unwrap();
+            let poll = polls
+                .get_mut(post_id)
+                .ok_or_else(|| HearthError::not_found(POLL_NOT_FOUND_ERROR_CODE.into()))?;
+            poll.voters_count = poll.voters_count.map(|count| count + 1);
+            for choice in choices {
+                let option = &mut poll.options[*choice as usize];
+                option.votes_count = option.votes_count.map(|count| count + 1);
+            }
+            Ok(())
+        }
+
+        async fn list_ended_unnotified(
+            &self,
+            at: DateTime<Utc>,
+            limit: u64
+        ) -> Result<Vec<Uuid>, HearthError> {
+            let notified = self.notified.lock().unwrap();
+            let mut ended: Vec<PollDTO> = self.polls
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|poll| poll.is_closed(at) && !notified.contains(&poll.post_id))
+                .cloned()
+                .collect();
+
+            ended.sort_by_key(|poll| poll.closes_at);
+            Ok(
+                ended
+                    .into_iter()
+                    .take(limit as usize)
+                    .map(|poll| poll.post_id)
+                    .collect()
+            )
+        }
+
+        async fn voters(&self, post_id: &Uuid) -> Result<Vec<Uuid>, HearthError> {
+            Ok(
+                self.votes
+                    .lock()
+                    .unwrap()
+                    .keys()
+                    .filter(|(poll_id, _)| poll_id == post_id)
+                    .map(|(_, user_id)| *user_id)
+                    .collect()
+            )
+        }
+
+        async fn mark_ended_notified(&self, post_id: &Uuid) -> Result<(), HearthError> {
+            self.notified.lock().unwrap().push(*post_id);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryListsRepository {
+        lists: Arc<Mutex<Vec<ListDTO>>>,
+        members: Arc<Mutex<HashMap<Uuid, Vec<ListMemberDTO>>>>,
+        subscriptions: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+    }
+
+    #[async_trait]
+    impl ListsRepository for InMemoryListsRepository {
+        async fn create(&self, list: ListDTO) -> Result<(), HearthError> {
+            self.lists.lock().unwrap().push(list);
+            Ok(())
+        }
+
+        async fn get(&self, list_id: &Uuid) -> Result<ListDTO, HearthError> {
+            self.lists
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|list| list.list_id == *list_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn update(&self, list: ListDTO) -> Result<(), HearthError> {
+            let mut lists = self.lists.lock().unwrap();
+            let existing = lists
+                .iter_mut()
+                .find(|l| l.list_id == list.list_id)
+                .ok_or_else(|| HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into()))?;
+            *existing = list;
+            Ok(())
+        }
+
+        async fn delete(&self, list_id: &Uuid) -> Result<(), HearthError> {
+            self.lists.lock().unwrap().retain(|list| list.list_id != *list_id);
+            self.members.lock().unwrap().remove(list_id);
+            self.remove_subscribers(list_id).await
+        }
+
+        async fn list_by_owner(&self, owner_id: &Uuid) -> Result<Vec<ListDTO>, HearthError> {
+            Ok(
+                self.lists
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|list| list.owner_id == *owner_id)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn add_member(
+            &self,
+            list_id: &Uuid,
+            member: ListMemberDTO,
+            _at: DateTime<Utc>
+        ) -> Result<bool, HearthError> {
+            let mut members = self.members.lock().unwrap();
+            let members = members.entry(*list_id).or_default();
+
+            if members.iter().any(|m| m.user_id == member.user_id) {
+                return Ok(false);
+            }
+
+            members.push(member);
+            Ok(true)
+        }
+
+        async fn remove_member(&self, list_id: &Uuid, member_id: &Uuid) -> Result<(), HearthError> {
+            if let Some(members) = self.members.lock().unwrap().get_mut(list_id) {
+                members.retain(|m| m.user_id != *member_id);
+            }
+            Ok(())
+        }
+
+        async fn members(&self, list_id: &Uuid) -> Result<Vec<ListMemberDTO>, HearthError> {
+            Ok(self.members.lock().unwrap().get(list_id).cloned().unwrap_or_default())
+        }
+
+        async fn subscribe(&self, list_id: &Uuid, user_id: &Uuid) -> Result<(), HearthError> {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            if !subscriptions.contains(&(*list_id, *user_id)) {
+                subscriptions.push((*list_id, *user_id));
+            }
+            Ok(())
+        }
+
+        async fn unsubscribe(&self, list_id: &Uuid, user_id: &Uuid) -> Result<(), HearthError> {
+            self.subscriptions.lock().unwrap().retain(|s| *s != (*list_id, *user_id));
+            Ok(())
+        }
+
+        async fn remove_subscribers(&self, list_id: &Uuid) -> Result<(), HearthError> {
+            self.subscriptions.lock().unwrap().retain(|(id, _)| id != list_id);
+            Ok(())
+        }
+
+        async fn subscriptions(&self, user_id: &Uuid) -> Result<Vec<ListDTO>, HearthError> {
+            let subscribed: Vec<Uuid> = self.subscriptions
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, subscriber)| subscriber == user_id)
+                .map(|(list_id, _)| *list_id)
+                .collect();
+
+            Ok(
+                self.lists
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|list| subscribed.contains(&list.list_id))
+                    .cloned()
+                    .collect()
+            )
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryConversationsRepository {
+        conversations: Arc<Mutex<Vec<ConversationDTO>>>,
+        messages: Arc<Mutex<Vec<MessageDTO>>>,
+    }
+
+    #[async_trait]
+    impl ConversationsRepository for InMemoryConversationsRepository {
+        async fn create(&self, conversation: ConversationDTO) -> Result<(), HearthError> {
+            self.conversations.lock().unwrap().push(conversation);
+            Ok(())
+        }
+
+        async fn get(&self, conversation_id: &Uuid) -> Result<ConversationDTO, HearthError> {
+            self.conversations
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|c| c.conversation_id == *conversation_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(CONVERSATION_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn find_direct(
+            &self,
+            user_a: &Uuid,
+            user_b: &Uuid
+        ) -> Result<Option<ConversationDTO>, HearthError> {
+            Ok(
+                self.conversations
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|c| {
+                        c.is_direct() &&
+                            c.participant_ids.contains(user_a) &&
+                            c.participant_ids.contains(user_b)
+                    })
+                    .cloned()
+            )
+        }
+
+        async fn list_for_user(&self, user_id: &Uuid) -> Result<Vec<ConversationDTO>, HearthError> {
+            let mut conversations: Vec<ConversationDTO> = self.conversations
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|c| c.participant_ids.contains(user_id))
+                .cloned()
+                .collect();
+
+            conversations.sort_by_key(|c| Reverse(c.last_message_at.unwrap_or(c.created_at)));
+            Ok(conversations)
+        }
+
+        async fn add_message(&self, message: MessageDTO) -> Result<(), HearthError> {
+            if
+                let Some(conversation) = self.conversations
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .find(|c| c.conversation_id == message.conversation_id)
+            {
+                conversation.last_message_at = Some(message.created_at);
+            }
+
+            self.messages.lock().unwrap().push(message);
+            Ok(())
+        }
+
+        async fn get_message(
+            &self,
+            conversation_id: &Uuid,
+            message_id: &Uuid
+        ) -> Result<MessageDTO, HearthError> {
+            self.messages
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|m| m.conversation_id == *conversation_id && m.message_id == *message_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn find_message(&self, message_id: &Uuid) -> Result<Option<MessageDTO>, HearthError> {
+            Ok(
+                self.messages
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|m| m.message_id == *message_id)
+                    .cloned()
+            )
+        }
+
+        async fn messages(
+            &self,
+            conversation_id: &Uuid,
+            cursor: Option<TimelineCursor>,
+            limit: u64
+        ) -> Result<Vec<MessageDTO>, HearthError> {
+            let mut messages: Vec<MessageDTO> = self.messages
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|m| m.conversation_id == *conversation_id)
+                .filter(|m| cursor.is_none_or(|c| c.is_before(m.created_at, m.message_id)))
+                .cloned()
+                .collect();
+
+            messages.sort_by_key(|m| Reverse((m.created_at, m.message_id)));
+            messages.truncate(limit as usize);
+            Ok(messages)
+        }
+
+        async fn set_read_position(
+            &self,
+            conversation_id: &Uuid,
+            position: ReadPositionDTO
+        ) -> Result<(), HearthError> {
+            let mut conversations = self.conversations.lock().unwrap();
+            let conversation = conversations
+                .iter_mut()
+                .find(|c| c.conversation_id == *conversation_id)
+                .ok_or_else(|| HearthError::not_found(CONVERSATION_NOT_FOUND_ERROR_CODE.into()))?;
+
+            conversation.read_positions.retain(|p| p.user_id != position.user_id);
+            conversation.read_positions.push(position);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryDmSettingsRepository {
+        permissions: Arc<Mutex<HashMap<Uuid, DmPermission>>>,
+    }
+
+    #[async_trait]
+    impl DmSettingsRepository for InMemoryDmSettingsRepository {
+        async fn get(&self, user_id: &Uuid) -> Result<DmPermission, HearthError> {
+            Ok(self.permissions.lock().unwrap().get(user_id).copied().unwrap_or_default())
+        }
+
+        async fn set(&self, user_id: &Uuid, permission: DmPermission) -> Result<(), HearthError> {
+            self.permissions.lock().unwrap().insert(*user_id, permission);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryFollowsRepository {
+        follows: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+    }
+
+    impl InMemoryFollowsRepository {
+        pub fn follow(&self, follower_id: &Uuid, followee_id: &Uuid) {
+            self.follows.lock().unwrap().push((*follower_id, *followee_id));
+        }
+    }
+
+    #[async_trait]
+    impl FollowsRepository for InMemoryFollowsRepository {
+        async fn is_following(&self, follower_id: &Uuid, followee_id: &Uuid) -> Result<bool, HearthError> {
+            Ok(self.follows.lock().unwrap().contains(&(*follower_id, *followee_id)))
+        }
+    }
+
+    type BroadcastEvents = Vec<(Vec<Uuid>, ConversationEventDTO)>;
+
+    #[derive(Default, Clone)]
+    pub struct FakeMessageBroadcaster {
+        events: Arc<Mutex<BroadcastEvents>>,
+    }
+
+    impl FakeMessageBroadcaster {
+        pub fn events(&self) -> BroadcastEvents {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl MessageBroadcaster for FakeMessageBroadcaster {
+        async fn publish(&self, recipient_ids: &[Uuid], event: ConversationEventDTO) {
+            self.events.lock().unwrap().push((recipient_ids.to_vec(), event));
+        }
+    }
+
+    type DeviceKey = (Uuid, Uuid);
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryDeviceKeysRepository {
+        devices: Arc<Mutex<Vec<DeviceKeysDTO>>>,
+        prekeys: Arc<Mutex<HashMap<DeviceKey, Vec<OneTimePrekeyDTO>>>>,
+    }
+
+    #[async_trait]
+    impl DeviceKeysRepository for InMemoryDeviceKeysRepository {
+        async fn upsert(&self, keys: DeviceKeysDTO) -> Result<(), HearthError> {
+            let mut devices = self.devices.lock().unwrap();
+            devices.retain(|d| !(d.user_id == keys.user_id && d.device_id == keys.device_id));
+            devices.push(keys);
+            Ok(())
+        }
+
+        async fn get(&self, user_id: &Uuid, device_id: &Uuid) -> Result<Option<DeviceKeysDTO>, HearthError> {
+            Ok(
+                self.devices
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|d| d.user_id == *user_id && d.device_id == *device_id)
+                    .cloned()
+            )
+        }
+
+        async fn list_for_user(&self, user_id: &Uuid) -> Result<Vec<DeviceKeysDTO>, HearthError> {
+            Ok(
+                self.devices
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|d| d.user_id == *user_id)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn add_one_time_prekeys(
+            &self,
+            user_id: &Uuid,
+            device_id: &Uuid,
+            prekeys: Vec<OneTimePrekeyDTO>
+        ) -> Result<(), HearthError> {
+            let mut all = self.prekeys.lock().unwrap();
+            let existing = all.entry((*user_id, *device_id)).or_default();
+            for prekey in prekeys {
+                if !existing.iter().any(|p| p.key_id == prekey.key_id) {
+                    existing.push(prekey);
+                }
+            }
+            Ok(())
+        }
+
+        async fn count_one_time_prekeys(&self, user_id: &Uuid, device_id: &Uuid) -> Result<u64, HearthError> {
+            Ok(
+                self.prekeys
+                    .lock()
+                    .unwrap()
+                    .get(&(*user_id, *device_id))
+                    .map_or(0, |p| p.len() as u64)
+            )
+        }
+
+        async fn claim_one_time_prekey(
+            &self,
+            user_id: &Uuid,
+            device_id: &Uuid
+        ) -> Result<Option<OneTimePrekeyDTO>, HearthError> {
+            let mut all = self.prekeys.lock().unwrap();
+            Ok(
+                all
+                    .get_mut(&(*user_id, *device_id))
+                    .filter(|p| !p.is_empty())
+                    .map(|p| p.remove(0))
+            )
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryEnvelopesRepository {
+        envelopes: Arc<Mutex<Vec<EnvelopeDTO>>>,
+    }
+
+    #[async_trait]
+    impl EnvelopesRepository for InMemoryEnvelopesRepository {
+        async fn add(&self, envelopes: Vec<EnvelopeDTO>) -> Result<(), HearthError> {
+            self.envelopes.lock().unwrap().extend(envelopes);
+            Ok(())
+        }
+
+        async fn pending(
+            &self,
+            user_id: &Uuid,
+            device_id: &Uuid,
+            limit: u64
+        ) -> Result<Vec<EnvelopeDTO>, HearthError> {
+            Ok(
+                self.envelopes
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|e| e.recipient_id == *user_id && e.recipient_device_id == *device_id)
+                    .take(limit as usize)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn delete(&self, user_id: &Uuid, device_id: &Uuid, message_id: &Uuid) -> Result<bool, HearthError> {
+            let mut envelopes = self.envelopes.lock().unwrap();
+            let before = envelopes.len();
+            envelopes.retain(
+                |e|
+                    !(
+                        e.recipient_id == *user_id &&
+                        e.recipient_device_id == *device_id &&
+                        e.message_id == *message_id
+                    )
+            );
+            Ok(envelopes.len() < before)
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryReportsRepository {
+        reports: Arc<Mutex<Vec<ReportDTO>>>,
+    }
+
+    #[async_trait]
+    impl ReportsRepository for InMemoryReportsRepository {
+        async fn create(&self, report: ReportDTO) -> Result<(), HearthError> {
+            self.reports.lock().unwrap().push(report);
+            Ok(())
+        }
+
+        async fn get(&self, report_id: &Uuid) -> Result<ReportDTO, HearthError> {
+            self.reports
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|r| r.report_id == *report_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(REPORT_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn find_open(
+            &self,
+            reporter_id: &Uuid,
+            target_type: ReportTargetType,
+            target_id: &Uuid
+        ) -> Result<Option<ReportDTO>, HearthError> {
+            Ok(
+                self.reports
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|r| {
+                        r.reporter_id == *reporter_id &&
+                            r.target_type == target_type &&
+                            r.target_id == *target_id &&
+                            r.state == ReportState::Open
+                    })
+                    .cloned()
+            )
+        }
+
+        async fn list(
+            &self,
+            state: ReportState,
+            cursor: Option<TimelineCursor>,
+            limit: u64
+        ) -> Result<Vec<ReportDTO>, HearthError> {
+            let mut reports: Vec<ReportDTO> = self.reports
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.state == state)
+                .filter(|r| cursor.is_none_or(|c| c.is_before(r.created_at, r.report_id)))
+                .cloned()
+                .collect();
+
+            reports.sort_by_key(|r| Reverse((r.created_at, r.report_id)));
+            reports.truncate(limit as usize);
+            Ok(reports)
+        }
+
+        async fn resolve(
+            &self,
+            report_id: &Uuid,
+            state: ReportState,
+            moderator_id: &Uuid,
+            at: DateTime<Utc>
+        ) -> Result<(), HearthError> {
+            let mut reports = self.reports.lock().unwrap();
+            let report = reports
+                .iter_mut()
+                .find(|r| r.report_id == *report_id)
+                .ok_or_else(|| HearthError::not_found(REPORT_NOT_FOUND_ERROR_CODE.into()))?;
+
+            report.state = state;
+            report.resolved_by = Some(*moderator_id);
+            report.resolved_at = Some(at);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryModerationLogRepository {
+        entries: Arc<Mutex<Vec<ModerationLogEntryDTO>>>,
+    }
+
+    #[async_trait]
+    impl ModerationLogRepository for InMemoryModerationLogRepository {
+        async fn append(&self, entry: ModerationLogEntryDTO) -> Result<(), HearthError> {
+            self.entries.lock().unwrap().push(entry);
+            Ok(())
+        }
+
+        async fn list(
+            &self,
+            cursor: Option<TimelineCursor>,
+            limit: u64
+        ) -> Result<Vec<ModerationLogEntryDTO>, HearthError> {
+            let mut entries: Vec<ModerationLogEntryDTO> = self.entries
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| cursor.is_none_or(|c| c.is_before(e.created_at, e.entry_id)))
+                .cloned()
+                .collect();
+
+            entries.sort_by_key(|e| Reverse((e.created_at, e.entry_id)));
+            entries.truncate(limit as usize);
+            Ok(entries)
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryKeywordFiltersRepository {
+        filters: Arc<Mutex<Vec<KeywordFilterDTO>>>,
+    }
+
+    #[async_trait]
+    impl KeywordFiltersRepository for InMemoryKeywordFiltersRepository {
+        async fn create(&self, filter: KeywordFilterDTO) -> Result<(), HearthError> {
+            self.filters.lock().unwrap().push(filter);
+            Ok(())
+        }
+
+        async fn list(&self, owner_id: &Uuid) -> Result<Vec<KeywordFilterDTO>, HearthError> {
+            Ok(
+                self.filters
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|f| f.owner_id == *owner_id)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn delete(&self, owner_id: &Uuid, filter_id: &Uuid) -> Result<(), HearthError> {
+            let mut filters = self.filters.lock().unwrap();
+            let before = filters.len();
+            filters.retain(|f| !(f.owner_id == *owner_id && f.filter_id == *filter_id));
+
+            if filters.len() == before {
+                return Err(HearthError::not_found(FILTER_NOT_FOUND_ERROR_CODE.into()));
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Tokens left and when they were counted.
+    type BucketState = (f64, DateTime<Utc>);
+
+    /// Same bucket arithmetic as the Redis limiter, tokens are fractional
+    /// between two refills.
+    #[derive(Default, Clone)]
+    pub struct InMemoryRateLimiter {
+        buckets: Arc<Mutex<HashMap<String, BucketState>>>,
+    }
+
+    #[async_trait]
+    impl RateLimiter for InMemoryRateLimiter {
+        async fn take(&self, key: &str, bucket: TokenBucket) -> Result<RateLimitDTO, HearthError> {
+            let now = Utc::now();
+            let capacity = bucket.capacity as f64;
+            let refill = bucket.refill_seconds as f64;
+
+            let mut buckets = self.buckets.lock().unwrap();
+            let (tokens, at) = buckets.get(key).copied().unwrap_or((capacity, now));
+            let elapsed = ((now - at).num_milliseconds() as f64) / 1000.0;
+            let mut tokens = (tokens + elapsed / refill).min(capacity);
+
+            let retry_after_seconds = if tokens >= 1.0 {
+                tokens -= 1.0;
+                None
+            } else {
+                Some(((1.0 - tokens) * refill).ceil() as u64)
+            };
+            buckets.insert(key.into(), (tokens, now));
+
+            Ok(RateLimitDTO {
+                limit: bucket.capacity,
+                remaining: tokens.floor() as u32,
+                reset_seconds: ((capacity - tokens) * refill).ceil() as u64,
+                retry_after_seconds,
+            })
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemorySignupChallengesRepository {
+        challenges: Arc<Mutex<HashMap<Uuid, SignupChallengeDTO>>>,
+    }
+
+    #[async_trait]
+    impl SignupChallengesRepository for InMemorySignupChallengesRepository {
+        async fn create(&self, challenge: &SignupChallengeDTO) -> Result<(), HearthError> {
+            self.challenges.lock().unwrap().insert(challenge.challenge_id, challenge.clone());
+            Ok(())
+        }
+
+        async fn take(
+            &self,
+            challenge_id: &Uuid
+        ) -> Result<Option<SignupChallengeDTO>, HearthError> {
+            Ok(self.challenges.lock().unwrap().remove(challenge_id))
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryTwoFactorRepository {
+        two_factors: Arc<Mutex<HashMap<Uuid, TwoFactorDTO>>>,
+        /// Code hash to its owner and whether it was used.
+        recovery_codes: Arc<Mutex<HashMap<String, (Uuid, bool)>>>,
+    }
+
+    #[async_trait]
+    impl TwoFactorRepository for InMemoryTwoFactorRepository {
+        async fn get(&self, user_id: &Uuid) -> Result<Option<TwoFactorDTO>, HearthError> {
+            Ok(self.two_factors.lock().unwrap().get(user_id).cloned())
+        }
+
+        async fn save_pending(
+            &self,
+            user_id: &Uuid,
+            encrypted_secret: Vec<u8>
+        ) -> Result<(), HearthError> {
+            self.two_factors.lock().unwrap().insert(*user_id, TwoFactorDTO {
+                user_id: *user_id,
+                encrypted_secret,
+                enabled_at: None,
+                last_step: None,
+            });
+            Ok(())
+        }
+
+        async fn enable(
+            &self,
+            user_id: &Uuid,
+            step: i64,
+            recovery_code_hashes: Vec<String>,
+            at: DateTime<Utc>
+        ) -> Result<(), HearthError> {
+            if let Some(two_factor) = self.two_factors.lock().unwrap().get_mut(user_id) {
+                two_factor.enabled_at = Some(at);
+                two_factor.last_step = Some(step);
+            }
+            let mut recovery_codes = self.recovery_codes.lock().unwrap();
+            recovery_codes.retain(|_, (owner, _)| owner != user_id);
+            for hash in recovery_code_hashes {
+                recovery_codes.insert(hash, (*user_id, false));
+            }
+            Ok(())
+        }
+
+        async fn record_step(&self, user_id: &Uuid, step: i64) -> Result<bool, HearthError> {
+            let mut two_factors = self.two_factors.lock().unwrap();
+            match two_factors.get_mut(user_id) {
+                Some(two_factor) if two_factor.last_step.is_none_or(|last| last < step) => {
+                    two_factor.last_step = Some(step);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn use_recovery_code(
+            &self,
+            user_id: &Uuid,
+            code_hash: &str,
+            _at: DateTime<Utc>
+        ) -> Result<bool, HearthError> {
+            match self.recovery_codes.lock().unwrap().get_mut(code_hash) {
+                Some((owner, used)) if owner == user_id && !*used => {
+                    *used = true;
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn disable(&self, user_id: &Uuid) -> Result<(), HearthError> {
+            self.two_factors.lock().unwrap().remove(user_id);
+            self.recovery_codes.lock().unwrap().retain(|_, (owner, _)| owner != user_id);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryLoginChallengesRepository {
+        challenges: Arc<Mutex<HashMap<String, Uuid>>>,
+        failures: Arc<Mutex<HashMap<String, u32>>>,
+    }
+
+    #[async_trait]
+    impl LoginChallengesRepository for InMemoryLoginChallengesRepository {
+        async fn create(
+            &self,
+            token: &str,
+            user_id: &Uuid,
+            _ttl_seconds: u64
+        ) -> Result<(), HearthError> {
+            self.challenges.lock().unwrap().insert(token.to_string(), *user_id);
+            Ok(())
+        }
+
+        async fn get_user_id(&self, token: &str) -> Result<Option<Uuid>, HearthError> {
+            Ok(self.challenges.lock().unwrap().get(token).copied())
+        }
+
+        async fn record_failure(&self, token: &str, _ttl_seconds: u64) -> Result<u32, HearthError> {
+            let mut failures = self.failures.lock().unwrap();
+            let count = failures.entry(token.to_string()).or_default();
+            *count += 1;
+            Ok(*count)
+        }
+
+        async fn delete(&self, token: &str) -> Result<(), HearthError> {
+            self.challenges.lock().unwrap().remove(token);
+            self.failures.lock().unwrap().remove(token);
+            Ok(())
+        }
+    }
+
+    /// Not encryption, only makes sure features never use the stored bytes as the secret.
+    #[derive(Default, Clone)]
+    pub struct XorSecretCipher {}
+
+    impl SecretCipher for XorSecretCipher {
+        fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, HearthError> {
+            Ok(
+                plaintext
+                    .iter()
+                    .map(|b| b ^ 0x5a)
+                    .collect()
+            )
+        }
+
+        fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, HearthError> {
+            self.encrypt(ciphertext)
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryPasskeysRepository {
+        passkeys: Arc<Mutex<Vec<PasskeyDTO>>>,
+    }
+
+    #[async_trait]
+    impl PasskeysRepository for InMemoryPasskeysRepository {
+        async fn create(&self, passkey: PasskeyDTO) -> Result<(), HearthError> {
+            self.passkeys.lock().unwrap().push(passkey);
+            Ok(())
+        }
+
+        async fn list(&self, user_id: &Uuid) -> Result<Vec<PasskeyDTO>, HearthError> {
+            Ok(
+                self.passkeys
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|passkey| passkey.user_id == *user_id)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn get_by_credential_id(
+            &self,
+            credential_id: &[u8]
+        ) -> Result<Option<PasskeyDTO>, HearthError> {
+            Ok(
+                self.passkeys
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|passkey| passkey.credential_id == credential_id)
+                    .cloned()
+            )
+        }
+
+        async fn update_sign_count(
+            &self,
+            passkey_id: &Uuid,
+            sign_count: u32,
+            at: DateTime<Utc>
+        ) -> Result<bool, HearthError> {
+            let mut passkeys = self.passkeys.lock().unwrap();
+            match passkeys.iter_mut().find(|passkey| passkey.passkey_id == *passkey_id) {
+                Some(passkey) if sign_count == 0 || passkey.sign_count < sign_count => {
+                    passkey.sign_count = sign_count;
+                    passkey.last_used_at = Some(at);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryWebAuthnChallengesRepository {
+        challenges: Arc<Mutex<HashMap<String, WebAuthnChallengeDTO>>>,
+    }
+
+    #[async_trait]
+    impl WebAuthnChallengesRepository for InMemoryWebAuthnChallengesRepository {
+        async fn create(
+            &self,
+            challenge: &WebAuthnChallengeDTO,
+            _ttl_seconds: u64
+        ) -> Result<(), HearthError> {
+            self.challenges.lock().unwrap().insert(challenge.challenge.clone(), challenge.clone());
+            Ok(())
+        }
+
+        async fn take(&self, challenge: &str) -> Result<Option<WebAuthnChallengeDTO>, HearthError> {
+            Ok(self.challenges.lock().unwrap().remove(challenge))
+        }
+    }
+
+    /// ES256 authenticator with `none` attestation, as a browser would drive it.
+    pub struct SoftwareAuthenticator {
+        key_pair: ring::signature::EcdsaKeyPair,
+        rp_id: String,
+        origin: String,
+        pub credential_id: Vec<u8>,
+        pub sign_count: u32,
+    }
+
+    impl SoftwareAuthenticator {
+        pub fn new(policy: &PasskeyPolicy) -> Self {
+            use ring::{ rand::SystemRandom, signature::{ ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair } };
+
+            let random = SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &random).unwrap();
+            let key_pair = EcdsaKeyPair::from_pkcs8(
+                &ECDSA_P256_SHA256_ASN1_SIGNING,
+                pkcs8.as_ref(),
+                &random
+            ).unwrap();
+
+            Self {
+                key_pair,
+                rp_id: policy.rp_id.clone(),
+                origin: policy.origins[0].clone(),
+                credential_id: Uuid::new_v4().as_bytes().to_vec(),
+                sign_count: 0,
+            }
+        }
+
+        pub fn public_key(&self) -> Vec<u8> {
+            use ring::signature::KeyPair;
+
+            self.key_pair.public_key().as_ref().to_vec()
+        }
+
+        /// Client data JSON and attestation object of a registration.
+        pub fn attestation(&mut self, challenge: &str) -> (Vec<u8>, Vec<u8>) {
+            use ciborium::Value;
+
+            let public_key = self.public_key();
+            let cose_key = Value::Map(
+                vec![
+                    (Value::from(1), Value::from(2)),
+                    (Value::from(3), Value::from(-7)),
+                    (Value::from(-1), Value::from(1)),
+                    (Value::from(-2), Value::Bytes(public_key[1..33].to_vec())),
+                    (Value::from(-3), Value::Bytes(public_key[33..].to_vec()))
+                ]
+            );
+
+            let mut auth_data = self.authenticator_data(0x41);
+            auth_data.extend_from_slice(&[0u8; 16]);
+            auth_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+            auth_data.extend_from_slice(&self.credential_id);
+            ciborium::ser::into_writer(&cose_key, &mut auth_data).unwrap();
+
+            let attestation_object = Value::Map(
+                vec![
+                    (Value::from("fmt"), Value::from("none")),
+                    (Value::from("attStmt"), Value::Map(vec![])),
+                    (Value::from("authData"), Value::Bytes(auth_data))
+                ]
+            );
+            let mut bytes = vec![];
+            ciborium::ser::into_writer(&attestation_object, &mut bytes).unwrap();
+
+            (self.client_data("webauthn.create", challenge), bytes)
+        }
+
+        /// Client data JSON, authenticator data and signature of a login.
+        pub fn assertion(&mut self, challenge: &str) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+            self.sign_count += 1;
+            let client_data_json = self.client_data("webauthn.get", challenge);
+            let authenticator_data = self.authenticator_data(0x01);
+
+            let mut message = authenticator_data.clone();
+            message.extend_from_slice(&sha2::Sha256::digest(&client_data_json));
+            let signature = self.key_pair
+                .sign(&ring::rand::SystemRandom::new(), &message)
+                .unwrap()
+                .as_ref()
+                .to_vec();
+
+            (client_data_json, authenticator_data, signature)
+        }
+
+        pub fn encoded_credential_id(&self) -> String {
+            WebAuthn::encode(&self.credential_id)
+        }
+
+        fn client_data(&self, kind: &str, challenge: &str) -> Vec<u8> {
+            serde_json
+                ::to_vec(
+                    &serde_json::json!({
+                        "type": kind,
+                        "challenge": challenge,
+                        "origin": self.origin,
+                        "crossOrigin": false,
+                    })
+                )
+                .unwrap()
+        }
+
+        fn authenticator_data(&self, flags: u8) -> Vec<u8> {
+            let mut data = sha2::Sha256::digest(self.rp_id.as_bytes()).to_vec();
+            data.push(flags);
+            data.extend_from_slice(&self.sign_count.to_be_bytes());
+            data
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryLinkedIdentitiesRepository {
+        identities: Arc<Mutex<Vec<LinkedIdentityDTO>>>,
+    }
+
+    #[async_trait]
+    impl LinkedIdentitiesRepository for InMemoryLinkedIdentitiesRepository {
+        async fn get(
+            &self,
+            provider: &str,
+            subject: &str
+        ) -> Result<Option<LinkedIdentityDTO>, HearthError> {
+            Ok(
+                self.identities
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|identity| identity.provider == provider && identity.subject == subject)
+                    .cloned()
+            )
+        }
+
+        async fn create(&self, identity: LinkedIdentityDTO) -> Result<(), HearthError> {
+            self.identities.lock().unwrap().push(identity);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryOidcStatesRepository {
+        states: Arc<Mutex<HashMap<String, OidcStateDTO>>>,
+        pending_signups: Arc<Mutex<HashMap<String, PendingOidcSignupDTO>>>,
+    }
+
+    #[async_trait]
+    impl OidcStatesRepository for InMemoryOidcStatesRepository {
+        async fn create_state(&self, state: &OidcStateDTO, _ttl_seconds: u64) -> Result<(), HearthError> {
+            self.states.lock().unwrap().insert(state.state.clone(), state.clone());
+            Ok(())
+        }
+
+        async fn take_state(&self, state: &str) -> Result<Option<OidcStateDTO>, HearthError> {
+            Ok(self.states.lock().unwrap().remove(state))
+        }
+
+        async fn create_pending_signup(
+            &self,
+            token: &str,
+            signup: &PendingOidcSignupDTO,
+            _ttl_seconds: u64
+        ) -> Result<(), HearthError> {
+            self.pending_signups.lock().unwrap().insert(token.to_string(), signup.clone());
+            Ok(())
+        }
+
+        async fn take_pending_signup(
+            &self,
+            token: &str
+        ) -> Result<Option<PendingOidcSignupDTO>, HearthError> {
+            Ok(self.pending_signups.lock().unwrap().remove(token))
+        }
+    }
+
+    /// Provider issuing ES256 ID tokens for `claims`, checks PKCE like a real one would.
+    #[derive(Clone)]
+    pub struct MockOidcProvider {
+        key_pair: Arc<ring::signature::EcdsaKeyPair>,
+        provider: OidcProvider,
+        pub claims: Arc<Mutex<OidcClaimsDTO>>,
+        /// Code to the nonce and PKCE challenge of its authorization request.
+        codes: Arc<Mutex<HashMap<String, (String, String)>>>,
+    }
+
+    impl MockOidcProvider {
+        pub fn new(provider: &OidcProvider, claims: OidcClaimsDTO) -> Self {
+            use ring::{ rand::SystemRandom, signature::{ ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair } };
+
+            let random = SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &random).unwrap();
+            let key_pair = EcdsaKeyPair::from_pkcs8(
+                &ECDSA_P256_SHA256_FIXED_SIGNING,
+                pkcs8.as_ref(),
+                &random
+            ).unwrap();
+
+            Self {
+                key_pair: Arc::new(key_pair),
+                provider: provider.clone(),
+                claims: Arc::new(Mutex::new(claims)),
+                codes: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+
+        /// Consent screen: code and state the provider redirects back with.
+        pub fn authorize(&self, authorization_url: &str) -> (String, String) {
+            let url = url::Url::parse(authorization_url).unwrap();
+            let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+            assert_eq!(params["response_type"], "code");
+            assert_eq!(params["client_id"], self.provider.client_id);
+            assert_eq!(params["redirect_uri"], self.provider.redirect_uri);
+            assert_eq!(params["code_challenge_method"], "S256");
+
+            let code = Oidc::generate_token();
+            self.codes
+                .lock()
+                .unwrap()
+                .insert(code.clone(), (params["nonce"].clone(), params["code_challenge"].clone()));
+
+            (code, params["state"].clone())
+        }
+
+        fn id_token(&self, nonce: &str) -> String {
+            use base64::{ Engine, engine::general_purpose::URL_SAFE_NO_PAD };
+
+            let claims = self.claims.lock().unwrap().clone();
+            let now = Utc::now().timestamp();
+            let header = serde_json::json!({ "alg": "ES256", "kid": "mock-key", "typ": "JWT" });
+            let payload = serde_json::json!({
+                "iss": self.provider.issuer,
+                "sub": claims.subject,
+                "aud": self.provider.client_id,
+                "exp": now + 300,
+                "iat": now,
+                "nonce": nonce,
+                "email": claims.email,
+                "email_verified": claims.email_verified,
+            });
+
+            let message = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(header.to_string()),
+                URL_SAFE_NO_PAD.encode(payload.to_string())
+            );
+            let signature = self.key_pair
+                .sign(&ring::rand::SystemRandom::new(), message.as_bytes())
+                .unwrap();
+
+            format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        }
+    }
+
+    #[async_trait]
+    impl OidcClient for MockOidcProvider {
+        async fn discover(&self, issuer: &str) -> Result<OidcDiscoveryDTO, HearthError> {
+            Ok(OidcDiscoveryDTO {
+                issuer: issuer.to_string(),
+                authorization_endpoint: format!("{}/authorize", issuer),
+                token_endpoint: format!("{}/token", issuer),
+                jwks_uri: format!("{}/jwks", issuer),
+            })
+        }
+
+        async fn exchange_code(
+            &self,
+            _discovery: &OidcDiscoveryDTO,
+            provider: &OidcProvider,
+            code: &str,
+            code_verifier: &str
+        ) -> Result<String, HearthError> {
+            let failed = || HearthError::Unauthorized(OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE.into());
+
+            let (nonce, code_challenge) = self.codes
+                .lock()
+                .unwrap()
+                .remove(code)
+                .ok_or_else(failed)?;
+            if
+                provider.client_secret != self.provider.client_secret ||
+                Oidc::code_challenge(code_verifier) != code_challenge
+            {
+                return Err(failed());
+            }
+
+            Ok(self.id_token(&nonce))
+        }
+
+        async fn jwks(&self, _jwks_uri: &str) -> Result<Vec<JwkDTO>, HearthError> {
+            use base64::{ Engine, engine::general_purpose::URL_SAFE_NO_PAD };
+            use ring::signature::KeyPair;
+
+            let public_key = self.key_pair.public_key().as_ref();
+
+            Ok(
+                vec![JwkDTO {
+                    kty: "EC".into(),
+                    kid: Some("mock-key".into()),
+                    alg: Some("ES256".into()),
+                    crv: Some("P-256".into()),
+                    x: Some(URL_SAFE_NO_PAD.encode(&public_key[1..33])),
+                    y: Some(URL_SAFE_NO_PAD.encode(&public_key[33..])),
+                    ..Default::default()
+                }]
+            )
+        }
+    }
+
+    #[derive(Default)]
+    pub struct InMemoryOAuthAppsRepository {
+        apps: Mutex<HashMap<Uuid, OAuthAppDTO>>,
+    }
+
+    #[async_trait]
+    impl OAuthAppsRepository for InMemoryOAuthAppsRepository {
+        async fn create(&self, app: OAuthAppDTO) -> Result<(), HearthError> {
+            self.apps.lock().unwrap().insert(app.client_id, app);
+            Ok(())
+        }
+
+        async fn get(&self, client_id: &Uuid) -> Result<Option<OAuthAppDTO>, HearthError> {
+            Ok(self.apps.lock().unwrap().get(client_id).cloned())
+        }
+
+        async fn count_by_owner(&self, owner_id: &Uuid) -> Result<u64, HearthError> {
+            Ok(
+                self.apps
+                    .lock()
+                    .unwrap()
+                    .values()
+                    .filter(|app| app.owner_id == *owner_id)
+                    .count() as u64
+            )
+        }
+    }
+
+    #[derive(Default)]
+    pub struct InMemoryOAuthCodesRepository {
+        codes: Mutex<HashMap<String, OAuthCodeDTO>>,
+    }
+
+    #[async_trait]
+    impl OAuthCodesRepository for InMemoryOAuthCodesRepository {
+        async fn create(
+            &self,
+            code_hash: &str,
+            code: &OAuthCodeDTO,
+            _ttl_seconds: u64
+        ) -> Result<(), HearthError> {
+            self.codes.lock().unwrap().insert(code_hash.to_string(), code.clone());
+            Ok(())
+        }
+
+        async fn take(&self, code_hash: &str) -> Result<Option<OAuthCodeDTO>, HearthError> {
+            Ok(self.codes.lock().unwrap().remove(code_hash))
+        }
+    }
+
+    #[derive(Default)]
+    pub struct InMemoryOAuthTokensRepository {
+        tokens: Mutex<HashMap<String, OAuthTokenDTO>>,
+    }
+
+    #[async_trait]
+    impl OAuthTokensRepository for InMemoryOAuthTokensRepository {
+        async fn create(&self, token: OAuthTokenDTO) -> Result<(), HearthError> {
+            self.tokens.lock().unwrap().insert(token.token_hash.clone(), token);
+            Ok(())
+        }
+
+        async fn get(&self, token_hash: &str) -> Result<Option<OAuthTokenDTO>, HearthError> {
+            Ok(self.tokens.lock().unwrap().get(token_hash).cloned())
+        }
+
+        async fn revoke(&self, token_hash: &str, at: DateTime<Utc>) -> Result<(), HearthError> {
+            if let Some(token) = self.tokens.lock().unwrap().get_mut(token_hash) {
+                token.revoked_at.get_or_insert(at);
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    pub struct InMemoryPersonalAccessTokensRepository {
+        tokens: Mutex<Vec<PersonalAccessTokenDTO>>,
+    }
+
+    #[async_trait]
+    impl PersonalAccessTokensRepository for InMemoryPersonalAccessTokensRepository {
+        async fn create(&self, token: PersonalAccessTokenDTO) -> Result<(), HearthError> {
+            self.tokens.lock().unwrap().push(token);
+            Ok(())
+        }
+
+        async fn get_by_hash(
+            &self,
+            token_hash: &str
+        ) -> Result<Option<PersonalAccessTokenDTO>, HearthError> {
+            Ok(
+                self.tokens
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|token| token.token_hash == token_hash)
+                    .cloned()
+            )
+        }
+
+        async fn list_by_user(
+            &self,
+            user_id: &Uuid
+        ) -> Result<Vec<PersonalAccessTokenDTO>, HearthError> {
+            let mut tokens: Vec<PersonalAccessTokenDTO> = self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|token| token.user_id == *user_id)
+                .cloned()
+                .collect();
+            tokens.sort_by_key(|token| Reverse(token.created_at));
+            Ok(tokens)
+        }
+
+        async fn count_by_user(&self, user_id: &Uuid) -> Result<u64, HearthError> {
+            Ok(
+                self.tokens
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|token| token.user_id == *user_id)
+                    .count() as u64
+            )
+        }
+
+        async fn delete(&self, user_id: &Uuid, token_id: &Uuid) -> Result<(), HearthError> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let count = tokens.len();
+            tokens.retain(|token| !(token.user_id == *user_id && token.token_id == *token_id));
+
+            if tokens.len() == count {
+                return Err(HearthError::not_found(PERSONAL_ACCESS_TOKEN_NOT_FOUND_ERROR_CODE.into()));
+            }
+            Ok(())
+        }
+
+        async fn touch(&self, token_id: &Uuid, at: DateTime<Utc>) -> Result<(), HearthError> {
+            if
+                let Some(token) = self.tokens
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .find(|token| token.token_id == *token_id)
+            {
+                token.last_used_at = Some(at);
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryDataExportsRepository {
+        exports: Arc<Mutex<Vec<DataExportDTO>>>,
+    }
+
+    #[async_trait]
+    impl DataExportsRepository for InMemoryDataExportsRepository {
+        async fn create(&self, export: DataExportDTO) -> Result<(), HearthError> {
+            self.exports.lock().unwrap().push(export);
+            Ok(())
+        }
+
+        async fn get(&self, export_id: &Uuid) -> Result<DataExportDTO, HearthError> {
+            self.exports
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|export| export.export_id == *export_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(DATA_EXPORT_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn find_active(
+            &self,
+            user_id: &Uuid,
+            at: DateTime<Utc>
+        ) -> Result<Option<DataExportDTO>, HearthError> {
+            Ok(
+                self.exports
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|export| {
+                        export.user_id == *user_id &&
+                            export.expires_at.is_none_or(|expires_at| expires_at > at)
+                    })
+                    .cloned()
+            )
+        }
+
+        async fn list_pending(&self, limit: u64) -> Result<Vec<DataExportDTO>, HearthError> {
+            Ok(
+                self.exports
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|export| export.status == DataExportStatus::Pending)
+                    .take(limit as usize)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn mark_ready(
+            &self,
+            export_id: &Uuid,
+            token_hash: &str,
+            expires_at: DateTime<Utc>
+        ) -> Result<(), HearthError> {
+            let mut exports = self.exports.lock().unwrap();
+            let export = exports
+                .iter_mut()
+                .find(|export| export.export_id == *export_id)
+                .ok_or_else(|| HearthError::not_found(DATA_EXPORT_NOT_FOUND_ERROR_CODE.into()))?;
+            export.status = DataExportStatus::Ready;
+            export.token_hash = Some(token_hash.into());
+            export.expires_at = Some(expires_at);
+            Ok(())
+        }
+
+        async fn list_expired(
+            &self,
+            at: DateTime<Utc>,
+            limit: u64
+        ) -> Result<Vec<DataExportDTO>, HearthError> {
+            Ok(
+                self.exports
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|export| export.expires_at.is_some_and(|expires_at| expires_at <= at))
+                    .take(limit as usize)
+                    .cloned()
+                    .collect()
+            )
+        }
+
+        async fn delete(&self, export_id: &Uuid) -> Result<(), HearthError> {
+            self.exports.lock().unwrap().retain(|export| export.export_id != *export_id);
+            Ok(())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryPersonalDataRepository {
+        pub data: Arc<Mutex<HashMap<Uuid, PersonalDataDTO>>>,
+    }
+
+    #[async_trait]
+    impl PersonalDataRepository for InMemoryPersonalDataRepository {
+        async fn collect(&self, user_id: &Uuid) -> Result<PersonalDataDTO, HearthError> {
+            self.data
+                .lock()
+                .unwrap()
+                .get(user_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()))
+        }
+    }
+
+    /// Not a ZIP, a JSON map of the paths to the file contents so tests can look inside.
+    pub struct FakeArchiveWriter;
+
+    #[async_trait]
+    impl ArchiveWriter for FakeArchiveWriter {
+        async fn write(&self, files: Vec<ArchiveFileDTO>) -> Result<Vec<u8>, HearthError> {
+            let files: HashMap<String, Vec<u8>> = files
+                .into_iter()
+                .map(|file| (file.path, file.bytes))
+                .collect();
+            Ok(serde_json::to_vec(&files).unwrap())
+        }
+    }
+
+    #[derive(Default)]
+    pub struct InMemoryImportedPostsRepository {
+        imported: Arc<Mutex<Vec<ImportedPostDTO>>>,
+    }
+
+    #[async_trait]
+    impl ImportedPostsRepository for InMemoryImportedPostsRepository {
+        async fn create(&self, imported: ImportedPostDTO) -> Result<(), HearthError> {
+            self.imported.lock().unwrap().push(imported);
+            Ok(())
+        }
+
+        async fn find_imported(
+            &self,
+            user_id: &Uuid,
+            source: ImportSource,
+            external_ids: &[String]
+        ) -> Result<Vec<String>, HearthError> {
+            Ok(
+                self.imported
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|imported| {
+                        imported.user_id == *user_id &&
+                            imported.source == source &&
+                            external_ids.contains(&imported.external_id)
+                    })
+                    .map(|imported| imported.external_id.clone())
+                    .collect()
+            )
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryImportsRepository {
+        imports: Arc<Mutex<Vec<ImportDTO>>>,
+    }
+
+    impl InMemoryImportsRepository {
+        fn update(&self, import_id: &Uuid, update: impl FnOnce(&mut ImportDTO)) {
+            let mut imports = self.imports.lock().unwrap();
+            if let Some(import) = imports.iter_mut().find(|import| import.import_id == *import_id) {
+                update(import);
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ImportsRepository for InMemoryImportsRepository {
+        async fn create(&self, import: ImportDTO) -> Result<(), HearthError> {
+            self.imports.lock().unwrap().push(import);
+            Ok(())
+        }
+
+        async fn get(&self, import_id: &Uuid) -> Result<ImportDTO, HearthError> {
+            self.imports
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|import| import.import_id == *import_id)
+                .cloned()
+                .ok_or_else(|| HearthError::not_found(IMPORT_NOT_FOUND_ERROR_CODE.into()))
+        }
+
+        async fn mark_done(&self, import_id: &Uuid, report: &ImportReportDTO) -> Result<(), HearthError> {
+            self.update(import_id, |import| {
+                import.status = ImportStatus::Done;
+                import.report = Some(report.clone());
+            });
+            Ok(())
+        }
+
+        async fn mark_failed(&self, import_id: &Uuid, error: &str) -> Result<(), HearthError> {
+            self.update(import_id, |import| {
+                import.status = ImportStatus::Failed;
+                import.error = Some(error.into());
+            });
+            Ok(())
+        }
+    }
+
+    /// Hands back the same posts whatever the archive, unless it is empty.
+    #[derive(Default)]
+    pub struct FakeArchiveReader {
+        pub posts: Vec<ArchivedPostDTO>,
+    }
+
+    #[async_trait]
+    impl ArchiveReader for FakeArchiveReader {
+        async fn read(
+            &self,
+            _source: ImportSource,
+            bytes: Vec<u8>
+        ) -> Result<Vec<ArchivedPostDTO>, HearthError> {
+            if bytes.is_empty() {
+                return Err(HearthError::Domain(IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE.into()));
+            }
+            Ok(self.posts.clone())
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct InMemoryJobQueue {
+        pub jobs: Arc<Mutex<Vec<JobDTO>>>,
+        pub dead: Arc<Mutex<Vec<JobDTO>>>,
+    }
+
+    impl InMemoryJobQueue {
+        fn update(&self, job_id: &Uuid, update: impl FnOnce(&mut JobDTO)) {
+            if let Some(job) = self.jobs.lock().unwrap().iter_mut().find(|job| job.job_id == *job_id) {
+                update(job);
+            }
+        }
+    }
+
+    #[async_trait]
+    impl JobQueue for InMemoryJobQueue {
+        async fn enqueue(&self, job: JobDTO) -> Result<(), HearthError> {
+            let mut jobs = self.jobs.lock().unwrap();
+            if !jobs.iter().any(|queued| queued.job_id == job.job_id) {
+                jobs.push(job);
+            }
+            Ok(())
+        }
+
+        async fn claim(
+            &self,
+            at: DateTime<Utc>,
+            lease_until: DateTime<Utc>,
+            limit: u64
+        ) -> Result<Vec<JobDTO>, HearthError> {
+            let mut jobs = self.jobs.lock().unwrap();
+            jobs.sort_by_key(|job| job.run_at);
+
+            Ok(
+                jobs
+                    .iter_mut()
+                    .filter(|job| job.run_at <= at)
+                    .take(limit as usize)
+                    .map(|job| {
+                        let claimed = job.clone();
+                        job.run_at = lease_until;
+                        claimed
+                    })
+                    .collect()
+            )
+        }
+
+        async fn complete(&self, job_id: &Uuid) -> Result<(), HearthError> {
+            self.jobs.lock().unwrap().retain(|job| job.job_id != *job_id);
+            Ok(())
+        }
+
+        async fn retry(
+            &self,
+            job_id: &Uuid,
+            attempts: u32,
+            run_at: DateTime<Utc>,
+            error: &str
+        ) -> Result<(), HearthError> {
+            self.update(job_id, |job| {
+                job.attempts = attempts;
+                job.run_at = run_at;
+                job.last_error = Some(error.into());
+            });
+            Ok(())
+        }
+
+        async fn dead_letter(
+            &self,
+            job_id: &Uuid,
+            attempts: u32,
+            error: &str,
+            _at: DateTime<Utc>
+        ) -> Result<(), HearthError> {
+            let mut jobs = self.jobs.lock().unwrap();
+            if let Some(index) = jobs.iter().position(|job| job.job_id == *job_id) {
+                let mut job = jobs.remove(index);
+                job.attempts = attempts;
+                job.last_error = Some(error.into());
+                self.dead.lock().unwrap().push(job);
+            }
+            Ok(())
+        }
+    }
+
+    /// Outbox rows as `(event, leased until, dispatched at)`.
+    type OutboxEntries = Vec<(EventDTO, DateTime<Utc>, Option<DateTime<Utc>>)>;
+
+    #[derive(Debug, Default, Clone)]
+    pub struct InMemoryOutboxRepository {
+        entries: Arc<Mutex<OutboxEntries>>,
+    }
+
+    impl InMemoryOutboxRepository {
+        pub fn append(&self, events: Vec<EventDTO>) {
+            let mut entries = self.entries.lock().unwrap();
+            for event in events {
+                let available_at = event.occurred_at;
+                entries.push((event, available_at, None));
+            }
+        }
+
+        pub fn events(&self) -> Vec<EventDTO> {
+            self.entries
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(event, _, _)| event.clone())
+                .collect()
+        }
+
+        pub fn is_dispatched(&self, event_id: &Uuid) -> bool {
+            self.entries
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(event, _, dispatched_at)| event.event_id == *event_id && dispatched_at.is_some())
+        }
+    }
+
+    #[async_trait]
+    impl OutboxRepository for InMemoryOutboxRepository {
+        async fn claim(
+            &self,
+            at: DateTime<Utc>,
+            lease_until: DateTime<Utc>,
+            limit: u64
+        ) -> Result<Vec<EventDTO>, HearthError> {
+            Ok(
+                self.entries
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .filter(|(_, available_at, dispatched_at)| {
+                        dispatched_at.is_none() && *available_at <= at
+                    })
+                    .take(limit as usize)
+                    .map(|(event, available_at, _)| {
+                        *available_at = lease_until;
+                        event.clone()
+                    })
+                    .collect()
+            )
+        }
+
+        async fn mark_dispatched(&self, event_id: &Uuid, at: DateTime<Utc>) -> Result<(), HearthError> {
+            for (event, _, dispatched_at) in self.entries.lock().unwrap().iter_mut() {
+                if event.event_id == *event_id {
+                    *dispatched_at = Some(at);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Records the published events, fails while `down` is set.
+    #[derive(Default, Clone)]
+    pub struct InMemoryEventPublisher {
+        pub published: Arc<Mutex<Vec<EventDTO>>>,
+        pub down: Arc<Mutex<bool>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for InMemoryEventPublisher {
+        async fn publish(&self, event: &EventDTO) -> Result<(), HearthError> {
+            if *self.down.lock().unwrap() {
+                return Err(HearthError::unexpected("EVENT_PUBLISHER_DOWN".into(), None));
+            }
+
+            self.published.lock().unwrap().push(event.clone());
+            Ok(())
+        }
     }
 }
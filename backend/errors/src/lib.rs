@@ -1,4 +1,7 @@
-use actix_web::{HttpResponse, ResponseError, http::StatusCode};
+use actix_web::{
+    HttpResponse, ResponseError,
+    http::{StatusCode, header},
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use validator::ValidationErrors;
@@ -31,6 +34,13 @@ pub enum HearthError {
     Unexpected(ErrorCode),
     #[error("validation error")]
     Validation(ErrorCode, ValidationErrors),
+    #[error("unauthorized")]
+    Unauthorized(ErrorCode),
+    #[error("forbidden")]
+    Forbidden(ErrorCode),
+    /// Carries the number of seconds to wait before retrying.
+    #[error("too many requests")]
+    TooManyRequests(ErrorCode, u64),
 }
 
 impl HearthError {
@@ -54,11 +64,18 @@ impl ResponseError for HearthError {
             }
             HearthError::Validation(_, _) => StatusCode::BAD_REQUEST,
             HearthError::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            HearthError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            HearthError::Forbidden(_) => StatusCode::FORBIDDEN,
+            HearthError::TooManyRequests(_, _) => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
         let status_code = self.status_code();
-        HttpResponse::build(status_code).json(serde_json::json!(self))
+        let mut response = HttpResponse::build(status_code);
+        if let HearthError::TooManyRequests(_, retry_after) = self {
+            response.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+        response.json(serde_json::json!(self))
     }
 }
@@ -0,0 +1,233 @@
+use std::io::Cursor;
+
+use image::{
+    DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, codecs::jpeg::JpegEncoder,
+};
+use thiserror::Error;
+
+/// Images bigger than this on either side are rejected before being decoded.
+pub const MAX_DIMENSION: u32 = 8192;
+
+const JPEG_QUALITY: u8 = 85;
+const BLURHASH_COMPONENTS: (u32, u32) = (4, 3);
+const BLURHASH_SOURCE_SIZE: u32 = 32;
+
+/// Name and longest side of every resized variant. The `original` variant keeps
+/// the uploaded dimensions.
+pub const VARIANTS: [(&str, u32); 2] = [("thumbnail", 256), ("preview", 1280)];
+pub const ORIGINAL_VARIANT: &str = "original";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MediaError {
+    #[error("unsupported media type")]
+    UnsupportedType,
+    #[error("could not decode image: {0}")]
+    Decode(String),
+    #[error("could not encode image: {0}")]
+    Encode(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct EncodedImage {
+    pub variant: &'static str,
+    pub mime_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessedImage {
+    /// Mime type sniffed from the uploaded bytes.
+    pub source_mime_type: &'static str,
+    /// `original` first, followed by the resized variants.
+    pub files: Vec<EncodedImage>,
+    pub blurhash: String,
+}
+
+/// Detects the mime type from the magic bytes, ignoring whatever the client claims.
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    infer::get(bytes).map(|kind| kind.mime_type())
+}
+
+/// Decodes the image, applies its EXIF orientation and re-encodes it. Re-encoding
+/// drops every metadata block (EXIF, GPS, XMP...) from the stored files.
+pub fn process_image(bytes: &[u8]) -> Result<ProcessedImage, MediaError> {
+    let source_mime_type = sniff_mime_type(bytes).ok_or(MediaError::UnsupportedType)?;
+    let format = ImageFormat::from_mime_type(source_mime_type).ok_or(MediaError::UnsupportedType)?;
+
+    let image = decode(bytes, format)?;
+
+    // PNG keeps transparency, everything else becomes a JPEG.
+    let output = match format {
+        ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP if image.color().has_alpha() => {
+            ImageFormat::Png
+        }
+        _ => ImageFormat::Jpeg,
+    };
+
+    let mut files = vec![encode(&image, ORIGINAL_VARIANT, output)?];
+
+    for (variant, max_side) in VARIANTS {
+        if image.width().max(image.height()) > max_side {
+            let resized = image.thumbnail(max_side, max_side);
+            files.push(encode(&resized, variant, output)?);
+        }
+    }
+
+    Ok(ProcessedImage {
+        source_mime_type,
+        files,
+        blurhash: blurhash(&image)?,
+    })
+}
+
+fn decode(bytes: &[u8], format: ImageFormat) -> Result<DynamicImage, MediaError> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+
+    let mut decoder = reader
+        .into_decoder()
+        .map_err(|e| MediaError::Decode(e.to_string()))?;
+    let orientation = decoder
+        .orientation()
+        .map_err(|e| MediaError::Decode(e.to_string()))?;
+
+    let mut image =
+        DynamicImage::from_decoder(decoder).map_err(|e| MediaError::Decode(e.to_string()))?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+fn encode(
+    image: &DynamicImage,
+    variant: &'static str,
+    format: ImageFormat,
+) -> Result<EncodedImage, MediaError> {
+    let mut bytes = Cursor::new(vec![]);
+
+    let result = match format {
+        ImageFormat::Png => image.write_to(&mut bytes, ImageFormat::Png),
+        _ => DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)),
+    };
+    result.map_err(|e| MediaError::Encode(e.to_string()))?;
+
+    Ok(EncodedImage {
+        variant,
+        mime_type: format.to_mime_type(),
+        width: image.width(),
+        height: image.height(),
+        bytes: bytes.into_inner(),
+    })
+}
+
+fn blurhash(image: &DynamicImage) -> Result<String, MediaError> {
+    let small = image
+        .thumbnail(BLURHASH_SOURCE_SIZE, BLURHASH_SOURCE_SIZE)
+        .to_rgba8();
+
+    blurhash::encode(
+        BLURHASH_COMPONENTS.0,
+        BLURHASH_COMPONENTS.1,
+        small.width(),
+        small.height(),
+        small.as_raw(),
+    )
+    .map_err(|e| MediaError::Encode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, ImageFormat, Rgb, RgbImage, Rgba, RgbaImage};
+
+    use super::{MediaError, process_image, sniff_mime_type};
+
+    fn jpeg_with_exif(width: u32, height: u32) -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(width, height, Rgb([200, 10, 10])));
+        let mut bytes = Cursor::new(vec![]);
+        image.write_to(&mut bytes, ImageFormat::Jpeg).unwrap();
+        let bytes = bytes.into_inner();
+
+        // Splice an APP1/Exif segment carrying a recognizable marker right after SOI.
+        let payload = b"Exif\0\0GPS-SECRET-LOCATION";
+        let length = (payload.len() + 2) as u16;
+        let mut with_exif = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        with_exif.extend_from_slice(&length.to_be_bytes());
+        with_exif.extend_from_slice(payload);
+        with_exif.extend_from_slice(&bytes[2..]);
+        with_exif
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn should_sniff_mime_type_from_content() {
+        assert_eq!(sniff_mime_type(&jpeg_with_exif(4, 4)), Some("image/jpeg"));
+        assert_eq!(sniff_mime_type(b"%PDF-1.4 report"), Some("application/pdf"));
+    }
+
+    #[test]
+    fn should_strip_exif_metadata() {
+        let upload = jpeg_with_exif(64, 48);
+        assert!(contains(&upload, b"GPS-SECRET-LOCATION"));
+
+        let processed = process_image(&upload).unwrap();
+
+        for file in &processed.files {
+            assert!(!contains(&file.bytes, b"GPS-SECRET-LOCATION"));
+        }
+    }
+
+    #[test]
+    fn should_generate_variants_only_when_downscaling() {
+        let small = process_image(&jpeg_with_exif(64, 48)).unwrap();
+        assert_eq!(small.files.len(), 1);
+
+        let large = process_image(&jpeg_with_exif(2000, 1000)).unwrap();
+        let variants: Vec<(&str, u32, u32)> = large
+            .files
+            .iter()
+            .map(|f| (f.variant, f.width, f.height))
+            .collect();
+
+        assert_eq!(
+            variants,
+            vec![
+                ("original", 2000, 1000),
+                ("thumbnail", 256, 128),
+                ("preview", 1280, 640)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_transparency_as_png_and_compute_blurhash() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(16, 16, Rgba([0, 0, 255, 128])));
+        let mut bytes = Cursor::new(vec![]);
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+
+        let processed = process_image(&bytes.into_inner()).unwrap();
+
+        assert_eq!(processed.source_mime_type, "image/png");
+        assert_eq!(processed.files[0].mime_type, "image/png");
+        assert!(!processed.blurhash.is_empty());
+    }
+
+    #[test]
+    fn should_reject_non_images() {
+        assert_eq!(
+            process_image(b"%PDF-1.4 not an image").unwrap_err(),
+            MediaError::UnsupportedType
+        );
+    }
+}
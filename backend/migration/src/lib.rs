@@ -1,12 +1,64 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20261019_000001_create_posts_and_hashtags;
+mod m20261019_000002_create_mentions_blocks_and_notifications;
+mod m20261019_000003_create_media;
+mod m20261019_000004_create_polls;
+mod m20261019_000005_create_link_previews;
+mod m20261019_000006_create_bookmarks;
+mod m20261019_000007_create_lists;
+mod m20261019_000008_create_conversations;
+mod m20261019_000009_create_device_keys;
+mod m20261019_000010_create_moderation;
+mod m20261019_000011_add_user_suspensions;
+mod m20261019_000012_create_keyword_filters;
+mod m20261019_000013_add_sensitive_posts;
+mod m20261019_000014_add_two_factor;
+mod m20261019_000015_create_passkeys;
+mod m20261019_000016_create_linked_identities;
+mod m20261019_000017_create_oauth;
+mod m20261019_000018_create_personal_access_tokens;
+mod m20261019_000019_add_account_deletion;
+mod m20261019_000020_create_data_exports;
+mod m20261019_000021_create_imported_posts;
+mod m20261019_000022_create_jobs;
+mod m20261019_000023_create_outbox_events;
+mod m20261019_000024_add_media_status;
+mod m20261019_000025_create_imports;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20261019_000001_create_posts_and_hashtags::Migration),
+            Box::new(m20261019_000002_create_mentions_blocks_and_notifications::Migration),
+            Box::new(m20261019_000003_create_media::Migration),
+            Box::new(m20261019_000004_create_polls::Migration),
+            Box::new(m20261019_000005_create_link_previews::Migration),
+            Box::new(m20261019_000006_create_bookmarks::Migration),
+            Box::new(m20261019_000007_create_lists::Migration),
+            Box::new(m20261019_000008_create_conversations::Migration),
+            Box::new(m20261019_000009_create_device_keys::Migration),
+            Box::new(m20261019_000010_create_moderation::Migration),
+            Box::new(m20261019_000011_add_user_suspensions::Migration),
+            Box::new(m20261019_000012_create_keyword_filters::Migration),
+            Box::new(m20261019_000013_add_sensitive_posts::Migration),
+            Box::new(m20261019_000014_add_two_factor::Migration),
+            Box::new(m20261019_000015_create_passkeys::Migration),
+            Box::new(m20261019_000016_create_linked_identities::Migration),
+            Box::new(m20261019_000017_create_oauth::Migration),
+            Box::new(m20261019_000018_create_personal_access_tokens::Migration),
+            Box::new(m20261019_000019_add_account_deletion::Migration),
+            Box::new(m20261019_000020_create_data_exports::Migration),
+            Box::new(m20261019_000021_create_imported_posts::Migration),
+            Box::new(m20261019_000022_create_jobs::Migration),
+            Box::new(m20261019_000023_create_outbox_events::Migration),
+            Box::new(m20261019_000024_add_media_status::Migration),
+            Box::new(m20261019_000025_create_imports::Migration),
+        ]
     }
 }
@@ -0,0 +1,118 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_POSTS: &str = "posts";
+const TABLE_HASHTAGS: &str = "hashtags";
+const TABLE_POST_HASHTAGS: &str = "post_hashtags";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POSTS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("author_id").not_null())
+                    .col(text("content").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_author_id_created_at")
+                    .table(TABLE_POSTS)
+                    .col("author_id")
+                    .col("created_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Hashtags are stored once in their normalized form (NFKC + lowercase).
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_HASHTAGS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(string("name").not_null().unique_key())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POST_HASHTAGS)
+                    .if_not_exists()
+                    .col(uuid("post_id").not_null())
+                    .col(uuid("hashtag_id").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col("post_id").col("hashtag_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POST_HASHTAGS, "post_id")
+                            .to(TABLE_POSTS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POST_HASHTAGS, "hashtag_id")
+                            .to(TABLE_HASHTAGS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Hashtag timelines read newest first per tag.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_hashtags_hashtag_id_created_at")
+                    .table(TABLE_POST_HASHTAGS)
+                    .col("hashtag_id")
+                    .col("created_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_POST_HASHTAGS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_HASHTAGS).to_owned())
+            .await?;
+        manager.drop_table(Table::drop().table(TABLE_POSTS).to_owned()).await
+    }
+}
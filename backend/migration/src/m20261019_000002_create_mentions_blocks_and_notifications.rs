@@ -0,0 +1,121 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_POSTS: &str = "posts";
+const TABLE_POST_MENTIONS: &str = "post_mentions";
+const TABLE_BLOCKS: &str = "blocks";
+const TABLE_NOTIFICATIONS: &str = "notifications";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Byte offsets point into `posts.content` so clients can linkify
+        // mentions without parsing the text again.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POST_MENTIONS)
+                    .if_not_exists()
+                    .col(uuid("post_id").not_null())
+                    .col(uuid("user_id").not_null())
+                    .col(integer("start_offset").not_null())
+                    .col(integer("end_offset").not_null())
+                    .primary_key(Index::create().col("post_id").col("start_offset"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POST_MENTIONS, "post_id")
+                            .to(TABLE_POSTS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_mentions_user_id")
+                    .table(TABLE_POST_MENTIONS)
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_BLOCKS)
+                    .if_not_exists()
+                    .col(uuid("blocker_id").not_null())
+                    .col(uuid("blocked_id").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col("blocker_id").col("blocked_id"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_blocks_blocked_id")
+                    .table(TABLE_BLOCKS)
+                    .col("blocked_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_NOTIFICATIONS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("recipient_id").not_null())
+                    .col(uuid("actor_id").not_null())
+                    .col(string("kind").not_null())
+                    .col(uuid_null("post_id"))
+                    .col(timestamp_null("read_at"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifications_recipient_id_created_at")
+                    .table(TABLE_NOTIFICATIONS)
+                    .col("recipient_id")
+                    .col("created_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_NOTIFICATIONS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_BLOCKS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_POST_MENTIONS).to_owned())
+            .await
+    }
+}
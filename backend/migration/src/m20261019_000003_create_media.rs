@@ -0,0 +1,138 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_POSTS: &str = "posts";
+const TABLE_MEDIA: &str = "media";
+const TABLE_MEDIA_VARIANTS: &str = "media_variants";
+const TABLE_POST_MEDIA: &str = "post_media";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `size_bytes` is the sum of every stored variant, used for quotas.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_MEDIA)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("owner_id").not_null())
+                    .col(string("mime_type").not_null())
+                    .col(string("blurhash").not_null())
+                    .col(big_integer("size_bytes").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MEDIA, "owner_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_owner_id")
+                    .table(TABLE_MEDIA)
+                    .col("owner_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_MEDIA_VARIANTS)
+                    .if_not_exists()
+                    .col(uuid("media_id").not_null())
+                    .col(string("name").not_null())
+                    .col(string("mime_type").not_null())
+                    .col(integer("width").not_null())
+                    .col(integer("height").not_null())
+                    .col(big_integer("size_bytes").not_null())
+                    .primary_key(Index::create().col("media_id").col("name"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MEDIA_VARIANTS, "media_id")
+                            .to(TABLE_MEDIA, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POST_MEDIA)
+                    .if_not_exists()
+                    .col(uuid("post_id").not_null())
+                    .col(uuid("media_id").not_null())
+                    .col(small_integer("position").not_null())
+                    .primary_key(Index::create().col("post_id").col("media_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POST_MEDIA, "post_id")
+                            .to(TABLE_POSTS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POST_MEDIA, "media_id")
+                            .to(TABLE_MEDIA, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .add_column(uuid_null("avatar_media_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_users_avatar_media_id")
+                            .from_tbl(TABLE_USERS)
+                            .from_col("avatar_media_id")
+                            .to_tbl(TABLE_MEDIA)
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .drop_foreign_key("fk_users_avatar_media_id")
+                    .drop_column("avatar_media_id")
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_POST_MEDIA).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_MEDIA_VARIANTS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_MEDIA).to_owned())
+            .await
+    }
+}
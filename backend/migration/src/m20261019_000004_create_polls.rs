@@ -0,0 +1,137 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_POSTS: &str = "posts";
+const TABLE_POLLS: &str = "polls";
+const TABLE_POLL_OPTIONS: &str = "poll_options";
+const TABLE_POLL_VOTERS: &str = "poll_voters";
+const TABLE_POLL_VOTES: &str = "poll_votes";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A poll shares the id of its post. Tallies are kept as counters on
+        // `polls` and `poll_options` so reads never aggregate votes.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POLLS)
+                    .if_not_exists()
+                    .col(pk_uuid("post_id"))
+                    .col(boolean("multiple_choice").not_null())
+                    .col(timestamp("closes_at").not_null())
+                    .col(big_integer("voters_count").not_null().default(0))
+                    .col(timestamp_null("ended_notified_at"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POLLS, "post_id")
+                            .to(TABLE_POSTS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_polls_closes_at")
+                    .table(TABLE_POLLS)
+                    .col("closes_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POLL_OPTIONS)
+                    .if_not_exists()
+                    .col(uuid("post_id").not_null())
+                    .col(small_integer("position").not_null())
+                    .col(string("title").not_null())
+                    .col(big_integer("votes_count").not_null().default(0))
+                    .primary_key(Index::create().col("post_id").col("position"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POLL_OPTIONS, "post_id")
+                            .to(TABLE_POLLS, "post_id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The primary key is what guarantees a single vote per user.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POLL_VOTERS)
+                    .if_not_exists()
+                    .col(uuid("post_id").not_null())
+                    .col(uuid("user_id").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col("post_id").col("user_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POLL_VOTERS, "post_id")
+                            .to(TABLE_POLLS, "post_id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POLL_VOTES)
+                    .if_not_exists()
+                    .col(uuid("post_id").not_null())
+                    .col(uuid("user_id").not_null())
+                    .col(small_integer("position").not_null())
+                    .primary_key(
+                        Index::create()
+                            .col("post_id")
+                            .col("user_id")
+                            .col("position"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_tbl(TABLE_POLL_VOTES)
+                            .from_col("post_id")
+                            .from_col("user_id")
+                            .to_tbl(TABLE_POLL_VOTERS)
+                            .to_col("post_id")
+                            .to_col("user_id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_POLL_VOTES).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_POLL_VOTERS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_POLL_OPTIONS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_POLLS).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_POSTS: &str = "posts";
+const TABLE_LINK_PREVIEWS: &str = "link_previews";
+const TABLE_POST_LINK_PREVIEWS: &str = "post_link_previews";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cache of unfurled pages, shared by every post linking the same
+        // normalized url.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_LINK_PREVIEWS)
+                    .if_not_exists()
+                    .col(text("url").primary_key())
+                    .col(text_null("title"))
+                    .col(text_null("description"))
+                    .col(text_null("image_url"))
+                    .col(text_null("site_name"))
+                    .col(timestamp("fetched_at").not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_POST_LINK_PREVIEWS)
+                    .if_not_exists()
+                    .col(pk_uuid("post_id"))
+                    .col(text("url").not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POST_LINK_PREVIEWS, "post_id")
+                            .to(TABLE_POSTS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_POST_LINK_PREVIEWS, "url")
+                            .to(TABLE_LINK_PREVIEWS, "url")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_POST_LINK_PREVIEWS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_LINK_PREVIEWS).to_owned())
+            .await
+    }
+}
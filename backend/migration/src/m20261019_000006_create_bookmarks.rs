@@ -0,0 +1,106 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_BOOKMARK_COLLECTIONS: &str = "bookmark_collections";
+const TABLE_BOOKMARKS: &str = "bookmarks";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_BOOKMARK_COLLECTIONS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("owner_id").not_null())
+                    .col(string("name").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_BOOKMARK_COLLECTIONS, "owner_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_bookmark_collections_owner_id_name")
+                    .table(TABLE_BOOKMARK_COLLECTIONS)
+                    .col("owner_id")
+                    .col("name")
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // `post_id` deliberately has no foreign key: bookmarks outlive their
+        // post and are listed as tombstones.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_BOOKMARKS)
+                    .if_not_exists()
+                    .col(uuid("owner_id").not_null())
+                    .col(uuid("post_id").not_null())
+                    .col(uuid_null("collection_id"))
+                    .col(big_integer("position").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col("owner_id").col("post_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_BOOKMARKS, "owner_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_BOOKMARKS, "collection_id")
+                            .to(TABLE_BOOKMARK_COLLECTIONS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_bookmarks_owner_id_collection_id_position")
+                    .table(TABLE_BOOKMARKS)
+                    .col("owner_id")
+                    .col("collection_id")
+                    .col("position")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_BOOKMARKS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_BOOKMARK_COLLECTIONS).to_owned())
+            .await
+    }
+}
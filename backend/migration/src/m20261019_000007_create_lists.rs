@@ -0,0 +1,131 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_LISTS: &str = "lists";
+const TABLE_LIST_MEMBERS: &str = "list_members";
+const TABLE_LIST_SUBSCRIPTIONS: &str = "list_subscriptions";
+const TABLE_NOTIFICATIONS: &str = "notifications";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_LISTS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("owner_id").not_null())
+                    .col(string("name").not_null())
+                    .col(boolean("is_private").not_null().default(false))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_LISTS, "owner_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_lists_owner_id")
+                    .table(TABLE_LISTS)
+                    .col("owner_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [TABLE_LIST_MEMBERS, TABLE_LIST_SUBSCRIPTIONS] {
+            manager
+                .create_table(
+                    Table::create()
+                        .table(table)
+                        .if_not_exists()
+                        .col(uuid("list_id").not_null())
+                        .col(uuid("user_id").not_null())
+                        .col(
+                            timestamp("created_at")
+                                .not_null()
+                                .default(Expr::current_timestamp()),
+                        )
+                        .primary_key(Index::create().col("list_id").col("user_id"))
+                        .foreign_key(
+                            ForeignKey::create()
+                                .from(table, "list_id")
+                                .to(TABLE_LISTS, "id")
+                                .on_delete(ForeignKeyAction::Cascade),
+                        )
+                        .foreign_key(
+                            ForeignKey::create()
+                                .from(table, "user_id")
+                                .to(TABLE_USERS, "id")
+                                .on_delete(ForeignKeyAction::Cascade),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+
+            manager
+                .create_index(
+                    Index::create()
+                        .name(format!("idx_{}_user_id", table))
+                        .table(table)
+                        .col("user_id")
+                        .if_not_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_NOTIFICATIONS)
+                    .add_column(uuid_null("list_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_notifications_list_id")
+                            .from_tbl(TABLE_NOTIFICATIONS)
+                            .from_col("list_id")
+                            .to_tbl(TABLE_LISTS)
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_NOTIFICATIONS)
+                    .drop_foreign_key("fk_notifications_list_id")
+                    .drop_column("list_id")
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_LIST_SUBSCRIPTIONS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_LIST_MEMBERS).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_LISTS).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,208 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_MEDIA: &str = "media";
+const TABLE_CONVERSATIONS: &str = "conversations";
+const TABLE_CONVERSATION_PARTICIPANTS: &str = "conversation_participants";
+const TABLE_MESSAGES: &str = "messages";
+const TABLE_MESSAGE_MEDIA: &str = "message_media";
+const TABLE_DM_SETTINGS: &str = "dm_settings";
+const TABLE_FOLLOWS: &str = "follows";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `direct_key` is "{smallest user id}:{largest user id}" for one-to-one
+        // conversations, its unique index keeps a single one per pair.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_CONVERSATIONS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(string_null("direct_key").unique_key())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_null("last_message_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_CONVERSATION_PARTICIPANTS)
+                    .if_not_exists()
+                    .col(uuid("conversation_id").not_null())
+                    .col(uuid("user_id").not_null())
+                    .col(small_integer("position").not_null())
+                    .col(uuid_null("last_read_message_id"))
+                    .col(timestamp_null("last_read_at"))
+                    .primary_key(Index::create().col("conversation_id").col("user_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_CONVERSATION_PARTICIPANTS, "conversation_id")
+                            .to(TABLE_CONVERSATIONS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_CONVERSATION_PARTICIPANTS, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_conversation_participants_user_id")
+                    .table(TABLE_CONVERSATION_PARTICIPANTS)
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_MESSAGES)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("conversation_id").not_null())
+                    .col(uuid("sender_id").not_null())
+                    .col(text("content").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MESSAGES, "conversation_id")
+                            .to(TABLE_CONVERSATIONS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MESSAGES, "sender_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_messages_conversation_id_created_at")
+                    .table(TABLE_MESSAGES)
+                    .col("conversation_id")
+                    .col("created_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_MESSAGE_MEDIA)
+                    .if_not_exists()
+                    .col(uuid("message_id").not_null())
+                    .col(uuid("media_id").not_null())
+                    .col(small_integer("position").not_null())
+                    .primary_key(Index::create().col("message_id").col("media_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MESSAGE_MEDIA, "message_id")
+                            .to(TABLE_MESSAGES, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MESSAGE_MEDIA, "media_id")
+                            .to(TABLE_MEDIA, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Users without a row accept messages from everyone.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_DM_SETTINGS)
+                    .if_not_exists()
+                    .col(pk_uuid("user_id"))
+                    .col(string("dm_permission").not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_DM_SETTINGS, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_FOLLOWS)
+                    .if_not_exists()
+                    .col(uuid("follower_id").not_null())
+                    .col(uuid("followee_id").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col("follower_id").col("followee_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_FOLLOWS, "follower_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_FOLLOWS, "followee_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            TABLE_FOLLOWS,
+            TABLE_DM_SETTINGS,
+            TABLE_MESSAGE_MEDIA,
+            TABLE_MESSAGES,
+            TABLE_CONVERSATION_PARTICIPANTS,
+            TABLE_CONVERSATIONS,
+        ] {
+            manager
+                .drop_table(Table::drop().table(table).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_CONVERSATIONS: &str = "conversations";
+const TABLE_DEVICE_KEYS: &str = "device_keys";
+const TABLE_ONE_TIME_PREKEYS: &str = "one_time_prekeys";
+const TABLE_MESSAGE_ENVELOPES: &str = "message_envelopes";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keys are stored as the base64 strings uploaded by the clients.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_DEVICE_KEYS)
+                    .if_not_exists()
+                    .col(uuid("user_id").not_null())
+                    .col(uuid("device_id").not_null())
+                    .col(string("identity_key").not_null())
+                    .col(string("signing_key").not_null())
+                    .col(string("signed_prekey_id").not_null())
+                    .col(string("signed_prekey").not_null())
+                    .col(string("signed_prekey_signature").not_null())
+                    .col(
+                        timestamp("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col("user_id").col("device_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_DEVICE_KEYS, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_ONE_TIME_PREKEYS)
+                    .if_not_exists()
+                    .col(uuid("user_id").not_null())
+                    .col(uuid("device_id").not_null())
+                    .col(string("key_id").not_null())
+                    .col(string("public_key").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col("user_id")
+                            .col("device_id")
+                            .col("key_id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_ONE_TIME_PREKEYS, ("user_id", "device_id"))
+                            .to(TABLE_DEVICE_KEYS, ("user_id", "device_id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Ciphertext waiting for a recipient device, deleted once acknowledged.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_MESSAGE_ENVELOPES)
+                    .if_not_exists()
+                    .col(uuid("message_id").not_null())
+                    .col(uuid("recipient_device_id").not_null())
+                    .col(uuid("recipient_id").not_null())
+                    .col(uuid("conversation_id").not_null())
+                    .col(uuid("sender_id").not_null())
+                    .col(uuid("sender_device_id").not_null())
+                    .col(small_integer("message_type").not_null())
+                    .col(text("ciphertext").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(Index::create().col("message_id").col("recipient_device_id"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MESSAGE_ENVELOPES, "conversation_id")
+                            .to(TABLE_CONVERSATIONS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_MESSAGE_ENVELOPES, "recipient_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_message_envelopes_recipient_created_at")
+                    .table(TABLE_MESSAGE_ENVELOPES)
+                    .col("recipient_id")
+                    .col("recipient_device_id")
+                    .col("created_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            TABLE_MESSAGE_ENVELOPES,
+            TABLE_ONE_TIME_PREKEYS,
+            TABLE_DEVICE_KEYS,
+        ] {
+            manager
+                .drop_table(Table::drop().table(table).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
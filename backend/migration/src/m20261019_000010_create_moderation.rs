@@ -0,0 +1,179 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_POSTS: &str = "posts";
+const TABLE_REPORTS: &str = "reports";
+const TABLE_MODERATION_LOG: &str = "moderation_log";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .add_column(string("role").not_null().default("user"))
+                    .add_column(string("status").not_null().default("active"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_POSTS)
+                    .add_column(timestamp_null("hidden_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // `target_id` points to a post, a user or a message depending on
+        // `target_type`, hence no foreign key.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_REPORTS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("reporter_id").not_null())
+                    .col(string("target_type").not_null())
+                    .col(uuid("target_id").not_null())
+                    .col(uuid("target_user_id").not_null())
+                    .col(string("reason").not_null())
+                    .col(text("details").not_null().default(""))
+                    .col(string("state").not_null().default("open"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(uuid_null("resolved_by"))
+                    .col(timestamp_null("resolved_at"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_REPORTS, "reporter_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_REPORTS, "target_user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_reports_state_created_at")
+                    .table(TABLE_REPORTS)
+                    .col("state")
+                    .col("created_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_reports_reporter_id_target_id")
+                    .table(TABLE_REPORTS)
+                    .col("reporter_id")
+                    .col("target_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // The audit log outlives the accounts it mentions, nothing cascades into it.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_MODERATION_LOG)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("moderator_id").not_null())
+                    .col(string("action").not_null())
+                    .col(uuid_null("report_id"))
+                    .col(string("target_type").not_null())
+                    .col(uuid("target_id").not_null())
+                    .col(text("note").not_null().default(""))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_moderation_log_created_at")
+                    .table(TABLE_MODERATION_LOG)
+                    .col("created_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Entries are immutable, even for someone with direct database access
+        // through the application role.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE OR REPLACE FUNCTION moderation_log_immutable() RETURNS trigger AS $$
+                 BEGIN
+                     RAISE EXCEPTION 'moderation_log entries are immutable';
+                 END;
+                 $$ LANGUAGE plpgsql;
+
+                 CREATE TRIGGER moderation_log_immutable
+                 BEFORE UPDATE OR DELETE ON moderation_log
+                 FOR EACH ROW EXECUTE FUNCTION moderation_log_immutable();",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_MODERATION_LOG).to_owned())
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared("DROP FUNCTION IF EXISTS moderation_log_immutable();")
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TABLE_REPORTS).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_POSTS)
+                    .drop_column("hidden_at")
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .drop_column("status")
+                    .drop_column("role")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Null while active or suspended indefinitely.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .add_column(timestamp_null("suspended_until"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .drop_column("suspended_until")
+                    .to_owned(),
+            )
+            .await
+    }
+}
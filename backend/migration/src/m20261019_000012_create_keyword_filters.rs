@@ -0,0 +1,56 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_KEYWORD_FILTERS: &str = "keyword_filters";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `contexts` is a comma separated list such as `home,search`.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_KEYWORD_FILTERS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("owner_id").not_null())
+                    .col(string("phrase").not_null())
+                    .col(boolean("whole_word").not_null().default(true))
+                    .col(string("contexts").not_null())
+                    .col(timestamp_null("expires_at"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_KEYWORD_FILTERS, "owner_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_keyword_filters_owner_id")
+                    .table(TABLE_KEYWORD_FILTERS)
+                    .col("owner_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_KEYWORD_FILTERS).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_POSTS: &str = "posts";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Sensitive posts are withheld from viewers below the content age.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_POSTS)
+                    .add_column(boolean("sensitive").default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_POSTS)
+                    .drop_column("sensitive")
+                    .to_owned(),
+            )
+            .await
+    }
+}
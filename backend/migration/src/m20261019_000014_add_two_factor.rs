@@ -0,0 +1,80 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_CREDENTIALS: &str = "credentials";
+const TABLE_RECOVERY_CODES: &str = "recovery_codes";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The secret is encrypted by the application, `totp_enabled_at` stays
+        // null until the first code was confirmed.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_CREDENTIALS)
+                    .add_column(binary_null("totp_secret"))
+                    .add_column(timestamp_null("totp_enabled_at"))
+                    .add_column(big_integer_null("totp_last_step"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_RECOVERY_CODES)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("user_id").not_null())
+                    .col(string("code_hash").not_null())
+                    .col(timestamp_null("used_at"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_RECOVERY_CODES, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_recovery_codes_user_id_code_hash")
+                    .table(TABLE_RECOVERY_CODES)
+                    .col("user_id")
+                    .col("code_hash")
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_RECOVERY_CODES).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_CREDENTIALS)
+                    .drop_column("totp_secret")
+                    .drop_column("totp_enabled_at")
+                    .drop_column("totp_last_step")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_PASSKEYS: &str = "passkeys";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `public_key` is an uncompressed P-256 point, `sign_count` the last
+        // counter reported by the authenticator.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_PASSKEYS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("user_id").not_null())
+                    .col(binary("credential_id").not_null().unique_key())
+                    .col(binary("public_key").not_null())
+                    .col(big_integer("sign_count").not_null().default(0))
+                    .col(string("name").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_null("last_used_at"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_PASSKEYS, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_passkeys_user_id")
+                    .table(TABLE_PASSKEYS)
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_PASSKEYS).to_owned())
+            .await
+    }
+}
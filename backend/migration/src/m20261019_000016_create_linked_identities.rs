@@ -0,0 +1,69 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_LINKED_IDENTITIES: &str = "linked_identities";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `subject` is the `sub` claim of the provider, only unique within it.
+        // `email` is the address it vouched for when the identity was linked.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_LINKED_IDENTITIES)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("user_id").not_null())
+                    .col(string("provider").not_null())
+                    .col(string("subject").not_null())
+                    .col(string_null("email"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_LINKED_IDENTITIES, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_linked_identities_provider_subject")
+                    .table(TABLE_LINKED_IDENTITIES)
+                    .col("provider")
+                    .col("subject")
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_linked_identities_user_id")
+                    .table(TABLE_LINKED_IDENTITIES)
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_LINKED_IDENTITIES).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_OAUTH_APPS: &str = "oauth_apps";
+const TABLE_OAUTH_ACCESS_TOKENS: &str = "oauth_access_tokens";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `id` is the OAuth2 `client_id`. Redirect uris and scopes are space separated,
+        // the same way scopes travel over the wire.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_OAUTH_APPS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("owner_id").not_null())
+                    .col(string("name").not_null())
+                    .col(text("redirect_uris").not_null())
+                    .col(string("scopes").not_null())
+                    .col(string_null("client_secret_hash"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_OAUTH_APPS, "owner_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_oauth_apps_owner_id")
+                    .table(TABLE_OAUTH_APPS)
+                    .col("owner_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_OAUTH_ACCESS_TOKENS)
+                    .if_not_exists()
+                    .col(string("token_hash").primary_key())
+                    .col(uuid("client_id").not_null())
+                    .col(uuid("user_id").not_null())
+                    .col(string("scopes").not_null())
+                    .col(timestamp("expires_at").not_null())
+                    .col(timestamp_null("revoked_at"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_OAUTH_ACCESS_TOKENS, "client_id")
+                            .to(TABLE_OAUTH_APPS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_OAUTH_ACCESS_TOKENS, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_oauth_access_tokens_user_id")
+                    .table(TABLE_OAUTH_ACCESS_TOKENS)
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_OAUTH_ACCESS_TOKENS).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TABLE_OAUTH_APPS).to_owned())
+            .await
+    }
+}
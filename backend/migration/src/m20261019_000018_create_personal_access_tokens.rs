@@ -0,0 +1,77 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_PERSONAL_ACCESS_TOKENS: &str = "personal_access_tokens";
+const TABLE_MODERATION_LOG: &str = "moderation_log";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Scopes are space separated, like the ones of OAuth access tokens.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_PERSONAL_ACCESS_TOKENS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("user_id").not_null())
+                    .col(string("name").not_null())
+                    .col(string("token_hash").not_null().unique_key())
+                    .col(string("scopes").not_null())
+                    .col(timestamp("expires_at").not_null())
+                    .col(timestamp_null("last_used_at"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_PERSONAL_ACCESS_TOKENS, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_personal_access_tokens_user_id")
+                    .table(TABLE_PERSONAL_ACCESS_TOKENS)
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // No foreign key, the log outlives revoked tokens.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_MODERATION_LOG)
+                    .add_column(uuid_null("personal_access_token_id"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_MODERATION_LOG)
+                    .drop_column("personal_access_token_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TABLE_PERSONAL_ACCESS_TOKENS).to_owned())
+            .await
+    }
+}
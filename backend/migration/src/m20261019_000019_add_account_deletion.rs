@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_HELD_USERNAMES: &str = "held_usernames";
+const TABLE_MESSAGES: &str = "messages";
+// Postgres' name for the unnamed key of `m20261019_000008_create_conversations`.
+const FK_MESSAGES_SENDER_ID: &str = "messages_sender_id_fkey";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Null unless the account is pending deletion.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .add_column(timestamp_null("deactivated_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Lowercased usernames of purged accounts, unavailable until `held_until`.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_HELD_USERNAMES)
+                    .if_not_exists()
+                    .col(string("username").primary_key())
+                    .col(timestamp("held_until").not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Messages of purged accounts are kept as tombstones, their sender is gone.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_MESSAGES)
+                    .drop_foreign_key(FK_MESSAGES_SENDER_ID)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_MESSAGES)
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name(FK_MESSAGES_SENDER_ID)
+                            .from_tbl(TABLE_MESSAGES)
+                            .from_col("sender_id")
+                            .to_tbl(TABLE_USERS)
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TABLE_HELD_USERNAMES).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_USERS)
+                    .drop_column("deactivated_at")
+                    .to_owned(),
+            )
+            .await
+    }
+}
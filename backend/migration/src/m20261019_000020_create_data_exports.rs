@@ -0,0 +1,48 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_DATA_EXPORTS: &str = "data_exports";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // No foreign key, rows outlive purged accounts until their archive expires.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_DATA_EXPORTS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(uuid("user_id").not_null())
+                    .col(string("status").not_null())
+                    .col(string_null("token_hash"))
+                    .col(timestamp_null("expires_at"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_data_exports_user_id")
+                    .table(TABLE_DATA_EXPORTS)
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_DATA_EXPORTS).to_owned())
+            .await
+    }
+}
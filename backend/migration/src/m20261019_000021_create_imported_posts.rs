@@ -0,0 +1,49 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_USERS: &str = "users";
+const TABLE_IMPORTED_POSTS: &str = "imported_posts";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per archived post brought in, so running an import again skips it.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_IMPORTED_POSTS)
+                    .if_not_exists()
+                    .col(uuid("user_id").not_null())
+                    .col(string("source").not_null())
+                    .col(string("external_id").not_null())
+                    .col(uuid("post_id").not_null())
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col("user_id")
+                            .col("source")
+                            .col("external_id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE_IMPORTED_POSTS, "user_id")
+                            .to(TABLE_USERS, "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_IMPORTED_POSTS).to_owned())
+            .await
+    }
+}
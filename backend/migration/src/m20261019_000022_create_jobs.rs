@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_JOBS: &str = "jobs";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Workers claim due rows with `FOR UPDATE SKIP LOCKED`, dead-lettered
+        // rows are kept with `dead_at` set until someone looks into them.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_JOBS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(string("kind").not_null())
+                    .col(text("payload").not_null())
+                    .col(integer("attempts").not_null().default(0))
+                    .col(timestamp("run_at").not_null())
+                    .col(text_null("last_error"))
+                    .col(timestamp_null("dead_at"))
+                    .col(
+                        timestamp("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_jobs_run_at")
+                    .table(TABLE_JOBS)
+                    .col("run_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_JOBS).to_owned())
+            .await
+    }
+}
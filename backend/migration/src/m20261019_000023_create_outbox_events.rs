@@ -0,0 +1,45 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_OUTBOX_EVENTS: &str = "outbox_events";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Written in the transaction of the change the event describes,
+        // `available_at` is pushed back while a worker dispatches the event.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE_OUTBOX_EVENTS)
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(string("kind").not_null())
+                    .col(text("payload").not_null())
+                    .col(timestamp("occurred_at").not_null())
+                    .col(timestamp("available_at").not_null())
+                    .col(timestamp_null("dispatched_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_outbox_events_available_at")
+                    .table(TABLE_OUTBOX_EVENTS)
+                    .col("available_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TABLE_OUTBOX_EVENTS).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,33 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const TABLE_MEDIA: &str = "media";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Uploads are processed by the worker, media uploaded before were
+        // processed inline and are ready.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_MEDIA)
+                    .add_column(string("status").not_null().default("ready"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE_MEDIA)
+                    .drop_column("status")
+                    .to_owned(),
+            )
+            .await
+    }
+}
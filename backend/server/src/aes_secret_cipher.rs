@@ -0,0 +1,55 @@
+use domain::repositories::secret_cipher::SecretCipher;
+use errors::HearthError;
+use ring::{
+    aead::{ AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey },
+    rand::{ SecureRandom, SystemRandom },
+};
+
+/// AES-256-GCM with a random nonce prepended to each ciphertext.
+pub struct AesSecretCipher {
+    key: LessSafeKey,
+    random: SystemRandom,
+}
+
+impl AesSecretCipher {
+    /// `key` must be 32 bytes long.
+    pub fn new(key: &[u8]) -> Self {
+        let key = UnboundKey::new(&AES_256_GCM, key).expect("The secret key must be 32 bytes long");
+
+        Self { key: LessSafeKey::new(key), random: SystemRandom::new() }
+    }
+}
+
+impl SecretCipher for AesSecretCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, HearthError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.random
+            .fill(&mut nonce)
+            .map_err(|_| HearthError::unexpected("SECRET_NONCE_ERROR".into(), None))?;
+
+        let mut sealed = plaintext.to_vec();
+        self.key
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut sealed)
+            .map_err(|_| HearthError::unexpected("SECRET_ENCRYPT_ERROR".into(), None))?;
+
+        Ok([nonce.to_vec(), sealed].concat())
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, HearthError> {
+        if ciphertext.len() < NONCE_LEN {
+            return Err(HearthError::unexpected("SECRET_DECRYPT_ERROR".into(), None));
+        }
+
+        let (nonce, sealed) = ciphertext.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_|
+            HearthError::unexpected("SECRET_DECRYPT_ERROR".into(), None)
+        )?;
+
+        let mut sealed = sealed.to_vec();
+        let plaintext = self.key
+            .open_in_place(nonce, Aad::empty(), &mut sealed)
+            .map_err(|_| HearthError::unexpected("SECRET_DECRYPT_ERROR".into(), None))?;
+
+        Ok(plaintext.to_vec())
+    }
+}
@@ -0,0 +1,203 @@
+use chrono::{ DateTime, Utc };
+use domain::dtos::import::ArchivedPostDTO;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    archive_reader::{ Archive, MAX_DOCUMENT_BYTES, MAX_MEDIA_BYTES, open, read_file },
+    link_unfurler::open_graph::decode_entities,
+};
+
+const OUTBOX: &str = "outbox.json";
+const MEDIA_DIRECTORY: &str = "media_attachments/";
+const PUBLIC_AUDIENCES: [&str; 3] = ["https://www.w3.org/ns/activitystreams#Public", "as:Public", "Public"];
+
+#[derive(Deserialize)]
+struct Outbox {
+    #[serde(rename = "orderedItems")]
+    ordered_items: Vec<Activity>,
+}
+
+#[derive(Deserialize)]
+struct Activity {
+    #[serde(rename = "type")]
+    kind: String,
+    /// A note for `Create`, the url of the boosted status for `Announce`.
+    object: Value,
+}
+
+#[derive(Deserialize)]
+struct Note {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    content: String,
+    published: String,
+    #[serde(default)]
+    sensitive: bool,
+    /// Content warning, hides the post as sensitive does.
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    to: Vec<String>,
+    #[serde(default)]
+    cc: Vec<String>,
+    #[serde(default)]
+    attachment: Vec<Attachment>,
+}
+
+#[derive(Deserialize)]
+struct Attachment {
+    url: String,
+}
+
+/// Reads the `outbox.json` of an account export, or the bare file. Boosts,
+/// followers only posts and direct messages are left out.
+pub(crate) fn read_archive(bytes: &[u8]) -> Option<Vec<ArchivedPostDTO>> {
+    let mut archive = open(bytes);
+    let outbox: Outbox = match archive.as_mut() {
+        Some(archive) => serde_json::from_slice(&read_file(archive, OUTBOX, MAX_DOCUMENT_BYTES)?),
+        None => serde_json::from_slice(bytes),
+    }.ok()?;
+
+    Some(
+        outbox.ordered_items
+            .into_iter()
+            .filter(|activity| activity.kind == "Create")
+            .filter_map(|activity| serde_json::from_value::<Note>(activity.object).ok())
+            .filter_map(|note| to_post(archive.as_mut(), note))
+            .collect()
+    )
+}
+
+fn to_post(archive: Option<&mut Archive>, note: Note) -> Option<ArchivedPostDTO> {
+    let public = note.to
+        .iter()
+        .chain(note.cc.iter())
+        .any(|audience| PUBLIC_AUDIENCES.contains(&audience.as_str()));
+    if note.kind != "Note" || !public {
+        return None;
+    }
+
+    let created_at = DateTime::parse_from_rfc3339(&note.published).ok()?.with_timezone(&Utc);
+
+    // Attachments are listed by their path on the instance, which mirrors the export.
+    let media = match archive {
+        Some(archive) =>
+            note.attachment
+                .iter()
+                .filter_map(|attachment| {
+                    let start = attachment.url.find(MEDIA_DIRECTORY)?;
+                    read_file(archive, &attachment.url[start..], MAX_MEDIA_BYTES)
+                })
+                .collect(),
+        None => vec![],
+    };
+
+    Some(ArchivedPostDTO {
+        external_id: note.id,
+        content: html_to_text(&note.content),
+        sensitive: note.sensitive || note.summary.is_some_and(|summary| !summary.is_empty()),
+        media,
+        created_at,
+    })
+}
+
+/// Statuses are HTML, paragraphs and line breaks are all that is kept.
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+
+        let Some(length) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+
+        let tag = rest[start + 1..start + length].trim().to_ascii_lowercase();
+        if tag.starts_with("br") {
+            text.push('\n');
+        } else if tag == "/p" {
+            text.push_str("\n\n");
+        }
+
+        rest = &rest[start + length + 1..];
+    }
+
+    text.push_str(rest);
+    decode_entities(text.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ TimeZone, Utc };
+    use domain::dtos::data_export::ArchiveFileDTO;
+
+    use super::{ html_to_text, read_archive };
+    use crate::zip_archive_writer::write_zip;
+
+    const OUTBOX: &str =
+        r##"{
+  "orderedItems": [
+    {
+      "type": "Create",
+      "object": {
+        "id": "https://mastodon.example/users/alice/statuses/1",
+        "type": "Note",
+        "content": "<p>Hello <a href=\"https://mastodon.example/tags/hearth\">#<span>hearth</span></a></p><p>bye &amp; thanks</p>",
+        "published": "2022-11-05T10:00:00Z",
+        "to": ["https://www.w3.org/ns/activitystreams#Public"],
+        "attachment": [{ "url": "/media_attachments/files/000/original/a.png" }]
+      }
+    },
+    {
+      "type": "Create",
+      "object": {
+        "id": "https://mastodon.example/users/alice/statuses/2",
+        "type": "Note",
+        "content": "<p>secret</p>",
+        "published": "2022-11-05T11:00:00Z",
+        "to": ["https://mastodon.example/users/bob"]
+      }
+    },
+    { "type": "Announce", "object": "https://elsewhere.example/statuses/3" }
+  ]
+}"##;
+
+    #[test]
+    fn should_read_public_statuses_with_their_media() {
+        let bytes = write_zip(
+            vec![
+                ArchiveFileDTO { path: "outbox.json".into(), bytes: OUTBOX.as_bytes().to_vec() },
+                ArchiveFileDTO {
+                    path: "media_attachments/files/000/original/a.png".into(),
+                    bytes: b"png".to_vec(),
+                }
+            ]
+        ).unwrap();
+
+        let posts = read_archive(&bytes).unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].external_id, "https://mastodon.example/users/alice/statuses/1");
+        assert_eq!(posts[0].content, "Hello #hearth\n\nbye & thanks");
+        assert_eq!(posts[0].created_at, Utc.with_ymd_and_hms(2022, 11, 5, 10, 0, 0).unwrap());
+        assert_eq!(posts[0].media, vec![b"png".to_vec()]);
+    }
+
+    #[test]
+    fn should_read_a_bare_outbox_without_media() {
+        let posts = read_archive(OUTBOX.as_bytes()).unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert!(posts[0].media.is_empty());
+    }
+
+    #[test]
+    fn should_keep_line_breaks_of_statuses() {
+        assert_eq!(html_to_text("<p>one<br>two<br />three</p>"), "one\ntwo\nthree");
+    }
+}
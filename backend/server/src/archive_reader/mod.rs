@@ -0,0 +1,58 @@
+mod mastodon;
+mod twitter;
+
+use std::io::{ Cursor, Read };
+
+use async_trait::async_trait;
+use domain::{
+    dtos::import::{ ArchivedPostDTO, ImportSource },
+    error_codes::IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE,
+    repositories::archive_reader::ArchiveReader,
+};
+use errors::HearthError;
+use zip::ZipArchive;
+
+use crate::database::unexpected;
+
+/// JSON documents listing the posts, read whole.
+const MAX_DOCUMENT_BYTES: u64 = 256 * 1024 * 1024;
+/// Attachments bigger than uploads are rejected anyway, one byte past the
+/// limit is enough to tell.
+const MAX_MEDIA_BYTES: u64 = 10 * 1024 * 1024 + 1;
+
+/// Adapter over the `zip` crate. Decompressing and parsing are CPU bound so
+/// they run on the blocking thread pool.
+pub struct ZipArchiveReader;
+
+#[async_trait]
+impl ArchiveReader for ZipArchiveReader {
+    async fn read(
+        &self,
+        source: ImportSource,
+        bytes: Vec<u8>
+    ) -> Result<Vec<ArchivedPostDTO>, HearthError> {
+        tokio::task
+            ::spawn_blocking(move || {
+                match source {
+                    ImportSource::Twitter => twitter::read_archive(&bytes),
+                    ImportSource::Mastodon => mastodon::read_archive(&bytes),
+                }
+            }).await
+            .map_err(unexpected("ARCHIVE_READ_ERROR"))?
+            .ok_or_else(|| HearthError::Domain(IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE.into()))
+    }
+}
+
+type Archive<'a> = ZipArchive<Cursor<&'a [u8]>>;
+
+fn open(bytes: &[u8]) -> Option<Archive<'_>> {
+    ZipArchive::new(Cursor::new(bytes)).ok()
+}
+
+/// Reads at most `max_bytes` of the file at `path`, `None` when there is none.
+fn read_file(archive: &mut Archive, path: &str, max_bytes: u64) -> Option<Vec<u8>> {
+    let file = archive.by_name(path).ok()?;
+    let mut bytes = vec![];
+    file.take(max_bytes).read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
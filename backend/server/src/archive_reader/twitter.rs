@@ -0,0 +1,197 @@
+use chrono::{ DateTime, Utc };
+use domain::dtos::import::ArchivedPostDTO;
+use serde::Deserialize;
+
+use crate::{
+    archive_reader::{ Archive, MAX_DOCUMENT_BYTES, MAX_MEDIA_BYTES, open, read_file },
+    link_unfurler::open_graph::decode_entities,
+};
+
+/// Bigger accounts get their tweets split across `tweets-part1.js` and so on.
+const TWEETS_PREFIXES: [&str; 2] = ["data/tweets", "data/tweet."];
+const MEDIA_DIRECTORIES: [&str; 2] = ["data/tweets_media", "data/tweet_media"];
+const CREATED_AT_FORMAT: &str = "%a %b %d %H:%M:%S %z %Y";
+
+#[derive(Deserialize)]
+struct Entry {
+    tweet: Tweet,
+}
+
+#[derive(Deserialize)]
+struct Tweet {
+    id_str: String,
+    full_text: String,
+    created_at: String,
+    #[serde(default)]
+    possibly_sensitive: bool,
+    #[serde(default)]
+    entities: Entities,
+    #[serde(default)]
+    extended_entities: Entities,
+}
+
+#[derive(Deserialize, Default)]
+struct Entities {
+    #[serde(default)]
+    urls: Vec<Url>,
+    #[serde(default)]
+    media: Vec<Media>,
+}
+
+#[derive(Deserialize)]
+struct Url {
+    url: String,
+    expanded_url: String,
+}
+
+#[derive(Deserialize)]
+struct Media {
+    url: String,
+    media_url_https: String,
+}
+
+/// Reads `data/tweets.js` of an archive, retweets left out.
+pub(crate) fn read_archive(bytes: &[u8]) -> Option<Vec<ArchivedPostDTO>> {
+    let mut archive = open(bytes)?;
+    let documents: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            TWEETS_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) && name.ends_with(".js")
+        })
+        .map(String::from)
+        .collect();
+
+    if documents.is_empty() {
+        return None;
+    }
+
+    let mut posts = vec![];
+    for document in documents {
+        let script = read_file(&mut archive, &document, MAX_DOCUMENT_BYTES)?;
+        for entry in parse_script(&script)? {
+            if let Some(post) = to_post(&mut archive, entry.tweet) {
+                posts.push(post);
+            }
+        }
+    }
+
+    Some(posts)
+}
+
+/// The JSON is assigned to a global, `window.YTD.tweets.part0 = [...]`.
+fn parse_script(script: &[u8]) -> Option<Vec<Entry>> {
+    let start = script.iter().position(|byte| *byte == b'[')?;
+    serde_json::from_slice(&script[start..]).ok()
+}
+
+fn to_post(archive: &mut Archive, tweet: Tweet) -> Option<ArchivedPostDTO> {
+    if tweet.full_text.starts_with("RT @") {
+        return None;
+    }
+
+    let created_at = DateTime::parse_from_str(&tweet.created_at, CREATED_AT_FORMAT)
+        .ok()?
+        .with_timezone(&Utc);
+
+    let media = if tweet.extended_entities.media.is_empty() {
+        &tweet.entities.media
+    } else {
+        &tweet.extended_entities.media
+    };
+
+    // Shortened links point back to their target, the ones of attachments go away.
+    let mut content = tweet.full_text.clone();
+    for url in &tweet.entities.urls {
+        content = content.replace(&url.url, &url.expanded_url);
+    }
+    for item in media {
+        content = content.replace(&item.url, "");
+    }
+
+    let files = media
+        .iter()
+        .filter_map(|item| {
+            let name = item.media_url_https.rsplit('/').next()?;
+            MEDIA_DIRECTORIES.iter().find_map(|directory| {
+                let path = format!("{}/{}-{}", directory, tweet.id_str, name);
+                read_file(archive, &path, MAX_MEDIA_BYTES)
+            })
+        })
+        .collect();
+
+    Some(ArchivedPostDTO {
+        external_id: tweet.id_str,
+        content: decode_entities(content.trim()),
+        sensitive: tweet.possibly_sensitive,
+        media: files,
+        created_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{ TimeZone, Utc };
+    use domain::dtos::data_export::ArchiveFileDTO;
+
+    use super::read_archive;
+    use crate::zip_archive_writer::write_zip;
+
+    const TWEETS: &str =
+        r#"window.YTD.tweets.part0 = [
+  {
+    "tweet" : {
+      "id_str" : "1106118447",
+      "full_text" : "Moving to Hearth &amp; more https://t.co/abc https://t.co/pic",
+      "created_at" : "Thu Mar 14 09:26:53 +0000 2019",
+      "entities" : {
+        "urls" : [ { "url" : "https://t.co/abc", "expanded_url" : "https://hearth.example" } ]
+      },
+      "extended_entities" : {
+        "media" : [
+          { "url" : "https://t.co/pic", "media_url_https" : "https://pbs.twimg.com/media/D1.jpg" }
+        ]
+      }
+    }
+  },
+  {
+    "tweet" : {
+      "id_str" : "1106118448",
+      "full_text" : "RT @someone: not mine",
+      "created_at" : "Thu Mar 14 09:30:00 +0000 2019"
+    }
+  }
+]"#;
+
+    fn archive(files: Vec<(&str, &[u8])>) -> Vec<u8> {
+        write_zip(
+            files
+                .into_iter()
+                .map(|(path, bytes)| ArchiveFileDTO { path: path.into(), bytes: bytes.to_vec() })
+                .collect()
+        ).unwrap()
+    }
+
+    #[test]
+    fn should_read_tweets_with_their_media() {
+        let bytes = archive(
+            vec![
+                ("data/tweets.js", TWEETS.as_bytes()),
+                ("data/tweets_media/1106118447-D1.jpg", b"jpeg")
+            ]
+        );
+
+        let posts = read_archive(&bytes).unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].external_id, "1106118447");
+        assert_eq!(posts[0].content, "Moving to Hearth & more https://hearth.example");
+        assert_eq!(posts[0].created_at, Utc.with_ymd_and_hms(2019, 3, 14, 9, 26, 53).unwrap());
+        assert_eq!(posts[0].media, vec![b"jpeg".to_vec()]);
+    }
+
+    #[test]
+    fn should_not_read_archives_without_tweets() {
+        assert!(read_archive(&archive(vec![("data/account.js", b"[]")])).is_none());
+        assert!(read_archive(b"not a zip").is_none());
+    }
+}
@@ -0,0 +1,163 @@
+use std::{future::Future, pin::Pin};
+
+use actix_web::{
+    FromRequest, HttpMessage, HttpRequest,
+    dev::Payload,
+    http::{Method, header},
+    web,
+};
+use domain::{
+    dtos::{auth::AuthenticatedUserDTO, oauth::Scope},
+    error_codes::{
+        INSUFFICIENT_SCOPE_ERROR_CODE, INVALID_SESSION_ERROR_CODE, SESSION_REQUIRED_ERROR_CODE,
+    },
+};
+use errors::HearthError;
+
+use crate::bootstrap::Dependencies;
+
+/// Extractor for routes that require a logged in user. Expects an
+/// `Authorization: Bearer <token>` header, holding either a session or an
+/// OAuth access token granted the scope the route needs.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser(pub AuthenticatedUserDTO);
+
+impl FromRequest for AuthenticatedUser {
+    type Error = HearthError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let dependencies = req.app_data::<web::Data<Dependencies>>().cloned();
+        let token = bearer_token(req);
+        let required_access = required_access(req.method(), req.path());
+        let authentication = req.extensions_mut().remove::<Authentication>();
+
+        Box::pin(async move {
+            let unauthorized = || HearthError::Unauthorized(INVALID_SESSION_ERROR_CODE.into());
+
+            let user = match authentication {
+                Some(Authentication(result)) => result?,
+                None => {
+                    let dependencies = dependencies.ok_or_else(unauthorized)?;
+                    let token = token.ok_or_else(unauthorized)?;
+                    dependencies.authenticate.execute(token).await?
+                }
+            };
+
+            match required_access {
+                RequiredAccess::Session if !user.is_session() => Err(
+                    HearthError::Forbidden(SESSION_REQUIRED_ERROR_CODE.into()),
+                ),
+                RequiredAccess::Scope(scope) if !user.has_scope(scope) => Err(
+                    HearthError::Forbidden(INSUFFICIENT_SCOPE_ERROR_CODE.into()),
+                ),
+                _ => Ok(AuthenticatedUser(user)),
+            }
+        })
+    }
+}
+
+/// Outcome of authenticating the bearer token, left in the request extensions
+/// by a middleware that needed the caller first so the route doesn't look the
+/// token up a second time.
+pub(crate) struct Authentication(pub Result<AuthenticatedUserDTO, HearthError>);
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum RequiredAccess {
+    /// Account security and app authorization are never delegated to apps.
+    Session,
+    Scope(Scope),
+}
+
+/// What a route needs from an OAuth access token, sessions carry every scope.
+pub(crate) fn required_access(method: &Method, path: &str) -> RequiredAccess {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+    match segments.as_slice() {
+        ["me"]
+        | ["me", "2fa", ..]
+        | ["me", "exports", ..]
+        | ["me", "passkeys", ..]
+        | ["me", "tokens", ..]
+        | ["oauth", ..] => RequiredAccess::Session,
+        ["admin", ..] | ["moderation", ..] => RequiredAccess::Scope(Scope::Admin),
+        ["conversations", ..]
+        | ["me", "devices", ..]
+        | ["me", "dm-settings", ..]
+        | ["users", _, "prekeys", ..]
+        | ["ws"] => RequiredAccess::Scope(Scope::Dm),
+        _ if method == Method::GET || method == Method::HEAD => RequiredAccess::Scope(Scope::Read),
+        _ => RequiredAccess::Scope(Scope::Write),
+    }
+}
+
+pub(crate) fn bearer_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::Method;
+    use domain::dtos::oauth::Scope;
+
+    use super::{RequiredAccess, required_access};
+
+    #[test]
+    fn should_require_read_or_write_by_method() {
+        assert_eq!(
+            required_access(&Method::GET, "/users/42"),
+            RequiredAccess::Scope(Scope::Read)
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/posts"),
+            RequiredAccess::Scope(Scope::Write)
+        );
+        assert_eq!(
+            required_access(&Method::DELETE, "/me/bookmarks/42"),
+            RequiredAccess::Scope(Scope::Write)
+        );
+    }
+
+    #[test]
+    fn should_require_dm_and_admin_scopes() {
+        assert_eq!(
+            required_access(&Method::GET, "/conversations"),
+            RequiredAccess::Scope(Scope::Dm)
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/users/42/prekeys/claim"),
+            RequiredAccess::Scope(Scope::Dm)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/moderation/reports"),
+            RequiredAccess::Scope(Scope::Admin)
+        );
+    }
+
+    #[test]
+    fn should_keep_account_security_to_sessions() {
+        assert_eq!(
+            required_access(&Method::POST, "/me/2fa/disable"),
+            RequiredAccess::Session
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/oauth/authorize"),
+            RequiredAccess::Session
+        );
+        assert_eq!(
+            required_access(&Method::DELETE, "/me/tokens/42"),
+            RequiredAccess::Session
+        );
+        assert_eq!(required_access(&Method::DELETE, "/me"), RequiredAccess::Session);
+        assert_eq!(
+            required_access(&Method::POST, "/me/exports"),
+            RequiredAccess::Session
+        );
+    }
+}
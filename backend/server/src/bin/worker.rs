@@ -0,0 +1,45 @@
+use std::env;
+
+use actix_web::web;
+use dotenvy::dotenv;
+use sea_orm::DatabaseConnection;
+use server::{
+    bootstrap::build_dependencies,
+    config::Config,
+    database::postgres_connector::connect,
+    scheduler::{
+        spawn_account_purger, spawn_data_export_builder, spawn_event_dispatcher, spawn_job_runner,
+        spawn_poll_notifier,
+    },
+};
+
+/// Runs the event dispatch, the job queue and the periodic batches, away from
+/// the request handlers. As many workers as needed can share the database.
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    dotenv().ok();
+
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .expect("Failed to install rustls crypto provider");
+
+    // Env variables
+    let db_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set in .env file");
+    let redis_url = env::var("REDIS_URL").expect("REDIS_URL is not set in .env file");
+
+    // Database stuff
+    let db: DatabaseConnection = connect(&db_url).await;
+    let client = redis::Client::open(redis_url).unwrap();
+
+    // Building Dependencies
+    let config = Config::from_env();
+    let dependencies = web::Data::new(build_dependencies(db, client, &config));
+
+    spawn_event_dispatcher(dependencies.clone());
+    spawn_job_runner(dependencies.clone());
+    spawn_poll_notifier(dependencies.clone());
+    spawn_account_purger(dependencies.clone());
+    spawn_data_export_builder(dependencies);
+
+    actix_web::rt::signal::ctrl_c().await
+}
@@ -1,46 +1,1202 @@
 use std::sync::Arc;
 
 use domain::{
-    features::signup::signup_with_email::{SignupWithEmail, SignupWithEmailFeature},
+    features::{
+        auth::{
+            authenticate::{Authenticate, AuthenticateFeature},
+            login_with_email::{LoginWithEmail, LoginWithEmailFeature},
+        },
+        blocks::{
+            block_user::{BlockUser, BlockUserFeature},
+            unblock_user::{UnblockUser, UnblockUserFeature},
+        },
+        bookmarks::{
+            bookmark_post::{BookmarkPost, BookmarkPostFeature},
+            create_bookmark_collection::{
+                CreateBookmarkCollection, CreateBookmarkCollectionFeature,
+            },
+            list_bookmark_collections::{ListBookmarkCollections, ListBookmarkCollectionsFeature},
+            list_bookmarks::{ListBookmarks, ListBookmarksFeature},
+            move_bookmark::{MoveBookmark, MoveBookmarkFeature},
+            remove_bookmark::{RemoveBookmark, RemoveBookmarkFeature},
+        },
+        conversations::{
+            acknowledge_envelope::{AcknowledgeEnvelope, AcknowledgeEnvelopeFeature},
+            get_dm_settings::{GetDmSettings, GetDmSettingsFeature},
+            list_conversations::{ListConversations, ListConversationsFeature},
+            list_envelopes::{ListEnvelopes, ListEnvelopesFeature},
+            list_messages::{ListMessages, ListMessagesFeature},
+            mark_conversation_read::{MarkConversationRead, MarkConversationReadFeature},
+            send_encrypted_message::{SendEncryptedMessage, SendEncryptedMessageFeature},
+            send_message::{SendMessage, SendMessageFeature},
+            start_conversation::{StartConversation, StartConversationFeature},
+            update_dm_settings::{UpdateDmSettings, UpdateDmSettingsFeature},
+        },
+        data_exports::{
+            build_data_exports::BuildDataExports,
+            download_data_export::{DownloadDataExport, DownloadDataExportFeature},
+            request_data_export::{RequestDataExport, RequestDataExportFeature},
+        },
+        device_keys::{
+            claim_prekeys::{ClaimPrekeys, ClaimPrekeysFeature},
+            get_prekey_count::{GetPrekeyCount, GetPrekeyCountFeature},
+            upload_device_keys::{UploadDeviceKeys, UploadDeviceKeysFeature},
+            upload_prekeys::{UploadPrekeys, UploadPrekeysFeature},
+        },
+        events::dispatch_events::{DispatchEvents, DispatchEventsFeature},
+        filters::{
+            create_keyword_filter::{CreateKeywordFilter, CreateKeywordFilterFeature},
+            delete_keyword_filter::{DeleteKeywordFilter, DeleteKeywordFilterFeature},
+            list_keyword_filters::{ListKeywordFilters, ListKeywordFiltersFeature},
+        },
+        imports::{
+            get_import::{GetImport, GetImportFeature},
+            import_archive::ImportArchive,
+            start_import::{StartImport, StartImportFeature},
+        },
+        jobs::{
+            run_jobs::{RunJobs, RunJobsFeature},
+            schedule_periodic_job::{SchedulePeriodicJob, SchedulePeriodicJobFeature},
+        },
+        lists::{
+            add_list_member::{AddListMember, AddListMemberFeature},
+            create_list::{CreateList, CreateListFeature},
+            delete_list::{DeleteList, DeleteListFeature},
+            get_list_members::{GetListMembers, GetListMembersFeature},
+            get_list_subscriptions::{GetListSubscriptions, GetListSubscriptionsFeature},
+            get_list_timeline::{GetListTimeline, GetListTimelineFeature},
+            get_user_lists::{GetUserLists, GetUserListsFeature},
+            remove_list_member::{RemoveListMember, RemoveListMemberFeature},
+            subscribe_list::{SubscribeList, SubscribeListFeature},
+            unsubscribe_list::{UnsubscribeList, UnsubscribeListFeature},
+            update_list::{UpdateList, UpdateListFeature},
+        },
+        media::{
+            get_media::{GetMedia, GetMediaFeature},
+            get_media_file::{GetMediaFile, GetMediaFileFeature},
+            process_media::ProcessMedia,
+            set_avatar::{SetAvatar, SetAvatarFeature},
+            upload_media::{UploadMedia, UploadMediaFeature},
+        },
+        moderation::{
+            create_report::{CreateReport, CreateReportFeature},
+            list_moderation_log::{ListModerationLog, ListModerationLogFeature},
+            list_reports::{ListReports, ListReportsFeature},
+            resolve_report::{ResolveReport, ResolveReportFeature},
+            set_user_role::{SetUserRole, SetUserRoleFeature},
+            suspend_user::{SuspendUser, SuspendUserFeature},
+        },
+        notifications::list_notifications::{ListNotifications, ListNotificationsFeature},
+        oauth::{
+            decide_oauth_consent::{DecideOAuthConsent, DecideOAuthConsentFeature},
+            exchange_oauth_token::{ExchangeOAuthToken, ExchangeOAuthTokenFeature},
+            get_oauth_consent::{GetOAuthConsent, GetOAuthConsentFeature},
+            introspect_oauth_token::{IntrospectOAuthToken, IntrospectOAuthTokenFeature},
+            register_oauth_app::{RegisterOAuthApp, RegisterOAuthAppFeature},
+            revoke_oauth_token::{RevokeOAuthToken, RevokeOAuthTokenFeature},
+        },
+        personal_access_tokens::{
+            create_personal_access_token::{
+                CreatePersonalAccessToken, CreatePersonalAccessTokenFeature,
+            },
+            list_personal_access_tokens::{
+                ListPersonalAccessTokens, ListPersonalAccessTokensFeature,
+            },
+            revoke_personal_access_token::{
+                RevokePersonalAccessToken, RevokePersonalAccessTokenFeature,
+            },
+        },
+        oidc::{
+            complete_oidc_signup::{CompleteOidcSignup, CompleteOidcSignupFeature},
+            finish_oidc_login::{FinishOidcLogin, FinishOidcLoginFeature},
+            start_oidc_login::{StartOidcLinkFeature, StartOidcLogin, StartOidcLoginFeature},
+        },
+        polls::{
+            get_poll::{GetPoll, GetPollFeature},
+            notify_ended_polls::NotifyEndedPolls,
+            vote_poll::{VotePoll, VotePollFeature},
+        },
+        passkeys::{
+            login_with_passkey::{LoginWithPasskey, LoginWithPasskeyFeature},
+            register_passkey::{RegisterPasskey, RegisterPasskeyFeature},
+            start_passkey_login::{StartPasskeyLogin, StartPasskeyLoginFeature},
+            start_passkey_registration::{
+                StartPasskeyRegistration, StartPasskeyRegistrationFeature,
+            },
+        },
+        posts::{
+            create_post::{CreatePost, CreatePostFeature},
+            get_hashtag_timeline::{GetHashtagTimeline, GetHashtagTimelineFeature},
+            notify_mentions::NotifyMentions,
+            unfurl_post_link::UnfurlPostLink,
+        },
+        rate_limit::check_rate_limit::{CheckRateLimit, CheckRateLimitFeature},
+        signup::{
+            create_signup_challenge::{CreateSignupChallenge, CreateSignupChallengeFeature},
+            signup_with_email::{SignupWithEmail, SignupWithEmailFeature},
+        },
+        trends::get_trends::{GetTrends, GetTrendsFeature},
+        two_factor::{
+            confirm_two_factor::{ConfirmTwoFactor, ConfirmTwoFactorFeature},
+            disable_two_factor::{DisableTwoFactor, DisableTwoFactorFeature},
+            enroll_two_factor::{EnrollTwoFactor, EnrollTwoFactorFeature},
+            login_with_two_factor::{LoginWithTwoFactor, LoginWithTwoFactorFeature},
+        },
+        users::{
+            delete_account::{DeleteAccount, DeleteAccountFeature},
+            get_profile::{GetProfile, GetProfileFeature},
+            purge_deleted_accounts::PurgeDeletedAccounts,
+            send_email_verification::{SendEmailVerification, SendEmailVerificationFeature},
+            verify_email::{VerifyEmail, VerifyEmailFeature},
+            welcome_registered_user::WelcomeRegisteredUser,
+        },
+    },
+    policies::{
+        account_deletion::AccountDeletionPolicy, conversation::ConversationPolicy,
+        device_keys::DeviceKeysPolicy, filter::FilterPolicy, import::ImportPolicy,
+        job::JobPolicy, link_preview::LinkPreviewPolicy,
+        list::ListPolicy, media::MediaPolicy, oauth::OAuthPolicy,
+        personal_access_token::PersonalAccessTokenPolicy, poll::PollPolicy,
+        rate_limit::RateLimitPolicy,
+    },
     repositories::{
+        archive_reader::ArchiveReader, archive_writer::ArchiveWriter,
+        blocks_repository::BlocksRepository,
+        bookmarks_repository::BookmarksRepository,
+        conversations_repository::ConversationsRepository,
+        credentials_repository::CredentialsRepository,
+        data_exports_repository::DataExportsRepository,
+        device_keys_repository::DeviceKeysRepository,
+        dm_settings_repository::DmSettingsRepository,
         email_sender_repository::EmailSenderRepository,
         email_verifications_repository::EmailVerificationRepository,
+        envelopes_repository::EnvelopesRepository,
+        event_publisher::EventPublisher,
+        follows_repository::FollowsRepository,
+        imported_posts_repository::ImportedPostsRepository,
+        imports_repository::ImportsRepository,
+        job_queue::JobQueue,
+        keyword_filters_repository::KeywordFiltersRepository,
+        link_previews_repository::LinkPreviewsRepository, link_unfurler::LinkUnfurler,
+        linked_identities_repository::LinkedIdentitiesRepository,
+        lists_repository::ListsRepository,
+        login_challenges_repository::LoginChallengesRepository,
+        media_processor::MediaProcessor, media_repository::MediaRepository,
+        message_broadcaster::MessageBroadcaster,
+        moderation_log_repository::ModerationLogRepository,
+        notifications_repository::NotificationsRepository,
+        oauth_apps_repository::OAuthAppsRepository,
+        oauth_codes_repository::OAuthCodesRepository,
+        oauth_tokens_repository::OAuthTokensRepository, object_store::ObjectStore,
+        oidc_client::OidcClient, oidc_states_repository::OidcStatesRepository,
+        outbox_repository::OutboxRepository,
+        passkeys_repository::PasskeysRepository,
+        personal_access_tokens_repository::PersonalAccessTokensRepository,
+        personal_data_repository::PersonalDataRepository,
+        polls_repository::PollsRepository, posts_repository::PostsRepository,
+        rate_limiter::RateLimiter,
+        reports_repository::ReportsRepository, secret_cipher::SecretCipher,
+        sessions_repository::SessionsRepository,
+        signup_challenges_repository::SignupChallengesRepository,
+        trends_repository::TrendsRepository,
+        two_factor_repository::TwoFactorRepository,
         users_repository::UsersRepository,
+        webauthn_challenges_repository::WebAuthnChallengesRepository,
     },
 };
 use macros::{BArc, barc};
 use redis::Client;
 use sea_orm::DatabaseConnection;
 
-use crate::database::{
-    email_sender_repository::EmailSenderGateway,
-    email_verifications_repository_redis::EmailVerificationsRepositoryRedis,
-    users_repository_postgres::UsersRepositoryPostgres,
+use crate::{
+    aes_secret_cipher::AesSecretCipher,
+    archive_reader::ZipArchiveReader,
+    config::{Config, StorageConfig},
+    database::{
+        blocks_repository_postgres::BlocksRepositoryPostgres,
+        bookmarks_repository_postgres::BookmarksRepositoryPostgres,
+        conversations_repository_postgres::ConversationsRepositoryPostgres,
+        credentials_repository_postgres::CredentialsRepositoryPostgres,
+        data_exports_repository_postgres::DataExportsRepositoryPostgres,
+        device_keys_repository_postgres::DeviceKeysRepositoryPostgres,
+        dm_settings_repository_postgres::DmSettingsRepositoryPostgres,
+        email_sender_repository::EmailSenderGateway,
+        email_verifications_repository_redis::EmailVerificationsRepositoryRedis,
+        envelopes_repository_postgres::EnvelopesRepositoryPostgres,
+        event_publisher_redis::EventPublisherRedis,
+        follows_repository_postgres::FollowsRepositoryPostgres,
+        imported_posts_repository_postgres::ImportedPostsRepositoryPostgres,
+        imports_repository_postgres::ImportsRepositoryPostgres,
+        job_queue_postgres::JobQueuePostgres,
+        keyword_filters_repository_postgres::KeywordFiltersRepositoryPostgres,
+        link_previews_repository_postgres::LinkPreviewsRepositoryPostgres,
+        linked_identities_repository_postgres::LinkedIdentitiesRepositoryPostgres,
+        lists_repository_postgres::ListsRepositoryPostgres,
+        login_challenges_repository_redis::LoginChallengesRepositoryRedis,
+        media_repository_postgres::MediaRepositoryPostgres,
+        moderation_log_repository_postgres::ModerationLogRepositoryPostgres,
+        notifications_repository_postgres::NotificationsRepositoryPostgres,
+        oauth_apps_repository_postgres::OAuthAppsRepositoryPostgres,
+        oauth_codes_repository_redis::OAuthCodesRepositoryRedis,
+        oauth_tokens_repository_postgres::OAuthTokensRepositoryPostgres,
+        oidc_states_repository_redis::OidcStatesRepositoryRedis,
+        outbox_repository_postgres::OutboxRepositoryPostgres,
+        passkeys_repository_postgres::PasskeysRepositoryPostgres,
+        personal_access_tokens_repository_postgres::PersonalAccessTokensRepositoryPostgres,
+        personal_data_repository_postgres::PersonalDataRepositoryPostgres,
+        polls_repository_postgres::PollsRepositoryPostgres,
+        posts_repository_postgres::PostsRepositoryPostgres,
+        rate_limiter_redis::RateLimiterRedis,
+        reports_repository_postgres::ReportsRepositoryPostgres,
+        sessions_repository_redis::SessionsRepositoryRedis,
+        signup_challenges_repository_redis::SignupChallengesRepositoryRedis,
+        trends_repository_redis::TrendsRepositoryRedis,
+        two_factor_repository_postgres::TwoFactorRepositoryPostgres,
+        users_repository_postgres::UsersRepositoryPostgres,
+        webauthn_challenges_repository_redis::WebAuthnChallengesRepositoryRedis,
+    },
+    image_media_processor::ImageMediaProcessor,
+    link_unfurler::{HttpLinkUnfurler, UnfurlerConfig},
+    oidc_client::HttpOidcClient,
+    realtime::ConnectionHub,
+    storage::{local_object_store::LocalObjectStore, s3_object_store::S3ObjectStore},
+    zip_archive_writer::ZipArchiveWriter,
 };
 
 pub struct DatabaseConnector {}
 
 pub struct Dependencies {
     pub signup_with_email: Box<SignupWithEmailFeature>,
+    pub create_signup_challenge: Box<CreateSignupChallengeFeature>,
+    pub login_with_email: Box<LoginWithEmailFeature>,
+    pub login_with_two_factor: Box<LoginWithTwoFactorFeature>,
+    pub enroll_two_factor: Box<EnrollTwoFactorFeature>,
+    pub confirm_two_factor: Box<ConfirmTwoFactorFeature>,
+    pub disable_two_factor: Box<DisableTwoFactorFeature>,
+    pub start_passkey_registration: Box<StartPasskeyRegistrationFeature>,
+    pub register_passkey: Box<RegisterPasskeyFeature>,
+    pub start_passkey_login: Box<StartPasskeyLoginFeature>,
+    pub login_with_passkey: Box<LoginWithPasskeyFeature>,
+    pub start_oidc_login: Box<StartOidcLoginFeature>,
+    pub start_oidc_link: Box<StartOidcLinkFeature>,
+    pub finish_oidc_login: Box<FinishOidcLoginFeature>,
+    pub complete_oidc_signup: Box<CompleteOidcSignupFeature>,
+    pub register_oauth_app: Box<RegisterOAuthAppFeature>,
+    pub get_oauth_consent: Box<GetOAuthConsentFeature>,
+    pub decide_oauth_consent: Box<DecideOAuthConsentFeature>,
+    pub exchange_oauth_token: Box<ExchangeOAuthTokenFeature>,
+    pub introspect_oauth_token: Box<IntrospectOAuthTokenFeature>,
+    pub revoke_oauth_token: Box<RevokeOAuthTokenFeature>,
+    pub create_personal_access_token: Box<CreatePersonalAccessTokenFeature>,
+    pub list_personal_access_tokens: Box<ListPersonalAccessTokensFeature>,
+    pub revoke_personal_access_token: Box<RevokePersonalAccessTokenFeature>,
+    pub authenticate: Box<AuthenticateFeature>,
+    pub create_post: Box<CreatePostFeature>,
+    pub get_hashtag_timeline: Box<GetHashtagTimelineFeature>,
+    pub get_trends: Box<GetTrendsFeature>,
+    pub block_user: Box<BlockUserFeature>,
+    pub unblock_user: Box<UnblockUserFeature>,
+    pub list_notifications: Box<ListNotificationsFeature>,
+    pub upload_media: Box<UploadMediaFeature>,
+    pub get_media: Box<GetMediaFeature>,
+    pub get_media_file: Box<GetMediaFileFeature>,
+    pub set_avatar: Box<SetAvatarFeature>,
+    pub get_poll: Box<GetPollFeature>,
+    pub vote_poll: Box<VotePollFeature>,
+    pub create_bookmark_collection: Box<CreateBookmarkCollectionFeature>,
+    pub list_bookmark_collections: Box<ListBookmarkCollectionsFeature>,
+    pub bookmark_post: Box<BookmarkPostFeature>,
+    pub remove_bookmark: Box<RemoveBookmarkFeature>,
+    pub move_bookmark: Box<MoveBookmarkFeature>,
+    pub list_bookmarks: Box<ListBookmarksFeature>,
+    pub create_list: Box<CreateListFeature>,
+    pub update_list: Box<UpdateListFeature>,
+    pub delete_list: Box<DeleteListFeature>,
+    pub get_list_members: Box<GetListMembersFeature>,
+    pub add_list_member: Box<AddListMemberFeature>,
+    pub remove_list_member: Box<RemoveListMemberFeature>,
+    pub subscribe_list: Box<SubscribeListFeature>,
+    pub unsubscribe_list: Box<UnsubscribeListFeature>,
+    pub get_list_timeline: Box<GetListTimelineFeature>,
+    pub get_user_lists: Box<GetUserListsFeature>,
+    pub get_list_subscriptions: Box<GetListSubscriptionsFeature>,
+    pub start_conversation: Box<StartConversationFeature>,
+    pub list_conversations: Box<ListConversationsFeature>,
+    pub list_messages: Box<ListMessagesFeature>,
+    pub send_message: Box<SendMessageFeature>,
+    pub mark_conversation_read: Box<MarkConversationReadFeature>,
+    pub get_dm_settings: Box<GetDmSettingsFeature>,
+    pub update_dm_settings: Box<UpdateDmSettingsFeature>,
+    pub send_encrypted_message: Box<SendEncryptedMessageFeature>,
+    pub list_envelopes: Box<ListEnvelopesFeature>,
+    pub acknowledge_envelope: Box<AcknowledgeEnvelopeFeature>,
+    pub upload_device_keys: Box<UploadDeviceKeysFeature>,
+    pub upload_prekeys: Box<UploadPrekeysFeature>,
+    pub get_prekey_count: Box<GetPrekeyCountFeature>,
+    pub claim_prekeys: Box<ClaimPrekeysFeature>,
+    pub create_report: Box<CreateReportFeature>,
+    pub list_reports: Box<ListReportsFeature>,
+    pub resolve_report: Box<ResolveReportFeature>,
+    pub list_moderation_log: Box<ListModerationLogFeature>,
+    pub set_user_role: Box<SetUserRoleFeature>,
+    pub suspend_user: Box<SuspendUserFeature>,
+    pub get_profile: Box<GetProfileFeature>,
+    pub delete_account: Box<DeleteAccountFeature>,
+    pub send_email_verification: Box<SendEmailVerificationFeature>,
+    pub verify_email: Box<VerifyEmailFeature>,
+    pub request_data_export: Box<RequestDataExportFeature>,
+    pub download_data_export: Box<DownloadDataExportFeature>,
+    pub start_import: Box<StartImportFeature>,
+    pub get_import: Box<GetImportFeature>,
+    pub run_jobs: Box<RunJobsFeature>,
+    pub schedule_periodic_job: Box<SchedulePeriodicJobFeature>,
+    pub dispatch_events: Box<DispatchEventsFeature>,
+    pub create_keyword_filter: Box<CreateKeywordFilterFeature>,
+    pub list_keyword_filters: Box<ListKeywordFiltersFeature>,
+    pub delete_keyword_filter: Box<DeleteKeywordFilterFeature>,
+    pub check_rate_limit: Box<CheckRateLimitFeature>,
+    /// WebSocket connections conversation events are pushed to.
+    pub connection_hub: ConnectionHub,
 }
 
-pub fn build_dependencies(connection: DatabaseConnection, client: Client) -> Dependencies {
+pub fn build_dependencies(
+    connection: DatabaseConnection,
+    client: Client,
+    config: &Config,
+) -> Dependencies {
+    let connection = Arc::new(connection);
+    let client = Arc::new(client);
+
     // Repositories
     let users_repository: BArc<dyn UsersRepository> =
-        barc!(UsersRepositoryPostgres::new(Arc::new(connection)));
+        barc!(UsersRepositoryPostgres::new(connection.clone()));
+
+    let credentials_repository: BArc<dyn CredentialsRepository> =
+        barc!(CredentialsRepositoryPostgres::new(connection.clone()));
+
+    let posts_repository: BArc<dyn PostsRepository> =
+        barc!(PostsRepositoryPostgres::new(connection.clone()));
+
+    let blocks_repository: BArc<dyn BlocksRepository> =
+        barc!(BlocksRepositoryPostgres::new(connection.clone()));
+
+    let notifications_repository: BArc<dyn NotificationsRepository> =
+        barc!(NotificationsRepositoryPostgres::new(connection.clone()));
+
+    let polls_repository: BArc<dyn PollsRepository> =
+        barc!(PollsRepositoryPostgres::new(connection.clone()));
+
+    let bookmarks_repository: BArc<dyn BookmarksRepository> =
+        barc!(BookmarksRepositoryPostgres::new(connection.clone()));
+
+    let lists_repository: BArc<dyn ListsRepository> =
+        barc!(ListsRepositoryPostgres::new(connection.clone()));
+
+    let conversations_repository: BArc<dyn ConversationsRepository> =
+        barc!(ConversationsRepositoryPostgres::new(connection.clone()));
+
+    let dm_settings_repository: BArc<dyn DmSettingsRepository> =
+        barc!(DmSettingsRepositoryPostgres::new(connection.clone()));
+
+    let follows_repository: BArc<dyn FollowsRepository> =
+        barc!(FollowsRepositoryPostgres::new(connection.clone()));
+
+    let device_keys_repository: BArc<dyn DeviceKeysRepository> =
+        barc!(DeviceKeysRepositoryPostgres::new(connection.clone()));
+
+    let envelopes_repository: BArc<dyn EnvelopesRepository> =
+        barc!(EnvelopesRepositoryPostgres::new(connection.clone()));
+
+    let reports_repository: BArc<dyn ReportsRepository> =
+        barc!(ReportsRepositoryPostgres::new(connection.clone()));
+
+    let moderation_log_repository: BArc<dyn ModerationLogRepository> =
+        barc!(ModerationLogRepositoryPostgres::new(connection.clone()));
+
+    let keyword_filters_repository: BArc<dyn KeywordFiltersRepository> =
+        barc!(KeywordFiltersRepositoryPostgres::new(connection.clone()));
+
+    let two_factor_repository: BArc<dyn TwoFactorRepository> =
+        barc!(TwoFactorRepositoryPostgres::new(connection.clone()));
+
+    let passkeys_repository: BArc<dyn PasskeysRepository> =
+        barc!(PasskeysRepositoryPostgres::new(connection.clone()));
+
+    let linked_identities_repository: BArc<dyn LinkedIdentitiesRepository> =
+        barc!(LinkedIdentitiesRepositoryPostgres::new(connection.clone()));
+
+    let oauth_apps_repository: BArc<dyn OAuthAppsRepository> =
+        barc!(OAuthAppsRepositoryPostgres::new(connection.clone()));
+
+    let oauth_tokens_repository: BArc<dyn OAuthTokensRepository> =
+        barc!(OAuthTokensRepositoryPostgres::new(connection.clone()));
+
+    let personal_access_tokens_repository: BArc<dyn PersonalAccessTokensRepository> =
+        barc!(PersonalAccessTokensRepositoryPostgres::new(connection.clone()));
+
+    let sessions_repository: BArc<dyn SessionsRepository> =
+        barc!(SessionsRepositoryRedis::new(client.clone()));
+
+    let login_challenges_repository: BArc<dyn LoginChallengesRepository> =
+        barc!(LoginChallengesRepositoryRedis::new(client.clone()));
+
+    let webauthn_challenges_repository: BArc<dyn WebAuthnChallengesRepository> =
+        barc!(WebAuthnChallengesRepositoryRedis::new(client.clone()));
+
+    let oidc_states_repository: BArc<dyn OidcStatesRepository> =
+        barc!(OidcStatesRepositoryRedis::new(client.clone()));
+
+    let oauth_codes_repository: BArc<dyn OAuthCodesRepository> =
+        barc!(OAuthCodesRepositoryRedis::new(client.clone()));
+
+    let oidc_client: BArc<dyn OidcClient> = barc!(HttpOidcClient::default());
+
+    let secret_cipher: BArc<dyn SecretCipher> =
+        barc!(AesSecretCipher::new(&config.secrets_encryption_key));
+
+    let trends_repository: BArc<dyn TrendsRepository> =
+        barc!(TrendsRepositoryRedis::new(client.clone()));
 
-    // let email_verifications_repository: BArc<dyn EmailVerificationRepository> = barc!(
-    //     EmailVerificationsRepositoryRedis::new(Arc::new(client))
-    // );
+    let signup_challenges_repository: BArc<dyn SignupChallengesRepository> =
+        barc!(SignupChallengesRepositoryRedis::new(client.clone()));
 
-    // let email_sender_repository: BArc<dyn EmailSenderRepository> = barc!(EmailSenderGateway {});
+    let rate_limiter: BArc<dyn RateLimiter> = barc!(RateLimiterRedis::new(client.clone()));
+
+    let media_repository: BArc<dyn MediaRepository> =
+        barc!(MediaRepositoryPostgres::new(connection.clone()));
+
+    let object_store: BArc<dyn ObjectStore> = match &config.storage {
+        StorageConfig::Local { root } => barc!(LocalObjectStore::new(root)),
+        StorageConfig::S3(s3) => barc!(S3ObjectStore::new(s3.clone())),
+    };
+
+    let media_processor: BArc<dyn MediaProcessor> = barc!(ImageMediaProcessor);
+
+    let data_exports_repository: BArc<dyn DataExportsRepository> =
+        barc!(DataExportsRepositoryPostgres::new(connection.clone()));
+
+    let personal_data_repository: BArc<dyn PersonalDataRepository> =
+        barc!(PersonalDataRepositoryPostgres::new(connection.clone()));
+
+    // Archives stay on local disk whatever the media storage is, they expire within days.
+    let export_store: BArc<dyn ObjectStore> = barc!(LocalObjectStore::new(&config.data_export_dir));
+
+    let archive_writer: BArc<dyn ArchiveWriter> = barc!(ZipArchiveWriter);
+
+    let imported_posts_repository: BArc<dyn ImportedPostsRepository> =
+        barc!(ImportedPostsRepositoryPostgres::new(connection.clone()));
+    let imports_repository: BArc<dyn ImportsRepository> =
+        barc!(ImportsRepositoryPostgres::new(connection.clone()));
+
+    let archive_reader: BArc<dyn ArchiveReader> = barc!(ZipArchiveReader);
+
+    let link_previews_repository: BArc<dyn LinkPreviewsRepository> =
+        barc!(LinkPreviewsRepositoryPostgres::new(connection.clone()));
+
+    let link_unfurler: BArc<dyn LinkUnfurler> =
+        barc!(HttpLinkUnfurler::new(UnfurlerConfig::default()));
+
+    let connection_hub = ConnectionHub::default();
+    let message_broadcaster: BArc<dyn MessageBroadcaster> = barc!(connection_hub.clone());
+
+    let email_verifications_repository: BArc<dyn EmailVerificationRepository> =
+        barc!(EmailVerificationsRepositoryRedis::new(client.clone()));
+
+    let email_sender_repository: BArc<dyn EmailSenderRepository> = barc!(EmailSenderGateway::new(config.mail.clone()));
+
+    let job_queue: BArc<dyn JobQueue> = barc!(JobQueuePostgres::new(connection.clone()));
+
+    let outbox_repository: BArc<dyn OutboxRepository> =
+        barc!(OutboxRepositoryPostgres::new(connection.clone()));
+
+    let event_publisher: BArc<dyn EventPublisher> = barc!(EventPublisherRedis::new(client.clone()));
 
     // Features
 
     // Signup
     let signup_with_email = Box::new(SignupWithEmail {
         users_repository: users_repository.clone(),
+        signup_challenges_repository: signup_challenges_repository.clone(),
+        email_policy: config.email_policy.clone(),
+        age_policy: config.age_policy.clone(),
+        proof_of_work_policy: config.proof_of_work.clone(),
+    });
+
+    let create_signup_challenge = Box::new(CreateSignupChallenge {
+        signup_challenges_repository: signup_challenges_repository.clone(),
+        policy: config.proof_of_work.clone(),
+    });
+
+    let account_deletion_policy = AccountDeletionPolicy::default();
+
+    // Auth
+    let login_with_email = Box::new(LoginWithEmail {
+        users_repository: users_repository.clone(),
+        credentials_repository: credentials_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        two_factor_repository: two_factor_repository.clone(),
+        login_challenges_repository: login_challenges_repository.clone(),
+        email_policy: config.email_policy.clone(),
+        two_factor_policy: config.two_factor_policy.clone(),
+        account_deletion_policy: account_deletion_policy.clone(),
+    });
+
+    let login_with_two_factor = Box::new(LoginWithTwoFactor {
+        users_repository: users_repository.clone(),
+        login_challenges_repository: login_challenges_repository.clone(),
+        two_factor_repository: two_factor_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        secret_cipher: secret_cipher.clone(),
+        policy: config.two_factor_policy.clone(),
+        account_deletion_policy: account_deletion_policy.clone(),
+    });
+
+    let enroll_two_factor = Box::new(EnrollTwoFactor {
+        users_repository: users_repository.clone(),
+        two_factor_repository: two_factor_repository.clone(),
+        secret_cipher: secret_cipher.clone(),
+        policy: config.two_factor_policy.clone(),
+    });
+
+    let confirm_two_factor = Box::new(ConfirmTwoFactor {
+        two_factor_repository: two_factor_repository.clone(),
+        secret_cipher: secret_cipher.clone(),
+        policy: config.two_factor_policy.clone(),
+    });
+
+    let disable_two_factor = Box::new(DisableTwoFactor {
+        credentials_repository: credentials_repository.clone(),
+        two_factor_repository: two_factor_repository.clone(),
+    });
+
+    // Passkeys
+    let start_passkey_registration = Box::new(StartPasskeyRegistration {
+        users_repository: users_repository.clone(),
+        passkeys_repository: passkeys_repository.clone(),
+        webauthn_challenges_repository: webauthn_challenges_repository.clone(),
+        policy: config.passkey_policy.clone(),
+    });
+
+    let register_passkey = Box::new(RegisterPasskey {
+        passkeys_repository: passkeys_repository.clone(),
+        webauthn_challenges_repository: webauthn_challenges_repository.clone(),
+        policy: config.passkey_policy.clone(),
+    });
+
+    let start_passkey_login = Box::new(StartPasskeyLogin {
+        passkeys_repository: passkeys_repository.clone(),
+        login_challenges_repository: login_challenges_repository.clone(),
+        webauthn_challenges_repository: webauthn_challenges_repository.clone(),
+        policy: config.passkey_policy.clone(),
+    });
+
+    let login_with_passkey = Box::new(LoginWithPasskey {
+        users_repository: users_repository.clone(),
+        passkeys_repository: passkeys_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        login_challenges_repository: login_challenges_repository.clone(),
+        webauthn_challenges_repository: webauthn_challenges_repository.clone(),
+        policy: config.passkey_policy.clone(),
+        account_deletion_policy: account_deletion_policy.clone(),
+    });
+
+    // "Sign in with" providers
+    let start_oidc_login = Box::new(StartOidcLogin {
+        oidc_client: oidc_client.clone(),
+        oidc_states_repository: oidc_states_repository.clone(),
+        policy: config.oidc_policy.clone(),
+    });
+
+    let start_oidc_link = Box::new(StartOidcLogin {
+        oidc_client: oidc_client.clone(),
+        oidc_states_repository: oidc_states_repository.clone(),
+        policy: config.oidc_policy.clone(),
+    });
+
+    let finish_oidc_login = Box::new(FinishOidcLogin {
+        users_repository: users_repository.clone(),
+        linked_identities_repository: linked_identities_repository.clone(),
+        oidc_client: oidc_client.clone(),
+        oidc_states_repository: oidc_states_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        two_factor_repository: two_factor_repository.clone(),
+        login_challenges_repository: login_challenges_repository.clone(),
+        policy: config.oidc_policy.clone(),
+        email_policy: config.email_policy.clone(),
+        two_factor_policy: config.two_factor_policy.clone(),
+        account_deletion_policy: account_deletion_policy.clone(),
+    });
+
+    let complete_oidc_signup = Box::new(CompleteOidcSignup {
+        users_repository: users_repository.clone(),
+        linked_identities_repository: linked_identities_repository.clone(),
+        oidc_states_repository: oidc_states_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        email_policy: config.email_policy.clone(),
+        age_policy: config.age_policy.clone(),
+    });
+
+    // OAuth
+    let register_oauth_app = Box::new(RegisterOAuthApp {
+        oauth_apps_repository: oauth_apps_repository.clone(),
+        policy: OAuthPolicy::default(),
+    });
+
+    let get_oauth_consent = Box::new(GetOAuthConsent {
+        oauth_apps_repository: oauth_apps_repository.clone(),
+    });
+
+    let decide_oauth_consent = Box::new(DecideOAuthConsent {
+        oauth_apps_repository: oauth_apps_repository.clone(),
+        oauth_codes_repository: oauth_codes_repository.clone(),
+        policy: OAuthPolicy::default(),
+    });
+
+    let exchange_oauth_token = Box::new(ExchangeOAuthToken {
+        oauth_apps_repository: oauth_apps_repository.clone(),
+        oauth_codes_repository: oauth_codes_repository.clone(),
+        oauth_tokens_repository: oauth_tokens_repository.clone(),
+        policy: OAuthPolicy::default(),
+    });
+
+    let introspect_oauth_token = Box::new(IntrospectOAuthToken {
+        oauth_apps_repository: oauth_apps_repository.clone(),
+        oauth_tokens_repository: oauth_tokens_repository.clone(),
+    });
+
+    let revoke_oauth_token = Box::new(RevokeOAuthToken {
+        oauth_apps_repository: oauth_apps_repository.clone(),
+        oauth_tokens_repository: oauth_tokens_repository.clone(),
+    });
+
+    // Personal access tokens
+    let create_personal_access_token = Box::new(CreatePersonalAccessToken {
+        personal_access_tokens_repository: personal_access_tokens_repository.clone(),
+        policy: PersonalAccessTokenPolicy::default(),
+    });
+
+    let list_personal_access_tokens = Box::new(ListPersonalAccessTokens {
+        personal_access_tokens_repository: personal_access_tokens_repository.clone(),
+    });
+
+    let revoke_personal_access_token = Box::new(RevokePersonalAccessToken {
+        personal_access_tokens_repository: personal_access_tokens_repository.clone(),
+    });
+
+    let authenticate = Box::new(Authenticate {
+        sessions_repository: sessions_repository.clone(),
+        users_repository: users_repository.clone(),
+        oauth_tokens_repository: oauth_tokens_repository.clone(),
+        personal_access_tokens_repository: personal_access_tokens_repository.clone(),
+    });
+
+    // Posts
+    let create_post = Box::new(CreatePost {
+        posts_repository: posts_repository.clone(),
+        trends_repository: trends_repository.clone(),
+        users_repository: users_repository.clone(),
+        media_repository: media_repository.clone(),
+        media_policy: MediaPolicy::default(),
+        polls_repository: polls_repository.clone(),
+        poll_policy: PollPolicy::default(),
+        job_queue: job_queue.clone(),
+    });
+
+    let get_hashtag_timeline = Box::new(GetHashtagTimeline {
+        posts_repository: posts_repository.clone(),
+        keyword_filters_repository: keyword_filters_repository.clone(),
+        users_repository: users_repository.clone(),
+        age_policy: config.age_policy.clone(),
+    });
+
+    // Trends
+    let get_trends = Box::new(GetTrends {
+        trends_repository: trends_repository.clone(),
+    });
+
+    // Blocks
+    let block_user = Box::new(BlockUser {
+        users_repository: users_repository.clone(),
+        blocks_repository: blocks_repository.clone(),
+    });
+
+    let unblock_user = Box::new(UnblockUser {
+        blocks_repository: blocks_repository.clone(),
+    });
+
+    // Notifications
+    let list_notifications = Box::new(ListNotifications {
+        notifications_repository: notifications_repository.clone(),
+        posts_repository: posts_repository.clone(),
+        keyword_filters_repository: keyword_filters_repository.clone(),
+    });
+
+    // Media
+    let upload_media = Box::new(UploadMedia {
+        media_repository: media_repository.clone(),
+        object_store: object_store.clone(),
+        media_processor: media_processor.clone(),
+        job_queue: job_queue.clone(),
+        policy: MediaPolicy::default(),
+    });
+
+    let get_media = Box::new(GetMedia {
+        media_repository: media_repository.clone(),
+    });
+
+    let get_media_file = Box::new(GetMediaFile {
+        media_repository: media_repository.clone(),
+        object_store: object_store.clone(),
+    });
+
+    let set_avatar = Box::new(SetAvatar {
+        media_repository: media_repository.clone(),
+        users_repository: users_repository.clone(),
+    });
+
+    // Polls
+    let get_poll = Box::new(GetPoll {
+        posts_repository: posts_repository.clone(),
+        polls_repository: polls_repository.clone(),
+    });
+
+    let vote_poll = Box::new(VotePoll {
+        polls_repository: polls_repository.clone(),
+    });
+
+    let notify_ended_polls = Box::new(NotifyEndedPolls {
+        posts_repository: posts_repository.clone(),
+        polls_repository: polls_repository.clone(),
+        notifications_repository: notifications_repository.clone(),
+    });
+
+    // Bookmarks
+    let create_bookmark_collection = Box::new(CreateBookmarkCollection {
+        bookmarks_repository: bookmarks_repository.clone(),
+    });
+
+    let list_bookmark_collections = Box::new(ListBookmarkCollections {
+        bookmarks_repository: bookmarks_repository.clone(),
+    });
+
+    let bookmark_post = Box::new(BookmarkPost {
+        posts_repository: posts_repository.clone(),
+        bookmarks_repository: bookmarks_repository.clone(),
+    });
+
+    let remove_bookmark = Box::new(RemoveBookmark {
+        bookmarks_repository: bookmarks_repository.clone(),
+    });
+
+    let move_bookmark = Box::new(MoveBookmark {
+        bookmarks_repository: bookmarks_repository.clone(),
+    });
+
+    let list_bookmarks = Box::new(ListBookmarks {
+        posts_repository: posts_repository.clone(),
+        bookmarks_repository: bookmarks_repository.clone(),
+        users_repository: users_repository.clone(),
+        polls_repository: polls_repository.clone(),
+        age_policy: config.age_policy.clone(),
+    });
+
+    // Lists
+    let create_list = Box::new(CreateList {
+        lists_repository: lists_repository.clone(),
+        policy: ListPolicy::default(),
+    });
+
+    let update_list = Box::new(UpdateList {
+        lists_repository: lists_repository.clone(),
+    });
+
+    let delete_list = Box::new(DeleteList {
+        lists_repository: lists_repository.clone(),
+    });
+
+    let get_list_members = Box::new(GetListMembers {
+        lists_repository: lists_repository.clone(),
+    });
+
+    let add_list_member = Box::new(AddListMember {
+        lists_repository: lists_repository.clone(),
+        users_repository: users_repository.clone(),
+        blocks_repository: blocks_repository.clone(),
+        notifications_repository: notifications_repository.clone(),
+        policy: ListPolicy::default(),
+    });
+
+    let remove_list_member = Box::new(RemoveListMember {
+        lists_repository: lists_repository.clone(),
+    });
+
+    let subscribe_list = Box::new(SubscribeList {
+        lists_repository: lists_repository.clone(),
+    });
+
+    let unsubscribe_list = Box::new(UnsubscribeList {
+        lists_repository: lists_repository.clone(),
+    });
+
+    let get_list_timeline = Box::new(GetListTimeline {
+        lists_repository: lists_repository.clone(),
+        posts_repository: posts_repository.clone(),
+        keyword_filters_repository: keyword_filters_repository.clone(),
+        users_repository: users_repository.clone(),
+        age_policy: config.age_policy.clone(),
+    });
+
+    let get_user_lists = Box::new(GetUserLists {
+        lists_repository: lists_repository.clone(),
+    });
+
+    let get_list_subscriptions = Box::new(GetListSubscriptions {
+        lists_repository: lists_repository.clone(),
+    });
+
+    // Conversations
+    let start_conversation = Box::new(StartConversation {
+        conversations_repository: conversations_repository.clone(),
+        users_repository: users_repository.clone(),
+        blocks_repository: blocks_repository.clone(),
+        dm_settings_repository: dm_settings_repository.clone(),
+        follows_repository: follows_repository.clone(),
+        policy: ConversationPolicy::default(),
+    });
+
+    let list_conversations = Box::new(ListConversations {
+        conversations_repository: conversations_repository.clone(),
+    });
+
+    let list_messages = Box::new(ListMessages {
+        conversations_repository: conversations_repository.clone(),
+    });
+
+    let send_message = Box::new(SendMessage {
+        conversations_repository: conversations_repository.clone(),
+        blocks_repository: blocks_repository.clone(),
+        media_repository: media_repository.clone(),
+        message_broadcaster: message_broadcaster.clone(),
+        policy: ConversationPolicy::default(),
+    });
+
+    let mark_conversation_read = Box::new(MarkConversationRead {
+        conversations_repository: conversations_repository.clone(),
+        message_broadcaster: message_broadcaster.clone(),
+    });
+
+    let get_dm_settings = Box::new(GetDmSettings {
+        dm_settings_repository: dm_settings_repository.clone(),
+    });
+
+    let update_dm_settings = Box::new(UpdateDmSettings {
+        dm_settings_repository: dm_settings_repository.clone(),
+    });
+
+    let send_encrypted_message = Box::new(SendEncryptedMessage {
+        conversations_repository: conversations_repository.clone(),
+        envelopes_repository: envelopes_repository.clone(),
+        device_keys_repository: device_keys_repository.clone(),
+        blocks_repository: blocks_repository.clone(),
+        message_broadcaster: message_broadcaster.clone(),
+        policy: ConversationPolicy::default(),
+    });
+
+    let list_envelopes = Box::new(ListEnvelopes {
+        envelopes_repository: envelopes_repository.clone(),
+    });
+
+    let acknowledge_envelope = Box::new(AcknowledgeEnvelope {
+        envelopes_repository: envelopes_repository.clone(),
+    });
+
+    // Device keys
+    let upload_device_keys = Box::new(UploadDeviceKeys {
+        device_keys_repository: device_keys_repository.clone(),
+        policy: DeviceKeysPolicy::default(),
+    });
+
+    let upload_prekeys = Box::new(UploadPrekeys {
+        device_keys_repository: device_keys_repository.clone(),
+        policy: DeviceKeysPolicy::default(),
+    });
+
+    let get_prekey_count = Box::new(GetPrekeyCount {
+        device_keys_repository: device_keys_repository.clone(),
+    });
+
+    let claim_prekeys = Box::new(ClaimPrekeys {
+        device_keys_repository: device_keys_repository.clone(),
+        users_repository: users_repository.clone(),
+        blocks_repository: blocks_repository.clone(),
+    });
+
+    // Moderation
+    let create_report = Box::new(CreateReport {
+        reports_repository: reports_repository.clone(),
+        posts_repository: posts_repository.clone(),
+        users_repository: users_repository.clone(),
+        conversations_repository: conversations_repository.clone(),
+    });
+
+    let list_reports = Box::new(ListReports {
+        reports_repository: reports_repository.clone(),
+        users_repository: users_repository.clone(),
+    });
+
+    let resolve_report = Box::new(ResolveReport {
+        reports_repository: reports_repository.clone(),
+        users_repository: users_repository.clone(),
+        posts_repository: posts_repository.clone(),
+        notifications_repository: notifications_repository.clone(),
+        moderation_log_repository: moderation_log_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+    });
+
+    let list_moderation_log = Box::new(ListModerationLog {
+        moderation_log_repository: moderation_log_repository.clone(),
+        users_repository: users_repository.clone(),
+    });
+
+    let set_user_role = Box::new(SetUserRole {
+        users_repository: users_repository.clone(),
+        moderation_log_repository: moderation_log_repository.clone(),
+    });
+
+    let suspend_user = Box::new(SuspendUser {
+        users_repository: users_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        moderation_log_repository: moderation_log_repository.clone(),
+    });
+
+    // Users
+    let get_profile = Box::new(GetProfile {
+        users_repository: users_repository.clone(),
+    });
+
+    let delete_account = Box::new(DeleteAccount {
+        users_repository: users_repository.clone(),
+        credentials_repository: credentials_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        policy: account_deletion_policy.clone(),
+    });
+
+    let send_email_verification = Box::new(SendEmailVerification {
+        users_repository: users_repository.clone(),
+        email_verification_repository: email_verifications_repository.clone(),
+        job_queue: job_queue.clone(),
+    });
+
+    let verify_email = Box::new(VerifyEmail {
+        users_repository: users_repository.clone(),
+        email_verification_repository: email_verifications_repository.clone(),
+    });
+
+    let purge_deleted_accounts = Box::new(PurgeDeletedAccounts {
+        users_repository: users_repository.clone(),
+        media_repository: media_repository.clone(),
+        object_store: object_store.clone(),
+        email_verification_repository: email_verifications_repository.clone(),
+        sessions_repository: sessions_repository.clone(),
+        policy: account_deletion_policy,
+    });
+
+    // Data exports
+    let request_data_export = Box::new(RequestDataExport {
+        data_exports_repository: data_exports_repository.clone(),
+    });
+
+    let build_data_exports = Box::new(BuildDataExports {
+        data_exports_repository: data_exports_repository.clone(),
+        personal_data_repository: personal_data_repository.clone(),
+        object_store: object_store.clone(),
+        export_store: export_store.clone(),
+        archive_writer: archive_writer.clone(),
+        job_queue: job_queue.clone(),
+        policy: config.data_export_policy.clone(),
+    });
+
+    let download_data_export = Box::new(DownloadDataExport {
+        data_exports_repository: data_exports_repository.clone(),
+        export_store: export_store.clone(),
+    });
+
+    // Imports
+    let start_import = Box::new(StartImport {
+        imports_repository: imports_repository.clone(),
+        object_store: object_store.clone(),
+        job_queue: job_queue.clone(),
+        policy: ImportPolicy::default(),
+    });
+
+    let get_import = Box::new(GetImport {
+        imports_repository: imports_repository.clone(),
+    });
+
+    let import_archive = Box::new(ImportArchive {
+        archive_reader: archive_reader.clone(),
+        imports_repository: imports_repository.clone(),
+        imported_posts_repository: imported_posts_repository.clone(),
+        posts_repository: posts_repository.clone(),
+        media_repository: media_repository.clone(),
+        object_store: object_store.clone(),
+        media_processor: media_processor.clone(),
+        job_queue: job_queue.clone(),
+        media_policy: MediaPolicy::default(),
+        policy: ImportPolicy::default(),
+    });
+
+    // Jobs
+    let run_jobs = Box::new(RunJobs {
+        job_queue: job_queue.clone(),
+        unfurl_post_link: Box::new(UnfurlPostLink {
+            link_previews_repository: link_previews_repository.clone(),
+            link_unfurler: link_unfurler.clone(),
+            policy: LinkPreviewPolicy::default(),
+        }),
+        notify_mentions: Box::new(NotifyMentions {
+            posts_repository: posts_repository.clone(),
+            blocks_repository: blocks_repository.clone(),
+            notifications_repository: notifications_repository.clone(),
+        }),
+        process_media: Box::new(ProcessMedia {
+            media_repository: media_repository.clone(),
+            object_store: object_store.clone(),
+            media_processor: media_processor.clone(),
+            policy: MediaPolicy::default(),
+        }),
+        import_archive,
+        notify_ended_polls,
+        purge_deleted_accounts,
+        build_data_exports,
+        email_sender_repository: email_sender_repository.clone(),
+        policy: JobPolicy::default(),
+    });
+
+    let schedule_periodic_job = Box::new(SchedulePeriodicJob {
+        job_queue: job_queue.clone(),
+    });
+
+    // Events
+    let dispatch_events = Box::new(DispatchEvents {
+        outbox_repository: outbox_repository.clone(),
+        event_publisher: event_publisher.clone(),
+        subscribers: vec![Box::new(WelcomeRegisteredUser {
+            users_repository: users_repository.clone(),
+            job_queue: job_queue.clone(),
+        })],
+    });
+
+    // Filters
+    let create_keyword_filter = Box::new(CreateKeywordFilter {
+        keyword_filters_repository: keyword_filters_repository.clone(),
+        policy: FilterPolicy::default(),
+    });
+
+    let list_keyword_filters = Box::new(ListKeywordFilters {
+        keyword_filters_repository: keyword_filters_repository.clone(),
+    });
+
+    let delete_keyword_filter = Box::new(DeleteKeywordFilter {
+        keyword_filters_repository: keyword_filters_repository.clone(),
+    });
+
+    // Rate limiting
+    let check_rate_limit = Box::new(CheckRateLimit {
+        rate_limiter: rate_limiter.clone(),
+        policy: RateLimitPolicy::default(),
     });
 
-    Dependencies { signup_with_email }
+    Dependencies {
+        signup_with_email,
+        create_signup_challenge,
+        login_with_email,
+        login_with_two_factor,
+        enroll_two_factor,
+        confirm_two_factor,
+        disable_two_factor,
+        start_passkey_registration,
+        register_passkey,
+        start_passkey_login,
+        login_with_passkey,
+        start_oidc_login,
+        start_oidc_link,
+        finish_oidc_login,
+        complete_oidc_signup,
+        register_oauth_app,
+        get_oauth_consent,
+        decide_oauth_consent,
+        exchange_oauth_token,
+        introspect_oauth_token,
+        revoke_oauth_token,
+        create_personal_access_token,
+        list_personal_access_tokens,
+        revoke_personal_access_token,
+        authenticate,
+        create_post,
+        get_hashtag_timeline,
+        get_trends,
+        block_user,
+        unblock_user,
+        list_notifications,
+        upload_media,
+        get_media,
+        get_media_file,
+        set_avatar,
+        get_poll,
+        vote_poll,
+        create_bookmark_collection,
+        list_bookmark_collections,
+        bookmark_post,
+        remove_bookmark,
+        move_bookmark,
+        list_bookmarks,
+        create_list,
+        update_list,
+        delete_list,
+        get_list_members,
+        add_list_member,
+        remove_list_member,
+        subscribe_list,
+        unsubscribe_list,
+        get_list_timeline,
+        get_user_lists,
+        get_list_subscriptions,
+        start_conversation,
+        list_conversations,
+        list_messages,
+        send_message,
+        mark_conversation_read,
+        get_dm_settings,
+        update_dm_settings,
+        send_encrypted_message,
+        list_envelopes,
+        acknowledge_envelope,
+        upload_device_keys,
+        upload_prekeys,
+        get_prekey_count,
+        claim_prekeys,
+        create_report,
+        list_reports,
+        resolve_report,
+        list_moderation_log,
+        set_user_role,
+        suspend_user,
+        get_profile,
+        delete_account,
+        send_email_verification,
+        verify_email,
+        request_data_export,
+        download_data_export,
+        start_import,
+        get_import,
+        run_jobs,
+        schedule_periodic_job,
+        dispatch_events,
+        create_keyword_filter,
+        list_keyword_filters,
+        delete_keyword_filter,
+        check_rate_limit,
+        connection_hub,
+    }
 }
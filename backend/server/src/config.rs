@@ -0,0 +1,212 @@
+use std::env;
+
+use domain::policies::{
+    age::AgePolicy,
+    data_export::DataExportPolicy,
+    oidc::{OidcPolicy, OidcProvider},
+    passkey::PasskeyPolicy,
+    signup::{EmailPolicy, PlusAddressing, ProofOfWorkPolicy},
+    two_factor::TwoFactorPolicy,
+};
+
+use crate::{database::email_sender_repository::MailConfig, storage::s3_object_store::S3Config};
+
+/// Where uploaded media are stored, selected with `MEDIA_STORAGE`.
+#[derive(Debug, Clone)]
+pub enum StorageConfig {
+    /// Files below `MEDIA_LOCAL_DIR` (defaults to `./media`).
+    Local { root: String },
+    /// Any S3 compatible service, configured with the `S3_*` variables.
+    S3(S3Config),
+}
+
+/// Settings read from the environment on top of the connection urls.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub storage: StorageConfig,
+    /// Where personal data exports are written, `DATA_EXPORT_DIR` (defaults to `./exports`).
+    pub data_export_dir: String,
+    pub data_export_policy: DataExportPolicy,
+    pub email_policy: EmailPolicy,
+    /// Enabled by setting `SIGNUP_POW_DIFFICULTY` above zero.
+    pub proof_of_work: Option<ProofOfWorkPolicy>,
+    pub age_policy: AgePolicy,
+    pub two_factor_policy: TwoFactorPolicy,
+    pub passkey_policy: PasskeyPolicy,
+    pub oidc_policy: OidcPolicy,
+    /// AES-256 key of the secrets stored at rest, `SECRETS_ENCRYPTION_KEY` in hex.
+    pub secrets_encryption_key: Vec<u8>,
+    pub mail: MailConfig,
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        let storage = match env::var("MEDIA_STORAGE").unwrap_or_else(|_| "local".into()).as_str() {
+            "s3" =>
+                StorageConfig::S3(S3Config {
+                    endpoint: required("S3_ENDPOINT"),
+                    bucket: required("S3_BUCKET"),
+                    region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".into()),
+                    access_key: required("S3_ACCESS_KEY"),
+                    secret_key: required("S3_SECRET_KEY"),
+                }),
+            "local" =>
+                StorageConfig::Local {
+                    root: env::var("MEDIA_LOCAL_DIR").unwrap_or_else(|_| "./media".into()),
+                },
+            other => panic!("MEDIA_STORAGE must be `local` or `s3`, got `{}`", other),
+        };
+
+        Self {
+            storage,
+            data_export_dir: env::var("DATA_EXPORT_DIR").unwrap_or_else(|_| "./exports".into()),
+            data_export_policy: data_export_policy(),
+            email_policy: email_policy(),
+            proof_of_work: proof_of_work(),
+            age_policy: age_policy(),
+            two_factor_policy: TwoFactorPolicy {
+                issuer: env::var("TOTP_ISSUER").unwrap_or_else(|_| "Hearth".into()),
+                ..TwoFactorPolicy::default()
+            },
+            secrets_encryption_key: secrets_encryption_key(),
+            passkey_policy: passkey_policy(),
+            oidc_policy: oidc_policy(),
+            mail: MailConfig {
+                api_url: required("MAIL_API_URL"),
+                api_key: required("MAIL_API_KEY"),
+                from: required("MAIL_FROM"),
+            },
+        }
+    }
+}
+
+/// `SIGNUP_BLOCKED_DOMAINS` replaces the built-in list of disposable
+/// domains, both lists are comma separated.
+fn email_policy() -> EmailPolicy {
+    let default = EmailPolicy::default();
+    let domains = |name: &str| {
+        env::var(name).ok().map(|list| {
+            list.split(',')
+                .map(|domain| domain.trim().to_lowercase())
+                .filter(|domain| !domain.is_empty())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let plus_addressing = match env::var("SIGNUP_PLUS_ADDRESSING").as_deref() {
+        Ok("keep") => PlusAddressing::Keep,
+        Ok("strip") | Err(_) => PlusAddressing::Strip,
+        Ok(other) => panic!("SIGNUP_PLUS_ADDRESSING must be `keep` or `strip`, got `{}`", other),
+    };
+
+    EmailPolicy {
+        blocked_domains: domains("SIGNUP_BLOCKED_DOMAINS").unwrap_or(default.blocked_domains),
+        allowed_domains: domains("SIGNUP_ALLOWED_DOMAINS").unwrap_or(default.allowed_domains),
+        plus_addressing,
+    }
+}
+
+/// `SIGNUP_MINIMUM_AGE` and `SENSITIVE_CONTENT_AGE` override the defaults.
+fn age_policy() -> AgePolicy {
+    let default = AgePolicy::default();
+    let age = |name: &str, default: u32| {
+        env::var(name).map_or(default, |age| {
+            age.parse()
+                .unwrap_or_else(|_| panic!("{} must be a number of years", name))
+        })
+    };
+
+    AgePolicy {
+        minimum_age: age("SIGNUP_MINIMUM_AGE", default.minimum_age),
+        sensitive_content_age: age("SENSITIVE_CONTENT_AGE", default.sensitive_content_age),
+        ..default
+    }
+}
+
+fn proof_of_work() -> Option<ProofOfWorkPolicy> {
+    let difficulty = env::var("SIGNUP_POW_DIFFICULTY")
+        .ok()?
+        .parse::<u8>()
+        .expect("SIGNUP_POW_DIFFICULTY must be a number of bits");
+
+    (difficulty > 0).then(|| ProofOfWorkPolicy {
+        difficulty,
+        ..ProofOfWorkPolicy::default()
+    })
+}
+
+/// `PUBLIC_URL` is the address clients reach the server at, download links
+/// are built from it.
+fn data_export_policy() -> DataExportPolicy {
+    let default = DataExportPolicy::default();
+
+    DataExportPolicy {
+        ttl_days: env::var("DATA_EXPORT_TTL_DAYS").map_or(default.ttl_days, |days| {
+            days.parse().expect("DATA_EXPORT_TTL_DAYS must be a number of days")
+        }),
+        download_base_url: env::var("PUBLIC_URL")
+            .map_or(default.download_base_url, |url| url.trim_end_matches('/').to_string()),
+    }
+}
+
+/// `WEBAUTHN_RP_ID` must be the domain the clients are served from, passkeys
+/// registered for one relying party can't be used with another.
+fn passkey_policy() -> PasskeyPolicy {
+    let default = PasskeyPolicy::default();
+
+    PasskeyPolicy {
+        rp_id: env::var("WEBAUTHN_RP_ID").unwrap_or(default.rp_id),
+        rp_name: env::var("WEBAUTHN_RP_NAME").unwrap_or(default.rp_name),
+        origins: env::var("WEBAUTHN_ORIGINS").map_or(default.origins, |origins| {
+            origins
+                .split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect()
+        }),
+        require_user_verification: env::var("WEBAUTHN_REQUIRE_USER_VERIFICATION")
+            .is_ok_and(|value| value == "true"),
+        ..default
+    }
+}
+
+/// `OIDC_PROVIDERS` lists the provider names, each one configured with
+/// `OIDC_<NAME>_ISSUER`, `_CLIENT_ID`, `_CLIENT_SECRET` and `_REDIRECT_URI`.
+fn oidc_policy() -> OidcPolicy {
+    let providers = env::var("OIDC_PROVIDERS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let variable =
+                |suffix: &str| required(&format!("OIDC_{}_{}", name.to_uppercase(), suffix));
+
+            OidcProvider {
+                issuer: variable("ISSUER"),
+                client_id: variable("CLIENT_ID"),
+                client_secret: variable("CLIENT_SECRET"),
+                redirect_uri: variable("REDIRECT_URI"),
+                scopes: vec!["openid".into(), "email".into()],
+                name,
+            }
+        })
+        .collect();
+
+    OidcPolicy {
+        providers,
+        ..OidcPolicy::default()
+    }
+}
+
+/// Losing or changing it makes every enrolled second factor unusable.
+fn secrets_encryption_key() -> Vec<u8> {
+    let key = hex::decode(required("SECRETS_ENCRYPTION_KEY"))
+        .expect("SECRETS_ENCRYPTION_KEY must be hex encoded");
+    assert_eq!(key.len(), 32, "SECRETS_ENCRYPTION_KEY must be 32 bytes long");
+    key
+}
+
+fn required(name: &str) -> String {
+    env::var(name).unwrap_or_else(|_| panic!("{} is not set in .env file", name))
+}
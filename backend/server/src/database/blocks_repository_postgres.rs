@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::repositories::blocks_repository::BlocksRepository;
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    DatabaseConnection,
+    EntityTrait,
+    PaginatorTrait,
+    QueryFilter,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::blocks, unexpected };
+
+pub struct BlocksRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl BlocksRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl BlocksRepository for BlocksRepositoryPostgres {
+    async fn block(&self, blocker_id: &Uuid, blocked_id: &Uuid) -> Result<(), HearthError> {
+        blocks::Entity
+            ::insert(blocks::ActiveModel {
+                blocker_id: Set(*blocker_id),
+                blocked_id: Set(*blocked_id),
+                ..Default::default()
+            })
+            .on_conflict(
+                OnConflict::columns([blocks::Column::BlockerId, blocks::Column::BlockedId])
+                    .do_nothing()
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("BLOCK_USER_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn unblock(&self, blocker_id: &Uuid, blocked_id: &Uuid) -> Result<(), HearthError> {
+        blocks::Entity
+            ::delete_many()
+            .filter(blocks::Column::BlockerId.eq(*blocker_id))
+            .filter(blocks::Column::BlockedId.eq(*blocked_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("UNBLOCK_USER_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn is_blocked_between(&self, user_a: &Uuid, user_b: &Uuid) -> Result<bool, HearthError> {
+        let count = blocks::Entity
+            ::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(blocks::Column::BlockerId.eq(*user_a))
+                            .add(blocks::Column::BlockedId.eq(*user_b))
+                    )
+                    .add(
+                        Condition::all()
+                            .add(blocks::Column::BlockerId.eq(*user_b))
+                            .add(blocks::Column::BlockedId.eq(*user_a))
+                    )
+            )
+            .count(self.connection.as_ref()).await
+            .map_err(unexpected("IS_BLOCKED_ERROR"))?;
+
+        Ok(count > 0)
+    }
+}
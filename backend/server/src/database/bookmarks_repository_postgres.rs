@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::{ bookmark::{ BookmarkCollectionDTO, BookmarkDTO }, pagination::PositionCursor },
+    error_codes::{
+        BOOKMARK_COLLECTION_NAME_TAKEN_ERROR_CODE,
+        BOOKMARK_COLLECTION_NOT_FOUND_ERROR_CODE,
+        BOOKMARK_NOT_FOUND_ERROR_CODE,
+    },
+    repositories::bookmarks_repository::BookmarksRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::{ Expr, OnConflict },
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::{ bookmark_collections, bookmarks }, transaction_error, unexpected };
+
+pub struct BookmarksRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl BookmarksRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+/// Bookmarks of `owner_id` within a collection, `None` being the default one.
+fn in_collection(owner_id: &Uuid, collection_id: Option<Uuid>) -> Condition {
+    let collection = match collection_id {
+        Some(collection_id) => bookmarks::Column::CollectionId.eq(collection_id),
+        None => bookmarks::Column::CollectionId.is_null(),
+    };
+
+    Condition::all().add(bookmarks::Column::OwnerId.eq(*owner_id)).add(collection)
+}
+
+fn to_collection_dto(model: bookmark_collections::Model) -> BookmarkCollectionDTO {
+    BookmarkCollectionDTO {
+        collection_id: model.id,
+        owner_id: model.owner_id,
+        name: model.name,
+        created_at: model.created_at.and_utc(),
+    }
+}
+
+pub(crate) fn to_dto(model: bookmarks::Model) -> BookmarkDTO {
+    BookmarkDTO {
+        post_id: model.post_id,
+        collection_id: model.collection_id,
+        position: model.position,
+        created_at: model.created_at.and_utc(),
+        post: None,
+    }
+}
+
+#[async_trait]
+impl BookmarksRepository for BookmarksRepositoryPostgres {
+    async fn create_collection(&self, collection: BookmarkCollectionDTO) -> Result<(), HearthError> {
+        let inserted = bookmark_collections::Entity
+            ::insert(bookmark_collections::ActiveModel {
+                id: Set(collection.collection_id),
+                owner_id: Set(collection.owner_id),
+                name: Set(collection.name),
+                created_at: Set(collection.created_at.naive_utc()),
+            })
+            .on_conflict(
+                OnConflict::columns([
+                    bookmark_collections::Column::OwnerId,
+                    bookmark_collections::Column::Name,
+                ])
+                    .do_nothing()
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_BOOKMARK_COLLECTION_ERROR"))?;
+
+        if inserted == 0 {
+            return Err(HearthError::Domain(BOOKMARK_COLLECTION_NAME_TAKEN_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn get_collection(
+        &self,
+        owner_id: &Uuid,
+        collection_id: &Uuid
+    ) -> Result<BookmarkCollectionDTO, HearthError> {
+        bookmark_collections::Entity
+            ::find_by_id(*collection_id)
+            .filter(bookmark_collections::Column::OwnerId.eq(*owner_id))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_BOOKMARK_COLLECTION_ERROR"))?
+            .map(to_collection_dto)
+            .ok_or_else(|| HearthError::not_found(BOOKMARK_COLLECTION_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn list_collections(
+        &self,
+        owner_id: &Uuid
+    ) -> Result<Vec<BookmarkCollectionDTO>, HearthError> {
+        let models = bookmark_collections::Entity
+            ::find()
+            .filter(bookmark_collections::Column::OwnerId.eq(*owner_id))
+            .order_by_asc(bookmark_collections::Column::Name)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_BOOKMARK_COLLECTIONS_ERROR"))?;
+
+        Ok(models.into_iter().map(to_collection_dto).collect())
+    }
+
+    async fn save(
+        &self,
+        owner_id: &Uuid,
+        post_id: &Uuid,
+        collection_id: Option<Uuid>,
+        at: DateTime<Utc>
+    ) -> Result<BookmarkDTO, HearthError> {
+        let owner_id = *owner_id;
+        let post_id = *post_id;
+
+        self.connection
+            .transaction::<_, BookmarkDTO, HearthError>(|transaction| {
+                Box::pin(async move {
+                    let top: Option<i64> = bookmarks::Entity
+                        ::find()
+                        .select_only()
+                        .column(bookmarks::Column::Position)
+                        .filter(in_collection(&owner_id, collection_id))
+                        .order_by_asc(bookmarks::Column::Position)
+                        .into_tuple()
+                        .one(transaction).await
+                        .map_err(unexpected("GET_BOOKMARK_POSITION_ERROR"))?;
+
+                    bookmarks::Entity
+                        ::insert(bookmarks::ActiveModel {
+                            owner_id: Set(owner_id),
+                            post_id: Set(post_id),
+                            collection_id: Set(collection_id),
+                            position: Set(top.unwrap_or(0) - 1),
+                            created_at: Set(at.naive_utc()),
+                        })
+                        .on_conflict(
+                            OnConflict::columns([
+                                bookmarks::Column::OwnerId,
+                                bookmarks::Column::PostId,
+                            ])
+                                .update_columns([
+                                    bookmarks::Column::CollectionId,
+                                    bookmarks::Column::Position,
+                                ])
+                                .to_owned()
+                        )
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("SAVE_BOOKMARK_ERROR"))?;
+
+                    bookmarks::Entity
+                        ::find_by_id((owner_id, post_id))
+                        .one(transaction).await
+                        .map_err(unexpected("GET_BOOKMARK_ERROR"))?
+                        .map(to_dto)
+                        .ok_or_else(|| HearthError::not_found(BOOKMARK_NOT_FOUND_ERROR_CODE.into()))
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn remove(&self, owner_id: &Uuid, post_id: &Uuid) -> Result<(), HearthError> {
+        bookmarks::Entity
+            ::delete_by_id((*owner_id, *post_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("REMOVE_BOOKMARK_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn move_to(&self, owner_id: &Uuid, post_id: &Uuid, index: usize) -> Result<(), HearthError> {
+        let owner_id = *owner_id;
+        let post_id = *post_id;
+
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    let bookmark = bookmarks::Entity
+                        ::find_by_id((owner_id, post_id))
+                        .lock_exclusive()
+                        .one(transaction).await
+                        .map_err(unexpected("GET_BOOKMARK_ERROR"))?
+                        .ok_or_else(|| HearthError::not_found(BOOKMARK_NOT_FOUND_ERROR_CODE.into()))?;
+
+                    let mut ordered: Vec<Uuid> = bookmarks::Entity
+                        ::find()
+                        .select_only()
+                        .column(bookmarks::Column::PostId)
+                        .filter(in_collection(&owner_id, bookmark.collection_id))
+                        .filter(bookmarks::Column::PostId.ne(post_id))
+                        .order_by_asc(bookmarks::Column::Position)
+                        .order_by_asc(bookmarks::Column::PostId)
+                        .into_tuple()
+                        .all(transaction).await
+                        .map_err(unexpected("LIST_BOOKMARKS_ERROR"))?;
+                    ordered.insert(index.min(ordered.len()), post_id);
+
+                    // Collections are small, renumbering them keeps positions dense.
+                    for (position, id) in ordered.into_iter().enumerate() {
+                        bookmarks::Entity
+                            ::update_many()
+                            .col_expr(bookmarks::Column::Position, Expr::value(position as i64))
+                            .filter(bookmarks::Column::OwnerId.eq(owner_id))
+                            .filter(bookmarks::Column::PostId.eq(id))
+                            .exec(transaction).await
+                            .map_err(unexpected("MOVE_BOOKMARK_ERROR"))?;
+                    }
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn list(
+        &self,
+        owner_id: &Uuid,
+        collection_id: Option<Uuid>,
+        cursor: Option<PositionCursor>,
+        limit: u64
+    ) -> Result<Vec<BookmarkDTO>, HearthError> {
+        let mut query = bookmarks::Entity::find().filter(in_collection(owner_id, collection_id));
+
+        if let Some(cursor) = cursor {
+            query = query.filter(
+                Condition::any()
+                    .add(bookmarks::Column::Position.gt(cursor.position))
+                    .add(
+                        Condition::all()
+                            .add(bookmarks::Column::Position.eq(cursor.position))
+                            .add(bookmarks::Column::PostId.gt(cursor.id))
+                    )
+            );
+        }
+
+        let models = query
+            .order_by_asc(bookmarks::Column::Position)
+            .order_by_asc(bookmarks::Column::PostId)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_BOOKMARKS_ERROR"))?;
+
+        Ok(models.into_iter().map(to_dto).collect())
+    }
+}
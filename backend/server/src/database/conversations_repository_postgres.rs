@@ -0,0 +1,365 @@
+use std::{ collections::HashMap, sync::Arc };
+
+use async_trait::async_trait;
+use domain::{
+    dtos::{
+        conversation::{ ConversationDTO, MessageDTO, ReadPositionDTO },
+        pagination::TimelineCursor,
+    },
+    error_codes::{ CONVERSATION_NOT_FOUND_ERROR_CODE, MESSAGE_NOT_FOUND_ERROR_CODE },
+    repositories::conversations_repository::ConversationsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    ConnectionTrait,
+    DatabaseConnection,
+    EntityTrait,
+    Order,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::{ Expr, Func, Query, SimpleExpr },
+};
+use uuid::Uuid;
+
+use crate::database::{
+    entities::{ conversation_participants, conversations, message_media, messages },
+    transaction_error,
+    unexpected,
+};
+
+pub struct ConversationsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+/// Identifies the one-to-one conversation of a pair, whatever the order of the ids.
+fn direct_key(user_a: &Uuid, user_b: &Uuid) -> String {
+    let (first, second) = if user_a < user_b { (user_a, user_b) } else { (user_b, user_a) };
+    format!("{}:{}", first, second)
+}
+
+impl ConversationsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+
+    /// Attaches participants and their read positions to the given conversations.
+    async fn hydrate<C: ConnectionTrait>(
+        connection: &C,
+        models: Vec<conversations::Model>
+    ) -> Result<Vec<ConversationDTO>, HearthError> {
+        let ids: Vec<Uuid> = models
+            .iter()
+            .map(|m| m.id)
+            .collect();
+
+        let participants = conversation_participants::Entity
+            ::find()
+            .filter(conversation_participants::Column::ConversationId.is_in(ids))
+            .order_by_asc(conversation_participants::Column::Position)
+            .all(connection).await
+            .map_err(unexpected("GET_CONVERSATION_PARTICIPANTS_ERROR"))?;
+
+        let mut by_conversation: HashMap<Uuid, Vec<conversation_participants::Model>> = HashMap::new();
+        for participant in participants {
+            by_conversation.entry(participant.conversation_id).or_default().push(participant);
+        }
+
+        Ok(
+            models
+                .into_iter()
+                .map(|model| {
+                    let participants = by_conversation.remove(&model.id).unwrap_or_default();
+
+                    ConversationDTO {
+                        conversation_id: model.id,
+                        participant_ids: participants
+                            .iter()
+                            .map(|p| p.user_id)
+                            .collect(),
+                        read_positions: participants
+                            .iter()
+                            .filter_map(|p| {
+                                Some(ReadPositionDTO {
+                                    user_id: p.user_id,
+                                    last_read_message_id: p.last_read_message_id?,
+                                    last_read_at: p.last_read_at?.and_utc(),
+                                })
+                            })
+                            .collect(),
+                        created_at: model.created_at.and_utc(),
+                        last_message_at: model.last_message_at.map(|at| at.and_utc()),
+                    }
+                })
+                .collect()
+        )
+    }
+
+    /// Attaches media ids, in display order, to the given messages.
+    pub(crate) async fn hydrate_messages<C: ConnectionTrait>(
+        connection: &C,
+        models: Vec<messages::Model>
+    ) -> Result<Vec<MessageDTO>, HearthError> {
+        let ids: Vec<Uuid> = models
+            .iter()
+            .map(|m| m.id)
+            .collect();
+
+        let rows: Vec<(Uuid, Uuid)> = message_media::Entity
+            ::find()
+            .select_only()
+            .column(message_media::Column::MessageId)
+            .column(message_media::Column::MediaId)
+            .filter(message_media::Column::MessageId.is_in(ids))
+            .order_by_asc(message_media::Column::Position)
+            .into_tuple()
+            .all(connection).await
+            .map_err(unexpected("GET_MESSAGE_MEDIA_ERROR"))?;
+
+        let mut media_by_message: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (message_id, media_id) in rows {
+            media_by_message.entry(message_id).or_default().push(media_id);
+        }
+
+        Ok(
+            models
+                .into_iter()
+                .map(|model| MessageDTO {
+                    media_ids: media_by_message.remove(&model.id).unwrap_or_default(),
+                    message_id: model.id,
+                    conversation_id: model.conversation_id,
+                    sender_id: model.sender_id,
+                    content: model.content,
+                    created_at: model.created_at.and_utc(),
+                })
+                .collect()
+        )
+    }
+}
+
+#[async_trait]
+impl ConversationsRepository for ConversationsRepositoryPostgres {
+    async fn create(&self, conversation: ConversationDTO) -> Result<(), HearthError> {
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    let key = match conversation.participant_ids[..] {
+                        [user_a, user_b] => Some(direct_key(&user_a, &user_b)),
+                        _ => None,
+                    };
+
+                    conversations::Entity
+                        ::insert(conversations::ActiveModel {
+                            id: Set(conversation.conversation_id),
+                            direct_key: Set(key),
+                            created_at: Set(conversation.created_at.naive_utc()),
+                            last_message_at: Set(None),
+                        })
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_CONVERSATION_ERROR"))?;
+
+                    conversation_participants::Entity
+                        ::insert_many(
+                            conversation.participant_ids
+                                .iter()
+                                .enumerate()
+                                .map(|(position, user_id)| conversation_participants::ActiveModel {
+                                    conversation_id: Set(conversation.conversation_id),
+                                    user_id: Set(*user_id),
+                                    position: Set(position as i16),
+                                    last_read_message_id: Set(None),
+                                    last_read_at: Set(None),
+                                })
+                        )
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_CONVERSATION_PARTICIPANTS_ERROR"))?;
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn get(&self, conversation_id: &Uuid) -> Result<ConversationDTO, HearthError> {
+        let model = conversations::Entity
+            ::find_by_id(*conversation_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_CONVERSATION_ERROR"))?
+            .ok_or_else(|| HearthError::not_found(CONVERSATION_NOT_FOUND_ERROR_CODE.into()))?;
+
+        Self::hydrate(self.connection.as_ref(), vec![model]).await?
+            .pop()
+            .ok_or_else(|| HearthError::not_found(CONVERSATION_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn find_direct(
+        &self,
+        user_a: &Uuid,
+        user_b: &Uuid
+    ) -> Result<Option<ConversationDTO>, HearthError> {
+        let model = conversations::Entity
+            ::find()
+            .filter(conversations::Column::DirectKey.eq(direct_key(user_a, user_b)))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("FIND_DIRECT_CONVERSATION_ERROR"))?;
+
+        match model {
+            Some(model) => Ok(Self::hydrate(self.connection.as_ref(), vec![model]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_for_user(&self, user_id: &Uuid) -> Result<Vec<ConversationDTO>, HearthError> {
+        let joined = Query::select()
+            .column(conversation_participants::Column::ConversationId)
+            .from(conversation_participants::Entity)
+            .and_where(conversation_participants::Column::UserId.eq(*user_id))
+            .to_owned();
+
+        let last_activity = Func::coalesce([
+            Expr::col(conversations::Column::LastMessageAt),
+            Expr::col(conversations::Column::CreatedAt),
+        ]);
+
+        let models = conversations::Entity
+            ::find()
+            .filter(conversations::Column::Id.in_subquery(joined))
+            .order_by(SimpleExpr::from(last_activity), Order::Desc)
+            .order_by_desc(conversations::Column::Id)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_CONVERSATIONS_ERROR"))?;
+
+        Self::hydrate(self.connection.as_ref(), models).await
+    }
+
+    async fn add_message(&self, message: MessageDTO) -> Result<(), HearthError> {
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    messages::Entity
+                        ::insert(messages::ActiveModel {
+                            id: Set(message.message_id),
+                            conversation_id: Set(message.conversation_id),
+                            sender_id: Set(message.sender_id),
+                            content: Set(message.content.clone()),
+                            created_at: Set(message.created_at.naive_utc()),
+                        })
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_MESSAGE_ERROR"))?;
+
+                    for (position, media_id) in message.media_ids.iter().enumerate() {
+                        message_media::Entity
+                            ::insert(message_media::ActiveModel {
+                                message_id: Set(message.message_id),
+                                media_id: Set(*media_id),
+                                position: Set(position as i16),
+                            })
+                            .exec_without_returning(transaction).await
+                            .map_err(unexpected("CREATE_MESSAGE_MEDIA_ERROR"))?;
+                    }
+
+                    conversations::Entity
+                        ::update_many()
+                        .col_expr(
+                            conversations::Column::LastMessageAt,
+                            Expr::value(message.created_at.naive_utc())
+                        )
+                        .filter(conversations::Column::Id.eq(message.conversation_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("UPDATE_CONVERSATION_ERROR"))?;
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn get_message(
+        &self,
+        conversation_id: &Uuid,
+        message_id: &Uuid
+    ) -> Result<MessageDTO, HearthError> {
+        let model = messages::Entity
+            ::find_by_id(*message_id)
+            .filter(messages::Column::ConversationId.eq(*conversation_id))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_MESSAGE_ERROR"))?
+            .ok_or_else(|| HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into()))?;
+
+        Self::hydrate_messages(self.connection.as_ref(), vec![model]).await?
+            .pop()
+            .ok_or_else(|| HearthError::not_found(MESSAGE_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn find_message(&self, message_id: &Uuid) -> Result<Option<MessageDTO>, HearthError> {
+        let model = messages::Entity
+            ::find_by_id(*message_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("FIND_MESSAGE_ERROR"))?;
+
+        match model {
+            Some(model) => Ok(Self::hydrate_messages(self.connection.as_ref(), vec![model]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn messages(
+        &self,
+        conversation_id: &Uuid,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<MessageDTO>, HearthError> {
+        let mut query = messages::Entity
+            ::find()
+            .filter(messages::Column::ConversationId.eq(*conversation_id));
+
+        if let Some(cursor) = cursor {
+            let created_at = cursor.created_at.naive_utc();
+            query = query.filter(
+                Condition::any()
+                    .add(messages::Column::CreatedAt.lt(created_at))
+                    .add(
+                        Condition::all()
+                            .add(messages::Column::CreatedAt.eq(created_at))
+                            .add(messages::Column::Id.lt(cursor.id))
+                    )
+            );
+        }
+
+        let models = query
+            .order_by_desc(messages::Column::CreatedAt)
+            .order_by_desc(messages::Column::Id)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_MESSAGES_ERROR"))?;
+
+        Self::hydrate_messages(self.connection.as_ref(), models).await
+    }
+
+    async fn set_read_position(
+        &self,
+        conversation_id: &Uuid,
+        position: ReadPositionDTO
+    ) -> Result<(), HearthError> {
+        conversation_participants::Entity
+            ::update_many()
+            .col_expr(
+                conversation_participants::Column::LastReadMessageId,
+                Expr::value(position.last_read_message_id)
+            )
+            .col_expr(
+                conversation_participants::Column::LastReadAt,
+                Expr::value(position.last_read_at.naive_utc())
+            )
+            .filter(conversation_participants::Column::ConversationId.eq(*conversation_id))
+            .filter(conversation_participants::Column::UserId.eq(position.user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("SET_READ_POSITION_ERROR"))?;
+
+        Ok(())
+    }
+}
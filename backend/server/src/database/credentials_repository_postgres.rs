@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::auth::CredentialsDTO, repositories::credentials_repository::CredentialsRepository,
+};
+use errors::HearthError;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use crate::database::{entities::credentials, unexpected};
+
+pub struct CredentialsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl CredentialsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl CredentialsRepository for CredentialsRepositoryPostgres {
+    async fn get(&self, user_id: &Uuid) -> Result<Option<CredentialsDTO>, HearthError> {
+        let model = credentials::Entity::find()
+            .filter(credentials::Column::UserId.eq(user_id.to_string()))
+            .one(self.connection.as_ref())
+            .await
+            .map_err(unexpected("GET_CREDENTIALS_ERROR"))?;
+
+        Ok(model.map(|m| CredentialsDTO {
+            user_id: *user_id,
+            password_hash: m.password_hash,
+        }))
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::data_export::{ DataExportDTO, DataExportStatus },
+    error_codes::DATA_EXPORT_NOT_FOUND_ERROR_CODE,
+    repositories::data_exports_repository::DataExportsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::data_exports, unexpected };
+
+pub struct DataExportsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl DataExportsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+
+    fn to_dto(model: data_exports::Model) -> DataExportDTO {
+        DataExportDTO {
+            export_id: model.id,
+            user_id: model.user_id,
+            status: DataExportStatus::parse(&model.status).unwrap_or_default(),
+            token_hash: model.token_hash,
+            expires_at: model.expires_at.map(|at| at.and_utc()),
+            created_at: model.created_at.and_utc(),
+        }
+    }
+}
+
+#[async_trait]
+impl DataExportsRepository for DataExportsRepositoryPostgres {
+    async fn create(&self, export: DataExportDTO) -> Result<(), HearthError> {
+        data_exports::Entity
+            ::insert(data_exports::ActiveModel {
+                id: Set(export.export_id),
+                user_id: Set(export.user_id),
+                status: Set(export.status.as_str().into()),
+                token_hash: Set(export.token_hash),
+                expires_at: Set(export.expires_at.map(|at| at.naive_utc())),
+                created_at: Set(export.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_DATA_EXPORT_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, export_id: &Uuid) -> Result<DataExportDTO, HearthError> {
+        data_exports::Entity
+            ::find_by_id(*export_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_DATA_EXPORT_ERROR"))?
+            .map(Self::to_dto)
+            .ok_or_else(|| HearthError::not_found(DATA_EXPORT_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn find_active(
+        &self,
+        user_id: &Uuid,
+        at: DateTime<Utc>
+    ) -> Result<Option<DataExportDTO>, HearthError> {
+        let model = data_exports::Entity
+            ::find()
+            .filter(data_exports::Column::UserId.eq(*user_id))
+            .filter(
+                Condition::any()
+                    .add(data_exports::Column::ExpiresAt.is_null())
+                    .add(data_exports::Column::ExpiresAt.gt(at.naive_utc()))
+            )
+            .order_by_desc(data_exports::Column::CreatedAt)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("FIND_ACTIVE_DATA_EXPORT_ERROR"))?;
+
+        Ok(model.map(Self::to_dto))
+    }
+
+    async fn list_pending(&self, limit: u64) -> Result<Vec<DataExportDTO>, HearthError> {
+        let models = data_exports::Entity
+            ::find()
+            .filter(data_exports::Column::Status.eq(DataExportStatus::Pending.as_str()))
+            .order_by_asc(data_exports::Column::CreatedAt)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_PENDING_DATA_EXPORTS_ERROR"))?;
+
+        Ok(models.into_iter().map(Self::to_dto).collect())
+    }
+
+    async fn mark_ready(
+        &self,
+        export_id: &Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>
+    ) -> Result<(), HearthError> {
+        let result = data_exports::Entity
+            ::update_many()
+            .col_expr(data_exports::Column::Status, Expr::value(DataExportStatus::Ready.as_str()))
+            .col_expr(data_exports::Column::TokenHash, Expr::value(token_hash))
+            .col_expr(data_exports::Column::ExpiresAt, Expr::value(expires_at.naive_utc()))
+            .filter(data_exports::Column::Id.eq(*export_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("MARK_DATA_EXPORT_READY_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(DATA_EXPORT_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn list_expired(
+        &self,
+        at: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<DataExportDTO>, HearthError> {
+        let models = data_exports::Entity
+            ::find()
+            .filter(data_exports::Column::ExpiresAt.lte(at.naive_utc()))
+            .order_by_asc(data_exports::Column::ExpiresAt)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_EXPIRED_DATA_EXPORTS_ERROR"))?;
+
+        Ok(models.into_iter().map(Self::to_dto).collect())
+    }
+
+    async fn delete(&self, export_id: &Uuid) -> Result<(), HearthError> {
+        data_exports::Entity
+            ::delete_by_id(*export_id)
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("DELETE_DATA_EXPORT_ERROR"))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use domain::{
+    dtos::device_keys::{ DeviceKeysDTO, OneTimePrekeyDTO, SignedPrekeyDTO },
+    repositories::device_keys_repository::DeviceKeysRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    PaginatorTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::{ LockBehavior, LockType, OnConflict },
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::{ device_keys, one_time_prekeys }, transaction_error, unexpected };
+
+pub struct DeviceKeysRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl DeviceKeysRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+
+    fn to_dto(model: device_keys::Model) -> DeviceKeysDTO {
+        DeviceKeysDTO {
+            user_id: model.user_id,
+            device_id: model.device_id,
+            identity_key: model.identity_key,
+            signing_key: model.signing_key,
+            signed_prekey: SignedPrekeyDTO {
+                key_id: model.signed_prekey_id,
+                public_key: model.signed_prekey,
+                signature: model.signed_prekey_signature,
+            },
+            updated_at: model.updated_at.and_utc(),
+        }
+    }
+}
+
+#[async_trait]
+impl DeviceKeysRepository for DeviceKeysRepositoryPostgres {
+    async fn upsert(&self, keys: DeviceKeysDTO) -> Result<(), HearthError> {
+        device_keys::Entity
+            ::insert(device_keys::ActiveModel {
+                user_id: Set(keys.user_id),
+                device_id: Set(keys.device_id),
+                identity_key: Set(keys.identity_key),
+                signing_key: Set(keys.signing_key),
+                signed_prekey_id: Set(keys.signed_prekey.key_id),
+                signed_prekey: Set(keys.signed_prekey.public_key),
+                signed_prekey_signature: Set(keys.signed_prekey.signature),
+                updated_at: Set(keys.updated_at.naive_utc()),
+            })
+            .on_conflict(
+                OnConflict::columns([device_keys::Column::UserId, device_keys::Column::DeviceId])
+                    .update_columns([
+                        device_keys::Column::IdentityKey,
+                        device_keys::Column::SigningKey,
+                        device_keys::Column::SignedPrekeyId,
+                        device_keys::Column::SignedPrekey,
+                        device_keys::Column::SignedPrekeySignature,
+                        device_keys::Column::UpdatedAt,
+                    ])
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("UPSERT_DEVICE_KEYS_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, user_id: &Uuid, device_id: &Uuid) -> Result<Option<DeviceKeysDTO>, HearthError> {
+        let model = device_keys::Entity
+            ::find_by_id((*user_id, *device_id))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_DEVICE_KEYS_ERROR"))?;
+
+        Ok(model.map(Self::to_dto))
+    }
+
+    async fn list_for_user(&self, user_id: &Uuid) -> Result<Vec<DeviceKeysDTO>, HearthError> {
+        let models = device_keys::Entity
+            ::find()
+            .filter(device_keys::Column::UserId.eq(*user_id))
+            .order_by_asc(device_keys::Column::DeviceId)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_DEVICE_KEYS_ERROR"))?;
+
+        Ok(models.into_iter().map(Self::to_dto).collect())
+    }
+
+    async fn add_one_time_prekeys(
+        &self,
+        user_id: &Uuid,
+        device_id: &Uuid,
+        prekeys: Vec<OneTimePrekeyDTO>
+    ) -> Result<(), HearthError> {
+        let now = Utc::now().naive_utc();
+
+        one_time_prekeys::Entity
+            ::insert_many(
+                prekeys.into_iter().map(|prekey| one_time_prekeys::ActiveModel {
+                    user_id: Set(*user_id),
+                    device_id: Set(*device_id),
+                    key_id: Set(prekey.key_id),
+                    public_key: Set(prekey.public_key),
+                    created_at: Set(now),
+                })
+            )
+            .on_conflict(
+                OnConflict::columns([
+                    one_time_prekeys::Column::UserId,
+                    one_time_prekeys::Column::DeviceId,
+                    one_time_prekeys::Column::KeyId,
+                ])
+                    .do_nothing()
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("ADD_ONE_TIME_PREKEYS_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn count_one_time_prekeys(&self, user_id: &Uuid, device_id: &Uuid) -> Result<u64, HearthError> {
+        one_time_prekeys::Entity
+            ::find()
+            .filter(one_time_prekeys::Column::UserId.eq(*user_id))
+            .filter(one_time_prekeys::Column::DeviceId.eq(*device_id))
+            .count(self.connection.as_ref()).await
+            .map_err(unexpected("COUNT_ONE_TIME_PREKEYS_ERROR"))
+    }
+
+    async fn claim_one_time_prekey(
+        &self,
+        user_id: &Uuid,
+        device_id: &Uuid
+    ) -> Result<Option<OneTimePrekeyDTO>, HearthError> {
+        let user_id = *user_id;
+        let device_id = *device_id;
+
+        // Concurrent claims skip the row locked by the other one instead of
+        // handing out the same prekey twice.
+        self.connection
+            .transaction::<_, Option<OneTimePrekeyDTO>, HearthError>(|transaction| {
+                Box::pin(async move {
+                    let model = one_time_prekeys::Entity
+                        ::find()
+                        .filter(one_time_prekeys::Column::UserId.eq(user_id))
+                        .filter(one_time_prekeys::Column::DeviceId.eq(device_id))
+                        .order_by_asc(one_time_prekeys::Column::CreatedAt)
+                        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+                        .one(transaction).await
+                        .map_err(unexpected("CLAIM_ONE_TIME_PREKEY_ERROR"))?;
+
+                    let Some(model) = model else {
+                        return Ok(None);
+                    };
+
+                    one_time_prekeys::Entity
+                        ::delete_by_id((model.user_id, model.device_id, model.key_id.clone()))
+                        .exec(transaction).await
+                        .map_err(unexpected("DELETE_ONE_TIME_PREKEY_ERROR"))?;
+
+                    Ok(Some(OneTimePrekeyDTO { key_id: model.key_id, public_key: model.public_key }))
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+}
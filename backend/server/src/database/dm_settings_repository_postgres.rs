@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::conversation::DmPermission,
+    repositories::dm_settings_repository::DmSettingsRepository,
+};
+use errors::HearthError;
+use sea_orm::{ ActiveValue::Set, DatabaseConnection, EntityTrait, sea_query::OnConflict };
+use uuid::Uuid;
+
+use crate::database::{ entities::dm_settings, unexpected };
+
+pub struct DmSettingsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl DmSettingsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl DmSettingsRepository for DmSettingsRepositoryPostgres {
+    async fn get(&self, user_id: &Uuid) -> Result<DmPermission, HearthError> {
+        let model = dm_settings::Entity
+            ::find_by_id(*user_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_DM_SETTINGS_ERROR"))?;
+
+        Ok(
+            model
+                .and_then(|model| DmPermission::parse(&model.dm_permission))
+                .unwrap_or_default()
+        )
+    }
+
+    async fn set(&self, user_id: &Uuid, permission: DmPermission) -> Result<(), HearthError> {
+        dm_settings::Entity
+            ::insert(dm_settings::ActiveModel {
+                user_id: Set(*user_id),
+                dm_permission: Set(permission.as_str().into()),
+            })
+            .on_conflict(
+                OnConflict::column(dm_settings::Column::UserId)
+                    .update_column(dm_settings::Column::DmPermission)
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("SET_DM_SETTINGS_ERROR"))?;
+
+        Ok(())
+    }
+}
@@ -2,16 +2,91 @@ use async_trait::async_trait;
 use domain::repositories::email_sender_repository::EmailSenderRepository;
 use email_verification_code::EmailVerificationCode;
 use errors::HearthError;
+use reqwest::{Client, header};
+use serde::Serialize;
 
-pub struct EmailSenderGateway {}
+use crate::database::unexpected;
+
+/// HTTP relay the emails are posted to as JSON, the shape most transactional
+/// mail services accept.
+#[derive(Debug, Clone)]
+pub struct MailConfig {
+    pub api_url: String,
+    /// Sent as a bearer token.
+    pub api_key: String,
+    pub from: String,
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    from: &'a str,
+    to: &'a str,
+    subject: &'a str,
+    text: String,
+}
+
+pub struct EmailSenderGateway {
+    config: MailConfig,
+    client: Client,
+}
+
+impl EmailSenderGateway {
+    pub fn new(config: MailConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+        }
+    }
+
+    async fn send(&self, to: &str, subject: &str, text: String) -> Result<(), HearthError> {
+        let config = &self.config;
+
+        let body = serde_json::to_vec(&Message {
+            from: &config.from,
+            to,
+            subject,
+            text,
+        })
+        .map_err(unexpected("SEND_EMAIL_SERIALIZE"))?;
+
+        self.client
+            .post(&config.api_url)
+            .bearer_auth(&config.api_key)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(unexpected("SEND_EMAIL"))?;
+
+        Ok(())
+    }
+}
 
 #[async_trait]
 impl EmailSenderRepository for EmailSenderGateway {
     async fn send_verify_email(
         &self,
-        email: &String,
+        email: &str,
         code: &EmailVerificationCode,
     ) -> Result<(), HearthError> {
-        todo!();
+        let text = format!(
+            "Enter this code to verify your email address:\n\n{}\n\nIt expires in an hour.\n",
+            code.code
+        );
+        self.send(email, "Verify your email address", text).await
+    }
+
+    async fn send_data_export_link(&self, email: &str, link: &str) -> Result<(), HearthError> {
+        let text = format!(
+            "Your data export is ready, download it before the link expires:\n\n{}\n",
+            link
+        );
+        self.send(email, "Your data export is ready", text).await
+    }
+
+    async fn send_welcome_email(&self, email: &str, username: &str) -> Result<(), HearthError> {
+        let text = format!("Welcome to Hearth, @{}!\n", username);
+        self.send(email, "Welcome to Hearth", text).await
     }
 }
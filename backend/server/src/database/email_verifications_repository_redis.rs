@@ -56,11 +56,26 @@ impl EmailVerificationRepository for EmailVerificationsRepositoryRedis {
                 HearthError::unexpected("EVR_CODE_MATCHES_ASYNC_CON".into(), Some(e.to_string()))
             })?;
 
-        let code = con.get::<&String, String>(email).await.map_err(|e| {
+        // Nothing stored once the code expired.
+        let code = con.get::<&String, Option<String>>(email).await.map_err(|e| {
             RedisError::code(&e);
             HearthError::unexpected("EVR_CODE_MATCHES_ASYNG_GET".into(), Some(e.to_string()))
         })?;
 
-        Ok(evc.code == code)
+        Ok(code.is_some_and(|code| evc.code == code))
+    }
+
+    async fn delete(&self, email: &String) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| {
+                HearthError::unexpected("EVR_DELETE_ASYNC_CON".into(), Some(e.to_string()))
+            })?;
+
+        con.del::<&String, ()>(email)
+            .await
+            .map_err(|e| HearthError::unexpected("EVR_DELETE".into(), Some(e.to_string())))
     }
 }
@@ -10,6 +10,9 @@ pub struct Model {
     #[sea_orm(unique)]
     pub user_id: String,
     pub password_hash: String,
+    pub totp_secret: Option<Vec<u8>>,
+    pub totp_enabled_at: Option<DateTime>,
+    pub totp_last_step: Option<i64>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -2,6 +2,50 @@
 
 pub mod prelude;
 
+pub mod blocks;
+pub mod bookmark_collections;
+pub mod bookmarks;
+pub mod conversation_participants;
+pub mod conversations;
 pub mod credentials;
+pub mod data_exports;
+pub mod device_keys;
+pub mod dm_settings;
 pub mod email_verified;
+pub mod follows;
+pub mod hashtags;
+pub mod held_usernames;
+pub mod imported_posts;
+pub mod imports;
+pub mod jobs;
+pub mod keyword_filters;
+pub mod link_previews;
+pub mod linked_identities;
+pub mod list_members;
+pub mod list_subscriptions;
+pub mod lists;
+pub mod media;
+pub mod media_variants;
+pub mod message_envelopes;
+pub mod message_media;
+pub mod messages;
+pub mod moderation_log;
+pub mod notifications;
+pub mod oauth_access_tokens;
+pub mod oauth_apps;
+pub mod one_time_prekeys;
+pub mod outbox_events;
+pub mod passkeys;
+pub mod personal_access_tokens;
+pub mod poll_options;
+pub mod poll_voters;
+pub mod poll_votes;
+pub mod polls;
+pub mod post_hashtags;
+pub mod post_link_previews;
+pub mod post_media;
+pub mod post_mentions;
+pub mod posts;
+pub mod recovery_codes;
+pub mod reports;
 pub mod users;
@@ -1,5 +1,49 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0
 
+pub use super::blocks::Entity as Blocks;
+pub use super::bookmark_collections::Entity as BookmarkCollections;
+pub use super::bookmarks::Entity as Bookmarks;
+pub use super::conversation_participants::Entity as ConversationParticipants;
+pub use super::conversations::Entity as Conversations;
 pub use super::credentials::Entity as Credentials;
+pub use super::data_exports::Entity as DataExports;
+pub use super::device_keys::Entity as DeviceKeys;
+pub use super::dm_settings::Entity as DmSettings;
 pub use super::email_verified::Entity as EmailVerified;
+pub use super::follows::Entity as Follows;
+pub use super::hashtags::Entity as Hashtags;
+pub use super::held_usernames::Entity as HeldUsernames;
+pub use super::imported_posts::Entity as ImportedPosts;
+pub use super::imports::Entity as Imports;
+pub use super::jobs::Entity as Jobs;
+pub use super::keyword_filters::Entity as KeywordFilters;
+pub use super::link_previews::Entity as LinkPreviews;
+pub use super::linked_identities::Entity as LinkedIdentities;
+pub use super::list_members::Entity as ListMembers;
+pub use super::list_subscriptions::Entity as ListSubscriptions;
+pub use super::lists::Entity as Lists;
+pub use super::media::Entity as Media;
+pub use super::media_variants::Entity as MediaVariants;
+pub use super::message_envelopes::Entity as MessageEnvelopes;
+pub use super::message_media::Entity as MessageMedia;
+pub use super::messages::Entity as Messages;
+pub use super::moderation_log::Entity as ModerationLog;
+pub use super::notifications::Entity as Notifications;
+pub use super::oauth_access_tokens::Entity as OauthAccessTokens;
+pub use super::oauth_apps::Entity as OauthApps;
+pub use super::one_time_prekeys::Entity as OneTimePrekeys;
+pub use super::outbox_events::Entity as OutboxEvents;
+pub use super::passkeys::Entity as Passkeys;
+pub use super::personal_access_tokens::Entity as PersonalAccessTokens;
+pub use super::poll_options::Entity as PollOptions;
+pub use super::poll_voters::Entity as PollVoters;
+pub use super::poll_votes::Entity as PollVotes;
+pub use super::polls::Entity as Polls;
+pub use super::post_hashtags::Entity as PostHashtags;
+pub use super::post_link_previews::Entity as PostLinkPreviews;
+pub use super::post_media::Entity as PostMedia;
+pub use super::post_mentions::Entity as PostMentions;
+pub use super::posts::Entity as Posts;
+pub use super::recovery_codes::Entity as RecoveryCodes;
+pub use super::reports::Entity as Reports;
 pub use super::users::Entity as Users;
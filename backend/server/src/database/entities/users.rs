@@ -13,6 +13,11 @@ pub struct Model {
     #[sea_orm(unique)]
     pub email: String,
     pub birthday: Date,
+    pub avatar_media_id: Option<Uuid>,
+    pub role: String,
+    pub status: String,
+    pub suspended_until: Option<DateTime>,
+    pub deactivated_at: Option<DateTime>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
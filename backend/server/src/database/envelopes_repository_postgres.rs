@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{ dtos::conversation::EnvelopeDTO, repositories::envelopes_repository::EnvelopesRepository };
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::{ conversations, message_envelopes }, transaction_error, unexpected };
+
+pub struct EnvelopesRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl EnvelopesRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl EnvelopesRepository for EnvelopesRepositoryPostgres {
+    async fn add(&self, envelopes: Vec<EnvelopeDTO>) -> Result<(), HearthError> {
+        let Some(first) = envelopes.first().cloned() else {
+            return Ok(());
+        };
+
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    message_envelopes::Entity
+                        ::insert_many(
+                            envelopes.into_iter().map(|envelope| message_envelopes::ActiveModel {
+                                message_id: Set(envelope.message_id),
+                                recipient_device_id: Set(envelope.recipient_device_id),
+                                recipient_id: Set(envelope.recipient_id),
+                                conversation_id: Set(envelope.conversation_id),
+                                sender_id: Set(envelope.sender_id),
+                                sender_device_id: Set(envelope.sender_device_id),
+                                message_type: Set(envelope.message_type),
+                                ciphertext: Set(envelope.ciphertext),
+                                created_at: Set(envelope.created_at.naive_utc()),
+                            })
+                        )
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_MESSAGE_ENVELOPES_ERROR"))?;
+
+                    conversations::Entity
+                        ::update_many()
+                        .col_expr(
+                            conversations::Column::LastMessageAt,
+                            Expr::value(first.created_at.naive_utc())
+                        )
+                        .filter(conversations::Column::Id.eq(first.conversation_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("UPDATE_CONVERSATION_ERROR"))?;
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn pending(
+        &self,
+        user_id: &Uuid,
+        device_id: &Uuid,
+        limit: u64
+    ) -> Result<Vec<EnvelopeDTO>, HearthError> {
+        let models = message_envelopes::Entity
+            ::find()
+            .filter(message_envelopes::Column::RecipientId.eq(*user_id))
+            .filter(message_envelopes::Column::RecipientDeviceId.eq(*device_id))
+            .order_by_asc(message_envelopes::Column::CreatedAt)
+            .order_by_asc(message_envelopes::Column::MessageId)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_MESSAGE_ENVELOPES_ERROR"))?;
+
+        Ok(
+            models
+                .into_iter()
+                .map(|model| EnvelopeDTO {
+                    message_id: model.message_id,
+                    conversation_id: model.conversation_id,
+                    sender_id: model.sender_id,
+                    sender_device_id: model.sender_device_id,
+                    recipient_id: model.recipient_id,
+                    recipient_device_id: model.recipient_device_id,
+                    message_type: model.message_type,
+                    ciphertext: model.ciphertext,
+                    created_at: model.created_at.and_utc(),
+                })
+                .collect()
+        )
+    }
+
+    async fn delete(&self, user_id: &Uuid, device_id: &Uuid, message_id: &Uuid) -> Result<bool, HearthError> {
+        let result = message_envelopes::Entity
+            ::delete_many()
+            .filter(message_envelopes::Column::MessageId.eq(*message_id))
+            .filter(message_envelopes::Column::RecipientDeviceId.eq(*device_id))
+            .filter(message_envelopes::Column::RecipientId.eq(*user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("DELETE_MESSAGE_ENVELOPE_ERROR"))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
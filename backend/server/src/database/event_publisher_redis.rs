@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{dtos::event::EventDTO, repositories::event_publisher::EventPublisher};
+use errors::HearthError;
+use redis::{AsyncCommands, Client};
+
+use crate::database::unexpected;
+
+/// Publishes every event as JSON on `events:<kind>`, so other processes can
+/// subscribe to a single kind or pattern-match all of them.
+pub struct EventPublisherRedis {
+    client: Arc<Client>,
+}
+
+impl EventPublisherRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    fn channel(event: &EventDTO) -> String {
+        format!("events:{}", event.event.kind())
+    }
+}
+
+#[async_trait]
+impl EventPublisher for EventPublisherRedis {
+    async fn publish(&self, event: &EventDTO) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("EP_PUBLISH_ASYNC_CON"))?;
+
+        let payload = serde_json::to_string(event).map_err(unexpected("EP_PUBLISH_SERIALIZE"))?;
+
+        con.publish::<_, _, ()>(Self::channel(event), payload)
+            .await
+            .map_err(unexpected("EP_PUBLISH"))
+    }
+}
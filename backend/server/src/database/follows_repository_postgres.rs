@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::repositories::follows_repository::FollowsRepository;
+use errors::HearthError;
+use sea_orm::{ DatabaseConnection, EntityTrait };
+use uuid::Uuid;
+
+use crate::database::{ entities::follows, unexpected };
+
+pub struct FollowsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl FollowsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl FollowsRepository for FollowsRepositoryPostgres {
+    async fn is_following(&self, follower_id: &Uuid, followee_id: &Uuid) -> Result<bool, HearthError> {
+        let follow = follows::Entity
+            ::find_by_id((*follower_id, *followee_id))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("IS_FOLLOWING_ERROR"))?;
+
+        Ok(follow.is_some())
+    }
+}
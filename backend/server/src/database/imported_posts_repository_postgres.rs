@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use domain::{
+    dtos::import::{ ImportSource, ImportedPostDTO },
+    repositories::imported_posts_repository::ImportedPostsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QuerySelect,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::imported_posts, unexpected };
+
+pub struct ImportedPostsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl ImportedPostsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl ImportedPostsRepository for ImportedPostsRepositoryPostgres {
+    async fn create(&self, imported: ImportedPostDTO) -> Result<(), HearthError> {
+        imported_posts::Entity
+            ::insert(imported_posts::ActiveModel {
+                user_id: Set(imported.user_id),
+                source: Set(imported.source.as_str().into()),
+                external_id: Set(imported.external_id),
+                post_id: Set(imported.post_id),
+                created_at: Set(Utc::now().naive_utc()),
+            })
+            .on_conflict(
+                OnConflict::columns([
+                    imported_posts::Column::UserId,
+                    imported_posts::Column::Source,
+                    imported_posts::Column::ExternalId,
+                ])
+                    .do_nothing()
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_IMPORTED_POST_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn find_imported(
+        &self,
+        user_id: &Uuid,
+        source: ImportSource,
+        external_ids: &[String]
+    ) -> Result<Vec<String>, HearthError> {
+        imported_posts::Entity
+            ::find()
+            .select_only()
+            .column(imported_posts::Column::ExternalId)
+            .filter(imported_posts::Column::UserId.eq(*user_id))
+            .filter(imported_posts::Column::Source.eq(source.as_str()))
+            .filter(imported_posts::Column::ExternalId.is_in(external_ids.to_vec()))
+            .into_tuple()
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("FIND_IMPORTED_POSTS_ERROR"))
+    }
+}
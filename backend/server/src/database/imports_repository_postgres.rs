@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::import::{ ImportDTO, ImportReportDTO, ImportSource, ImportStatus },
+    error_codes::IMPORT_NOT_FOUND_ERROR_CODE,
+    repositories::imports_repository::ImportsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::imports, unexpected };
+
+pub struct ImportsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl ImportsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+
+    fn to_dto(model: imports::Model) -> ImportDTO {
+        ImportDTO {
+            import_id: model.id,
+            user_id: model.user_id,
+            source: ImportSource::parse(&model.source).unwrap_or_default(),
+            dry_run: model.dry_run,
+            status: ImportStatus::parse(&model.status).unwrap_or_default(),
+            report: model.report.and_then(|report| serde_json::from_str(&report).ok()),
+            error: model.error,
+            created_at: model.created_at.and_utc(),
+        }
+    }
+}
+
+#[async_trait]
+impl ImportsRepository for ImportsRepositoryPostgres {
+    async fn create(&self, import: ImportDTO) -> Result<(), HearthError> {
+        imports::Entity
+            ::insert(imports::ActiveModel {
+                id: Set(import.import_id),
+                user_id: Set(import.user_id),
+                source: Set(import.source.as_str().into()),
+                dry_run: Set(import.dry_run),
+                status: Set(import.status.as_str().into()),
+                report: Set(None),
+                error: Set(import.error),
+                created_at: Set(import.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_IMPORT_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, import_id: &Uuid) -> Result<ImportDTO, HearthError> {
+        imports::Entity
+            ::find_by_id(*import_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_IMPORT_ERROR"))?
+            .map(Self::to_dto)
+            .ok_or_else(|| HearthError::not_found(IMPORT_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn mark_done(&self, import_id: &Uuid, report: &ImportReportDTO) -> Result<(), HearthError> {
+        let report = serde_json
+            ::to_string(report)
+            .map_err(unexpected("IMPORT_REPORT_SERIALIZE_ERROR"))?;
+
+        let result = imports::Entity
+            ::update_many()
+            .col_expr(imports::Column::Status, Expr::value(ImportStatus::Done.as_str()))
+            .col_expr(imports::Column::Report, Expr::value(report))
+            .filter(imports::Column::Id.eq(*import_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("MARK_IMPORT_DONE_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(IMPORT_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, import_id: &Uuid, error: &str) -> Result<(), HearthError> {
+        let result = imports::Entity
+            ::update_many()
+            .col_expr(imports::Column::Status, Expr::value(ImportStatus::Failed.as_str()))
+            .col_expr(imports::Column::Error, Expr::value(error))
+            .filter(imports::Column::Id.eq(*import_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("MARK_IMPORT_FAILED_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(IMPORT_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{ dtos::job::{ JobDTO, JobPayload }, repositories::job_queue::JobQueue };
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::{ Expr, LockBehavior, LockType, OnConflict },
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::jobs, transaction_error, unexpected };
+
+pub struct JobQueuePostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl JobQueuePostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+
+    fn to_dto(model: jobs::Model, payload: JobPayload) -> JobDTO {
+        JobDTO {
+            job_id: model.id,
+            payload,
+            attempts: model.attempts as u32,
+            run_at: model.run_at.and_utc(),
+            last_error: model.last_error,
+            created_at: model.created_at.and_utc(),
+        }
+    }
+}
+
+#[async_trait]
+impl JobQueue for JobQueuePostgres {
+    async fn enqueue(&self, job: JobDTO) -> Result<(), HearthError> {
+        let payload = serde_json
+            ::to_string(&job.payload)
+            .map_err(unexpected("ENQUEUE_JOB_SERIALIZE_ERROR"))?;
+
+        jobs::Entity
+            ::insert(jobs::ActiveModel {
+                id: Set(job.job_id),
+                kind: Set(job.payload.kind().into()),
+                payload: Set(payload),
+                attempts: Set(job.attempts as i32),
+                run_at: Set(job.run_at.naive_utc()),
+                last_error: Set(job.last_error),
+                dead_at: Set(None),
+                created_at: Set(job.created_at.naive_utc()),
+            })
+            .on_conflict(OnConflict::column(jobs::Column::Id).do_nothing().to_owned())
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("ENQUEUE_JOB_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn claim(
+        &self,
+        at: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<JobDTO>, HearthError> {
+        // Concurrent workers skip the rows locked by the others, the lease then
+        // keeps them hidden once the transaction is over.
+        self.connection
+            .transaction::<_, Vec<JobDTO>, HearthError>(|transaction| {
+                Box::pin(async move {
+                    let models = jobs::Entity
+                        ::find()
+                        .filter(jobs::Column::DeadAt.is_null())
+                        .filter(jobs::Column::RunAt.lte(at.naive_utc()))
+                        .order_by_asc(jobs::Column::RunAt)
+                        .limit(limit)
+                        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+                        .all(transaction).await
+                        .map_err(unexpected("CLAIM_JOBS_ERROR"))?;
+
+                    let mut claimed = vec![];
+                    for model in models {
+                        let payload = serde_json::from_str::<JobPayload>(&model.payload);
+                        let Ok(payload) = payload else {
+                            // Written by a release that knew of a job this one does not.
+                            jobs::Entity
+                                ::update_many()
+                                .col_expr(jobs::Column::DeadAt, Expr::value(at.naive_utc()))
+                                .col_expr(
+                                    jobs::Column::LastError,
+                                    Expr::value(format!("Unknown {} payload", model.kind))
+                                )
+                                .filter(jobs::Column::Id.eq(model.id))
+                                .exec(transaction).await
+                                .map_err(unexpected("DEAD_LETTER_JOB_ERROR"))?;
+                            continue;
+                        };
+                        claimed.push(Self::to_dto(model, payload));
+                    }
+
+                    if !claimed.is_empty() {
+                        jobs::Entity
+                            ::update_many()
+                            .col_expr(jobs::Column::RunAt, Expr::value(lease_until.naive_utc()))
+                            .filter(jobs::Column::Id.is_in(claimed.iter().map(|job| job.job_id)))
+                            .exec(transaction).await
+                            .map_err(unexpected("LEASE_JOBS_ERROR"))?;
+                    }
+
+                    Ok(claimed)
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn complete(&self, job_id: &Uuid) -> Result<(), HearthError> {
+        jobs::Entity
+            ::delete_by_id(*job_id)
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("COMPLETE_JOB_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn retry(
+        &self,
+        job_id: &Uuid,
+        attempts: u32,
+        run_at: DateTime<Utc>,
+        error: &str
+    ) -> Result<(), HearthError> {
+        jobs::Entity
+            ::update_many()
+            .col_expr(jobs::Column::Attempts, Expr::value(attempts as i32))
+            .col_expr(jobs::Column::RunAt, Expr::value(run_at.naive_utc()))
+            .col_expr(jobs::Column::LastError, Expr::value(error))
+            .filter(jobs::Column::Id.eq(*job_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("RETRY_JOB_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn dead_letter(
+        &self,
+        job_id: &Uuid,
+        attempts: u32,
+        error: &str,
+        at: DateTime<Utc>
+    ) -> Result<(), HearthError> {
+        jobs::Entity
+            ::update_many()
+            .col_expr(jobs::Column::Attempts, Expr::value(attempts as i32))
+            .col_expr(jobs::Column::LastError, Expr::value(error))
+            .col_expr(jobs::Column::DeadAt, Expr::value(at.naive_utc()))
+            .filter(jobs::Column::Id.eq(*job_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("DEAD_LETTER_JOB_ERROR"))?;
+
+        Ok(())
+    }
+}
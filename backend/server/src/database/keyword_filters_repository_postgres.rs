@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::filter::{ FilterContext, KeywordFilterDTO },
+    error_codes::FILTER_NOT_FOUND_ERROR_CODE,
+    repositories::keyword_filters_repository::KeywordFiltersRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::keyword_filters, unexpected };
+
+pub struct KeywordFiltersRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl KeywordFiltersRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+fn to_keyword_filter_dto(model: keyword_filters::Model) -> KeywordFilterDTO {
+    KeywordFilterDTO {
+        filter_id: model.id,
+        owner_id: model.owner_id,
+        phrase: model.phrase,
+        whole_word: model.whole_word,
+        contexts: model.contexts.split(',').filter_map(FilterContext::parse).collect(),
+        expires_at: model.expires_at.map(|at| at.and_utc()),
+        created_at: model.created_at.and_utc(),
+    }
+}
+
+#[async_trait]
+impl KeywordFiltersRepository for KeywordFiltersRepositoryPostgres {
+    async fn create(&self, filter: KeywordFilterDTO) -> Result<(), HearthError> {
+        let contexts: Vec<&str> = filter.contexts.iter().map(|context| context.as_str()).collect();
+
+        keyword_filters::Entity
+            ::insert(keyword_filters::ActiveModel {
+                id: Set(filter.filter_id),
+                owner_id: Set(filter.owner_id),
+                phrase: Set(filter.phrase),
+                whole_word: Set(filter.whole_word),
+                contexts: Set(contexts.join(",")),
+                expires_at: Set(filter.expires_at.map(|at| at.naive_utc())),
+                created_at: Set(filter.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_KEYWORD_FILTER_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn list(&self, owner_id: &Uuid) -> Result<Vec<KeywordFilterDTO>, HearthError> {
+        let models = keyword_filters::Entity
+            ::find()
+            .filter(keyword_filters::Column::OwnerId.eq(*owner_id))
+            .order_by_asc(keyword_filters::Column::CreatedAt)
+            .order_by_asc(keyword_filters::Column::Id)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_KEYWORD_FILTERS_ERROR"))?;
+
+        Ok(models.into_iter().map(to_keyword_filter_dto).collect())
+    }
+
+    async fn delete(&self, owner_id: &Uuid, filter_id: &Uuid) -> Result<(), HearthError> {
+        let result = keyword_filters::Entity
+            ::delete_many()
+            .filter(keyword_filters::Column::Id.eq(*filter_id))
+            .filter(keyword_filters::Column::OwnerId.eq(*owner_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("DELETE_KEYWORD_FILTER_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(FILTER_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::{ collections::HashMap, sync::Arc };
+
+use async_trait::async_trait;
+use domain::{
+    dtos::link_preview::LinkPreviewDTO,
+    repositories::link_previews_repository::LinkPreviewsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    ConnectionTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::{ link_previews, post_link_previews }, unexpected };
+
+pub struct LinkPreviewsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl LinkPreviewsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+fn to_dto(model: link_previews::Model) -> LinkPreviewDTO {
+    LinkPreviewDTO {
+        url: model.url,
+        title: model.title,
+        description: model.description,
+        image_url: model.image_url,
+        site_name: model.site_name,
+        fetched_at: model.fetched_at.and_utc(),
+    }
+}
+
+/// Loads the previews attached to the given posts, keyed by post id.
+pub(crate) async fn find_link_previews<C: ConnectionTrait>(
+    connection: &C,
+    post_ids: &[Uuid]
+) -> Result<HashMap<Uuid, LinkPreviewDTO>, HearthError> {
+    let rows = post_link_previews::Entity
+        ::find()
+        .filter(post_link_previews::Column::PostId.is_in(post_ids.to_vec()))
+        .find_also_related(link_previews::Entity)
+        .all(connection).await
+        .map_err(unexpected("GET_POST_LINK_PREVIEWS_ERROR"))?;
+
+    Ok(
+        rows
+            .into_iter()
+            .filter_map(|(row, preview)| preview.map(|preview| (row.post_id, to_dto(preview))))
+            .collect()
+    )
+}
+
+#[async_trait]
+impl LinkPreviewsRepository for LinkPreviewsRepositoryPostgres {
+    async fn get(&self, url: &str) -> Result<Option<LinkPreviewDTO>, HearthError> {
+        link_previews::Entity
+            ::find_by_id(url.to_string())
+            .one(self.connection.as_ref()).await
+            .map(|model| model.map(to_dto))
+            .map_err(unexpected("GET_LINK_PREVIEW_ERROR"))
+    }
+
+    async fn save(&self, preview: LinkPreviewDTO) -> Result<(), HearthError> {
+        link_previews::Entity
+            ::insert(link_previews::ActiveModel {
+                url: Set(preview.url),
+                title: Set(preview.title),
+                description: Set(preview.description),
+                image_url: Set(preview.image_url),
+                site_name: Set(preview.site_name),
+                fetched_at: Set(preview.fetched_at.naive_utc()),
+            })
+            .on_conflict(
+                OnConflict::column(link_previews::Column::Url)
+                    .update_columns([
+                        link_previews::Column::Title,
+                        link_previews::Column::Description,
+                        link_previews::Column::ImageUrl,
+                        link_previews::Column::SiteName,
+                        link_previews::Column::FetchedAt,
+                    ])
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("SAVE_LINK_PREVIEW_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn attach(&self, post_id: &Uuid, url: &str) -> Result<(), HearthError> {
+        post_link_previews::Entity
+            ::insert(post_link_previews::ActiveModel {
+                post_id: Set(*post_id),
+                url: Set(url.to_string()),
+            })
+            .on_conflict(
+                OnConflict::column(post_link_previews::Column::PostId)
+                    .update_column(post_link_previews::Column::Url)
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("ATTACH_LINK_PREVIEW_ERROR"))?;
+
+        Ok(())
+    }
+}
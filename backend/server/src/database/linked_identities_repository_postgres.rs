@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::oidc::LinkedIdentityDTO,
+    repositories::linked_identities_repository::LinkedIdentitiesRepository,
+};
+use errors::HearthError;
+use sea_orm::{ ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter };
+use uuid::Uuid;
+
+use crate::database::{ entities::linked_identities, unexpected };
+
+pub struct LinkedIdentitiesRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl LinkedIdentitiesRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl LinkedIdentitiesRepository for LinkedIdentitiesRepositoryPostgres {
+    async fn get(
+        &self,
+        provider: &str,
+        subject: &str
+    ) -> Result<Option<LinkedIdentityDTO>, HearthError> {
+        let model = linked_identities::Entity
+            ::find()
+            .filter(linked_identities::Column::Provider.eq(provider))
+            .filter(linked_identities::Column::Subject.eq(subject))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_LINKED_IDENTITY_ERROR"))?;
+
+        Ok(
+            model.map(|model| LinkedIdentityDTO {
+                user_id: model.user_id,
+                provider: model.provider,
+                subject: model.subject,
+                email: model.email,
+                created_at: model.created_at.and_utc(),
+            })
+        )
+    }
+
+    async fn create(&self, identity: LinkedIdentityDTO) -> Result<(), HearthError> {
+        linked_identities::Entity
+            ::insert(linked_identities::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                user_id: Set(identity.user_id),
+                provider: Set(identity.provider),
+                subject: Set(identity.subject),
+                email: Set(identity.email),
+                created_at: Set(identity.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_LINKED_IDENTITY_ERROR"))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use std::{ collections::HashMap, sync::Arc };
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::list::{ ListDTO, ListMemberDTO },
+    error_codes::LIST_NOT_FOUND_ERROR_CODE,
+    repositories::lists_repository::ListsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::{ list_members, list_subscriptions, lists, users }, unexpected };
+
+pub struct ListsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl ListsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+fn to_dto(model: lists::Model) -> ListDTO {
+    ListDTO {
+        list_id: model.id,
+        owner_id: model.owner_id,
+        name: model.name,
+        is_private: model.is_private,
+        created_at: model.created_at.and_utc(),
+    }
+}
+
+#[async_trait]
+impl ListsRepository for ListsRepositoryPostgres {
+    async fn create(&self, list: ListDTO) -> Result<(), HearthError> {
+        lists::Entity
+            ::insert(lists::ActiveModel {
+                id: Set(list.list_id),
+                owner_id: Set(list.owner_id),
+                name: Set(list.name),
+                is_private: Set(list.is_private),
+                created_at: Set(list.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_LIST_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, list_id: &Uuid) -> Result<ListDTO, HearthError> {
+        lists::Entity
+            ::find_by_id(*list_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_LIST_ERROR"))?
+            .map(to_dto)
+            .ok_or_else(|| HearthError::not_found(LIST_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn update(&self, list: ListDTO) -> Result<(), HearthError> {
+        lists::Entity
+            ::update_many()
+            .set(lists::ActiveModel {
+                name: Set(list.name),
+                is_private: Set(list.is_private),
+                ..Default::default()
+            })
+            .filter(lists::Column::Id.eq(list.list_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("UPDATE_LIST_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, list_id: &Uuid) -> Result<(), HearthError> {
+        // Members, subscriptions and notifications cascade.
+        lists::Entity
+            ::delete_by_id(*list_id)
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("DELETE_LIST_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn list_by_owner(&self, owner_id: &Uuid) -> Result<Vec<ListDTO>, HearthError> {
+        let models = lists::Entity
+            ::find()
+            .filter(lists::Column::OwnerId.eq(*owner_id))
+            .order_by_asc(lists::Column::CreatedAt)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_LISTS_ERROR"))?;
+
+        Ok(models.into_iter().map(to_dto).collect())
+    }
+
+    async fn add_member(
+        &self,
+        list_id: &Uuid,
+        member: ListMemberDTO,
+        at: DateTime<Utc>
+    ) -> Result<bool, HearthError> {
+        let inserted = list_members::Entity
+            ::insert(list_members::ActiveModel {
+                list_id: Set(*list_id),
+                user_id: Set(member.user_id),
+                created_at: Set(at.naive_utc()),
+            })
+            .on_conflict(
+                OnConflict::columns([list_members::Column::ListId, list_members::Column::UserId])
+                    .do_nothing()
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("ADD_LIST_MEMBER_ERROR"))?;
+
+        Ok(inserted > 0)
+    }
+
+    async fn remove_member(&self, list_id: &Uuid, member_id: &Uuid) -> Result<(), HearthError> {
+        list_members::Entity
+            ::delete_by_id((*list_id, *member_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("REMOVE_LIST_MEMBER_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn members(&self, list_id: &Uuid) -> Result<Vec<ListMemberDTO>, HearthError> {
+        let member_ids: Vec<Uuid> = list_members::Entity
+            ::find()
+            .select_only()
+            .column(list_members::Column::UserId)
+            .filter(list_members::Column::ListId.eq(*list_id))
+            .order_by_asc(list_members::Column::CreatedAt)
+            .into_tuple()
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_LIST_MEMBERS_ERROR"))?;
+
+        let usernames: HashMap<Uuid, String> = users::Entity
+            ::find()
+            .filter(users::Column::Id.is_in(member_ids.clone()))
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_LIST_MEMBERS_ERROR"))?
+            .into_iter()
+            .map(|user| (user.id, user.username))
+            .collect();
+
+        Ok(
+            member_ids
+                .into_iter()
+                .filter_map(|user_id| {
+                    let username = usernames.get(&user_id)?.clone();
+                    Some(ListMemberDTO { user_id, username })
+                })
+                .collect()
+        )
+    }
+
+    async fn subscribe(&self, list_id: &Uuid, user_id: &Uuid) -> Result<(), HearthError> {
+        list_subscriptions::Entity
+            ::insert(list_subscriptions::ActiveModel {
+                list_id: Set(*list_id),
+                user_id: Set(*user_id),
+                created_at: Set(Utc::now().naive_utc()),
+            })
+            .on_conflict(
+                OnConflict::columns([
+                    list_subscriptions::Column::ListId,
+                    list_subscriptions::Column::UserId,
+                ])
+                    .do_nothing()
+                    .to_owned()
+            )
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("SUBSCRIBE_LIST_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, list_id: &Uuid, user_id: &Uuid) -> Result<(), HearthError> {
+        list_subscriptions::Entity
+            ::delete_by_id((*list_id, *user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("UNSUBSCRIBE_LIST_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn remove_subscribers(&self, list_id: &Uuid) -> Result<(), HearthError> {
+        list_subscriptions::Entity
+            ::delete_many()
+            .filter(list_subscriptions::Column::ListId.eq(*list_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("REMOVE_LIST_SUBSCRIBERS_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn subscriptions(&self, user_id: &Uuid) -> Result<Vec<ListDTO>, HearthError> {
+        let list_ids: Vec<Uuid> = list_subscriptions::Entity
+            ::find()
+            .select_only()
+            .column(list_subscriptions::Column::ListId)
+            .filter(list_subscriptions::Column::UserId.eq(*user_id))
+            .into_tuple()
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_LIST_SUBSCRIPTIONS_ERROR"))?;
+
+        let models = lists::Entity
+            ::find()
+            .filter(lists::Column::Id.is_in(list_ids))
+            .order_by_asc(lists::Column::Name)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_LIST_SUBSCRIPTIONS_ERROR"))?;
+
+        Ok(models.into_iter().map(to_dto).collect())
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::repositories::login_challenges_repository::LoginChallengesRepository;
+use errors::HearthError;
+use redis::{AsyncCommands, Client};
+use uuid::Uuid;
+
+use crate::database::unexpected;
+
+pub struct LoginChallengesRepositoryRedis {
+    client: Arc<Client>,
+}
+
+impl LoginChallengesRepositoryRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    fn key(token: &str) -> String {
+        format!("login_challenge:{}", token)
+    }
+
+    fn failures_key(token: &str) -> String {
+        format!("login_challenge_failures:{}", token)
+    }
+}
+
+#[async_trait]
+impl LoginChallengesRepository for LoginChallengesRepositoryRedis {
+    async fn create(&self, token: &str, user_id: &Uuid, ttl_seconds: u64) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("LCR_CREATE_ASYNC_CON"))?;
+
+        con.set_ex::<String, String, ()>(Self::key(token), user_id.to_string(), ttl_seconds)
+            .await
+            .map_err(unexpected("LCR_CREATE"))
+    }
+
+    async fn get_user_id(&self, token: &str) -> Result<Option<Uuid>, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("LCR_GET_ASYNC_CON"))?;
+
+        let user_id = con
+            .get::<String, Option<String>>(Self::key(token))
+            .await
+            .map_err(unexpected("LCR_GET"))?;
+
+        Ok(user_id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    async fn record_failure(&self, token: &str, ttl_seconds: u64) -> Result<u32, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("LCR_FAILURE_ASYNC_CON"))?;
+
+        let key = Self::failures_key(token);
+        let (failures,) = redis::pipe()
+            .atomic()
+            .incr(&key, 1)
+            .expire(&key, ttl_seconds as i64)
+            .ignore()
+            .query_async::<(u32,)>(&mut con)
+            .await
+            .map_err(unexpected("LCR_FAILURE"))?;
+
+        Ok(failures)
+    }
+
+    async fn delete(&self, token: &str) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("LCR_DELETE_ASYNC_CON"))?;
+
+        con.del::<Vec<String>, ()>(vec![Self::key(token), Self::failures_key(token)])
+            .await
+            .map_err(unexpected("LCR_DELETE"))
+    }
+}
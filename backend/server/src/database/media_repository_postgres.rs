@@ -0,0 +1,205 @@
+use std::{ collections::HashMap, sync::Arc };
+
+use async_trait::async_trait;
+use domain::{
+    dtos::media::{ MediaDTO, MediaStatus, MediaVariantDTO, ORIGINAL_VARIANT },
+    error_codes::MEDIA_NOT_FOUND_ERROR_CODE,
+    repositories::media_repository::MediaRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    ConnectionTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::{ media, media_variants }, transaction_error, unexpected };
+
+pub struct MediaRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl MediaRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+/// Loads media rows along with their variants, `original` first.
+pub(crate) async fn find_media<C: ConnectionTrait>(
+    connection: &C,
+    media_ids: &[Uuid]
+) -> Result<Vec<MediaDTO>, HearthError> {
+    let rows = media::Entity
+        ::find()
+        .filter(media::Column::Id.is_in(media_ids.to_vec()))
+        .find_with_related(media_variants::Entity)
+        .all(connection).await
+        .map_err(unexpected("GET_MEDIA_ERROR"))?;
+
+    Ok(
+        rows
+            .into_iter()
+            .map(|(model, variants)| {
+                let mut variants: Vec<MediaVariantDTO> = variants
+                    .into_iter()
+                    .map(|v| MediaVariantDTO {
+                        name: v.name,
+                        mime_type: v.mime_type,
+                        width: v.width as u32,
+                        height: v.height as u32,
+                        size_bytes: v.size_bytes as u64,
+                    })
+                    .collect();
+                variants.sort_by_key(|v| (v.name != ORIGINAL_VARIANT, v.width));
+
+                MediaDTO {
+                    media_id: model.id,
+                    owner_id: model.owner_id,
+                    mime_type: model.mime_type,
+                    status: MediaStatus::parse(&model.status).unwrap_or(MediaStatus::Failed),
+                    blurhash: model.blurhash,
+                    variants,
+                    created_at: model.created_at.and_utc(),
+                }
+            })
+            .collect()
+    )
+}
+
+async fn insert_variants<C: ConnectionTrait>(
+    connection: &C,
+    media: &MediaDTO
+) -> Result<(), HearthError> {
+    for variant in &media.variants {
+        media_variants::Entity
+            ::insert(media_variants::ActiveModel {
+                media_id: Set(media.media_id),
+                name: Set(variant.name.clone()),
+                mime_type: Set(variant.mime_type.clone()),
+                width: Set(variant.width as i32),
+                height: Set(variant.height as i32),
+                size_bytes: Set(variant.size_bytes as i64),
+            })
+            .exec_without_returning(connection).await
+            .map_err(unexpected("CREATE_MEDIA_VARIANT_ERROR"))?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl MediaRepository for MediaRepositoryPostgres {
+    async fn create(&self, media: MediaDTO) -> Result<(), HearthError> {
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    media::Entity
+                        ::insert(media::ActiveModel {
+                            id: Set(media.media_id),
+                            owner_id: Set(media.owner_id),
+                            mime_type: Set(media.mime_type.clone()),
+                            blurhash: Set(media.blurhash.clone()),
+                            size_bytes: Set(media.size_bytes() as i64),
+                            created_at: Set(media.created_at.naive_utc()),
+                            status: Set(media.status.as_str().into()),
+                        })
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_MEDIA_ERROR"))?;
+
+                    insert_variants(transaction, &media).await
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn update(&self, media: MediaDTO) -> Result<(), HearthError> {
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    media::Entity
+                        ::update_many()
+                        .col_expr(media::Column::Status, Expr::value(media.status.as_str()))
+                        .col_expr(media::Column::Blurhash, Expr::value(media.blurhash.clone()))
+                        .col_expr(media::Column::SizeBytes, Expr::value(media.size_bytes() as i64))
+                        .filter(media::Column::Id.eq(media.media_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("UPDATE_MEDIA_ERROR"))?;
+
+                    media_variants::Entity
+                        ::delete_many()
+                        .filter(media_variants::Column::MediaId.eq(media.media_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("DELETE_MEDIA_VARIANTS_ERROR"))?;
+
+                    insert_variants(transaction, &media).await
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn get(&self, media_id: &Uuid) -> Result<MediaDTO, HearthError> {
+        find_media(self.connection.as_ref(), &[*media_id]).await?
+            .pop()
+            .ok_or_else(|| HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn get_many(&self, media_ids: &[Uuid]) -> Result<Vec<MediaDTO>, HearthError> {
+        find_media(self.connection.as_ref(), media_ids).await
+    }
+
+    async fn total_bytes(&self, owner_id: &Uuid) -> Result<u64, HearthError> {
+        // SUM over bigint yields a numeric in Postgres, cast it back.
+        let total: Option<i64> = media::Entity
+            ::find()
+            .select_only()
+            .column_as(Expr::cust("COALESCE(SUM(size_bytes), 0)::bigint"), "total")
+            .filter(media::Column::OwnerId.eq(*owner_id))
+            .into_tuple()
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_MEDIA_TOTAL_BYTES_ERROR"))?;
+
+        Ok(total.unwrap_or_default() as u64)
+    }
+
+    async fn list_by_owner(&self, owner_id: &Uuid) -> Result<Vec<MediaDTO>, HearthError> {
+        let media_ids: Vec<Uuid> = media::Entity
+            ::find()
+            .select_only()
+            .column(media::Column::Id)
+            .filter(media::Column::OwnerId.eq(*owner_id))
+            .into_tuple()
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_MEDIA_BY_OWNER_ERROR"))?;
+
+        find_media(self.connection.as_ref(), &media_ids).await
+    }
+}
+
+/// Groups the media attached to each post, in attachment order.
+pub(crate) async fn find_media_by_post<C: ConnectionTrait>(
+    connection: &C,
+    rows: Vec<(Uuid, Uuid)>
+) -> Result<HashMap<Uuid, Vec<MediaDTO>>, HearthError> {
+    let media_ids: Vec<Uuid> = rows
+        .iter()
+        .map(|(_, media_id)| *media_id)
+        .collect();
+    let media = find_media(connection, &media_ids).await?;
+
+    let mut by_post: HashMap<Uuid, Vec<MediaDTO>> = HashMap::new();
+    for (post_id, media_id) in rows {
+        if let Some(item) = media.iter().find(|m| m.media_id == media_id) {
+            by_post.entry(post_id).or_default().push(item.clone());
+        }
+    }
+
+    Ok(by_post)
+}
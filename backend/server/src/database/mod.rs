@@ -1,5 +1,59 @@
+pub mod blocks_repository_postgres;
+pub mod bookmarks_repository_postgres;
+pub mod conversations_repository_postgres;
+pub mod credentials_repository_postgres;
+pub mod data_exports_repository_postgres;
+pub mod device_keys_repository_postgres;
+pub mod dm_settings_repository_postgres;
 pub mod email_sender_repository;
 pub mod email_verifications_repository_redis;
+pub mod envelopes_repository_postgres;
+pub mod event_publisher_redis;
+pub mod follows_repository_postgres;
+pub mod imported_posts_repository_postgres;
+pub mod imports_repository_postgres;
+pub mod job_queue_postgres;
+pub mod keyword_filters_repository_postgres;
+pub mod link_previews_repository_postgres;
+pub mod linked_identities_repository_postgres;
+pub mod lists_repository_postgres;
+pub mod login_challenges_repository_redis;
+pub mod media_repository_postgres;
+pub mod moderation_log_repository_postgres;
+pub mod notifications_repository_postgres;
+pub mod oauth_apps_repository_postgres;
+pub mod oauth_codes_repository_redis;
+pub mod oauth_tokens_repository_postgres;
+pub mod oidc_states_repository_redis;
+pub mod outbox_repository_postgres;
+pub mod passkeys_repository_postgres;
+pub mod personal_access_tokens_repository_postgres;
+pub mod personal_data_repository_postgres;
+pub mod polls_repository_postgres;
+pub mod posts_repository_postgres;
+pub mod rate_limiter_redis;
+pub mod reports_repository_postgres;
+pub mod sessions_repository_redis;
+pub mod signup_challenges_repository_redis;
+pub mod trends_repository_redis;
+pub mod two_factor_repository_postgres;
 pub mod users_repository_postgres;
+pub mod webauthn_challenges_repository_redis;
 pub mod entities;
-pub mod postgres_connector;
\ No newline at end of file
+pub mod postgres_connector;
+
+use errors::HearthError;
+use sea_orm::TransactionError;
+
+/// Wraps an infrastructure error into an unexpected `HearthError` tagged with `code`.
+pub(crate) fn unexpected<E: ToString>(code: &'static str) -> impl FnOnce(E) -> HearthError {
+    move |e| HearthError::unexpected(code.into(), Some(e.to_string()))
+}
+
+pub(crate) fn transaction_error(e: TransactionError<HearthError>) -> HearthError {
+    match e {
+        TransactionError::Transaction(err) => err,
+        TransactionError::Connection(err) =>
+            HearthError::unexpected("DATABASE_CONNECTION_ERROR".into(), Some(err.to_string())),
+    }
+}
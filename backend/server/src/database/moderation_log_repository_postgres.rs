@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::{
+        moderation::{ ModerationAction, ModerationLogEntryDTO, ReportTargetType },
+        pagination::TimelineCursor,
+    },
+    repositories::moderation_log_repository::ModerationLogRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+};
+
+use crate::database::{ entities::moderation_log, unexpected };
+
+/// Updates and deletes are also rejected by a trigger on the table.
+pub struct ModerationLogRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl ModerationLogRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl ModerationLogRepository for ModerationLogRepositoryPostgres {
+    async fn append(&self, entry: ModerationLogEntryDTO) -> Result<(), HearthError> {
+        moderation_log::Entity
+            ::insert(moderation_log::ActiveModel {
+                id: Set(entry.entry_id),
+                moderator_id: Set(entry.moderator_id),
+                personal_access_token_id: Set(entry.personal_access_token_id),
+                action: Set(entry.action.as_str().into()),
+                report_id: Set(entry.report_id),
+                target_type: Set(entry.target_type.as_str().into()),
+                target_id: Set(entry.target_id),
+                note: Set(entry.note),
+                created_at: Set(entry.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("APPEND_MODERATION_LOG_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<ModerationLogEntryDTO>, HearthError> {
+        let mut query = moderation_log::Entity::find();
+
+        if let Some(cursor) = cursor {
+            let created_at = cursor.created_at.naive_utc();
+            query = query.filter(
+                Condition::any()
+                    .add(moderation_log::Column::CreatedAt.lt(created_at))
+                    .add(
+                        Condition::all()
+                            .add(moderation_log::Column::CreatedAt.eq(created_at))
+                            .add(moderation_log::Column::Id.lt(cursor.id))
+                    )
+            );
+        }
+
+        let models = query
+            .order_by_desc(moderation_log::Column::CreatedAt)
+            .order_by_desc(moderation_log::Column::Id)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_MODERATION_LOG_ERROR"))?;
+
+        Ok(
+            models
+                .into_iter()
+                .filter_map(|model| {
+                    Some(ModerationLogEntryDTO {
+                        action: ModerationAction::parse(&model.action)?,
+                        target_type: ReportTargetType::parse(&model.target_type)?,
+                        entry_id: model.id,
+                        moderator_id: model.moderator_id,
+                        personal_access_token_id: model.personal_access_token_id,
+                        report_id: model.report_id,
+                        target_id: model.target_id,
+                        note: model.note,
+                        created_at: model.created_at.and_utc(),
+                    })
+                })
+                .collect()
+        )
+    }
+}
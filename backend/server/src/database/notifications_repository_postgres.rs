@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::{ notification::{ NotificationDTO, NotificationKind }, pagination::TimelineCursor },
+    repositories::notifications_repository::NotificationsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::notifications, unexpected };
+
+pub struct NotificationsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl NotificationsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl NotificationsRepository for NotificationsRepositoryPostgres {
+    async fn create(&self, notification: NotificationDTO) -> Result<(), HearthError> {
+        notifications::Entity
+            ::insert(notifications::ActiveModel {
+                id: Set(notification.notification_id),
+                recipient_id: Set(notification.recipient_id),
+                actor_id: Set(notification.actor_id),
+                kind: Set(notification.kind.as_str().into()),
+                post_id: Set(notification.post_id),
+                list_id: Set(notification.list_id),
+                read_at: Set(None),
+                created_at: Set(notification.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_NOTIFICATION_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        recipient_id: &Uuid,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<NotificationDTO>, HearthError> {
+        let mut query = notifications::Entity
+            ::find()
+            .filter(notifications::Column::RecipientId.eq(*recipient_id));
+
+        if let Some(cursor) = cursor {
+            let created_at = cursor.created_at.naive_utc();
+            query = query.filter(
+                Condition::any()
+                    .add(notifications::Column::CreatedAt.lt(created_at))
+                    .add(
+                        Condition::all()
+                            .add(notifications::Column::CreatedAt.eq(created_at))
+                            .add(notifications::Column::Id.lt(cursor.id))
+                    )
+            );
+        }
+
+        let models = query
+            .order_by_desc(notifications::Column::CreatedAt)
+            .order_by_desc(notifications::Column::Id)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_NOTIFICATIONS_ERROR"))?;
+
+        Ok(
+            models
+                .into_iter()
+                .filter_map(|model| {
+                    Some(NotificationDTO {
+                        kind: NotificationKind::parse(&model.kind)?,
+                        notification_id: model.id,
+                        recipient_id: model.recipient_id,
+                        actor_id: model.actor_id,
+                        post_id: model.post_id,
+                        list_id: model.list_id,
+                        filtered: vec![],
+                        created_at: model.created_at.and_utc(),
+                    })
+                })
+                .collect()
+        )
+    }
+}
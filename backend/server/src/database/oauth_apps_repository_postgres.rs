@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::oauth::{ OAuthAppDTO, Scope },
+    repositories::oauth_apps_repository::OAuthAppsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    PaginatorTrait,
+    QueryFilter,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::oauth_apps, unexpected };
+
+pub struct OAuthAppsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl OAuthAppsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl OAuthAppsRepository for OAuthAppsRepositoryPostgres {
+    async fn create(&self, app: OAuthAppDTO) -> Result<(), HearthError> {
+        oauth_apps::Entity
+            ::insert(oauth_apps::ActiveModel {
+                id: Set(app.client_id),
+                owner_id: Set(app.owner_id),
+                name: Set(app.name),
+                redirect_uris: Set(app.redirect_uris.join(" ")),
+                scopes: Set(Scope::join(&app.scopes)),
+                client_secret_hash: Set(app.client_secret_hash),
+                created_at: Set(app.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_OAUTH_APP_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, client_id: &Uuid) -> Result<Option<OAuthAppDTO>, HearthError> {
+        let model = oauth_apps::Entity
+            ::find_by_id(*client_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_OAUTH_APP_ERROR"))?;
+
+        Ok(
+            model.map(|model| OAuthAppDTO {
+                client_id: model.id,
+                owner_id: model.owner_id,
+                name: model.name,
+                redirect_uris: model.redirect_uris.split(' ').map(String::from).collect(),
+                scopes: Scope::parse_list(&model.scopes).unwrap_or_default(),
+                client_secret_hash: model.client_secret_hash,
+                created_at: model.created_at.and_utc(),
+            })
+        )
+    }
+
+    async fn count_by_owner(&self, owner_id: &Uuid) -> Result<u64, HearthError> {
+        oauth_apps::Entity
+            ::find()
+            .filter(oauth_apps::Column::OwnerId.eq(*owner_id))
+            .count(self.connection.as_ref()).await
+            .map_err(unexpected("COUNT_OAUTH_APPS_ERROR"))
+    }
+}
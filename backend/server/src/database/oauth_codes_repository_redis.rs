@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::oauth::OAuthCodeDTO,
+    repositories::oauth_codes_repository::OAuthCodesRepository,
+};
+use errors::HearthError;
+use redis::{AsyncCommands, Client};
+
+use crate::database::unexpected;
+
+/// Authorization codes are stored as JSON and read at most once.
+pub struct OAuthCodesRepositoryRedis {
+    client: Arc<Client>,
+}
+
+impl OAuthCodesRepositoryRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    fn key(code_hash: &str) -> String {
+        format!("oauth_code:{}", code_hash)
+    }
+}
+
+#[async_trait]
+impl OAuthCodesRepository for OAuthCodesRepositoryRedis {
+    async fn create(
+        &self,
+        code_hash: &str,
+        code: &OAuthCodeDTO,
+        ttl_seconds: u64
+    ) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("OCR_CREATE_ASYNC_CON"))?;
+
+        let value = serde_json::to_string(code).map_err(unexpected("OCR_CREATE_SERIALIZE"))?;
+
+        con.set_ex::<String, String, ()>(Self::key(code_hash), value, ttl_seconds)
+            .await
+            .map_err(unexpected("OCR_CREATE"))
+    }
+
+    async fn take(&self, code_hash: &str) -> Result<Option<OAuthCodeDTO>, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("OCR_TAKE_ASYNC_CON"))?;
+
+        let value = con
+            .get_del::<String, Option<String>>(Self::key(code_hash))
+            .await
+            .map_err(unexpected("OCR_TAKE"))?;
+
+        Ok(value.and_then(|value| serde_json::from_str(&value).ok()))
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::oauth::{ OAuthTokenDTO, Scope },
+    repositories::oauth_tokens_repository::OAuthTokensRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    sea_query::Expr,
+};
+
+use crate::database::{ entities::oauth_access_tokens, unexpected };
+
+pub struct OAuthTokensRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl OAuthTokensRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl OAuthTokensRepository for OAuthTokensRepositoryPostgres {
+    async fn create(&self, token: OAuthTokenDTO) -> Result<(), HearthError> {
+        oauth_access_tokens::Entity
+            ::insert(oauth_access_tokens::ActiveModel {
+                token_hash: Set(token.token_hash),
+                client_id: Set(token.client_id),
+                user_id: Set(token.user_id),
+                scopes: Set(Scope::join(&token.scopes)),
+                expires_at: Set(token.expires_at.naive_utc()),
+                revoked_at: Set(token.revoked_at.map(|at| at.naive_utc())),
+                created_at: Set(Utc::now().naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_OAUTH_TOKEN_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, token_hash: &str) -> Result<Option<OAuthTokenDTO>, HearthError> {
+        let model = oauth_access_tokens::Entity
+            ::find_by_id(token_hash.to_string())
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_OAUTH_TOKEN_ERROR"))?;
+
+        Ok(
+            model.map(|model| OAuthTokenDTO {
+                token_hash: model.token_hash,
+                client_id: model.client_id,
+                user_id: model.user_id,
+                scopes: Scope::parse_list(&model.scopes).unwrap_or_default(),
+                expires_at: model.expires_at.and_utc(),
+                revoked_at: model.revoked_at.map(|at| at.and_utc()),
+            })
+        )
+    }
+
+    async fn revoke(&self, token_hash: &str, at: DateTime<Utc>) -> Result<(), HearthError> {
+        oauth_access_tokens::Entity
+            ::update_many()
+            .col_expr(oauth_access_tokens::Column::RevokedAt, Expr::value(at.naive_utc()))
+            .filter(oauth_access_tokens::Column::TokenHash.eq(token_hash))
+            .filter(oauth_access_tokens::Column::RevokedAt.is_null())
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("REVOKE_OAUTH_TOKEN_ERROR"))?;
+
+        Ok(())
+    }
+}
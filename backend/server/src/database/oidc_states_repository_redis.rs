@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::oidc::{ OidcStateDTO, PendingOidcSignupDTO },
+    repositories::oidc_states_repository::OidcStatesRepository,
+};
+use errors::HearthError;
+use redis::{AsyncCommands, Client};
+use serde::{ Serialize, de::DeserializeOwned };
+
+use crate::database::unexpected;
+
+/// States and pending signups are stored as JSON and read at most once.
+pub struct OidcStatesRepositoryRedis {
+    client: Arc<Client>,
+}
+
+impl OidcStatesRepositoryRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    fn state_key(state: &str) -> String {
+        format!("oidc_state:{}", state)
+    }
+
+    fn pending_signup_key(token: &str) -> String {
+        format!("oidc_pending_signup:{}", token)
+    }
+
+    async fn set<T: Serialize + Sync>(
+        &self,
+        key: String,
+        value: &T,
+        ttl_seconds: u64
+    ) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("OSR_SET_ASYNC_CON"))?;
+
+        let value = serde_json::to_string(value).map_err(unexpected("OSR_SET_SERIALIZE"))?;
+
+        con.set_ex::<String, String, ()>(key, value, ttl_seconds)
+            .await
+            .map_err(unexpected("OSR_SET"))
+    }
+
+    async fn take<T: DeserializeOwned>(&self, key: String) -> Result<Option<T>, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("OSR_TAKE_ASYNC_CON"))?;
+
+        let value = con
+            .get_del::<String, Option<String>>(key)
+            .await
+            .map_err(unexpected("OSR_TAKE"))?;
+
+        Ok(value.and_then(|value| serde_json::from_str(&value).ok()))
+    }
+}
+
+#[async_trait]
+impl OidcStatesRepository for OidcStatesRepositoryRedis {
+    async fn create_state(&self, state: &OidcStateDTO, ttl_seconds: u64) -> Result<(), HearthError> {
+        self.set(Self::state_key(&state.state), state, ttl_seconds).await
+    }
+
+    async fn take_state(&self, state: &str) -> Result<Option<OidcStateDTO>, HearthError> {
+        self.take(Self::state_key(state)).await
+    }
+
+    async fn create_pending_signup(
+        &self,
+        token: &str,
+        signup: &PendingOidcSignupDTO,
+        ttl_seconds: u64
+    ) -> Result<(), HearthError> {
+        self.set(Self::pending_signup_key(token), signup, ttl_seconds).await
+    }
+
+    async fn take_pending_signup(
+        &self,
+        token: &str
+    ) -> Result<Option<PendingOidcSignupDTO>, HearthError> {
+        self.take(Self::pending_signup_key(token)).await
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::event::{ DomainEvent, EventDTO },
+    repositories::outbox_repository::OutboxRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    ConnectionTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::{ Expr, LockBehavior, LockType },
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::outbox_events, transaction_error, unexpected };
+
+/// Writes the events with the connection of the caller, so that they are
+/// committed or rolled back along with the change they describe.
+pub(crate) async fn append_events<C: ConnectionTrait>(
+    connection: &C,
+    events: Vec<EventDTO>
+) -> Result<(), HearthError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let mut models = vec![];
+    for event in events {
+        let payload = serde_json
+            ::to_string(&event.event)
+            .map_err(unexpected("APPEND_EVENT_SERIALIZE_ERROR"))?;
+
+        models.push(outbox_events::ActiveModel {
+            id: Set(event.event_id),
+            kind: Set(event.event.kind().into()),
+            payload: Set(payload),
+            occurred_at: Set(event.occurred_at.naive_utc()),
+            available_at: Set(event.occurred_at.naive_utc()),
+            dispatched_at: Set(None),
+        });
+    }
+
+    outbox_events::Entity
+        ::insert_many(models)
+        .exec_without_returning(connection).await
+        .map_err(unexpected("APPEND_EVENTS_ERROR"))?;
+
+    Ok(())
+}
+
+pub struct OutboxRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl OutboxRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for OutboxRepositoryPostgres {
+    async fn claim(
+        &self,
+        at: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<EventDTO>, HearthError> {
+        self.connection
+            .transaction::<_, Vec<EventDTO>, HearthError>(|transaction| {
+                Box::pin(async move {
+                    let models = outbox_events::Entity
+                        ::find()
+                        .filter(outbox_events::Column::DispatchedAt.is_null())
+                        .filter(outbox_events::Column::AvailableAt.lte(at.naive_utc()))
+                        .order_by_asc(outbox_events::Column::OccurredAt)
+                        .limit(limit)
+                        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+                        .all(transaction).await
+                        .map_err(unexpected("CLAIM_EVENTS_ERROR"))?;
+
+                    if models.is_empty() {
+                        return Ok(vec![]);
+                    }
+
+                    outbox_events::Entity
+                        ::update_many()
+                        .col_expr(
+                            outbox_events::Column::AvailableAt,
+                            Expr::value(lease_until.naive_utc())
+                        )
+                        .filter(outbox_events::Column::Id.is_in(models.iter().map(|m| m.id)))
+                        .exec(transaction).await
+                        .map_err(unexpected("LEASE_EVENTS_ERROR"))?;
+
+                    models
+                        .into_iter()
+                        .map(|model| {
+                            let event = serde_json
+                                ::from_str::<DomainEvent>(&model.payload)
+                                .map_err(unexpected("CLAIM_EVENTS_DESERIALIZE_ERROR"))?;
+
+                            Ok(EventDTO {
+                                event_id: model.id,
+                                event,
+                                occurred_at: model.occurred_at.and_utc(),
+                            })
+                        })
+                        .collect()
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn mark_dispatched(&self, event_id: &Uuid, at: DateTime<Utc>) -> Result<(), HearthError> {
+        outbox_events::Entity
+            ::update_many()
+            .col_expr(outbox_events::Column::DispatchedAt, Expr::value(at.naive_utc()))
+            .filter(outbox_events::Column::Id.eq(*event_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("MARK_EVENT_DISPATCHED_ERROR"))?;
+
+        Ok(())
+    }
+}
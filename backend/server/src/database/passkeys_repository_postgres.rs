@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{ dtos::passkey::PasskeyDTO, repositories::passkeys_repository::PasskeysRepository };
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::passkeys, unexpected };
+
+pub struct PasskeysRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl PasskeysRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+fn to_passkey_dto(model: passkeys::Model) -> PasskeyDTO {
+    PasskeyDTO {
+        passkey_id: model.id,
+        user_id: model.user_id,
+        credential_id: model.credential_id,
+        public_key: model.public_key,
+        sign_count: model.sign_count as u32,
+        name: model.name,
+        created_at: model.created_at.and_utc(),
+        last_used_at: model.last_used_at.map(|at| at.and_utc()),
+    }
+}
+
+#[async_trait]
+impl PasskeysRepository for PasskeysRepositoryPostgres {
+    async fn create(&self, passkey: PasskeyDTO) -> Result<(), HearthError> {
+        passkeys::Entity
+            ::insert(passkeys::ActiveModel {
+                id: Set(passkey.passkey_id),
+                user_id: Set(passkey.user_id),
+                credential_id: Set(passkey.credential_id),
+                public_key: Set(passkey.public_key),
+                sign_count: Set(passkey.sign_count as i64),
+                name: Set(passkey.name),
+                created_at: Set(passkey.created_at.naive_utc()),
+                last_used_at: Set(passkey.last_used_at.map(|at| at.naive_utc())),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_PASSKEY_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn list(&self, user_id: &Uuid) -> Result<Vec<PasskeyDTO>, HearthError> {
+        let models = passkeys::Entity
+            ::find()
+            .filter(passkeys::Column::UserId.eq(*user_id))
+            .order_by_asc(passkeys::Column::CreatedAt)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_PASSKEYS_ERROR"))?;
+
+        Ok(models.into_iter().map(to_passkey_dto).collect())
+    }
+
+    async fn get_by_credential_id(&self, credential_id: &[u8]) -> Result<Option<PasskeyDTO>, HearthError> {
+        let model = passkeys::Entity
+            ::find()
+            .filter(passkeys::Column::CredentialId.eq(credential_id.to_vec()))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_PASSKEY_ERROR"))?;
+
+        Ok(model.map(to_passkey_dto))
+    }
+
+    async fn update_sign_count(
+        &self,
+        passkey_id: &Uuid,
+        sign_count: u32,
+        at: DateTime<Utc>
+    ) -> Result<bool, HearthError> {
+        let mut update = passkeys::Entity
+            ::update_many()
+            .col_expr(passkeys::Column::SignCount, Expr::value(sign_count as i64))
+            .col_expr(passkeys::Column::LastUsedAt, Expr::value(at.naive_utc()))
+            .filter(passkeys::Column::Id.eq(*passkey_id));
+
+        // Authenticators without a counter always report zero.
+        if sign_count > 0 {
+            update = update.filter(passkeys::Column::SignCount.lt(sign_count as i64));
+        }
+
+        let result = update
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("UPDATE_PASSKEY_SIGN_COUNT_ERROR"))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::{ oauth::Scope, personal_access_token::PersonalAccessTokenDTO },
+    error_codes::PERSONAL_ACCESS_TOKEN_NOT_FOUND_ERROR_CODE,
+    repositories::personal_access_tokens_repository::PersonalAccessTokensRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    PaginatorTrait,
+    QueryFilter,
+    QueryOrder,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::personal_access_tokens, unexpected };
+
+pub struct PersonalAccessTokensRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl PersonalAccessTokensRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+
+    fn to_dto(model: personal_access_tokens::Model) -> PersonalAccessTokenDTO {
+        PersonalAccessTokenDTO {
+            token_id: model.id,
+            user_id: model.user_id,
+            name: model.name,
+            token_hash: model.token_hash,
+            scopes: Scope::parse_list(&model.scopes).unwrap_or_default(),
+            expires_at: model.expires_at.and_utc(),
+            last_used_at: model.last_used_at.map(|at| at.and_utc()),
+            created_at: model.created_at.and_utc(),
+        }
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokensRepository for PersonalAccessTokensRepositoryPostgres {
+    async fn create(&self, token: PersonalAccessTokenDTO) -> Result<(), HearthError> {
+        personal_access_tokens::Entity
+            ::insert(personal_access_tokens::ActiveModel {
+                id: Set(token.token_id),
+                user_id: Set(token.user_id),
+                name: Set(token.name),
+                token_hash: Set(token.token_hash),
+                scopes: Set(Scope::join(&token.scopes)),
+                expires_at: Set(token.expires_at.naive_utc()),
+                last_used_at: Set(token.last_used_at.map(|at| at.naive_utc())),
+                created_at: Set(token.created_at.naive_utc()),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_PERSONAL_ACCESS_TOKEN_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get_by_hash(
+        &self,
+        token_hash: &str
+    ) -> Result<Option<PersonalAccessTokenDTO>, HearthError> {
+        let model = personal_access_tokens::Entity
+            ::find()
+            .filter(personal_access_tokens::Column::TokenHash.eq(token_hash))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_PERSONAL_ACCESS_TOKEN_ERROR"))?;
+
+        Ok(model.map(Self::to_dto))
+    }
+
+    async fn list_by_user(&self, user_id: &Uuid) -> Result<Vec<PersonalAccessTokenDTO>, HearthError> {
+        let models = personal_access_tokens::Entity
+            ::find()
+            .filter(personal_access_tokens::Column::UserId.eq(*user_id))
+            .order_by_desc(personal_access_tokens::Column::CreatedAt)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_PERSONAL_ACCESS_TOKENS_ERROR"))?;
+
+        Ok(models.into_iter().map(Self::to_dto).collect())
+    }
+
+    async fn count_by_user(&self, user_id: &Uuid) -> Result<u64, HearthError> {
+        personal_access_tokens::Entity
+            ::find()
+            .filter(personal_access_tokens::Column::UserId.eq(*user_id))
+            .count(self.connection.as_ref()).await
+            .map_err(unexpected("COUNT_PERSONAL_ACCESS_TOKENS_ERROR"))
+    }
+
+    async fn delete(&self, user_id: &Uuid, token_id: &Uuid) -> Result<(), HearthError> {
+        let result = personal_access_tokens::Entity
+            ::delete_many()
+            .filter(personal_access_tokens::Column::Id.eq(*token_id))
+            .filter(personal_access_tokens::Column::UserId.eq(*user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("DELETE_PERSONAL_ACCESS_TOKEN_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(PERSONAL_ACCESS_TOKEN_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn touch(&self, token_id: &Uuid, at: DateTime<Utc>) -> Result<(), HearthError> {
+        personal_access_tokens::Entity
+            ::update_many()
+            .col_expr(personal_access_tokens::Column::LastUsedAt, Expr::value(at.naive_utc()))
+            .filter(personal_access_tokens::Column::Id.eq(*token_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("TOUCH_PERSONAL_ACCESS_TOKEN_ERROR"))?;
+
+        Ok(())
+    }
+}
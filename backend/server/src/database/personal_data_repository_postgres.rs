@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::data_export::{ ExportedFollowsDTO, ExportedProfileDTO, PersonalDataDTO },
+    error_codes::USER_NOT_FOUND_ERROR_CODE,
+    repositories::personal_data_repository::PersonalDataRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    sea_query::Query,
+};
+use uuid::Uuid;
+
+use crate::database::{
+    bookmarks_repository_postgres,
+    conversations_repository_postgres::ConversationsRepositoryPostgres,
+    entities::{ bookmarks, conversation_participants, follows, media, messages, posts, users },
+    media_repository_postgres::find_media,
+    posts_repository_postgres::PostsRepositoryPostgres,
+    unexpected,
+};
+
+/// Reads straight from the tables of the other repositories, in a single place, so
+/// that an export doesn't have to page through each of them.
+pub struct PersonalDataRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl PersonalDataRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl PersonalDataRepository for PersonalDataRepositoryPostgres {
+    async fn collect(&self, user_id: &Uuid) -> Result<PersonalDataDTO, HearthError> {
+        let connection = self.connection.as_ref();
+
+        let user = users::Entity
+            ::find_by_id(*user_id)
+            .one(connection).await
+            .map_err(unexpected("EXPORT_USER_ERROR"))?
+            .ok_or_else(|| HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()))?;
+
+        let post_models = posts::Entity
+            ::find()
+            .filter(posts::Column::AuthorId.eq(*user_id))
+            .order_by_asc(posts::Column::CreatedAt)
+            .all(connection).await
+            .map_err(unexpected("EXPORT_POSTS_ERROR"))?;
+        let posts = PostsRepositoryPostgres::hydrate(connection, post_models).await?;
+
+        let following: Vec<Uuid> = follows::Entity
+            ::find()
+            .select_only()
+            .column(follows::Column::FolloweeId)
+            .filter(follows::Column::FollowerId.eq(*user_id))
+            .order_by_asc(follows::Column::CreatedAt)
+            .into_tuple()
+            .all(connection).await
+            .map_err(unexpected("EXPORT_FOLLOWS_ERROR"))?;
+        let followers: Vec<Uuid> = follows::Entity
+            ::find()
+            .select_only()
+            .column(follows::Column::FollowerId)
+            .filter(follows::Column::FolloweeId.eq(*user_id))
+            .order_by_asc(follows::Column::CreatedAt)
+            .into_tuple()
+            .all(connection).await
+            .map_err(unexpected("EXPORT_FOLLOWS_ERROR"))?;
+
+        let bookmarks = bookmarks::Entity
+            ::find()
+            .filter(bookmarks::Column::OwnerId.eq(*user_id))
+            .order_by_asc(bookmarks::Column::CreatedAt)
+            .all(connection).await
+            .map_err(unexpected("EXPORT_BOOKMARKS_ERROR"))?
+            .into_iter()
+            .map(bookmarks_repository_postgres::to_dto)
+            .collect();
+
+        let message_models = messages::Entity
+            ::find()
+            .filter(
+                messages::Column::ConversationId.in_subquery(
+                    Query::select()
+                        .column(conversation_participants::Column::ConversationId)
+                        .from(conversation_participants::Entity)
+                        .and_where(conversation_participants::Column::UserId.eq(*user_id))
+                        .to_owned()
+                )
+            )
+            .order_by_asc(messages::Column::ConversationId)
+            .order_by_asc(messages::Column::CreatedAt)
+            .all(connection).await
+            .map_err(unexpected("EXPORT_MESSAGES_ERROR"))?;
+        let messages = ConversationsRepositoryPostgres::hydrate_messages(
+            connection,
+            message_models
+        ).await?;
+
+        let media_ids: Vec<Uuid> = media::Entity
+            ::find()
+            .select_only()
+            .column(media::Column::Id)
+            .filter(media::Column::OwnerId.eq(*user_id))
+            .into_tuple()
+            .all(connection).await
+            .map_err(unexpected("EXPORT_MEDIA_ERROR"))?;
+        let media = find_media(connection, &media_ids).await?;
+
+        Ok(PersonalDataDTO {
+            profile: ExportedProfileDTO {
+                user_id: user.id,
+                username: user.username,
+                email: user.email,
+                birthday: user.birthday,
+                avatar_media_id: user.avatar_media_id,
+                created_at: user.created_at.and_utc(),
+            },
+            posts,
+            follows: ExportedFollowsDTO { following, followers },
+            bookmarks,
+            messages,
+            media,
+        })
+    }
+}
@@ -0,0 +1,251 @@
+use std::{ collections::HashMap, sync::Arc };
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::poll::{ PollDTO, PollOptionDTO },
+    error_codes::{ POLL_ALREADY_VOTED_ERROR_CODE, POLL_NOT_FOUND_ERROR_CODE },
+    repositories::polls_repository::PollsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    ConnectionTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::{ Expr, ExprTrait, OnConflict },
+};
+use uuid::Uuid;
+
+use crate::database::{
+    entities::{ poll_options, poll_voters, poll_votes, polls },
+    transaction_error,
+    unexpected,
+};
+
+pub struct PollsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl PollsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+/// Loads the polls of the given posts with their tallies, keyed by post id.
+pub(crate) async fn find_polls<C: ConnectionTrait>(
+    connection: &C,
+    post_ids: &[Uuid]
+) -> Result<HashMap<Uuid, PollDTO>, HearthError> {
+    let rows = polls::Entity
+        ::find()
+        .filter(polls::Column::PostId.is_in(post_ids.to_vec()))
+        .find_with_related(poll_options::Entity)
+        .all(connection).await
+        .map_err(unexpected("GET_POLLS_ERROR"))?;
+
+    Ok(
+        rows
+            .into_iter()
+            .map(|(poll, mut options)| {
+                options.sort_by_key(|option| option.position);
+
+                (
+                    poll.post_id,
+                    PollDTO {
+                        post_id: poll.post_id,
+                        options: options
+                            .into_iter()
+                            .map(|option| PollOptionDTO {
+                                title: option.title,
+                                votes_count: Some(option.votes_count as u64),
+                            })
+                            .collect(),
+                        multiple_choice: poll.multiple_choice,
+                        closes_at: poll.closes_at.and_utc(),
+                        voters_count: Some(poll.voters_count as u64),
+                        own_votes: vec![],
+                    },
+                )
+            })
+            .collect()
+    )
+}
+
+#[async_trait]
+impl PollsRepository for PollsRepositoryPostgres {
+    async fn create(&self, poll: PollDTO) -> Result<(), HearthError> {
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    polls::Entity
+                        ::insert(polls::ActiveModel {
+                            post_id: Set(poll.post_id),
+                            multiple_choice: Set(poll.multiple_choice),
+                            closes_at: Set(poll.closes_at.naive_utc()),
+                            voters_count: Set(0),
+                            ended_notified_at: Set(None),
+                        })
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_POLL_ERROR"))?;
+
+                    for (position, option) in poll.options.into_iter().enumerate() {
+                        poll_options::Entity
+                            ::insert(poll_options::ActiveModel {
+                                post_id: Set(poll.post_id),
+                                position: Set(position as i16),
+                                title: Set(option.title),
+                                votes_count: Set(0),
+                            })
+                            .exec_without_returning(transaction).await
+                            .map_err(unexpected("CREATE_POLL_OPTION_ERROR"))?;
+                    }
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn get(&self, post_id: &Uuid) -> Result<PollDTO, HearthError> {
+        find_polls(self.connection.as_ref(), &[*post_id]).await?
+            .remove(post_id)
+            .ok_or_else(|| HearthError::not_found(POLL_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn own_votes(&self, post_id: &Uuid, user_id: &Uuid) -> Result<Vec<u8>, HearthError> {
+        let positions: Vec<i16> = poll_votes::Entity
+            ::find()
+            .select_only()
+            .column(poll_votes::Column::Position)
+            .filter(poll_votes::Column::PostId.eq(*post_id))
+            .filter(poll_votes::Column::UserId.eq(*user_id))
+            .order_by_asc(poll_votes::Column::Position)
+            .into_tuple()
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("GET_OWN_VOTES_ERROR"))?;
+
+        Ok(
+            positions
+                .into_iter()
+                .map(|p| p as u8)
+                .collect()
+        )
+    }
+
+    async fn vote(&self, post_id: &Uuid, user_id: &Uuid, choices: &[u8]) -> Result<(), HearthError> {
+        let post_id = *post_id;
+        let user_id = *user_id;
+        let positions: Vec<i16> = choices
+            .iter()
+            .map(|c| *c as i16)
+            .collect();
+
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    let inserted = poll_voters::Entity
+                        ::insert(poll_voters::ActiveModel {
+                            post_id: Set(post_id),
+                            user_id: Set(user_id),
+                            created_at: Set(Utc::now().naive_utc()),
+                        })
+                        .on_conflict(
+                            OnConflict::columns([
+                                poll_voters::Column::PostId,
+                                poll_voters::Column::UserId,
+                            ])
+                                .do_nothing()
+                                .to_owned()
+                        )
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_POLL_VOTER_ERROR"))?;
+
+                    if inserted == 0 {
+                        return Err(HearthError::Domain(POLL_ALREADY_VOTED_ERROR_CODE.into()));
+                    }
+
+                    for position in &positions {
+                        poll_votes::Entity
+                            ::insert(poll_votes::ActiveModel {
+                                post_id: Set(post_id),
+                                user_id: Set(user_id),
+                                position: Set(*position),
+                            })
+                            .exec_without_returning(transaction).await
+                            .map_err(unexpected("CREATE_POLL_VOTE_ERROR"))?;
+                    }
+
+                    poll_options::Entity
+                        ::update_many()
+                        .col_expr(
+                            poll_options::Column::VotesCount,
+                            Expr::col(poll_options::Column::VotesCount).add(1)
+                        )
+                        .filter(poll_options::Column::PostId.eq(post_id))
+                        .filter(poll_options::Column::Position.is_in(positions))
+                        .exec(transaction).await
+                        .map_err(unexpected("UPDATE_POLL_OPTIONS_ERROR"))?;
+
+                    polls::Entity
+                        ::update_many()
+                        .col_expr(
+                            polls::Column::VotersCount,
+                            Expr::col(polls::Column::VotersCount).add(1)
+                        )
+                        .filter(polls::Column::PostId.eq(post_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("UPDATE_POLL_ERROR"))?;
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn list_ended_unnotified(
+        &self,
+        at: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<Uuid>, HearthError> {
+        polls::Entity
+            ::find()
+            .select_only()
+            .column(polls::Column::PostId)
+            .filter(polls::Column::ClosesAt.lte(at.naive_utc()))
+            .filter(polls::Column::EndedNotifiedAt.is_null())
+            .order_by_asc(polls::Column::ClosesAt)
+            .limit(limit)
+            .into_tuple()
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_ENDED_POLLS_ERROR"))
+    }
+
+    async fn voters(&self, post_id: &Uuid) -> Result<Vec<Uuid>, HearthError> {
+        poll_voters::Entity
+            ::find()
+            .select_only()
+            .column(poll_voters::Column::UserId)
+            .filter(poll_voters::Column::PostId.eq(*post_id))
+            .into_tuple()
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("GET_POLL_VOTERS_ERROR"))
+    }
+
+    async fn mark_ended_notified(&self, post_id: &Uuid) -> Result<(), HearthError> {
+        polls::Entity
+            ::update_many()
+            .col_expr(polls::Column::EndedNotifiedAt, Expr::current_timestamp())
+            .filter(polls::Column::PostId.eq(*post_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("MARK_POLL_NOTIFIED_ERROR"))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+use std::{ collections::HashMap, sync::Arc };
+
+use async_trait::async_trait;
+use domain::{
+    dtos::{ pagination::TimelineCursor, post::{ MentionDTO, PostDTO } },
+    error_codes::POST_NOT_FOUND_ERROR_CODE,
+    repositories::posts_repository::PostsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    ConnectionTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    TransactionTrait,
+    sea_query::{ Expr, ExprTrait, OnConflict, Query },
+};
+use uuid::Uuid;
+
+use crate::database::{
+    entities::{ hashtags, post_hashtags, post_media, post_mentions, posts, users },
+    link_previews_repository_postgres::find_link_previews,
+    media_repository_postgres::find_media_by_post,
+    polls_repository_postgres::find_polls,
+    transaction_error,
+    unexpected,
+};
+
+pub struct PostsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl PostsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+
+    /// Attaches hashtag names, resolved mentions, media, polls and link previews to
+    /// the given posts.
+    pub(crate) async fn hydrate<C: ConnectionTrait>(
+        connection: &C,
+        models: Vec<posts::Model>
+    ) -> Result<Vec<PostDTO>, HearthError> {
+        let ids: Vec<Uuid> = models
+            .iter()
+            .map(|m| m.id)
+            .collect();
+
+        let rows = post_hashtags::Entity
+            ::find()
+            .filter(post_hashtags::Column::PostId.is_in(ids.clone()))
+            .find_also_related(hashtags::Entity)
+            .all(connection).await
+            .map_err(unexpected("GET_POST_HASHTAGS_ERROR"))?;
+
+        let mut tags_by_post: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for (row, hashtag) in rows {
+            if let Some(hashtag) = hashtag {
+                tags_by_post.entry(row.post_id).or_default().push(hashtag.name);
+            }
+        }
+
+        let rows = post_mentions::Entity
+            ::find()
+            .filter(post_mentions::Column::PostId.is_in(ids.clone()))
+            .order_by_asc(post_mentions::Column::StartOffset)
+            .find_also_related(users::Entity)
+            .all(connection).await
+            .map_err(unexpected("GET_POST_MENTIONS_ERROR"))?;
+
+        let mut mentions_by_post: HashMap<Uuid, Vec<MentionDTO>> = HashMap::new();
+        for (row, user) in rows {
+            if let Some(user) = user {
+                mentions_by_post.entry(row.post_id).or_default().push(MentionDTO {
+                    user_id: row.user_id,
+                    username: user.username,
+                    start: row.start_offset as usize,
+                    end: row.end_offset as usize,
+                });
+            }
+        }
+
+        let rows: Vec<(Uuid, Uuid)> = post_media::Entity
+            ::find()
+            .select_only()
+            .column(post_media::Column::PostId)
+            .column(post_media::Column::MediaId)
+            .filter(post_media::Column::PostId.is_in(ids.clone()))
+            .order_by_asc(post_media::Column::Position)
+            .into_tuple()
+            .all(connection).await
+            .map_err(unexpected("GET_POST_MEDIA_ERROR"))?;
+
+        let mut media_by_post = find_media_by_post(connection, rows).await?;
+        let mut polls_by_post = find_polls(connection, &ids).await?;
+        let mut previews_by_post = find_link_previews(connection, &ids).await?;
+
+        Ok(
+            models
+                .into_iter()
+                .map(|model| PostDTO {
+                    hashtags: tags_by_post.remove(&model.id).unwrap_or_default(),
+                    mentions: mentions_by_post.remove(&model.id).unwrap_or_default(),
+                    media: media_by_post.remove(&model.id).unwrap_or_default(),
+                    poll: polls_by_post.remove(&model.id),
+                    link_preview: previews_by_post.remove(&model.id),
+                    filtered: vec![],
+                    sensitive: model.sensitive,
+                    age_gated: false,
+                    post_id: model.id,
+                    author_id: model.author_id,
+                    content: model.content,
+                    created_at: model.created_at.and_utc(),
+                })
+                .collect()
+        )
+    }
+}
+
+#[async_trait]
+impl PostsRepository for PostsRepositoryPostgres {
+    async fn create(&self, post: PostDTO) -> Result<(), HearthError> {
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    let created_at = post.created_at.naive_utc();
+
+                    posts::Entity
+                        ::insert(posts::ActiveModel {
+                            id: Set(post.post_id),
+                            author_id: Set(post.author_id),
+                            content: Set(post.content),
+                            created_at: Set(created_at),
+                            updated_at: Set(created_at),
+                            hidden_at: Set(None),
+                            sensitive: Set(post.sensitive),
+                        })
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_POST_ERROR"))?;
+
+                    for mention in &post.mentions {
+                        post_mentions::Entity
+                            ::insert(post_mentions::ActiveModel {
+                                post_id: Set(post.post_id),
+                                user_id: Set(mention.user_id),
+                                start_offset: Set(mention.start as i32),
+                                end_offset: Set(mention.end as i32),
+                            })
+                            .exec_without_returning(transaction).await
+                            .map_err(unexpected("CREATE_POST_MENTION_ERROR"))?;
+                    }
+
+                    for (position, media) in post.media.iter().enumerate() {
+                        post_media::Entity
+                            ::insert(post_media::ActiveModel {
+                                post_id: Set(post.post_id),
+                                media_id: Set(media.media_id),
+                                position: Set(position as i16),
+                            })
+                            .exec_without_returning(transaction).await
+                            .map_err(unexpected("CREATE_POST_MEDIA_ERROR"))?;
+                    }
+
+                    if post.hashtags.is_empty() {
+                        return Ok(());
+                    }
+
+                    for tag in &post.hashtags {
+                        hashtags::Entity
+                            ::insert(hashtags::ActiveModel {
+                                id: Set(Uuid::new_v4()),
+                                name: Set(tag.clone()),
+                                ..Default::default()
+                            })
+                            .on_conflict(
+                                OnConflict::column(hashtags::Column::Name).do_nothing().to_owned()
+                            )
+                            .exec_without_returning(transaction).await
+                            .map_err(unexpected("CREATE_HASHTAG_ERROR"))?;
+                    }
+
+                    let stored = hashtags::Entity
+                        ::find()
+                        .filter(hashtags::Column::Name.is_in(post.hashtags.clone()))
+                        .all(transaction).await
+                        .map_err(unexpected("GET_HASHTAGS_ERROR"))?;
+
+                    for hashtag in stored {
+                        post_hashtags::Entity
+                            ::insert(post_hashtags::ActiveModel {
+                                post_id: Set(post.post_id),
+                                hashtag_id: Set(hashtag.id),
+                                created_at: Set(created_at),
+                            })
+                            .exec_without_returning(transaction).await
+                            .map_err(unexpected("CREATE_POST_HASHTAG_ERROR"))?;
+                    }
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn get(&self, post_id: &Uuid) -> Result<PostDTO, HearthError> {
+        let model = posts::Entity
+            ::find_by_id(*post_id)
+            .filter(posts::Column::HiddenAt.is_null())
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_POST_ERROR"))?
+            .ok_or_else(|| HearthError::not_found(POST_NOT_FOUND_ERROR_CODE.into()))?;
+
+        let mut posts = Self::hydrate(self.connection.as_ref(), vec![model]).await?;
+        Ok(posts.remove(0))
+    }
+
+    async fn get_many(&self, post_ids: &[Uuid]) -> Result<Vec<PostDTO>, HearthError> {
+        let models = posts::Entity
+            ::find()
+            .filter(posts::Column::Id.is_in(post_ids.to_vec()))
+            .filter(posts::Column::HiddenAt.is_null())
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("GET_POSTS_ERROR"))?;
+
+        Self::hydrate(self.connection.as_ref(), models).await
+    }
+
+    async fn list_by_hashtag(
+        &self,
+        tag: &str,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<PostDTO>, HearthError> {
+        let tagged = Query::select()
+            .column(post_hashtags::Column::PostId)
+            .from(post_hashtags::Entity)
+            .inner_join(
+                hashtags::Entity,
+                sea_orm::sea_query::Expr
+                    ::col((hashtags::Entity, hashtags::Column::Id))
+                    .equals((post_hashtags::Entity, post_hashtags::Column::HashtagId))
+            )
+            .and_where(hashtags::Column::Name.eq(tag))
+            .to_owned();
+
+        let mut query = posts::Entity
+            ::find()
+            .filter(posts::Column::Id.in_subquery(tagged))
+            .filter(posts::Column::HiddenAt.is_null());
+
+        if let Some(cursor) = cursor {
+            let created_at = cursor.created_at.naive_utc();
+            query = query.filter(
+                Condition::any()
+                    .add(posts::Column::CreatedAt.lt(created_at))
+                    .add(
+                        Condition::all()
+                            .add(posts::Column::CreatedAt.eq(created_at))
+                            .add(posts::Column::Id.lt(cursor.id))
+                    )
+            );
+        }
+
+        let models = query
+            .order_by_desc(posts::Column::CreatedAt)
+            .order_by_desc(posts::Column::Id)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_POSTS_BY_HASHTAG_ERROR"))?;
+
+        Self::hydrate(self.connection.as_ref(), models).await
+    }
+
+    async fn list_by_authors(
+        &self,
+        author_ids: &[Uuid],
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<PostDTO>, HearthError> {
+        let mut query = posts::Entity
+            ::find()
+            .filter(posts::Column::AuthorId.is_in(author_ids.iter().copied()))
+            .filter(posts::Column::HiddenAt.is_null());
+
+        if let Some(cursor) = cursor {
+            let created_at = cursor.created_at.naive_utc();
+            query = query.filter(
+                Condition::any()
+                    .add(posts::Column::CreatedAt.lt(created_at))
+                    .add(
+                        Condition::all()
+                            .add(posts::Column::CreatedAt.eq(created_at))
+                            .add(posts::Column::Id.lt(cursor.id))
+                    )
+            );
+        }
+
+        let models = query
+            .order_by_desc(posts::Column::CreatedAt)
+            .order_by_desc(posts::Column::Id)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_POSTS_BY_AUTHORS_ERROR"))?;
+
+        Self::hydrate(self.connection.as_ref(), models).await
+    }
+
+    async fn hide(&self, post_id: &Uuid) -> Result<(), HearthError> {
+        posts::Entity
+            ::update_many()
+            .col_expr(posts::Column::HiddenAt, Expr::current_timestamp())
+            .filter(posts::Column::Id.eq(*post_id))
+            .filter(posts::Column::HiddenAt.is_null())
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("HIDE_POST_ERROR"))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::rate_limit::RateLimitDTO, policies::rate_limit::TokenBucket,
+    repositories::rate_limiter::RateLimiter,
+};
+use errors::HearthError;
+use redis::{Client, Script};
+
+use crate::database::unexpected;
+
+/// Refills and takes a token in a single step so concurrent requests from
+/// several instances cannot both spend the last token. The clock is the one
+/// of Redis, instances with drifting clocks still agree on the refill.
+///
+/// Returns `{allowed, remaining, reset_ms, retry_after_ms}`.
+const TAKE_TOKEN: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_ms = tonumber(ARGV[2]) * 1000
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'at')
+local tokens = tonumber(state[1]) or capacity
+local at = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + (now - at) / refill_ms)
+
+local allowed = 0
+local retry_after_ms = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_after_ms = math.ceil((1 - tokens) * refill_ms)
+end
+
+local reset_ms = math.ceil((capacity - tokens) * refill_ms)
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'at', now)
+redis.call('PEXPIRE', KEYS[1], reset_ms + 1000)
+
+return { allowed, math.floor(tokens), reset_ms, retry_after_ms }
+"#;
+
+pub struct RateLimiterRedis {
+    client: Arc<Client>,
+    script: Script,
+}
+
+impl RateLimiterRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            script: Script::new(TAKE_TOKEN),
+        }
+    }
+}
+
+fn to_seconds(ms: u64) -> u64 {
+    ms.div_ceil(1000)
+}
+
+#[async_trait]
+impl RateLimiter for RateLimiterRedis {
+    async fn take(&self, key: &str, bucket: TokenBucket) -> Result<RateLimitDTO, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("RL_TAKE_ASYNC_CON"))?;
+
+        let (allowed, remaining, reset_ms, retry_after_ms): (u8, u32, u64, u64) = self
+            .script
+            .key(key)
+            .arg(bucket.capacity)
+            .arg(bucket.refill_seconds)
+            .invoke_async(&mut con)
+            .await
+            .map_err(unexpected("RL_TAKE"))?;
+
+        Ok(RateLimitDTO {
+            limit: bucket.capacity,
+            remaining,
+            reset_seconds: to_seconds(reset_ms),
+            retry_after_seconds: (allowed == 0).then(|| to_seconds(retry_after_ms)),
+        })
+    }
+}
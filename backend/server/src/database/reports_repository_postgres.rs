@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::{
+        moderation::{ ReportDTO, ReportReason, ReportState, ReportTargetType },
+        pagination::TimelineCursor,
+    },
+    error_codes::{ REPORT_ALREADY_RESOLVED_ERROR_CODE, REPORT_NOT_FOUND_ERROR_CODE },
+    repositories::reports_repository::ReportsRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::reports, unexpected };
+
+pub struct ReportsRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl ReportsRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+fn to_report_dto(model: reports::Model) -> Option<ReportDTO> {
+    Some(ReportDTO {
+        report_id: model.id,
+        reporter_id: model.reporter_id,
+        target_type: ReportTargetType::parse(&model.target_type)?,
+        target_id: model.target_id,
+        target_user_id: model.target_user_id,
+        reason: ReportReason::parse(&model.reason)?,
+        details: model.details,
+        state: ReportState::parse(&model.state)?,
+        created_at: model.created_at.and_utc(),
+        resolved_by: model.resolved_by,
+        resolved_at: model.resolved_at.map(|at| at.and_utc()),
+    })
+}
+
+#[async_trait]
+impl ReportsRepository for ReportsRepositoryPostgres {
+    async fn create(&self, report: ReportDTO) -> Result<(), HearthError> {
+        reports::Entity
+            ::insert(reports::ActiveModel {
+                id: Set(report.report_id),
+                reporter_id: Set(report.reporter_id),
+                target_type: Set(report.target_type.as_str().into()),
+                target_id: Set(report.target_id),
+                target_user_id: Set(report.target_user_id),
+                reason: Set(report.reason.as_str().into()),
+                details: Set(report.details),
+                state: Set(report.state.as_str().into()),
+                created_at: Set(report.created_at.naive_utc()),
+                resolved_by: Set(report.resolved_by),
+                resolved_at: Set(report.resolved_at.map(|at| at.naive_utc())),
+            })
+            .exec_without_returning(self.connection.as_ref()).await
+            .map_err(unexpected("CREATE_REPORT_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, report_id: &Uuid) -> Result<ReportDTO, HearthError> {
+        reports::Entity
+            ::find_by_id(*report_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_REPORT_ERROR"))?
+            .and_then(to_report_dto)
+            .ok_or_else(|| HearthError::not_found(REPORT_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn find_open(
+        &self,
+        reporter_id: &Uuid,
+        target_type: ReportTargetType,
+        target_id: &Uuid
+    ) -> Result<Option<ReportDTO>, HearthError> {
+        let model = reports::Entity
+            ::find()
+            .filter(reports::Column::ReporterId.eq(*reporter_id))
+            .filter(reports::Column::TargetType.eq(target_type.as_str()))
+            .filter(reports::Column::TargetId.eq(*target_id))
+            .filter(reports::Column::State.eq(ReportState::Open.as_str()))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("FIND_OPEN_REPORT_ERROR"))?;
+
+        Ok(model.and_then(to_report_dto))
+    }
+
+    async fn list(
+        &self,
+        state: ReportState,
+        cursor: Option<TimelineCursor>,
+        limit: u64
+    ) -> Result<Vec<ReportDTO>, HearthError> {
+        let mut query = reports::Entity
+            ::find()
+            .filter(reports::Column::State.eq(state.as_str()));
+
+        if let Some(cursor) = cursor {
+            let created_at = cursor.created_at.naive_utc();
+            query = query.filter(
+                Condition::any()
+                    .add(reports::Column::CreatedAt.lt(created_at))
+                    .add(
+                        Condition::all()
+                            .add(reports::Column::CreatedAt.eq(created_at))
+                            .add(reports::Column::Id.lt(cursor.id))
+                    )
+            );
+        }
+
+        let models = query
+            .order_by_desc(reports::Column::CreatedAt)
+            .order_by_desc(reports::Column::Id)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_REPORTS_ERROR"))?;
+
+        Ok(models.into_iter().filter_map(to_report_dto).collect())
+    }
+
+    async fn resolve(
+        &self,
+        report_id: &Uuid,
+        state: ReportState,
+        moderator_id: &Uuid,
+        at: DateTime<Utc>
+    ) -> Result<(), HearthError> {
+        // Guarded on the open state so two moderators can't resolve the same report.
+        let result = reports::Entity
+            ::update_many()
+            .col_expr(reports::Column::State, Expr::value(state.as_str()))
+            .col_expr(reports::Column::ResolvedBy, Expr::value(*moderator_id))
+            .col_expr(reports::Column::ResolvedAt, Expr::value(at.naive_utc()))
+            .filter(reports::Column::Id.eq(*report_id))
+            .filter(reports::Column::State.eq(ReportState::Open.as_str()))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("RESOLVE_REPORT_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::Domain(REPORT_ALREADY_RESOLVED_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+}
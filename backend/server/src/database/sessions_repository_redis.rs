@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::repositories::sessions_repository::SessionsRepository;
+use errors::HearthError;
+use redis::{AsyncCommands, Client};
+use uuid::Uuid;
+
+use crate::database::unexpected;
+
+pub struct SessionsRepositoryRedis {
+    client: Arc<Client>,
+    ttl: u64,
+}
+
+impl SessionsRepositoryRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            ttl: 60 * 60 * 24 * 30, // Sessions expire after 30 days
+        }
+    }
+
+    fn key(token: &str) -> String {
+        format!("session:{}", token)
+    }
+
+    /// Tokens issued to a user, so they can all be revoked at once.
+    fn user_key(user_id: &Uuid) -> String {
+        format!("user_sessions:{}", user_id)
+    }
+}
+
+#[async_trait]
+impl SessionsRepository for SessionsRepositoryRedis {
+    async fn create(&self, token: &str, user_id: &Uuid) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("SR_CREATE_ASYNC_CON"))?;
+
+        let user_key = Self::user_key(user_id);
+        redis::pipe()
+            .atomic()
+            .set_ex(Self::key(token), user_id.to_string(), self.ttl)
+            .ignore()
+            .sadd(&user_key, token)
+            .ignore()
+            .expire(&user_key, self.ttl as i64)
+            .ignore()
+            .query_async::<()>(&mut con)
+            .await
+            .map_err(unexpected("SR_CREATE"))
+    }
+
+    async fn get_user_id(&self, token: &str) -> Result<Option<Uuid>, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("SR_GET_ASYNC_CON"))?;
+
+        let user_id = con
+            .get::<String, Option<String>>(Self::key(token))
+            .await
+            .map_err(unexpected("SR_GET"))?;
+
+        Ok(user_id.and_then(|id| Uuid::parse_str(&id).ok()))
+    }
+
+    async fn revoke_all(&self, user_id: &Uuid) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("SR_REVOKE_ASYNC_CON"))?;
+
+        let user_key = Self::user_key(user_id);
+        let tokens = con
+            .smembers::<&str, Vec<String>>(&user_key)
+            .await
+            .map_err(unexpected("SR_REVOKE_MEMBERS"))?;
+
+        let mut keys: Vec<String> = tokens.iter().map(|token| Self::key(token)).collect();
+        keys.push(user_key);
+
+        con.del::<Vec<String>, ()>(keys)
+            .await
+            .map_err(unexpected("SR_REVOKE"))
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use domain::{
+    dtos::signup::SignupChallengeDTO,
+    repositories::signup_challenges_repository::SignupChallengesRepository,
+};
+use errors::HearthError;
+use redis::{AsyncCommands, Client};
+use uuid::Uuid;
+
+use crate::database::unexpected;
+
+/// Challenges are stored as JSON and expire with the challenge itself.
+pub struct SignupChallengesRepositoryRedis {
+    client: Arc<Client>,
+}
+
+impl SignupChallengesRepositoryRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    fn key(challenge_id: &Uuid) -> String {
+        format!("signup_challenge:{}", challenge_id)
+    }
+}
+
+#[async_trait]
+impl SignupChallengesRepository for SignupChallengesRepositoryRedis {
+    async fn create(&self, challenge: &SignupChallengeDTO) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("SCR_CREATE_ASYNC_CON"))?;
+
+        let value = serde_json::to_string(challenge).map_err(unexpected("SCR_CREATE_SERIALIZE"))?;
+        let ttl = (challenge.expires_at - Utc::now()).num_seconds().max(1) as u64;
+
+        con.set_ex::<String, String, ()>(Self::key(&challenge.challenge_id), value, ttl)
+            .await
+            .map_err(unexpected("SCR_CREATE"))
+    }
+
+    async fn take(&self, challenge_id: &Uuid) -> Result<Option<SignupChallengeDTO>, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("SCR_TAKE_ASYNC_CON"))?;
+
+        let value = con
+            .get_del::<String, Option<String>>(Self::key(challenge_id))
+            .await
+            .map_err(unexpected("SCR_TAKE"))?;
+
+        Ok(value.and_then(|value| serde_json::from_str(&value).ok()))
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::{
+    dtos::trend::TrendDTO, policies::trending::TrendingPolicy,
+    repositories::trends_repository::TrendsRepository,
+};
+use errors::HearthError;
+use redis::Client;
+use uuid::Uuid;
+
+use crate::database::unexpected;
+
+/// Seconds a union outlives the request that built it, should it fail before
+/// deleting it.
+const SCORES_TTL: i64 = 10;
+
+/// Hashtag counts live in one sorted set per time bucket. Reading the trends
+/// unions the buckets of the window into a sorted set of its own, weighting
+/// each bucket by its decay so Redis does the scoring.
+pub struct TrendsRepositoryRedis {
+    client: Arc<Client>,
+    policy: TrendingPolicy,
+}
+
+impl TrendsRepositoryRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            policy: TrendingPolicy::default(),
+        }
+    }
+
+    fn bucket_key(bucket: i64) -> String {
+        format!("trends:bucket:{}", bucket)
+    }
+
+    fn scores_key() -> String {
+        format!("trends:scores:{}", Uuid::new_v4())
+    }
+}
+
+#[async_trait]
+impl TrendsRepository for TrendsRepositoryRedis {
+    async fn record(&self, tags: &[String], at: DateTime<Utc>) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("TR_RECORD_ASYNC_CON"))?;
+
+        let key = Self::bucket_key(self.policy.bucket_of(at));
+        let mut pipe = redis::pipe();
+        for tag in tags {
+            pipe.zincr(&key, tag, 1).ignore();
+        }
+        pipe.expire(&key, self.policy.retention_seconds()).ignore();
+
+        pipe.query_async::<()>(&mut con)
+            .await
+            .map_err(unexpected("TR_RECORD"))
+    }
+
+    async fn top(&self, limit: u64, at: DateTime<Utc>) -> Result<Vec<TrendDTO>, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("TR_TOP_ASYNC_CON"))?;
+
+        let buckets = self.policy.weighted_buckets(at);
+        let keys: Vec<String> = buckets.iter().map(|(b, _)| Self::bucket_key(*b)).collect();
+        let weights: Vec<f64> = buckets.iter().map(|(_, w)| *w).collect();
+
+        let scores_key = Self::scores_key();
+        let (scores,) = redis::pipe()
+            .atomic()
+            .cmd("ZUNIONSTORE")
+            .arg(&scores_key)
+            .arg(keys.len())
+            .arg(&keys)
+            .arg("WEIGHTS")
+            .arg(&weights)
+            .ignore()
+            .expire(&scores_key, SCORES_TTL)
+            .ignore()
+            .zrevrange_withscores(&scores_key, 0, limit as isize - 1)
+            .del(&scores_key)
+            .ignore()
+            .query_async::<(Vec<(String, f64)>,)>(&mut con)
+            .await
+            .map_err(unexpected("TR_TOP"))?;
+
+        Ok(scores
+            .into_iter()
+            .map(|(tag, score)| TrendDTO { tag, score })
+            .collect())
+    }
+}
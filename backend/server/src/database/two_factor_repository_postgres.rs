@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
+use domain::{
+    dtos::two_factor::TwoFactorDTO,
+    repositories::two_factor_repository::TwoFactorRepository,
+};
+use errors::HearthError;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait,
+    Condition,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    TransactionTrait,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::database::{ entities::{ credentials, recovery_codes }, transaction_error, unexpected };
+
+/// The secret lives next to the password hash in `credentials`, recovery
+/// codes in their own table.
+pub struct TwoFactorRepositoryPostgres {
+    connection: Arc<DatabaseConnection>,
+}
+
+impl TwoFactorRepositoryPostgres {
+    pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for TwoFactorRepositoryPostgres {
+    async fn get(&self, user_id: &Uuid) -> Result<Option<TwoFactorDTO>, HearthError> {
+        let model = credentials::Entity
+            ::find()
+            .filter(credentials::Column::UserId.eq(user_id.to_string()))
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_TWO_FACTOR_ERROR"))?;
+
+        Ok(
+            model.and_then(|m| {
+                m.totp_secret.map(|encrypted_secret| TwoFactorDTO {
+                    user_id: *user_id,
+                    encrypted_secret,
+                    enabled_at: m.totp_enabled_at.map(|at| at.and_utc()),
+                    last_step: m.totp_last_step,
+                })
+            })
+        )
+    }
+
+    async fn save_pending(&self, user_id: &Uuid, encrypted_secret: Vec<u8>) -> Result<(), HearthError> {
+        credentials::Entity
+            ::update_many()
+            .col_expr(credentials::Column::TotpSecret, Expr::value(Some(encrypted_secret)))
+            .col_expr(credentials::Column::TotpEnabledAt, Expr::value(None::<chrono::NaiveDateTime>))
+            .col_expr(credentials::Column::TotpLastStep, Expr::value(None::<i64>))
+            .col_expr(credentials::Column::UpdatedAt, Expr::current_timestamp())
+            .filter(credentials::Column::UserId.eq(user_id.to_string()))
+            .filter(credentials::Column::TotpEnabledAt.is_null())
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("SAVE_PENDING_TWO_FACTOR_ERROR"))?;
+
+        Ok(())
+    }
+
+    async fn enable(
+        &self,
+        user_id: &Uuid,
+        step: i64,
+        recovery_code_hashes: Vec<String>,
+        at: DateTime<Utc>
+    ) -> Result<(), HearthError> {
+        let user_id = *user_id;
+
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    credentials::Entity
+                        ::update_many()
+                        .col_expr(credentials::Column::TotpEnabledAt, Expr::value(at.naive_utc()))
+                        .col_expr(credentials::Column::TotpLastStep, Expr::value(step))
+                        .col_expr(credentials::Column::UpdatedAt, Expr::current_timestamp())
+                        .filter(credentials::Column::UserId.eq(user_id.to_string()))
+                        .exec(transaction).await
+                        .map_err(unexpected("ENABLE_TWO_FACTOR_ERROR"))?;
+
+                    recovery_codes::Entity
+                        ::delete_many()
+                        .filter(recovery_codes::Column::UserId.eq(user_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("DELETE_RECOVERY_CODES_ERROR"))?;
+
+                    recovery_codes::Entity
+                        ::insert_many(
+                            recovery_code_hashes.into_iter().map(|code_hash| recovery_codes::ActiveModel {
+                                id: Set(Uuid::new_v4()),
+                                user_id: Set(user_id),
+                                code_hash: Set(code_hash),
+                                used_at: Set(None),
+                                created_at: Set(at.naive_utc()),
+                            })
+                        )
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("CREATE_RECOVERY_CODES_ERROR"))?;
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn record_step(&self, user_id: &Uuid, step: i64) -> Result<bool, HearthError> {
+        // Conditional update, only one of two concurrent logins moves the step.
+        let result = credentials::Entity
+            ::update_many()
+            .col_expr(credentials::Column::TotpLastStep, Expr::value(step))
+            .filter(credentials::Column::UserId.eq(user_id.to_string()))
+            .filter(
+                Condition::any()
+                    .add(credentials::Column::TotpLastStep.is_null())
+                    .add(credentials::Column::TotpLastStep.lt(step))
+            )
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("RECORD_TWO_FACTOR_STEP_ERROR"))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn use_recovery_code(
+        &self,
+        user_id: &Uuid,
+        code_hash: &str,
+        at: DateTime<Utc>
+    ) -> Result<bool, HearthError> {
+        let result = recovery_codes::Entity
+            ::update_many()
+            .col_expr(recovery_codes::Column::UsedAt, Expr::value(at.naive_utc()))
+            .filter(recovery_codes::Column::UserId.eq(*user_id))
+            .filter(recovery_codes::Column::CodeHash.eq(code_hash))
+            .filter(recovery_codes::Column::UsedAt.is_null())
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("USE_RECOVERY_CODE_ERROR"))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn disable(&self, user_id: &Uuid) -> Result<(), HearthError> {
+        let user_id = *user_id;
+
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    credentials::Entity
+                        ::update_many()
+                        .col_expr(credentials::Column::TotpSecret, Expr::value(None::<Vec<u8>>))
+                        .col_expr(
+                            credentials::Column::TotpEnabledAt,
+                            Expr::value(None::<chrono::NaiveDateTime>)
+                        )
+                        .col_expr(credentials::Column::TotpLastStep, Expr::value(None::<i64>))
+                        .col_expr(credentials::Column::UpdatedAt, Expr::current_timestamp())
+                        .filter(credentials::Column::UserId.eq(user_id.to_string()))
+                        .exec(transaction).await
+                        .map_err(unexpected("DISABLE_TWO_FACTOR_ERROR"))?;
+
+                    recovery_codes::Entity
+                        ::delete_many()
+                        .filter(recovery_codes::Column::UserId.eq(user_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("DELETE_RECOVERY_CODES_ERROR"))?;
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+}
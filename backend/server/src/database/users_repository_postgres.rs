@@ -1,20 +1,54 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{ DateTime, Utc };
 use domain::{
-    dtos::{ auth::CredentialsDTO, user::{ CreateUserDTO, UserDTO } },
+    dtos::{
+        auth::CredentialsDTO,
+        event::EventDTO,
+        user::{ CreateUserDTO, Role, UserDTO, UserStatus },
+    },
+    error_codes::USER_NOT_FOUND_ERROR_CODE,
     repositories::users_repository::UsersRepository,
 };
 use errors::HearthError;
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::Set,
+    ColumnTrait,
+    Condition,
     DatabaseConnection,
+    EntityTrait,
+    PaginatorTrait,
+    QueryFilter,
+    QueryOrder,
+    QuerySelect,
     TransactionError,
     TransactionTrait,
+    sea_query::{ BinOper, Expr, ExprTrait, Func, OnConflict, Query },
 };
+use uuid::Uuid;
 
-use crate::database::entities::{ credentials, users };
+use crate::database::{
+    entities::{
+        blocks,
+        credentials,
+        email_verified,
+        held_usernames,
+        messages,
+        notifications,
+        poll_voters,
+        polls,
+        post_hashtags,
+        post_link_previews,
+        post_mentions,
+        posts,
+        users,
+    },
+    outbox_repository_postgres::append_events,
+    transaction_error,
+    unexpected,
+};
 
 pub struct UsersRepositoryPostgres {
     connection: Arc<DatabaseConnection>,
@@ -28,10 +62,11 @@ impl UsersRepositoryPostgres {
 
 #[async_trait]
 impl UsersRepository for UsersRepositoryPostgres {
-    async fn create(
+    async fn create_with_events(
         &self,
         dto: CreateUserDTO,
-        credentials_dto: CredentialsDTO
+        credentials_dto: CredentialsDTO,
+        events: Vec<EventDTO>
     ) -> Result<(), HearthError> {
         self.connection
             .transaction::<_, (), HearthError>(|transaction| {
@@ -62,6 +97,8 @@ impl UsersRepository for UsersRepositoryPostgres {
                             )
                         )?;
 
+                    append_events(transaction, events).await?;
+
                     Ok(())
                 })
             }).await
@@ -80,15 +117,371 @@ impl UsersRepository for UsersRepositoryPostgres {
     }
 
     async fn get(&self, user_id: String) -> Result<UserDTO, HearthError> {
-        todo!();
+        let user_id = uuid::Uuid
+            ::parse_str(&user_id)
+            .map_err(|_| HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()))?;
+
+        users::Entity
+            ::find_by_id(user_id)
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_USER_ERROR"))?
+            .map(to_user_dto)
+            .ok_or_else(|| HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()))
+    }
+
+    async fn get_by_email(&self, email: &str) -> Result<Option<UserDTO>, HearthError> {
+        let model = users::Entity
+            ::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(users::Column::Email))).binary(
+                    BinOper::Equal,
+                    email.to_lowercase()
+                )
+            )
+            .one(self.connection.as_ref()).await
+            .map_err(unexpected("GET_USER_BY_EMAIL_ERROR"))?;
+
+        Ok(model.map(to_user_dto))
+    }
+
+    async fn get_by_usernames(&self, usernames: &[String]) -> Result<Vec<UserDTO>, HearthError> {
+        let lowercased: Vec<String> = usernames
+            .iter()
+            .map(|u| u.to_lowercase())
+            .collect();
+
+        let models = users::Entity
+            ::find()
+            .filter(Expr::expr(Func::lower(Expr::col(users::Column::Username))).is_in(lowercased))
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("GET_USERS_BY_USERNAMES_ERROR"))?;
+
+        Ok(models.into_iter().map(to_user_dto).collect())
     }
 
     async fn email_exists(&self, email: &String) -> Result<bool, HearthError> {
-        todo!();
+        let count = users::Entity
+            ::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(users::Column::Email))).binary(
+                    BinOper::Equal,
+                    email.to_lowercase()
+                )
+            )
+            .count(self.connection.as_ref()).await
+            .map_err(unexpected("EMAIL_EXISTS_ERROR"))?;
+
+        Ok(count > 0)
     }
 
     async fn username_exists(&self, username: &String) -> Result<bool, HearthError> {
-        todo!();
+        let count = users::Entity
+            ::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(users::Column::Username))).eq(username.to_lowercase())
+            )
+            .count(self.connection.as_ref()).await
+            .map_err(unexpected("USERNAME_EXISTS_ERROR"))?;
+        if count > 0 {
+            return Ok(true);
+        }
+
+        let held = held_usernames::Entity
+            ::find_by_id(username.to_lowercase())
+            .filter(held_usernames::Column::HeldUntil.gt(Utc::now().naive_utc()))
+            .count(self.connection.as_ref()).await
+            .map_err(unexpected("USERNAME_HELD_ERROR"))?;
+
+        Ok(held > 0)
+    }
+
+    async fn set_avatar(&self, user_id: &Uuid, media_id: Option<Uuid>) -> Result<(), HearthError> {
+        let result = users::Entity
+            ::update_many()
+            .col_expr(users::Column::AvatarMediaId, Expr::value(media_id))
+            .col_expr(users::Column::UpdatedAt, Expr::current_timestamp())
+            .filter(users::Column::Id.eq(*user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("SET_AVATAR_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn set_email_verified_with_events(
+        &self,
+        user_id: &Uuid,
+        events: Vec<EventDTO>
+    ) -> Result<(), HearthError> {
+        let user_id = *user_id;
+
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    let result = users::Entity
+                        ::update_many()
+                        .col_expr(users::Column::IsVerified, Expr::value(true))
+                        .col_expr(users::Column::UpdatedAt, Expr::current_timestamp())
+                        .filter(users::Column::Id.eq(user_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("SET_EMAIL_VERIFIED_ERROR"))?;
+
+                    if result.rows_affected == 0 {
+                        return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+                    }
+
+                    append_events(transaction, events).await
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+
+    async fn set_role(&self, user_id: &Uuid, role: Role) -> Result<(), HearthError> {
+        let result = users::Entity
+            ::update_many()
+            .col_expr(users::Column::Role, Expr::value(role.as_str()))
+            .col_expr(users::Column::UpdatedAt, Expr::current_timestamp())
+            .filter(users::Column::Id.eq(*user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("SET_ROLE_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn set_status(
+        &self,
+        user_id: &Uuid,
+        status: UserStatus,
+        suspended_until: Option<DateTime<Utc>>
+    ) -> Result<(), HearthError> {
+        let result = users::Entity
+            ::update_many()
+            .col_expr(users::Column::Status, Expr::value(status.as_str()))
+            .col_expr(
+                users::Column::SuspendedUntil,
+                Expr::value(suspended_until.map(|until| until.naive_utc()))
+            )
+            .col_expr(users::Column::UpdatedAt, Expr::current_timestamp())
+            .filter(users::Column::Id.eq(*user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("SET_STATUS_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn set_deactivated(
+        &self,
+        user_id: &Uuid,
+        deactivated_at: Option<DateTime<Utc>>
+    ) -> Result<(), HearthError> {
+        // Cancelling the deletion of an account suspended in the meantime keeps it suspended.
+        let status = match deactivated_at {
+            Some(_) => Expr::value(UserStatus::Deactivated.as_str()),
+            None =>
+                Expr::case(
+                    users::Column::Status.eq(UserStatus::Deactivated.as_str()),
+                    UserStatus::Active.as_str()
+                )
+                    .finally(Expr::col(users::Column::Status))
+                    .into(),
+        };
+
+        let result = users::Entity
+            ::update_many()
+            .col_expr(users::Column::Status, status)
+            .col_expr(
+                users::Column::DeactivatedAt,
+                Expr::value(deactivated_at.map(|at| at.naive_utc()))
+            )
+            .col_expr(users::Column::UpdatedAt, Expr::current_timestamp())
+            .filter(users::Column::Id.eq(*user_id))
+            .exec(self.connection.as_ref()).await
+            .map_err(unexpected("SET_DEACTIVATED_ERROR"))?;
+
+        if result.rows_affected == 0 {
+            return Err(HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()));
+        }
+
+        Ok(())
+    }
+
+    async fn list_deactivated_before(
+        &self,
+        before: DateTime<Utc>,
+        limit: u64
+    ) -> Result<Vec<UserDTO>, HearthError> {
+        let models = users::Entity
+            ::find()
+            .filter(users::Column::DeactivatedAt.lt(before.naive_utc()))
+            .order_by_asc(users::Column::DeactivatedAt)
+            .limit(limit)
+            .all(self.connection.as_ref()).await
+            .map_err(unexpected("LIST_DEACTIVATED_USERS_ERROR"))?;
+
+        Ok(models.into_iter().map(to_user_dto).collect())
+    }
+
+    async fn purge(
+        &self,
+        user_id: &Uuid,
+        username_held_until: DateTime<Utc>
+    ) -> Result<(), HearthError> {
+        let user_id = *user_id;
+
+        self.connection
+            .transaction::<_, (), HearthError>(|transaction| {
+                Box::pin(async move {
+                    let user = users::Entity
+                        ::find_by_id(user_id)
+                        .one(transaction).await
+                        .map_err(unexpected("PURGE_USER_ERROR"))?
+                        .ok_or_else(|| HearthError::not_found(USER_NOT_FOUND_ERROR_CODE.into()))?;
+
+                    // Tombstones, the other participants still see a message was there.
+                    // Its media go away with the account.
+                    messages::Entity
+                        ::update_many()
+                        .col_expr(messages::Column::Content, Expr::value(""))
+                        .filter(messages::Column::SenderId.eq(user_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_MESSAGES_ERROR"))?;
+
+                    // Posts are tombstones as well so threads keep their replies, what was
+                    // attached to them goes. Their author has no foreign key, like senders.
+                    let authored = Query::select()
+                        .column(posts::Column::Id)
+                        .from(posts::Entity)
+                        .and_where(posts::Column::AuthorId.eq(user_id))
+                        .to_owned();
+
+                    posts::Entity
+                        ::update_many()
+                        .col_expr(posts::Column::Content, Expr::value(""))
+                        .col_expr(posts::Column::Sensitive, Expr::value(false))
+                        .filter(posts::Column::AuthorId.eq(user_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_POSTS_ERROR"))?;
+
+                    post_hashtags::Entity
+                        ::delete_many()
+                        .filter(post_hashtags::Column::PostId.in_subquery(authored.clone()))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_POST_HASHTAGS_ERROR"))?;
+
+                    post_link_previews::Entity
+                        ::delete_many()
+                        .filter(post_link_previews::Column::PostId.in_subquery(authored.clone()))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_POST_LINK_PREVIEWS_ERROR"))?;
+
+                    // Options and votes cascade.
+                    polls::Entity
+                        ::delete_many()
+                        .filter(polls::Column::PostId.in_subquery(authored.clone()))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_POLLS_ERROR"))?;
+
+                    // Mentions made in those posts and of the account elsewhere.
+                    post_mentions::Entity
+                        ::delete_many()
+                        .filter(
+                            Condition::any()
+                                .add(post_mentions::Column::PostId.in_subquery(authored))
+                                .add(post_mentions::Column::UserId.eq(user_id))
+                        )
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_MENTIONS_ERROR"))?;
+
+                    // Tallies are kept on the polls, only who voted goes.
+                    poll_voters::Entity
+                        ::delete_many()
+                        .filter(poll_voters::Column::UserId.eq(user_id))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_POLL_VOTERS_ERROR"))?;
+
+                    notifications::Entity
+                        ::delete_many()
+                        .filter(
+                            Condition::any()
+                                .add(notifications::Column::RecipientId.eq(user_id))
+                                .add(notifications::Column::ActorId.eq(user_id))
+                        )
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_NOTIFICATIONS_ERROR"))?;
+
+                    blocks::Entity
+                        ::delete_many()
+                        .filter(
+                            Condition::any()
+                                .add(blocks::Column::BlockerId.eq(user_id))
+                                .add(blocks::Column::BlockedId.eq(user_id))
+                        )
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_BLOCKS_ERROR"))?;
+
+                    credentials::Entity
+                        ::delete_many()
+                        .filter(credentials::Column::UserId.eq(user_id.to_string()))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_CREDENTIALS_ERROR"))?;
+
+                    email_verified::Entity
+                        ::delete_many()
+                        .filter(email_verified::Column::Email.eq(user.email.clone()))
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_EMAIL_VERIFIED_ERROR"))?;
+
+                    held_usernames::Entity
+                        ::insert(held_usernames::ActiveModel {
+                            username: Set(user.username.to_lowercase()),
+                            held_until: Set(username_held_until.naive_utc()),
+                        })
+                        .on_conflict(
+                            OnConflict::column(held_usernames::Column::Username)
+                                .update_column(held_usernames::Column::HeldUntil)
+                                .to_owned()
+                        )
+                        .exec_without_returning(transaction).await
+                        .map_err(unexpected("HOLD_USERNAME_ERROR"))?;
+
+                    // Everything else the account owned cascades.
+                    users::Entity
+                        ::delete_by_id(user_id)
+                        .exec(transaction).await
+                        .map_err(unexpected("PURGE_USER_ERROR"))?;
+
+                    Ok(())
+                })
+            }).await
+            .map_err(transaction_error)
+    }
+}
+
+fn to_user_dto(model: users::Model) -> UserDTO {
+    UserDTO {
+        user_id: model.id,
+        username: model.username,
+        email: model.email,
+        email_verified: model.is_verified,
+        birthday: model.birthday,
+        avatar_media_id: model.avatar_media_id,
+        role: Role::parse(&model.role).unwrap_or_default(),
+        status: UserStatus::parse(&model.status).unwrap_or_default(),
+        suspended_until: model.suspended_until.map(|until| until.and_utc()),
+        deactivated_at: model.deactivated_at.map(|at| at.and_utc()),
+        created_at: model.created_at.and_utc(),
+        updated_at: model.updated_at.and_utc(),
     }
 }
 
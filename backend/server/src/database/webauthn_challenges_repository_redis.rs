@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::passkey::WebAuthnChallengeDTO,
+    repositories::webauthn_challenges_repository::WebAuthnChallengesRepository,
+};
+use errors::HearthError;
+use redis::{AsyncCommands, Client};
+
+use crate::database::unexpected;
+
+/// Challenges are stored as JSON, keyed by the challenge itself.
+pub struct WebAuthnChallengesRepositoryRedis {
+    client: Arc<Client>,
+}
+
+impl WebAuthnChallengesRepositoryRedis {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    fn key(challenge: &str) -> String {
+        format!("webauthn_challenge:{}", challenge)
+    }
+}
+
+#[async_trait]
+impl WebAuthnChallengesRepository for WebAuthnChallengesRepositoryRedis {
+    async fn create(&self, challenge: &WebAuthnChallengeDTO, ttl_seconds: u64) -> Result<(), HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("WCR_CREATE_ASYNC_CON"))?;
+
+        let value = serde_json::to_string(challenge).map_err(unexpected("WCR_CREATE_SERIALIZE"))?;
+
+        con.set_ex::<String, String, ()>(Self::key(&challenge.challenge), value, ttl_seconds)
+            .await
+            .map_err(unexpected("WCR_CREATE"))
+    }
+
+    async fn take(&self, challenge: &str) -> Result<Option<WebAuthnChallengeDTO>, HearthError> {
+        let mut con = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(unexpected("WCR_TAKE_ASYNC_CON"))?;
+
+        let value = con
+            .get_del::<String, Option<String>>(Self::key(challenge))
+            .await
+            .map_err(unexpected("WCR_TAKE"))?;
+
+        Ok(value.and_then(|value| serde_json::from_str(&value).ok()))
+    }
+}
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use domain::{
+    dtos::media::{ ProcessedFileDTO, ProcessedMediaDTO },
+    error_codes::{ MEDIA_TYPE_NOT_ALLOWED_ERROR_CODE, MEDIA_UNREADABLE_ERROR_CODE },
+    repositories::media_processor::MediaProcessor,
+};
+use errors::HearthError;
+use media::{ MediaError, process_image, sniff_mime_type };
+
+use crate::database::unexpected;
+
+/// Adapter over the `media` crate. Decoding is CPU bound so it runs on the
+/// blocking thread pool.
+pub struct ImageMediaProcessor;
+
+#[async_trait]
+impl MediaProcessor for ImageMediaProcessor {
+    fn sniff_mime_type(&self, bytes: &[u8]) -> Option<String> {
+        sniff_mime_type(bytes).map(String::from)
+    }
+
+    async fn process(&self, bytes: Vec<u8>) -> Result<ProcessedMediaDTO, HearthError> {
+        let processed = tokio::task
+            ::spawn_blocking(move || process_image(&bytes)).await
+            .map_err(unexpected("MEDIA_PROCESSING_ERROR"))?
+            .map_err(|e| {
+                match e {
+                    MediaError::UnsupportedType =>
+                        HearthError::Domain(MEDIA_TYPE_NOT_ALLOWED_ERROR_CODE.into()),
+                    MediaError::Decode(_) => HearthError::Domain(MEDIA_UNREADABLE_ERROR_CODE.into()),
+                    MediaError::Encode(reason) =>
+                        HearthError::unexpected("MEDIA_PROCESSING_ERROR".into(), Some(reason)),
+                }
+            })?;
+
+        Ok(ProcessedMediaDTO {
+            files: processed.files
+                .into_iter()
+                .map(|file| ProcessedFileDTO {
+                    variant: file.variant.into(),
+                    mime_type: file.mime_type.into(),
+                    width: file.width,
+                    height: file.height,
+                    bytes: file.bytes,
+                })
+                .collect(),
+            blurhash: processed.blurhash,
+        })
+    }
+}
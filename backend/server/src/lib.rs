@@ -1,5 +1,17 @@
+pub mod aes_secret_cipher;
+pub mod archive_reader;
+pub mod auth;
 pub mod bootstrap;
+pub mod config;
 pub mod connector;
 pub mod database;
+pub mod image_media_processor;
+pub mod link_unfurler;
+pub mod oidc_client;
+pub mod rate_limit;
+pub mod realtime;
 pub mod routes;
+pub mod scheduler;
 pub mod server;
+pub mod storage;
+pub mod zip_archive_writer;
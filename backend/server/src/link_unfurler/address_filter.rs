@@ -0,0 +1,115 @@
+use std::net::{ IpAddr, Ipv4Addr, Ipv6Addr };
+
+/// Whether `ip` is reachable on the public internet. Everything else (loopback,
+/// private, link-local, CGNAT, multicast, documentation...) is refused so that
+/// link previews can't be used to probe the internal network.
+pub(crate) fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => is_public_v6(ip),
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    !(
+        a == 0 ||
+        ip.is_loopback() ||
+        ip.is_private() ||
+        ip.is_link_local() ||
+        ip.is_broadcast() ||
+        ip.is_documentation() ||
+        ip.is_multicast() ||
+        // Shared address space (CGNAT) 100.64.0.0/10
+        (a == 100 && (b & 0b1100_0000) == 64) ||
+        // IETF protocol assignments 192.0.0.0/24
+        (a == 192 && b == 0 && c == 0) ||
+        // Benchmarking 198.18.0.0/15
+        (a == 198 && (b & 0xfe) == 18) ||
+        // Reserved 240.0.0.0/4
+        a >= 240
+    )
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    // Addresses embedding an IPv4 are judged on the embedded address.
+    if let Some(embedded) = embedded_v4(ip) {
+        return is_public_v4(embedded);
+    }
+
+    let segments = ip.segments();
+
+    !(
+        ip.is_unspecified() ||
+        ip.is_loopback() ||
+        ip.is_multicast() ||
+        // Unique local fc00::/7
+        (segments[0] & 0xfe00) == 0xfc00 ||
+        // Link-local fe80::/10
+        (segments[0] & 0xffc0) == 0xfe80 ||
+        // Deprecated site-local fec0::/10
+        (segments[0] & 0xffc0) == 0xfec0 ||
+        // Documentation 2001:db8::/32
+        (segments[0] == 0x2001 && segments[1] == 0x0db8)
+    )
+}
+
+/// IPv4-mapped (`::ffff:a.b.c.d`), IPv4-compatible (`::a.b.c.d`), NAT64
+/// (`64:ff9b::a.b.c.d`) and 6to4 (`2002:aabb:ccdd::`) addresses.
+fn embedded_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = ip.segments();
+    let tail = |high: u16, low: u16| Ipv4Addr::from(((high as u32) << 16) | (low as u32));
+
+    match segments {
+        [0, 0, 0, 0, 0, 0xffff, high, low] => Some(tail(high, low)),
+        [0, 0, 0, 0, 0, 0, high, low] if high != 0 => Some(tail(high, low)),
+        [0x64, 0xff9b, 0, 0, 0, 0, high, low] => Some(tail(high, low)),
+        [0x2002, high, low, ..] => Some(tail(high, low)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::is_public;
+
+    fn public(ip: &str) -> bool {
+        is_public(ip.parse::<IpAddr>().unwrap())
+    }
+
+    #[test]
+    fn should_refuse_non_public_addresses() {
+        for ip in [
+            "0.0.0.0",
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "198.18.0.1",
+            "224.0.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "2002:c0a8:0101::1",
+        ] {
+            assert!(!public(ip), "{} should be refused", ip);
+        }
+    }
+
+    #[test]
+    fn should_accept_public_addresses() {
+        for ip in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111", "::ffff:8.8.8.8"] {
+            assert!(public(ip), "{} should be accepted", ip);
+        }
+    }
+}
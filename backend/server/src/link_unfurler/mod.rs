@@ -0,0 +1,297 @@
+mod address_filter;
+pub(crate) mod open_graph;
+
+use std::{ net::{ IpAddr, SocketAddr }, time::Duration };
+
+use async_trait::async_trait;
+use chrono::Utc;
+use domain::{
+    dtos::link_preview::LinkPreviewDTO,
+    error_codes::{ LINK_PREVIEW_BLOCKED_ERROR_CODE, LINK_PREVIEW_UNAVAILABLE_ERROR_CODE },
+    repositories::link_unfurler::LinkUnfurler,
+};
+use errors::HearthError;
+use reqwest::{ Client, Url, header, redirect };
+
+use crate::link_unfurler::{ address_filter::is_public, open_graph::parse_metadata };
+
+const USER_AGENT: &str = "HearthBot/0.1 (link previews)";
+
+#[derive(Debug, Clone)]
+pub struct UnfurlerConfig {
+    /// Budget for the whole unfurl, redirects included.
+    pub timeout: Duration,
+    /// Bytes of the body read at most, metadata sits in the `<head>` anyway.
+    pub max_body_bytes: usize,
+    pub max_redirects: usize,
+    /// Only meant for tests running against a local stand-in server.
+    pub allow_private_networks: bool,
+}
+
+impl Default for UnfurlerConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            max_body_bytes: 512 * 1024,
+            max_redirects: 3,
+            allow_private_networks: false,
+        }
+    }
+}
+
+/// Fetches pages over HTTP(S) and reads their OpenGraph metadata.
+///
+/// Every hop resolves the host once, checks all of its addresses and then pins
+/// the connection to the checked address, so DNS rebinding can't swap in a
+/// private address between the check and the request. Redirects are followed
+/// by hand for the same reason.
+pub struct HttpLinkUnfurler {
+    config: UnfurlerConfig,
+}
+
+impl HttpLinkUnfurler {
+    pub fn new(config: UnfurlerConfig) -> Self {
+        Self { config }
+    }
+
+    async fn fetch(&self, url: &str) -> Result<(Url, String), HearthError> {
+        let mut url = Url::parse(url).map_err(|_| blocked())?;
+
+        for _ in 0..=self.config.max_redirects {
+            let address = self.resolve(&url).await?;
+            let host = url.host_str().ok_or_else(blocked)?.to_string();
+
+            let client = Client::builder()
+                .redirect(redirect::Policy::none())
+                .no_proxy()
+                .user_agent(USER_AGENT)
+                .timeout(self.config.timeout)
+                .resolve(&host, address)
+                .build()
+                .map_err(|_| unavailable())?;
+
+            let mut response = client
+                .get(url.clone())
+                .header(header::ACCEPT, "text/html,application/xhtml+xml")
+                .send().await
+                .map_err(|_| unavailable())?;
+
+            if response.status().is_redirection() {
+                let location = response
+                    .headers()
+                    .get(header::LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .ok_or_else(unavailable)?;
+                url = url.join(location).map_err(|_| blocked())?;
+                continue;
+            }
+
+            let is_html = response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|content_type| content_type.to_str().ok())
+                .is_some_and(|content_type| {
+                    let content_type = content_type.to_ascii_lowercase();
+                    content_type.starts_with("text/html") ||
+                        content_type.starts_with("application/xhtml+xml")
+                });
+            if !response.status().is_success() || !is_html {
+                return Err(unavailable());
+            }
+
+            let mut body = vec![];
+            while let Some(chunk) = response.chunk().await.map_err(|_| unavailable())? {
+                let remaining = self.config.max_body_bytes - body.len();
+                body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+                if body.len() >= self.config.max_body_bytes {
+                    break;
+                }
+            }
+
+            return Ok((url, String::from_utf8_lossy(&body).into_owned()));
+        }
+
+        Err(unavailable())
+    }
+
+    /// Picks the address to connect to, refusing hosts that resolve to any
+    /// non public address.
+    async fn resolve(&self, url: &Url) -> Result<SocketAddr, HearthError> {
+        if !matches!(url.scheme(), "http" | "https") || !url.username().is_empty() {
+            return Err(blocked());
+        }
+
+        let host = url.host_str().ok_or_else(blocked)?;
+        let port = url.port_or_known_default().ok_or_else(blocked)?;
+
+        let addresses: Vec<SocketAddr> = match host.trim_matches(['[', ']']).parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) =>
+                tokio::net
+                    ::lookup_host((host, port)).await
+                    .map_err(|_| unavailable())?
+                    .collect(),
+        };
+
+        if addresses.is_empty() {
+            return Err(unavailable());
+        }
+
+        if !self.config.allow_private_networks && addresses.iter().any(|a| !is_public(a.ip())) {
+            return Err(blocked());
+        }
+
+        Ok(addresses[0])
+    }
+}
+
+#[async_trait]
+impl LinkUnfurler for HttpLinkUnfurler {
+    async fn unfurl(&self, url: &str) -> Result<LinkPreviewDTO, HearthError> {
+        let (final_url, html) = tokio::time
+            ::timeout(self.config.timeout, self.fetch(url)).await
+            .map_err(|_| unavailable())??;
+
+        let metadata = parse_metadata(&html, &final_url);
+
+        Ok(LinkPreviewDTO {
+            url: url.into(),
+            title: metadata.title,
+            description: metadata.description,
+            image_url: metadata.image_url,
+            site_name: metadata.site_name,
+            fetched_at: Utc::now(),
+        })
+    }
+}
+
+fn blocked() -> HearthError {
+    HearthError::Domain(LINK_PREVIEW_BLOCKED_ERROR_CODE.into())
+}
+
+fn unavailable() -> HearthError {
+    HearthError::Domain(LINK_PREVIEW_UNAVAILABLE_ERROR_CODE.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use actix_web::{ App, HttpResponse, HttpServer, web };
+    use domain::{
+        error_codes::{ LINK_PREVIEW_BLOCKED_ERROR_CODE, LINK_PREVIEW_UNAVAILABLE_ERROR_CODE },
+        repositories::link_unfurler::LinkUnfurler,
+    };
+    use errors::HearthError;
+
+    use super::{ HttpLinkUnfurler, UnfurlerConfig };
+
+    const PAGE: &str =
+        r#"<html><head><meta property="og:title" content="Stand-in"><meta property="og:image" content="/cover.png"></head></html>"#;
+
+    /// Serves a few canned pages on a random local port.
+    fn stand_in() -> String {
+        let server = HttpServer::new(|| {
+            App::new()
+                .route(
+                    "/page",
+                    web::get().to(|| async {
+                        HttpResponse::Ok().content_type("text/html; charset=utf-8").body(PAGE)
+                    })
+                )
+                .route(
+                    "/redirect",
+                    web::get().to(|| async {
+                        HttpResponse::Found().insert_header(("location", "/page")).finish()
+                    })
+                )
+                .route(
+                    "/loop",
+                    web::get().to(|| async {
+                        HttpResponse::Found().insert_header(("location", "/loop")).finish()
+                    })
+                )
+                .route(
+                    "/huge",
+                    web::get().to(|| async {
+                        let body = format!("{}{}", " ".repeat(64 * 1024), PAGE);
+                        HttpResponse::Ok().content_type("text/html").body(body)
+                    })
+                )
+                .route(
+                    "/slow",
+                    web::get().to(|| async {
+                        actix_web::rt::time::sleep(Duration::from_secs(2)).await;
+                        HttpResponse::Ok().content_type("text/html").body(PAGE)
+                    })
+                )
+                .route(
+                    "/image",
+                    web::get().to(|| async {
+                        HttpResponse::Ok().content_type("image/png").body("png")
+                    })
+                )
+        })
+            .workers(1)
+            .bind(("127.0.0.1", 0))
+            .unwrap();
+        let address = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+
+        format!("http://{}", address)
+    }
+
+    fn local() -> HttpLinkUnfurler {
+        HttpLinkUnfurler::new(UnfurlerConfig {
+            timeout: Duration::from_millis(500),
+            max_body_bytes: 16 * 1024,
+            allow_private_networks: true,
+            ..Default::default()
+        })
+    }
+
+    #[actix_web::test]
+    async fn should_unfurl_pages_and_follow_redirects() {
+        let base = stand_in();
+        let url = format!("{}/redirect", base);
+
+        let preview = local().unfurl(&url).await.unwrap();
+
+        assert_eq!(preview.url, url);
+        assert_eq!(preview.title.as_deref(), Some("Stand-in"));
+        assert_eq!(preview.image_url, Some(format!("{}/cover.png", base)));
+    }
+
+    #[actix_web::test]
+    async fn should_refuse_private_addresses() {
+        let base = stand_in();
+        let unfurler = HttpLinkUnfurler::new(UnfurlerConfig::default());
+        let blocked = HearthError::Domain(LINK_PREVIEW_BLOCKED_ERROR_CODE.into());
+
+        assert_eq!(unfurler.unfurl(&format!("{}/page", base)).await.unwrap_err(), blocked);
+        assert_eq!(
+            unfurler.unfurl(&base.replace("127.0.0.1", "localhost")).await.unwrap_err(),
+            blocked
+        );
+        assert_eq!(unfurler.unfurl("http://[::ffff:7f00:1]/").await.unwrap_err(), blocked);
+        assert_eq!(unfurler.unfurl("file:///etc/passwd").await.unwrap_err(), blocked);
+    }
+
+    #[actix_web::test]
+    async fn should_cap_body_size_timeouts_and_redirects() {
+        let base = stand_in();
+        let unfurler = local();
+        let unavailable = HearthError::Domain(LINK_PREVIEW_UNAVAILABLE_ERROR_CODE.into());
+
+        // The metadata sits past the cap, so it is never read.
+        let preview = unfurler.unfurl(&format!("{}/huge", base)).await.unwrap();
+        assert_eq!(preview.title, None);
+
+        for path in ["/slow", "/loop", "/image"] {
+            assert_eq!(
+                unfurler.unfurl(&format!("{}{}", base, path)).await.unwrap_err(),
+                unavailable
+            );
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use reqwest::Url;
+
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_DESCRIPTION_CHARS: usize = 500;
+const MAX_SITE_NAME_CHARS: usize = 100;
+
+/// Metadata read from the `<head>` of a page.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PageMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
+}
+
+/// Reads OpenGraph tags, falling back to Twitter cards and then to the plain
+/// `<title>` / `<meta name="description">`. Relative image urls are resolved
+/// against `base`, the url the page was finally served from.
+pub(crate) fn parse_metadata(html: &str, base: &Url) -> PageMetadata {
+    let mut tags: Vec<(String, String)> = vec![];
+    let lower = html.to_ascii_lowercase();
+    let mut position = 0;
+
+    while let Some(offset) = lower[position..].find("<meta") {
+        let start = position + offset + "<meta".len();
+        let Some(length) = tag_end(&html[start..]) else {
+            break;
+        };
+
+        let attributes = parse_attributes(&html[start..start + length]);
+        let key = attribute(&attributes, "property").or_else(|| attribute(&attributes, "name"));
+        if let (Some(key), Some(content)) = (key, attribute(&attributes, "content")) {
+            tags.push((key.to_ascii_lowercase(), content.to_string()));
+        }
+
+        position = start + length;
+    }
+
+    let find = |keys: &[&str]| {
+        keys.iter().find_map(|key| {
+            tags.iter()
+                .find(|(name, content)| name == key && !content.trim().is_empty())
+                .map(|(_, content)| content.clone())
+        })
+    };
+
+    PageMetadata {
+        title: find(&["og:title", "twitter:title"])
+            .or_else(|| title_element(html, &lower))
+            .and_then(|title| clean(&title, MAX_TITLE_CHARS)),
+        description: find(&["og:description", "twitter:description", "description"]).and_then(
+            |description| clean(&description, MAX_DESCRIPTION_CHARS)
+        ),
+        image_url: find(&["og:image:secure_url", "og:image", "twitter:image"]).and_then(|image| {
+            let url = base.join(decode_entities(image.trim()).as_str()).ok()?;
+            matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+        }),
+        site_name: find(&["og:site_name"]).and_then(|site| clean(&site, MAX_SITE_NAME_CHARS)),
+    }
+}
+
+/// Length of the tag body up to the closing `>`, ignoring `>` inside quotes.
+fn tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (index, c) in rest.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('>', None) => {
+                return Some(index);
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn parse_attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attributes = vec![];
+    let mut chars = tag.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() || c == '/' {
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        while let Some((index, c)) = chars.peek().copied() {
+            if c.is_whitespace() || c == '=' || c == '/' {
+                break;
+            }
+            end = index + c.len_utf8();
+            chars.next();
+        }
+        let name = tag[start..end].to_ascii_lowercase();
+
+        while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek().map(|(_, c)| *c) != Some('=') {
+            attributes.push((name, String::new()));
+            continue;
+        }
+        chars.next();
+        while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+            chars.next();
+        }
+
+        let value = match chars.peek().copied() {
+            Some((index, quote @ ('"' | '\''))) => {
+                chars.next();
+                let value_start = index + 1;
+                let mut value_end = tag.len();
+                for (index, c) in chars.by_ref() {
+                    if c == quote {
+                        value_end = index;
+                        break;
+                    }
+                }
+                &tag[value_start..value_end.max(value_start)]
+            }
+            Some((index, _)) => {
+                let mut value_end = tag.len();
+                while let Some((i, c)) = chars.peek().copied() {
+                    if c.is_whitespace() {
+                        value_end = i;
+                        break;
+                    }
+                    chars.next();
+                }
+                &tag[index..value_end]
+            }
+            None => "",
+        };
+
+        attributes.push((name, value.to_string()));
+    }
+
+    attributes
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn title_element(html: &str, lower: &str) -> Option<String> {
+    let open = lower.find("<title")?;
+    let start = open + tag_end(&html[open..])? + 1;
+    let end = start + lower[start..].find("</title")?;
+    Some(html[start..end].to_string())
+}
+
+/// Decodes entities, collapses whitespace and truncates to `max_chars`.
+fn clean(value: &str, max_chars: usize) -> Option<String> {
+    let decoded = decode_entities(value);
+    let collapsed = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if collapsed.is_empty() {
+        return None;
+    }
+
+    if collapsed.chars().count() <= max_chars {
+        return Some(collapsed);
+    }
+
+    let truncated: String = collapsed.chars().take(max_chars - 1).collect();
+    Some(format!("{}…", truncated.trim_end()))
+}
+
+pub(crate) fn decode_entities(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| (&rest[1..end], end));
+        let character = entity.and_then(|(name, _)| {
+            match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => {
+                    let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => name.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                    };
+                    code.and_then(char::from_u32)
+                }
+            }
+        });
+
+        match (character, entity) {
+            (Some(character), Some((_, end))) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Url;
+
+    use super::{ PageMetadata, parse_metadata };
+
+    fn base() -> Url {
+        Url::parse("https://example.com/articles/1").unwrap()
+    }
+
+    #[test]
+    fn should_prefer_open_graph_then_twitter_cards() {
+        let html = r#"<html><head>
+            <title>Plain title</title>
+            <meta name="twitter:title" content="Twitter title">
+            <meta property="og:title" content="Tom &amp; Jerry &#8212; &quot;live&quot;" />
+            <meta name='description' content='Fallback description'>
+            <meta name=twitter:description content=Short>
+            <meta property="og:image" content="/img/cover.png?a=1&amp;b=2">
+            <meta property="og:site_name" content="Example">
+        </head></html>"#;
+
+        assert_eq!(parse_metadata(html, &base()), PageMetadata {
+            title: Some("Tom & Jerry — \"live\"".into()),
+            description: Some("Short".into()),
+            image_url: Some("https://example.com/img/cover.png?a=1&b=2".into()),
+            site_name: Some("Example".into()),
+        });
+    }
+
+    #[test]
+    fn should_fall_back_to_the_title_element() {
+        let html = "<head><TITLE data-x=\"a>b\">\n  Hello\n   world </TITLE></head>";
+        let metadata = parse_metadata(html, &base());
+
+        assert_eq!(metadata.title.as_deref(), Some("Hello world"));
+        assert_eq!(metadata.description, None);
+    }
+
+    #[test]
+    fn should_ignore_non_http_images_and_truncate_long_titles() {
+        let long = "a".repeat(300);
+        let html = format!(
+            r#"<meta property="og:title" content="{}"><meta property="og:image" content="javascript:alert(1)">"#,
+            long
+        );
+        let metadata = parse_metadata(&html, &base());
+
+        assert_eq!(metadata.title.unwrap().chars().count(), 200);
+        assert_eq!(metadata.image_url, None);
+    }
+}
@@ -1,8 +1,14 @@
 use std::env;
 
+use actix_web::web;
 use dotenvy::dotenv;
 use sea_orm::{Database, DatabaseConnection};
-use server::{bootstrap::build_dependencies, database::postgres_connector::connect, server::build_server};
+use server::{
+    bootstrap::build_dependencies,
+    config::Config,
+    database::postgres_connector::connect,
+    server::build_server,
+};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -25,7 +31,9 @@ async fn main() -> std::io::Result<()> {
     let client = redis::Client::open(redis_url).unwrap();
 
     // Building Dependencies
-    let dependencies = build_dependencies(db, client);
+    let config = Config::from_env();
+    // Background work is left to the `worker` binary.
+    let dependencies = web::Data::new(build_dependencies(db, client, &config));
 
     build_server(dependencies, port).await
 }
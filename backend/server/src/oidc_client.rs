@@ -0,0 +1,392 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use domain::{
+    dtos::oidc::{ JwkDTO, OidcDiscoveryDTO },
+    error_codes::OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE,
+    policies::oidc::OidcProvider,
+    repositories::oidc_client::OidcClient,
+};
+use errors::HearthError;
+use reqwest::{ Client, Response };
+use serde::{ Deserialize, de::DeserializeOwned };
+
+use crate::database::unexpected;
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+struct JwkSet {
+    keys: Vec<JwkDTO>,
+}
+
+/// Talks to OpenID Connect providers over HTTPS, client credentials are sent
+/// in the token request body (`client_secret_post`).
+pub struct HttpOidcClient {
+    client: Client,
+}
+
+impl HttpOidcClient {
+    pub fn new(timeout: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("The OIDC HTTP client must build");
+
+        Self { client }
+    }
+}
+
+impl Default for HttpOidcClient {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+async fn read_json<T: DeserializeOwned>(
+    response: Response,
+    code: &'static str
+) -> Result<T, HearthError> {
+    let body = response
+        .error_for_status()
+        .map_err(unexpected(code))?
+        .text().await
+        .map_err(unexpected(code))?;
+
+    serde_json::from_str(&body).map_err(unexpected(code))
+}
+
+#[async_trait]
+impl OidcClient for HttpOidcClient {
+    async fn discover(&self, issuer: &str) -> Result<OidcDiscoveryDTO, HearthError> {
+        let url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+        let response = self.client
+            .get(url)
+            .send().await
+            .map_err(unexpected("OIDC_DISCOVERY_REQUEST"))?;
+        let discovery: OidcDiscoveryDTO = read_json(response, "OIDC_DISCOVERY_RESPONSE").await?;
+
+        // Required by the spec, a discovery document can't vouch for another issuer.
+        if discovery.issuer != issuer {
+            return Err(
+                HearthError::unexpected("OIDC_ISSUER_MISMATCH".into(), Some(discovery.issuer))
+            );
+        }
+
+        Ok(discovery)
+    }
+
+    async fn exchange_code(
+        &self,
+        discovery: &OidcDiscoveryDTO,
+        provider: &OidcProvider,
+        code: &str,
+        code_verifier: &str
+    ) -> Result<String, HearthError> {
+        let response = self.client
+            .post(&discovery.token_endpoint)
+            .form(
+                &[
+                    ("grant_type", "authorization_code"),
+                    ("code", code),
+                    ("redirect_uri", &provider.redirect_uri),
+                    ("client_id", &provider.client_id),
+                    ("client_secret", &provider.client_secret),
+                    ("code_verifier", code_verifier),
+                ]
+            )
+            .send().await
+            .map_err(unexpected("OIDC_TOKEN_REQUEST"))?;
+
+        // Providers answer 400 to codes that are expired, reused or not bound to the verifier.
+        if response.status().is_client_error() {
+            return Err(HearthError::Unauthorized(OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE.into()));
+        }
+
+        let token: TokenResponse = read_json(response, "OIDC_TOKEN_RESPONSE").await?;
+        Ok(token.id_token)
+    }
+
+    async fn jwks(&self, jwks_uri: &str) -> Result<Vec<JwkDTO>, HearthError> {
+        let response = self.client
+            .get(jwks_uri)
+            .send().await
+            .map_err(unexpected("OIDC_JWKS_REQUEST"))?;
+        let jwks: JwkSet = read_json(response, "OIDC_JWKS_RESPONSE").await?;
+
+        Ok(jwks.keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ collections::HashMap, sync::{ Arc, Mutex } };
+
+    use actix_web::{ App, HttpResponse, HttpServer, web };
+    use base64::{ Engine, engine::general_purpose::URL_SAFE_NO_PAD };
+    use chrono::Utc;
+    use domain::{
+        dtos::oidc::OidcClaimsDTO,
+        entities::oidc::Oidc,
+        error_codes::OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE,
+        policies::oidc::OidcProvider,
+        repositories::oidc_client::OidcClient,
+    };
+    use errors::{ HearthError, TechnicalErrors };
+    use ring::{
+        rand::SystemRandom,
+        signature::{ ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair },
+    };
+
+    use super::HttpOidcClient;
+
+    const CLIENT_ID: &str = "hearth";
+    const CLIENT_SECRET: &str = "s3cret";
+    const REDIRECT_URI: &str = "tauri://localhost/oidc/mock";
+
+    /// Authorization code to the nonce and PKCE challenge it was issued for.
+    type Codes = Arc<Mutex<HashMap<String, (String, String)>>>;
+
+    struct MockProvider {
+        issuer: String,
+        key_pair: EcdsaKeyPair,
+        codes: Codes,
+    }
+
+    impl MockProvider {
+        fn id_token(&self, nonce: &str) -> String {
+            let now = Utc::now().timestamp();
+            let header = serde_json::json!({ "alg": "ES256", "kid": "mock", "typ": "JWT" });
+            let claims = serde_json::json!({
+                "iss": self.issuer,
+                "sub": "mock-subject",
+                "aud": CLIENT_ID,
+                "exp": now + 300,
+                "iat": now,
+                "nonce": nonce,
+                "email": "jane@example.com",
+                "email_verified": true,
+            });
+            let message = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(header.to_string()),
+                URL_SAFE_NO_PAD.encode(claims.to_string())
+            );
+            let signature = self.key_pair.sign(&SystemRandom::new(), message.as_bytes()).unwrap();
+
+            format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        }
+    }
+
+    async fn discovery(provider: web::Data<MockProvider>) -> HttpResponse {
+        HttpResponse::Ok().json(serde_json::json!({
+            "issuer": provider.issuer,
+            "authorization_endpoint": format!("{}/authorize", provider.issuer),
+            "token_endpoint": format!("{}/token", provider.issuer),
+            "jwks_uri": format!("{}/jwks", provider.issuer),
+            "response_types_supported": ["code"],
+        }))
+    }
+
+    async fn authorize(
+        provider: web::Data<MockProvider>,
+        query: web::Query<HashMap<String, String>>
+    ) -> HttpResponse {
+        if query.get("client_id").map(String::as_str) != Some(CLIENT_ID) {
+            return HttpResponse::BadRequest().finish();
+        }
+
+        let code = Oidc::generate_token();
+        provider.codes
+            .lock()
+            .unwrap()
+            .insert(code.clone(), (query["nonce"].clone(), query["code_challenge"].clone()));
+
+        HttpResponse::Found()
+            .insert_header((
+                "location",
+                format!("{}?code={}&state={}", REDIRECT_URI, code, query["state"]),
+            ))
+            .finish()
+    }
+
+    async fn token(
+        provider: web::Data<MockProvider>,
+        form: web::Form<HashMap<String, String>>
+    ) -> HttpResponse {
+        let issued = provider.codes.lock().unwrap().remove(&form["code"]);
+        let valid = |challenge: &str| {
+            form["client_id"] == CLIENT_ID &&
+                form["client_secret"] == CLIENT_SECRET &&
+                form["redirect_uri"] == REDIRECT_URI &&
+                Oidc::code_challenge(&form["code_verifier"]) == challenge
+        };
+
+        match issued {
+            Some((nonce, challenge)) if valid(&challenge) =>
+                HttpResponse::Ok().json(serde_json::json!({
+                    "access_token": "unused",
+                    "token_type": "Bearer",
+                    "id_token": provider.id_token(&nonce),
+                })),
+            _ => HttpResponse::BadRequest().json(serde_json::json!({ "error": "invalid_grant" })),
+        }
+    }
+
+    async fn jwks(provider: web::Data<MockProvider>) -> HttpResponse {
+        let public_key = provider.key_pair.public_key().as_ref();
+
+        HttpResponse::Ok().json(serde_json::json!({
+            "keys": [{
+                "kty": "EC",
+                "kid": "mock",
+                "alg": "ES256",
+                "use": "sig",
+                "crv": "P-256",
+                "x": URL_SAFE_NO_PAD.encode(&public_key[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&public_key[33..]),
+            }],
+        }))
+    }
+
+    /// Local OpenID provider on a random port, returns its issuer.
+    fn mock_provider() -> String {
+        let random = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &random)
+            .unwrap()
+            .as_ref()
+            .to_vec();
+        let codes: Codes = Arc::default();
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = HttpServer::new({
+            let issuer = issuer.clone();
+            move || {
+                let key_pair = EcdsaKeyPair::from_pkcs8(
+                    &ECDSA_P256_SHA256_FIXED_SIGNING,
+                    &pkcs8,
+                    &SystemRandom::new()
+                ).unwrap();
+
+                App::new()
+                    .app_data(
+                        web::Data::new(MockProvider {
+                            issuer: issuer.clone(),
+                            key_pair,
+                            codes: codes.clone(),
+                        })
+                    )
+                    .route("/.well-known/openid-configuration", web::get().to(discovery))
+                    .route("/authorize", web::get().to(authorize))
+                    .route("/token", web::post().to(token))
+                    .route("/jwks", web::get().to(jwks))
+            }
+        })
+            .workers(1)
+            .listen(listener)
+            .unwrap();
+        actix_web::rt::spawn(server.run());
+
+        issuer
+    }
+
+    fn provider(issuer: &str) -> OidcProvider {
+        OidcProvider {
+            name: "mock".into(),
+            issuer: issuer.into(),
+            client_id: CLIENT_ID.into(),
+            client_secret: CLIENT_SECRET.into(),
+            redirect_uri: REDIRECT_URI.into(),
+            scopes: vec!["openid".into(), "email".into()],
+        }
+    }
+
+    /// Follows the authorization URL like a browser would, returns the code.
+    async fn consent(authorization_url: &str) -> String {
+        let response = reqwest::Client
+            ::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap()
+            .get(authorization_url)
+            .send().await
+            .unwrap();
+        let location = reqwest::Url
+            ::parse(response.headers()["location"].to_str().unwrap())
+            .unwrap();
+        let params: HashMap<_, _> = location.query_pairs().into_owned().collect();
+
+        params["code"].clone()
+    }
+
+    #[actix_web::test]
+    async fn should_complete_an_authorization_code_flow() {
+        let issuer = mock_provider();
+        let provider = provider(&issuer);
+        let client = HttpOidcClient::default();
+        let (state, nonce, code_verifier) = (
+            Oidc::generate_token(),
+            Oidc::generate_token(),
+            Oidc::generate_token(),
+        );
+
+        let discovery = client.discover(&issuer).await.unwrap();
+        let url = Oidc::authorization_url(&discovery, &provider, &state, &nonce, &code_verifier)
+            .unwrap();
+        let code = consent(&url).await;
+
+        let id_token = client
+            .exchange_code(&discovery, &provider, &code, &code_verifier).await
+            .unwrap();
+        let jwks = client.jwks(&discovery.jwks_uri).await.unwrap();
+
+        assert_eq!(
+            Oidc::verify_id_token(&id_token, &jwks, &issuer, CLIENT_ID, &nonce, Utc::now(), 60),
+            Some(OidcClaimsDTO {
+                subject: "mock-subject".into(),
+                email: Some("jane@example.com".into()),
+                email_verified: true,
+            })
+        );
+        // Codes are single use.
+        assert_eq!(
+            client.exchange_code(&discovery, &provider, &code, &code_verifier).await.unwrap_err(),
+            HearthError::Unauthorized(OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE.into())
+        );
+    }
+
+    #[actix_web::test]
+    async fn should_refuse_a_code_without_its_verifier() {
+        let issuer = mock_provider();
+        let provider = provider(&issuer);
+        let client = HttpOidcClient::default();
+
+        let discovery = client.discover(&issuer).await.unwrap();
+        let url = Oidc::authorization_url(&discovery, &provider, "state", "nonce", "verifier")
+            .unwrap();
+        let code = consent(&url).await;
+
+        let result = client.exchange_code(&discovery, &provider, &code, "another-verifier").await;
+        assert_eq!(
+            result.unwrap_err(),
+            HearthError::Unauthorized(OIDC_CODE_EXCHANGE_FAILED_ERROR_CODE.into())
+        );
+    }
+
+    #[actix_web::test]
+    async fn should_refuse_a_discovery_document_of_another_issuer() {
+        let issuer = mock_provider();
+        let other = issuer.replace("127.0.0.1", "localhost");
+
+        let result = HttpOidcClient::default().discover(&other).await;
+
+        let Err(HearthError::Technical(TechnicalErrors::Unexpected(code, _))) = result else {
+            panic!("expected an unexpected error, got {:?}", result);
+        };
+        assert_eq!(code, "OIDC_ISSUER_MISMATCH");
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    future::{Ready, ready},
+    rc::Rc,
+};
+
+use actix_web::{
+    Error, HttpMessage, ResponseError,
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+    web,
+};
+use domain::{
+    dtos::rate_limit::{RateLimitDTO, RateLimitRequestDTO, RateLimitScope},
+    error_codes::RATE_LIMITED_ERROR_CODE,
+};
+use errors::HearthError;
+use futures_util::future::LocalBoxFuture;
+
+use crate::{
+    auth::{Authentication, bearer_token},
+    bootstrap::Dependencies,
+};
+
+const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// Throttles a route with the bucket of `scope`, to be used as
+/// `#[post("/path", wrap = "RateLimit::new(RateLimitScope::Login)")]`.
+///
+/// Callers are told where they stand with the `RateLimit-*` headers, and
+/// get a 429 with a `Retry-After` header once their bucket is empty. The
+/// client ip is the peer address, a reverse proxy in front of the server
+/// would make every caller share a single bucket.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    scope: RateLimitScope,
+}
+
+impl RateLimit {
+    pub fn new(scope: RateLimitScope) -> Self {
+        Self { scope }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            scope: self.scope,
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    scope: RateLimitScope,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let scope = self.scope;
+
+        Box::pin(async move {
+            let Some(dependencies) = req.app_data::<web::Data<Dependencies>>().cloned() else {
+                return service.call(req).await.map(ServiceResponse::map_into_left_body);
+            };
+
+            // An invalid token is rejected by the route itself, until then
+            // the caller is treated as anonymous. The outcome is handed over
+            // to the `AuthenticatedUser` extractor.
+            let user = match bearer_token(req.request()) {
+                Some(token) => {
+                    let result = dependencies.authenticate.execute(token).await;
+                    let user = result.as_ref().ok().cloned();
+                    req.extensions_mut().insert(Authentication(result));
+                    user
+                }
+                None => None,
+            };
+
+            let input = RateLimitRequestDTO {
+                scope,
+                ip: req.peer_addr().map(|addr| addr.ip().to_string()),
+                user_id: user.as_ref().map(|user| user.user_id),
+                personal_access_token_id: user.and_then(|user| user.personal_access_token_id),
+            };
+
+            // Only the throttled routes fail while the limiter is unreachable.
+            let limit = match dependencies.check_rate_limit.execute(input).await {
+                Ok(limit) => limit,
+                Err(e) => {
+                    return Ok(req.into_response(e.error_response()).map_into_right_body());
+                }
+            };
+
+            let mut res = match limit.retry_after_seconds {
+                Some(retry_after) => {
+                    let error =
+                        HearthError::TooManyRequests(RATE_LIMITED_ERROR_CODE.into(), retry_after);
+                    req.into_response(error.error_response())
+                        .map_into_right_body()
+                }
+                None => service.call(req).await?.map_into_left_body(),
+            };
+
+            insert_headers(res.headers_mut(), &limit);
+            Ok(res)
+        })
+    }
+}
+
+fn insert_headers(headers: &mut HeaderMap, limit: &RateLimitDTO) {
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(limit.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(limit.remaining));
+    headers.insert(RATE_LIMIT_RESET, HeaderValue::from(limit.reset_seconds));
+}
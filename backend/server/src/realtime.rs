@@ -0,0 +1,136 @@
+use std::{
+    collections::HashMap,
+    sync::{ Arc, Mutex, atomic::{ AtomicU64, Ordering } },
+};
+
+use async_trait::async_trait;
+use domain::{
+    dtos::conversation::ConversationEventDTO,
+    repositories::message_broadcaster::MessageBroadcaster,
+};
+use tokio::sync::mpsc::{ UnboundedReceiver, UnboundedSender, unbounded_channel };
+use uuid::Uuid;
+
+type Connections = HashMap<Uuid, Vec<(u64, UnboundedSender<String>)>>;
+
+/// Open WebSocket connections by user, a user may be connected from several
+/// devices. Connections only live in this process: with more than one server
+/// instance, a user only gets the events published by the instance they are
+/// connected to and catches up on the others through the HTTP routes.
+#[derive(Clone, Default)]
+pub struct ConnectionHub {
+    connections: Arc<Mutex<Connections>>,
+    next_id: Arc<AtomicU64>,
+}
+
+/// Receiving end of a connection, unregistered from the hub when dropped.
+pub struct Connection {
+    id: u64,
+    user_id: Uuid,
+    hub: ConnectionHub,
+    receiver: UnboundedReceiver<String>,
+}
+
+impl ConnectionHub {
+    pub fn connect(&self, user_id: Uuid) -> Connection {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = unbounded_channel();
+
+        self.connections.lock().unwrap().entry(user_id).or_default().push((id, sender));
+
+        Connection { id, user_id, hub: self.clone(), receiver }
+    }
+
+    pub fn connected_count(&self, user_id: &Uuid) -> usize {
+        self.connections.lock().unwrap().get(user_id).map_or(0, Vec::len)
+    }
+
+    fn disconnect(&self, user_id: &Uuid, id: u64) {
+        let mut connections = self.connections.lock().unwrap();
+
+        if let Some(senders) = connections.get_mut(user_id) {
+            senders.retain(|(connection_id, _)| *connection_id != id);
+            if senders.is_empty() {
+                connections.remove(user_id);
+            }
+        }
+    }
+}
+
+impl Connection {
+    /// Next serialized event, `None` once the hub is gone.
+    pub async fn recv(&mut self) -> Option<String> {
+        self.receiver.recv().await
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.hub.disconnect(&self.user_id, self.id);
+    }
+}
+
+#[async_trait]
+impl MessageBroadcaster for ConnectionHub {
+    async fn publish(&self, recipient_ids: &[Uuid], event: ConversationEventDTO) {
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                eprintln!("Failed to serialize conversation event: {:?}", e);
+                return;
+            }
+        };
+
+        let connections = self.connections.lock().unwrap();
+        for recipient_id in recipient_ids {
+            for (_, sender) in connections.get(recipient_id).into_iter().flatten() {
+                // Fails only when the connection is closing, it unregisters itself.
+                let _ = sender.send(payload.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use domain::{
+        dtos::conversation::{ ConversationEventDTO, ReadPositionDTO },
+        repositories::message_broadcaster::MessageBroadcaster,
+    };
+    use uuid::Uuid;
+
+    use super::ConnectionHub;
+
+    #[actix_web::test]
+    async fn should_deliver_events_to_every_connection_of_the_recipients() {
+        let hub = ConnectionHub::default();
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        let mut phone = hub.connect(alice);
+        let mut laptop = hub.connect(alice);
+        let mut eavesdropper = hub.connect(bob);
+
+        let event = ConversationEventDTO::Read {
+            conversation_id: Uuid::new_v4(),
+            position: ReadPositionDTO {
+                user_id: bob,
+                last_read_message_id: Uuid::new_v4(),
+                last_read_at: Utc::now(),
+            },
+        };
+        hub.publish(&[alice], event.clone()).await;
+
+        let expected = serde_json::to_string(&event).unwrap();
+        assert_eq!(phone.recv().await.unwrap(), expected);
+        assert_eq!(laptop.recv().await.unwrap(), expected);
+        assert!(eavesdropper.receiver.try_recv().is_err());
+        assert!(expected.contains(r#""type":"read""#));
+
+        drop(phone);
+        assert_eq!(hub.connected_count(&alice), 1);
+        drop(laptop);
+        assert_eq!(hub.connected_count(&alice), 0);
+    }
+}
@@ -1,10 +1,32 @@
 use actix_web::{HttpResponse, post, web};
-use domain::dtos::signup::SignupEmailDTO;
+use domain::dtos::{rate_limit::RateLimitScope, signup::SignupEmailDTO};
 use errors::HearthError;
 
-use crate::bootstrap::Dependencies;
+use crate::{bootstrap::Dependencies, rate_limit::RateLimit};
 
-#[post("/signup/email")]
+pub mod auth;
+pub mod blocks;
+pub mod bookmarks;
+pub mod conversations;
+pub mod data_exports;
+pub mod device_keys;
+pub mod filters;
+pub mod imports;
+pub mod lists;
+pub mod media;
+pub mod moderation;
+pub mod notifications;
+pub mod oauth;
+pub mod oidc;
+pub mod passkeys;
+pub mod personal_access_tokens;
+pub mod polls;
+pub mod posts;
+pub mod trends;
+pub mod two_factor;
+pub mod users;
+
+#[post("/signup/email", wrap = "RateLimit::new(RateLimitScope::Signup)")]
 pub async fn signup_email_handler(
     dependencies: web::Data<Dependencies>,
     req_body: String,
@@ -16,3 +38,15 @@ pub async fn signup_email_handler(
         .await
         .map(|_| HttpResponse::Created().finish())
 }
+
+/// Only answers when signups require a proof of work.
+#[post("/signup/challenge", wrap = "RateLimit::new(RateLimitScope::Signup)")]
+pub async fn signup_challenge_handler(
+    dependencies: web::Data<Dependencies>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .create_signup_challenge
+        .execute(())
+        .await
+        .map(|challenge| HttpResponse::Created().json(challenge))
+}
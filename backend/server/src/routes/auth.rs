@@ -0,0 +1,30 @@
+use actix_web::{HttpResponse, post, web};
+use domain::dtos::{auth::LoginEmailDTO, rate_limit::RateLimitScope, two_factor::LoginTwoFactorDTO};
+use errors::HearthError;
+
+use crate::{bootstrap::Dependencies, rate_limit::RateLimit};
+
+#[post("/login/email", wrap = "RateLimit::new(RateLimitScope::Login)")]
+pub async fn login_email_handler(
+    dependencies: web::Data<Dependencies>,
+    dto: web::Json<LoginEmailDTO>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .login_with_email
+        .execute(dto.into_inner())
+        .await
+        .map(|result| HttpResponse::Ok().json(result))
+}
+
+/// Second step for accounts with two-factor authentication, shares the login budget.
+#[post("/login/2fa", wrap = "RateLimit::new(RateLimitScope::Login)")]
+pub async fn login_two_factor_handler(
+    dependencies: web::Data<Dependencies>,
+    dto: web::Json<LoginTwoFactorDTO>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .login_with_two_factor
+        .execute(dto.into_inner())
+        .await
+        .map(|session| HttpResponse::Ok().json(session))
+}
@@ -0,0 +1,42 @@
+use actix_web::{HttpResponse, delete, post, web};
+use domain::dtos::block::BlockUserDTO;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[post("/users/{user_id}/block")]
+pub async fn block_user_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    blocked_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = BlockUserDTO {
+        blocker_id: user.user_id,
+        blocked_id: blocked_id.into_inner(),
+    };
+
+    dependencies
+        .block_user
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[delete("/users/{user_id}/block")]
+pub async fn unblock_user_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    blocked_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = BlockUserDTO {
+        blocker_id: user.user_id,
+        blocked_id: blocked_id.into_inner(),
+    };
+
+    dependencies
+        .unblock_user
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,127 @@
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use domain::dtos::{
+    bookmark::{
+        BookmarkPostDTO, CreateBookmarkCollectionDTO, ListBookmarksDTO, MoveBookmarkDTO,
+        RemoveBookmarkDTO,
+    },
+    pagination::PageRequest,
+};
+use errors::HearthError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[derive(Deserialize)]
+pub struct CollectionQuery {
+    collection_id: Option<Uuid>,
+}
+
+#[post("/me/bookmark-collections")]
+pub async fn create_bookmark_collection_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<CreateBookmarkCollectionDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = CreateBookmarkCollectionDTO {
+        owner_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .create_bookmark_collection
+        .execute(dto)
+        .await
+        .map(|collection| HttpResponse::Created().json(collection))
+}
+
+#[get("/me/bookmark-collections")]
+pub async fn list_bookmark_collections_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .list_bookmark_collections
+        .execute(user.user_id)
+        .await
+        .map(|collections| HttpResponse::Ok().json(collections))
+}
+
+/// Without `collection_id`, lists the default collection.
+#[get("/me/bookmarks")]
+pub async fn list_bookmarks_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    collection: web::Query<CollectionQuery>,
+    page: web::Query<PageRequest>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListBookmarksDTO {
+        owner_id: user.user_id,
+        collection_id: collection.into_inner().collection_id,
+        page: page.into_inner(),
+    };
+
+    dependencies
+        .list_bookmarks
+        .execute(dto)
+        .await
+        .map(|page| HttpResponse::Ok().json(page))
+}
+
+#[put("/me/bookmarks/{post_id}")]
+pub async fn bookmark_post_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+    dto: web::Json<BookmarkPostDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = BookmarkPostDTO {
+        owner_id: user.user_id,
+        post_id: post_id.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .bookmark_post
+        .execute(dto)
+        .await
+        .map(|bookmark| HttpResponse::Ok().json(bookmark))
+}
+
+#[delete("/me/bookmarks/{post_id}")]
+pub async fn remove_bookmark_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = RemoveBookmarkDTO {
+        owner_id: user.user_id,
+        post_id: post_id.into_inner(),
+    };
+
+    dependencies
+        .remove_bookmark
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[put("/me/bookmarks/{post_id}/position")]
+pub async fn move_bookmark_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+    dto: web::Json<MoveBookmarkDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = MoveBookmarkDTO {
+        owner_id: user.user_id,
+        post_id: post_id.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .move_bookmark
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
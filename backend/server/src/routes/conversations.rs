@@ -0,0 +1,236 @@
+use actix_web::{HttpRequest, HttpResponse, delete, get, post, put, web};
+use actix_ws::Message;
+use domain::dtos::{
+    conversation::{
+        AcknowledgeEnvelopeDTO, ListMessagesDTO, MarkConversationReadDTO,
+        SendEncryptedMessageDTO, SendMessageDTO, StartConversationDTO, UpdateDmSettingsDTO,
+    },
+    device_keys::DeviceRefDTO,
+    pagination::PageRequest,
+};
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+/// Answers with the existing conversation when starting a one-to-one
+/// conversation twice.
+#[post("/conversations")]
+pub async fn start_conversation_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<StartConversationDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = StartConversationDTO {
+        creator_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .start_conversation
+        .execute(dto)
+        .await
+        .map(|conversation| HttpResponse::Ok().json(conversation))
+}
+
+#[get("/conversations")]
+pub async fn list_conversations_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .list_conversations
+        .execute(user.user_id)
+        .await
+        .map(|conversations| HttpResponse::Ok().json(conversations))
+}
+
+#[get("/conversations/{conversation_id}/messages")]
+pub async fn list_messages_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    conversation_id: web::Path<Uuid>,
+    page: web::Query<PageRequest>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListMessagesDTO {
+        conversation_id: conversation_id.into_inner(),
+        viewer_id: user.user_id,
+        page: page.into_inner(),
+    };
+
+    dependencies
+        .list_messages
+        .execute(dto)
+        .await
+        .map(|page| HttpResponse::Ok().json(page))
+}
+
+#[post("/conversations/{conversation_id}/messages")]
+pub async fn send_message_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    conversation_id: web::Path<Uuid>,
+    dto: web::Json<SendMessageDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = SendMessageDTO {
+        conversation_id: conversation_id.into_inner(),
+        sender_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .send_message
+        .execute(dto)
+        .await
+        .map(|message| HttpResponse::Created().json(message))
+}
+
+/// The body only carries ciphertext, one envelope per recipient device.
+#[post("/conversations/{conversation_id}/encrypted-messages")]
+pub async fn send_encrypted_message_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    conversation_id: web::Path<Uuid>,
+    dto: web::Json<SendEncryptedMessageDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = SendEncryptedMessageDTO {
+        conversation_id: conversation_id.into_inner(),
+        sender_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .send_encrypted_message
+        .execute(dto)
+        .await
+        .map(|message| HttpResponse::Created().json(message))
+}
+
+#[get("/me/devices/{device_id}/envelopes")]
+pub async fn list_envelopes_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    device_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = DeviceRefDTO {
+        user_id: user.user_id,
+        device_id: device_id.into_inner(),
+    };
+
+    dependencies
+        .list_envelopes
+        .execute(dto)
+        .await
+        .map(|envelopes| HttpResponse::Ok().json(envelopes))
+}
+
+#[delete("/me/devices/{device_id}/envelopes/{message_id}")]
+pub async fn acknowledge_envelope_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, HearthError> {
+    let (device_id, message_id) = path.into_inner();
+    let dto = AcknowledgeEnvelopeDTO {
+        user_id: user.user_id,
+        device_id,
+        message_id,
+    };
+
+    dependencies
+        .acknowledge_envelope
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[put("/conversations/{conversation_id}/read")]
+pub async fn mark_conversation_read_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    conversation_id: web::Path<Uuid>,
+    dto: web::Json<MarkConversationReadDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = MarkConversationReadDTO {
+        conversation_id: conversation_id.into_inner(),
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .mark_conversation_read
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[get("/me/dm-settings")]
+pub async fn get_dm_settings_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .get_dm_settings
+        .execute(user.user_id)
+        .await
+        .map(|settings| HttpResponse::Ok().json(settings))
+}
+
+#[put("/me/dm-settings")]
+pub async fn update_dm_settings_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<UpdateDmSettingsDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = UpdateDmSettingsDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .update_dm_settings
+        .execute(dto)
+        .await
+        .map(|settings| HttpResponse::Ok().json(settings))
+}
+
+/// Pushes conversation events (new messages, envelopes, read receipts) to the
+/// user as JSON text frames. The socket is receive only, clients send over HTTP.
+#[get("/ws")]
+pub async fn websocket_handler(
+    req: HttpRequest,
+    body: web::Payload,
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> actix_web::Result<HttpResponse> {
+    let (response, mut session, mut stream) = actix_ws::handle(&req, body)?;
+    let mut connection = dependencies.connection_hub.connect(user.user_id);
+
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                event = connection.recv() => match event {
+                    Some(event) => {
+                        if session.text(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                },
+                message = stream.recv() => match message {
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
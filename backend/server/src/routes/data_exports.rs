@@ -0,0 +1,44 @@
+use actix_web::{HttpResponse, get, http::header, post, web};
+use domain::dtos::data_export::DownloadDataExportDTO;
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+/// Queues an export, the download link is emailed once the archive is built.
+#[post("/me/exports")]
+pub async fn request_data_export_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .request_data_export
+        .execute(user.user_id)
+        .await
+        .map(|export| HttpResponse::Accepted().json(export))
+}
+
+/// Public, the token of the emailed link authorizes the download.
+#[get("/exports/{export_id}")]
+pub async fn download_data_export_handler(
+    dependencies: web::Data<Dependencies>,
+    export_id: web::Path<Uuid>,
+    query: web::Query<DownloadDataExportDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let export_id = export_id.into_inner();
+    let dto = DownloadDataExportDTO {
+        export_id,
+        ..query.into_inner()
+    };
+
+    dependencies.download_data_export.execute(dto).await.map(|bytes| {
+        HttpResponse::Ok()
+            .content_type("application/zip")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"hearth-export-{}.zip\"", export_id),
+            ))
+            .insert_header((header::CACHE_CONTROL, "no-store"))
+            .body(bytes)
+    })
+}
@@ -0,0 +1,85 @@
+use actix_web::{HttpResponse, get, post, put, web};
+use domain::dtos::device_keys::{
+    ClaimPrekeysDTO, DeviceRefDTO, UploadDeviceKeysDTO, UploadPrekeysDTO,
+};
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[put("/me/devices/{device_id}/keys")]
+pub async fn upload_device_keys_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    device_id: web::Path<Uuid>,
+    dto: web::Json<UploadDeviceKeysDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = UploadDeviceKeysDTO {
+        user_id: user.user_id,
+        device_id: device_id.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .upload_device_keys
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[post("/me/devices/{device_id}/prekeys")]
+pub async fn upload_prekeys_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    device_id: web::Path<Uuid>,
+    dto: web::Json<UploadPrekeysDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = UploadPrekeysDTO {
+        user_id: user.user_id,
+        device_id: device_id.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .upload_prekeys
+        .execute(dto)
+        .await
+        .map(|count| HttpResponse::Ok().json(count))
+}
+
+#[get("/me/devices/{device_id}/prekeys")]
+pub async fn prekey_count_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    device_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = DeviceRefDTO {
+        user_id: user.user_id,
+        device_id: device_id.into_inner(),
+    };
+
+    dependencies
+        .get_prekey_count
+        .execute(dto)
+        .await
+        .map(|count| HttpResponse::Ok().json(count))
+}
+
+/// A POST since every call consumes one-time prekeys.
+#[post("/users/{user_id}/prekeys/claim")]
+pub async fn claim_prekeys_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    user_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ClaimPrekeysDTO {
+        claimer_id: user.user_id,
+        user_id: user_id.into_inner(),
+    };
+
+    dependencies
+        .claim_prekeys
+        .execute(dto)
+        .await
+        .map(|bundles| HttpResponse::Ok().json(bundles))
+}
@@ -0,0 +1,54 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use domain::dtos::filter::{CreateKeywordFilterDTO, DeleteKeywordFilterDTO};
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[post("/me/filters")]
+pub async fn create_keyword_filter_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<CreateKeywordFilterDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = CreateKeywordFilterDTO {
+        owner_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .create_keyword_filter
+        .execute(dto)
+        .await
+        .map(|filter| HttpResponse::Created().json(filter))
+}
+
+#[get("/me/filters")]
+pub async fn list_keyword_filters_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .list_keyword_filters
+        .execute(user.user_id)
+        .await
+        .map(|filters| HttpResponse::Ok().json(filters))
+}
+
+#[delete("/me/filters/{filter_id}")]
+pub async fn delete_keyword_filter_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    filter_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = DeleteKeywordFilterDTO {
+        owner_id: user.user_id,
+        filter_id: filter_id.into_inner(),
+    };
+
+    dependencies
+        .delete_keyword_filter
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,81 @@
+use actix_multipart::Multipart;
+use actix_web::{HttpResponse, get, post, web};
+use domain::{
+    dtos::import::{GetImportDTO, ImportArchiveDTO, StartImportDTO},
+    error_codes::IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE,
+};
+use errors::HearthError;
+use futures_util::{StreamExt, future::join};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthenticatedUser, bootstrap::Dependencies, routes::media::FILE_FIELD,
+    storage::ChannelByteStream,
+};
+
+/// Chunks received ahead of the object store before the upload waits for it.
+const BUFFERED_CHUNKS: usize = 16;
+
+/// Stores the archive sent as the `file` field, `?source=twitter|mastodon`
+/// and `&dry_run=true` to only get the report. The import runs in the
+/// background, its status and report are at `/me/imports/{import_id}`.
+#[post("/me/imports")]
+pub async fn start_import_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    query: web::Query<ImportArchiveDTO>,
+    mut payload: Multipart,
+) -> Result<HttpResponse, HearthError> {
+    let unreadable = || HearthError::Domain(IMPORT_ARCHIVE_UNREADABLE_ERROR_CODE.into());
+
+    let mut field = loop {
+        match payload.next().await {
+            Some(Ok(field)) if field.name() == Some(FILE_FIELD) => break field,
+            Some(Ok(_)) => continue,
+            _ => return Err(unreadable()),
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel(BUFFERED_CHUNKS);
+    let dto = StartImportDTO {
+        query: ImportArchiveDTO {
+            user_id: user.user_id,
+            ..query.into_inner()
+        },
+        archive: Box::new(ChannelByteStream(receiver)),
+    };
+
+    // Fed as it is received, stops once the store gave up on it.
+    let receive = async move {
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map(|bytes| bytes.to_vec()).map_err(|_| unreadable());
+            let failed = chunk.is_err();
+
+            if sender.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    };
+
+    let (_, import) = join(receive, dependencies.start_import.execute(dto)).await;
+    import.map(|import| HttpResponse::Accepted().json(import))
+}
+
+#[get("/me/imports/{import_id}")]
+pub async fn get_import_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    import_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = GetImportDTO {
+        user_id: user.user_id,
+        import_id: import_id.into_inner(),
+    };
+
+    dependencies
+        .get_import
+        .execute(dto)
+        .await
+        .map(|import| HttpResponse::Ok().json(import))
+}
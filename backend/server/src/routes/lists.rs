@@ -0,0 +1,214 @@
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use domain::dtos::{
+    list::{
+        AddListMemberDTO, CreateListDTO, ListAccessDTO, ListTimelineDTO, RemoveListMemberDTO,
+        UpdateListDTO, UserListsDTO,
+    },
+    pagination::PageRequest,
+};
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[post("/lists")]
+pub async fn create_list_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<CreateListDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = CreateListDTO {
+        owner_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .create_list
+        .execute(dto)
+        .await
+        .map(|list| HttpResponse::Created().json(list))
+}
+
+#[put("/lists/{list_id}")]
+pub async fn update_list_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    list_id: web::Path<Uuid>,
+    dto: web::Json<UpdateListDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = UpdateListDTO {
+        list_id: list_id.into_inner(),
+        owner_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .update_list
+        .execute(dto)
+        .await
+        .map(|list| HttpResponse::Ok().json(list))
+}
+
+#[delete("/lists/{list_id}")]
+pub async fn delete_list_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListAccessDTO {
+        list_id: list_id.into_inner(),
+        viewer_id: user.user_id,
+    };
+
+    dependencies
+        .delete_list
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[get("/lists/{list_id}/members")]
+pub async fn list_members_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListAccessDTO {
+        list_id: list_id.into_inner(),
+        viewer_id: user.user_id,
+    };
+
+    dependencies
+        .get_list_members
+        .execute(dto)
+        .await
+        .map(|members| HttpResponse::Ok().json(members))
+}
+
+#[put("/lists/{list_id}/members/{user_id}")]
+pub async fn add_list_member_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+    dto: web::Json<AddListMemberDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let (list_id, member_id) = path.into_inner();
+    let dto = AddListMemberDTO {
+        list_id,
+        owner_id: user.user_id,
+        member_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .add_list_member
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[delete("/lists/{list_id}/members/{user_id}")]
+pub async fn remove_list_member_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, HearthError> {
+    let (list_id, member_id) = path.into_inner();
+    let dto = RemoveListMemberDTO {
+        list_id,
+        owner_id: user.user_id,
+        member_id,
+    };
+
+    dependencies
+        .remove_list_member
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[post("/lists/{list_id}/subscription")]
+pub async fn subscribe_list_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListAccessDTO {
+        list_id: list_id.into_inner(),
+        viewer_id: user.user_id,
+    };
+
+    dependencies
+        .subscribe_list
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[delete("/lists/{list_id}/subscription")]
+pub async fn unsubscribe_list_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListAccessDTO {
+        list_id: list_id.into_inner(),
+        viewer_id: user.user_id,
+    };
+
+    dependencies
+        .unsubscribe_list
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[get("/lists/{list_id}/timeline")]
+pub async fn list_timeline_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    list_id: web::Path<Uuid>,
+    page: web::Query<PageRequest>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListTimelineDTO {
+        list_id: list_id.into_inner(),
+        viewer_id: user.user_id,
+        page: page.into_inner(),
+    };
+
+    dependencies
+        .get_list_timeline
+        .execute(dto)
+        .await
+        .map(|page| HttpResponse::Ok().json(page))
+}
+
+#[get("/users/{user_id}/lists")]
+pub async fn user_lists_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    owner_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = UserListsDTO {
+        owner_id: owner_id.into_inner(),
+        viewer_id: user.user_id,
+    };
+
+    dependencies
+        .get_user_lists
+        .execute(dto)
+        .await
+        .map(|lists| HttpResponse::Ok().json(lists))
+}
+
+#[get("/me/list-subscriptions")]
+pub async fn list_subscriptions_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .get_list_subscriptions
+        .execute(user.user_id)
+        .await
+        .map(|lists| HttpResponse::Ok().json(lists))
+}
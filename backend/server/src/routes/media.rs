@@ -0,0 +1,108 @@
+use actix_multipart::Multipart;
+use actix_web::{HttpResponse, get, http::header, post, put, web};
+use domain::{
+    dtos::media::{MediaFileRequestDTO, SetAvatarDTO, UploadMediaDTO},
+    error_codes::MEDIA_UNREADABLE_ERROR_CODE,
+    policies::media::MediaPolicy,
+};
+use errors::HearthError;
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+pub(crate) const FILE_FIELD: &str = "file";
+
+#[post("/media")]
+pub async fn upload_media_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    payload: Multipart,
+) -> Result<HttpResponse, HearthError> {
+    let dto = UploadMediaDTO {
+        media_id: Uuid::new_v4(),
+        owner_id: user.user_id,
+        bytes: read_file_field(payload, MediaPolicy::default().max_upload_bytes).await?,
+    };
+
+    dependencies
+        .upload_media
+        .execute(dto)
+        .await
+        .map(|media| HttpResponse::Accepted().json(media))
+}
+
+#[get("/media/{media_id}")]
+pub async fn get_media_handler(
+    dependencies: web::Data<Dependencies>,
+    media_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .get_media
+        .execute(media_id.into_inner())
+        .await
+        .map(|media| HttpResponse::Ok().json(media))
+}
+
+#[get("/media/{media_id}/{variant}")]
+pub async fn media_file_handler(
+    dependencies: web::Data<Dependencies>,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse, HearthError> {
+    let (media_id, variant) = path.into_inner();
+    let dto = MediaFileRequestDTO { media_id, variant };
+
+    dependencies.get_media_file.execute(dto).await.map(|file| {
+        HttpResponse::Ok()
+            .content_type(file.mime_type)
+            .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+            .insert_header((header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+            .body(file.bytes)
+    })
+}
+
+#[put("/me/avatar")]
+pub async fn set_avatar_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<SetAvatarDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = SetAvatarDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .set_avatar
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+/// Reads the `file` field, stopping one byte past `max_bytes` so oversized
+/// uploads are rejected by the domain without being buffered entirely.
+async fn read_file_field(mut payload: Multipart, max_bytes: u64) -> Result<Vec<u8>, HearthError> {
+    let unreadable = || HearthError::Domain(MEDIA_UNREADABLE_ERROR_CODE.into());
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|_| unreadable())?;
+
+        if field.name() != Some(FILE_FIELD) {
+            continue;
+        }
+
+        let mut bytes = vec![];
+        while let Some(chunk) = field.next().await {
+            bytes.extend_from_slice(&chunk.map_err(|_| unreadable())?);
+
+            if bytes.len() as u64 > max_bytes {
+                bytes.truncate(max_bytes as usize + 1);
+                break;
+            }
+        }
+
+        return Ok(bytes);
+    }
+
+    Err(unreadable())
+}
@@ -0,0 +1,139 @@
+use actix_web::{HttpResponse, get, post, put, web};
+use domain::dtos::{
+    moderation::{
+        CreateReportDTO, ListModerationLogDTO, ListReportsDTO, ReportState, ResolveReportDTO,
+        SetUserRoleDTO, SuspendUserDTO,
+    },
+    pagination::PageRequest,
+};
+use errors::HearthError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[derive(Deserialize)]
+pub struct ReportStateQuery {
+    #[serde(default)]
+    state: ReportState,
+}
+
+#[post("/reports")]
+pub async fn create_report_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<CreateReportDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = CreateReportDTO {
+        reporter_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .create_report
+        .execute(dto)
+        .await
+        .map(|report| HttpResponse::Created().json(report))
+}
+
+/// Without `state`, lists the open reports.
+#[get("/moderation/reports")]
+pub async fn list_reports_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    state: web::Query<ReportStateQuery>,
+    page: web::Query<PageRequest>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListReportsDTO {
+        moderator_id: user.user_id,
+        state: state.into_inner().state,
+        page: page.into_inner(),
+    };
+
+    dependencies
+        .list_reports
+        .execute(dto)
+        .await
+        .map(|page| HttpResponse::Ok().json(page))
+}
+
+#[post("/moderation/reports/{report_id}/resolve")]
+pub async fn resolve_report_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    report_id: web::Path<Uuid>,
+    dto: web::Json<ResolveReportDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ResolveReportDTO {
+        moderator_id: user.user_id,
+        personal_access_token_id: user.personal_access_token_id,
+        report_id: report_id.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .resolve_report
+        .execute(dto)
+        .await
+        .map(|report| HttpResponse::Ok().json(report))
+}
+
+#[get("/moderation/log")]
+pub async fn list_moderation_log_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    page: web::Query<PageRequest>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListModerationLogDTO {
+        moderator_id: user.user_id,
+        page: page.into_inner(),
+    };
+
+    dependencies
+        .list_moderation_log
+        .execute(dto)
+        .await
+        .map(|page| HttpResponse::Ok().json(page))
+}
+
+#[put("/admin/users/{user_id}/role")]
+pub async fn set_user_role_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    user_id: web::Path<Uuid>,
+    dto: web::Json<SetUserRoleDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = SetUserRoleDTO {
+        admin_id: user.user_id,
+        personal_access_token_id: user.personal_access_token_id,
+        user_id: user_id.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .set_user_role
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[post("/admin/users/{user_id}/suspension")]
+pub async fn suspend_user_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    user_id: web::Path<Uuid>,
+    dto: web::Json<SuspendUserDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = SuspendUserDTO {
+        admin_id: user.user_id,
+        personal_access_token_id: user.personal_access_token_id,
+        user_id: user_id.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .suspend_user
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,23 @@
+use actix_web::{HttpResponse, get, web};
+use domain::dtos::{notification::ListNotificationsDTO, pagination::PageRequest};
+use errors::HearthError;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[get("/notifications")]
+pub async fn list_notifications_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    page: web::Query<PageRequest>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ListNotificationsDTO {
+        user_id: user.user_id,
+        page: page.into_inner(),
+    };
+
+    dependencies
+        .list_notifications
+        .execute(dto)
+        .await
+        .map(|page| HttpResponse::Ok().json(page))
+}
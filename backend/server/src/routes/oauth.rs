@@ -0,0 +1,102 @@
+use actix_web::{HttpResponse, get, post, web};
+use domain::dtos::{
+    oauth::{
+        OAuthAuthorizationRequestDTO, OAuthConsentDecisionDTO, OAuthTokenActionDTO,
+        OAuthTokenRequestDTO, RegisterOAuthAppDTO,
+    },
+    rate_limit::RateLimitScope,
+};
+use errors::HearthError;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies, rate_limit::RateLimit};
+
+/// `client_secret` is only returned here, for apps registered as confidential.
+#[post("/oauth/apps")]
+pub async fn register_oauth_app_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<RegisterOAuthAppDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = RegisterOAuthAppDTO {
+        owner_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .register_oauth_app
+        .execute(dto)
+        .await
+        .map(|app| HttpResponse::Created().json(app))
+}
+
+/// The consent screen forwards the query of the app's authorization request here.
+#[get("/oauth/authorize")]
+pub async fn oauth_consent_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    query: web::Query<OAuthAuthorizationRequestDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = OAuthAuthorizationRequestDTO {
+        user_id: user.user_id,
+        ..query.into_inner()
+    };
+
+    dependencies
+        .get_oauth_consent
+        .execute(dto)
+        .await
+        .map(|consent| HttpResponse::Ok().json(consent))
+}
+
+/// Same request with the user's decision, the client then opens `redirect_uri`.
+#[post("/oauth/authorize")]
+pub async fn oauth_decision_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<OAuthConsentDecisionDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let mut dto = dto.into_inner();
+    dto.request.user_id = user.user_id;
+
+    dependencies
+        .decide_oauth_consent
+        .execute(dto)
+        .await
+        .map(|redirect| HttpResponse::Ok().json(redirect))
+}
+
+#[post("/oauth/token", wrap = "RateLimit::new(RateLimitScope::Login)")]
+pub async fn oauth_token_handler(
+    dependencies: web::Data<Dependencies>,
+    form: web::Form<OAuthTokenRequestDTO>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .exchange_oauth_token
+        .execute(form.into_inner())
+        .await
+        .map(|token| HttpResponse::Ok().json(token))
+}
+
+#[post("/oauth/introspect")]
+pub async fn oauth_introspect_handler(
+    dependencies: web::Data<Dependencies>,
+    form: web::Form<OAuthTokenActionDTO>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .introspect_oauth_token
+        .execute(form.into_inner())
+        .await
+        .map(|introspection| HttpResponse::Ok().json(introspection))
+}
+
+#[post("/oauth/revoke")]
+pub async fn oauth_revoke_handler(
+    dependencies: web::Data<Dependencies>,
+    form: web::Form<OAuthTokenActionDTO>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .revoke_oauth_token
+        .execute(form.into_inner())
+        .await
+        .map(|_| HttpResponse::Ok().finish())
+}
@@ -0,0 +1,71 @@
+use actix_web::{HttpResponse, get, post, web};
+use domain::dtos::{
+    oidc::{CompleteOidcSignupDTO, OidcCallbackDTO, OidcLinkDTO},
+    rate_limit::RateLimitScope,
+};
+use errors::HearthError;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies, rate_limit::RateLimit};
+
+/// The client opens `authorization_url` and keeps `state` to match the redirect.
+#[get("/oidc/{provider}/authorize")]
+pub async fn oidc_authorize_handler(
+    dependencies: web::Data<Dependencies>,
+    provider: web::Path<String>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .start_oidc_login
+        .execute(provider.into_inner())
+        .await
+        .map(|authorization| HttpResponse::Ok().json(authorization))
+}
+
+/// Like `oidc_authorize_handler`, the callback then links the provider to the logged-in user.
+#[post("/me/oidc/{provider}/link")]
+pub async fn oidc_link_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    provider: web::Path<String>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = OidcLinkDTO {
+        user_id: user.user_id,
+        provider: provider.into_inner(),
+    };
+
+    dependencies
+        .start_oidc_link
+        .execute(dto)
+        .await
+        .map(|authorization| HttpResponse::Ok().json(authorization))
+}
+
+/// `code` and `state` are the query parameters the provider redirected back with.
+#[post("/oidc/{provider}/callback", wrap = "RateLimit::new(RateLimitScope::Login)")]
+pub async fn oidc_callback_handler(
+    dependencies: web::Data<Dependencies>,
+    provider: web::Path<String>,
+    dto: web::Json<OidcCallbackDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = OidcCallbackDTO {
+        provider: provider.into_inner(),
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .finish_oidc_login
+        .execute(dto)
+        .await
+        .map(|result| HttpResponse::Ok().json(result))
+}
+
+#[post("/oidc/signup", wrap = "RateLimit::new(RateLimitScope::Signup)")]
+pub async fn oidc_signup_handler(
+    dependencies: web::Data<Dependencies>,
+    dto: web::Json<CompleteOidcSignupDTO>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .complete_oidc_signup
+        .execute(dto.into_inner())
+        .await
+        .map(|session| HttpResponse::Created().json(session))
+}
@@ -0,0 +1,63 @@
+use actix_web::{HttpResponse, post, web};
+use domain::dtos::{
+    passkey::{LoginPasskeyDTO, PasskeyLoginOptionsRequestDTO, RegisterPasskeyDTO},
+    rate_limit::RateLimitScope,
+};
+use errors::HearthError;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies, rate_limit::RateLimit};
+
+#[post("/me/passkeys/options")]
+pub async fn passkey_registration_options_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .start_passkey_registration
+        .execute(user.user_id)
+        .await
+        .map(|options| HttpResponse::Ok().json(options))
+}
+
+#[post("/me/passkeys")]
+pub async fn register_passkey_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<RegisterPasskeyDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = RegisterPasskeyDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .register_passkey
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::Created().finish())
+}
+
+/// Send the `challenge_token` of a password login to use the passkey as a second factor.
+#[post("/login/passkey/options", wrap = "RateLimit::new(RateLimitScope::Login)")]
+pub async fn passkey_login_options_handler(
+    dependencies: web::Data<Dependencies>,
+    dto: Option<web::Json<PasskeyLoginOptionsRequestDTO>>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .start_passkey_login
+        .execute(dto.map(|dto| dto.into_inner()).unwrap_or_default())
+        .await
+        .map(|options| HttpResponse::Ok().json(options))
+}
+
+#[post("/login/passkey", wrap = "RateLimit::new(RateLimitScope::Login)")]
+pub async fn login_passkey_handler(
+    dependencies: web::Data<Dependencies>,
+    dto: web::Json<LoginPasskeyDTO>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .login_with_passkey
+        .execute(dto.into_inner())
+        .await
+        .map(|session| HttpResponse::Ok().json(session))
+}
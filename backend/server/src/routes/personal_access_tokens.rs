@@ -0,0 +1,57 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use domain::dtos::personal_access_token::{
+    CreatePersonalAccessTokenDTO, RevokePersonalAccessTokenDTO,
+};
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+/// The token is only shown in this response, the server keeps its hash.
+#[post("/me/tokens")]
+pub async fn create_personal_access_token_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<CreatePersonalAccessTokenDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = CreatePersonalAccessTokenDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .create_personal_access_token
+        .execute(dto)
+        .await
+        .map(|token| HttpResponse::Created().json(token))
+}
+
+#[get("/me/tokens")]
+pub async fn list_personal_access_tokens_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .list_personal_access_tokens
+        .execute(user.user_id)
+        .await
+        .map(|tokens| HttpResponse::Ok().json(tokens))
+}
+
+#[delete("/me/tokens/{token_id}")]
+pub async fn revoke_personal_access_token_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    token_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = RevokePersonalAccessTokenDTO {
+        user_id: user.user_id,
+        token_id: token_id.into_inner(),
+    };
+
+    dependencies
+        .revoke_personal_access_token
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,44 @@
+use actix_web::{HttpResponse, get, post, web};
+use domain::dtos::poll::{GetPollDTO, VotePollDTO};
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[get("/posts/{post_id}/poll")]
+pub async fn get_poll_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = GetPollDTO {
+        post_id: post_id.into_inner(),
+        viewer_id: user.user_id,
+    };
+
+    dependencies
+        .get_poll
+        .execute(dto)
+        .await
+        .map(|poll| HttpResponse::Ok().json(poll))
+}
+
+#[post("/posts/{post_id}/poll/votes")]
+pub async fn vote_poll_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+    dto: web::Json<VotePollDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = VotePollDTO {
+        post_id: post_id.into_inner(),
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .vote_poll
+        .execute(dto)
+        .await
+        .map(|poll| HttpResponse::Ok().json(poll))
+}
@@ -0,0 +1,46 @@
+use actix_web::{HttpResponse, get, post, web};
+use domain::dtos::{
+    pagination::PageRequest,
+    post::{CreatePostDTO, HashtagTimelineDTO},
+    rate_limit::RateLimitScope,
+};
+use errors::HearthError;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies, rate_limit::RateLimit};
+
+#[post("/posts", wrap = "RateLimit::new(RateLimitScope::Posting)")]
+pub async fn create_post_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<CreatePostDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = CreatePostDTO {
+        author_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    let post = dependencies.create_post.execute(dto).await?;
+
+    Ok(HttpResponse::Created().json(post))
+}
+
+/// Public, but a logged in viewer gets their keyword filters applied.
+#[get("/tags/{tag}")]
+pub async fn hashtag_timeline_handler(
+    dependencies: web::Data<Dependencies>,
+    viewer: Option<AuthenticatedUser>,
+    tag: web::Path<String>,
+    page: web::Query<PageRequest>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = HashtagTimelineDTO {
+        tag: tag.into_inner(),
+        viewer_id: viewer.map(|AuthenticatedUser(user)| user.user_id),
+        page: page.into_inner(),
+    };
+
+    dependencies
+        .get_hashtag_timeline
+        .execute(dto)
+        .await
+        .map(|page| HttpResponse::Ok().json(page))
+}
@@ -0,0 +1,24 @@
+use actix_web::{HttpResponse, get, web};
+use errors::HearthError;
+use serde::Deserialize;
+
+use crate::bootstrap::Dependencies;
+
+const DEFAULT_TRENDS_LIMIT: u64 = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct TrendsQuery {
+    pub limit: Option<u64>,
+}
+
+#[get("/trends")]
+pub async fn trends_handler(
+    dependencies: web::Data<Dependencies>,
+    query: web::Query<TrendsQuery>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .get_trends
+        .execute(query.limit.unwrap_or(DEFAULT_TRENDS_LIMIT))
+        .await
+        .map(|trends| HttpResponse::Ok().json(trends))
+}
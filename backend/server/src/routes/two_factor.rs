@@ -0,0 +1,54 @@
+use actix_web::{HttpResponse, post, web};
+use domain::dtos::two_factor::{ConfirmTwoFactorDTO, DisableTwoFactorDTO};
+use errors::HearthError;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies};
+
+#[post("/me/2fa/enroll")]
+pub async fn enroll_two_factor_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .enroll_two_factor
+        .execute(user.user_id)
+        .await
+        .map(|enrollment| HttpResponse::Created().json(enrollment))
+}
+
+/// Answers with the recovery codes, they are never shown again.
+#[post("/me/2fa/confirm")]
+pub async fn confirm_two_factor_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<ConfirmTwoFactorDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = ConfirmTwoFactorDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .confirm_two_factor
+        .execute(dto)
+        .await
+        .map(|recovery_codes| HttpResponse::Ok().json(recovery_codes))
+}
+
+#[post("/me/2fa/disable")]
+pub async fn disable_two_factor_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<DisableTwoFactorDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = DisableTwoFactorDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .disable_two_factor
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
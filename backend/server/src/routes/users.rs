@@ -0,0 +1,77 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use domain::dtos::{
+    rate_limit::RateLimitScope,
+    user::{DeleteAccountDTO, VerifyEmailDTO},
+};
+use errors::HearthError;
+use uuid::Uuid;
+
+use crate::{auth::AuthenticatedUser, bootstrap::Dependencies, rate_limit::RateLimit};
+
+#[get("/users/{user_id}")]
+pub async fn get_profile_handler(
+    dependencies: web::Data<Dependencies>,
+    user_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .get_profile
+        .execute(user_id.into_inner())
+        .await
+        .map(|profile| HttpResponse::Ok().json(profile))
+}
+
+/// Deactivates the account, it is purged once the grace period is over.
+#[delete("/me")]
+pub async fn delete_account_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<DeleteAccountDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = DeleteAccountDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .delete_account
+        .execute(dto)
+        .await
+        .map(|deletion| HttpResponse::Ok().json(deletion))
+}
+
+/// Emails a code to verify the address of the account.
+#[post(
+    "/me/email/verification",
+    wrap = "RateLimit::new(RateLimitScope::VerificationCode)"
+)]
+pub async fn send_email_verification_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<HttpResponse, HearthError> {
+    dependencies
+        .send_email_verification
+        .execute(user.user_id)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+#[post(
+    "/me/email/verify",
+    wrap = "RateLimit::new(RateLimitScope::VerificationCode)"
+)]
+pub async fn verify_email_handler(
+    dependencies: web::Data<Dependencies>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    dto: web::Json<VerifyEmailDTO>,
+) -> Result<HttpResponse, HearthError> {
+    let dto = VerifyEmailDTO {
+        user_id: user.user_id,
+        ..dto.into_inner()
+    };
+
+    dependencies
+        .verify_email
+        .execute(dto)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
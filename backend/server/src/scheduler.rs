@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use domain::{dtos::job::JobPayload, features::feature::Feature};
+
+use crate::bootstrap::Dependencies;
+
+const POLLS_INTERVAL: Duration = Duration::from_secs(60);
+const ACCOUNT_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const DATA_EXPORTS_INTERVAL: Duration = Duration::from_secs(60);
+const JOBS_INTERVAL: Duration = Duration::from_secs(2);
+const EVENTS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Processes one batch of whatever is due at the given instant, returns how
+/// much of it there was.
+type BatchFeature = dyn Feature<DateTime<Utc>, u64>;
+
+/// Runs the feature picked by `select` every `period` on the current actix
+/// runtime, until the process exits. Full batches are followed by another one
+/// right away so a backlog drains without waiting for the next tick. A failed
+/// batch is tried again at the next tick, the jobs keep their own errors.
+fn spawn_periodic(
+    dependencies: web::Data<Dependencies>,
+    period: Duration,
+    select: fn(&Dependencies) -> &BatchFeature,
+) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(period);
+
+        loop {
+            interval.tick().await;
+
+            loop {
+                match select(&dependencies).execute(Utc::now()).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => continue,
+                }
+            }
+        }
+    });
+}
+
+/// Queues `payload` every `period` on the current actix runtime. Every worker
+/// does, the job runner of whichever claims it runs the batch.
+fn spawn_periodic_job(
+    dependencies: web::Data<Dependencies>,
+    period: Duration,
+    payload: JobPayload,
+) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(period);
+
+        loop {
+            interval.tick().await;
+            // Queued at the next tick if the queue is unreachable.
+            let _ = dependencies
+                .schedule_periodic_job
+                .execute(payload.clone())
+                .await;
+        }
+    });
+}
+
+/// Periodically notifies the voters of ended polls.
+pub fn spawn_poll_notifier(dependencies: web::Data<Dependencies>) {
+    spawn_periodic_job(dependencies, POLLS_INTERVAL, JobPayload::NotifyEndedPolls);
+}
+
+/// Periodically purges the accounts whose deletion grace period is over.
+pub fn spawn_account_purger(dependencies: web::Data<Dependencies>) {
+    spawn_periodic_job(
+        dependencies,
+        ACCOUNT_PURGE_INTERVAL,
+        JobPayload::PurgeDeletedAccounts,
+    );
+}
+
+/// Periodically builds the requested data exports and deletes the expired ones.
+pub fn spawn_data_export_builder(dependencies: web::Data<Dependencies>) {
+    spawn_periodic_job(
+        dependencies,
+        DATA_EXPORTS_INTERVAL,
+        JobPayload::BuildDataExports,
+    );
+}
+
+/// Polls the job queue, every worker claims its own batches so several of them
+/// can run side by side.
+pub fn spawn_job_runner(dependencies: web::Data<Dependencies>) {
+    spawn_periodic(dependencies, JOBS_INTERVAL, |dependencies| {
+        dependencies.run_jobs.as_ref()
+    });
+}
+
+/// Dispatches the outbox events to their subscribers and to Redis.
+pub fn spawn_event_dispatcher(dependencies: web::Data<Dependencies>) {
+    spawn_periodic(dependencies, EVENTS_INTERVAL, |dependencies| {
+        dependencies.dispatch_events.as_ref()
+    });
+}
@@ -1,14 +1,165 @@
 use actix_web::{App, HttpServer, web};
 
-use crate::{bootstrap::Dependencies, routes::signup_email_handler};
+use crate::{
+    bootstrap::Dependencies,
+    routes::{
+        auth::{login_email_handler, login_two_factor_handler},
+        blocks::{block_user_handler, unblock_user_handler},
+        bookmarks::{
+            bookmark_post_handler, create_bookmark_collection_handler,
+            list_bookmark_collections_handler, list_bookmarks_handler, move_bookmark_handler,
+            remove_bookmark_handler,
+        },
+        conversations::{
+            acknowledge_envelope_handler, get_dm_settings_handler, list_conversations_handler,
+            list_envelopes_handler, list_messages_handler, mark_conversation_read_handler,
+            send_encrypted_message_handler, send_message_handler, start_conversation_handler,
+            update_dm_settings_handler, websocket_handler,
+        },
+        data_exports::{download_data_export_handler, request_data_export_handler},
+        device_keys::{
+            claim_prekeys_handler, prekey_count_handler, upload_device_keys_handler,
+            upload_prekeys_handler,
+        },
+        filters::{
+            create_keyword_filter_handler, delete_keyword_filter_handler,
+            list_keyword_filters_handler,
+        },
+        imports::{get_import_handler, start_import_handler},
+        lists::{
+            add_list_member_handler, create_list_handler, delete_list_handler,
+            list_members_handler, list_subscriptions_handler, list_timeline_handler,
+            remove_list_member_handler, subscribe_list_handler, unsubscribe_list_handler,
+            update_list_handler, user_lists_handler,
+        },
+        media::{
+            get_media_handler, media_file_handler, set_avatar_handler, upload_media_handler,
+        },
+        moderation::{
+            create_report_handler, list_moderation_log_handler, list_reports_handler,
+            resolve_report_handler, set_user_role_handler, suspend_user_handler,
+        },
+        notifications::list_notifications_handler,
+        oauth::{
+            oauth_consent_handler, oauth_decision_handler, oauth_introspect_handler,
+            oauth_revoke_handler, oauth_token_handler, register_oauth_app_handler,
+        },
+        oidc::{
+            oidc_authorize_handler, oidc_callback_handler, oidc_link_handler, oidc_signup_handler,
+        },
+        passkeys::{
+            login_passkey_handler, passkey_login_options_handler,
+            passkey_registration_options_handler, register_passkey_handler,
+        },
+        personal_access_tokens::{
+            create_personal_access_token_handler, list_personal_access_tokens_handler,
+            revoke_personal_access_token_handler,
+        },
+        polls::{get_poll_handler, vote_poll_handler},
+        posts::{create_post_handler, hashtag_timeline_handler},
+        signup_challenge_handler, signup_email_handler,
+        trends::trends_handler,
+        two_factor::{
+            confirm_two_factor_handler, disable_two_factor_handler, enroll_two_factor_handler,
+        },
+        users::{
+            delete_account_handler, get_profile_handler, send_email_verification_handler,
+            verify_email_handler,
+        },
+    },
+};
 
-pub async fn build_server(dependencies: Dependencies, port: u16) -> std::io::Result<()> {
-    // Data is a wrapper around Arc, so we can clone it.
-    let data = web::Data::new(dependencies);
+/// `Data` is a wrapper around Arc, so the dependencies can be shared with
+/// background tasks as well.
+pub async fn build_server(data: web::Data<Dependencies>, port: u16) -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(data.clone())
             .service(signup_email_handler)
+            .service(signup_challenge_handler)
+            .service(login_email_handler)
+            .service(login_two_factor_handler)
+            .service(passkey_login_options_handler)
+            .service(login_passkey_handler)
+            .service(oidc_authorize_handler)
+            .service(oidc_callback_handler)
+            .service(oidc_signup_handler)
+            .service(register_oauth_app_handler)
+            .service(oauth_consent_handler)
+            .service(oauth_decision_handler)
+            .service(oauth_token_handler)
+            .service(oauth_introspect_handler)
+            .service(oauth_revoke_handler)
+            .service(create_post_handler)
+            .service(hashtag_timeline_handler)
+            .service(trends_handler)
+            .service(block_user_handler)
+            .service(unblock_user_handler)
+            .service(list_notifications_handler)
+            .service(upload_media_handler)
+            .service(get_media_handler)
+            .service(media_file_handler)
+            .service(set_avatar_handler)
+            .service(get_poll_handler)
+            .service(vote_poll_handler)
+            .service(create_bookmark_collection_handler)
+            .service(list_bookmark_collections_handler)
+            .service(list_bookmarks_handler)
+            .service(bookmark_post_handler)
+            .service(remove_bookmark_handler)
+            .service(move_bookmark_handler)
+            .service(create_list_handler)
+            .service(update_list_handler)
+            .service(delete_list_handler)
+            .service(list_members_handler)
+            .service(add_list_member_handler)
+            .service(remove_list_member_handler)
+            .service(subscribe_list_handler)
+            .service(unsubscribe_list_handler)
+            .service(list_timeline_handler)
+            .service(user_lists_handler)
+            .service(list_subscriptions_handler)
+            .service(start_conversation_handler)
+            .service(list_conversations_handler)
+            .service(list_messages_handler)
+            .service(send_message_handler)
+            .service(mark_conversation_read_handler)
+            .service(get_dm_settings_handler)
+            .service(update_dm_settings_handler)
+            .service(websocket_handler)
+            .service(upload_device_keys_handler)
+            .service(upload_prekeys_handler)
+            .service(prekey_count_handler)
+            .service(claim_prekeys_handler)
+            .service(send_encrypted_message_handler)
+            .service(list_envelopes_handler)
+            .service(acknowledge_envelope_handler)
+            .service(create_report_handler)
+            .service(list_reports_handler)
+            .service(resolve_report_handler)
+            .service(list_moderation_log_handler)
+            .service(set_user_role_handler)
+            .service(suspend_user_handler)
+            .service(get_profile_handler)
+            .service(delete_account_handler)
+            .service(send_email_verification_handler)
+            .service(verify_email_handler)
+            .service(request_data_export_handler)
+            .service(download_data_export_handler)
+            .service(start_import_handler)
+            .service(get_import_handler)
+            .service(create_keyword_filter_handler)
+            .service(list_keyword_filters_handler)
+            .service(delete_keyword_filter_handler)
+            .service(enroll_two_factor_handler)
+            .service(confirm_two_factor_handler)
+            .service(disable_two_factor_handler)
+            .service(passkey_registration_options_handler)
+            .service(register_passkey_handler)
+            .service(oidc_link_handler)
+            .service(create_personal_access_token_handler)
+            .service(list_personal_access_tokens_handler)
+            .service(revoke_personal_access_token_handler)
     })
     .bind(("127.0.0.1", port))?
     .run()
@@ -0,0 +1,154 @@
+use std::{ io::ErrorKind, path::{ Path, PathBuf } };
+
+use async_trait::async_trait;
+use domain::{
+    error_codes::MEDIA_NOT_FOUND_ERROR_CODE,
+    repositories::object_store::{ ByteStream, ObjectStore },
+};
+use errors::HearthError;
+use tokio::{ fs, io::AsyncWriteExt };
+use uuid::Uuid;
+
+use crate::{ database::unexpected, storage::validate_key };
+
+/// Stores objects as plain files below `root`, one file per key.
+pub struct LocalObjectStore {
+    root: PathBuf,
+}
+
+impl LocalObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_of(&self, key: &str) -> Result<PathBuf, HearthError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    /// Creates the parent directories, returns a temporary path next to the
+    /// file to write to before renaming it.
+    async fn prepare(&self, path: &Path) -> Result<PathBuf, HearthError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(unexpected("OBJECT_STORE_PUT_ERROR"))?;
+        }
+
+        Ok(path.with_extension(format!("{}.tmp", Uuid::new_v4().simple())))
+    }
+}
+
+async fn write_stream(path: &Path, mut stream: Box<dyn ByteStream>) -> Result<u64, HearthError> {
+    let mut file = fs::File::create(path).await.map_err(unexpected("OBJECT_STORE_PUT_ERROR"))?;
+    let mut size = 0;
+
+    while let Some(chunk) = stream.next_chunk().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await.map_err(unexpected("OBJECT_STORE_PUT_ERROR"))?;
+        size += chunk.len() as u64;
+    }
+
+    file.flush().await.map_err(unexpected("OBJECT_STORE_PUT_ERROR"))?;
+    Ok(size)
+}
+
+#[async_trait]
+impl ObjectStore for LocalObjectStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), HearthError> {
+        let path = self.path_of(key)?;
+
+        // Write then rename so readers never see a partially written file.
+        let temporary = self.prepare(&path).await?;
+        fs::write(&temporary, bytes).await.map_err(unexpected("OBJECT_STORE_PUT_ERROR"))?;
+        fs::rename(&temporary, &path).await.map_err(unexpected("OBJECT_STORE_PUT_ERROR"))
+    }
+
+    async fn put_stream(
+        &self,
+        key: &str,
+        stream: Box<dyn ByteStream>,
+        _content_type: &str
+    ) -> Result<u64, HearthError> {
+        let path = self.path_of(key)?;
+        let temporary = self.prepare(&path).await?;
+
+        match write_stream(&temporary, stream).await {
+            Ok(size) => {
+                fs::rename(&temporary, &path).await.map_err(unexpected("OBJECT_STORE_PUT_ERROR"))?;
+                Ok(size)
+            }
+            Err(e) => {
+                fs::remove_file(&temporary).await.ok();
+                Err(e)
+            }
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, HearthError> {
+        match fs::read(self.path_of(key)?).await {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()))
+            }
+            Err(e) => Err(unexpected("OBJECT_STORE_GET_ERROR")(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), HearthError> {
+        match fs::remove_file(self.path_of(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                Err(unexpected("OBJECT_STORE_DELETE_ERROR")(e))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{ error_codes::MEDIA_NOT_FOUND_ERROR_CODE, repositories::object_store::ObjectStore };
+    use errors::HearthError;
+    use uuid::Uuid;
+
+    use super::LocalObjectStore;
+    use crate::storage::stream_of;
+
+    #[actix_web::test]
+    async fn should_put_get_and_delete_objects() {
+        let root = std::env::temp_dir().join(format!("hearth-{}", Uuid::new_v4()));
+        let store = LocalObjectStore::new(&root);
+        let not_found = HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into());
+
+        store.put("media/a/original", b"bytes".to_vec(), "image/jpeg").await.unwrap();
+        assert_eq!(store.get("media/a/original").await.unwrap(), b"bytes");
+
+        store.delete("media/a/original").await.unwrap();
+        assert_eq!(store.get("media/a/original").await.unwrap_err(), not_found);
+
+        for key in ["../secret", "/etc/passwd", "media//x", "media/./x", ""] {
+            assert_eq!(store.get(key).await.unwrap_err(), not_found);
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[actix_web::test]
+    async fn should_write_streams_whole_or_not_at_all() {
+        let root = std::env::temp_dir().join(format!("hearth-{}", Uuid::new_v4()));
+        let store = LocalObjectStore::new(&root);
+        let cut_short = || HearthError::unexpected("UPLOAD_ERROR".into(), None);
+
+        let stream = stream_of(vec![Ok(b"PK".to_vec()), Ok(b"archive".to_vec())]);
+        assert_eq!(store.put_stream("imports/a", stream, "application/zip").await.unwrap(), 9);
+        assert_eq!(store.get("imports/a").await.unwrap(), b"PKarchive");
+
+        let stream = stream_of(vec![Ok(b"PK".to_vec()), Err(cut_short())]);
+        assert_eq!(
+            store.put_stream("imports/b", stream, "application/zip").await.unwrap_err(),
+            cut_short()
+        );
+        assert!(store.get("imports/b").await.is_err());
+        assert_eq!(std::fs::read_dir(root.join("imports")).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,44 @@
+pub mod local_object_store;
+pub mod s3_object_store;
+
+use async_trait::async_trait;
+use domain::{ error_codes::MEDIA_NOT_FOUND_ERROR_CODE, repositories::object_store::ByteStream };
+use errors::HearthError;
+use tokio::sync::mpsc;
+
+/// Object keys come from the domain (`media/{id}/{variant}`), this only guards
+/// against a key ever escaping the store root.
+pub(crate) fn validate_key(key: &str) -> Result<(), HearthError> {
+    let valid =
+        !key.is_empty() &&
+        !key.starts_with('/') &&
+        !key.contains('\\') &&
+        key.split('/').all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+
+    if !valid {
+        return Err(HearthError::not_found(MEDIA_NOT_FOUND_ERROR_CODE.into()));
+    }
+
+    Ok(())
+}
+
+/// Reads what is sent on the other end, so a request handler can feed an upload
+/// to the object store as it is received.
+pub struct ChannelByteStream(pub mpsc::Receiver<Result<Vec<u8>, HearthError>>);
+
+#[async_trait]
+impl ByteStream for ChannelByteStream {
+    async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, HearthError>> {
+        self.0.recv().await
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn stream_of(chunks: Vec<Result<Vec<u8>, HearthError>>) -> Box<dyn ByteStream> {
+    let (sender, receiver) = mpsc::channel(chunks.len().max(1));
+    for chunk in chunks {
+        sender.try_send(chunk).unwrap();
+    }
+
+    Box::new(ChannelByteStream(receiver))
+}
@@ -0,0 +1,59 @@
+use std::io::{ Cursor, Write };
+
+use async_trait::async_trait;
+use domain::{ dtos::data_export::ArchiveFileDTO, repositories::archive_writer::ArchiveWriter };
+use errors::HearthError;
+use zip::{ CompressionMethod, ZipWriter, result::ZipError, write::SimpleFileOptions };
+
+use crate::database::unexpected;
+
+/// Adapter over the `zip` crate. Compression is CPU bound so it runs on the
+/// blocking thread pool.
+pub struct ZipArchiveWriter;
+
+pub(crate) fn write_zip(files: Vec<ArchiveFileDTO>) -> Result<Vec<u8>, ZipError> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for file in files {
+        writer.start_file(file.path, options)?;
+        writer.write_all(&file.bytes)?;
+    }
+
+    Ok(writer.finish()?.into_inner())
+}
+
+#[async_trait]
+impl ArchiveWriter for ZipArchiveWriter {
+    async fn write(&self, files: Vec<ArchiveFileDTO>) -> Result<Vec<u8>, HearthError> {
+        tokio::task
+            ::spawn_blocking(move || write_zip(files)).await
+            .map_err(unexpected("ARCHIVE_WRITE_ERROR"))?
+            .map_err(unexpected("ARCHIVE_WRITE_ERROR"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use zip::ZipArchive;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_zip_every_file_at_its_path() {
+        let files = vec![
+            ArchiveFileDTO { path: "profile.json".into(), bytes: b"{}".to_vec() },
+            ArchiveFileDTO { path: "media/1/original".into(), bytes: vec![1, 2, 3] }
+        ];
+
+        let bytes = ZipArchiveWriter.write(files).await.unwrap();
+
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut content = Vec::new();
+        archive.by_name("media/1/original").unwrap().read_to_end(&mut content).unwrap();
+        assert_eq!(content, vec![1, 2, 3]);
+    }
+}
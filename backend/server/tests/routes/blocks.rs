@@ -0,0 +1,47 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::blocks::{block_user_handler, unblock_user_handler};
+
+use crate::utils::{bearer, build_dependencies};
+
+const USER_URI: &str = "/users/0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11/block";
+
+#[actix_web::test]
+async fn should_be_able_to_block_and_unblock_a_user() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(block_user_handler)
+            .service(unblock_user_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(USER_URI)
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::delete()
+        .uri(USER_URI)
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+#[actix_web::test]
+async fn should_not_block_without_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(block_user_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post().uri(USER_URI).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
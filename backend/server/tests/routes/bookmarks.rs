@@ -0,0 +1,82 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::bookmarks::{
+    bookmark_post_handler, create_bookmark_collection_handler, list_bookmarks_handler,
+    move_bookmark_handler, remove_bookmark_handler,
+};
+
+use crate::utils::{bearer, build_dependencies};
+
+const POST_ID: &str = "0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11";
+const COLLECTION_ID: &str = "5f0c1d9e-7b1a-4c38-9d2e-3a4b5c6d7e8f";
+
+#[actix_web::test]
+async fn should_be_able_to_manage_bookmarks() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_bookmark_collection_handler)
+            .service(bookmark_post_handler)
+            .service(move_bookmark_handler)
+            .service(remove_bookmark_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/bookmark-collections")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "collection_id": COLLECTION_ID, "name": "Recipes" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/me/bookmarks/{}", POST_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "collection_id": COLLECTION_ID }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["post_id"], POST_ID);
+    assert_eq!(body["collection_id"], COLLECTION_ID);
+    assert!(body.get("position").is_none());
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/me/bookmarks/{}/position", POST_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "position": 0 }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/me/bookmarks/{}", POST_ID))
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+#[actix_web::test]
+async fn should_list_bookmarks_only_with_a_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(list_bookmarks_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/me/bookmarks?collection_id={}&limit=10", COLLECTION_ID))
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::get().uri("/me/bookmarks").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
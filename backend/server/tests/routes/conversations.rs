@@ -0,0 +1,92 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::conversations::{
+    send_message_handler, start_conversation_handler, update_dm_settings_handler,
+    websocket_handler,
+};
+
+use crate::utils::{TEST_USER_ID, bearer, build_dependencies};
+
+const CONVERSATION_ID: &str = "5b0e4c2a-8d1f-4e3b-9a6c-2f7d1e0b3c4a";
+const MESSAGE_ID: &str = "9e8d7c6b-5a4f-4e3d-8c2b-1a0f9e8d7c6b";
+const PARTICIPANT_ID: &str = "1f2e3d4c-5b6a-4978-8695-a4b3c2d1e0f9";
+
+#[actix_web::test]
+async fn should_be_able_to_start_a_conversation_and_send_a_message() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(start_conversation_handler)
+            .service(send_message_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/conversations")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({
+            "conversation_id": CONVERSATION_ID,
+            "participant_ids": [PARTICIPANT_ID],
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["conversation_id"], CONVERSATION_ID);
+    assert_eq!(
+        body["participant_ids"],
+        serde_json::json!([TEST_USER_ID.to_string(), PARTICIPANT_ID])
+    );
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/conversations/{}/messages", CONVERSATION_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "message_id": MESSAGE_ID, "content": "Hello" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["conversation_id"], CONVERSATION_ID);
+    assert_eq!(body["sender_id"], TEST_USER_ID.to_string());
+    assert_eq!(body["content"], "Hello");
+}
+
+#[actix_web::test]
+async fn should_be_able_to_update_dm_settings() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(update_dm_settings_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/me/dm-settings")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "dm_permission": "following" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["dm_permission"], "following");
+}
+
+#[actix_web::test]
+async fn should_not_open_a_websocket_without_a_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(App::new().app_data(dependencies).service(websocket_handler)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/ws")
+        .insert_header(("upgrade", "websocket"))
+        .insert_header(("connection", "upgrade"))
+        .insert_header(("sec-websocket-version", "13"))
+        .insert_header(("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
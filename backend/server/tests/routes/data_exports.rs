@@ -0,0 +1,66 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::data_exports::{download_data_export_handler, request_data_export_handler};
+
+use crate::utils::{TEST_PERSONAL_ACCESS_TOKEN, bearer, build_dependencies};
+
+const EXPORT_ID: &str = "5f0c2a1e-7b3d-4c8e-9a6f-0d1e2f3a4b5c";
+
+#[actix_web::test]
+async fn should_request_an_export_with_a_session_only() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(request_data_export_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/exports")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["status"], "pending");
+
+    let req = test::TestRequest::post()
+        .uri("/me/exports")
+        .insert_header(("Authorization", format!("Bearer {}", TEST_PERSONAL_ACCESS_TOKEN)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn should_download_an_export_with_the_emailed_token() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(download_data_export_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/exports/{}?token=secret", EXPORT_ID))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "application/zip");
+    assert!(
+        resp.headers()
+            .get("Content-Disposition")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("attachment")
+    );
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/exports/{}", EXPORT_ID))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,78 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::{
+    conversations::send_encrypted_message_handler,
+    device_keys::{claim_prekeys_handler, upload_device_keys_handler},
+};
+
+use crate::utils::{TEST_USER_ID, bearer, build_dependencies};
+
+const DEVICE_ID: &str = "0a9b8c7d-6e5f-4a3b-9c2d-1e0f9a8b7c6d";
+const CONVERSATION_ID: &str = "5b0e4c2a-8d1f-4e3b-9a6c-2f7d1e0b3c4a";
+const MESSAGE_ID: &str = "9e8d7c6b-5a4f-4e3d-8c2b-1a0f9e8d7c6b";
+const RECIPIENT_ID: &str = "1f2e3d4c-5b6a-4978-8695-a4b3c2d1e0f9";
+const RECIPIENT_DEVICE_ID: &str = "6d5c4b3a-2f1e-4d0c-9b8a-7f6e5d4c3b2a";
+
+#[actix_web::test]
+async fn should_be_able_to_publish_and_claim_device_keys() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(upload_device_keys_handler)
+            .service(claim_prekeys_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/me/devices/{}/keys", DEVICE_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({
+            "identity_key": "aWRlbnRpdHk",
+            "signing_key": "c2lnbmluZw",
+            "signed_prekey": { "key_id": "AAAAAQ", "public_key": "cHJla2V5", "signature": "c2ln" },
+            "one_time_prekeys": [{ "key_id": "AAAAAg", "public_key": "b3Rr" }],
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/users/{}/prekeys/claim", RECIPIENT_ID))
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn should_be_able_to_send_an_encrypted_message() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(send_encrypted_message_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/conversations/{}/encrypted-messages", CONVERSATION_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({
+            "message_id": MESSAGE_ID,
+            "sender_device_id": DEVICE_ID,
+            "envelopes": [{
+                "recipient_id": RECIPIENT_ID,
+                "recipient_device_id": RECIPIENT_DEVICE_ID,
+                "message_type": 0,
+                "ciphertext": "b3BhcXVl",
+            }],
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["conversation_id"], CONVERSATION_ID);
+    assert_eq!(body["sender_id"], TEST_USER_ID.to_string());
+    assert_eq!(body["sender_device_id"], DEVICE_ID);
+}
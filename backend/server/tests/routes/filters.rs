@@ -0,0 +1,42 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::filters::{create_keyword_filter_handler, delete_keyword_filter_handler};
+
+use crate::utils::{TEST_USER_ID, bearer, build_dependencies};
+
+const FILTER_ID: &str = "8b7a6c5d-4e3f-4a2b-9c1d-0e9f8a7b6c5d";
+
+#[actix_web::test]
+async fn should_be_able_to_create_and_delete_a_keyword_filter() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_keyword_filter_handler)
+            .service(delete_keyword_filter_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/filters")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({
+            "filter_id": FILTER_ID,
+            "phrase": "season finale",
+            "contexts": ["home", "search"],
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["owner_id"], TEST_USER_ID.to_string());
+    assert_eq!(body["whole_word"], true);
+    assert_eq!(body["contexts"], serde_json::json!(["home", "search"]));
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/me/filters/{}", FILTER_ID))
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
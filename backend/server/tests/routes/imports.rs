@@ -0,0 +1,116 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::imports::{get_import_handler, start_import_handler};
+
+use crate::utils::{bearer, build_dependencies};
+
+const BOUNDARY: &str = "hearth-boundary";
+
+fn multipart(bytes: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"archive.zip\"\r\n\
+         Content-Type: application/zip\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+#[actix_web::test]
+async fn should_queue_the_import_of_the_file_field() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(start_import_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/imports?source=mastodon&dry_run=true")
+        .insert_header(bearer())
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(multipart(b"{}"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["status"], "pending");
+}
+
+#[actix_web::test]
+async fn should_require_the_file_field() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(start_import_handler),
+    )
+    .await;
+
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"other\"\r\n\r\nPK\r\n--{BOUNDARY}--\r\n"
+    );
+    let req = test::TestRequest::post()
+        .uri("/me/imports?source=twitter")
+        .insert_header(bearer())
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(body)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+}
+
+#[actix_web::test]
+async fn should_require_a_known_source() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(start_import_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/imports?source=myspace")
+        .insert_header(bearer())
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(multipart(b"{}"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn should_show_an_import_with_a_session_only() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(get_import_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/me/imports/5f0c3a1e-8d2b-4c6a-9e7f-1a2b3c4d5e6f")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::get()
+        .uri("/me/imports/5f0c3a1e-8d2b-4c6a-9e7f-1a2b3c4d5e6f")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
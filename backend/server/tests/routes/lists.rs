@@ -0,0 +1,88 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::lists::{
+    add_list_member_handler, create_list_handler, list_timeline_handler, subscribe_list_handler,
+    update_list_handler,
+};
+
+use crate::utils::{TEST_USER_ID, bearer, build_dependencies};
+
+const LIST_ID: &str = "7d1c2b3a-4e5f-4a6b-8c7d-9e0f1a2b3c4d";
+const MEMBER_ID: &str = "3c2b1a0f-9e8d-4c7b-a6f5-e4d3c2b1a098";
+
+#[actix_web::test]
+async fn should_be_able_to_manage_a_list() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_list_handler)
+            .service(update_list_handler)
+            .service(add_list_member_handler)
+            .service(subscribe_list_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/lists")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "list_id": LIST_ID, "name": "Rustaceans" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["list_id"], LIST_ID);
+    assert_eq!(body["owner_id"], TEST_USER_ID.to_string());
+    assert_eq!(body["is_private"], false);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/lists/{}", LIST_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "name": "Crabs", "is_private": true }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["name"], "Crabs");
+    assert_eq!(body["is_private"], true);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/lists/{}/members/{}", LIST_ID, MEMBER_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "notify": true }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/lists/{}/subscription", LIST_ID))
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+#[actix_web::test]
+async fn should_read_list_timelines_only_with_a_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(list_timeline_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/lists/{}/timeline?limit=10", LIST_ID))
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/lists/{}/timeline", LIST_ID))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,34 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::auth::login_email_handler;
+
+use crate::utils::{TEST_TOKEN, build_dependencies};
+
+#[actix_web::test]
+async fn should_be_able_to_login_with_email() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(login_email_handler),
+    )
+    .await;
+
+    let payload = r#"
+                {
+                    "email": "john.smith@gmail.com",
+                    "password": "qwerty123"
+                }
+            "#;
+    let req = test::TestRequest::post()
+        .uri("/login/email")
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(payload)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["status"], "session");
+    assert_eq!(body["token"], TEST_TOKEN);
+}
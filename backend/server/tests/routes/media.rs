@@ -0,0 +1,142 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::media::{
+    get_media_handler, media_file_handler, set_avatar_handler, upload_media_handler,
+};
+
+use crate::utils::{TEST_USER_ID, bearer, build_dependencies};
+
+const BOUNDARY: &str = "hearth-boundary";
+
+fn multipart(field: &str, bytes: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"cat.png\"\r\n\
+         Content-Type: image/png\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+#[actix_web::test]
+async fn should_upload_media_from_the_file_field() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(upload_media_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .insert_header(bearer())
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(multipart("file", b"12345"))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["owner_id"], TEST_USER_ID.to_string());
+    assert_eq!(body["status"], "pending");
+}
+
+#[actix_web::test]
+async fn should_report_the_processing_status_of_media() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(get_media_handler)
+            .service(media_file_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/media/0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11")
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["variants"][0]["size_bytes"], 5);
+}
+
+#[actix_web::test]
+async fn should_reject_uploads_without_a_file_field() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(upload_media_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/media")
+        .insert_header(bearer())
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(multipart("avatar", b"12345"))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+}
+
+#[actix_web::test]
+async fn should_serve_media_files_with_safe_headers() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(media_file_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/media/0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11/original")
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "image/png");
+    assert_eq!(resp.headers().get("x-content-type-options").unwrap(), "nosniff");
+}
+
+#[actix_web::test]
+async fn should_set_avatar_when_logged_in() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(set_avatar_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/me/avatar")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "media_id": "0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11" }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::put()
+        .uri("/me/avatar")
+        .set_json(serde_json::json!({ "media_id": null }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
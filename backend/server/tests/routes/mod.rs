@@ -1 +1,23 @@
+mod blocks;
+mod bookmarks;
+mod conversations;
+mod data_exports;
+mod device_keys;
+mod filters;
+mod imports;
+mod lists;
+mod login_with_email;
+mod media;
+mod moderation;
+mod notifications;
+mod oauth;
+mod oidc;
+mod passkeys;
+mod personal_access_tokens;
+mod polls;
+mod posts;
+mod rate_limit;
 mod signup_with_email;
+mod trends;
+mod two_factor;
+mod users;
@@ -0,0 +1,89 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::moderation::{
+    create_report_handler, list_reports_handler, resolve_report_handler, set_user_role_handler,
+    suspend_user_handler,
+};
+
+use crate::utils::{TEST_USER_ID, bearer, build_dependencies};
+
+const REPORT_ID: &str = "3c2b1a09-8f7e-4d6c-9b5a-4e3d2c1b0a9f";
+const POST_ID: &str = "7a6b5c4d-3e2f-4a1b-8c0d-9e8f7a6b5c4d";
+const USER_ID: &str = "2d3e4f5a-6b7c-4d8e-9f0a-1b2c3d4e5f6a";
+
+#[actix_web::test]
+async fn should_be_able_to_report_a_post() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_report_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/reports")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({
+            "report_id": REPORT_ID,
+            "target_type": "post",
+            "target_id": POST_ID,
+            "reason": "hate_speech",
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["reporter_id"], TEST_USER_ID.to_string());
+    assert_eq!(body["reason"], "hate_speech");
+    assert_eq!(body["state"], "open");
+}
+
+#[actix_web::test]
+async fn should_be_able_to_work_the_moderation_queue() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(list_reports_handler)
+            .service(resolve_report_handler)
+            .service(set_user_role_handler)
+            .service(suspend_user_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/moderation/reports?state=dismissed&limit=10")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/moderation/reports/{}/resolve", REPORT_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "action": "hide_post", "note": "slur" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["state"], "actioned");
+    assert_eq!(body["resolved_by"], TEST_USER_ID.to_string());
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/admin/users/{}/role", USER_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "role": "moderator" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/admin/users/{}/suspension", USER_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "until": "2030-01-01T00:00:00Z", "note": "spam wave" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,23 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::notifications::list_notifications_handler;
+
+use crate::utils::{bearer, build_dependencies};
+
+#[actix_web::test]
+async fn should_be_able_to_list_notifications() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(list_notifications_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/notifications")
+        .insert_header(bearer())
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
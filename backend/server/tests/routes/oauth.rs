@@ -0,0 +1,113 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::{
+    notifications::list_notifications_handler,
+    oauth::{oauth_revoke_handler, oauth_token_handler, register_oauth_app_handler},
+    posts::create_post_handler,
+};
+
+use crate::utils::{TEST_CLIENT_ID, TEST_OAUTH_TOKEN, bearer, build_dependencies};
+
+fn oauth_bearer() -> (&'static str, String) {
+    ("Authorization", format!("Bearer {}", TEST_OAUTH_TOKEN))
+}
+
+#[actix_web::test]
+async fn should_register_oauth_apps_with_a_session_only() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(register_oauth_app_handler),
+    )
+    .await;
+    let payload = serde_json::json!({
+        "name": "Client",
+        "redirect_uris": ["https://app.example.com/callback"],
+        "scopes": ["read", "write"],
+    });
+
+    let req = test::TestRequest::post()
+        .uri("/oauth/apps")
+        .insert_header(bearer())
+        .set_json(&payload)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let req = test::TestRequest::post()
+        .uri("/oauth/apps")
+        .insert_header(oauth_bearer())
+        .set_json(&payload)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["message"], "SESSION_REQUIRED");
+}
+
+#[actix_web::test]
+async fn should_enforce_the_scopes_of_oauth_tokens() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(list_notifications_handler)
+            .service(create_post_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/notifications")
+        .insert_header(oauth_bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::post()
+        .uri("/posts")
+        .insert_header(oauth_bearer())
+        .set_json(serde_json::json!({
+            "post_id": "0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11",
+            "content": "Hello",
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["message"], "INSUFFICIENT_SCOPE");
+}
+
+#[actix_web::test]
+async fn should_accept_form_encoded_token_requests() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(oauth_token_handler)
+            .service(oauth_revoke_handler),
+    )
+    .await;
+    let client_id = TEST_CLIENT_ID.to_string();
+
+    let req = test::TestRequest::post()
+        .uri("/oauth/token")
+        .set_form([
+            ("grant_type", "authorization_code"),
+            ("client_id", client_id.as_str()),
+            ("code", "code"),
+            ("redirect_uri", "https://app.example.com/callback"),
+            ("code_verifier", "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+        ])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::post()
+        .uri("/oauth/revoke")
+        .set_form([("token", TEST_OAUTH_TOKEN), ("client_id", client_id.as_str())])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
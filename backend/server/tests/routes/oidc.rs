@@ -0,0 +1,80 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::oidc::{oidc_authorize_handler, oidc_callback_handler, oidc_signup_handler};
+
+use crate::utils::{TEST_TOKEN, build_dependencies};
+
+#[actix_web::test]
+async fn should_be_able_to_start_an_oidc_login() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(oidc_authorize_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/oidc/example/authorize")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert!(body["authorization_url"].is_string());
+    assert!(body["state"].is_string());
+}
+
+#[actix_web::test]
+async fn should_ask_for_a_username_after_a_first_oidc_login() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(oidc_callback_handler)
+            .service(oidc_signup_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/oidc/example/callback")
+        .set_json(serde_json::json!({ "code": "code", "state": "state" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["status"], "username_required");
+    assert_eq!(body["signup_token"], TEST_TOKEN);
+
+    let req = test::TestRequest::post()
+        .uri("/oidc/signup")
+        .set_json(serde_json::json!({
+            "signup_token": TEST_TOKEN,
+            "username": "jane",
+            "birthday": "1994-03-08",
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["token"], TEST_TOKEN);
+}
+
+#[actix_web::test]
+async fn should_not_find_an_unknown_oidc_provider() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(oidc_callback_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/oidc/unknown/callback")
+        .set_json(serde_json::json!({ "code": "code", "state": "state" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
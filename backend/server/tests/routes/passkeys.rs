@@ -0,0 +1,75 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::passkeys::{
+    login_passkey_handler, passkey_login_options_handler, passkey_registration_options_handler,
+    register_passkey_handler,
+};
+
+use crate::utils::{TEST_TOKEN, bearer, build_dependencies};
+
+#[actix_web::test]
+async fn should_be_able_to_register_a_passkey() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(passkey_registration_options_handler)
+            .service(register_passkey_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/passkeys/options")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert!(body["pubKeyCredParams"].is_array());
+
+    let req = test::TestRequest::post()
+        .uri("/me/passkeys")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({
+            "name": "Laptop",
+            "credential_id": "AAEC",
+            "client_data_json": "e30",
+            "attestation_object": "oA",
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+#[actix_web::test]
+async fn should_be_able_to_login_with_a_passkey() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(passkey_login_options_handler)
+            .service(login_passkey_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/login/passkey/options")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::post()
+        .uri("/login/passkey")
+        .set_json(serde_json::json!({
+            "credential_id": "AAEC",
+            "client_data_json": "e30",
+            "authenticator_data": "AA",
+            "signature": "AA",
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["token"], TEST_TOKEN);
+}
@@ -0,0 +1,68 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::personal_access_tokens::{
+    create_personal_access_token_handler, list_personal_access_tokens_handler,
+    revoke_personal_access_token_handler,
+};
+
+use crate::utils::{TEST_PERSONAL_ACCESS_TOKEN, bearer, build_dependencies};
+
+#[actix_web::test]
+async fn should_manage_personal_access_tokens() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_personal_access_token_handler)
+            .service(list_personal_access_tokens_handler)
+            .service(revoke_personal_access_token_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/tokens")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({
+            "name": "Backup script",
+            "scopes": ["read"],
+            "expires_in_days": 7,
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let req = test::TestRequest::get()
+        .uri("/me/tokens")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::delete()
+        .uri("/me/tokens/0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+#[actix_web::test]
+async fn should_not_mint_tokens_with_a_token() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_personal_access_token_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/tokens")
+        .insert_header(("Authorization", format!("Bearer {}", TEST_PERSONAL_ACCESS_TOKEN)))
+        .set_json(serde_json::json!({ "name": "Escalation", "scopes": ["admin"] }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["message"], "SESSION_REQUIRED");
+}
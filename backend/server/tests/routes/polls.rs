@@ -0,0 +1,71 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::polls::{get_poll_handler, vote_poll_handler};
+
+use crate::utils::{bearer, build_dependencies};
+
+const POST_ID: &str = "0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11";
+
+#[actix_web::test]
+async fn should_be_able_to_vote_on_a_poll() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(vote_poll_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{}/poll/votes", POST_ID))
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "choices": [1] }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["post_id"], POST_ID);
+    assert_eq!(body["own_votes"], serde_json::json!([1]));
+}
+
+#[actix_web::test]
+async fn should_not_vote_without_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(vote_poll_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/posts/{}/poll/votes", POST_ID))
+        .set_json(serde_json::json!({ "choices": [1] }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn should_be_able_to_read_a_poll() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(get_poll_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/posts/{}/poll", POST_ID))
+        .insert_header(bearer())
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert!(body["voters_count"].is_null());
+}
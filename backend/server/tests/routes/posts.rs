@@ -0,0 +1,75 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::posts::{create_post_handler, hashtag_timeline_handler};
+
+use crate::utils::{TEST_USER_ID, bearer, build_dependencies};
+
+const PAYLOAD: &str = r#"
+                {
+                    "post_id": "0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11",
+                    "content": "Hello #Hearth"
+                }
+            "#;
+
+#[actix_web::test]
+async fn should_be_able_to_create_a_post() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_post_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/posts")
+        .insert_header(bearer())
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(PAYLOAD)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["author_id"], TEST_USER_ID.to_string());
+    assert_eq!(body["sensitive"], false);
+    assert_eq!(body["age_gated"], false);
+}
+
+#[actix_web::test]
+async fn should_not_create_a_post_without_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(create_post_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/posts")
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(PAYLOAD)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn should_be_able_to_read_a_hashtag_timeline() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(hashtag_timeline_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/tags/hearth?limit=10")
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
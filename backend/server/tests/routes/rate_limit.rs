@@ -0,0 +1,109 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use actix_web::{App, http::StatusCode, test, web};
+use domain::{dtos::auth::AuthenticatedUserDTO, features::feature::Feature};
+use errors::HearthError;
+use server::routes::{auth::login_email_handler, posts::create_post_handler};
+
+use crate::utils::{THROTTLED_IP, bearer, build_dependencies};
+
+/// Counts the lookups before handing the token to the usual fake.
+struct CountingAuthenticate {
+    inner: Box<dyn Feature<String, AuthenticatedUserDTO>>,
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl Feature<String, AuthenticatedUserDTO> for CountingAuthenticate {
+    async fn execute(&self, token: String) -> Result<AuthenticatedUserDTO, HearthError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.execute(token).await
+    }
+}
+
+fn login_request(ip: &str) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri("/login/email")
+        .peer_addr(format!("{}:40000", ip).parse().unwrap())
+        .set_json(serde_json::json!({
+            "email": "john.smith@gmail.com",
+            "password": "qwerty123",
+        }))
+}
+
+#[actix_web::test]
+async fn should_send_rate_limit_headers() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(login_email_handler),
+    )
+    .await;
+
+    let resp = test::call_service(&app, login_request("198.51.100.4").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("RateLimit-Limit").unwrap(), "5");
+    assert_eq!(resp.headers().get("RateLimit-Remaining").unwrap(), "4");
+    assert_eq!(resp.headers().get("RateLimit-Reset").unwrap(), "60");
+}
+
+#[actix_web::test]
+async fn should_reject_callers_with_an_empty_bucket() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(login_email_handler),
+    )
+    .await;
+
+    let resp = test::call_service(&app, login_request(THROTTLED_IP).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers().get("Retry-After").unwrap(), "12");
+    assert_eq!(resp.headers().get("RateLimit-Remaining").unwrap(), "0");
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "TooManyRequests");
+    assert_eq!(body["message"][0], "RATE_LIMITED");
+}
+
+#[actix_web::test]
+async fn should_authenticate_the_caller_of_a_throttled_route_once() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut dependencies = build_dependencies();
+    dependencies.authenticate = Box::new(CountingAuthenticate {
+        inner: dependencies.authenticate,
+        calls: calls.clone(),
+    });
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dependencies))
+            .service(create_post_handler),
+    )
+    .await;
+
+    let post = || {
+        test::TestRequest::post()
+            .uri("/posts")
+            .set_json(serde_json::json!({
+                "post_id": "0b5e6a1c-2f1a-4f57-9a53-8d1d0f3c2b11",
+                "content": "Hello #Hearth",
+            }))
+    };
+
+    let resp = test::call_service(&app, post().insert_header(bearer()).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // A rejected token is rejected by the route without a second lookup.
+    let req = post()
+        .insert_header(("Authorization", "Bearer expired-token"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
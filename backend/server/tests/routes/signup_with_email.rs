@@ -1,5 +1,5 @@
 use actix_web::{App, http::StatusCode, test, web};
-use server::routes::signup_email_handler;
+use server::routes::{signup_challenge_handler, signup_email_handler};
 
 use crate::utils::build_dependencies;
 
@@ -32,3 +32,26 @@ async fn should_be_able_to_signup_with_email() {
     let resp = test::call_service(&app, req).await;
     assert_eq!(resp.status(), StatusCode::CREATED);
 }
+
+#[actix_web::test]
+async fn should_hand_out_a_signup_challenge() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(signup_challenge_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup/challenge")
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    for field in ["challenge_id", "prefix", "difficulty", "expires_at"] {
+        assert!(body.get(field).is_some(), "missing {}", field);
+    }
+}
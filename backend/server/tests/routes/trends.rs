@@ -0,0 +1,20 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::trends::trends_handler;
+
+use crate::utils::build_dependencies;
+
+#[actix_web::test]
+async fn should_be_able_to_get_trends() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(trends_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/trends").to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
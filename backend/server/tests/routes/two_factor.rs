@@ -0,0 +1,82 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::{
+    auth::login_two_factor_handler,
+    two_factor::{confirm_two_factor_handler, disable_two_factor_handler, enroll_two_factor_handler},
+};
+
+use crate::utils::{TEST_TOKEN, bearer, build_dependencies};
+
+#[actix_web::test]
+async fn should_be_able_to_enroll_confirm_and_disable_two_factor() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(enroll_two_factor_handler)
+            .service(confirm_two_factor_handler)
+            .service(disable_two_factor_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/2fa/enroll")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let req = test::TestRequest::post()
+        .uri("/me/2fa/confirm")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "code": "123456" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert!(body["codes"].is_array());
+
+    let req = test::TestRequest::post()
+        .uri("/me/2fa/disable")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "password": "qwerty123" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+#[actix_web::test]
+async fn should_not_enroll_without_a_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(enroll_two_factor_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post().uri("/me/2fa/enroll").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn should_open_a_session_with_the_second_factor() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(login_two_factor_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/login/2fa")
+        .set_json(serde_json::json!({ "challenge_token": "challenge", "code": "123456" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["token"], TEST_TOKEN);
+}
@@ -0,0 +1,120 @@
+use actix_web::{App, http::StatusCode, test, web};
+use server::routes::users::{
+    delete_account_handler, get_profile_handler, send_email_verification_handler,
+    verify_email_handler,
+};
+
+use crate::utils::{TEST_PERSONAL_ACCESS_TOKEN, THROTTLED_IP, bearer, build_dependencies};
+
+const USER_ID: &str = "2d3e4f5a-6b7c-4d8e-9f0a-1b2c3d4e5f6a";
+
+#[actix_web::test]
+async fn should_be_able_to_view_a_profile_without_a_session() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(get_profile_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/users/{}", USER_ID))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["suspended"], false);
+}
+
+#[actix_web::test]
+async fn should_delete_the_account_with_a_session_only() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(delete_account_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::delete()
+        .uri("/me")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "password": "qwerty123" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::delete()
+        .uri("/me")
+        .insert_header(("Authorization", format!("Bearer {}", TEST_PERSONAL_ACCESS_TOKEN)))
+        .set_json(serde_json::json!({ "password": "qwerty123" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn should_verify_the_email_of_the_logged_in_user() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(send_email_verification_handler)
+            .service(verify_email_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/me/email/verification")
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::post()
+        .uri("/me/email/verify")
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "code": "ABC123" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let req = test::TestRequest::post()
+        .uri("/me/email/verify")
+        .set_json(serde_json::json!({ "code": "ABC123" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn should_throttle_email_verification_codes() {
+    let dependencies = web::Data::new(build_dependencies());
+    let app = test::init_service(
+        App::new()
+            .app_data(dependencies)
+            .service(send_email_verification_handler)
+            .service(verify_email_handler),
+    )
+    .await;
+    let peer_addr = format!("{}:40000", THROTTLED_IP).parse().unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/me/email/verification")
+        .peer_addr(peer_addr)
+        .insert_header(bearer())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let req = test::TestRequest::post()
+        .uri("/me/email/verify")
+        .peer_addr(peer_addr)
+        .insert_header(bearer())
+        .set_json(serde_json::json!({ "code": "ABC123" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+}
@@ -1,11 +1,67 @@
-use domain::dtos::signup::{EmailVerificationDTO, SignupEmailDTO};
-use server::bootstrap::Dependencies;
+use chrono::Utc;
+use domain::dtos::{
+    auth::{AuthenticatedUserDTO, LoginEmailDTO, LoginResultDTO, SessionDTO},
+    media::{
+        MediaDTO, MediaFileDTO, MediaFileRequestDTO, MediaStatus, MediaVariantDTO,
+        ORIGINAL_VARIANT, UploadMediaDTO,
+    },
+    bookmark::{BookmarkCollectionDTO, BookmarkDTO, BookmarkPostDTO, CreateBookmarkCollectionDTO},
+    conversation::{
+        ConversationDTO, DmSettingsDTO, EncryptedMessageDTO, MessageDTO, SendEncryptedMessageDTO,
+        SendMessageDTO, StartConversationDTO, UpdateDmSettingsDTO,
+    },
+    filter::{CreateKeywordFilterDTO, KeywordFilterDTO},
+    list::{CreateListDTO, ListDTO, UpdateListDTO},
+    moderation::{
+        CreateReportDTO, ModerationAction, ReportDTO, ReportReason, ReportState, ReportTargetType,
+        ResolveReportDTO,
+    },
+    oauth::Scope,
+    oidc::{CompleteOidcSignupDTO, OidcCallbackDTO},
+    passkey::LoginPasskeyDTO,
+    poll::{GetPollDTO, PollDTO, PollOptionDTO, VotePollDTO},
+    post::{CreatePostDTO, PostDTO},
+    rate_limit::{RateLimitDTO, RateLimitRequestDTO},
+    signup::{EmailVerificationDTO, SignupEmailDTO},
+    two_factor::LoginTwoFactorDTO,
+};
+use errors::HearthError;
+use server::{bootstrap::Dependencies, realtime::ConnectionHub};
 use uuid::Uuid;
 
+pub const TEST_TOKEN: &str = "test-token";
+pub const TEST_USER_ID: Uuid = Uuid::from_u128(0x47578122_3977_438a_8e2c_1f1f4fe8b7ef);
+/// OAuth access token of `TEST_USER_ID` only granted the `read` scope.
+pub const TEST_OAUTH_TOKEN: &str = "test-oauth-token";
+pub const TEST_CLIENT_ID: Uuid = Uuid::from_u128(0x0b9d3c51_8a7e_4f06_9d2a_5c3e7f1a2b4d);
+/// Personal access token of `TEST_USER_ID` granted `read` and `write`.
+pub const TEST_PERSONAL_ACCESS_TOKEN: &str = "hpat_test-token";
+pub const TEST_PERSONAL_ACCESS_TOKEN_ID: Uuid =
+    Uuid::from_u128(0x5f2c8e41_6b3d_4a97_8c1e_2d4f6a8b0c13);
+/// Peer address whose rate limit buckets are always empty.
+pub const THROTTLED_IP: &str = "203.0.113.9";
+
+/// Succeeds without side effects, answering with the default output.
+pub struct FakeFeature;
+
+#[async_trait::async_trait]
+impl<Input, Output> domain::features::feature::Feature<Input, Output> for FakeFeature
+where
+    Input: Send + 'static,
+    Output: Default + Send + 'static,
+{
+    async fn execute(&self, _input: Input) -> Result<Output, HearthError> {
+        Ok(Output::default())
+    }
+}
+
+pub fn bearer() -> (&'static str, String) {
+    ("Authorization", format!("Bearer {}", TEST_TOKEN))
+}
+
 pub fn build_dependencies() -> Dependencies {
     use async_trait::async_trait;
     use domain::features::feature::Feature;
-    use errors::HearthError;
 
     struct FakeSignupWithEmail;
 
@@ -34,7 +90,508 @@ pub fn build_dependencies() -> Dependencies {
         }
     }
 
+    struct FakeLoginWithEmail;
+
+    #[async_trait]
+    impl Feature<LoginEmailDTO, LoginResultDTO> for FakeLoginWithEmail {
+        async fn execute(&self, _dto: LoginEmailDTO) -> Result<LoginResultDTO, HearthError> {
+            Ok(LoginResultDTO::Session(SessionDTO {
+                token: TEST_TOKEN.into(),
+                user_id: TEST_USER_ID,
+            }))
+        }
+    }
+
+    struct FakeLoginWithTwoFactor;
+
+    #[async_trait]
+    impl Feature<LoginTwoFactorDTO, SessionDTO> for FakeLoginWithTwoFactor {
+        async fn execute(&self, _dto: LoginTwoFactorDTO) -> Result<SessionDTO, HearthError> {
+            Ok(SessionDTO {
+                token: TEST_TOKEN.into(),
+                user_id: TEST_USER_ID,
+            })
+        }
+    }
+
+    struct FakeLoginWithPasskey;
+
+    #[async_trait]
+    impl Feature<LoginPasskeyDTO, SessionDTO> for FakeLoginWithPasskey {
+        async fn execute(&self, _dto: LoginPasskeyDTO) -> Result<SessionDTO, HearthError> {
+            Ok(SessionDTO {
+                token: TEST_TOKEN.into(),
+                user_id: TEST_USER_ID,
+            })
+        }
+    }
+
+    struct FakeFinishOidcLogin;
+
+    #[async_trait]
+    impl Feature<OidcCallbackDTO, LoginResultDTO> for FakeFinishOidcLogin {
+        async fn execute(&self, dto: OidcCallbackDTO) -> Result<LoginResultDTO, HearthError> {
+            if dto.provider != "example" {
+                return Err(HearthError::not_found("OIDC_PROVIDER_NOT_FOUND".into()));
+            }
+            Ok(LoginResultDTO::UsernameRequired {
+                signup_token: TEST_TOKEN.into(),
+                email: "jane@example.com".into(),
+            })
+        }
+    }
+
+    struct FakeCompleteOidcSignup;
+
+    #[async_trait]
+    impl Feature<CompleteOidcSignupDTO, SessionDTO> for FakeCompleteOidcSignup {
+        async fn execute(&self, _dto: CompleteOidcSignupDTO) -> Result<SessionDTO, HearthError> {
+            Ok(SessionDTO {
+                token: TEST_TOKEN.into(),
+                user_id: TEST_USER_ID,
+            })
+        }
+    }
+
+    struct FakeAuthenticate;
+
+    #[async_trait]
+    impl Feature<String, AuthenticatedUserDTO> for FakeAuthenticate {
+        async fn execute(&self, token: String) -> Result<AuthenticatedUserDTO, HearthError> {
+            match token.as_str() {
+                TEST_TOKEN => Ok(AuthenticatedUserDTO {
+                    user_id: TEST_USER_ID,
+                    client_id: None,
+                    personal_access_token_id: None,
+                    scopes: Scope::ALL.to_vec(),
+                }),
+                TEST_OAUTH_TOKEN => Ok(AuthenticatedUserDTO {
+                    user_id: TEST_USER_ID,
+                    client_id: Some(TEST_CLIENT_ID),
+                    personal_access_token_id: None,
+                    scopes: vec![Scope::Read],
+                }),
+                TEST_PERSONAL_ACCESS_TOKEN => Ok(AuthenticatedUserDTO {
+                    user_id: TEST_USER_ID,
+                    client_id: None,
+                    personal_access_token_id: Some(TEST_PERSONAL_ACCESS_TOKEN_ID),
+                    scopes: vec![Scope::Read, Scope::Write],
+                }),
+                _ => Err(HearthError::Unauthorized("INVALID_SESSION".into())),
+            }
+        }
+    }
+
+    struct FakeCheckRateLimit;
+
+    #[async_trait]
+    impl Feature<RateLimitRequestDTO, RateLimitDTO> for FakeCheckRateLimit {
+        async fn execute(&self, dto: RateLimitRequestDTO) -> Result<RateLimitDTO, HearthError> {
+            let throttled = dto.ip.as_deref() == Some(THROTTLED_IP);
+            Ok(RateLimitDTO {
+                limit: 5,
+                remaining: if throttled { 0 } else { 4 },
+                reset_seconds: 60,
+                retry_after_seconds: throttled.then_some(12),
+            })
+        }
+    }
+
+    struct FakeCreatePost;
+
+    #[async_trait]
+    impl Feature<CreatePostDTO, PostDTO> for FakeCreatePost {
+        async fn execute(&self, dto: CreatePostDTO) -> Result<PostDTO, HearthError> {
+            Ok(PostDTO {
+                post_id: dto.post_id,
+                author_id: dto.author_id,
+                content: dto.content,
+                hashtags: vec![],
+                mentions: vec![],
+                media: vec![],
+                poll: None,
+                link_preview: None,
+                filtered: vec![],
+                sensitive: dto.sensitive,
+                age_gated: false,
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeUploadMedia;
+
+    #[async_trait]
+    impl Feature<UploadMediaDTO, MediaDTO> for FakeUploadMedia {
+        async fn execute(&self, dto: UploadMediaDTO) -> Result<MediaDTO, HearthError> {
+            Ok(MediaDTO {
+                media_id: dto.media_id,
+                owner_id: dto.owner_id,
+                mime_type: "image/png".into(),
+                status: MediaStatus::Pending,
+                blurhash: String::new(),
+                variants: vec![],
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeGetMedia;
+
+    #[async_trait]
+    impl Feature<Uuid, MediaDTO> for FakeGetMedia {
+        async fn execute(&self, media_id: Uuid) -> Result<MediaDTO, HearthError> {
+            Ok(MediaDTO {
+                media_id,
+                owner_id: TEST_USER_ID,
+                mime_type: "image/png".into(),
+                status: MediaStatus::Ready,
+                blurhash: "LEHV6nWB2yk8pyo0adR*.7kCMdnj".into(),
+                variants: vec![MediaVariantDTO {
+                    name: ORIGINAL_VARIANT.into(),
+                    mime_type: "image/png".into(),
+                    width: 1,
+                    height: 1,
+                    size_bytes: 5,
+                }],
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeGetMediaFile;
+
+    #[async_trait]
+    impl Feature<MediaFileRequestDTO, MediaFileDTO> for FakeGetMediaFile {
+        async fn execute(&self, _dto: MediaFileRequestDTO) -> Result<MediaFileDTO, HearthError> {
+            Ok(MediaFileDTO {
+                mime_type: "image/png".into(),
+                bytes: b"png".to_vec(),
+            })
+        }
+    }
+
+    fn poll(post_id: Uuid, own_votes: Vec<u8>) -> PollDTO {
+        PollDTO {
+            post_id,
+            options: vec![
+                PollOptionDTO {
+                    title: "yes".into(),
+                    votes_count: None,
+                },
+                PollOptionDTO {
+                    title: "no".into(),
+                    votes_count: None,
+                },
+            ],
+            multiple_choice: false,
+            closes_at: Utc::now(),
+            voters_count: None,
+            own_votes,
+        }
+    }
+
+    struct FakeGetPoll;
+
+    #[async_trait]
+    impl Feature<GetPollDTO, PollDTO> for FakeGetPoll {
+        async fn execute(&self, dto: GetPollDTO) -> Result<PollDTO, HearthError> {
+            Ok(poll(dto.post_id, vec![]))
+        }
+    }
+
+    struct FakeVotePoll;
+
+    #[async_trait]
+    impl Feature<VotePollDTO, PollDTO> for FakeVotePoll {
+        async fn execute(&self, dto: VotePollDTO) -> Result<PollDTO, HearthError> {
+            Ok(poll(dto.post_id, dto.choices))
+        }
+    }
+
+    struct FakeCreateBookmarkCollection;
+
+    #[async_trait]
+    impl Feature<CreateBookmarkCollectionDTO, BookmarkCollectionDTO> for FakeCreateBookmarkCollection {
+        async fn execute(
+            &self,
+            dto: CreateBookmarkCollectionDTO,
+        ) -> Result<BookmarkCollectionDTO, HearthError> {
+            Ok(BookmarkCollectionDTO {
+                collection_id: dto.collection_id,
+                owner_id: dto.owner_id,
+                name: dto.name,
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeBookmarkPost;
+
+    #[async_trait]
+    impl Feature<BookmarkPostDTO, BookmarkDTO> for FakeBookmarkPost {
+        async fn execute(&self, dto: BookmarkPostDTO) -> Result<BookmarkDTO, HearthError> {
+            Ok(BookmarkDTO {
+                post_id: dto.post_id,
+                collection_id: dto.collection_id,
+                position: 0,
+                created_at: Utc::now(),
+                post: None,
+            })
+        }
+    }
+
+    struct FakeCreateList;
+
+    #[async_trait]
+    impl Feature<CreateListDTO, ListDTO> for FakeCreateList {
+        async fn execute(&self, dto: CreateListDTO) -> Result<ListDTO, HearthError> {
+            Ok(ListDTO {
+                list_id: dto.list_id,
+                owner_id: dto.owner_id,
+                name: dto.name,
+                is_private: dto.is_private,
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeUpdateList;
+
+    #[async_trait]
+    impl Feature<UpdateListDTO, ListDTO> for FakeUpdateList {
+        async fn execute(&self, dto: UpdateListDTO) -> Result<ListDTO, HearthError> {
+            Ok(ListDTO {
+                list_id: dto.list_id,
+                owner_id: dto.owner_id,
+                name: dto.name,
+                is_private: dto.is_private,
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeStartConversation;
+
+    #[async_trait]
+    impl Feature<StartConversationDTO, ConversationDTO> for FakeStartConversation {
+        async fn execute(&self, dto: StartConversationDTO) -> Result<ConversationDTO, HearthError> {
+            Ok(ConversationDTO {
+                conversation_id: dto.conversation_id,
+                participant_ids: std::iter::once(dto.creator_id)
+                    .chain(dto.participant_ids)
+                    .collect(),
+                read_positions: vec![],
+                created_at: Utc::now(),
+                last_message_at: None,
+            })
+        }
+    }
+
+    struct FakeSendMessage;
+
+    #[async_trait]
+    impl Feature<SendMessageDTO, MessageDTO> for FakeSendMessage {
+        async fn execute(&self, dto: SendMessageDTO) -> Result<MessageDTO, HearthError> {
+            Ok(MessageDTO {
+                message_id: dto.message_id,
+                conversation_id: dto.conversation_id,
+                sender_id: dto.sender_id,
+                content: dto.content,
+                media_ids: dto.media_ids,
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeUpdateDmSettings;
+
+    #[async_trait]
+    impl Feature<UpdateDmSettingsDTO, DmSettingsDTO> for FakeUpdateDmSettings {
+        async fn execute(&self, dto: UpdateDmSettingsDTO) -> Result<DmSettingsDTO, HearthError> {
+            Ok(DmSettingsDTO {
+                dm_permission: dto.dm_permission,
+            })
+        }
+    }
+
+    struct FakeSendEncryptedMessage;
+
+    #[async_trait]
+    impl Feature<SendEncryptedMessageDTO, EncryptedMessageDTO> for FakeSendEncryptedMessage {
+        async fn execute(
+            &self,
+            dto: SendEncryptedMessageDTO,
+        ) -> Result<EncryptedMessageDTO, HearthError> {
+            Ok(EncryptedMessageDTO {
+                message_id: dto.message_id,
+                conversation_id: dto.conversation_id,
+                sender_id: dto.sender_id,
+                sender_device_id: dto.sender_device_id,
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    struct FakeCreateReport;
+
+    #[async_trait]
+    impl Feature<CreateReportDTO, ReportDTO> for FakeCreateReport {
+        async fn execute(&self, dto: CreateReportDTO) -> Result<ReportDTO, HearthError> {
+            Ok(ReportDTO {
+                report_id: dto.report_id,
+                reporter_id: dto.reporter_id,
+                target_type: dto.target_type,
+                target_id: dto.target_id,
+                target_user_id: dto.target_id,
+                reason: dto.reason,
+                details: dto.details,
+                state: ReportState::Open,
+                created_at: Utc::now(),
+                resolved_by: None,
+                resolved_at: None,
+            })
+        }
+    }
+
+    struct FakeResolveReport;
+
+    #[async_trait]
+    impl Feature<ResolveReportDTO, ReportDTO> for FakeResolveReport {
+        async fn execute(&self, dto: ResolveReportDTO) -> Result<ReportDTO, HearthError> {
+            let state = match dto.action {
+                ModerationAction::Dismiss => ReportState::Dismissed,
+                _ => ReportState::Actioned,
+            };
+
+            Ok(ReportDTO {
+                report_id: dto.report_id,
+                reporter_id: Uuid::new_v4(),
+                target_type: ReportTargetType::Post,
+                target_id: Uuid::new_v4(),
+                target_user_id: Uuid::new_v4(),
+                reason: ReportReason::Spam,
+                details: String::new(),
+                state,
+                created_at: Utc::now(),
+                resolved_by: Some(dto.moderator_id),
+                resolved_at: Some(Utc::now()),
+            })
+        }
+    }
+
+    struct FakeCreateKeywordFilter;
+
+    #[async_trait]
+    impl Feature<CreateKeywordFilterDTO, KeywordFilterDTO> for FakeCreateKeywordFilter {
+        async fn execute(
+            &self,
+            dto: CreateKeywordFilterDTO,
+        ) -> Result<KeywordFilterDTO, HearthError> {
+            Ok(KeywordFilterDTO {
+                filter_id: dto.filter_id,
+                owner_id: dto.owner_id,
+                phrase: dto.phrase,
+                whole_word: dto.whole_word,
+                contexts: dto.contexts,
+                expires_at: dto.expires_at,
+                created_at: Utc::now(),
+            })
+        }
+    }
+
     let signup_with_email = Box::new(FakeSignupWithEmail);
 
-    Dependencies { signup_with_email }
+    Dependencies {
+        signup_with_email,
+        create_signup_challenge: Box::new(FakeFeature),
+        login_with_email: Box::new(FakeLoginWithEmail),
+        login_with_two_factor: Box::new(FakeLoginWithTwoFactor),
+        enroll_two_factor: Box::new(FakeFeature),
+        confirm_two_factor: Box::new(FakeFeature),
+        disable_two_factor: Box::new(FakeFeature),
+        start_passkey_registration: Box::new(FakeFeature),
+        register_passkey: Box::new(FakeFeature),
+        start_passkey_login: Box::new(FakeFeature),
+        login_with_passkey: Box::new(FakeLoginWithPasskey),
+        start_oidc_login: Box::new(FakeFeature),
+        start_oidc_link: Box::new(FakeFeature),
+        finish_oidc_login: Box::new(FakeFinishOidcLogin),
+        complete_oidc_signup: Box::new(FakeCompleteOidcSignup),
+        register_oauth_app: Box::new(FakeFeature),
+        get_oauth_consent: Box::new(FakeFeature),
+        decide_oauth_consent: Box::new(FakeFeature),
+        exchange_oauth_token: Box::new(FakeFeature),
+        introspect_oauth_token: Box::new(FakeFeature),
+        revoke_oauth_token: Box::new(FakeFeature),
+        create_personal_access_token: Box::new(FakeFeature),
+        list_personal_access_tokens: Box::new(FakeFeature),
+        revoke_personal_access_token: Box::new(FakeFeature),
+        authenticate: Box::new(FakeAuthenticate),
+        create_post: Box::new(FakeCreatePost),
+        get_hashtag_timeline: Box::new(FakeFeature),
+        get_trends: Box::new(FakeFeature),
+        block_user: Box::new(FakeFeature),
+        unblock_user: Box::new(FakeFeature),
+        list_notifications: Box::new(FakeFeature),
+        upload_media: Box::new(FakeUploadMedia),
+        get_media: Box::new(FakeGetMedia),
+        get_media_file: Box::new(FakeGetMediaFile),
+        set_avatar: Box::new(FakeFeature),
+        get_poll: Box::new(FakeGetPoll),
+        vote_poll: Box::new(FakeVotePoll),
+        create_bookmark_collection: Box::new(FakeCreateBookmarkCollection),
+        list_bookmark_collections: Box::new(FakeFeature),
+        bookmark_post: Box::new(FakeBookmarkPost),
+        remove_bookmark: Box::new(FakeFeature),
+        move_bookmark: Box::new(FakeFeature),
+        list_bookmarks: Box::new(FakeFeature),
+        create_list: Box::new(FakeCreateList),
+        update_list: Box::new(FakeUpdateList),
+        delete_list: Box::new(FakeFeature),
+        get_list_members: Box::new(FakeFeature),
+        add_list_member: Box::new(FakeFeature),
+        remove_list_member: Box::new(FakeFeature),
+        subscribe_list: Box::new(FakeFeature),
+        unsubscribe_list: Box::new(FakeFeature),
+        get_list_timeline: Box::new(FakeFeature),
+        get_user_lists: Box::new(FakeFeature),
+        get_list_subscriptions: Box::new(FakeFeature),
+        start_conversation: Box::new(FakeStartConversation),
+        list_conversations: Box::new(FakeFeature),
+        list_messages: Box::new(FakeFeature),
+        send_message: Box::new(FakeSendMessage),
+        mark_conversation_read: Box::new(FakeFeature),
+        get_dm_settings: Box::new(FakeFeature),
+        update_dm_settings: Box::new(FakeUpdateDmSettings),
+        send_encrypted_message: Box::new(FakeSendEncryptedMessage),
+        list_envelopes: Box::new(FakeFeature),
+        acknowledge_envelope: Box::new(FakeFeature),
+        upload_device_keys: Box::new(FakeFeature),
+        upload_prekeys: Box::new(FakeFeature),
+        get_prekey_count: Box::new(FakeFeature),
+        claim_prekeys: Box::new(FakeFeature),
+        create_report: Box::new(FakeCreateReport),
+        list_reports: Box::new(FakeFeature),
+        resolve_report: Box::new(FakeResolveReport),
+        list_moderation_log: Box::new(FakeFeature),
+        set_user_role: Box::new(FakeFeature),
+        suspend_user: Box::new(FakeFeature),
+        get_profile: Box::new(FakeFeature),
+        delete_account: Box::new(FakeFeature),
+        send_email_verification: Box::new(FakeFeature),
+        verify_email: Box::new(FakeFeature),
+        request_data_export: Box::new(FakeFeature),
+        download_data_export: Box::new(FakeFeature),
+        start_import: Box::new(FakeFeature),
+        get_import: Box::new(FakeFeature),
+        run_jobs: Box::new(FakeFeature),
+        schedule_periodic_job: Box::new(FakeFeature),
+        dispatch_events: Box::new(FakeFeature),
+        create_keyword_filter: Box::new(FakeCreateKeywordFilter),
+        list_keyword_filters: Box::new(FakeFeature),
+        delete_keyword_filter: Box::new(FakeFeature),
+        check_rate_limit: Box::new(FakeCheckRateLimit),
+        connection_hub: ConnectionHub::default(),
+    }
 }
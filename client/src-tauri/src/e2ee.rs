@@ -0,0 +1,198 @@
+//! End-to-end encryption of direct messages, built on `vodozemac` (Olm).
+//!
+//! The account and its sessions only live in memory for now: restarting the
+//! app means registering the device again.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use vodozemac::{
+    base64_decode, base64_encode,
+    olm::{Account, OlmMessage, Session, SessionConfig},
+    Curve25519PublicKey, Ed25519PublicKey, Ed25519Signature,
+};
+
+#[derive(Default)]
+pub struct E2ee(Mutex<Option<Device>>);
+
+struct Device {
+    account: Account,
+    /// Keyed by the peer's device id.
+    sessions: HashMap<String, Session>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SignedPrekey {
+    pub key_id: String,
+    pub public_key: String,
+    pub signature: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OneTimePrekey {
+    pub key_id: String,
+    pub public_key: String,
+}
+
+/// Body of `PUT /me/devices/{device_id}/keys`.
+#[derive(Serialize)]
+pub struct DeviceKeys {
+    pub identity_key: String,
+    pub signing_key: String,
+    pub signed_prekey: SignedPrekey,
+    pub one_time_prekeys: Vec<OneTimePrekey>,
+}
+
+/// One item of `POST /users/{user_id}/prekeys/claim`.
+#[derive(Deserialize)]
+pub struct PrekeyBundle {
+    pub identity_key: String,
+    pub signing_key: String,
+    pub signed_prekey: SignedPrekey,
+    pub one_time_prekey: Option<OneTimePrekey>,
+}
+
+/// What the server stores and relays for one recipient device.
+#[derive(Serialize, Deserialize)]
+pub struct Envelope {
+    pub message_type: usize,
+    pub ciphertext: String,
+}
+
+fn curve25519(key: &str) -> Result<Curve25519PublicKey, String> {
+    Curve25519PublicKey::from_base64(key).map_err(|e| e.to_string())
+}
+
+fn one_time_prekeys(account: &Account) -> Vec<OneTimePrekey> {
+    account
+        .one_time_keys()
+        .into_iter()
+        .map(|(key_id, key)| OneTimePrekey {
+            key_id: key_id.to_base64(),
+            public_key: key.to_base64(),
+        })
+        .collect()
+}
+
+/// Creates the device account on first use, then hands out fresh one-time
+/// prekeys. The signed prekey is rotated on every call.
+#[tauri::command]
+pub fn e2ee_generate_keys(
+    state: State<'_, E2ee>,
+    one_time_prekeys_count: usize,
+) -> Result<DeviceKeys, String> {
+    let mut device = state.0.lock().map_err(|e| e.to_string())?;
+    let device = device.get_or_insert_with(|| Device {
+        account: Account::new(),
+        sessions: HashMap::new(),
+    });
+
+    device.account.generate_one_time_keys(one_time_prekeys_count);
+    device.account.generate_fallback_key();
+
+    let (key_id, fallback_key) = device
+        .account
+        .fallback_key()
+        .into_iter()
+        .next()
+        .ok_or("No signed prekey was generated")?;
+    let public_key = fallback_key.to_base64();
+
+    let keys = DeviceKeys {
+        identity_key: device.account.curve25519_key().to_base64(),
+        signing_key: device.account.ed25519_key().to_base64(),
+        signed_prekey: SignedPrekey {
+            key_id: key_id.to_base64(),
+            signature: device.account.sign(&public_key).to_base64(),
+            public_key,
+        },
+        one_time_prekeys: one_time_prekeys(&device.account),
+    };
+
+    device.account.mark_keys_as_published();
+
+    Ok(keys)
+}
+
+/// Encrypts for one device of the recipient. The first message to a device
+/// needs its claimed prekey bundle, later ones reuse the session.
+#[tauri::command]
+pub fn e2ee_encrypt(
+    state: State<'_, E2ee>,
+    device_id: String,
+    bundle: Option<PrekeyBundle>,
+    plaintext: String,
+) -> Result<Envelope, String> {
+    let mut device = state.0.lock().map_err(|e| e.to_string())?;
+    let device = device.as_mut().ok_or("Device keys were not generated")?;
+
+    if !device.sessions.contains_key(&device_id) {
+        let bundle = bundle.ok_or("No session with this device, claim its prekeys first")?;
+
+        let signing_key =
+            Ed25519PublicKey::from_base64(&bundle.signing_key).map_err(|e| e.to_string())?;
+        let signature = Ed25519Signature::from_base64(&bundle.signed_prekey.signature)
+            .map_err(|e| e.to_string())?;
+        signing_key
+            .verify(bundle.signed_prekey.public_key.as_bytes(), &signature)
+            .map_err(|_| "The signed prekey doesn't match the device's signing key")?;
+
+        let prekey = match &bundle.one_time_prekey {
+            Some(one_time_prekey) => &one_time_prekey.public_key,
+            None => &bundle.signed_prekey.public_key,
+        };
+
+        let session = device.account.create_outbound_session(
+            SessionConfig::version_2(),
+            curve25519(&bundle.identity_key)?,
+            curve25519(prekey)?,
+        );
+        device.sessions.insert(device_id.clone(), session);
+    }
+
+    let session = device
+        .sessions
+        .get_mut(&device_id)
+        .ok_or("No session with this device")?;
+    let (message_type, ciphertext) = session.encrypt(plaintext).to_parts();
+
+    Ok(Envelope {
+        message_type,
+        ciphertext: base64_encode(ciphertext),
+    })
+}
+
+/// Decrypts an envelope addressed to this device, opening the session when
+/// it is the sender's first message.
+#[tauri::command]
+pub fn e2ee_decrypt(
+    state: State<'_, E2ee>,
+    sender_device_id: String,
+    envelope: Envelope,
+) -> Result<String, String> {
+    let mut device = state.0.lock().map_err(|e| e.to_string())?;
+    let device = device.as_mut().ok_or("Device keys were not generated")?;
+
+    let ciphertext = base64_decode(&envelope.ciphertext).map_err(|e| e.to_string())?;
+    let message =
+        OlmMessage::from_parts(envelope.message_type, &ciphertext).map_err(|e| e.to_string())?;
+
+    if let Some(session) = device.sessions.get_mut(&sender_device_id) {
+        if let Ok(plaintext) = session.decrypt(&message) {
+            return String::from_utf8(plaintext).map_err(|e| e.to_string());
+        }
+    }
+
+    let OlmMessage::PreKey(prekey_message) = &message else {
+        return Err("No session with the sender's device".into());
+    };
+
+    let result = device
+        .account
+        .create_inbound_session(prekey_message.identity_key(), prekey_message)
+        .map_err(|e| e.to_string())?;
+    device.sessions.insert(sender_device_id, result.session);
+
+    String::from_utf8(result.plaintext).map_err(|e| e.to_string())
+}
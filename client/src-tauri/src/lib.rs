@@ -1,3 +1,5 @@
+mod e2ee;
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -8,7 +10,13 @@ fn greet(name: &str) -> String {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .manage(e2ee::E2ee::default())
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            e2ee::e2ee_generate_keys,
+            e2ee::e2ee_encrypt,
+            e2ee::e2ee_decrypt
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }